@@ -21,6 +21,20 @@ pub struct Persona {
     pub visibility: VisibilityProfile,
     pub risk_modifiers: RiskModifiers,
     pub suspicion: PersonaSuspicion,
+    /// Set once exposure maxes out; a burned persona's cover is gone for good.
+    #[serde(default)]
+    pub burned: bool,
+}
+
+/// Exposure risk at which a persona burns.
+pub const EXPOSURE_MAX: u8 = 100;
+/// Exposure risk from which favors or cash can still pull a persona back.
+pub const EXPOSURE_BRINK: u8 = 85;
+
+impl Persona {
+    pub fn is_on_brink(&self) -> bool {
+        !self.burned && self.suspicion.exposure_risk >= EXPOSURE_BRINK
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -84,6 +98,16 @@ pub enum Alignment {
 }
 
 impl PersonaStack {
+    /// Starting identities for a freshly chosen alignment.
+    pub fn for_alignment(alignment: Alignment) -> Self {
+        match alignment {
+            Alignment::Neutral => neutral_persona_stack(),
+            Alignment::Hero => hero_persona_stack(),
+            Alignment::Vigilante => vigilante_persona_stack(),
+            Alignment::Villain => villain_persona_stack(),
+        }
+    }
+
     pub fn active_persona(&self) -> Option<&Persona> {
         self.personas
             .iter()
@@ -173,7 +197,7 @@ pub fn hero_persona_stack() -> PersonaStack {
             Persona {
                 persona_id: "civilian".to_string(),
                 persona_type: PersonaType::Civilian,
-                label: "Civilian".to_string(),
+                label: "Public Identity".to_string(),
                 location_rules: LocationRules {
                     allowed_tags: vec![
                         LocationTag::Public,
@@ -198,11 +222,12 @@ pub fn hero_persona_stack() -> PersonaStack {
                     exposure_risk: 1.0,
                 },
                 suspicion: PersonaSuspicion::default(),
+                burned: false,
             },
             Persona {
                 persona_id: "masked".to_string(),
                 persona_type: PersonaType::Masked,
-                label: "Masked".to_string(),
+                label: "Hero".to_string(),
                 location_rules: LocationRules {
                     allowed_tags: vec![
                         LocationTag::Public,
@@ -229,6 +254,7 @@ pub fn hero_persona_stack() -> PersonaStack {
                     exposure_risk: 1.0,
                 },
                 suspicion: PersonaSuspicion::default(),
+                burned: false,
             },
         ],
         active_persona_id: "civilian".to_string(),
@@ -244,11 +270,12 @@ pub fn vigilante_persona_stack() -> PersonaStack {
                 persona.risk_modifiers.public_suspicion = 1.0;
                 persona.risk_modifiers.civilian_suspicion = 1.1;
                 persona.risk_modifiers.wanted_level = 0.9;
-                persona.label = "Civilian".to_string();
+                persona.label = "Day Job".to_string();
             }
             PersonaType::Masked => {
                 persona.risk_modifiers.public_suspicion = 1.2;
                 persona.risk_modifiers.wanted_level = 1.3;
+                persona.persona_id = "vigilante".to_string();
                 persona.label = "Vigilante".to_string();
             }
         }
@@ -256,16 +283,25 @@ pub fn vigilante_persona_stack() -> PersonaStack {
     stack
 }
 
+/// A villain's civilian life is a cover to protect, not a public identity.
 pub fn villain_persona_stack() -> PersonaStack {
     let mut stack = hero_persona_stack();
     for persona in stack.personas.iter_mut() {
-        if persona.persona_type == PersonaType::Masked {
-            persona.label = "Villain".to_string();
-            persona.risk_modifiers.public_suspicion = 0.9;
-            persona.risk_modifiers.wanted_level = 1.3;
-            persona.risk_modifiers.exposure_risk = 0.8;
+        match persona.persona_type {
+            PersonaType::Civilian => {
+                persona.persona_id = "cover".to_string();
+                persona.label = "Civilian Cover".to_string();
+            }
+            PersonaType::Masked => {
+                persona.persona_id = "villain".to_string();
+                persona.label = "Villain".to_string();
+                persona.risk_modifiers.public_suspicion = 0.9;
+                persona.risk_modifiers.wanted_level = 1.3;
+                persona.risk_modifiers.exposure_risk = 0.8;
+            }
         }
     }
+    stack.active_persona_id = "cover".to_string();
     stack
 }
 
@@ -278,7 +314,7 @@ impl PersonaSuspicion {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct SuspicionDelta {
     pub public_suspicion: i32,
     pub civilian_suspicion: i32,
@@ -4,8 +4,8 @@ pub mod sqlite;
 pub mod names;
 
 pub use repository::{
-    ExpressionId, OriginAcquisitionProfile, PersonaExpression, PowerId, PowerInfo, PowerRepository,
-    PowerStats,
+    ExpressionId, OriginAcquisitionProfile, PersonaExpression, PowerAcquisitionProfile, PowerId,
+    PowerInfo, PowerRepository, PowerStats, RepositoryError,
 };
-pub use sqlite::SqlitePowerRepository;
+pub use sqlite::{SqlitePowerRepository, DEFAULT_CONTENT_DB_PATH};
 pub use names::{NameDb, NameDbError, NameGender};
@@ -1,11 +1,12 @@
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::rules::expression::ExpressionDef;
+use crate::rules::expression::{ExpressionDef, Scale};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct PowerId(pub i64);
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ExpressionId(pub String);
 
 #[derive(Debug, Clone)]
@@ -41,17 +42,69 @@ pub struct OriginAcquisitionProfile {
     pub rarity_weight: i64,
 }
 
+#[derive(Debug, Clone)]
+pub struct PowerAcquisitionProfile {
+    pub acq_id: String,
+    pub power_id: PowerId,
+    pub origin_class: Option<String>,
+    pub origin_subtype: Option<String>,
+    pub delivery_channel: String,
+    pub event_kind: String,
+    pub rarity_weight: i64,
+    pub requires_entity_kind: Option<String>,
+    pub requires_tags_any: Option<String>,
+    pub requires_tags_all: Option<String>,
+}
+
+/// Failure from a content lookup, split so callers can tell a missing row
+/// from a broken database.
+#[derive(Debug)]
+pub enum RepositoryError {
+    NotFound { kind: &'static str, id: String },
+    Sqlite(rusqlite::Error),
+    SchemaMismatch(String),
+}
+
+impl std::fmt::Display for RepositoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepositoryError::NotFound { kind, id } => write!(f, "{} {} not found", kind, id),
+            RepositoryError::Sqlite(err) => write!(f, "database error: {}", err),
+            RepositoryError::SchemaMismatch(message) => {
+                write!(f, "content schema mismatch: {}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RepositoryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RepositoryError::Sqlite(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for RepositoryError {
+    fn from(err: rusqlite::Error) -> Self {
+        RepositoryError::Sqlite(err)
+    }
+}
+
 pub trait PowerRepository {
     fn stats(&self) -> Result<PowerStats, Box<dyn std::error::Error>>;
-    fn expression(&self, expr_id: &ExpressionId) -> Result<ExpressionDef, Box<dyn std::error::Error>>;
+    fn expression(&self, expr_id: &ExpressionId) -> Result<ExpressionDef, RepositoryError>;
     fn expressions_for_power(
         &self,
         power_id: PowerId,
-    ) -> Result<Vec<ExpressionDef>, Box<dyn std::error::Error>>;
-    fn power_info(
+    ) -> Result<Vec<ExpressionDef>, RepositoryError>;
+    fn expressions_by_scale(
         &self,
-        power_id: PowerId,
-    ) -> Result<Option<PowerInfo>, Box<dyn std::error::Error>>;
+        scale: Scale,
+        limit: usize,
+    ) -> Result<Vec<ExpressionDef>, Box<dyn std::error::Error>>;
+    fn power_info(&self, power_id: PowerId) -> Result<Option<PowerInfo>, RepositoryError>;
     fn power_id_by_name(
         &self,
         name: &str,
@@ -73,4 +126,8 @@ pub trait PowerRepository {
         origin_class: &str,
         origin_subtype: &str,
     ) -> Result<Vec<OriginAcquisitionProfile>, Box<dyn std::error::Error>>;
+    fn acquisition_profiles(
+        &self,
+        power_id: PowerId,
+    ) -> Result<Vec<PowerAcquisitionProfile>, Box<dyn std::error::Error>>;
 }
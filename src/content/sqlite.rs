@@ -8,20 +8,24 @@ use serde_json::Value;
 use crate::content::schema::{CONTENT_SCHEMA_VERSION, CONTENT_VERSION, DEFAULT_LOCALE};
 use crate::rules::cost::{CostSpec, CostType};
 use crate::rules::expression::{
-    Constraints, Delivery, ExpressionDef, ExpressionForm, ExpressionText, Scale,
+    Constraints, Delivery, EnvironmentEffect, ExpressionDef, ExpressionForm, ExpressionText,
+    Scale,
 };
 use crate::content::repository::{
-    ExpressionId, OriginAcquisitionProfile, PersonaExpression, PowerId, PowerInfo, PowerRepository,
-    PowerStats,
+    ExpressionId, OriginAcquisitionProfile, PersonaExpression, PowerAcquisitionProfile, PowerId,
+    PowerInfo, PowerRepository, PowerStats, RepositoryError,
 };
 use crate::rules::signature::{SignatureSpec, SignatureType};
 
+/// Where the shipped power content lives, relative to the working directory.
+pub const DEFAULT_CONTENT_DB_PATH: &str = "./assets/db/content_v1.db";
+
 pub struct SqlitePowerRepository {
     conn: Connection,
 }
 
 impl SqlitePowerRepository {
-    pub fn open(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, RepositoryError> {
         let conn = Connection::open(path)?;
         validate_content_meta(&conn)?;
         Ok(Self { conn })
@@ -38,11 +42,11 @@ impl SqlitePowerRepository {
         let signatures = load_signatures(&self.conn)?;
 
         let mut stmt = self.conn.prepare(
-            "SELECT e.expression_id, e.power_id, e.form, e.delivery, e.scale, e.constraints,\
-                    t.ui_name, t.tooltip_short\
-             FROM power_expression e\
-             JOIN power_expression_text t\
-               ON t.expression_id = e.expression_id AND t.locale = ?1\
+            "SELECT e.expression_id, e.power_id, e.form, e.delivery, e.scale, e.constraints, \
+                    t.ui_name, t.tooltip_short \
+             FROM power_expression e \
+             JOIN power_expression_text t \
+               ON t.expression_id = e.expression_id AND t.locale = ?1 \
              WHERE e.is_enabled = 1",
         )?;
 
@@ -107,16 +111,16 @@ impl PowerRepository for SqlitePowerRepository {
         })
     }
 
-    fn expression(&self, expr_id: &ExpressionId) -> Result<ExpressionDef, Box<dyn std::error::Error>> {
+    fn expression(&self, expr_id: &ExpressionId) -> Result<ExpressionDef, RepositoryError> {
         let costs = load_costs(&self.conn)?;
         let signatures = load_signatures(&self.conn)?;
 
         let mut stmt = self.conn.prepare(
-            "SELECT e.expression_id, e.power_id, e.form, e.delivery, e.scale, e.constraints,\
-                    t.ui_name, t.tooltip_short\
-             FROM power_expression e\
-             JOIN power_expression_text t\
-               ON t.expression_id = e.expression_id AND t.locale = ?1\
+            "SELECT e.expression_id, e.power_id, e.form, e.delivery, e.scale, e.constraints, \
+                    t.ui_name, t.tooltip_short \
+             FROM power_expression e \
+             JOIN power_expression_text t \
+               ON t.expression_id = e.expression_id AND t.locale = ?1 \
              WHERE e.is_enabled = 1 AND e.expression_id = ?2",
         )?;
 
@@ -139,6 +143,11 @@ impl PowerRepository for SqlitePowerRepository {
                 ui_name,
                 tooltip_short,
             ))
+        })
+        .optional()?
+        .ok_or_else(|| RepositoryError::NotFound {
+            kind: "expression",
+            id: expr_id.0.clone(),
         })?;
 
         let def = build_expression_def(
@@ -159,17 +168,17 @@ impl PowerRepository for SqlitePowerRepository {
     fn expressions_for_power(
         &self,
         power_id: PowerId,
-    ) -> Result<Vec<ExpressionDef>, Box<dyn std::error::Error>> {
+    ) -> Result<Vec<ExpressionDef>, RepositoryError> {
         let costs = load_costs(&self.conn)?;
         let signatures = load_signatures(&self.conn)?;
 
         let mut stmt = self.conn.prepare(
-            "SELECT e.expression_id, e.power_id, e.form, e.delivery, e.scale, e.constraints,\
-                    t.ui_name, t.tooltip_short\
-             FROM power_expression e\
-             JOIN power_expression_text t\
-               ON t.expression_id = e.expression_id AND t.locale = ?1\
-             WHERE e.is_enabled = 1 AND e.power_id = ?2\
+            "SELECT e.expression_id, e.power_id, e.form, e.delivery, e.scale, e.constraints, \
+                    t.ui_name, t.tooltip_short \
+             FROM power_expression e \
+             JOIN power_expression_text t \
+               ON t.expression_id = e.expression_id AND t.locale = ?1 \
+             WHERE e.is_enabled = 1 AND e.power_id = ?2 \
              ORDER BY e.expression_id",
         )?;
 
@@ -223,10 +232,79 @@ impl PowerRepository for SqlitePowerRepository {
         Ok(out)
     }
 
-    fn power_info(
+    fn expressions_by_scale(
         &self,
-        power_id: PowerId,
-    ) -> Result<Option<PowerInfo>, Box<dyn std::error::Error>> {
+        scale: Scale,
+        limit: usize,
+    ) -> Result<Vec<ExpressionDef>, Box<dyn std::error::Error>> {
+        let costs = load_costs(&self.conn)?;
+        let signatures = load_signatures(&self.conn)?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT e.expression_id, e.power_id, e.form, e.delivery, e.scale, e.constraints, \
+                    t.ui_name, t.tooltip_short \
+             FROM power_expression e \
+             JOIN power_expression_text t \
+               ON t.expression_id = e.expression_id AND t.locale = ?1 \
+             WHERE e.is_enabled = 1 AND e.scale = ?2 \
+             ORDER BY e.expression_id \
+             LIMIT ?3",
+        )?;
+
+        let rows = stmt.query_map(
+            params![DEFAULT_LOCALE, scale_to_str(scale), limit as i64],
+            |row| {
+                let expr_id: String = row.get(0)?;
+                let power_id: i64 = row.get(1)?;
+                let form: String = row.get(2)?;
+                let delivery: String = row.get(3)?;
+                let scale: String = row.get(4)?;
+                let constraints_raw: String = row.get(5)?;
+                let ui_name: String = row.get(6)?;
+                let tooltip_short: String = row.get(7)?;
+                Ok((
+                    expr_id,
+                    power_id,
+                    form,
+                    delivery,
+                    scale,
+                    constraints_raw,
+                    ui_name,
+                    tooltip_short,
+                ))
+            },
+        )?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            let (
+                expr_id,
+                power_id,
+                form,
+                delivery,
+                scale,
+                constraints_raw,
+                ui_name,
+                tooltip_short,
+            ) = row?;
+            out.push(build_expression_def(
+                &expr_id,
+                power_id,
+                &form,
+                &delivery,
+                &scale,
+                &constraints_raw,
+                ui_name,
+                tooltip_short,
+                &costs,
+                &signatures,
+            )?);
+        }
+
+        Ok(out)
+    }
+
+    fn power_info(&self, power_id: PowerId) -> Result<Option<PowerInfo>, RepositoryError> {
         let mut stmt = self.conn.prepare(
             "SELECT name, overview, description FROM Superpower4 WHERE rowid = ?1",
         )?;
@@ -349,14 +427,14 @@ impl PowerRepository for SqlitePowerRepository {
         let signatures = load_signatures(&self.conn)?;
 
         let mut stmt = self.conn.prepare(
-            "SELECT pe.expression_id, pe.power_id, pe.form, pe.delivery, pe.scale, pe.constraints,\
-                    pet.ui_name, pet.tooltip_short,\
-                    pp.persona_id, pp.mastery_level, pp.modifiers, pp.is_unlocked\
-             FROM persona_power pp\
-             JOIN power_expression pe\
-               ON pe.expression_id = pp.expression_id AND pe.is_enabled = 1\
-             JOIN power_expression_text pet\
-               ON pet.expression_id = pe.expression_id AND pet.locale = ?1\
+            "SELECT pe.expression_id, pe.power_id, pe.form, pe.delivery, pe.scale, pe.constraints, \
+                    pet.ui_name, pet.tooltip_short, \
+                    pp.persona_id, pp.mastery_level, pp.modifiers, pp.is_unlocked \
+             FROM persona_power pp \
+             JOIN power_expression pe \
+               ON pe.expression_id = pp.expression_id AND pe.is_enabled = 1 \
+             JOIN power_expression_text pet \
+               ON pet.expression_id = pe.expression_id AND pet.locale = ?1 \
              WHERE pp.persona_id = ?2 AND pp.is_unlocked = 1",
         )?;
 
@@ -436,10 +514,10 @@ impl PowerRepository for SqlitePowerRepository {
         origin_subtype: &str,
     ) -> Result<Vec<OriginAcquisitionProfile>, Box<dyn std::error::Error>> {
         let mut stmt = self.conn.prepare(
-            "SELECT acq_id, power_id, rarity_weight\
-             FROM power_acquisition_profile\
-             WHERE is_enabled = 1\
-               AND origin_class = ?1\
+            "SELECT acq_id, power_id, rarity_weight \
+             FROM power_acquisition_profile \
+             WHERE is_enabled = 1 \
+               AND origin_class = ?1 \
                AND (origin_subtype = ?2 OR origin_subtype IS NULL OR origin_subtype = '')",
         )?;
 
@@ -458,6 +536,42 @@ impl PowerRepository for SqlitePowerRepository {
 
         Ok(out)
     }
+
+    fn acquisition_profiles(
+        &self,
+        power_id: PowerId,
+    ) -> Result<Vec<PowerAcquisitionProfile>, Box<dyn std::error::Error>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT acq_id, power_id, origin_class, origin_subtype, delivery_channel, \
+                    acquisition_event_kind, rarity_weight, requires_entity_kind, \
+                    requires_tags_any, requires_tags_all \
+             FROM power_acquisition_profile \
+             WHERE is_enabled = 1 AND power_id = ?1 \
+             ORDER BY rarity_weight DESC, acq_id",
+        )?;
+
+        let rows = stmt.query_map(params![power_id.0], |row| {
+            Ok(PowerAcquisitionProfile {
+                acq_id: row.get(0)?,
+                power_id: PowerId(row.get(1)?),
+                origin_class: row.get(2)?,
+                origin_subtype: row.get(3)?,
+                delivery_channel: row.get(4)?,
+                event_kind: row.get(5)?,
+                rarity_weight: row.get(6)?,
+                requires_entity_kind: row.get(7)?,
+                requires_tags_any: row.get(8)?,
+                requires_tags_all: row.get(9)?,
+            })
+        })?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+
+        Ok(out)
+    }
 }
 
 fn build_expression_def(
@@ -471,13 +585,16 @@ fn build_expression_def(
     tooltip_short: String,
     costs: &HashMap<String, Vec<CostSpec>>,
     signatures: &HashMap<String, Vec<SignatureSpec>>,
-) -> Result<ExpressionDef, Box<dyn std::error::Error>> {
-    let constraints_json: Value = serde_json::from_str(constraints_raw)?;
-    let constraints = Constraints::from_json(&constraints_json);
-    let form = ExpressionForm::from_str(form)?;
-    let delivery = Delivery::from_str(delivery)?;
-    let scale = Scale::from_str(scale)?;
+) -> Result<ExpressionDef, RepositoryError> {
     let expr_id = ExpressionId(expr_id.to_string());
+    let constraints_json: Value = serde_json::from_str(constraints_raw).map_err(schema_mismatch)?;
+    Constraints::validate_json(&expr_id, &constraints_json).map_err(schema_mismatch)?;
+    let constraints = Constraints::from_json(&constraints_json);
+    let form = ExpressionForm::from_str(form).map_err(schema_mismatch)?;
+    let delivery = Delivery::from_str(delivery).map_err(schema_mismatch)?;
+    let scale = Scale::from_str(scale).map_err(schema_mismatch)?;
+    let environment_effects =
+        EnvironmentEffect::list_from_json(&expr_id, &constraints_json).map_err(schema_mismatch)?;
 
     let def = ExpressionDef {
         id: expr_id.clone(),
@@ -495,14 +612,31 @@ fn build_expression_def(
             .get(&expr_id.0)
             .cloned()
             .unwrap_or_default(),
+        environment_effects,
     };
-    def.validate_defaults()?;
+    def.validate_defaults().map_err(schema_mismatch)?;
     Ok(def)
 }
 
-fn load_costs(conn: &Connection) -> Result<HashMap<String, Vec<CostSpec>>, Box<dyn std::error::Error>> {
+fn schema_mismatch(err: impl std::fmt::Display) -> RepositoryError {
+    RepositoryError::SchemaMismatch(err.to_string())
+}
+
+fn scale_to_str(scale: Scale) -> &'static str {
+    match scale {
+        Scale::Street => "STREET",
+        Scale::Block => "BLOCK",
+        Scale::District => "DISTRICT",
+        Scale::City => "CITY",
+        Scale::Regional => "REGIONAL",
+        Scale::Global => "GLOBAL",
+        Scale::Cosmic => "COSMIC",
+    }
+}
+
+fn load_costs(conn: &Connection) -> Result<HashMap<String, Vec<CostSpec>>, RepositoryError> {
     let mut stmt = conn.prepare(
-        "SELECT expression_id, cost_type, value, risk_type, risk_chance\
+        "SELECT expression_id, cost_type, value, risk_type, risk_chance \
          FROM power_expression_cost",
     )?;
 
@@ -518,7 +652,7 @@ fn load_costs(conn: &Connection) -> Result<HashMap<String, Vec<CostSpec>>, Box<d
     let mut out: HashMap<String, Vec<CostSpec>> = HashMap::new();
     for row in rows {
         let (expr_id, cost_type, value, risk_type, risk_chance) = row?;
-        let cost_type = CostType::from_str(&cost_type)?;
+        let cost_type = CostType::from_str(&cost_type).map_err(schema_mismatch)?;
         out.entry(expr_id).or_default().push(CostSpec {
             cost_type,
             value,
@@ -532,9 +666,9 @@ fn load_costs(conn: &Connection) -> Result<HashMap<String, Vec<CostSpec>>, Box<d
 
 fn load_signatures(
     conn: &Connection,
-) -> Result<HashMap<String, Vec<SignatureSpec>>, Box<dyn std::error::Error>> {
+) -> Result<HashMap<String, Vec<SignatureSpec>>, RepositoryError> {
     let mut stmt = conn.prepare(
-        "SELECT expression_id, signature_type, strength, persistence_turns\
+        "SELECT expression_id, signature_type, strength, persistence_turns \
          FROM power_expression_signature",
     )?;
 
@@ -549,7 +683,7 @@ fn load_signatures(
     let mut out: HashMap<String, Vec<SignatureSpec>> = HashMap::new();
     for row in rows {
         let (expr_id, signature_type, strength, persistence_turns) = row?;
-        let signature_type = SignatureType::from_str(&signature_type)?;
+        let signature_type = SignatureType::from_str(&signature_type).map_err(schema_mismatch)?;
         out.entry(expr_id).or_default().push(SignatureSpec {
             signature_type,
             strength,
@@ -566,7 +700,7 @@ fn count_rows(conn: &Connection, table: &str) -> Result<i64, Box<dyn std::error:
     Ok(count)
 }
 
-fn validate_content_meta(conn: &Connection) -> Result<(), Box<dyn std::error::Error>> {
+fn validate_content_meta(conn: &Connection) -> Result<(), RepositoryError> {
     let table = conn
         .query_row(
             "SELECT name FROM sqlite_master WHERE type='table' AND name='content_meta'",
@@ -575,11 +709,9 @@ fn validate_content_meta(conn: &Connection) -> Result<(), Box<dyn std::error::Er
         )
         .optional()?;
     if table.is_none() {
-        return Err(std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
+        return Err(schema_mismatch(
             "content_meta table missing (rebuild content_v1.db with tools/export_content_db.py)",
-        )
-        .into());
+        ));
     }
 
     let meta = conn
@@ -591,33 +723,78 @@ fn validate_content_meta(conn: &Connection) -> Result<(), Box<dyn std::error::Er
         .optional()?;
 
     let Some((schema_version, content_version)) = meta else {
-        return Err(std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            "content_meta missing row id=1",
-        )
-        .into());
+        return Err(schema_mismatch("content_meta missing row id=1"));
     };
 
     if schema_version != CONTENT_SCHEMA_VERSION {
-        return Err(std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            format!(
-                "content_meta schema_version {} != expected {}",
-                schema_version, CONTENT_SCHEMA_VERSION
-            ),
-        )
-        .into());
+        return Err(schema_mismatch(format!(
+            "content_meta schema_version {} != expected {}",
+            schema_version, CONTENT_SCHEMA_VERSION
+        )));
     }
     if content_version != CONTENT_VERSION {
-        return Err(std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            format!(
-                "content_meta content_version {} != expected {}",
-                content_version, CONTENT_VERSION
-            ),
-        )
-        .into());
+        return Err(schema_mismatch(format!(
+            "content_meta content_version {} != expected {}",
+            content_version, CONTENT_VERSION
+        )));
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_db_loads_acquisition_profiles() {
+        let conn = Connection::open("./assets/db/content_v1.db").expect("open sample content db");
+        let repo = SqlitePowerRepository { conn };
+        let profiles = repo
+            .acquisition_profiles(PowerId(1))
+            .expect("query acquisition profiles");
+        assert!(!profiles.is_empty());
+        assert!(profiles.iter().all(|profile| profile.power_id == PowerId(1)));
+        assert!(profiles
+            .iter()
+            .any(|profile| profile.origin_class.as_deref() == Some("ASCENDANT")));
+    }
+
+    #[test]
+    fn missing_expression_is_not_found() {
+        let conn = Connection::open("./assets/db/content_v1.db").expect("open sample content db");
+        let repo = SqlitePowerRepository { conn };
+        let err = repo
+            .expression(&ExpressionId("no_such_expression".to_string()))
+            .expect_err("missing expression should fail");
+        assert!(matches!(
+            err,
+            RepositoryError::NotFound { kind: "expression", ref id } if id == "no_such_expression"
+        ));
+    }
+
+    #[test]
+    fn bad_identity_constraints_fail_the_load() {
+        let build = |constraints: &str| {
+            build_expression_def(
+                "aura_pulse",
+                1,
+                "AURA",
+                "INSTANT",
+                "STREET",
+                constraints,
+                "Aura Pulse".to_string(),
+                String::new(),
+                &HashMap::new(),
+                &HashMap::new(),
+            )
+        };
+        let err = build(r#"{"requires_persona":"MASKD"}"#).expect_err("typo must not load");
+        assert!(matches!(err, RepositoryError::SchemaMismatch(ref reason)
+            if reason.contains("requires_persona")));
+        let err = build(r#"{"allowed_alignments":["HERO","PALADIN"]}"#)
+            .expect_err("unknown alignment must not load");
+        assert!(matches!(err, RepositoryError::SchemaMismatch(ref reason)
+            if reason.contains("allowed_alignments")));
+    }
+}
@@ -2,17 +2,18 @@ use bevy_ecs::prelude::*;
 use bevy_ecs::schedule::SystemSet;
 
 use crate::core::world::ActionQueue;
-use crate::core::world::IdAllocator;
+use crate::core::world::{IdAllocator, WorldSeed};
 use crate::simulation::agents::{AgentEventLog, AgentRegistry};
 use crate::simulation::city::{CityEventLog, CityState};
 use crate::simulation::case::{CaseEventLog, CaseRegistry};
 use crate::simulation::evidence::WorldEvidence;
 use crate::simulation::identity_evidence::IdentityEvidenceStore;
-use crate::simulation::civilian::CivilianState;
+use crate::simulation::civilian::{CivilianEventLibrary, CivilianState};
 use crate::simulation::storylets::StoryletLibrary;
 use crate::simulation::storylet_state::StoryletState;
 use crate::simulation::pressure::PressureState;
 use crate::simulation::region::{RegionEventLog, RegionState};
+use crate::simulation::stats::RunStats;
 use crate::simulation::time::{advance_time_system, GameTime};
 use crate::simulation::nemesis::NemesisState;
 use crate::systems::combat::{combat_system, CombatLog};
@@ -22,13 +23,16 @@ use crate::systems::economy::economy_system;
 use crate::systems::event_resolver::{event_resolver_system, ResolvedFactionEventLog};
 use crate::systems::faction::{faction_director_system, FactionDirector, FactionEventLog};
 use crate::systems::heat::{heat_decay_system, signature_heat_system, update_active_location_system, WorldEventLog};
+use crate::systems::influence::district_control_system;
 use crate::systems::nemesis::{nemesis_system, NemesisDirector, NemesisEventLog};
 use crate::systems::movement_system;
 use crate::systems::persona::{persona_switch_system, PersonaEventLog};
 use crate::systems::pressure::pressure_system;
+use crate::systems::stats::run_stats_system;
 use crate::systems::region::{global_faction_system, region_system, GlobalFactionDirector, GlobalFactionEventLog};
-use crate::systems::suspicion::suspicion_system;
+use crate::systems::suspicion::{exposure_breach_system, suspicion_system};
 use crate::systems::units::unit_movement_system;
+use crate::data::civilian_events::{load_civilian_event_catalog, DEFAULT_CIVILIAN_EVENT_CATALOG};
 use crate::data::storylets::{load_storylet_catalog, Storylet};
 
 /// Canonical tick ordering for the simulation.
@@ -41,8 +45,9 @@ pub enum TickSet {
 }
 
 /// Build the ECS world with baseline resources.
-pub fn create_world(_seed: u64) -> World {
+pub fn create_world(seed: u64) -> World {
     let mut world = World::new();
+    world.insert_resource(WorldSeed(seed));
     world.insert_resource(GameTime::default());
     world.insert_resource(ActionQueue::default());
     world.insert_resource(CombatLog::default());
@@ -61,6 +66,7 @@ pub fn create_world(_seed: u64) -> World {
     world.insert_resource(RegionState::default());
     world.insert_resource(RegionEventLog::default());
     world.insert_resource(CivilianState::default());
+    world.insert_resource(load_civilian_events());
     world.insert_resource(NemesisState::default());
     world.insert_resource(NemesisEventLog::default());
     world.insert_resource(load_faction_director());
@@ -71,6 +77,7 @@ pub fn create_world(_seed: u64) -> World {
     world.insert_resource(StoryletState::default());
     world.insert_resource(load_agents());
     world.insert_resource(AgentEventLog::default());
+    world.insert_resource(RunStats::default());
     world
 }
 
@@ -92,6 +99,9 @@ pub fn create_schedule() -> Schedule {
             persona_switch_system.in_set(TickSet::Simulation),
             faction_director_system.in_set(TickSet::Simulation),
             event_resolver_system.in_set(TickSet::Simulation),
+            district_control_system
+                .in_set(TickSet::Simulation)
+                .after(event_resolver_system),
             case_progress_system.in_set(TickSet::Simulation),
             nemesis_system
                 .in_set(TickSet::Simulation)
@@ -103,6 +113,19 @@ pub fn create_schedule() -> Schedule {
             civilian_system
                 .in_set(TickSet::Simulation)
                 .after(pressure_system),
+            exposure_breach_system
+                .in_set(TickSet::Simulation)
+                .after(suspicion_system)
+                .after(persona_switch_system),
+            run_stats_system
+                .in_set(TickSet::Simulation)
+                .after(civilian_system)
+                .after(exposure_breach_system)
+                .after(case_progress_system),
+        ),
+    );
+    schedule.add_systems(
+        (
             heat_decay_system.in_set(TickSet::Time),
             region_system
                 .in_set(TickSet::Time)
@@ -142,9 +165,24 @@ fn load_storylets() -> StoryletLibrary {
         hero: load_storylet_file("./assets/data/storylets_hero.json"),
         vigilante: load_storylet_file("./assets/data/storylets_vigilante.json"),
         villain: load_storylet_file("./assets/data/storylets_villain.json"),
+        endgame_hero: load_endgame_storylet_file("./assets/data/storylets_endgame_hero.json"),
+        endgame_vigilante: load_endgame_storylet_file(
+            "./assets/data/storylets_endgame_vigilante.json",
+        ),
+        endgame_villain: load_endgame_storylet_file(
+            "./assets/data/storylets_endgame_villain.json",
+        ),
     }
 }
 
+/// Endgame pools are optional; a missing file is an empty pool.
+fn load_endgame_storylet_file(path: &str) -> Vec<Storylet> {
+    if !std::path::Path::new(path).exists() {
+        return Vec::new();
+    }
+    load_storylet_file(path)
+}
+
 fn load_storylet_file(path: &str) -> Vec<Storylet> {
     match load_storylet_catalog(path) {
         Ok(catalog) => catalog.storylets,
@@ -155,6 +193,16 @@ fn load_storylet_file(path: &str) -> Vec<Storylet> {
     }
 }
 
+fn load_civilian_events() -> CivilianEventLibrary {
+    match load_civilian_event_catalog(DEFAULT_CIVILIAN_EVENT_CATALOG) {
+        Ok(catalog) => CivilianEventLibrary(catalog.events),
+        Err(err) => {
+            eprintln!("Failed to load civilian events: {}", err);
+            CivilianEventLibrary::default()
+        }
+    }
+}
+
 fn load_agents() -> AgentRegistry {
     match AgentRegistry::load_default() {
         Ok(registry) => registry,
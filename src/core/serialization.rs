@@ -8,12 +8,10 @@ use serde::{Deserialize, Serialize};
 use crate::components::combat::Health;
 use crate::components::faction::Faction;
 use crate::components::identity::{CivilianIdentity, Name, SuperIdentity};
-use crate::components::persona::{
-    hero_persona_stack, neutral_persona_stack, vigilante_persona_stack, villain_persona_stack,
-    Alignment, PersonaStack,
-};
+use crate::components::persona::{Alignment, PersonaStack};
 use crate::components::world::{EntityId, Player, Position};
 use crate::core::world::IdAllocator;
+use crate::simulation::civilian::{CivilianState, LegacyRecord};
 use crate::simulation::storylet_state::StoryletState;
 use crate::simulation::time::GameTime;
 
@@ -28,6 +26,9 @@ pub struct SaveState {
     pub npcs: Vec<SavedActor>,
     #[serde(default)]
     pub storylet_state: StoryletState,
+    /// The legacy of a life that has ended; a save carrying one loads locked.
+    #[serde(default)]
+    pub final_legacy: Option<LegacyRecord>,
 }
 
 fn default_save_version() -> u32 {
@@ -92,6 +93,10 @@ pub fn extract_state_from_world(world: &World, player: Entity, seed: u64) -> Sav
         .get_resource::<StoryletState>()
         .cloned()
         .unwrap_or_default();
+    let final_legacy = world
+        .get_resource::<CivilianState>()
+        .filter(|civilian| civilian.life_ended)
+        .and_then(|civilian| civilian.legacy.last().cloned());
 
     let player_uid = world.get::<EntityId>(player).map(|id| id.0).unwrap_or(0);
 
@@ -202,6 +207,7 @@ pub fn extract_state_from_world(world: &World, player: Entity, seed: u64) -> Sav
         },
         npcs,
         storylet_state,
+        final_legacy,
     }
 }
 
@@ -215,6 +221,17 @@ pub fn apply_state_to_world(state: SaveState, world: &mut World, player: Entity)
     } else {
         world.insert_resource(state.storylet_state.clone());
     }
+    if let Some(mut civilian) = world.get_resource_mut::<CivilianState>() {
+        civilian.life_ended = state.final_legacy.is_some();
+        if let Some(record) = &state.final_legacy {
+            let recorded = civilian.legacy.last().is_some_and(|last| {
+                last.day == record.day && last.death_reason == record.death_reason
+            });
+            if !recorded {
+                civilian.legacy.push(record.clone());
+            }
+        }
+    }
 
     if let Some(mut ent_id) = world.get_mut::<EntityId>(player) {
         ent_id.0 = state.player.uid;
@@ -332,12 +349,7 @@ pub fn apply_state_to_world(state: SaveState, world: &mut World, player: Entity)
 
     if state.player.persona_stack.is_none() {
         let alignment = state.player.alignment.unwrap_or(Alignment::Neutral);
-        let default_stack = match alignment {
-            Alignment::Neutral => neutral_persona_stack(),
-            Alignment::Hero => hero_persona_stack(),
-            Alignment::Vigilante => vigilante_persona_stack(),
-            Alignment::Villain => villain_persona_stack(),
-        };
+        let default_stack = PersonaStack::for_alignment(alignment);
         if let Some(mut ent) = world.get_entity_mut(player) {
             ent.insert(default_stack);
         }
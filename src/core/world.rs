@@ -1,20 +1,50 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::components::combat::Health;
 use crate::components::faction::Faction;
 use crate::components::identity::{CivilianIdentity, Name, SuperIdentity};
-use crate::components::persona::{neutral_persona_stack, Alignment};
+use crate::components::persona::{
+    neutral_persona_stack, Alignment, PersonaStack, PersonaSuspicion, SuspicionDelta,
+};
 use crate::components::world::{EntityId, Player, Position};
+use crate::content::{
+    ExpressionId, PowerRepository, RepositoryError, SqlitePowerRepository,
+    DEFAULT_CONTENT_DB_PATH,
+};
 use crate::core::ecs::{create_schedule, create_world};
+use crate::data::civilian_events::{
+    load_civilian_event_catalogs, CivilianEventCollision, CivilianEventDataError,
+    DEFAULT_CIVILIAN_EVENT_CATALOG,
+};
+use crate::data::factions::{DataError, FactionInstanceDefinition, FactionTypeDefinition};
 use crate::core::serialization::{
     apply_state_to_world, extract_state_from_world, load_state_from_path, save_state_to_path, SaveState,
 };
-use crate::simulation::time::GameTime;
+use crate::simulation::civilian::{
+    pending_civilian_event_views, resolve_civilian_choice, CivilianChoiceContext,
+    CivilianEventError, CivilianEventLibrary, CivilianEventView, CivilianStart, CivilianStartError, CivilianState, LegacyRecord,
+};
+use crate::rules::signature::SignatureInstance;
+use crate::rules::use_power::{
+    use_power, ActorState, TargetContext, UseContext, UseError, WorldState,
+};
+use crate::simulation::agents::{AgentCatalog, AgentRegistry};
+use crate::simulation::case::CaseRegistry;
+use crate::simulation::city::{CityState, LocationId};
+use crate::simulation::evidence::WorldEvidence;
+use crate::simulation::pressure::{PressureAxis, PressureState};
+use crate::simulation::stats::RunStats;
+use crate::simulation::time::{GameTime, TimeConfig};
 use crate::simulation::origin::assign_origin_for_player;
 use crate::content::names::{NameDb, NameGender};
 use crate::systems::combat::CombatLog;
+use crate::systems::faction::FactionDirector;
+use crate::systems::heat::WorldEventLog;
+use crate::systems::suspicion::apply_crime_suspicion;
 
 /// Intent-driven commands fed into the ECS each tick.
 #[derive(Debug, Clone)]
@@ -31,9 +61,16 @@ pub enum ActionIntent {
 #[derive(Resource, Default, Debug)]
 pub struct ActionQueue(pub Vec<ActionIntent>);
 
+/// The seed the game was created with, for systems whose rolls must replay.
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct WorldSeed(pub u64);
+
 /// Data snapshot returned to the UI layer after each tick.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Snapshot {
+    /// Tick the snapshot was taken at. The per-tick logs below hold only that
+    /// tick's lines, so a line's sequence number is (tick, position).
+    pub tick: u64,
     pub time_str: String,
     pub player_name: String,
     pub suspicion: u8,
@@ -41,16 +78,224 @@ pub struct Snapshot {
     pub player_pos: (i32, i32),
     pub combat_log: Vec<String>,
     pub entities: Vec<EntitySummary>,
+    pub pressure: PressureState,
+    pub cases: Vec<CaseSummary>,
+    pub world_events: Vec<String>,
+    pub personas: Vec<PersonaSummary>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct EntitySummary {
     pub id: u32,
     pub name: String,
     pub position: (i32, i32),
     pub health: Option<(i32, i32)>,
+    pub suspicion: Option<u8>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CaseSummary {
+    pub case_id: u32,
+    pub faction_id: String,
+    pub location_id: u32,
+    pub progress: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PersonaSummary {
+    pub persona_id: String,
+    pub label: String,
+    pub suspicion: PersonaSuspicion,
+}
+
+/// What changed between two snapshots, for front-ends that redraw only the
+/// parts of the world that moved.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SnapshotDiff {
+    pub pressure: Vec<PressureChange>,
+    pub new_cases: Vec<CaseSummary>,
+    pub new_world_events: Vec<String>,
+    pub new_combat_log: Vec<String>,
+    pub player_suspicion_delta: i32,
+    pub player_moved: Option<((i32, i32), (i32, i32))>,
+    pub player_health: Option<((i32, i32), (i32, i32))>,
+    pub persona_suspicion: Vec<PersonaSuspicionChange>,
+    pub entities: Vec<EntityChange>,
+    /// Entities whose health ran out since the previous snapshot.
+    pub defeated: Vec<u32>,
+    /// Set when a fight started or ended between the two snapshots.
+    pub combat: Option<CombatTransition>,
+}
+
+/// A fight is on while attacks are resolving each tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CombatTransition {
+    Started,
+    Ended { reason: CombatEndReason },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CombatEndReason {
+    PlayerDefeated,
+    /// Every wounded entity is down.
+    OpponentsDefeated,
+    /// Attacks stopped with someone still standing.
+    Disengaged,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PressureChange {
+    pub axis: &'static str,
+    pub before: f32,
+    pub after: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PersonaSuspicionChange {
+    pub persona_id: String,
+    pub delta: SuspicionDelta,
+}
+
+/// An entity that appeared, moved, was hurt or drew suspicion. `from` is None
+/// for entities that were not in the previous snapshot.
+#[derive(Debug, Clone, Serialize)]
+pub struct EntityChange {
+    pub id: u32,
+    pub name: String,
+    pub from: Option<(i32, i32)>,
+    pub to: (i32, i32),
+    pub health: Option<(i32, i32)>,
+    pub suspicion_delta: i32,
+}
+
+/// Scenario options for a new game: who the civilian is when play begins.
+#[derive(Debug, Clone)]
+pub struct GameOptions {
+    pub civilian: CivilianStart,
+    pub alignment: Alignment,
+    /// Civilian event catalogs merged in order; later files override events by id.
+    pub civilian_event_catalogs: Vec<PathBuf>,
+    /// Authored factions and agents seeded over the default city data.
+    pub scenario: ScenarioSeed,
+    /// Clock cadence; kept with the save so a reload ticks at the same pace.
+    pub time: TimeConfig,
+    /// Power content for `use_expression`. The game runs without it; only
+    /// expressions are unavailable.
+    pub content_db: PathBuf,
+}
+
+/// Bespoke antagonists for an authored campaign.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ScenarioSeed {
+    /// Faction types added to the defaults; a matching id replaces the default type.
+    pub faction_types: Vec<FactionTypeDefinition>,
+    /// Faction instances merged by id; their `influence` entries seed the city.
+    pub factions: Vec<FactionInstanceDefinition>,
+    /// Drop the default faction instances instead of merging over them.
+    pub replace_factions: bool,
+    /// Agent roster that replaces the default agents when present.
+    pub agents: Option<AgentCatalog>,
+}
+
+impl Default for GameOptions {
+    fn default() -> Self {
+        Self {
+            civilian: CivilianStart::default(),
+            alignment: Alignment::Neutral,
+            civilian_event_catalogs: vec![PathBuf::from(DEFAULT_CIVILIAN_EVENT_CATALOG)],
+            scenario: ScenarioSeed::default(),
+            time: TimeConfig::default(),
+            content_db: PathBuf::from(DEFAULT_CONTENT_DB_PATH),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum GameSetupError {
+    Civilian(CivilianStartError),
+    CivilianEvents(CivilianEventDataError),
+    Factions(DataError),
+    UnknownLocation { source: String, location_id: u32 },
+}
+
+impl std::fmt::Display for GameSetupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameSetupError::Civilian(err) => write!(f, "invalid civilian start: {}", err),
+            GameSetupError::CivilianEvents(err) => write!(f, "civilian events: {}", err),
+            GameSetupError::Factions(err) => write!(f, "scenario factions: {}", err),
+            GameSetupError::UnknownLocation {
+                source,
+                location_id,
+            } => write!(f, "{} references unknown location {}", source, location_id),
+        }
+    }
+}
+
+impl std::error::Error for GameSetupError {}
+
+impl From<CivilianStartError> for GameSetupError {
+    fn from(err: CivilianStartError) -> Self {
+        Self::Civilian(err)
+    }
+}
+
+impl From<CivilianEventDataError> for GameSetupError {
+    fn from(err: CivilianEventDataError) -> Self {
+        Self::CivilianEvents(err)
+    }
 }
 
+impl From<DataError> for GameSetupError {
+    fn from(err: DataError) -> Self {
+        Self::Factions(err)
+    }
+}
+
+/// What one expression use cost and left behind, with the tick that followed.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExpressionUse {
+    pub expression_id: String,
+    pub name: String,
+    pub stamina: i64,
+    pub focus: i64,
+    pub cooldown_turns: Option<i64>,
+    pub signatures: Vec<SignatureInstance>,
+    pub snapshot: Snapshot,
+}
+
+#[derive(Debug)]
+pub enum UseExpressionError {
+    LifeEnded,
+    NoContent,
+    /// The content lookup failed, or the content DB never opened; an open
+    /// failure is kept and returned on every use.
+    Repository(Arc<RepositoryError>),
+    Rejected {
+        expression_id: String,
+        reason: UseError,
+    },
+}
+
+impl std::fmt::Display for UseExpressionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UseExpressionError::LifeEnded => write!(f, "this life has ended"),
+            UseExpressionError::NoContent => write!(f, "no power content is loaded"),
+            UseExpressionError::Repository(err) => write!(f, "{}", err),
+            UseExpressionError::Rejected {
+                expression_id,
+                reason,
+            } => write!(f, "cannot use {}: {:?}", expression_id, reason),
+        }
+    }
+}
+
+impl std::error::Error for UseExpressionError {}
+
 /// Wrapper around the ECS world and schedule.
 pub struct Game {
     world: World,
@@ -58,6 +303,13 @@ pub struct Game {
     player: Entity,
     player_uid: u32,
     seed: u64,
+    start: CivilianStart,
+    ended: Option<LegacyRecord>,
+    civilian_event_collisions: Vec<CivilianEventCollision>,
+    /// `None` for a game built without options; otherwise the content DB or
+    /// the error that kept it from opening.
+    content: Option<Result<SqlitePowerRepository, Arc<RepositoryError>>>,
+    actor: ActorState,
 }
 
 impl Game {
@@ -76,20 +328,231 @@ impl Game {
             player,
             player_uid,
             seed,
+            start: CivilianStart::default(),
+            ended: None,
+            civilian_event_collisions: Vec::new(),
+            content: None,
+            actor: ActorState {
+                stamina: 10,
+                focus: 10,
+                ..Default::default()
+            },
         }
     }
 
+    /// Create a new game whose civilian starts from the given scenario options.
+    pub fn with_options(seed: u64, options: GameOptions) -> Result<Self, GameSetupError> {
+        let civilian = CivilianState::from_start(&options.civilian)?;
+        let civilian_events = load_civilian_event_catalogs(&options.civilian_event_catalogs)?;
+        let mut game = Self::new(seed);
+        seed_scenario(&mut game.world, options.scenario)?;
+        game.world.insert_resource(GameTime::with_config(options.time));
+        game.world.insert_resource(civilian);
+        game.world
+            .insert_resource(CivilianEventLibrary(civilian_events.events));
+        game.civilian_event_collisions = civilian_events.collisions;
+        if let Some(mut alignment) = game.world.get_mut::<Alignment>(game.player) {
+            *alignment = options.alignment;
+        }
+        if let Some(mut stack) = game.world.get_mut::<PersonaStack>(game.player) {
+            *stack = PersonaStack::for_alignment(options.alignment);
+        }
+        game.start = options.civilian;
+        game.content = Some(SqlitePowerRepository::open(&options.content_db).map_err(Arc::new));
+        Ok(game)
+    }
+
     /// Run a simulation tick with the provided intents and return a snapshot for rendering.
+    /// Once the civilian life has ended, intents are rejected until a new life starts.
     pub fn tick(&mut self, intents: Vec<ActionIntent>) -> Snapshot {
-        {
-            let mut queue = self.world.resource_mut::<ActionQueue>();
-            queue.0 = intents;
+        if self.ended.is_none() {
+            {
+                let mut queue = self.world.resource_mut::<ActionQueue>();
+                queue.0 = intents;
+            }
+
+            self.schedule.run(&mut self.world);
+            self.actor.tick_cooldowns();
+            self.finalize_death();
         }
+        Snapshot::capture(self.player, &self.world)
+    }
 
-        self.schedule.run(&mut self.world);
+    /// Snapshot of the current world without advancing the simulation.
+    pub fn snapshot(&self) -> Snapshot {
         Snapshot::capture(self.player, &self.world)
     }
 
+    /// True once the civilian has died and the game is waiting for a new life.
+    pub fn is_locked(&self) -> bool {
+        self.ended.is_some()
+    }
+
+    /// Legacy record of the life that just ended, if the game is locked.
+    pub fn final_legacy(&self) -> Option<&LegacyRecord> {
+        self.ended.as_ref()
+    }
+
+    /// Civilian event ids that a later catalog overrode when the game was set up.
+    pub fn civilian_event_collisions(&self) -> &[CivilianEventCollision] {
+        &self.civilian_event_collisions
+    }
+
+    /// Lifetime tallies for the run so far.
+    pub fn stats(&self) -> &RunStats {
+        self.world.resource::<RunStats>()
+    }
+
+    /// All legacy records accumulated across lives.
+    pub fn legacy(&self) -> &[LegacyRecord] {
+        &self.world.resource::<CivilianState>().legacy
+    }
+
+    /// Start over with a fresh civilian life from the game's starting options,
+    /// keeping the legacy history.
+    pub fn start_new_life(&mut self) {
+        let day = self.world.resource::<GameTime>().day;
+        let mut civilian = self.world.resource_mut::<CivilianState>();
+        let legacy = std::mem::take(&mut civilian.legacy);
+        *civilian = CivilianState::from_start(&self.start).unwrap_or_default();
+        civilian.legacy = legacy;
+        civilian.life.birth_day = day;
+        self.ended = None;
+        self.actor = ActorState {
+            stamina: 10,
+            focus: 10,
+            ..Default::default()
+        };
+    }
+
+    /// Pending civilian events rendered against the loaded event catalog.
+    pub fn pending_civilian_events(&self) -> Vec<CivilianEventView> {
+        let library = self.world.resource::<CivilianEventLibrary>();
+        pending_civilian_event_views(self.world.resource::<CivilianState>(), &library.0)
+    }
+
+    /// Resolve a pending civilian event and return the effects that applied.
+    pub fn resolve_civilian_event(
+        &mut self,
+        event_id: &str,
+        choice_id: &str,
+    ) -> Result<Vec<String>, CivilianEventError> {
+        let seed = self.world.resource::<WorldSeed>().0 ^ self.world.resource::<GameTime>().tick;
+        let resolution =
+            self.world
+                .resource_scope(|world, library: Mut<CivilianEventLibrary>| {
+                    world.resource_scope(|world, city: Mut<CityState>| {
+                        let mut civilian = world.resource_mut::<CivilianState>();
+                        if !civilian
+                            .pending_events
+                            .iter()
+                            .any(|event| event.storylet_id == event_id)
+                        {
+                            return Err(CivilianEventError::NotPending(event_id.to_string()));
+                        }
+                        let context = CivilianChoiceContext {
+                            library: &library.0,
+                            city: &city,
+                            seed,
+                        };
+                        resolve_civilian_choice(&mut civilian, context, event_id, choice_id)
+                    })
+                })?;
+        if let Some(attempt) = &resolution.crime {
+            let alignment = self
+                .world
+                .get::<Alignment>(self.player)
+                .copied()
+                .unwrap_or(Alignment::Neutral);
+            if let Some(mut stack) = self.world.get_mut::<PersonaStack>(self.player) {
+                apply_crime_suspicion(
+                    &mut stack,
+                    alignment,
+                    attempt.wanted_delta,
+                    attempt.civilian_suspicion_delta,
+                );
+            }
+        }
+        Ok(resolution.applied)
+    }
+
+    /// Use a power expression as the player, then tick once. The expression's
+    /// signatures are left as evidence at the active location.
+    pub fn use_expression(&mut self, expr_id: &str) -> Result<ExpressionUse, UseExpressionError> {
+        if self.ended.is_some() {
+            return Err(UseExpressionError::LifeEnded);
+        }
+        let repo = match &self.content {
+            Some(Ok(repo)) => repo,
+            Some(Err(err)) => return Err(UseExpressionError::Repository(Arc::clone(err))),
+            None => return Err(UseExpressionError::NoContent),
+        };
+        let expr = repo
+            .expression(&ExpressionId(expr_id.to_string()))
+            .map_err(|err| UseExpressionError::Repository(Arc::new(err)))?;
+        let time = self.world.resource::<GameTime>();
+        let world_state = WorldState {
+            turn: time.tick,
+            ..Default::default()
+        };
+        // The REPL's default scene: a target 10m off, in plain view.
+        let target = TargetContext {
+            distance_m: Some(10),
+            has_line_of_sight: true,
+            has_contact: false,
+            in_public: true,
+            witnesses: 0,
+        };
+        let mut ctx = UseContext {
+            actor: &mut self.actor,
+            world: &world_state,
+            mastery: None,
+            unlocked: None,
+            persona_type: self
+                .world
+                .get::<PersonaStack>(self.player)
+                .and_then(|stack| stack.active_persona())
+                .map(|persona| persona.persona_type),
+            alignment: self.world.get::<Alignment>(self.player).copied(),
+            is_day: Some(time.is_day),
+        };
+        let result =
+            use_power(&mut ctx, &expr, &target).map_err(|reason| UseExpressionError::Rejected {
+                expression_id: expr_id.to_string(),
+                reason,
+            })?;
+        let location = self.world.resource::<CityState>().active_location;
+        self.world
+            .resource_mut::<WorldEvidence>()
+            .emit(location, &result.emitted_signatures);
+        let snapshot = self.tick(vec![ActionIntent::Wait]);
+        Ok(ExpressionUse {
+            expression_id: expr.id.0,
+            name: expr.text.ui_name,
+            stamina: self.actor.stamina,
+            focus: self.actor.focus,
+            cooldown_turns: result.cooldown_turns,
+            signatures: result.emitted_signatures,
+            snapshot,
+        })
+    }
+
+    fn finalize_death(&mut self) {
+        let alignment = self
+            .world
+            .get::<Alignment>(self.player)
+            .copied()
+            .unwrap_or(Alignment::Neutral);
+        let stats = self.world.resource::<RunStats>().clone();
+        let mut civilian = self.world.resource_mut::<CivilianState>();
+        let Some(death) = civilian.pending_death.take() else {
+            return;
+        };
+        let record =
+            civilian.record_legacy(&format!("{:?}", alignment), &death.reason, death.day, &stats);
+        self.ended = Some(record);
+    }
+
     /// Expose the player's entity index for intent addressing.
     pub fn get_player_id(&self) -> u32 {
         self.player_uid
@@ -103,7 +566,14 @@ impl Game {
     /// Apply a saved state back into the live world.
     pub fn load_state(&mut self, state: SaveState) {
         self.seed = state.seed;
+        self.world.insert_resource(WorldSeed(state.seed));
         apply_state_to_world(state, &mut self.world, self.player);
+        let civilian = self.world.resource::<CivilianState>();
+        self.ended = if civilian.life_ended {
+            civilian.legacy.last().cloned()
+        } else {
+            None
+        };
         self.player_uid = self
             .world
             .get::<EntityId>(self.player)
@@ -149,6 +619,67 @@ impl IdAllocator {
     }
 }
 
+/// Validates the scenario against the seeded city, then merges its factions,
+/// starting influence and agent roster into the world.
+fn seed_scenario(world: &mut World, scenario: ScenarioSeed) -> Result<(), GameSetupError> {
+    let city = world.resource::<CityState>();
+    let check = |source: String, location_id: u32| {
+        if city.locations.contains_key(&LocationId(location_id)) {
+            Ok(())
+        } else {
+            Err(GameSetupError::UnknownLocation {
+                source,
+                location_id,
+            })
+        }
+    };
+    for faction in &scenario.factions {
+        let source = format!("faction {}", faction.id);
+        for location_id in &faction.scope.location_ids {
+            check(source.clone(), *location_id)?;
+        }
+        for entry in &faction.influence {
+            check(source.clone(), entry.location_id)?;
+        }
+    }
+    if let Some(catalog) = &scenario.agents {
+        for template in &catalog.templates {
+            let source = format!("agent {}", template.id);
+            check(source.clone(), template.home_location)?;
+            check(source, template.haunt_location)?;
+        }
+    }
+
+    let influences: Vec<(String, u32, u16)> = scenario
+        .factions
+        .iter()
+        .flat_map(|faction| {
+            faction.influence.iter().map(|entry| {
+                (
+                    faction.id.clone(),
+                    entry.location_id,
+                    entry.influence.clamp(0, 100) as u16,
+                )
+            })
+        })
+        .collect();
+    world.resource_mut::<FactionDirector>().merge_scenario(
+        scenario.faction_types,
+        scenario.factions,
+        scenario.replace_factions,
+    )?;
+    let mut city = world.resource_mut::<CityState>();
+    for (faction_id, location_id, influence) in influences {
+        if let Some(location) = city.locations.get_mut(&LocationId(location_id)) {
+            location.faction_influence.insert(faction_id, influence);
+        }
+    }
+    if let Some(catalog) = scenario.agents {
+        world.insert_resource(AgentRegistry::from_catalog(catalog));
+    }
+    Ok(())
+}
+
 fn allocate_entity_id(world: &mut World) -> u32 {
     let mut alloc = world.resource_mut::<IdAllocator>();
     alloc.alloc()
@@ -283,6 +814,7 @@ fn build_npc_name(
 impl Snapshot {
     fn capture(player: Entity, world: &World) -> Self {
         let time = world.resource::<GameTime>();
+        let tick = time.tick;
         let time_str = time.to_string();
 
         let player_name = world
@@ -319,11 +851,13 @@ impl Snapshot {
                     .map(|p| (p.x, p.y))
                     .unwrap_or((0, 0));
                 let health = e.get::<Health>().map(|hp| (hp.current, hp.max));
+                let suspicion = e.get::<CivilianIdentity>().map(|civ| civ.suspicion_meter);
                 Some(EntitySummary {
                     id,
                     name,
                     position,
                     health,
+                    suspicion,
                 })
             })
             .collect();
@@ -333,7 +867,46 @@ impl Snapshot {
             .map(|log| log.0.clone())
             .unwrap_or_default();
 
+        let pressure = world
+            .get_resource::<PressureState>()
+            .cloned()
+            .unwrap_or_default();
+        let cases = world
+            .get_resource::<CaseRegistry>()
+            .map(|registry| {
+                registry
+                    .cases
+                    .iter()
+                    .map(|case| CaseSummary {
+                        case_id: case.case_id,
+                        faction_id: case.faction_id.clone(),
+                        location_id: case.location_id.0,
+                        progress: case.progress,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let world_events = world
+            .get_resource::<WorldEventLog>()
+            .map(|log| log.0.clone())
+            .unwrap_or_default();
+        let personas = world
+            .get::<PersonaStack>(player)
+            .map(|stack| {
+                stack
+                    .personas
+                    .iter()
+                    .map(|persona| PersonaSummary {
+                        persona_id: persona.persona_id.clone(),
+                        label: persona.label.clone(),
+                        suspicion: persona.suspicion.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
         Snapshot {
+            tick,
             time_str,
             player_name,
             suspicion,
@@ -341,6 +914,618 @@ impl Snapshot {
             player_pos,
             combat_log,
             entities,
+            pressure,
+            cases,
+            world_events,
+            personas,
+        }
+    }
+
+    /// Changes since `prev`. Log lines are new when they come from a later
+    /// tick than `prev`, or sit past the end of `prev`'s log for the same tick.
+    pub fn diff(&self, prev: &Snapshot) -> SnapshotDiff {
+        let pressure = pressure_axes(&prev.pressure)
+            .into_iter()
+            .zip(pressure_axes(&self.pressure))
+            .filter(|((_, before), (_, after))| before != after)
+            .map(|((axis, before), (_, after))| PressureChange { axis, before, after })
+            .collect();
+        let new_cases = self
+            .cases
+            .iter()
+            .filter(|case| !prev.cases.iter().any(|old| old.case_id == case.case_id))
+            .cloned()
+            .collect();
+        let persona_suspicion = self
+            .personas
+            .iter()
+            .filter_map(|persona| {
+                let old = prev
+                    .personas
+                    .iter()
+                    .find(|old| old.persona_id == persona.persona_id)
+                    .map(|old| old.suspicion.clone())
+                    .unwrap_or_default();
+                let now = &persona.suspicion;
+                let delta = SuspicionDelta {
+                    public_suspicion: now.public_suspicion as i32 - old.public_suspicion as i32,
+                    civilian_suspicion: now.civilian_suspicion as i32
+                        - old.civilian_suspicion as i32,
+                    wanted_level: now.wanted_level as i32 - old.wanted_level as i32,
+                    exposure_risk: now.exposure_risk as i32 - old.exposure_risk as i32,
+                };
+                let unchanged = delta.public_suspicion == 0
+                    && delta.civilian_suspicion == 0
+                    && delta.wanted_level == 0
+                    && delta.exposure_risk == 0;
+                (!unchanged).then(|| PersonaSuspicionChange {
+                    persona_id: persona.persona_id.clone(),
+                    delta,
+                })
+            })
+            .collect();
+
+        let mut entities = Vec::new();
+        let mut defeated = Vec::new();
+        for entity in &self.entities {
+            let old = prev.entities.iter().find(|old| old.id == entity.id);
+            let suspicion_delta = entity.suspicion.unwrap_or(0) as i32
+                - old.and_then(|old| old.suspicion).unwrap_or(0) as i32;
+            let moved = old.map(|old| old.position) != Some(entity.position);
+            let hurt = old.map(|old| old.health) != Some(entity.health);
+            if !moved && !hurt && suspicion_delta == 0 {
+                continue;
+            }
+            let was_standing = old
+                .and_then(|old| old.health)
+                .is_none_or(|(current, _)| current > 0);
+            if was_standing && entity.health.is_some_and(|(current, _)| current <= 0) {
+                defeated.push(entity.id);
+            }
+            entities.push(EntityChange {
+                id: entity.id,
+                name: entity.name.clone(),
+                from: old.map(|old| old.position),
+                to: entity.position,
+                health: entity.health,
+                suspicion_delta,
+            });
+        }
+
+        SnapshotDiff {
+            pressure,
+            new_cases,
+            new_world_events: self.new_log_lines(prev, &prev.world_events, &self.world_events),
+            new_combat_log: self.new_log_lines(prev, &prev.combat_log, &self.combat_log),
+            player_suspicion_delta: self.suspicion as i32 - prev.suspicion as i32,
+            player_moved: (prev.player_pos != self.player_pos)
+                .then_some((prev.player_pos, self.player_pos)),
+            player_health: (prev.player_health != self.player_health)
+                .then_some((prev.player_health, self.player_health)),
+            persona_suspicion,
+            entities,
+            defeated,
+            combat: self.combat_transition(prev),
+        }
+    }
+
+    fn in_combat(&self) -> bool {
+        !self.combat_log.is_empty()
+    }
+
+    fn combat_transition(&self, prev: &Snapshot) -> Option<CombatTransition> {
+        match (prev.in_combat(), self.in_combat()) {
+            (false, true) => Some(CombatTransition::Started),
+            (true, false) => {
+                let reason = if self.player_health.0 <= 0 {
+                    CombatEndReason::PlayerDefeated
+                } else if self
+                    .entities
+                    .iter()
+                    .filter_map(|entity| entity.health)
+                    .filter(|(current, max)| current < max)
+                    .all(|(current, _)| current <= 0)
+                {
+                    CombatEndReason::OpponentsDefeated
+                } else {
+                    CombatEndReason::Disengaged
+                };
+                Some(CombatTransition::Ended { reason })
+            }
+            _ => None,
+        }
+    }
+
+    fn new_log_lines(&self, prev: &Snapshot, before: &[String], now: &[String]) -> Vec<String> {
+        if self.tick != prev.tick {
+            return now.to_vec();
+        }
+        now.get(before.len()..).unwrap_or_default().to_vec()
+    }
+}
+
+fn pressure_axes(pressure: &PressureState) -> [(&'static str, f32); 6] {
+    PressureAxis::ALL.map(|axis| (axis.key(), pressure.axis(axis)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_diff_reports_only_what_moved() {
+        let mut game = Game::new(7);
+        let before = game.snapshot();
+        assert!(serde_json::to_value(before.diff(&before))
+            .expect("diff serializes")
+            .is_object());
+        let quiet = before.diff(&before);
+        assert!(quiet.entities.is_empty() && quiet.pressure.is_empty());
+        assert!(quiet.player_moved.is_none());
+
+        let thug = before
+            .entities
+            .iter()
+            .find(|entity| entity.name.contains("Street Thug"))
+            .expect("roster spawns a thug")
+            .id;
+        let mut query = game.world.query::<(&EntityId, &mut Position)>();
+        for (id, mut pos) in query.iter_mut(&mut game.world) {
+            if id.0 == thug {
+                pos.x += 5;
+            }
+        }
+        game.world.resource_mut::<PressureState>().moral += 10.0;
+        let player = game.get_player_id();
+        let after = game.tick(vec![ActionIntent::Move {
+            entity_id: player,
+            dx: 1,
+            dy: 0,
+        }]);
+
+        let diff = after.diff(&before);
+        assert_eq!(diff.player_moved, Some(((0, 0), (1, 0))));
+        let moved = diff
+            .entities
+            .iter()
+            .find(|change| change.id == thug)
+            .expect("moved thug is reported");
+        assert_eq!(moved.from, Some((2, 0)));
+        assert_eq!(moved.to, (7, 0));
+        assert!(diff.pressure.iter().any(|change| change.axis == "moral"));
+        let encoded = serde_json::to_value(&diff).expect("diff serializes");
+        assert_eq!(encoded["player_moved"], serde_json::json!([[0, 0], [1, 0]]));
+    }
+
+    #[test]
+    fn snapshot_diff_reports_combat_transitions_and_repeated_log_lines() {
+        let mut game = Game::new(7);
+        let player = game.get_player_id();
+        let whiff = ActionIntent::Attack {
+            attacker_id: player,
+            target_id: Some(u32::MAX),
+        };
+        let calm = game.snapshot();
+        let first = game.tick(vec![whiff.clone()]);
+        let diff = first.diff(&calm);
+        assert_eq!(diff.combat, Some(CombatTransition::Started));
+        assert_eq!(diff.new_combat_log.len(), 1);
+
+        // The same line again is still news: it belongs to a later tick.
+        let second = game.tick(vec![whiff]);
+        assert_eq!(second.combat_log, first.combat_log);
+        let diff = second.diff(&first);
+        assert_eq!(diff.combat, None);
+        assert_eq!(diff.new_combat_log, second.combat_log);
+        assert!(game.snapshot().diff(&second).new_combat_log.is_empty());
+
+        let ended = game.tick(vec![ActionIntent::Wait]);
+        assert_eq!(
+            ended.diff(&second).combat,
+            Some(CombatTransition::Ended {
+                reason: CombatEndReason::OpponentsDefeated
+            })
+        );
+
+        let thug = calm
+            .entities
+            .iter()
+            .find(|entity| entity.name.contains("Street Thug"))
+            .expect("roster spawns a thug")
+            .id;
+        let hit = game.tick(vec![ActionIntent::Attack {
+            attacker_id: player,
+            target_id: Some(thug),
+        }]);
+        let walked_off = game.tick(vec![ActionIntent::Wait]);
+        let diff = walked_off.diff(&hit);
+        assert_eq!(
+            diff.combat,
+            Some(CombatTransition::Ended {
+                reason: CombatEndReason::Disengaged
+            })
+        );
+        let encoded = serde_json::to_value(&diff).expect("diff serializes");
+        assert_eq!(
+            encoded["combat"],
+            serde_json::json!({ "kind": "ended", "reason": "disengaged" })
+        );
+    }
+
+    #[test]
+    fn elderly_civilian_death_locks_game() {
+        let mut game = Game::new(7);
+        {
+            let mut civilian = game.world.resource_mut::<CivilianState>();
+            civilian.life.age_years = 95;
+            civilian.life.birth_day = 0;
+        }
+        for _ in 0..48 {
+            game.tick(vec![ActionIntent::Wait]);
+            if game.is_locked() {
+                break;
+            }
+        }
+        assert!(game.is_locked());
+        let record = game.final_legacy().expect("death should record a legacy");
+        assert_eq!(record.death_reason, "mortality");
+        assert_eq!(game.legacy().len(), 1);
+
+        let turn = game.world.resource::<GameTime>().tick;
+        game.tick(vec![ActionIntent::Wait]);
+        assert_eq!(game.world.resource::<GameTime>().tick, turn);
+
+        game.start_new_life();
+        assert!(!game.is_locked());
+        assert_eq!(game.legacy().len(), 1);
+    }
+
+    #[test]
+    fn death_lock_survives_save_and_load() {
+        let mut game = Game::new(7);
+        {
+            let mut civilian = game.world.resource_mut::<CivilianState>();
+            civilian.life.age_years = 95;
+            civilian.life.birth_day = 0;
+        }
+        for _ in 0..48 {
+            game.tick(vec![ActionIntent::Wait]);
+            if game.is_locked() {
+                break;
+            }
+        }
+        assert!(game.is_locked());
+
+        let mut reloaded = Game::new(7);
+        reloaded.load_state(game.save_state());
+        assert!(reloaded.is_locked());
+        assert_eq!(
+            reloaded.final_legacy().map(|record| record.death_reason.as_str()),
+            Some("mortality")
+        );
+        let turn = reloaded.world.resource::<GameTime>().tick;
+        reloaded.tick(vec![ActionIntent::Wait]);
+        assert_eq!(reloaded.world.resource::<GameTime>().tick, turn);
+
+        reloaded.start_new_life();
+        let mut fresh = Game::new(7);
+        fresh.load_state(reloaded.save_state());
+        assert!(!fresh.is_locked());
+    }
+
+    /// Jumping Jack Bolt: 2 stamina and a 2-turn cooldown.
+    const BOLT: &str = "a3eece895ae9d8ddae44ad80bc2e817cddcc721b";
+
+    /// A copy of the shipped content DB stamped so `SqlitePowerRepository::open`
+    /// accepts it.
+    fn stamped_content_db(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "collapsing_worlds_{}_{}.db",
+            name,
+            std::process::id()
+        ));
+        std::fs::copy(DEFAULT_CONTENT_DB_PATH, &path).expect("copy content db");
+        let conn = rusqlite::Connection::open(&path).expect("open content copy");
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS content_meta (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                schema_version INTEGER NOT NULL,
+                content_version TEXT NOT NULL
+            );
+            DELETE FROM content_meta;",
+        )
+        .expect("content_meta table");
+        conn.execute(
+            "INSERT INTO content_meta (id, schema_version, content_version) VALUES (1, ?1, ?2)",
+            rusqlite::params![
+                crate::content::schema::CONTENT_SCHEMA_VERSION,
+                crate::content::schema::CONTENT_VERSION
+            ],
+        )
+        .expect("content_meta row");
+        path
+    }
+
+    #[test]
+    fn a_new_life_starts_with_a_rested_actor() {
+        let content_db = stamped_content_db("game_new_life_content");
+        let options = GameOptions {
+            content_db: content_db.clone(),
+            ..GameOptions::default()
+        };
+        let mut game = Game::with_options(7, options).expect("game with content");
+        let used = game.use_expression(BOLT).expect("use the bolt");
+        assert_eq!(used.stamina, 8);
+
+        {
+            let mut civilian = game.world.resource_mut::<CivilianState>();
+            civilian.life.age_years = 95;
+            civilian.life.birth_day = 0;
+        }
+        for _ in 0..48 {
+            game.tick(vec![ActionIntent::Wait]);
+            if game.is_locked() {
+                break;
+            }
+        }
+        assert!(game.is_locked());
+
+        game.start_new_life();
+        assert_eq!(game.actor.stamina, 10);
+        assert_eq!(game.actor.focus, 10);
+        assert!(game.actor.cooldowns.is_empty());
+        let used = game
+            .use_expression(BOLT)
+            .expect("the new life can use the bolt");
+        assert_eq!(used.stamina, 8);
+        let _ = std::fs::remove_file(&content_db);
+    }
+
+    #[test]
+    fn a_content_db_that_fails_to_open_is_reported_on_use() {
+        let content_db = std::env::temp_dir()
+            .join(format!(
+                "collapsing_worlds_no_such_dir_{}",
+                std::process::id()
+            ))
+            .join("content.db");
+        let options = GameOptions {
+            content_db,
+            ..GameOptions::default()
+        };
+        let mut game = Game::with_options(7, options).expect("game without usable content");
+        for _ in 0..2 {
+            let err = game.use_expression(BOLT).expect_err("content never opened");
+            assert!(
+                matches!(&err, UseExpressionError::Repository(cause)
+                    if matches!(**cause, RepositoryError::Sqlite(_))),
+                "{:?}",
+                err
+            );
         }
     }
+
+    #[test]
+    fn pending_civilian_events_resolve_through_game() {
+        let catalog: crate::data::civilian_events::CivilianEventCatalog =
+            serde_json::from_value(serde_json::json!({
+                "schema_version": 1,
+                "events": [{
+                    "id": "sample_favor",
+                    "title": "Favor for {contact}",
+                    "text_stub": "{contact} needs a hand.",
+                    "choices": [
+                        { "id": "help", "text": "Help {contact}.", "effects": ["support:+5"] },
+                        { "id": "refuse", "text": "Refuse.", "effects": ["strain:+2"] }
+                    ]
+                }]
+            }))
+            .expect("sample catalog");
+        let mut game = Game::new(7);
+        game.world.insert_resource(CivilianEventLibrary(catalog.events));
+        {
+            let mut civilian = game.world.resource_mut::<CivilianState>();
+            civilian.pending_events = vec![crate::simulation::civilian::CivilianEvent {
+                storylet_id: "sample_favor".to_string(),
+                created_tick: 3,
+                contact_name: Some("Rosa".to_string()),
+            }];
+        }
+
+        let views = game.pending_civilian_events();
+        assert_eq!(views.len(), 1);
+        assert_eq!(views[0].title, "Favor for Rosa");
+        assert_eq!(views[0].choices[0].text, "Help Rosa.");
+
+        assert_eq!(
+            game.resolve_civilian_event("sample_favor", "shrug"),
+            Err(CivilianEventError::UnknownChoice {
+                event_id: "sample_favor".to_string(),
+                choice_id: "shrug".to_string(),
+            })
+        );
+        let support = game.world.resource::<CivilianState>().social.support;
+        let applied = game
+            .resolve_civilian_event("sample_favor", "help")
+            .expect("resolve pending event");
+        assert_eq!(applied, vec!["support +5".to_string()]);
+        assert_eq!(
+            game.world.resource::<CivilianState>().social.support,
+            (support + 5).min(100)
+        );
+        assert!(game.pending_civilian_events().is_empty());
+        assert_eq!(
+            game.resolve_civilian_event("sample_favor", "help"),
+            Err(CivilianEventError::NotPending("sample_favor".to_string()))
+        );
+    }
+
+    #[test]
+    fn game_options_set_the_clock_cadence() {
+        let options = GameOptions {
+            time: TimeConfig::daily(),
+            ..GameOptions::default()
+        };
+        let mut game = Game::with_options(3, options).expect("daily game");
+        let day = game.world.resource::<GameTime>().day;
+        game.tick(vec![ActionIntent::Wait]);
+        let time = game.world.resource::<GameTime>();
+        assert_eq!(time.config, TimeConfig::daily());
+        assert_eq!(time.day, day + 1);
+    }
+
+    #[test]
+    fn taking_the_quick_hit_through_game_rolls_the_crime() {
+        let mut game = Game::new(7);
+        {
+            let mut civilian = game.world.resource_mut::<CivilianState>();
+            civilian.pending_events = vec![crate::simulation::civilian::CivilianEvent {
+                storylet_id: crate::simulation::civilian::CRIME_QUICK_HIT_EVENT.to_string(),
+                created_tick: 0,
+                contact_name: None,
+            }];
+        }
+        let wanted_before = game
+            .world
+            .get::<PersonaStack>(game.player)
+            .and_then(|stack| stack.active_persona())
+            .map(|persona| persona.suspicion.wanted_level)
+            .unwrap_or(0);
+
+        let applied = game
+            .resolve_civilian_event(crate::simulation::civilian::CRIME_QUICK_HIT_EVENT, "take")
+            .expect("resolve quick hit");
+        assert!(
+            applied
+                .iter()
+                .any(|line| line.ends_with("(quick hit)") || line.ends_with("(arrest fine)")),
+            "{:?}",
+            applied
+        );
+        let wanted_after = game
+            .world
+            .get::<PersonaStack>(game.player)
+            .and_then(|stack| stack.active_persona())
+            .map(|persona| persona.suspicion.wanted_level)
+            .unwrap_or(0);
+        assert!(wanted_after > wanted_before);
+    }
+
+    #[test]
+    fn scenario_faction_is_seeded_and_resolves_events() {
+        let scenario: ScenarioSeed = serde_json::from_value(serde_json::json!({
+            "faction_types": [{
+                "id": "cult_cell",
+                "name": "Cult Cell",
+                "domain": "CRIMINAL",
+                "default_jurisdiction": "LOCAL",
+                "detection": { "heat_min": 5 },
+                "response": {
+                    "thresholds": [{
+                        "heat": 5,
+                        "level": "STIR",
+                        "actions": [{ "kind": "PROXY_CRIME" }]
+                    }]
+                }
+            }],
+            "factions": [{
+                "id": "crimson_choir",
+                "type_id": "cult_cell",
+                "jurisdiction": "LOCAL",
+                "scope": { "location_ids": [2] },
+                "influence": [{ "location_id": 2, "influence": 65 }]
+            }],
+            "replace_factions": true
+        }))
+        .expect("scenario");
+        let options = GameOptions {
+            scenario,
+            ..GameOptions::default()
+        };
+        let mut game = Game::with_options(7, options).expect("scenario game");
+
+        let world = &mut game.world;
+        let mut city = world.remove_resource::<CityState>().expect("city");
+        let location = city.locations.get_mut(&LocationId(2)).expect("location 2");
+        assert_eq!(location.faction_influence.get("crimson_choir"), Some(&65));
+        location.heat = 20;
+        let mut director = world.remove_resource::<FactionDirector>().expect("director");
+        let evidence = crate::simulation::evidence::WorldEvidence::default();
+        let mut events = crate::systems::faction::FactionEventLog::default();
+        crate::systems::faction::run_faction_director(&mut director, &city, &evidence, &mut events);
+        let mut resolved = crate::systems::event_resolver::ResolvedFactionEventLog::default();
+        let mut cases = crate::simulation::case::CaseRegistry::default();
+        let mut case_log = crate::simulation::case::CaseEventLog::default();
+        crate::systems::event_resolver::resolve_faction_events(
+            &mut events,
+            &mut resolved,
+            &mut city,
+            &evidence,
+            &mut cases,
+            &mut case_log,
+            None,
+        );
+        assert_eq!(resolved.0.len(), 1);
+        assert_eq!(resolved.0[0].faction_id, "crimson_choir");
+        assert_eq!(resolved.0[0].location_id, LocationId(2));
+
+        let bad: ScenarioSeed = serde_json::from_value(serde_json::json!({
+            "factions": [{
+                "id": "lost",
+                "type_id": "cult_cell",
+                "jurisdiction": "LOCAL",
+                "influence": [{ "location_id": 99, "influence": 10 }]
+            }]
+        }))
+        .expect("scenario");
+        let options = GameOptions {
+            scenario: bad,
+            ..GameOptions::default()
+        };
+        assert!(matches!(
+            Game::with_options(7, options),
+            Err(GameSetupError::UnknownLocation { location_id: 99, .. })
+        ));
+    }
+
+    #[test]
+    fn starting_personas_follow_the_chosen_alignment() {
+        use crate::components::persona::PersonaType;
+
+        let mut ids = std::collections::HashSet::new();
+        for alignment in [Alignment::Hero, Alignment::Vigilante, Alignment::Villain] {
+            let stack = PersonaStack::for_alignment(alignment);
+            let types: Vec<PersonaType> = stack
+                .personas
+                .iter()
+                .map(|persona| persona.persona_type)
+                .collect();
+            assert_eq!(types, vec![PersonaType::Civilian, PersonaType::Masked]);
+            let masked = stack
+                .personas
+                .iter()
+                .find(|persona| persona.persona_type == PersonaType::Masked)
+                .expect("masked persona");
+            assert!(ids.insert(masked.persona_id.clone()));
+            assert_eq!(
+                stack.active_persona().map(|persona| persona.persona_type),
+                Some(PersonaType::Civilian)
+            );
+        }
+        let neutral = PersonaStack::for_alignment(Alignment::Neutral);
+        assert_eq!(neutral.personas.len(), 1);
+        assert_eq!(neutral.personas[0].persona_type, PersonaType::Civilian);
+
+        let options = GameOptions {
+            alignment: Alignment::Villain,
+            ..GameOptions::default()
+        };
+        let game = Game::with_options(7, options).expect("villain game");
+        let stack = game.world.get::<PersonaStack>(game.player).expect("stack");
+        assert_eq!(stack.active_persona_id, "cover");
+        assert!(stack
+            .personas
+            .iter()
+            .any(|persona| persona.persona_id == "villain" && persona.label == "Villain"));
+    }
 }
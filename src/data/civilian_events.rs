@@ -1,9 +1,11 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
+pub const DEFAULT_CIVILIAN_EVENT_CATALOG: &str = "./assets/data/civilian_events.json";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CivilianEventCatalog {
     pub schema_version: u32,
@@ -70,6 +72,61 @@ pub fn load_civilian_event_catalog(
     Ok(catalog)
 }
 
+/// An event id defined by more than one catalog; the later catalog wins.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CivilianEventCollision {
+    pub event_id: String,
+    pub replaced_from: String,
+    pub replaced_by: String,
+}
+
+/// Civilian events merged from several catalogs.
+#[derive(Debug, Clone, Default)]
+pub struct MergedCivilianEvents {
+    pub events: Vec<CivilianStorylet>,
+    pub collisions: Vec<CivilianEventCollision>,
+}
+
+/// Merges catalogs in order. An event whose id is already present replaces the
+/// earlier definition in place, so expansions can override base events.
+pub fn merge_civilian_event_catalogs(
+    catalogs: impl IntoIterator<Item = (String, CivilianEventCatalog)>,
+) -> MergedCivilianEvents {
+    let mut merged = MergedCivilianEvents::default();
+    let mut sources: HashMap<String, (usize, String)> = HashMap::new();
+    for (source, catalog) in catalogs {
+        for event in catalog.events {
+            match sources.get_mut(&event.id) {
+                Some((index, previous)) => {
+                    merged.collisions.push(CivilianEventCollision {
+                        event_id: event.id.clone(),
+                        replaced_from: std::mem::replace(previous, source.clone()),
+                        replaced_by: source.clone(),
+                    });
+                    merged.events[*index] = event;
+                }
+                None => {
+                    sources.insert(event.id.clone(), (merged.events.len(), source.clone()));
+                    merged.events.push(event);
+                }
+            }
+        }
+    }
+    merged
+}
+
+/// Loads and merges catalogs in order; see `merge_civilian_event_catalogs`.
+pub fn load_civilian_event_catalogs<P: AsRef<Path>>(
+    paths: &[P],
+) -> Result<MergedCivilianEvents, CivilianEventDataError> {
+    let mut catalogs = Vec::with_capacity(paths.len());
+    for path in paths {
+        let catalog = load_civilian_event_catalog(path)?;
+        catalogs.push((path.as_ref().display().to_string(), catalog));
+    }
+    Ok(merge_civilian_event_catalogs(catalogs))
+}
+
 impl CivilianEventCatalog {
     pub fn validate(&self) -> Result<(), CivilianEventDataError> {
         let mut ids = HashSet::new();
@@ -107,3 +164,50 @@ impl CivilianEventCatalog {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: &str, title: &str) -> CivilianStorylet {
+        CivilianStorylet {
+            id: id.to_string(),
+            title: title.to_string(),
+            text_stub: "Something happens.".to_string(),
+            details: Vec::new(),
+            choices: vec![CivilianChoice {
+                id: "ok".to_string(),
+                text: "Ok.".to_string(),
+                effects: Vec::new(),
+            }],
+            effects: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn later_catalogs_override_events_by_id_and_report_collisions() {
+        let base = CivilianEventCatalog {
+            schema_version: 1,
+            events: vec![event("rent_due", "Rent Due"), event("gym_day", "Gym Day")],
+        };
+        let expansion = CivilianEventCatalog {
+            schema_version: 1,
+            events: vec![event("gym_day", "Gym Day (Remix)"), event("art_show", "Art Show")],
+        };
+        let merged = merge_civilian_event_catalogs(vec![
+            ("base.json".to_string(), base),
+            ("expansion.json".to_string(), expansion),
+        ]);
+
+        let titles: Vec<&str> = merged.events.iter().map(|event| event.title.as_str()).collect();
+        assert_eq!(titles, vec!["Rent Due", "Gym Day (Remix)", "Art Show"]);
+        assert_eq!(
+            merged.collisions,
+            vec![CivilianEventCollision {
+                event_id: "gym_day".to_string(),
+                replaced_from: "base.json".to_string(),
+                replaced_by: "expansion.json".to_string(),
+            }]
+        );
+    }
+}
@@ -30,6 +30,8 @@ pub struct NemesisActionDefinition {
     #[serde(default)]
     pub case_progress_delta: i32,
     #[serde(default)]
+    pub min_resentment: i32,
+    #[serde(default)]
     pub notes: Option<String>,
 }
 
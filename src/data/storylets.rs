@@ -4,6 +4,8 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
+use crate::simulation::combat::CombatScale;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoryletCatalog {
     pub schema_version: u32,
@@ -22,6 +24,10 @@ pub struct Storylet {
     pub effects: Vec<String>,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Relative odds among the storylets eligible on the same turn; unset
+    /// counts as 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +38,58 @@ pub struct StoryletChoice {
     pub effects: Vec<String>,
 }
 
+/// Effects that hand a storylet off to another system instead of adjusting stats:
+/// `queue_civilian:<event_id>` and `start_combat:<scale>:<opponents>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoryletHandoff {
+    QueueCivilian(String),
+    StartCombat { scale: CombatScale, opponents: u32 },
+}
+
+impl StoryletHandoff {
+    /// Returns None for effects outside the handoff verbs, and an error for a
+    /// handoff verb with malformed arguments.
+    pub fn parse(effect: &str) -> Option<Result<Self, String>> {
+        let mut parts = effect.trim().split(':');
+        match parts.next()?.trim() {
+            "queue_civilian" => Some(
+                parts
+                    .next()
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(|id| StoryletHandoff::QueueCivilian(id.to_string()))
+                    .ok_or_else(|| format!("{} is missing an event id", effect)),
+            ),
+            "start_combat" => {
+                let scale = parts.next().and_then(CombatScale::parse);
+                let opponents = parts.next().and_then(|raw| raw.trim().parse::<u32>().ok());
+                Some(match (scale, opponents) {
+                    (Some(scale), Some(opponents)) if opponents > 0 => {
+                        Ok(StoryletHandoff::StartCombat { scale, opponents })
+                    }
+                    _ => Err(format!("{} expects start_combat:<scale>:<opponents>", effect)),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Storylet {
+    pub fn selection_weight(&self) -> u32 {
+        self.weight.unwrap_or(1)
+    }
+
+    /// Handoffs attached to the storylet itself, which fire as soon as it triggers.
+    pub fn handoffs(&self) -> Vec<StoryletHandoff> {
+        self.effects
+            .iter()
+            .filter_map(|effect| StoryletHandoff::parse(effect))
+            .filter_map(Result::ok)
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum StoryletCategory {
@@ -106,7 +164,93 @@ impl StoryletCatalog {
                     storylet.id
                 )));
             }
+            if storylet.weight == Some(0) {
+                return Err(StoryletDataError::Validation(format!(
+                    "storylet {} weight must be positive",
+                    storylet.id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks handoff effects, resolving queued civilian events against the
+    /// loaded civilian event catalog.
+    pub fn validate_handoffs(
+        &self,
+        civilian_event_ids: &HashSet<String>,
+    ) -> Result<(), StoryletDataError> {
+        for storylet in &self.storylets {
+            let effects = storylet
+                .effects
+                .iter()
+                .chain(storylet.choices.iter().flat_map(|choice| choice.effects.iter()));
+            for effect in effects {
+                match StoryletHandoff::parse(effect) {
+                    Some(Ok(StoryletHandoff::QueueCivilian(id))) => {
+                        if !civilian_event_ids.contains(&id) {
+                            return Err(StoryletDataError::Validation(format!(
+                                "storylet {} queues unknown civilian event {}",
+                                storylet.id, id
+                            )));
+                        }
+                    }
+                    Some(Err(message)) => {
+                        return Err(StoryletDataError::Validation(format!(
+                            "storylet {}: {}",
+                            storylet.id, message
+                        )));
+                    }
+                    Some(Ok(StoryletHandoff::StartCombat { .. })) | None => {}
+                }
+            }
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storylet_with_effects(effects: &[&str]) -> StoryletCatalog {
+        StoryletCatalog {
+            schema_version: 1,
+            storylets: vec![Storylet {
+                id: "test_handoff".to_string(),
+                category: StoryletCategory::MaskedLife,
+                preconditions: Vec::new(),
+                text_stub: "Something stirs.".to_string(),
+                choices: vec![StoryletChoice {
+                    id: "go".to_string(),
+                    text: "Go.".to_string(),
+                    effects: effects.iter().map(|effect| effect.to_string()).collect(),
+                }],
+                effects: Vec::new(),
+                tags: Vec::new(),
+                weight: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn handoff_effects_parse_and_validate_against_catalog() {
+        assert_eq!(
+            StoryletHandoff::parse("start_combat:district:3"),
+            Some(Ok(StoryletHandoff::StartCombat {
+                scale: CombatScale::District,
+                opponents: 3
+            }))
+        );
+        assert!(matches!(StoryletHandoff::parse("start_combat:alley:3"), Some(Err(_))));
+        assert_eq!(StoryletHandoff::parse("heat +1"), None);
+
+        let known: HashSet<String> = ["civilian_rent_due".to_string()].into_iter().collect();
+        assert!(storylet_with_effects(&["queue_civilian:civilian_rent_due"])
+            .validate_handoffs(&known)
+            .is_ok());
+        assert!(storylet_with_effects(&["queue_civilian:no_such_event"])
+            .validate_handoffs(&known)
+            .is_err());
+    }
+}
@@ -5,6 +5,7 @@ pub mod core;
 pub mod data;
 pub mod narrative;
 pub mod rules;
+pub mod scripting;
 pub mod simulation;
 pub mod systems;
 pub mod ui;
@@ -12,4 +13,4 @@ pub mod world;
 
 // Expose the main Game wrapper and types needed for interaction
 pub use crate::core::serialization::SaveState;
-pub use crate::core::world::{ActionIntent, EntitySummary, Game, Snapshot};
+pub use crate::core::world::{ActionIntent, EntitySummary, Game, GameOptions, GameSetupError, Snapshot};
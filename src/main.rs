@@ -1,109 +1,43 @@
-use std::collections::HashSet;
 use std::env;
 use std::io::{self, Write};
-use std::path::PathBuf;
-
-use superhero_universe::components::persona::{
-    hero_persona_stack, neutral_persona_stack, vigilante_persona_stack, villain_persona_stack,
-    Alignment, PersonaStack, PersonaType,
-};
-use superhero_universe::components::world::Position;
-use superhero_universe::content::{ExpressionId, PowerId, PowerRepository, SqlitePowerRepository};
-use superhero_universe::core::world::ActionIntent;
-use superhero_universe::data::alien_generation::load_alien_generation_catalog;
-use superhero_universe::data::cosmic_constants::{load_cosmic_constants, CosmicConstantsCatalog};
-use superhero_universe::data::civilian_events::{load_civilian_event_catalog, CivilianStorylet};
-use superhero_universe::data::endgame_events::{load_endgame_event_catalog, EndgameEvent};
-use superhero_universe::data::global_events::{load_global_event_catalog, GlobalEventDefinition};
-use superhero_universe::data::nemesis::load_nemesis_action_catalog;
-use superhero_universe::data::omni_powers::{load_omni_powers, OmniPowerCatalog};
-use superhero_universe::data::storylets::{load_storylet_catalog, Storylet};
-use superhero_universe::rules::{
-    can_use, use_power, ActorState, CostType, PressureModifiers, TargetContext, UseContext,
-    WorldState,
-};
-use superhero_universe::simulation::agents::{
-    tick_agents, AgentEvent, AgentEventLog, AgentRegistry,
-};
-use superhero_universe::simulation::alien::{format_alien_profile, generate_alien_species};
-use superhero_universe::simulation::cosmic::OmniPowerRegistry;
-use superhero_universe::simulation::case::{CaseEventLog, CaseRegistry};
-use superhero_universe::simulation::city::{CityEventLog, CityState, LocationTag};
-use superhero_universe::simulation::civilian::{
-    apply_civilian_effects, tick_civilian_economy, tick_civilian_life, AutoChoicePreferences,
-    CivilianEvent, CivilianEventCategory, CivilianEventSettings, CivilianState, ContactDomain,
-    RelationType,
-};
-use superhero_universe::simulation::combat::{
-    CombatConsequences, CombatEnd, CombatIntent, CombatPressureDelta, CombatScale, CombatState,
-};
-use superhero_universe::simulation::cast::{
-    current_year_from_day, tick_cast_aging, PersistentCharacter, PromotionCandidate,
-    PromotionReason,
-};
-use superhero_universe::simulation::endgame::{
-    apply_transformation_event, evaluate_transformation, EndgameState, TransformationState,
-};
-use superhero_universe::simulation::economy::WealthTier;
-use superhero_universe::simulation::evidence::WorldEvidence;
-use superhero_universe::simulation::growth::{
-    record_expression_use, select_evolution_candidate, GrowthState,
-};
-use superhero_universe::simulation::identity_evidence::{
-    combat_consequence_modifiers, IdentityEvidenceModifiers, IdentityEvidenceStore, PersonaHint,
-};
-use superhero_universe::simulation::nemesis::NemesisState;
-use superhero_universe::simulation::power_assignment::{
-    assign_alien_powers, assign_mutant_powers, classify_mutant_tier, roll_mutant_lineage,
-    seed_mutant_inheritance, MutantInheritanceProfile, PowerAssignmentConfig, PowerAssignmentResult,
-};
-use superhero_universe::simulation::origin::{
-    apply_origin_effects, current_origin_stage, load_origin_catalog, load_origin_path_catalog,
-    parse_origin_effects, register_origin_event, select_origin_paths, start_origin_path,
-    tick_origin_path, OriginPathCatalog, OriginPathDefinition, OriginQuestState, OriginStageReward,
-};
-use superhero_universe::simulation::pressure::PressureState;
-use superhero_universe::simulation::region::{
-    tick_global_events, GlobalEventInstance, GlobalEventLog, GlobalEventState, RegionEventLog,
-    RegionState,
-};
-use superhero_universe::simulation::storylet_state::StoryletState;
-use superhero_universe::simulation::storylets::{
-    is_punctuation_storylet, storylet_has_gate_requirements, StoryletLibrary,
-};
-use superhero_universe::simulation::time::GameTime;
-use superhero_universe::systems::case::update_cases;
-use superhero_universe::systems::civilian::apply_civilian_pressure;
-use superhero_universe::systems::combat_loop::{
-    combat_post_consequences, combat_tick, force_escalate, force_escape, resolve_combat,
-    start_combat,
-};
-use superhero_universe::systems::event_resolver::{
-    resolve_faction_events, ResolvedFactionEventLog,
-};
-use superhero_universe::systems::faction::{
-    run_faction_director, FactionDirector, FactionEventLog,
+use std::path::{Path, PathBuf};
+
+use superhero_universe::content::SqlitePowerRepository;
+use superhero_universe::core::world::{Game, GameOptions};
+use superhero_universe::data::civilian_events::DEFAULT_CIVILIAN_EVENT_CATALOG;
+use superhero_universe::scripting::{
+    clock_seed, print_civilian_event_collisions, print_save_diagnostics, CommandFlow, Session,
+    COMMAND_HELP,
 };
-use superhero_universe::systems::heat::{
-    apply_combat_consequence_heat, apply_signatures, decay_heat, WorldEventLog,
-};
-use superhero_universe::systems::persona::{attempt_switch, PersonaSwitchError};
-use superhero_universe::systems::pressure::update_pressure;
-use superhero_universe::systems::region::{
-    run_global_faction_director, run_region_update, GlobalFactionDirector, GlobalFactionEventLog,
-};
-use superhero_universe::systems::suspicion::apply_suspicion_for_intents;
-use superhero_universe::systems::units::update_units;
-use superhero_universe::ui::authoring::render_authoring_dashboard;
+use superhero_universe::ui::json::run_json_session;
 use superhero_universe::world::{WorldDb, WorldDbState, WorldRepository};
 
-const DEFAULT_PUNCTUATION_TURNS: i32 = 2;
-const DEFAULT_PUNCTUATION_COOLDOWN_TURNS: i32 = 3;
-const MAX_FAST_FORWARD_TICKS: u32 = 5000;
-
 fn main() {
+    let args: Vec<String> = env::args().collect();
+    let requested_seed = parse_seed(&args);
+    let civilian_event_paths = parse_civilian_event_paths(&args);
+    if args.iter().any(|arg| arg == "--json") {
+        let content_db = parse_paths(args.clone()).content_db;
+        run_json_mode(
+            requested_seed.unwrap_or_else(clock_seed),
+            civilian_event_paths,
+            content_db,
+        );
+        return;
+    }
     println!("Initializing Superhero Universe (Rules Debug)...");
-    let (content_db_path, world_db_path) = parse_paths(env::args().collect());
+    let CliPaths {
+        content_db: content_db_path,
+        world_db: world_db_path,
+        script: script_path,
+        export_json,
+        import_json,
+        diagnose,
+    } = parse_paths(args);
+    if diagnose {
+        diagnose_world_db(&world_db_path);
+        return;
+    }
     if !content_db_path.exists() {
         eprintln!(
             "DB not found at {}. Use --db <path> to point at a valid SQLite file.",
@@ -127,144 +61,46 @@ fn main() {
             std::process::exit(1);
         }
     };
-    let world_state = match world_repo.load_or_init() {
-        Ok(state) => state,
-        Err(err) => {
-            eprintln!("Failed to load world state: {}", err);
-            WorldDbState::default()
-        }
-    };
-    let mut characters = match world_repo.load_characters() {
-        Ok(list) => list,
-        Err(err) => {
-            eprintln!("Failed to load characters: {}", err);
-            Vec::new()
-        }
-    };
-
-    print_stats(&repo);
-
-    let mut actor = ActorState {
-        stamina: 10,
-        focus: 10,
-        ..Default::default()
-    };
-    let mut evidence = WorldEvidence::default();
-    let mut identity_evidence = IdentityEvidenceStore::default();
-    let WorldDbState {
-        world_turn,
-        game_time,
-        city,
-        cases,
-        combat,
-        growth,
-        storylet_state,
-        persona_stack,
-        alignment,
-        civilian_state,
-        omni_registry,
-    } = world_state;
-
-    let mut world = WorldState {
-        turn: world_turn,
-        pressure: PressureModifiers::default(),
-    };
-    let mut game_time = game_time;
-    let mut last_cast_aging_year = current_year_from_day(game_time.day);
-    let mut city = city;
-    let mut city_events = CityEventLog::default();
-    let mut event_log = WorldEventLog::default();
-    let mut region = RegionState::default();
-    let mut region_events = RegionEventLog::default();
-    let mut global_event_state = GlobalEventState::default();
-    let mut global_event_log = GlobalEventLog::default();
-    let mut nemesis_state = NemesisState::default();
-    let mut persona_stack = persona_stack;
-    let mut alignment = alignment;
-    let mut storylet_state = storylet_state;
-    let mut growth = growth;
-    let storylets = load_storylet_library();
-    let cosmic_constants = match load_cosmic_constants("./assets/data/cosmic_constants.json") {
-        Ok(catalog) => Some(catalog),
-        Err(err) => {
-            eprintln!("Failed to load cosmic constants: {}", err);
-            None
-        }
-    };
-    let omni_catalog = match load_omni_powers("./assets/data/omni_powers.json") {
-        Ok(catalog) => catalog,
-        Err(err) => {
-            eprintln!("Failed to load omni powers: {}", err);
-            superhero_universe::data::omni_powers::OmniPowerCatalog {
-                schema_version: 1,
-                powers: Vec::new(),
-            }
-        }
-    };
-    let mut omni_registry = omni_registry;
-    let power_assignment_config = PowerAssignmentConfig::default();
-    let origin_paths = match load_origin_path_catalog("./assets/data/origin_paths.json") {
-        Ok(catalog) => catalog,
-        Err(err) => {
-            eprintln!("Failed to load origin paths: {}", err);
-            OriginPathCatalog::default()
-        }
-    };
-    let mut origin_quest = OriginQuestState::default();
-    let mut player_pos = Position { x: 0, y: 0 };
-    let mut pressure = PressureState::default();
-    let mut faction_director = match FactionDirector::load_default() {
-        Ok(director) => director,
-        Err(err) => {
-            eprintln!("Failed to load faction data: {}", err);
-            FactionDirector::default()
+    if let Some(import_path) = import_json {
+        if let Err(err) = import_world_json(world_repo.as_mut(), &import_path) {
+            eprintln!("Failed to import {}: {}", import_path.display(), err);
+            std::process::exit(1);
         }
-    };
-    let mut faction_events = FactionEventLog::default();
-    let mut resolved_faction_events = ResolvedFactionEventLog::default();
-    let mut global_faction_director = GlobalFactionDirector::load_default();
-    let mut global_faction_events = GlobalFactionEventLog::default();
-    let mut cases = cases;
-    let mut case_log = CaseEventLog::default();
-    let mut agents = match AgentRegistry::load_default() {
-        Ok(registry) => registry,
-        Err(err) => {
-            eprintln!("Failed to load agent data: {}", err);
-            AgentRegistry::default()
+        println!("Imported world state from {}.", import_path.display());
+    }
+    if let Some(export_path) = export_json {
+        if let Err(err) = export_world_json(world_repo.as_mut(), &export_path) {
+            eprintln!("Failed to export {}: {}", export_path.display(), err);
+            std::process::exit(1);
         }
-    };
-    let mut agent_events = AgentEventLog::default();
-    let mut combat = combat;
-    let mut endgame_state = EndgameState::default();
-    let mut target = TargetContext {
-        distance_m: Some(10),
-        has_line_of_sight: true,
-        has_contact: false,
-        in_public: true,
-        witnesses: 0,
-    };
-    let mut civilian_state = civilian_state;
-    update_pressure(&mut pressure, &city, &evidence, &cases, &game_time);
-    apply_civilian_pressure(&civilian_state, &mut pressure);
-    apply_pressure_modifiers(&mut world, &pressure, &endgame_state);
-    run_region_update(
-        &mut region,
-        &city,
-        &pressure,
-        &mut city_events,
-        &mut region_events,
-    );
-    run_global_faction_director(
-        &mut global_faction_director,
-        &region,
-        &mut global_faction_events,
+        println!("Exported world state to {}.", export_path.display());
+        return;
+    }
+    let mut session = Session::open(
+        repo,
+        world_repo,
+        &world_db_path,
+        requested_seed,
+        &civilian_event_paths,
     );
+    print_lines(session.take_output());
 
-    let civilian_events = load_civilian_event_library();
-    let endgame_events = load_endgame_event_library();
-    let global_events = load_global_event_library();
+    if let Some(script_path) = script_path {
+        let script = match std::fs::read_to_string(&script_path) {
+            Ok(script) => script,
+            Err(err) => {
+                eprintln!("Failed to read script {}: {}", script_path.display(), err);
+                std::process::exit(1);
+            }
+        };
+        let commands: Vec<&str> = script.lines().collect();
+        print_lines(session.run_script(&commands));
+        session.persist();
+        print_lines(session.take_output());
+        return;
+    }
 
-    println!("Commands: stats | power <id> | use <expression_id> | ctx | loc | persona | personas | alignment [status|choose <hero|vigilante|villain>] | alterego <set <name>> | life <new> | cast | promote <first> <last> [role] | growth [expr|unlock|mastery] | switch <persona_id> | storylets [all] | punctuation <on|off|turns> | author | civilian [events [detail]|detail <event_id>|resolve <event_id> <choice_id>|prefs ...|profile <balanced|vigilante|corporate>] | global [events [detail]|detail <event_id>|resolve <event_id> <choice_id>] | origin [paths|choose|status|event|tick] | alien [generate|detail|powers] [seed] | cosmic [constants|omni] | set <field> <value> | cd | scene | events | cases | combat <start|use|intent|tick|log|resolve|force_escape|force_escalate> | tick [n|next|rent|crisis|skip [days]] | quit");
+    println!("{}", COMMAND_HELP);
     loop {
         print!("> ");
         io::stdout().flush().unwrap();
@@ -273,1646 +109,106 @@ fn main() {
         if io::stdin().read_line(&mut input).is_err() {
             break;
         }
-        let trimmed = input.trim();
-        if trimmed.is_empty() {
-            continue;
+        let flow = session.dispatch(&input);
+        print_lines(session.take_output());
+        if flow == CommandFlow::Quit {
+            break;
         }
+    }
 
-        let mut parts = trimmed.split_whitespace();
-        let cmd = parts.next().unwrap_or("").to_lowercase();
-
-        match cmd.as_str() {
-            "quit" | "exit" => break,
-            "help" => {
-                println!("Commands: stats | power <id> | use <expression_id> | ctx | loc | persona | personas | alignment [status|choose <hero|vigilante|villain>] | alterego <set <name>> | life <new> | cast | promote <first> <last> [role] | growth [expr|unlock|mastery] | switch <persona_id> | storylets [all] | punctuation <on|off|turns> | author | civilian [events [detail]|detail <event_id>|resolve <event_id> <choice_id>|prefs ...|profile <balanced|vigilante|corporate>] | global [events [detail]|detail <event_id>|resolve <event_id> <choice_id>] | origin [paths|choose|status|event|tick] | alien [generate|detail|powers] [seed] | cosmic [constants|omni] | set <field> <value> | cd | scene | events | cases | combat <start|use|intent|tick|log|resolve|force_escape|force_escalate> | tick [n|next|rent|crisis|skip [days]] | quit");
-            }
-            "stats" => {
-                print_stats(&repo);
-            }
-            "power" | "list" => {
-                if let Some(id_raw) = parts.next() {
-                    match id_raw.parse::<i64>() {
-                        Ok(power_id) => {
-                            print_power(&repo, power_id);
-                        }
-                        Err(_) => println!("Invalid power_id: {}", id_raw),
-                    }
-                } else {
-                    println!("Usage: power <power_id>");
-                }
-            }
-            "ctx" => {
-                print_context(&target, &world, &city, &pressure, &endgame_state);
-            }
-            "loc" => {
-                print_location(&city);
-            }
-            "persona" => {
-                print_persona_state(&persona_stack, alignment, &city, &cases);
-            }
-            "personas" => {
-                print_persona_stack(&persona_stack, world.turn);
-            }
-            "alignment" => {
-                let sub = parts.next().unwrap_or("").to_lowercase();
-                match sub.as_str() {
-                    "" | "status" => {
-                        print_alignment_status(
-                            alignment,
-                            &origin_quest,
-                            &growth,
-                            &civilian_state,
-                        );
-                    }
-                    "choose" => {
-                        let Some(choice_raw) = parts.next() else {
-                            println!("Usage: alignment choose <hero|vigilante|villain>");
-                            continue;
-                        };
-                        if alignment != Alignment::Neutral {
-                            println!("Alignment already chosen: {:?}", alignment);
-                            continue;
-                        }
-                        let Some(choice) = parse_alignment(choice_raw) else {
-                            println!("Unknown alignment: {}", choice_raw);
-                            continue;
-                        };
-                        if choice == Alignment::Neutral {
-                            println!("Choose hero, vigilante, or villain.");
-                            continue;
-                        }
-                        let status =
-                            alignment_unlock_status(&origin_quest, &growth, &civilian_state);
-                        if !status.unlocked {
-                            println!(
-                                "Alignment choice locked: power_unlocked={} wealth_unlocked={}",
-                                status.has_power, status.has_wealth
-                            );
-                            continue;
-                        }
-                        apply_alignment_choice(&mut alignment, &mut persona_stack, choice);
-                        storylet_state.flags.remove("alignment_choice_unlocked");
-                        storylet_state
-                            .flags
-                            .insert("alignment_chosen".to_string(), true);
-                        println!("Alignment set to {:?}.", alignment);
-                        print_persona_state(&persona_stack, alignment, &city, &cases);
-                    }
-                    _ => {
-                        println!("Usage: alignment [status|choose <hero|vigilante|villain>]");
-                    }
-                }
-            }
-            "alterego" => {
-                let sub = parts.next().unwrap_or("").to_lowercase();
-                match sub.as_str() {
-                    "set" => {
-                        let Some(name) = parts.next() else {
-                            println!("Usage: alterego set <name>");
-                            continue;
-                        };
-                        if alignment == Alignment::Neutral {
-                            println!("Alignment is neutral. Choose alignment first.");
-                            continue;
-                        }
-                        if set_masked_label(&mut persona_stack, name) {
-                            println!("Alter ego set to {}.", name);
-                        } else {
-                            println!("No masked persona available.");
-                        }
-                    }
-                    _ => {
-                        println!("Usage: alterego set <name>");
-                    }
-                }
-            }
-            "life" => {
-                let sub = parts.next().unwrap_or("").to_lowercase();
-                match sub.as_str() {
-                    "new" => {
-                        start_new_life(
-                            &mut actor,
-                            &mut growth,
-                            &mut origin_quest,
-                            &mut persona_stack,
-                            &mut alignment,
-                            &mut civilian_state,
-                            &mut player_pos,
-                            &mut target,
-                            &mut storylet_state,
-                            &game_time,
-                            &repo,
-                            world.turn,
-                        );
-                        update_pressure(&mut pressure, &city, &evidence, &cases, &game_time);
-                        apply_civilian_pressure(&civilian_state, &mut pressure);
-                        apply_pressure_modifiers(&mut world, &pressure, &endgame_state);
-                        println!("New life started. Alignment reset to Neutral.");
-                    }
-                    _ => {
-                        println!("Usage: life new");
-                    }
-                }
-            }
-            "cast" => {
-                print_cast(&characters);
-            }
-            "promote" => {
-                let Some(first) = parts.next() else {
-                    println!("Usage: promote <first> <last> [role]");
-                    continue;
-                };
-                let Some(last) = parts.next() else {
-                    println!("Usage: promote <first> <last> [role]");
-                    continue;
-                };
-                let role = parts.next().unwrap_or("CIVILIAN");
-                let scope_id = format!("city:{}", city.city_id.0);
-                let candidate = PromotionCandidate {
-                    scope_id,
-                    first_name: first.to_string(),
-                    last_name: last.to_string(),
-                    role_type: role.to_string(),
-                    faction_id: None,
-                    rank: None,
-                    persona_kind: Some("CIVILIAN".to_string()),
-                    persona_label: Some("Civilian".to_string()),
-                    reason: PromotionReason::Manual,
-                };
-                match world_repo.promote_candidate(&candidate, world.turn) {
-                    Ok(character) => {
-                        println!(
-                            "Promoted {} {} ({}) -> {}",
-                            character.first_name,
-                            character.last_name,
-                            candidate.role_type,
-                            character.character_id
-                        );
-                        characters.push(character);
-                    }
-                    Err(err) => println!("Failed to promote: {}", err),
-                }
-            }
-            "growth" => {
-                let sub = parts.next();
-                match sub {
-                    None => print_growth_state(&growth),
-                    Some("expr") => {
-                        if let Some(expr_id) = parts.next() {
-                            print_growth_expr(&growth, expr_id);
-                        } else {
-                            println!("Usage: growth expr <expression_id>");
-                        }
-                    }
-                    Some("unlock") => {
-                        if let Some(expr_id) = parts.next() {
-                            let expr_id = ExpressionId(expr_id.to_string());
-                            growth.unlocked_expressions.insert(expr_id.clone());
-                            println!("Unlocked expression {}", expr_id.0);
-                        } else {
-                            println!("Usage: growth unlock <expression_id>");
-                        }
-                    }
-                    Some("mastery") => {
-                        if let (Some(expr_id), Some(uses_raw)) = (parts.next(), parts.next()) {
-                            if let Ok(uses) = uses_raw.parse::<u32>() {
-                                seed_mastery(&mut growth, expr_id, uses);
-                            } else {
-                                println!("Usage: growth mastery <expression_id> <uses>");
-                            }
-                        } else {
-                            println!("Usage: growth mastery <expression_id> <uses>");
-                        }
-                    }
-                    Some(_) => {
-                        println!("Usage: growth [expr|unlock|mastery]");
-                    }
-                }
-            }
-            "set" => {
-                let field = parts.next().unwrap_or("");
-                let value = parts.next().unwrap_or("");
-                if update_context(&mut target, field, value).is_err() {
-                    println!("Usage: set dist <m> | set los <on|off> | set contact <on|off> | set public <on|off> | set witnesses <n>");
-                }
-            }
-            "switch" => {
-                if let Some(persona_id) = parts.next() {
-                    let intents = vec![ActionIntent::SwitchPersona {
-                        entity_id: 1,
-                        persona_id: persona_id.to_string(),
-                    }];
-                    handle_switch(
-                        persona_id,
-                        &mut persona_stack,
-                        world.turn,
-                        &city,
-                        &evidence,
-                        &target,
-                    );
-                    apply_suspicion_for_intents(
-                        &mut persona_stack,
-                        alignment,
-                        &player_pos,
-                        &city,
-                        &cases,
-                        &identity_evidence,
-                        &intents,
-                        1,
-                    );
-                    print_persona_state(&persona_stack, alignment, &city, &cases);
-                } else {
-                    println!("Usage: switch <persona_id>");
-                }
-            }
-            "use" => {
-                if let Some(expr_raw) = parts.next() {
-                    let expr_id = ExpressionId(expr_raw.to_string());
-                    match repo.expression(&expr_id) {
-                        Ok(expr) => {
-                            let mut ctx = UseContext {
-                                actor: &mut actor,
-                                world: &world,
-                                mastery: growth.mastery.get(&expr.id).map(|entry| entry.stage),
-                                unlocked: Some(&growth.unlocked_expressions),
-                            };
-                            match can_use(&ctx, &expr, &target) {
-                                Ok(_) => match use_power(&mut ctx, &expr, &target) {
-                                    Ok(result) => {
-                                        print_use_result(&result);
-                                        let location_id = city.active_location;
-                                        apply_action_signatures(
-                                            &result.emitted_signatures,
-                                            location_id,
-                                            world.turn,
-                                            target.witnesses,
-                                            target.in_public,
-                                            PersonaHint::Unknown,
-                                            None,
-                                            &mut city,
-                                            &mut city_events,
-                                            &mut evidence,
-                                            &mut identity_evidence,
-                                            &mut faction_director,
-                                            &mut faction_events,
-                                            &mut resolved_faction_events,
-                                            &mut cases,
-                                            &mut case_log,
-                                            &mut persona_stack,
-                                            alignment,
-                                            &player_pos,
-                                            &mut event_log,
-                                        );
-                                        update_pressure(
-                                            &mut pressure,
-                                            &city,
-                                            &evidence,
-                                            &cases,
-                                            &game_time,
-                                        );
-                                        apply_civilian_pressure(&civilian_state, &mut pressure);
-                                        apply_pressure_modifiers(
-                                            &mut world,
-                                            &pressure,
-                                            &endgame_state,
-                                        );
-                                        run_region_update(
-                                            &mut region,
-                                            &city,
-                                            &pressure,
-                                            &mut city_events,
-                                            &mut region_events,
-                                        );
-                                        run_global_faction_director(
-                                            &mut global_faction_director,
-                                            &region,
-                                            &mut global_faction_events,
-                                        );
-                                        handle_endgame_transition(
-                                            &cases,
-                                            &pressure,
-                                            &resolved_faction_events,
-                                            &mut world,
-                                            &mut storylet_state,
-                                            &mut endgame_state,
-                                        );
-                                        persist_world_state(
-                                            &mut *world_repo,
-                                            &world,
-                                            &game_time,
-                                            &city,
-                                            &cases,
-                                            &combat,
-                                            &growth,
-                                            &civilian_state,
-                                            &storylet_state,
-                                            &persona_stack,
-                                            alignment,
-                                            &omni_registry,
-                                        );
-                                        print_event_log(&mut event_log);
-                                        println!(
-                                            "Actor: stamina={}, focus={}, cooldowns={}",
-                                            actor.stamina,
-                                            actor.focus,
-                                            actor.cooldowns.len()
-                                        );
-                                        apply_growth_on_use(
-                                            &mut growth,
-                                            &expr,
-                                            &repo,
-                                            &mut storylet_state,
-                                        );
-                                    }
-                                    Err(err) => println!("use_power failed: {:?}", err),
-                                },
-                                Err(err) => print_use_error(&err, &expr, &target, &actor),
-                            }
-                        }
-                        Err(err) => println!("Expression not found: {}", err),
-                    }
-                } else {
-                    println!("Usage: use <expression_id>");
-                }
-            }
-            "cd" => {
-                print_cooldowns(&actor);
-            }
-            "scene" => {
-                print_scene(&evidence);
-            }
-            "storylets" => {
-                let all = matches!(parts.next(), Some("all"));
-                if all {
-                    list_storylets_all(&storylets, alignment);
-                } else {
-                    list_storylets_available(
-                        &storylets,
-                        alignment,
-                        &persona_stack,
-                        &storylet_state,
-                        &endgame_state,
-                        &city,
-                        &evidence,
-                        &cases,
-                        &pressure,
-                        &civilian_state,
-                        &game_time,
-                    );
-                }
-            }
-            "punctuation" => {
-                let action = parts.next();
-                match action {
-                    None => {
-                        if storylet_state.punctuation.only {
-                            println!(
-                                "Punctuation layer: ON ({} turns remaining)",
-                                storylet_state.punctuation.remaining_turns
-                            );
-                        } else {
-                            println!("Punctuation layer: OFF");
-                        }
-                    }
-                    Some("off") => {
-                        storylet_state.punctuation.clear();
-                        println!("Punctuation layer disabled.");
-                    }
-                    Some("on") => {
-                        let turns = parts
-                            .next()
-                            .and_then(|raw| raw.parse::<i32>().ok())
-                            .unwrap_or(DEFAULT_PUNCTUATION_TURNS);
-                        storylet_state.punctuation.activate(turns);
-                        println!(
-                            "Punctuation layer enabled for {} turns.",
-                            storylet_state.punctuation.remaining_turns
-                        );
-                    }
-                    Some(raw) => {
-                        if let Ok(turns) = raw.parse::<i32>() {
-                            storylet_state.punctuation.activate(turns);
-                            println!(
-                                "Punctuation layer enabled for {} turns.",
-                                storylet_state.punctuation.remaining_turns
-                            );
-                        } else {
-                            println!("Usage: punctuation <on|off|turns>");
-                        }
-                    }
-                }
-            }
-            "author" => {
-                let origin_catalog = match load_origin_catalog("./assets/data/origins.json") {
-                    Ok(catalog) => catalog,
-                    Err(err) => {
-                        println!("Failed to load origins: {}", err);
-                        continue;
-                    }
-                };
-                let nemesis_catalog =
-                    match load_nemesis_action_catalog("./assets/data/nemesis_actions.json") {
-                        Ok(catalog) => catalog,
-                        Err(err) => {
-                            println!("Failed to load nemesis actions: {}", err);
-                            continue;
-                        }
-                    };
-                let panel = render_authoring_dashboard(
-                    &storylets,
-                    &civilian_events,
-                    &endgame_events,
-                    &endgame_state,
-                    &origin_catalog,
-                    &origin_paths,
-                    &nemesis_catalog,
-                    &region,
-                    &global_events,
-                    &global_event_state,
-                );
-                println!("{}", panel);
-            }
-            "civilian" => {
-                let sub = parts.next();
-                match sub {
-                    None => {
-                        print_civilian_status(&civilian_state, &game_time);
-                        print_pending_civilian_events(
-                            &mut civilian_state,
-                            &civilian_events,
-                            &game_time,
-                            false,
-                        );
-                    }
-                    Some("events") => {
-                        let show_details = match parts.next().map(|value| value.to_lowercase()) {
-                            None => false,
-                            Some(value) if value == "detail" || value == "details" => true,
-                            Some(_) => {
-                                println!("Usage: civilian events [detail]");
-                                continue;
-                            }
-                        };
-                        print_pending_civilian_events(
-                            &mut civilian_state,
-                            &civilian_events,
-                            &game_time,
-                            show_details,
-                        );
-                    }
-                    Some("detail") | Some("details") => {
-                        let Some(event_id) = parts.next() else {
-                            println!("Usage: civilian detail <event_id>");
-                            continue;
-                        };
-                        print_civilian_event_detail(
-                            &mut civilian_state,
-                            &civilian_events,
-                            &game_time,
-                            event_id,
-                        );
-                    }
-                    Some("resolve") => {
-                        let Some(event_id) = parts.next() else {
-                            println!("Usage: civilian resolve <event_id> <choice_id>");
-                            continue;
-                        };
-                        let Some(choice_id) = parts.next() else {
-                            println!("Usage: civilian resolve <event_id> <choice_id>");
-                            continue;
-                        };
-                        resolve_civilian_event(
-                            &mut civilian_state,
-                            &civilian_events,
-                            &mut origin_quest,
-                            &origin_paths,
-                            &mut pressure,
-                            event_id,
-                            choice_id,
-                            true,
-                        );
-                        apply_civilian_pressure(&civilian_state, &mut pressure);
-                    }
-                    Some("prefs") => {
-                        let sub = parts.next();
-                        match sub {
-                            None | Some("show") => {
-                                print_civilian_preferences(&civilian_state);
-                            }
-                            Some("reset") => {
-                                civilian_state.event_settings = CivilianEventSettings::default();
-                                civilian_state.auto_choices = AutoChoicePreferences::default();
-                                println!("Civilian prefs reset to defaults.");
-                            }
-                            Some("repeat") => {
-                                let Some(days_raw) = parts.next() else {
-                                    println!("Usage: civilian prefs repeat <days>");
-                                    continue;
-                                };
-                                let Ok(days) = days_raw.parse::<u32>() else {
-                                    println!("Invalid days: {}", days_raw);
-                                    continue;
-                                };
-                                civilian_state.event_settings.suppress_repeat_days = days;
-                                println!("Repeat suppression set to {} days.", days);
-                            }
-                            Some("min_effect") => {
-                                let Some(value_raw) = parts.next() else {
-                                    println!("Usage: civilian prefs min_effect <value>");
-                                    continue;
-                                };
-                                let Ok(value) = value_raw.parse::<i32>() else {
-                                    println!("Invalid value: {}", value_raw);
-                                    continue;
-                                };
-                                civilian_state.event_settings.min_effect_magnitude = value.max(0);
-                                println!(
-                                    "Min effect magnitude set to {}.",
-                                    civilian_state.event_settings.min_effect_magnitude
-                                );
-                            }
-                            Some("mute") => {
-                                let Some(category_raw) = parts.next() else {
-                                    println!("Usage: civilian prefs mute <category> [on|off]");
-                                    continue;
-                                };
-                                let Some(category) = parse_event_category(category_raw) else {
-                                    println!("Unknown category: {}", category_raw);
-                                    continue;
-                                };
-                                let enabled = match parts.next().map(|v| v.to_ascii_lowercase()) {
-                                    None => true,
-                                    Some(value) if value == "on" => true,
-                                    Some(value) if value == "off" => false,
-                                    Some(_) => {
-                                        println!("Usage: civilian prefs mute <category> [on|off]");
-                                        continue;
-                                    }
-                                };
-                                let muted = &mut civilian_state.event_settings.muted_categories;
-                                if enabled {
-                                    if !muted.contains(&category) {
-                                        muted.push(category);
-                                    }
-                                } else {
-                                    muted.retain(|entry| entry != &category);
-                                }
-                                println!(
-                                    "Muted {}: {}.",
-                                    event_category_label(category),
-                                    if enabled { "on" } else { "off" }
-                                );
-                            }
-                            Some("auto") => {
-                                let Some(rule) = parts.next() else {
-                                    println!("Usage: civilian prefs auto <rent|school|rest> <value|on|off>");
-                                    continue;
-                                };
-                                match rule.to_ascii_lowercase().as_str() {
-                                    "rent" => {
-                                        let Some(value_raw) = parts.next() else {
-                                            println!("Usage: civilian prefs auto rent <cash|off>");
-                                            continue;
-                                        };
-                                        if value_raw.eq_ignore_ascii_case("off") {
-                                            civilian_state.auto_choices.pay_rent_if_cash_at_least = None;
-                                            println!("Auto rent pay disabled.");
-                                            continue;
-                                        }
-                                        let Ok(value) = value_raw.parse::<i32>() else {
-                                            println!("Invalid cash value: {}", value_raw);
-                                            continue;
-                                        };
-                                        civilian_state.auto_choices.pay_rent_if_cash_at_least = Some(value.max(0));
-                                        println!(
-                                            "Auto rent pay set to cash >= {}.",
-                                            value.max(0)
-                                        );
-                                    }
-                                    "school" => {
-                                        let Some(value_raw) = parts.next() else {
-                                            println!("Usage: civilian prefs auto school <on|off>");
-                                            continue;
-                                        };
-                                        let enabled = match value_raw.to_ascii_lowercase().as_str() {
-                                            "on" => true,
-                                            "off" => false,
-                                            _ => {
-                                                println!("Usage: civilian prefs auto school <on|off>");
-                                                continue;
-                                            }
-                                        };
-                                        civilian_state.auto_choices.attend_school = enabled;
-                                        println!(
-                                            "Auto school attendance {}.",
-                                            if enabled { "enabled" } else { "disabled" }
-                                        );
-                                    }
-                                    "rest" => {
-                                        let Some(value_raw) = parts.next() else {
-                                            println!("Usage: civilian prefs auto rest <sleep_debt|off>");
-                                            continue;
-                                        };
-                                        if value_raw.eq_ignore_ascii_case("off") {
-                                            civilian_state.auto_choices.rest_if_sleep_debt_at_least = None;
-                                            println!("Auto rest disabled.");
-                                            continue;
-                                        }
-                                        let Ok(value) = value_raw.parse::<i32>() else {
-                                            println!("Invalid sleep debt value: {}", value_raw);
-                                            continue;
-                                        };
-                                        civilian_state.auto_choices.rest_if_sleep_debt_at_least = Some(value.max(0));
-                                        println!(
-                                            "Auto rest set to sleep_debt >= {}.",
-                                            value.max(0)
-                                        );
-                                    }
-                                    _ => {
-                                        println!("Usage: civilian prefs auto <rent|school|rest> <value|on|off>");
-                                    }
-                                }
-                            }
-                            Some(_) => {
-                                println!("Usage: civilian prefs [show|reset|repeat <days>|min_effect <value>|mute <category> [on|off]|auto <rent|school|rest> <value|on|off>]");
-                            }
-                        }
-                    }
-                    Some("profile") => {
-                        let Some(profile) = parts.next() else {
-                            println!("Usage: civilian profile <balanced|vigilante|corporate>");
-                            continue;
-                        };
-                        let effect = format!("wealth_profile:{}", profile);
-                        let applied = apply_civilian_effects(&mut civilian_state, &[effect]);
-                        if applied.is_empty() {
-                            println!("Unknown wealth profile: {}", profile);
-                            continue;
-                        }
-                        println!("Applied effects:");
-                        for entry in applied {
-                            println!("  {}", entry);
-                        }
-                        apply_civilian_pressure(&civilian_state, &mut pressure);
-                        apply_pressure_modifiers(&mut world, &pressure, &endgame_state);
-                    }
-                    Some(_) => {
-                        println!("Usage: civilian [events [detail]|detail <event_id>|resolve <event_id> <choice_id>|prefs ...|profile <balanced|vigilante|corporate>]");
-                    }
-                }
-            }
-            "global" => {
-                let sub = parts.next();
-                match sub {
-                    None => {
-                        print_global_escalation(&region);
-                        print_pending_global_events(
-                            &global_event_state,
-                            &global_events,
-                            &game_time,
-                            false,
-                        );
-                    }
-                    Some("events") => {
-                        let show_details = match parts.next().map(|value| value.to_lowercase()) {
-                            None => false,
-                            Some(value) if value == "detail" || value == "details" => true,
-                            Some(_) => {
-                                println!("Usage: global events [detail]");
-                                continue;
-                            }
-                        };
-                        print_pending_global_events(
-                            &global_event_state,
-                            &global_events,
-                            &game_time,
-                            show_details,
-                        );
-                    }
-                    Some("detail") | Some("details") => {
-                        let Some(event_id) = parts.next() else {
-                            println!("Usage: global detail <event_id>");
-                            continue;
-                        };
-                        print_global_event_detail(
-                            &global_event_state,
-                            &global_events,
-                            &game_time,
-                            event_id,
-                        );
-                    }
-                    Some("resolve") => {
-                        let Some(event_id) = parts.next() else {
-                            println!("Usage: global resolve <event_id> <choice_id>");
-                            continue;
-                        };
-                        let Some(choice_id) = parts.next() else {
-                            println!("Usage: global resolve <event_id> <choice_id>");
-                            continue;
-                        };
-                        resolve_global_event(
-                            &mut global_event_state,
-                            &global_events,
-                            &mut pressure,
-                            &mut nemesis_state,
-                            &mut storylet_state,
-                            event_id,
-                            choice_id,
-                        );
-                        apply_pressure_modifiers(&mut world, &pressure, &endgame_state);
-                    }
-                    Some(_) => {
-                        println!("Usage: global [events [detail]|detail <event_id>|resolve <event_id> <choice_id>]");
-                    }
-                }
-            }
-            "events" => {
-                print_faction_events(&mut resolved_faction_events);
-            }
-            "cases" => {
-                print_cases(&cases);
-                print_case_log(&mut case_log);
-            }
-            "origin" => {
-                let sub = parts.next().unwrap_or("").to_lowercase();
-                match sub.as_str() {
-                    "paths" => {
-                        let seed = parts
-                            .next()
-                            .and_then(|raw| raw.parse::<u64>().ok())
-                            .unwrap_or(world.turn);
-                        let count = parts
-                            .next()
-                            .and_then(|raw| raw.parse::<usize>().ok())
-                            .unwrap_or(3);
-                        let options = select_origin_paths(&origin_paths, None, seed, count);
-                        print_origin_paths(&options, seed);
-                    }
-                    "choose" => {
-                        let Some(path_id) = parts.next() else {
-                            println!("Usage: origin choose <path_id>");
-                            continue;
-                        };
-                        match start_origin_path(&mut origin_quest, &origin_paths, path_id) {
-                            Ok(path) => {
-                                println!("Origin path selected: {} - {}", path.label, path.summary);
-                                print_origin_path_status(&origin_quest, &origin_paths);
-                            }
-                            Err(err) => println!("Failed to start origin path: {}", err),
-                        }
-                    }
-                    "status" => {
-                        print_origin_path_status(&origin_quest, &origin_paths);
-                    }
-                    "event" => {
-                        let Some(event_tag) = parts.next() else {
-                            println!("Usage: origin event <tag>");
-                            continue;
-                        };
-                        let rewards =
-                            register_origin_event(&mut origin_quest, &origin_paths, event_tag);
-                        apply_origin_rewards(&rewards, &mut pressure);
-                        apply_pressure_modifiers(&mut world, &pressure, &endgame_state);
-                        if !rewards.is_empty() {
-                            print_origin_path_status(&origin_quest, &origin_paths);
-                        }
-                    }
-                    "tick" => {
-                        let count = parts
-                            .next()
-                            .and_then(|raw| raw.parse::<u32>().ok())
-                            .unwrap_or(1);
-                        for _ in 0..count {
-                            let rewards = tick_origin_path(&mut origin_quest, &origin_paths);
-                            apply_origin_rewards(&rewards, &mut pressure);
-                            apply_pressure_modifiers(&mut world, &pressure, &endgame_state);
-                            if !rewards.is_empty() {
-                                print_origin_path_status(&origin_quest, &origin_paths);
-                            }
-                        }
-                    }
-                    "" => {
-                        println!("Usage: origin [paths|choose|status|event|tick]");
-                    }
-                    _ => {
-                        println!("Usage: origin [paths|choose|status|event|tick]");
-                    }
-                }
-            }
-            "alien" => {
-                let sub = parts.next().unwrap_or("generate").to_lowercase();
-                let seed = parts
-                    .next()
-                    .and_then(|raw| raw.parse::<u64>().ok())
-                    .unwrap_or(world.turn);
-                let catalog =
-                    match load_alien_generation_catalog("./assets/data/alien_generation.json") {
-                        Ok(catalog) => catalog,
-                        Err(err) => {
-                            println!("Failed to load alien generator: {}", err);
-                            continue;
-                        }
-                    };
-                let profile = generate_alien_species(&catalog, seed);
-                match sub.as_str() {
-                    "generate" => {
-                        for line in format_alien_profile(&profile, false) {
-                            println!("{}", line);
-                        }
-                    }
-                    "detail" => {
-                        for line in format_alien_profile(&profile, true) {
-                            println!("{}", line);
-                        }
-                    }
-                    "powers" => {
-                        for line in format_alien_profile(&profile, false) {
-                            println!("{}", line);
-                        }
-                        let holder_id = format!("alien:{}", seed);
-                        match assign_alien_powers(
-                            &repo,
-                            &profile,
-                            &omni_catalog,
-                            &mut omni_registry,
-                            &holder_id,
-                            "prime",
-                            seed,
-                            power_assignment_config.clone(),
-                        ) {
-                            Ok(assignment) => {
-                                print_power_assignment(&repo, &assignment);
-                            }
-                            Err(err) => {
-                                println!("Failed to assign alien powers: {}", err);
-                            }
-                        }
-                    }
-                    _ => {
-                        println!("Usage: alien [generate|detail|powers] [seed]");
-                    }
-                }
-            }
-            "cosmic" => {
-                let sub = parts.next().unwrap_or("constants").to_lowercase();
-                match sub.as_str() {
-                    "constants" => {
-                        if let Some(catalog) = &cosmic_constants {
-                            print_cosmic_constants(catalog);
-                        } else {
-                            println!("Cosmic constants not loaded.");
-                        }
-                    }
-                    "omni" => {
-                        print_omni_registry(&omni_registry);
-                    }
-                    _ => {
-                        println!("Usage: cosmic [constants|omni]");
-                    }
-                }
-            }
-            "combat" => {
-                let sub = parts.next().unwrap_or("").to_lowercase();
-                match sub.as_str() {
-                    "start" => {
-                        let label = parts.next().unwrap_or("incident");
-                        let scale = parts
-                            .next()
-                            .and_then(parse_combat_scale)
-                            .unwrap_or(CombatScale::Street);
-                        let opponent_count = parts
-                            .next()
-                            .and_then(|v| v.parse::<u32>().ok())
-                            .unwrap_or(2);
-                        let player_name = persona_stack
-                            .active_persona()
-                            .map(|p| p.label.clone())
-                            .unwrap_or_else(|| "Player".to_string());
-                        start_combat(
-                            &mut combat,
-                            city.active_location,
-                            label,
-                            scale,
-                            &player_name,
-                            opponent_count,
-                            world.turn,
-                        );
-                        print_combat_status(&combat);
-                    }
-                    "use" => {
-                        if !combat.active {
-                            println!("No active combat. Use `combat start <label>` first.");
-                        } else if let Some(expr_raw) = parts.next() {
-                            combat.pending_player_expression =
-                                Some(ExpressionId(expr_raw.to_string()));
-                            println!("Queued expression {} for combat.", expr_raw);
-                        } else {
-                            println!("Usage: combat use <expression_id>");
-                        }
-                    }
-                    "intent" => {
-                        if !combat.active {
-                            println!("No active combat. Use `combat start <label>` first.");
-                        } else if let Some(raw) = parts.next() {
-                            match parse_combat_intent(raw) {
-                                Some(intent) => {
-                                    if let Some(player) = combat.player_mut() {
-                                        player.intent = intent;
-                                        combat
-                                            .log
-                                            .push(format!("Player intent set to {:?}.", intent));
-                                    }
-                                    println!("Player intent -> {:?}.", intent);
-                                }
-                                None => {
-                                    println!("Usage: combat intent <attack|escape|hold|capture>");
-                                }
-                            }
-                        } else {
-                            println!("Usage: combat intent <attack|escape|hold|capture>");
-                        }
-                    }
-                    "tick" => {
-                        if !combat.active {
-                            println!("No active combat. Use `combat start <label>` first.");
-                        } else {
-                            let count = parts
-                                .next()
-                                .and_then(|v| v.parse::<u32>().ok())
-                                .unwrap_or(1);
-                            for _ in 0..count {
-                                let expr_def = match combat.pending_player_expression.as_ref() {
-                                    Some(expr_id) => match repo.expression(expr_id) {
-                                        Ok(expr) => Some(expr),
-                                        Err(err) => {
-                                            println!("Expression not found: {}", err);
-                                            None
-                                        }
-                                    },
-                                    None => None,
-                                };
-                                let mastery_stage = combat
-                                    .pending_player_expression
-                                    .as_ref()
-                                    .and_then(|expr_id| growth.mastery.get(expr_id))
-                                    .map(|entry| entry.stage);
-
-                                let tick_result = combat_tick(
-                                    &mut combat,
-                                    &mut actor,
-                                    &world,
-                                    &target,
-                                    expr_def.as_ref(),
-                                    mastery_stage,
-                                    Some(&growth.unlocked_expressions),
-                                );
-
-                                if tick_result.used_success {
-                                    if let Some(expr) = expr_def.as_ref() {
-                                        apply_growth_on_use(
-                                            &mut growth,
-                                            expr,
-                                            &repo,
-                                            &mut storylet_state,
-                                        );
-                                    }
-                                }
-
-                                if !tick_result.emitted_signatures.is_empty() {
-                                    let witnesses = target.witnesses.saturating_add(2);
-                                    apply_action_signatures(
-                                        &tick_result.emitted_signatures,
-                                        combat.location_id,
-                                        world.turn,
-                                        witnesses,
-                                        target.in_public,
-                                        PersonaHint::Unknown,
-                                        None,
-                                        &mut city,
-                                        &mut city_events,
-                                        &mut evidence,
-                                        &mut identity_evidence,
-                                        &mut faction_director,
-                                        &mut faction_events,
-                                        &mut resolved_faction_events,
-                                        &mut cases,
-                                        &mut case_log,
-                                        &mut persona_stack,
-                                        alignment,
-                                        &player_pos,
-                                        &mut event_log,
-                                    );
-                                }
+    session.persist();
+    print_lines(session.take_output());
+}
 
-                                world.turn += 1;
-                                tick_cooldowns(&mut actor);
-                                update_units(&mut city);
-                                evidence.tick_decay();
-                                decay_heat(&mut city, &cases, &mut city_events);
-                                game_time.advance();
-                                tick_civilian_life(&mut civilian_state, &game_time);
-                                maybe_awaken_mutant_powers(
-                                    &mut civilian_state,
-                                    &repo,
-                                    &omni_catalog,
-                                    &mut omni_registry,
-                                    &power_assignment_config,
-                                    world.turn,
-                                );
-                                storylet_state.tick();
-                                update_pressure(
-                                    &mut pressure,
-                                    &city,
-                                    &evidence,
-                                    &cases,
-                                    &game_time,
-                                );
-                                apply_civilian_pressure(&civilian_state, &mut pressure);
-                                apply_pressure_modifiers(&mut world, &pressure, &endgame_state);
-                                run_region_update(
-                                    &mut region,
-                                    &city,
-                                    &pressure,
-                                    &mut city_events,
-                                    &mut region_events,
-                                );
-                                run_global_faction_director(
-                                    &mut global_faction_director,
-                                    &region,
-                                    &mut global_faction_events,
-                                );
-                                let end_reason = tick_result.ended;
-                                if let Some(end_reason) = end_reason {
-                                    let consequences = tick_result
-                                        .post_combat_consequences
-                                        .unwrap_or_else(|| {
-                                            combat_post_consequences(
-                                                &mut combat,
-                                                end_reason,
-                                                &target,
-                                            )
-                                        });
-                                    handle_combat_end_consequences(
-                                        end_reason,
-                                        consequences,
-                                        combat.location_id,
-                                        &mut world,
-                                        &target,
-                                        &mut pressure,
-                                        &endgame_state,
-                                        &mut city,
-                                        &mut city_events,
-                                        &mut evidence,
-                                        &mut identity_evidence,
-                                        &mut faction_director,
-                                        &mut faction_events,
-                                        &mut resolved_faction_events,
-                                        &mut cases,
-                                        &mut case_log,
-                                        &mut persona_stack,
-                                        alignment,
-                                        &player_pos,
-                                        &mut event_log,
-                                    );
-                                }
-                                handle_endgame_transition(
-                                    &cases,
-                                    &pressure,
-                                    &resolved_faction_events,
-                                    &mut world,
-                                    &mut storylet_state,
-                                    &mut endgame_state,
-                                );
-                                if let Some(end_reason) = end_reason {
-                                    if end_reason == CombatEnd::PlayerDefeated {
-                                        start_new_life(
-                                            &mut actor,
-                                            &mut growth,
-                                            &mut origin_quest,
-                                            &mut persona_stack,
-                                            &mut alignment,
-                                            &mut civilian_state,
-                                            &mut player_pos,
-                                            &mut target,
-                                            &mut storylet_state,
-                                            &game_time,
-                                            &repo,
-                                            world.turn,
-                                        );
-                                        update_pressure(
-                                            &mut pressure,
-                                            &city,
-                                            &evidence,
-                                            &cases,
-                                            &game_time,
-                                        );
-                                        apply_civilian_pressure(&civilian_state, &mut pressure);
-                                        apply_pressure_modifiers(
-                                            &mut world,
-                                            &pressure,
-                                            &endgame_state,
-                                        );
-                                        println!("You died. Starting a new life.");
-                                    }
-                                    println!("Combat ended: {}", format_combat_end(end_reason));
-                                    process_civilian_death(
-                                        &mut actor,
-                                        &mut growth,
-                                        &mut origin_quest,
-                                        &mut persona_stack,
-                                        &mut alignment,
-                                        &mut civilian_state,
-                                        &mut player_pos,
-                                        &mut target,
-                                        &mut storylet_state,
-                                        &game_time,
-                                        &repo,
-                                        world.turn,
-                                        &mut world,
-                                        &mut pressure,
-                                        &city,
-                                        &evidence,
-                                        &cases,
-                                        &endgame_state,
-                                        &combat,
-                                    );
-                                    break;
-                                }
-                            }
-                            print_combat_status(&combat);
-                            print_event_log(&mut event_log);
-                        }
-                    }
-                    "log" => {
-                        print_combat_log(&combat);
-                    }
-                    "resolve" => {
-                        if let Some(end_reason) = resolve_combat(&mut combat) {
-                            let consequences =
-                                combat_post_consequences(&mut combat, end_reason, &target);
-                            handle_combat_end_consequences(
-                                end_reason,
-                                consequences,
-                                combat.location_id,
-                                &mut world,
-                                &target,
-                                &mut pressure,
-                                &endgame_state,
-                                &mut city,
-                                &mut city_events,
-                                &mut evidence,
-                                &mut identity_evidence,
-                                &mut faction_director,
-                                &mut faction_events,
-                                &mut resolved_faction_events,
-                                &mut cases,
-                                &mut case_log,
-                                &mut persona_stack,
-                                alignment,
-                                &player_pos,
-                                &mut event_log,
-                            );
-                            println!("Combat ended: {}", format_combat_end(end_reason));
-                            process_civilian_death(
-                                &mut actor,
-                                &mut growth,
-                                &mut origin_quest,
-                                &mut persona_stack,
-                                &mut alignment,
-                                &mut civilian_state,
-                                &mut player_pos,
-                                &mut target,
-                                &mut storylet_state,
-                                &game_time,
-                                &repo,
-                                world.turn,
-                                &mut world,
-                                &mut pressure,
-                                &city,
-                                &evidence,
-                                &cases,
-                                &endgame_state,
-                                &combat,
-                            );
-                        } else {
-                            println!("No active combat.");
-                        }
-                    }
-                    "force_escape" => {
-                        if let Some(end_reason) = force_escape(&mut combat) {
-                            let consequences =
-                                combat_post_consequences(&mut combat, end_reason, &target);
-                            handle_combat_end_consequences(
-                                end_reason,
-                                consequences,
-                                combat.location_id,
-                                &mut world,
-                                &target,
-                                &mut pressure,
-                                &endgame_state,
-                                &mut city,
-                                &mut city_events,
-                                &mut evidence,
-                                &mut identity_evidence,
-                                &mut faction_director,
-                                &mut faction_events,
-                                &mut resolved_faction_events,
-                                &mut cases,
-                                &mut case_log,
-                                &mut persona_stack,
-                                alignment,
-                                &player_pos,
-                                &mut event_log,
-                            );
-                            println!("Combat ended: {}", format_combat_end(end_reason));
-                            process_civilian_death(
-                                &mut actor,
-                                &mut growth,
-                                &mut origin_quest,
-                                &mut persona_stack,
-                                &mut alignment,
-                                &mut civilian_state,
-                                &mut player_pos,
-                                &mut target,
-                                &mut storylet_state,
-                                &game_time,
-                                &repo,
-                                world.turn,
-                                &mut world,
-                                &mut pressure,
-                                &city,
-                                &evidence,
-                                &cases,
-                                &endgame_state,
-                                &combat,
-                            );
-                        } else {
-                            println!("No active combat.");
-                        }
-                    }
-                    "force_escalate" => {
-                        if force_escalate(&mut combat) {
-                            println!("Combat escalated to {:?}.", combat.scale);
-                        } else {
-                            println!("No escalation available.");
-                        }
-                    }
-                    _ => {
-                        println!("Usage: combat start <label> [scale] [opponents] | combat use <expression_id> | combat intent <attack|escape|hold|capture> | combat tick [n] | combat log | combat resolve | combat force_escape | combat force_escalate");
-                    }
-                }
-            }
-            "tick" => {
-                let next = parts.next();
-                let tick_mode = match next {
-                    None => TickMode::Count(1),
-                    Some(raw) => {
-                        if let Ok(count) = raw.parse::<u32>() {
-                            TickMode::Count(count)
-                        } else {
-                            match raw.to_ascii_lowercase().as_str() {
-                                "next" => TickMode::NextEvent,
-                                "rent" => TickMode::NextRent,
-                                "crisis" => TickMode::CrisisOnly,
-                                "skip" => {
-                                    let days = parts
-                                        .next()
-                                        .and_then(|value| value.parse::<u32>().ok())
-                                        .unwrap_or(1)
-                                        .max(1);
-                                    TickMode::SkipDays(days)
-                                }
-                                _ => {
-                                    println!("Usage: tick [n|next|rent|crisis|skip [days]]");
-                                    continue;
-                                }
-                            }
-                        }
-                    }
-                };
-                let auto_mode = match tick_mode {
-                    TickMode::NextRent => AutoResolveMode::SkipRent,
-                    TickMode::CrisisOnly | TickMode::SkipDays(_) => AutoResolveMode::SkipCrises,
-                    TickMode::Count(_) | TickMode::NextEvent => AutoResolveMode::Normal,
-                };
-                let auto_resolved = auto_resolve_civilian_events(
-                    &mut civilian_state,
-                    &civilian_events,
-                    &mut origin_quest,
-                    &origin_paths,
-                    &mut pressure,
-                    auto_mode,
-                );
-                if auto_resolved > 0 {
-                    apply_civilian_pressure(&civilian_state, &mut pressure);
-                    apply_pressure_modifiers(&mut world, &pressure, &endgame_state);
-                }
-                let mut stop_reason = None;
-                if !matches!(tick_mode, TickMode::Count(_)) {
-                    stop_reason = match tick_mode {
-                        TickMode::NextRent if has_rent_due_pending(&civilian_state) => {
-                            Some(TickStopReason::RentDue)
-                        }
-                        TickMode::NextEvent
-                            if has_visible_pending_civilian_events(
-                                &civilian_state,
-                                &civilian_events,
-                                &game_time,
-                            ) || !global_event_state.pending.is_empty() =>
-                        {
-                            Some(TickStopReason::PendingEvent)
-                        }
-                        TickMode::CrisisOnly | TickMode::SkipDays(_)
-                            if has_crisis_pending(&civilian_state)
-                                || !global_event_state.pending.is_empty() =>
-                        {
-                            Some(TickStopReason::Crisis)
-                        }
-                        _ => None,
-                    };
-                }
-                let mut ticks_run = 0u32;
-                let mut death_pending = false;
-                if stop_reason.is_none() {
-                    let target_ticks = match tick_mode {
-                        TickMode::Count(count) => count,
-                        TickMode::SkipDays(days) => days.saturating_mul(24),
-                        _ => MAX_FAST_FORWARD_TICKS,
-                    };
-                    for _ in 0..target_ticks {
-                        let mut storylet_triggered = false;
-                        death_pending = tick_world(
-                            &mut world,
-                            &mut actor,
-                            &mut evidence,
-                            &mut identity_evidence,
-                            &mut city,
-                            &mut city_events,
-                            &mut faction_director,
-                            &mut faction_events,
-                            &mut resolved_faction_events,
-                            &mut cases,
-                            &mut case_log,
-                            &mut agents,
-                            &mut agent_events,
-                            &mut persona_stack,
-                            &storylets,
-                            &civilian_events,
-                            alignment,
-                            &mut player_pos,
-                            &mut game_time,
-                            &mut civilian_state,
-                            &mut storylet_state,
-                            &mut endgame_state,
-                            &mut pressure,
-                            &mut region,
-                            &mut region_events,
-                            &mut global_event_state,
-                            &mut global_event_log,
-                            &global_events,
-                            &mut global_faction_director,
-                            &mut global_faction_events,
-                            &mut origin_quest,
-                            &growth,
-                            &origin_paths,
-                            1,
-                            auto_mode,
-                            &mut storylet_triggered,
-                        );
-                        maybe_awaken_mutant_powers(
-                            &mut civilian_state,
-                            &repo,
-                            &omni_catalog,
-                            &mut omni_registry,
-                            &power_assignment_config,
-                            world.turn,
-                        );
-                        ticks_run += 1;
-                        if death_pending {
-                            stop_reason = Some(TickStopReason::Death);
-                            break;
-                        }
-                        match tick_mode {
-                            TickMode::Count(_) => {
-                                if ticks_run >= target_ticks {
-                                    stop_reason = Some(TickStopReason::Completed);
-                                    break;
-                                }
-                            }
-                            TickMode::NextRent => {
-                                if has_rent_due_pending(&civilian_state) {
-                                    stop_reason = Some(TickStopReason::RentDue);
-                                    break;
-                                }
-                            }
-                            TickMode::NextEvent => {
-                                if storylet_triggered {
-                                    stop_reason = Some(TickStopReason::Storylet);
-                                    break;
-                                }
-                                if has_visible_pending_civilian_events(
-                                    &civilian_state,
-                                    &civilian_events,
-                                    &game_time,
-                                ) || !global_event_state.pending.is_empty()
-                                {
-                                    stop_reason = Some(TickStopReason::PendingEvent);
-                                    break;
-                                }
-                            }
-                            TickMode::CrisisOnly => {
-                                if has_crisis_pending(&civilian_state)
-                                    || !global_event_state.pending.is_empty()
-                                {
-                                    stop_reason = Some(TickStopReason::Crisis);
-                                    break;
-                                }
-                            }
-                            TickMode::SkipDays(_) => {
-                                if has_crisis_pending(&civilian_state)
-                                    || !global_event_state.pending.is_empty()
-                                {
-                                    stop_reason = Some(TickStopReason::Crisis);
-                                    break;
-                                }
-                                if ticks_run >= target_ticks {
-                                    stop_reason = Some(TickStopReason::Completed);
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    if stop_reason.is_none() && !matches!(tick_mode, TickMode::Count(_)) {
-                        stop_reason = Some(TickStopReason::MaxTicks);
-                    }
-                }
-                if !matches!(tick_mode, TickMode::Count(_)) {
-                    let reason = stop_reason.unwrap_or(TickStopReason::Completed);
-                    println!(
-                        "Tick stop: {} ({} ticks).",
-                        tick_stop_reason_label(reason),
-                        ticks_run
-                    );
-                }
-                if death_pending {
-                    process_civilian_death(
-                        &mut actor,
-                        &mut growth,
-                        &mut origin_quest,
-                        &mut persona_stack,
-                        &mut alignment,
-                        &mut civilian_state,
-                        &mut player_pos,
-                        &mut target,
-                        &mut storylet_state,
-                        &game_time,
-                        &repo,
-                        world.turn,
-                        &mut world,
-                        &mut pressure,
-                        &city,
-                        &evidence,
-                        &cases,
-                        &endgame_state,
-                        &combat,
-                    );
-                }
-                handle_endgame_transition(
-                    &cases,
-                    &pressure,
-                    &resolved_faction_events,
-                    &mut world,
-                    &mut storylet_state,
-                    &mut endgame_state,
-                );
-                apply_cast_aging(
-                    &mut characters,
-                    &mut *world_repo,
-                    game_time.day,
-                    world.turn,
-                    &mut last_cast_aging_year,
-                );
-                persist_world_state(
-                    &mut *world_repo,
-                    &world,
-                    &game_time,
-                    &city,
-                    &cases,
-                    &combat,
-                    &growth,
-                    &civilian_state,
-                    &storylet_state,
-                    &persona_stack,
-                    alignment,
-                    &omni_registry,
-                );
-                print_tick_summary(
-                    &world,
-                    &persona_stack,
-                    &city,
-                    &cases,
-                    &pressure,
-                    &endgame_state,
-                );
-                print_case_log(&mut case_log);
-            }
-            _ => {
-                println!("Unknown command. Type 'help'.");
-            }
-        }
+fn print_lines(lines: Vec<String>) {
+    for line in lines {
+        println!("{}", line);
     }
-
-    persist_world_state(
-        &mut *world_repo,
-        &world,
-        &game_time,
-        &city,
-        &cases,
-        &combat,
-        &growth,
-        &civilian_state,
-        &storylet_state,
-        &persona_stack,
-        alignment,
-        &omni_registry,
-    );
 }
 
-fn apply_growth_on_use(
-    growth: &mut GrowthState,
-    expr: &superhero_universe::rules::ExpressionDef,
-    repo: &dyn PowerRepository,
-    storylet_state: &mut StoryletState,
-) {
-    let stage_change = record_expression_use(growth, expr);
-    if let Some(stage) = stage_change {
-        println!("Mastery advanced: {} -> {:?}", expr.id.0, stage);
-        if let Some(flag) = mastery_stage_flag(stage) {
-            storylet_state.flags.insert(flag.to_string(), true);
-            storylet_state
-                .flags
-                .insert(format!("{}_{}", flag, expr.id.0), true);
-        }
-        if let Ok(candidates) = repo.expressions_for_power(expr.power_id) {
-            if let Some(unlocked) =
-                select_evolution_candidate(expr, &candidates, &growth.unlocked_expressions)
-            {
-                growth.unlocked_expressions.insert(unlocked.clone());
-                println!("Evolution unlocked expression {}", unlocked.0);
-            }
+fn parse_seed(args: &[String]) -> Option<u64> {
+    let index = args.iter().position(|arg| arg == "--seed")?;
+    let raw = args.get(index + 1)?;
+    match raw.parse::<u64>() {
+        Ok(seed) => Some(seed),
+        Err(_) => {
+            eprintln!("Ignoring invalid --seed value: {}", raw);
+            None
         }
     }
 }
 
-fn mastery_stage_flag(stage: superhero_universe::rules::MasteryStage) -> Option<&'static str> {
-    match stage {
-        superhero_universe::rules::MasteryStage::Precise => Some("mastery_precise"),
-        superhero_universe::rules::MasteryStage::Silent => Some("mastery_silent"),
-        superhero_universe::rules::MasteryStage::Iconic => Some("mastery_iconic"),
-        _ => None,
+/// The base civilian event catalog followed by any `--civilian-events <path>`
+/// catalogs, in the order given.
+fn parse_civilian_event_paths(args: &[String]) -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from(DEFAULT_CIVILIAN_EVENT_CATALOG)];
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--civilian-events" {
+            if let Some(value) = iter.next() {
+                paths.push(PathBuf::from(value));
+            }
+        }
     }
+    paths
 }
 
-fn print_growth_expr(growth: &GrowthState, expr_id: &str) {
-    let expr_id = ExpressionId(expr_id.to_string());
-    let unlocked = growth.unlocked_expressions.contains(&expr_id);
-    match growth.mastery.get(&expr_id) {
-        Some(entry) => {
-            println!(
-                "Expression {} -> unlocked={} stage={:?} uses={}",
-                expr_id.0, unlocked, entry.stage, entry.uses
-            );
-        }
-        None => {
-            println!(
-                "Expression {} -> unlocked={} stage=RAW uses=0",
-                expr_id.0, unlocked
-            );
+/// Drives a `Game` with line-delimited JSON; see `ui::json` for the schema.
+fn run_json_mode(seed: u64, civilian_event_catalogs: Vec<PathBuf>, content_db: PathBuf) {
+    let options = GameOptions {
+        civilian_event_catalogs,
+        content_db,
+        ..GameOptions::default()
+    };
+    let mut game = match Game::with_options(seed, options) {
+        Ok(game) => game,
+        Err(err) => {
+            eprintln!("Failed to set up game: {}", err);
+            std::process::exit(1);
         }
+    };
+    // Stdout carries the JSON replies, so load notes go to stderr.
+    let mut notes = Vec::new();
+    print_civilian_event_collisions(&mut notes, game.civilian_event_collisions());
+    for line in notes {
+        eprintln!("{}", line);
+    }
+    let stdin = io::stdin();
+    if let Err(err) = run_json_session(&mut game, stdin.lock(), io::stdout().lock()) {
+        eprintln!("JSON session ended: {}", err);
+        std::process::exit(1);
     }
 }
 
-fn seed_mastery(growth: &mut GrowthState, expr_id: &str, uses: u32) {
-    let expr_id = ExpressionId(expr_id.to_string());
-    let stage = superhero_universe::rules::stage_from_uses(uses);
-    growth.unlocked_expressions.insert(expr_id.clone());
-    growth.mastery.insert(
-        expr_id.clone(),
-        superhero_universe::simulation::growth::ExpressionMastery { stage, uses },
-    );
-    println!(
-        "Mastery seeded: {} -> {:?} (uses={})",
-        expr_id.0, stage, uses
-    );
+struct CliPaths {
+    content_db: PathBuf,
+    world_db: PathBuf,
+    /// `--script <path>`: run the file's lines as REPL commands, print their
+    /// output and exit instead of reading stdin.
+    script: Option<PathBuf>,
+    /// `--export-json <path>`: write the saved world as JSON and exit.
+    export_json: Option<PathBuf>,
+    /// `--import-json <path>`: replace the saved world with a JSON export.
+    import_json: Option<PathBuf>,
+    /// `--diagnose`: report on the world save without opening or migrating it.
+    diagnose: bool,
 }
 
-fn parse_paths(args: Vec<String>) -> (PathBuf, PathBuf) {
+fn parse_paths(args: Vec<String>) -> CliPaths {
     let mut iter = args.iter();
     let mut content_path = PathBuf::from("./assets/db/content_v1.db");
     let mut world_path = PathBuf::from("./assets/db/world.db");
+    let mut script = None;
+    let mut export_json = None;
+    let mut import_json = None;
+    let mut diagnose = false;
     let mut explicit_content = false;
     while let Some(arg) = iter.next() {
         match arg.as_str() {
+            "--script" => script = iter.next().map(PathBuf::from),
+            "--export-json" => export_json = iter.next().map(PathBuf::from),
+            "--import-json" => import_json = iter.next().map(PathBuf::from),
+            "--diagnose" => diagnose = true,
             "--db" => {
                 if let Some(value) = iter.next() {
                     content_path = PathBuf::from(value);
@@ -1937,3242 +233,49 @@ fn parse_paths(args: Vec<String>) -> (PathBuf, PathBuf) {
             );
         }
     }
-    (content_path, world_path)
-}
-
-fn print_stats(repo: &dyn PowerRepository) {
-    match repo.stats() {
-        Ok(stats) => println!(
-            "Stats: powers={}, expressions={}, acquisition_profiles={}",
-            stats.power_count, stats.expression_count, stats.acquisition_count
-        ),
-        Err(err) => println!("Stats unavailable: {}", err),
-    }
-}
-
-fn print_power(repo: &dyn PowerRepository, power_id: i64) {
-    let info = match repo.power_info(PowerId(power_id)) {
-        Ok(info) => info,
-        Err(err) => {
-            println!("Failed to query power: {}", err);
-            return;
-        }
-    };
-
-    let Some(info) = info else {
-        println!("No power found for id {}", power_id);
-        return;
-    };
-
-    println!("Power [{}]: {}", power_id, info.name);
-    if let Some(overview) = info.overview {
-        if !overview.trim().is_empty() {
-            println!("Overview: {}", overview.trim());
-        }
-    }
-    if let Some(description) = info.description {
-        if !description.trim().is_empty() {
-            println!("Description: {}", description.trim());
-        }
-    }
-    if let Some(short) = info.text_short {
-        if !short.trim().is_empty() {
-            println!("Short: {}", short.trim());
-        }
-    }
-    if let Some(mech) = info.text_mechanical {
-        if !mech.trim().is_empty() {
-            println!("Mechanical: {}", mech.trim());
-        }
-    }
-
-    match repo.expressions_for_power(PowerId(power_id)) {
-        Ok(expressions) => {
-            println!("Expressions:");
-            for expr in expressions {
-                println!(
-                    "  [{}] {} ({:?}/{:?}/{:?})",
-                    expr.id.0, expr.text.ui_name, expr.form, expr.delivery, expr.scale
-                );
-            }
-        }
-        Err(err) => println!("Failed to load expressions: {}", err),
-    }
-}
-
-fn print_use_result(result: &superhero_universe::rules::UseResult) {
-    println!("Mastery: {:?}", result.mastery_stage);
-    let mut stamina = 0;
-    let mut focus = 0;
-    let mut resource = 0;
-    let mut cooldown = None;
-    for cost in &result.applied_costs {
-        match cost.cost_type {
-            CostType::Stamina => stamina += cost.value.unwrap_or(0),
-            CostType::Focus => focus += cost.value.unwrap_or(0),
-            CostType::Resource => resource += cost.value.unwrap_or(0),
-            CostType::Cooldown => cooldown = cost.value.or(cooldown),
-            CostType::Risk => {}
-        }
-    }
-    println!(
-        "Costs: stamina={}, focus={}, resource={}, cooldown={:?}",
-        stamina, focus, resource, cooldown
-    );
-
-    if result.emitted_signatures.is_empty() {
-        println!("Signatures: none");
-    } else {
-        println!("Signatures:");
-        for sig in &result.emitted_signatures {
-            println!(
-                "  {:?} strength={} persistence={}",
-                sig.signature.signature_type, sig.signature.strength, sig.remaining_turns
-            );
-        }
-    }
-}
-
-fn print_event_log(log: &mut WorldEventLog) {
-    if log.0.is_empty() {
-        return;
-    }
-    println!("World events:");
-    for entry in log.0.drain(..) {
-        println!("  {}", entry);
-    }
-}
-
-fn print_context(
-    target: &TargetContext,
-    world: &WorldState,
-    city: &CityState,
-    pressure: &PressureState,
-    endgame_state: &EndgameState,
-) {
-    let active = city
-        .locations
-        .get(&city.active_location)
-        .map(|loc| (loc.heat, loc.response))
-        .unwrap_or((0, superhero_universe::simulation::city::HeatResponse::None));
-    println!(
-        "Context: turn={}, dist_m={:?}, los={}, contact={}, public={}, witnesses={}, location={}, heat={}, response={:?}",
-        world.turn,
-        target.distance_m,
-        target.has_line_of_sight,
-        target.has_contact,
-        target.in_public,
-        target.witnesses,
-        city.active_location.0,
-        active.0,
-        active.1
-    );
-    println!(
-        "Pressure: temporal={:.1} identity={:.1} institutional={:.1} moral={:.1} resource={:.1} psychological={:.1}",
-        pressure.temporal,
-        pressure.identity,
-        pressure.institutional,
-        pressure.moral,
-        pressure.resource,
-        pressure.psychological
-    );
-    let modifiers = endgame_state.modifiers();
-    println!(
-        "Endgame: {} | modifiers cost={:.2} risk={:.2}",
-        endgame_state.label(),
-        modifiers.cost_scale,
-        modifiers.risk_scale
-    );
-}
-
-fn apply_pressure_modifiers(
-    world: &mut WorldState,
-    pressure: &PressureState,
-    endgame_state: &EndgameState,
-) {
-    let base = pressure.to_modifiers();
-    world.pressure = endgame_state.apply_modifiers(base);
-}
-
-fn print_combat_status(state: &CombatState) {
-    if !state.active {
-        println!("Combat: inactive");
-        return;
-    }
-    let player_stress = state.player().map(|p| p.stress).unwrap_or(0);
-    let opponents = state.active_opponent_count();
-    println!(
-        "Combat: {:?} | tick={} | opponents={} | player_stress={}",
-        state.scale, state.tick, opponents, player_stress
-    );
-}
-
-fn print_combat_log(state: &CombatState) {
-    if state.log.is_empty() {
-        println!("Combat log: empty");
-        return;
-    }
-    println!("Combat log:");
-    for entry in state.log.iter() {
-        println!("  {}", entry);
-    }
-}
-
-fn format_combat_end(end: CombatEnd) -> &'static str {
-    match end {
-        CombatEnd::PlayerEscaped => "player escaped",
-        CombatEnd::PlayerDefeated => "player defeated",
-        CombatEnd::OpponentsDefeated => "opponents defeated",
-        CombatEnd::Resolved => "resolved",
-    }
-}
-
-fn parse_combat_scale(raw: &str) -> Option<CombatScale> {
-    match raw.to_lowercase().as_str() {
-        "street" => Some(CombatScale::Street),
-        "district" => Some(CombatScale::District),
-        "city" => Some(CombatScale::City),
-        "national" => Some(CombatScale::National),
-        "cosmic" => Some(CombatScale::Cosmic),
-        _ => None,
-    }
-}
-
-fn parse_combat_intent(raw: &str) -> Option<CombatIntent> {
-    match raw.to_lowercase().as_str() {
-        "attack" => Some(CombatIntent::Attack),
-        "escape" => Some(CombatIntent::Escape),
-        "hold" => Some(CombatIntent::Hold),
-        "capture" => Some(CombatIntent::Capture),
-        _ => None,
-    }
-}
-
-fn print_persona_state(
-    stack: &PersonaStack,
-    alignment: Alignment,
-    city: &CityState,
-    cases: &CaseRegistry,
-) {
-    println!(
-        "Alignment: {:?} | Active persona: {}",
-        alignment, stack.active_persona_id
-    );
-    if let Some(active) = stack.active_persona() {
-        println!(
-            "Active type: {:?} | Label: {}",
-            active.persona_type, active.label
-        );
-    }
-    for persona in &stack.personas {
-        println!(
-            "Persona {} ({:?}) -> public={} civilian={} wanted={} exposure={}",
-            persona.persona_id,
-            persona.persona_type,
-            persona.suspicion.public_suspicion,
-            persona.suspicion.civilian_suspicion,
-            persona.suspicion.wanted_level,
-            persona.suspicion.exposure_risk
-        );
-    }
-    let active_location = city.active_location;
-    let max_progress = cases
-        .cases
-        .iter()
-        .filter(|case| case.location_id == active_location)
-        .map(|case| case.progress)
-        .max()
-        .unwrap_or(0);
-    if let Some(location) = city.locations.get(&active_location) {
-        println!(
-            "Pressure: heat={} cases_max_progress={}",
-            location.heat, max_progress
-        );
-    }
-}
-
-fn print_growth_state(growth: &GrowthState) {
-    println!(
-        "Growth: mastery={} unlocked={} pressure_resistance={}",
-        growth.mastery.len(),
-        growth.unlocked_expressions.len(),
-        growth.pressure_resistance
-    );
-    println!(
-        "Reputation: trust={} fear={} infamy={} symbolism={}",
-        growth.reputation.trust,
-        growth.reputation.fear,
-        growth.reputation.infamy,
-        growth.reputation.symbolism
-    );
-}
-
-fn print_persona_stack(stack: &PersonaStack, turn: u64) {
-    println!("Persona stack:");
-    for persona in &stack.personas {
-        let cooldown = if turn < stack.next_switch_tick {
-            stack.next_switch_tick - turn
-        } else {
-            0
-        };
-        println!(
-            "  {} | {:?} | label={} | switch_cd={} turns",
-            persona.persona_id, persona.persona_type, persona.label, cooldown
-        );
-    }
-}
-
-struct AlignmentUnlockStatus {
-    has_power: bool,
-    has_wealth: bool,
-    unlocked: bool,
-}
-
-fn alignment_unlock_status(
-    origin_quest: &OriginQuestState,
-    growth: &GrowthState,
-    civilian_state: &CivilianState,
-) -> AlignmentUnlockStatus {
-    let has_power = origin_quest.completed || !growth.unlocked_expressions.is_empty();
-    let has_wealth =
-        civilian_state.wealth.tier.rank() >= WealthTier::Affluent.rank();
-    AlignmentUnlockStatus {
-        has_power,
-        has_wealth,
-        unlocked: has_power || has_wealth,
-    }
-}
-
-fn print_alignment_status(
-    alignment: Alignment,
-    origin_quest: &OriginQuestState,
-    growth: &GrowthState,
-    civilian_state: &CivilianState,
-) {
-    println!("Alignment: {:?}", alignment);
-    if alignment != Alignment::Neutral {
-        return;
+    CliPaths {
+        content_db: content_path,
+        world_db: world_path,
+        script,
+        export_json,
+        import_json,
+        diagnose,
     }
-    let status = alignment_unlock_status(origin_quest, growth, civilian_state);
-    println!(
-        "Alignment choice: power_unlocked={} wealth_unlocked={} -> {}",
-        status.has_power,
-        status.has_wealth,
-        if status.unlocked { "available" } else { "locked" }
-    );
 }
 
-fn apply_alignment_choice(
-    alignment: &mut Alignment,
-    persona_stack: &mut PersonaStack,
-    choice: Alignment,
-) {
-    *alignment = choice;
-    *persona_stack = match choice {
-        Alignment::Neutral => neutral_persona_stack(),
-        Alignment::Hero => hero_persona_stack(),
-        Alignment::Vigilante => vigilante_persona_stack(),
-        Alignment::Villain => villain_persona_stack(),
-    };
+fn import_world_json(
+    world_repo: &mut dyn WorldRepository,
+    path: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let json = std::fs::read_to_string(path)?;
+    let state = WorldDbState::from_json(&json)?;
+    world_repo.save_state(&state)
 }
 
-fn set_masked_label(stack: &mut PersonaStack, label: &str) -> bool {
-    if let Some(masked) = stack
-        .personas
-        .iter_mut()
-        .find(|persona| persona.persona_type == PersonaType::Masked)
-    {
-        masked.label = label.to_string();
-        return true;
-    }
-    false
+fn export_world_json(
+    world_repo: &mut dyn WorldRepository,
+    path: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (state, _) = world_repo.load_or_init()?;
+    std::fs::write(path, state.to_json()?)?;
+    Ok(())
 }
 
-fn initialize_mutant_lineage(
-    civilian_state: &mut CivilianState,
-    repo: &dyn PowerRepository,
-    seed: u64,
-) {
-    if civilian_state.life.mutant_gene {
-        if civilian_state.mutant_profile.parent_power_ids.is_empty() {
-            match seed_mutant_inheritance(repo, seed, 1) {
-                Ok(inheritance) => {
-                    civilian_state.mutant_profile.omega_parent = inheritance.omega_parent;
-                    civilian_state.mutant_profile.parent_power_ids = inheritance
-                        .parent_powers
-                        .iter()
-                        .map(|power| power.0)
-                        .collect();
-                }
-                Err(err) => {
-                    eprintln!("Failed to seed mutant inheritance: {}", err);
-                }
-            }
-        }
-        return;
-    }
-    if !civilian_state.mutant_profile.parent_power_ids.is_empty() {
-        civilian_state.life.mutant_gene = true;
-        return;
-    }
-
-    match roll_mutant_lineage(repo, seed) {
-        Ok(outcome) => {
-            if outcome.mutant_gene {
-                civilian_state.life.mutant_gene = true;
-                if let Some(inheritance) = outcome.inheritance {
-                    civilian_state.mutant_profile.omega_parent = inheritance.omega_parent;
-                    civilian_state.mutant_profile.parent_power_ids = inheritance
-                        .parent_powers
-                        .iter()
-                        .map(|power| power.0)
-                        .collect();
-                }
-            }
-        }
+/// Prints the save report for `--diagnose` and exits non-zero if it is unhealthy.
+fn diagnose_world_db(path: &Path) {
+    let report = match WorldDb::diagnose_path(path) {
+        Ok(report) => report,
         Err(err) => {
-            eprintln!("Failed to roll mutant lineage: {}", err);
+            eprintln!("Failed to diagnose {}: {}", path.display(), err);
+            std::process::exit(1);
         }
-    }
-}
-
-fn start_new_life(
-    actor: &mut ActorState,
-    growth: &mut GrowthState,
-    origin_quest: &mut OriginQuestState,
-    persona_stack: &mut PersonaStack,
-    alignment: &mut Alignment,
-    civilian_state: &mut CivilianState,
-    player_pos: &mut Position,
-    target: &mut TargetContext,
-    storylet_state: &mut StoryletState,
-    game_time: &GameTime,
-    repo: &dyn PowerRepository,
-    seed: u64,
-) {
-    *actor = ActorState {
-        stamina: 10,
-        focus: 10,
-        ..Default::default()
-    };
-    *growth = GrowthState::default();
-    *origin_quest = OriginQuestState::default();
-    let legacy = std::mem::take(&mut civilian_state.legacy);
-    *civilian_state = CivilianState::default();
-    civilian_state.legacy = legacy;
-    civilian_state.life.birth_day = game_time.day;
-    *alignment = Alignment::Neutral;
-    *persona_stack = neutral_persona_stack();
-    *player_pos = Position { x: 0, y: 0 };
-    *target = TargetContext {
-        distance_m: Some(10),
-        has_line_of_sight: true,
-        has_contact: false,
-        in_public: true,
-        witnesses: 0,
     };
-    storylet_state.flags.remove("alignment_choice_unlocked");
-    storylet_state.flags.remove("alignment_chosen");
-    storylet_state.fired.remove("alignment_choice_prompt");
-    storylet_state.cooldowns.remove("alignment_choice_prompt");
-    storylet_state.flags.remove("life.mutation_ready");
-    storylet_state.fired.remove("life_mutation_spark");
-    storylet_state.cooldowns.remove("life_mutation_spark");
-    apply_legacy_perks(civilian_state, storylet_state);
-    initialize_mutant_lineage(civilian_state, repo, seed);
-}
-
-fn apply_legacy_perks(
-    civilian_state: &mut CivilianState,
-    storylet_state: &mut StoryletState,
-) {
-    let Some(record) = civilian_state.legacy.last() else {
-        return;
-    };
-    let mut effects = Vec::new();
-
-    if record.wealth_tier.rank() >= WealthTier::Affluent.rank() {
-        effects.push("cash:+60".to_string());
-        storylet_state
-            .flags
-            .insert("legacy.rumor.wealth".to_string(), true);
-    }
-    if record.reputation.community >= 70 {
-        effects.push("community:+4".to_string());
-        storylet_state
-            .flags
-            .insert("legacy.rumor.community".to_string(), true);
-    }
-    if record.reputation.media >= 70 {
-        effects.push("media:+4".to_string());
-        storylet_state
-            .flags
-            .insert("legacy.rumor.media".to_string(), true);
-    }
-    if record.reputation.career >= 70 {
-        effects.push("career:+4".to_string());
-        storylet_state
-            .flags
-            .insert("legacy.rumor.career".to_string(), true);
-    }
-    if record.career_level >= 4 {
-        effects.push("career_xp:+8".to_string());
-    }
-    if record
-        .achievements
-        .iter()
-        .any(|entry| entry.eq_ignore_ascii_case("Power broker"))
-    {
-        effects.push("favors:+2".to_string());
-        storylet_state
-            .flags
-            .insert("legacy.rumor.power".to_string(), true);
-    }
-    if record
-        .achievements
-        .iter()
-        .any(|entry| entry.eq_ignore_ascii_case("Local influence"))
-    {
-        effects.push("access:+2".to_string());
-        storylet_state
-            .flags
-            .insert("legacy.rumor.influence".to_string(), true);
-    }
-    if record.alignment != "Neutral" {
-        storylet_state
-            .flags
-            .insert("legacy.rumor.masked".to_string(), true);
-    }
-
-    if !effects.is_empty() {
-        apply_civilian_effects(civilian_state, &effects);
-    }
-}
-
-fn process_civilian_death(
-    actor: &mut ActorState,
-    growth: &mut GrowthState,
-    origin_quest: &mut OriginQuestState,
-    persona_stack: &mut PersonaStack,
-    alignment: &mut Alignment,
-    civilian_state: &mut CivilianState,
-    player_pos: &mut Position,
-    target: &mut TargetContext,
-    storylet_state: &mut StoryletState,
-    game_time: &GameTime,
-    repo: &dyn PowerRepository,
-    seed: u64,
-    world: &mut WorldState,
-    pressure: &mut PressureState,
-    city: &CityState,
-    evidence: &WorldEvidence,
-    cases: &CaseRegistry,
-    endgame_state: &EndgameState,
-    combat: &CombatState,
-) -> bool {
-    if combat.active {
-        return false;
-    }
-    let Some(death) = civilian_state.pending_death.take() else {
-        return false;
-    };
-    let alignment_label = format!("{:?}", *alignment);
-    let record = civilian_state.record_legacy(&alignment_label, &death.reason, death.day);
-    start_new_life(
-        actor,
-        growth,
-        origin_quest,
-        persona_stack,
-        alignment,
-        civilian_state,
-        player_pos,
-        target,
-        storylet_state,
-        game_time,
-        repo,
-        seed,
-    );
-    update_pressure(pressure, city, evidence, cases, game_time);
-    apply_civilian_pressure(civilian_state, pressure);
-    apply_pressure_modifiers(world, pressure, endgame_state);
-    println!(
-        "Life ended at age {} ({}). New life started. Legacy recorded: {} achievements.",
-        record.age_years,
-        record.death_reason,
-        record.achievements.len()
-    );
-    true
-}
-
-fn maybe_awaken_mutant_powers(
-    civilian_state: &mut CivilianState,
-    repo: &dyn PowerRepository,
-    omni_catalog: &OmniPowerCatalog,
-    omni_registry: &mut OmniPowerRegistry,
-    config: &PowerAssignmentConfig,
-    seed: u64,
-) {
-    if !civilian_state.life.mutant_gene
-        || !civilian_state.life.mutation_ready
-        || civilian_state.mutant_profile.awakened
-    {
-        return;
-    }
-
-    if civilian_state.mutant_profile.parent_power_ids.is_empty() {
-        match seed_mutant_inheritance(repo, seed, 1) {
-            Ok(inheritance) => {
-                civilian_state.mutant_profile.omega_parent = inheritance.omega_parent;
-                civilian_state.mutant_profile.parent_power_ids = inheritance
-                    .parent_powers
-                    .iter()
-                    .map(|power| power.0)
-                    .collect();
-            }
-            Err(err) => {
-                eprintln!("Failed to seed mutant inheritance: {}", err);
-            }
-        }
-    }
-
-    let parent_powers: Vec<PowerId> = civilian_state
-        .mutant_profile
-        .parent_power_ids
-        .iter()
-        .map(|power_id| PowerId(*power_id))
-        .collect();
-    let inheritance = MutantInheritanceProfile {
-        parent_powers,
-        omega_parent: civilian_state.mutant_profile.omega_parent,
-    };
-
-    match assign_mutant_powers(
-        repo,
-        omni_catalog,
-        omni_registry,
-        "player",
-        "prime",
-        seed,
-        config.clone(),
-        Some(inheritance),
-    ) {
-        Ok(assignment) => {
-            civilian_state.mutant_profile.awakened = true;
-            civilian_state.mutant_profile.baseline_power_id =
-                assignment.baseline.map(|power| power.0);
-            civilian_state.mutant_profile.expression_power_ids = assignment
-                .expressions
-                .iter()
-                .map(|power| power.0)
-                .collect();
-            civilian_state.mutant_profile.omni_power_ids = assignment
-                .omni
-                .iter()
-                .map(|power| power.0)
-                .collect();
-
-            if let Ok(tier) = classify_mutant_tier(
-                repo,
-                assignment.baseline,
-                &assignment.expressions,
-                &assignment.omni,
-                civilian_state.mutant_profile.omega_parent,
-            ) {
-                civilian_state.mutant_profile.tier = tier;
-            }
-
-            let baseline_count = if assignment.baseline.is_some() { 1 } else { 0 };
-            println!(
-                "Mutation awakened: tier={} baseline={} expressions={} omni={}",
-                civilian_state.mutant_profile.tier_label(),
-                baseline_count,
-                assignment.expressions.len(),
-                assignment.omni.len()
-            );
-        }
-        Err(err) => {
-            eprintln!("Failed to assign mutant powers: {}", err);
-        }
-    }
-}
-
-fn handle_switch(
-    persona_id: &str,
-    stack: &mut PersonaStack,
-    turn: u64,
-    city: &CityState,
-    evidence: &WorldEvidence,
-    target: &TargetContext,
-) {
-    let location_id = city.active_location;
-    let Some(location) = city.locations.get(&location_id) else {
-        println!("Unknown location.");
-        return;
-    };
-    let witnesses = if target.in_public {
-        target.witnesses
-    } else {
-        0
-    };
-    let has_visual = evidence.signatures.iter().any(|event| {
-        event.location_id == location_id
-            && event.signature.signature.signature_type
-                == superhero_universe::rules::SignatureType::VisualAnomaly
-    });
-
-    match attempt_switch(stack, persona_id, turn, location, witnesses, has_visual) {
-        Ok(result) => {
-            println!(
-                "Switch succeeded: {} -> {:?}",
-                result.new_persona_id, result.new_persona_type
-            );
-            if result.suspicion_applied {
-                println!("Suspicion increased due to risky switch.");
-            }
-        }
-        Err(err) => {
-            print_switch_error(err);
-        }
-    }
-}
-
-fn print_switch_error(err: PersonaSwitchError) {
-    let reason = match err {
-        PersonaSwitchError::UnknownPersona => "Unknown persona",
-        PersonaSwitchError::AlreadyActive => "Already active",
-        PersonaSwitchError::SwitchBlockedByWitnesses => "Blocked by witnesses",
-        PersonaSwitchError::SwitchBlockedByLocation => "Blocked by location",
-        PersonaSwitchError::SwitchOnCooldown => "Switch on cooldown",
-    };
-    println!("Switch failed: {}", reason);
-}
-
-fn print_location(city: &CityState) {
-    let Some(location) = city.locations.get(&city.active_location) else {
-        println!("Location not found.");
-        return;
-    };
-    println!(
-        "Location {} | heat={} crime_pressure={} response={:?}",
-        location.id.0, location.heat, location.crime_pressure, location.response
-    );
-    println!(
-        "Security: police_presence={} surveillance_level={} lockdown_level={}",
-        location.police_presence, location.surveillance_level, location.lockdown_level
-    );
-    println!(
-        "Units: police_units={} investigators={} gang_units={}",
-        location.police_units, location.investigators, location.gang_units
-    );
-    if !location.faction_influence.is_empty() {
-        println!("Influence:");
-        for (faction, influence) in &location.faction_influence {
-            println!("  {} -> {}", faction, influence);
-        }
-    }
-}
-
-fn print_faction_events(events: &mut ResolvedFactionEventLog) {
-    if events.0.is_empty() {
-        println!("Faction events: none");
-        return;
-    }
-    println!("Faction events:");
-    for event in events.0.drain(..) {
-        println!(
-            "  {} -> {} at loc {} (level {})",
-            event.faction_id, event.faction_type_id, event.location_id.0, event.level
-        );
-        for action in event.actions {
-            println!("    action {}", action.kind);
-        }
-    }
-}
-
-fn print_cases(cases: &CaseRegistry) {
-    if cases.cases.is_empty() {
-        println!("Cases: none");
-        return;
-    }
-    println!("Cases:");
-    for case in &cases.cases {
-        println!(
-            "  Case {} | faction={} loc={} progress={} status={:?} target={:?}",
-            case.case_id,
-            case.faction_id,
-            case.location_id.0,
-            case.progress,
-            case.status,
-            case.target_type
-        );
-        if !case.signature_pattern.is_empty() {
-            println!("    signatures: {:?}", case.signature_pattern);
-        }
-    }
-}
-
-fn print_case_log(log: &mut CaseEventLog) {
-    if log.0.is_empty() {
-        return;
-    }
-    println!("Case events:");
-    for entry in log.0.drain(..) {
-        println!("  {}", entry);
-    }
-}
-
-fn update_context(target: &mut TargetContext, field: &str, value: &str) -> Result<(), ()> {
-    match field {
-        "dist" | "distance" => {
-            let dist = value.parse::<i64>().map_err(|_| ())?;
-            target.distance_m = Some(dist);
-            Ok(())
-        }
-        "los" => match value {
-            "on" | "true" => {
-                target.has_line_of_sight = true;
-                Ok(())
-            }
-            "off" | "false" => {
-                target.has_line_of_sight = false;
-                Ok(())
-            }
-            _ => Err(()),
-        },
-        "contact" => match value {
-            "on" | "true" => {
-                target.has_contact = true;
-                Ok(())
-            }
-            "off" | "false" => {
-                target.has_contact = false;
-                Ok(())
-            }
-            _ => Err(()),
-        },
-        "public" => match value {
-            "on" | "true" => {
-                target.in_public = true;
-                Ok(())
-            }
-            "off" | "false" => {
-                target.in_public = false;
-                Ok(())
-            }
-            _ => Err(()),
-        },
-        "witnesses" => {
-            let count = value.parse::<u32>().map_err(|_| ())?;
-            target.witnesses = count;
-            Ok(())
-        }
-        _ => Err(()),
-    }
-}
-
-fn print_cooldowns(actor: &ActorState) {
-    if actor.cooldowns.is_empty() {
-        println!("Cooldowns: none");
-        return;
-    }
-    println!("Cooldowns:");
-    for (expr_id, turns) in actor.cooldowns.iter() {
-        println!("  {} -> {}", expr_id.0, turns);
-    }
-}
-
-fn print_scene(scene: &WorldEvidence) {
-    if scene.signatures.is_empty() {
-        println!("Scene evidence: none");
-        return;
-    }
-    println!("Scene evidence:");
-    for item in &scene.signatures {
-        println!(
-            "  loc={} {:?} strength={} remaining={}",
-            item.location_id.0,
-            item.signature.signature.signature_type,
-            item.signature.signature.strength,
-            item.signature.remaining_turns
-        );
-    }
-}
-
-fn print_civilian_status(state: &CivilianState, time: &GameTime) {
-    let pressure = state.pressure_targets();
-    let rewards = state.effective_rewards();
-    let (career_xp, career_needed) = state.career_progress();
-    println!("Civilian status @ {}:", time.to_string());
-    println!(
-        "  Job: {:?} ({:?} L{} | satisfaction={} stability={} | career_xp {}/{})",
-        state.job_status,
-        state.job.role,
-        state.job.level,
-        state.job.satisfaction,
-        state.job.stability,
-        career_xp,
-        career_needed
-    );
-    println!(
-        "  Life: age={} stage={:?} mutant_gene={} mutation_ready={}",
-        state.life.age_years,
-        state.life.life_stage,
-        state.life.mutant_gene,
-        state.life.mutation_ready
-    );
-    if state.life.mutant_gene || state.mutant_profile.awakened {
-        let baseline_count = if state.mutant_profile.baseline_power_id.is_some() {
-            1
-        } else {
-            0
-        };
-        println!(
-            "  Mutant: awakened={} tier={} parent_powers={} baseline={} expressions={} omni={}",
-            state.mutant_profile.awakened,
-            state.mutant_profile.tier_label(),
-            state.mutant_profile.parent_power_ids.len(),
-            baseline_count,
-            state.mutant_profile.expression_power_ids.len(),
-            state.mutant_profile.omni_power_ids.len()
-        );
-    }
-    println!(
-        "  Mortality: risk={} legacy_count={}",
-        state.life.mortality_risk,
-        state.legacy.len()
-    );
-    println!(
-        "  Risk: mistake={} tech_access={}",
-        state.mistake_risk,
-        state.tech_access_score()
-    );
-    println!(
-        "  Education: level={:?} progress={} attendance={} dropout_risk={} credits={} enrolled={}",
-        state.education.level,
-        state.education.progress,
-        state.education.attendance,
-        state.education.dropout_risk,
-        state.education.credits,
-        state.education.is_enrolled
-    );
-    println!(
-        "  Health: stress={} sleep_debt={} fitness={} injuries={}",
-        state.health.stress,
-        state.health.sleep_debt,
-        state.health.fitness,
-        state.health.injuries
-    );
-    println!("  Routine: {}", state.routine_summary());
-    println!(
-        "  Finances: cash={} debt={} rent={} rent_due_in={} wage={}",
-        state.finances.cash,
-        state.finances.debt,
-        state.finances.rent,
-        state.finances.rent_due_in,
-        state.finances.wage
-    );
-    println!(
-        "  Housing: neighborhood={} rent={} stability={} safety={} privacy={} relocation_cd={}",
-        state.housing.neighborhood_id,
-        state.housing.rent,
-        state.housing.stability,
-        state.housing.safety,
-        state.housing.privacy,
-        state.housing.relocation_cooldown
-    );
-    println!(
-        "  Wealth: tier={:?} profile={} net_worth={}CR liquidity={:.2} income={}CR upkeep={}CR",
-        state.wealth.tier,
-        state.wealth_profile.label(),
-        state.net_worth_cr(),
-        state.wealth.liquidity,
-        state.wealth.income_per_tick,
-        state.wealth.upkeep_per_tick
-    );
-    println!(
-        "  Social: support={} strain={} obligation={}",
-        state.social.support, state.social.strain, state.social.obligation
-    );
-    println!(
-        "  Reputation: career={} community={} media={}",
-        state.reputation.career, state.reputation.community, state.reputation.media
-    );
-    println!("  Civilian tier: {:?}", state.civilian_tier);
-    println!(
-        "  Social web: professional={} community={} media={} underground={}",
-        state.social_web.professional,
-        state.social_web.community,
-        state.social_web.media,
-        state.social_web.underground
-    );
-    println!(
-        "  Rewards: income_boost={} safehouse={} access={} intel={} favors={}",
-        rewards.income_boost, rewards.safehouse, rewards.access, rewards.intel, rewards.favors
-    );
-    if !state.network_rewards.is_empty() {
-        println!(
-            "  Network bonus: income_boost={} safehouse={} access={} intel={} favors={}",
-            state.network_rewards.income_boost,
-            state.network_rewards.safehouse,
-            state.network_rewards.access,
-            state.network_rewards.intel,
-            state.network_rewards.favors
-        );
-    }
-    if state.contacts.is_empty() {
-        println!("  Contacts: none");
-    } else {
-        println!("  Contacts:");
-        for contact in &state.contacts {
-            println!(
-                "    {} -> {:?} {:?} {:?} (bond={} influence={} last_day={})",
-                contact.name,
-                contact.domain,
-                contact.relation_type,
-                contact.level,
-                contact.bond,
-                contact.influence,
-                contact.last_interaction_day
-            );
-        }
-    }
-    println!(
-        "  Civilian pressure targets: temporal={:.1} resource={:.1} moral={:.1} identity={:.1}",
-        pressure.temporal, pressure.resource, pressure.moral, pressure.identity
-    );
-}
-
-fn print_civilian_preferences(state: &CivilianState) {
-    let settings = &state.event_settings;
-    let muted: Vec<&str> = settings
-        .muted_categories
-        .iter()
-        .map(|category| event_category_label(*category))
-        .collect();
-    let muted_label = if muted.is_empty() {
-        "none".to_string()
-    } else {
-        muted.join(", ")
-    };
-    let rent_auto = match state.auto_choices.pay_rent_if_cash_at_least {
-        Some(value) => format!("cash >= {}", value),
-        None => "off".to_string(),
-    };
-    let rest_auto = match state.auto_choices.rest_if_sleep_debt_at_least {
-        Some(value) => format!("sleep_debt >= {}", value),
-        None => "off".to_string(),
-    };
-    println!("Civilian prefs:");
-    println!(
-        "  Filters: repeat_suppress_days={} min_effect_magnitude={} muted=[{}]",
-        settings.suppress_repeat_days, settings.min_effect_magnitude, muted_label
-    );
-    println!(
-        "  Auto: rent_pay={} school_attend={} rest_if_sleep_debt={}",
-        rent_auto,
-        if state.auto_choices.attend_school {
-            "on"
-        } else {
-            "off"
-        },
-        rest_auto
-    );
-}
-
-fn contact_domain_label(domain: ContactDomain) -> &'static str {
-    match domain {
-        ContactDomain::Professional => "professional",
-        ContactDomain::Community => "community",
-        ContactDomain::Media => "media",
-        ContactDomain::Underground => "underground",
-    }
-}
-
-fn relation_type_label(relation_type: RelationType) -> &'static str {
-    match relation_type {
-        RelationType::Family => "family",
-        RelationType::Mentor => "mentor",
-        RelationType::Rival => "rival",
-        RelationType::Romance => "romance",
-        RelationType::Colleague => "colleague",
-        RelationType::Peer => "peer",
-    }
-}
-
-fn event_category_label(category: CivilianEventCategory) -> &'static str {
-    match category {
-        CivilianEventCategory::Work => "work",
-        CivilianEventCategory::School => "school",
-        CivilianEventCategory::Health => "health",
-        CivilianEventCategory::Housing => "housing",
-        CivilianEventCategory::Social => "social",
-        CivilianEventCategory::Opportunity => "opportunity",
-        CivilianEventCategory::Crime => "crime",
-        CivilianEventCategory::Routine => "routine",
-        CivilianEventCategory::Other => "other",
-    }
-}
-
-fn parse_event_category(value: &str) -> Option<CivilianEventCategory> {
-    match value.trim().to_ascii_lowercase().as_str() {
-        "work" => Some(CivilianEventCategory::Work),
-        "school" => Some(CivilianEventCategory::School),
-        "health" => Some(CivilianEventCategory::Health),
-        "housing" => Some(CivilianEventCategory::Housing),
-        "social" => Some(CivilianEventCategory::Social),
-        "opportunity" | "opp" => Some(CivilianEventCategory::Opportunity),
-        "crime" => Some(CivilianEventCategory::Crime),
-        "routine" => Some(CivilianEventCategory::Routine),
-        "other" => Some(CivilianEventCategory::Other),
-        _ => None,
-    }
-}
-
-fn event_category_for_id(event_id: &str) -> CivilianEventCategory {
-    match event_id {
-        "civilian_work_shift" => CivilianEventCategory::Work,
-        "civilian_school_day" => CivilianEventCategory::School,
-        "civilian_hobby_session" => CivilianEventCategory::Routine,
-        "civilian_health_checkin" => CivilianEventCategory::Health,
-        "civilian_rent_due" | "civilian_relocation_offer" => CivilianEventCategory::Housing,
-        "civilian_crime_quick_hit" => CivilianEventCategory::Crime,
-        "civilian_job_offer"
-        | "civilian_deadline_crunch"
-        | "civilian_side_gig"
-        | "civilian_mentor_session"
-        | "civilian_colleague_project" => CivilianEventCategory::Opportunity,
-        "civilian_relationship_checkin"
-        | "civilian_contact_checkin"
-        | "civilian_contact_family"
-        | "civilian_contact_mentor"
-        | "civilian_contact_colleague"
-        | "civilian_contact_romance"
-        | "civilian_contact_rival"
-        | "civilian_family_obligation"
-        | "civilian_romance_date"
-        | "civilian_rival_runin"
-        | "civilian_social_favor"
-        | "civilian_trust_test"
-        | "civilian_community_meeting"
-        | "civilian_civic_protest"
-        | "civilian_safehouse_tip" => CivilianEventCategory::Social,
-        _ => CivilianEventCategory::Other,
-    }
-}
-
-fn is_crisis_event_id(event_id: &str) -> bool {
-    matches!(
-        event_id,
-        "civilian_rent_due"
-            | "civilian_relocation_offer"
-            | "civilian_health_checkin"
-            | "civilian_crime_quick_hit"
-    )
-}
-
-fn effect_magnitude(effects: &[String]) -> i32 {
-    let mut total = 0i32;
-    for effect in effects {
-        for part in effect.split(':') {
-            if let Ok(value) = part.trim().parse::<i32>() {
-                total = total.saturating_add(value.abs());
-            }
-        }
-    }
-    total
-}
-
-fn event_effect_magnitude(def: &CivilianStorylet) -> i32 {
-    let base = effect_magnitude(&def.effects);
-    let choice_max = def
-        .choices
-        .iter()
-        .map(|choice| effect_magnitude(&choice.effects))
-        .max()
-        .unwrap_or(0);
-    base.saturating_add(choice_max)
-}
-
-fn should_show_civilian_event(
-    state: &CivilianState,
-    def: &CivilianStorylet,
-    event: &CivilianEvent,
-    time: &GameTime,
-) -> bool {
-    if is_crisis_event_id(&event.storylet_id) {
-        return true;
-    }
-    let settings = &state.event_settings;
-    let category = event_category_for_id(&event.storylet_id);
-    if settings.is_muted(category) && default_choice_for_event(&event.storylet_id).is_some() {
-        return false;
-    }
-    if settings.min_effect_magnitude > 0
-        && event_effect_magnitude(def) < settings.min_effect_magnitude
-    {
-        return false;
-    }
-    if settings.suppress_repeat_days > 0 {
-        if let Some(last_day) = state.last_event_seen_day(&def.id) {
-            let since = time.day.saturating_sub(last_day);
-            if since < settings.suppress_repeat_days {
-                return false;
-            }
-        }
-    }
-    true
-}
-
-fn render_civilian_text(
-    template: &str,
-    state: &CivilianState,
-    event: &CivilianEvent,
-) -> String {
-    let Some(contact_name) = event.contact_name.as_deref() else {
-        return template.to_string();
-    };
-    let mut output = template
-        .replace("{contact}", contact_name)
-        .replace("{contact_name}", contact_name);
-    if let Some(contact) = state.contacts.iter().find(|entry| entry.name == contact_name) {
-        output = output.replace("{relation}", relation_type_label(contact.relation_type));
-        output = output.replace("{domain}", contact_domain_label(contact.domain));
-    }
-    output
-}
-
-fn expand_civilian_effects(
-    effects: &[String],
-    state: &CivilianState,
-    event: &CivilianEvent,
-) -> Vec<String> {
-    effects
-        .iter()
-        .map(|effect| render_civilian_text(effect, state, event))
-        .collect()
-}
-
-fn print_civilian_event_details(
-    state: &CivilianState,
-    event: &CivilianEvent,
-    def: &CivilianStorylet,
-) {
-    if def.details.is_empty() {
-        return;
-    }
-    println!("    details:");
-    for detail in &def.details {
-        let line = render_civilian_text(detail, state, event);
-        println!("      - {}", line);
-    }
-}
-
-fn print_civilian_event_entry(
-    state: &CivilianState,
-    event: &CivilianEvent,
-    def: &CivilianStorylet,
-    time: &GameTime,
-    show_details: bool,
-) {
-    let age = time.tick.saturating_sub(event.created_tick);
-    let title = render_civilian_text(&def.title, state, event);
-    let summary = render_civilian_text(&def.text_stub, state, event);
-    println!("  {} | {} | queued {} ticks ago", def.id, title, age);
-    println!("    {}", summary);
-    if show_details {
-        print_civilian_event_details(state, event, def);
-    }
-    for choice in &def.choices {
-        let text = render_civilian_text(&choice.text, state, event);
-        println!("    [{}] {}", choice.id, text);
-    }
-    if !show_details && !def.details.is_empty() {
-        println!("    (see more: civilian detail {})", def.id);
-    }
-}
-
-fn print_pending_civilian_events(
-    state: &mut CivilianState,
-    library: &[CivilianStorylet],
-    time: &GameTime,
-    show_details: bool,
-) {
-    if state.pending_events.is_empty() {
-        println!("Civilian events: none");
-        return;
-    }
-    println!("Civilian events:");
-    let pending = state.pending_events.clone();
-    let mut suppressed = 0usize;
-    for event in &pending {
-        match find_civilian_event(library, &event.storylet_id) {
-            Some(def) => {
-                if !should_show_civilian_event(state, def, event, time) {
-                    suppressed += 1;
-                    continue;
-                }
-                print_civilian_event_entry(state, event, def, time, show_details);
-                state.mark_event_seen(&def.id, time.day);
-            }
-            None => {
-                let age = time.tick.saturating_sub(event.created_tick);
-                println!(
-                    "  {} | (missing definition) | queued {} ticks ago",
-                    event.storylet_id, age
-                );
-            }
-        }
-    }
-    if suppressed > 0 {
-        println!("  (suppressed {} events by filters)", suppressed);
-    }
-}
-
-fn print_civilian_event_detail(
-    state: &mut CivilianState,
-    library: &[CivilianStorylet],
-    time: &GameTime,
-    event_id: &str,
-) {
-    let Some(event) = state
-        .pending_events
-        .iter()
-        .find(|event| event.storylet_id == event_id)
-    else {
-        println!("Civilian event not pending: {}", event_id);
-        return;
-    };
-    let Some(def) = find_civilian_event(library, event_id) else {
-        println!("Civilian event missing definition: {}", event_id);
-        return;
-    };
-    println!("Civilian event detail:");
-    print_civilian_event_entry(state, event, def, time, true);
-    state.mark_event_seen(&def.id, time.day);
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum AutoResolveMode {
-    Normal,
-    SkipRent,
-    SkipCrises,
-}
-
-fn auto_resolve_allowed(event_id: &str, mode: AutoResolveMode) -> bool {
-    match mode {
-        AutoResolveMode::Normal => true,
-        AutoResolveMode::SkipRent => event_id != "civilian_rent_due",
-        AutoResolveMode::SkipCrises => !is_crisis_event_id(event_id),
-    }
-}
-
-fn default_choice_for_event(event_id: &str) -> Option<&'static str> {
-    match event_id {
-        "civilian_work_shift" => Some("work"),
-        "civilian_school_day" => Some("attend"),
-        _ => None,
-    }
-}
-
-fn select_auto_choice(
-    state: &CivilianState,
-    event_id: &str,
-) -> Option<&'static str> {
-    let category = event_category_for_id(event_id);
-    if state.event_settings.is_muted(category) {
-        if let Some(choice) = default_choice_for_event(event_id) {
-            return Some(choice);
-        }
-    }
-    match event_id {
-        "civilian_rent_due" => {
-            let threshold = state.auto_choices.pay_rent_if_cash_at_least?;
-            if state.finances.cash >= threshold && state.finances.cash >= state.finances.rent {
-                Some("pay")
-            } else {
-                None
-            }
-        }
-        "civilian_school_day" => {
-            if state.auto_choices.attend_school {
-                Some("attend")
-            } else {
-                None
-            }
-        }
-        "civilian_health_checkin" => {
-            let threshold = state.auto_choices.rest_if_sleep_debt_at_least?;
-            if state.health.sleep_debt >= threshold {
-                Some("rest")
-            } else {
-                None
-            }
-        }
-        _ => None,
-    }
-}
-
-fn auto_resolve_civilian_events(
-    state: &mut CivilianState,
-    library: &[CivilianStorylet],
-    origin_quest: &mut OriginQuestState,
-    origin_paths: &OriginPathCatalog,
-    pressure: &mut PressureState,
-    mode: AutoResolveMode,
-) -> usize {
-    if state.pending_events.is_empty() {
-        return 0;
-    }
-    let mut resolved = 0usize;
-    let mut index = 0usize;
-    while index < state.pending_events.len() {
-        let event_id = state.pending_events[index].storylet_id.clone();
-        if !auto_resolve_allowed(&event_id, mode) {
-            index += 1;
-            continue;
-        }
-        let Some(choice_id) = select_auto_choice(state, &event_id) else {
-            index += 1;
-            continue;
-        };
-        let Some(def) = find_civilian_event(library, &event_id) else {
-            index += 1;
-            continue;
-        };
-        if !def.choices.iter().any(|choice| choice.id == choice_id) {
-            index += 1;
-            continue;
-        }
-        resolve_civilian_event(
-            state,
-            library,
-            origin_quest,
-            origin_paths,
-            pressure,
-            &event_id,
-            choice_id,
-            false,
-        );
-        resolved += 1;
-    }
-    resolved
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum TickMode {
-    Count(u32),
-    NextEvent,
-    NextRent,
-    CrisisOnly,
-    SkipDays(u32),
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum TickStopReason {
-    Completed,
-    PendingEvent,
-    RentDue,
-    Crisis,
-    Storylet,
-    Death,
-    MaxTicks,
-}
-
-fn tick_stop_reason_label(reason: TickStopReason) -> &'static str {
-    match reason {
-        TickStopReason::Completed => "completed",
-        TickStopReason::PendingEvent => "pending event",
-        TickStopReason::RentDue => "rent due",
-        TickStopReason::Crisis => "crisis",
-        TickStopReason::Storylet => "storylet",
-        TickStopReason::Death => "death",
-        TickStopReason::MaxTicks => "max ticks",
-    }
-}
-
-fn has_visible_pending_civilian_events(
-    state: &CivilianState,
-    library: &[CivilianStorylet],
-    time: &GameTime,
-) -> bool {
-    for event in &state.pending_events {
-        match find_civilian_event(library, &event.storylet_id) {
-            Some(def) => {
-                if should_show_civilian_event(state, def, event, time) {
-                    return true;
-                }
-            }
-            None => return true,
-        }
-    }
-    false
-}
-
-fn has_crisis_pending(state: &CivilianState) -> bool {
-    state
-        .pending_events
-        .iter()
-        .any(|event| is_crisis_event_id(&event.storylet_id))
-}
-
-fn has_rent_due_pending(state: &CivilianState) -> bool {
-    state
-        .pending_events
-        .iter()
-        .any(|event| event.storylet_id == "civilian_rent_due")
-}
-
-fn resolve_civilian_event(
-    state: &mut CivilianState,
-    library: &[CivilianStorylet],
-    origin_quest: &mut OriginQuestState,
-    origin_paths: &OriginPathCatalog,
-    pressure: &mut PressureState,
-    event_id: &str,
-    choice_id: &str,
-    announce: bool,
-) {
-    let Some(event_def) = find_civilian_event(library, event_id) else {
-        if announce {
-            println!("Unknown civilian event: {}", event_id);
-        }
-        return;
-    };
-    let pending_event = state
-        .pending_events
-        .iter()
-        .find(|event| event.storylet_id == event_id)
-        .cloned()
-        .unwrap_or(CivilianEvent {
-            storylet_id: event_id.to_string(),
-            created_tick: 0,
-            contact_name: None,
-        });
-    let Some(choice) = event_def
-        .choices
-        .iter()
-        .find(|choice| choice.id == choice_id)
-    else {
-        if announce {
-            println!("Unknown choice {} for event {}", choice_id, event_id);
-        }
-        return;
-    };
-    let event_effects = expand_civilian_effects(&event_def.effects, state, &pending_event);
-    let choice_effects = expand_civilian_effects(&choice.effects, state, &pending_event);
-    let mut applied = apply_civilian_effects(state, &event_effects);
-    applied.extend(apply_civilian_effects(state, &choice_effects));
-    let mut all_effects = Vec::new();
-    all_effects.extend(event_effects.iter().cloned());
-    all_effects.extend(choice_effects.iter().cloned());
-    let origin_effects = apply_origin_effects(
-        parse_origin_effects(&all_effects),
-        origin_quest,
-        origin_paths,
-    );
-    if let Some(index) = state
-        .pending_events
-        .iter()
-        .position(|event| event.storylet_id == event_id)
-    {
-        state.pending_events.remove(index);
-    }
-    if announce {
-        println!("Resolved {} -> {}", event_def.title, choice.text);
-        if applied.is_empty() {
-            println!("No civilian effects applied.");
-        } else {
-            println!("Applied effects:");
-            for entry in applied {
-                println!("  {}", entry);
-            }
-        }
-        if !origin_effects.messages.is_empty() {
-            println!("Origin updates:");
-            for entry in origin_effects.messages {
-                println!("  {}", entry);
-            }
-        }
-    }
-    state.mark_event_seen(event_id, state.last_day);
-    apply_origin_rewards(origin_effects.rewards.as_slice(), pressure);
-}
-
-fn resolve_global_event(
-    state: &mut GlobalEventState,
-    catalog: &[GlobalEventDefinition],
-    pressure: &mut PressureState,
-    nemesis: &mut NemesisState,
-    storylet_state: &mut StoryletState,
-    event_id: &str,
-    choice_id: &str,
-) {
-    let Some(event_def) = find_global_event(catalog, event_id) else {
-        println!("Unknown global event: {}", event_id);
-        return;
-    };
-    let Some(choice) = event_def
-        .choices
-        .iter()
-        .find(|choice| choice.id == choice_id)
-    else {
-        println!("Unknown choice {} for event {}", choice_id, event_id);
-        return;
-    };
-
-    let mut applied = Vec::new();
-    let mut effects = Vec::new();
-    effects.extend(event_def.effects.iter().cloned());
-    effects.extend(choice.effects.iter().cloned());
-    applied.extend(apply_global_event_effects(
-        &effects,
-        pressure,
-        nemesis,
-        storylet_state,
-    ));
-
-    if let Some(index) = state
-        .pending
-        .iter()
-        .position(|event| event.event_id == event_id)
-    {
-        state.pending.remove(index);
-    }
-    state.fired.insert(event_def.id.clone());
-    storylet_state
-        .flags
-        .insert(format!("global_event_{}", event_def.id), true);
-    storylet_state
-        .flags
-        .insert("global_event".to_string(), true);
-
-    println!("Resolved {} -> {}", event_def.title, choice.text);
-    if applied.is_empty() {
-        println!("No global effects applied.");
-    } else {
-        println!("Applied global effects:");
-        for entry in applied {
-            println!("  {}", entry);
-        }
-    }
-}
-
-fn find_global_event<'a>(
-    catalog: &'a [GlobalEventDefinition],
-    event_id: &str,
-) -> Option<&'a GlobalEventDefinition> {
-    catalog.iter().find(|event| event.id == event_id)
-}
-
-fn apply_global_event_effects(
-    effects: &[String],
-    pressure: &mut PressureState,
-    nemesis: &mut NemesisState,
-    storylet_state: &mut StoryletState,
-) -> Vec<String> {
-    let mut applied = Vec::new();
-    for effect in effects {
-        let mut parts = effect.split(':');
-        let key = parts.next().unwrap_or("").trim();
-        match key {
-            "pressure.temporal" => {
-                apply_pressure_delta(&mut pressure.temporal, parts.next(), "pressure.temporal", &mut applied);
-            }
-            "pressure.identity" => {
-                apply_pressure_delta(&mut pressure.identity, parts.next(), "pressure.identity", &mut applied);
-            }
-            "pressure.institutional" => {
-                apply_pressure_delta(
-                    &mut pressure.institutional,
-                    parts.next(),
-                    "pressure.institutional",
-                    &mut applied,
-                );
-            }
-            "pressure.moral" => {
-                apply_pressure_delta(&mut pressure.moral, parts.next(), "pressure.moral", &mut applied);
-            }
-            "pressure.resource" => {
-                apply_pressure_delta(
-                    &mut pressure.resource,
-                    parts.next(),
-                    "pressure.resource",
-                    &mut applied,
-                );
-            }
-            "pressure.psychological" => {
-                apply_pressure_delta(
-                    &mut pressure.psychological,
-                    parts.next(),
-                    "pressure.psychological",
-                    &mut applied,
-                );
-            }
-            "nemesis.threat" => {
-                if let Some(delta_raw) = parts.next() {
-                    if let Ok(delta) = delta_raw.trim().parse::<i32>() {
-                        nemesis.apply_global_threat(delta);
-                        applied.push(format!("nemesis threat {:+}", delta));
-                    }
-                }
-            }
-            "storylet.flag" => {
-                if let Some(flag) = parts.next().map(str::trim).filter(|flag| !flag.is_empty()) {
-                    storylet_state.flags.insert(flag.to_string(), true);
-                    applied.push(format!("storylet flag {}", flag));
-                }
-            }
-            _ => {}
-        }
-    }
-    applied
-}
-
-fn apply_pressure_delta(
-    target: &mut f32,
-    value: Option<&str>,
-    label: &str,
-    applied: &mut Vec<String>,
-) {
-    let Some(value) = value else {
-        return;
-    };
-    if let Ok(delta) = value.trim().parse::<f32>() {
-        *target = (*target + delta).clamp(0.0, 100.0);
-        applied.push(format!("{} {:+.1}", label, delta));
-    }
-}
-
-fn set_storylet_flag(state: &mut StoryletState, flag: &str, enabled: bool) {
-    if enabled {
-        state.flags.insert(flag.to_string(), true);
-    } else {
-        state.flags.remove(flag);
-    }
-}
-
-fn update_social_storylet_flags(civilian: &CivilianState, storylet_state: &mut StoryletState) {
-    let leverage = civilian.social_leverage_score();
-    let protection = civilian.social_protection_score();
-    let vulnerability = civilian.social_vulnerability_score();
-    set_storylet_flag(storylet_state, "social.leverage", leverage >= 60);
-    set_storylet_flag(storylet_state, "social.protected", protection >= 60);
-    set_storylet_flag(storylet_state, "social.vulnerable", vulnerability >= 60);
-}
-
-fn update_reputation_storylet_flags(civilian: &CivilianState, storylet_state: &mut StoryletState) {
-    let public_rep = civilian.public_reputation_score();
-    set_storylet_flag(storylet_state, "reputation.public_high", public_rep >= 70);
-    set_storylet_flag(storylet_state, "reputation.public_low", public_rep <= 30);
-    set_storylet_flag(
-        storylet_state,
-        "reputation.community_high",
-        civilian.reputation.community >= 70,
-    );
-    set_storylet_flag(
-        storylet_state,
-        "reputation.career_high",
-        civilian.reputation.career >= 70,
-    );
-}
-
-fn update_tech_storylet_flags(civilian: &CivilianState, storylet_state: &mut StoryletState) {
-    let tier_rank = civilian.wealth.tier.rank();
-    set_storylet_flag(
-        storylet_state,
-        "tech.access_basic",
-        tier_rank >= WealthTier::Working.rank(),
-    );
-    set_storylet_flag(
-        storylet_state,
-        "tech.access_advanced",
-        tier_rank >= WealthTier::Affluent.rank(),
-    );
-    set_storylet_flag(
-        storylet_state,
-        "tech.access_prototype",
-        tier_rank >= WealthTier::Wealthy.rank(),
-    );
-    set_storylet_flag(
-        storylet_state,
-        "tech.access_arsenal",
-        tier_rank >= WealthTier::UltraWealthy.rank(),
-    );
-}
-
-fn apply_tech_capability(actor: &mut ActorState, civilian: &CivilianState) {
-    let tech_access = civilian.tech_access_score();
-    let resource_budget = (tech_access / 12).max(0) as i64;
-    actor
-        .resources
-        .insert("resource".to_string(), resource_budget);
-}
-
-fn apply_public_reputation_attention(
-    civilian: &mut CivilianState,
-    city: &mut CityState,
-    time: &GameTime,
-) {
-    if civilian.last_reputation_heat_day == time.day {
-        return;
-    }
-    civilian.last_reputation_heat_day = time.day;
-    let public_rep = civilian.public_reputation_score();
-    if public_rep < 70 || time.day % 7 != 0 {
-        return;
-    }
-    let bonus = 1 + ((public_rep - 70) / 20).clamp(0, 2);
-    if let Some(location) = city.locations.get_mut(&city.active_location) {
-        location.heat = (location.heat + bonus).clamp(0, 100);
-    }
-}
-
-fn apply_civilian_mistake_consequences(
-    civilian: &mut CivilianState,
-    city: &mut CityState,
-    pressure: &mut PressureState,
-    time: &GameTime,
-) {
-    if civilian.last_mistake_day == time.day {
-        return;
-    }
-    if civilian.mistake_risk < 65 {
-        return;
-    }
-    civilian.last_mistake_day = time.day;
-    let public_rep = civilian.public_reputation_score();
-    let visibility_bonus = if public_rep >= 85 {
-        2
-    } else if public_rep >= 70 {
-        1
-    } else {
-        0
-    };
-    let heat_delta = ((civilian.mistake_risk - 55) / 15).clamp(1, 6) + visibility_bonus;
-    if let Some(location) = city.locations.get_mut(&city.active_location) {
-        location.heat = (location.heat + heat_delta).clamp(0, 100);
-    }
-    let identity_delta = (civilian.mistake_risk as f32 * 0.05).clamp(1.0, 8.0);
-    pressure.identity = (pressure.identity + identity_delta).clamp(0.0, 100.0);
-}
-
-fn apply_cast_aging(
-    characters: &mut Vec<PersistentCharacter>,
-    world_repo: &mut dyn WorldRepository,
-    current_day: u32,
-    current_tick: u64,
-    last_cast_aging_year: &mut i32,
-) {
-    let current_year = current_year_from_day(current_day);
-    if current_year == *last_cast_aging_year {
-        return;
-    }
-    *last_cast_aging_year = current_year;
-    let report = tick_cast_aging(characters, current_year, current_tick);
-    if report.changed_ids.is_empty() {
-        return;
-    }
-    for character in characters.iter() {
-        if report.changed_ids.contains(&character.character_id) {
-            if let Err(err) = world_repo.upsert_character(character) {
-                eprintln!(
-                    "Failed to persist character {}: {}",
-                    character.character_id, err
-                );
-            }
-        }
-    }
-    if !report.retired.is_empty() || !report.deceased.is_empty() {
-        println!(
-            "Cast aging: retired={} deceased={}",
-            report.retired.len(),
-            report.deceased.len()
-        );
-    }
-}
-
-fn find_civilian_event<'a>(
-    library: &'a [CivilianStorylet],
-    event_id: &str,
-) -> Option<&'a CivilianStorylet> {
-    library.iter().find(|event| event.id == event_id)
-}
-
-fn record_identity_evidence(
-    identity: &mut IdentityEvidenceStore,
-    city: &CityState,
-    location_id: superhero_universe::simulation::city::LocationId,
-    turn: u64,
-    signatures: &[superhero_universe::rules::SignatureInstance],
-    witnesses: u32,
-    persona_hint: PersonaHint,
-    modifiers: Option<IdentityEvidenceModifiers>,
-) {
-    let (surveillance, in_public) = city
-        .locations
-        .get(&location_id)
-        .map(|loc| {
-            (
-                loc.surveillance_level,
-                loc.tags
-                    .contains(&superhero_universe::simulation::city::LocationTag::Public),
-            )
-        })
-        .unwrap_or((0, true));
-    let modifiers = modifiers.unwrap_or_default();
-    let witness_count = if in_public {
-        witnesses
-            .max(1)
-            .saturating_add(modifiers.witness_bonus)
-    } else {
-        0
-    };
-    let visual_quality = (surveillance as i32
-        + (witness_count as i32 * 10)
-        + modifiers.visual_bonus)
-        .clamp(0, 100) as u8;
-    for sig in signatures {
-        identity.record(
-            location_id,
-            turn,
-            vec![sig.signature.signature_type],
-            witness_count,
-            visual_quality,
-            persona_hint,
-            Vec::new(),
-        );
-    }
-}
-
-fn apply_agent_events(
-    agent_events: &AgentEventLog,
-    turn: u64,
-    city: &mut CityState,
-    city_events: &mut CityEventLog,
-    evidence: &mut WorldEvidence,
-    identity_evidence: &mut IdentityEvidenceStore,
-    event_log: &mut WorldEventLog,
-) {
-    for event in &agent_events.0 {
-        if let AgentEvent::Incident {
-            location_id,
-            signatures,
-            ..
-        } = event
-        {
-            apply_agent_incident(
-                signatures,
-                *location_id,
-                turn,
-                city,
-                city_events,
-                evidence,
-                identity_evidence,
-                event_log,
-            );
-        }
-    }
-}
-
-fn apply_agent_incident(
-    signatures: &[superhero_universe::rules::SignatureInstance],
-    location_id: superhero_universe::simulation::city::LocationId,
-    turn: u64,
-    city: &mut CityState,
-    city_events: &mut CityEventLog,
-    evidence: &mut WorldEvidence,
-    identity_evidence: &mut IdentityEvidenceStore,
-    event_log: &mut WorldEventLog,
-) {
-    if signatures.is_empty() {
-        return;
-    }
-
-    let (in_public, witnesses) = city
-        .locations
-        .get(&location_id)
-        .map(|location| {
-            let in_public = location.tags.contains(&LocationTag::Public);
-            let witnesses = if in_public {
-                2 + (location.surveillance_level / 20).max(0) as u32
-            } else {
-                0
-            };
-            (in_public, witnesses)
-        })
-        .unwrap_or((true, 0));
-
-    evidence.emit(location_id, signatures);
-    apply_signatures(
-        city,
-        location_id,
-        signatures,
-        witnesses,
-        in_public,
-        event_log,
-        city_events,
-    );
-    record_identity_evidence(
-        identity_evidence,
-        city,
-        location_id,
-        turn,
-        signatures,
-        witnesses,
-        PersonaHint::Unknown,
-        None,
-    );
-}
-
-fn apply_action_signatures(
-    signatures: &[superhero_universe::rules::SignatureInstance],
-    location_id: superhero_universe::simulation::city::LocationId,
-    turn: u64,
-    witnesses: u32,
-    in_public: bool,
-    persona_hint: PersonaHint,
-    identity_modifiers: Option<IdentityEvidenceModifiers>,
-    city: &mut CityState,
-    city_events: &mut CityEventLog,
-    evidence: &mut WorldEvidence,
-    identity_evidence: &mut IdentityEvidenceStore,
-    faction_director: &mut FactionDirector,
-    faction_events: &mut FactionEventLog,
-    resolved_faction_events: &mut ResolvedFactionEventLog,
-    cases: &mut CaseRegistry,
-    case_log: &mut CaseEventLog,
-    persona_stack: &mut PersonaStack,
-    alignment: Alignment,
-    player_pos: &Position,
-    event_log: &mut WorldEventLog,
-) {
-    evidence.emit(location_id, signatures);
-    apply_signatures(
-        city,
-        location_id,
-        signatures,
-        witnesses,
-        in_public,
-        event_log,
-        city_events,
-    );
-    record_identity_evidence(
-        identity_evidence,
-        city,
-        location_id,
-        turn,
-        signatures,
-        witnesses,
-        persona_hint,
-        identity_modifiers,
-    );
-    run_faction_director(faction_director, city, evidence, faction_events);
-    resolve_faction_events(
-        faction_events,
-        resolved_faction_events,
-        city,
-        evidence,
-        cases,
-        case_log,
-    );
-    update_cases(cases, city, evidence, identity_evidence, case_log);
-    apply_suspicion_for_intents(
-        persona_stack,
-        alignment,
-        player_pos,
-        city,
-        cases,
-        identity_evidence,
-        &[],
-        1,
-    );
-}
-
-fn apply_combat_pressure_delta(pressure: &mut PressureState, delta: CombatPressureDelta) {
-    pressure.temporal = (pressure.temporal + delta.temporal).clamp(0.0, 100.0);
-    pressure.identity = (pressure.identity + delta.identity).clamp(0.0, 100.0);
-    pressure.institutional = (pressure.institutional + delta.institutional).clamp(0.0, 100.0);
-    pressure.moral = (pressure.moral + delta.moral).clamp(0.0, 100.0);
-    pressure.resource = (pressure.resource + delta.resource).clamp(0.0, 100.0);
-    pressure.psychological = (pressure.psychological + delta.psychological).clamp(0.0, 100.0);
-}
-
-fn combat_case_progress_summary(
-    cases: &CaseRegistry,
-    location_id: superhero_universe::simulation::city::LocationId,
-) -> Vec<String> {
-    cases
-        .cases
-        .iter()
-        .filter(|case| case.location_id == location_id)
-        .map(|case| format!("case#{}:{}", case.case_id, case.progress))
-        .collect()
-}
-
-fn handle_combat_end_consequences(
-    end: CombatEnd,
-    consequences: CombatConsequences,
-    location_id: superhero_universe::simulation::city::LocationId,
-    world: &mut WorldState,
-    target: &TargetContext,
-    pressure: &mut PressureState,
-    endgame_state: &EndgameState,
-    city: &mut CityState,
-    city_events: &mut CityEventLog,
-    evidence: &mut WorldEvidence,
-    identity_evidence: &mut IdentityEvidenceStore,
-    faction_director: &mut FactionDirector,
-    faction_events: &mut FactionEventLog,
-    resolved_faction_events: &mut ResolvedFactionEventLog,
-    cases: &mut CaseRegistry,
-    case_log: &mut CaseEventLog,
-    persona_stack: &mut PersonaStack,
-    alignment: Alignment,
-    player_pos: &Position,
-    event_log: &mut WorldEventLog,
-) {
-    if !consequences.signatures.is_empty() {
-        let witnesses = target.witnesses.saturating_add(4);
-        let identity_modifiers = combat_consequence_modifiers(consequences.combat_consequence);
-        apply_action_signatures(
-            &consequences.signatures,
-            location_id,
-            world.turn,
-            witnesses,
-            target.in_public,
-            PersonaHint::Unknown,
-            Some(identity_modifiers),
-            city,
-            city_events,
-            evidence,
-            identity_evidence,
-            faction_director,
-            faction_events,
-            resolved_faction_events,
-            cases,
-            case_log,
-            persona_stack,
-            alignment,
-            player_pos,
-            event_log,
-        );
-    }
-
-    apply_combat_consequence_heat(
-        city,
-        location_id,
-        consequences.combat_consequence,
-        event_log,
-        city_events,
-    );
-
-    apply_combat_pressure_delta(pressure, consequences.pressure_delta);
-    apply_pressure_modifiers(world, pressure, endgame_state);
-
-    let case_summary = combat_case_progress_summary(cases, location_id);
-    println!(
-        "Combat fallout: end={:?} signatures={} consequence(p={} c={} n={}) pressure(t={:.1} id={:.1} inst={:.1} moral={:.1} res={:.1} psy={:.1}) evidence={} identity_evidence={} cases=[{}]",
-        end,
-        consequences.signatures.len(),
-        consequences.combat_consequence.publicness,
-        consequences.combat_consequence.collateral,
-        consequences.combat_consequence.notoriety,
-        pressure.temporal,
-        pressure.identity,
-        pressure.institutional,
-        pressure.moral,
-        pressure.resource,
-        pressure.psychological,
-        evidence.signatures.len(),
-        identity_evidence.items.len(),
-        case_summary.join(", ")
-    );
-}
-
-fn tick_world(
-    world: &mut WorldState,
-    actor: &mut ActorState,
-    scene: &mut WorldEvidence,
-    identity_evidence: &mut IdentityEvidenceStore,
-    city: &mut CityState,
-    city_events: &mut CityEventLog,
-    faction_director: &mut FactionDirector,
-    faction_events: &mut FactionEventLog,
-    resolved_faction_events: &mut ResolvedFactionEventLog,
-    cases: &mut CaseRegistry,
-    case_log: &mut CaseEventLog,
-    agents: &mut AgentRegistry,
-    agent_events: &mut AgentEventLog,
-    persona_stack: &mut PersonaStack,
-    storylets: &StoryletLibrary,
-    civilian_events: &[CivilianStorylet],
-    alignment: Alignment,
-    position: &mut Position,
-    game_time: &mut GameTime,
-    civilian_state: &mut CivilianState,
-    storylet_state: &mut StoryletState,
-    endgame_state: &mut EndgameState,
-    pressure: &mut PressureState,
-    region: &mut RegionState,
-    region_events: &mut RegionEventLog,
-    global_event_state: &mut GlobalEventState,
-    global_event_log: &mut GlobalEventLog,
-    global_events: &[GlobalEventDefinition],
-    global_faction_director: &mut GlobalFactionDirector,
-    global_faction_events: &mut GlobalFactionEventLog,
-    origin_quest: &mut OriginQuestState,
-    growth: &GrowthState,
-    origin_paths: &OriginPathCatalog,
-    turns: u32,
-    auto_mode: AutoResolveMode,
-    storylet_triggered: &mut bool,
-) -> bool {
-    let mut agent_event_log = WorldEventLog::default();
-    let mut death_pending = false;
-    for _ in 0..turns {
-        world.turn += 1;
-        tick_cooldowns(actor);
-        update_units(city);
-        scene.tick_decay();
-        decay_heat(city, cases, city_events);
-        game_time.advance();
-        tick_agents(agents, city, game_time, agent_events);
-        agent_event_log.0.clear();
-        apply_agent_events(
-            agent_events,
-            world.turn,
-            city,
-            city_events,
-            scene,
-            identity_evidence,
-            &mut agent_event_log,
-        );
-        tick_civilian_life(civilian_state, game_time);
-        if civilian_state.pending_death.is_some() {
-            death_pending = true;
-            break;
-        }
-        tick_civilian_economy(civilian_state, game_time);
-        apply_tech_capability(actor, civilian_state);
-        auto_resolve_civilian_events(
-            civilian_state,
-            civilian_events,
-            origin_quest,
-            origin_paths,
-            pressure,
-            auto_mode,
-        );
-        storylet_state.tick();
-        let rewards = tick_origin_path(origin_quest, origin_paths);
-        apply_origin_rewards(rewards.as_slice(), pressure);
-        if alignment == Alignment::Neutral {
-            let status = alignment_unlock_status(origin_quest, growth, civilian_state);
-            if status.unlocked
-                && !storylet_state
-                    .flags
-                    .contains_key("alignment_choice_unlocked")
-            {
-                storylet_state
-                    .flags
-                    .insert("alignment_choice_unlocked".to_string(), true);
-                println!(
-                    "Alignment choice unlocked. Use `alignment choose <hero|vigilante|villain>`."
-                );
-            }
-        }
-        if civilian_state.life.mutation_ready
-            && !storylet_state.flags.contains_key("life.mutation_ready")
-        {
-            storylet_state
-                .flags
-                .insert("life.mutation_ready".to_string(), true);
-            println!("Mutation potential awakened.");
-        }
-        run_faction_director(faction_director, city, scene, faction_events);
-        resolve_faction_events(
-            faction_events,
-            resolved_faction_events,
-            city,
-            scene,
-            cases,
-            case_log,
-        );
-        update_cases(cases, city, scene, identity_evidence, case_log);
-        apply_suspicion_for_intents(
-            persona_stack,
-            alignment,
-            position,
-            city,
-            cases,
-            identity_evidence,
-            &[],
-            1,
-        );
-        update_pressure(pressure, city, scene, cases, game_time);
-        apply_civilian_pressure(civilian_state, pressure);
-        apply_public_reputation_attention(civilian_state, city, game_time);
-        apply_civilian_mistake_consequences(civilian_state, city, pressure, game_time);
-        update_social_storylet_flags(civilian_state, storylet_state);
-        update_reputation_storylet_flags(civilian_state, storylet_state);
-        update_tech_storylet_flags(civilian_state, storylet_state);
-        set_storylet_flag(
-            storylet_state,
-            "civilian.mistake_recent",
-            civilian_state.last_mistake_day == game_time.day,
-        );
-        apply_pressure_modifiers(world, pressure, endgame_state);
-        let ctx = build_storylet_context(
-            alignment,
-            persona_stack,
-            storylet_state,
-            endgame_state,
-            city,
-            scene,
-            cases,
-            pressure,
-            civilian_state,
-            game_time,
-        );
-        if let Some(storylet) = select_storylet_for_turn(storylets, alignment, storylet_state, &ctx)
-        {
-            println!(
-                "Storylet triggered: {} | {}",
-                storylet.id, storylet.text_stub
-            );
-            *storylet_triggered = true;
-        }
-        run_region_update(region, city, pressure, city_events, region_events);
-        tick_global_events(
-            global_event_state,
-            global_events,
-            region,
-            game_time,
-            global_event_log,
-        );
-        run_global_faction_director(global_faction_director, region, global_faction_events);
-    }
-    death_pending
-}
-
-fn handle_endgame_transition(
-    cases: &CaseRegistry,
-    pressure: &PressureState,
-    faction_events: &ResolvedFactionEventLog,
-    world: &mut WorldState,
-    storylet_state: &mut StoryletState,
-    endgame_state: &mut EndgameState,
-) {
-    if let Some(event) = evaluate_transformation(cases, pressure, faction_events) {
-        if let Some(update) =
-            apply_transformation_event(endgame_state, storylet_state, event)
-        {
-            apply_pressure_modifiers(world, pressure, endgame_state);
-            println!(
-                "Endgame triggered ({:?}): {}",
-                update.event.trigger, update.narrative
-            );
-        }
-    }
-}
-
-fn tick_cooldowns(actor: &mut ActorState) {
-    let mut to_clear = Vec::new();
-    for (expr_id, remaining) in actor.cooldowns.iter_mut() {
-        if *remaining > 0 {
-            *remaining -= 1;
-        }
-        if *remaining <= 0 {
-            to_clear.push(expr_id.clone());
-        }
-    }
-    for expr_id in to_clear {
-        actor.cooldowns.remove(&expr_id);
-    }
-}
-
-fn print_use_error(
-    err: &superhero_universe::rules::UseError,
-    expr: &superhero_universe::rules::ExpressionDef,
-    target: &TargetContext,
-    actor: &ActorState,
-) {
-    println!("can_use failed: {:?}", err);
-    match err {
-        superhero_universe::rules::UseError::Locked => {
-            println!("Expression is locked. Use `growth unlock <expression_id>`.");
-        }
-        superhero_universe::rules::UseError::ConstraintFailed(reason) => {
-            println!(
-                "Constraint: {} | requires_los={} requires_contact={} range_m={:?}",
-                reason,
-                expr.constraints.requires_los,
-                expr.constraints.requires_contact,
-                expr.constraints.range_m
-            );
-            println!(
-                "Context: dist_m={:?} los={} contact={}",
-                target.distance_m, target.has_line_of_sight, target.has_contact
-            );
-        }
-        superhero_universe::rules::UseError::OnCooldown => {
-            if let Some(turns) = actor.cooldowns.get(&expr.id) {
-                println!("Cooldown remaining: {}", turns);
-            }
-        }
-        superhero_universe::rules::UseError::NotEnoughStamina => {
-            let required = sum_costs(&expr.costs, CostType::Stamina);
-            println!("Stamina: have={}, need={}", actor.stamina, required);
-        }
-        superhero_universe::rules::UseError::NotEnoughFocus => {
-            let required = sum_costs(&expr.costs, CostType::Focus);
-            println!("Focus: have={}, need={}", actor.focus, required);
-        }
-        superhero_universe::rules::UseError::MissingResource => {
-            let required = sum_costs(&expr.costs, CostType::Resource);
-            let available = actor.resources.get("resource").copied().unwrap_or(0);
-            println!("Resource: have={}, need={}", available, required);
-        }
-    }
-}
-
-fn sum_costs(costs: &[superhero_universe::rules::CostSpec], cost_type: CostType) -> i64 {
-    costs
-        .iter()
-        .filter(|c| c.cost_type == cost_type)
-        .filter_map(|c| c.value)
-        .sum()
-}
-
-fn persist_world_state(
-    world_db: &mut dyn WorldRepository,
-    world: &WorldState,
-    game_time: &GameTime,
-    city: &CityState,
-    cases: &CaseRegistry,
-    combat: &CombatState,
-    growth: &GrowthState,
-    civilian_state: &CivilianState,
-    storylet_state: &StoryletState,
-    persona_stack: &PersonaStack,
-    alignment: Alignment,
-    omni_registry: &OmniPowerRegistry,
-) {
-    let state = WorldDbState {
-        world_turn: world.turn,
-        game_time: game_time.clone(),
-        city: city.clone(),
-        cases: cases.clone(),
-        combat: combat.clone(),
-        growth: growth.clone(),
-        civilian_state: civilian_state.clone(),
-        storylet_state: storylet_state.clone(),
-        persona_stack: persona_stack.clone(),
-        alignment,
-        omni_registry: omni_registry.clone(),
-    };
-    if let Err(err) = world_db.save_state(&state) {
-        eprintln!("Failed to persist world state: {}", err);
-    }
-}
-
-fn load_storylet_library() -> StoryletLibrary {
-    StoryletLibrary {
-        hero: load_storylet_file("./assets/data/storylets_hero.json"),
-        vigilante: load_storylet_file("./assets/data/storylets_vigilante.json"),
-        villain: load_storylet_file("./assets/data/storylets_villain.json"),
-    }
-}
-
-fn load_storylet_file(path: &str) -> Vec<Storylet> {
-    match load_storylet_catalog(path) {
-        Ok(catalog) => catalog.storylets,
-        Err(err) => {
-            eprintln!("Failed to load storylets from {}: {}", path, err);
-            Vec::new()
-        }
-    }
-}
-
-fn load_civilian_event_library() -> Vec<CivilianStorylet> {
-    match load_civilian_event_catalog("./assets/data/civilian_events.json") {
-        Ok(catalog) => catalog.events,
-        Err(err) => {
-            eprintln!("Failed to load civilian events: {}", err);
-            Vec::new()
-        }
-    }
-}
-
-fn load_endgame_event_library() -> Vec<EndgameEvent> {
-    match load_endgame_event_catalog("./assets/data/endgame_events.json") {
-        Ok(catalog) => catalog.events,
-        Err(err) => {
-            eprintln!("Failed to load endgame events: {}", err);
-            Vec::new()
-        }
-    }
-}
-
-fn load_global_event_library() -> Vec<GlobalEventDefinition> {
-    match load_global_event_catalog("./assets/data/global_events.json") {
-        Ok(catalog) => catalog.events,
-        Err(err) => {
-            eprintln!("Failed to load global events: {}", err);
-            Vec::new()
-        }
-    }
-}
-
-fn print_global_escalation(region: &RegionState) {
-    println!(
-        "Global escalation: {:?} (pressure {:.1})",
-        region.global_pressure.escalation, region.global_pressure.total
-    );
-}
-
-fn print_pending_global_events(
-    state: &GlobalEventState,
-    catalog: &[GlobalEventDefinition],
-    time: &GameTime,
-    show_details: bool,
-) {
-    if state.pending.is_empty() {
-        println!("Global events: none");
-        return;
-    }
-    println!("Global events:");
-    for event in &state.pending {
-        match find_global_event(catalog, &event.event_id) {
-            Some(def) => {
-                print_global_event_entry(event, def, time, show_details);
-            }
-            None => {
-                let age = time.tick.saturating_sub(event.created_tick);
-                println!(
-                    "  {} | (missing definition) | queued {} ticks ago",
-                    event.event_id, age
-                );
-            }
-        }
-    }
-}
-
-fn print_global_event_entry(
-    event: &GlobalEventInstance,
-    def: &GlobalEventDefinition,
-    time: &GameTime,
-    show_details: bool,
-) {
-    let age = time.tick.saturating_sub(event.created_tick);
-    println!("  {} | {} | queued {} ticks ago", def.id, def.title, age);
-    println!("    {}", def.text_stub);
-    if show_details && !def.details.is_empty() {
-        println!("    details:");
-        for detail in &def.details {
-            println!("      - {}", detail);
-        }
-    }
-    for choice in &def.choices {
-        println!("    [{}] {}", choice.id, choice.text);
-    }
-    if !show_details && !def.details.is_empty() {
-        println!("    (see more: global detail {})", def.id);
-    }
-}
-
-fn print_global_event_detail(
-    state: &GlobalEventState,
-    catalog: &[GlobalEventDefinition],
-    time: &GameTime,
-    event_id: &str,
-) {
-    let Some(event) = state
-        .pending
-        .iter()
-        .find(|event| event.event_id == event_id)
-    else {
-        println!("Global event not pending: {}", event_id);
-        return;
-    };
-    let Some(def) = find_global_event(catalog, event_id) else {
-        println!("Global event missing definition: {}", event_id);
-        return;
-    };
-    println!("Global event detail:");
-    print_global_event_entry(event, def, time, true);
-}
-
-fn print_cast(characters: &[PersistentCharacter]) {
-    println!("Characters: {}", characters.len());
-    for (idx, character) in characters.iter().take(10).enumerate() {
-        let role = character
-            .roles
-            .get(0)
-            .map(|role| role.role_type.as_str())
-            .unwrap_or("UNKNOWN");
-        println!(
-            "  {}. {} {} ({}) id={}",
-            idx + 1,
-            character.first_name,
-            character.last_name,
-            role,
-            character.character_id
-        );
-    }
-    if characters.len() > 10 {
-        println!("  ...");
-    }
-}
-
-struct StoryletContext {
-    alignment: Alignment,
-    active_persona: Option<PersonaType>,
-    flags: HashSet<String>,
-    endgame_state: Option<TransformationState>,
-    public_suspicion: i32,
-    civilian_suspicion: i32,
-    wanted_level: i32,
-    exposure_risk: i32,
-    heat: i32,
-    surveillance_level: i32,
-    crime_pressure: i32,
-    case_progress: i32,
-    has_visible_signatures: bool,
-    is_day: bool,
-    stress: i32,
-    reputation: i32,
-    reputation_media: i32,
-    reputation_community: i32,
-    reputation_career: i32,
-    wealth_tier: i32,
-    tech_access: i32,
-    social_leverage: i32,
-    social_protection: i32,
-    social_vulnerability: i32,
-    pressure_identity: i32,
-    pressure_moral: i32,
-    pressure_institutional: i32,
-    pressure_resource: i32,
-    pressure_temporal: i32,
-    pressure_psychological: i32,
-}
-
-struct StoryletEligibility {
-    eligible: bool,
-    matched: Vec<String>,
-}
-
-fn list_storylets_all(library: &StoryletLibrary, alignment: Alignment) {
-    let storylets = library.for_alignment(alignment);
-    if storylets.is_empty() {
-        println!("Storylets: none");
-        return;
-    }
-    println!("Storylets ({:?}):", alignment);
-    for storylet in storylets {
-        let prereqs = if storylet.preconditions.is_empty() {
-            "always".to_string()
-        } else {
-            storylet.preconditions.join(" & ")
-        };
-        println!("  {} | {:?} | {}", storylet.id, storylet.category, prereqs);
-    }
-}
-
-fn list_storylets_available(
-    library: &StoryletLibrary,
-    alignment: Alignment,
-    persona_stack: &PersonaStack,
-    storylet_state: &StoryletState,
-    endgame_state: &EndgameState,
-    city: &CityState,
-    evidence: &WorldEvidence,
-    cases: &CaseRegistry,
-    pressure: &PressureState,
-    civilian_state: &CivilianState,
-    game_time: &GameTime,
-) {
-    let ctx = build_storylet_context(
-        alignment,
-        persona_stack,
-        storylet_state,
-        endgame_state,
-        city,
-        evidence,
-        cases,
-        pressure,
-        civilian_state,
-        game_time,
-    );
-    let mut count = 0;
-    if storylet_state.punctuation.only {
-        println!(
-            "Punctuation layer active ({} turns remaining).",
-            storylet_state.punctuation.remaining_turns
-        );
-    }
-    if storylet_state.punctuation_cooldown > 0 {
-        println!(
-            "Punctuation cooldown active ({} turns remaining).",
-            storylet_state.punctuation_cooldown
-        );
-    }
-    println!("Storylets available:");
-    for storylet in library.for_alignment(alignment) {
-        if !storylet_passes_state_gates(storylet, storylet_state) {
-            continue;
-        }
-        let eval = evaluate_storylet(storylet, &ctx);
-        if !eval.eligible {
-            continue;
-        }
-        let reason = if eval.matched.is_empty() {
-            "always".to_string()
-        } else {
-            eval.matched.join(" & ")
-        };
-        println!("  {} | {:?} | {}", storylet.id, storylet.category, reason);
-        count += 1;
+    let mut out = Vec::new();
+    print_save_diagnostics(&mut out, &report);
+    for line in out {
+        println!("{}", line);
     }
-    if count == 0 {
-        println!("  (none)");
-    }
-}
-
-fn storylet_passes_state_gates(storylet: &Storylet, storylet_state: &StoryletState) -> bool {
-    if storylet_state.fired.contains(&storylet.id) {
-        return false;
-    }
-    if storylet_state
-        .cooldowns
-        .get(&storylet.id)
-        .copied()
-        .unwrap_or(0)
-        > 0
-    {
-        return false;
-    }
-    if !storylet_has_gate_requirements(storylet) {
-        return false;
-    }
-    if storylet_state.punctuation.only && !is_punctuation_storylet(storylet) {
-        return false;
-    }
-    if storylet_state.punctuation_cooldown > 0 && is_punctuation_storylet(storylet) {
-        return false;
-    }
-    true
-}
-
-fn build_storylet_context(
-    alignment: Alignment,
-    persona_stack: &PersonaStack,
-    storylet_state: &StoryletState,
-    endgame_state: &EndgameState,
-    city: &CityState,
-    evidence: &WorldEvidence,
-    cases: &CaseRegistry,
-    pressure: &PressureState,
-    civilian_state: &CivilianState,
-    game_time: &GameTime,
-) -> StoryletContext {
-    let active_persona = persona_stack.active_persona();
-    let (public_suspicion, civilian_suspicion, wanted_level, exposure_risk) = active_persona
-        .map(|persona| {
-            (
-                persona.suspicion.public_suspicion as i32,
-                persona.suspicion.civilian_suspicion as i32,
-                persona.suspicion.wanted_level as i32,
-                persona.suspicion.exposure_risk as i32,
-            )
-        })
-        .unwrap_or((0, 0, 0, 0));
-
-    let location = city.locations.get(&city.active_location);
-    let heat = location.map(|loc| loc.heat).unwrap_or(0);
-    let surveillance_level = location.map(|loc| loc.surveillance_level).unwrap_or(0);
-    let crime_pressure = location.map(|loc| loc.crime_pressure).unwrap_or(0);
-
-    let case_progress = cases
-        .cases
-        .iter()
-        .filter(|case| case.location_id == city.active_location)
-        .map(|case| case.progress as i32)
-        .max()
-        .unwrap_or(0);
-
-    let has_visible_signatures = evidence.signatures.iter().any(|event| {
-        event.location_id == city.active_location && event.signature.remaining_turns > 0
-    });
-    let flags = storylet_state
-        .flags
-        .iter()
-        .filter_map(|(flag, enabled)| enabled.then(|| flag.clone()))
-        .collect();
-
-    let reputation = civilian_state.public_reputation_score();
-    let reputation_media = civilian_state.reputation.media;
-    let reputation_community = civilian_state.reputation.community;
-    let reputation_career = civilian_state.reputation.career;
-    let wealth_tier = civilian_state.wealth.tier.rank() as i32;
-    let tech_access = civilian_state.tech_access_score();
-    let social_leverage = civilian_state.social_leverage_score();
-    let social_protection = civilian_state.social_protection_score();
-    let social_vulnerability = civilian_state.social_vulnerability_score();
-
-    StoryletContext {
-        alignment,
-        active_persona: active_persona.map(|persona| persona.persona_type),
-        flags,
-        endgame_state: endgame_state.phase,
-        public_suspicion,
-        civilian_suspicion,
-        wanted_level,
-        exposure_risk,
-        heat,
-        surveillance_level,
-        crime_pressure,
-        case_progress,
-        has_visible_signatures,
-        is_day: game_time.is_day,
-        stress: 0,
-        reputation,
-        reputation_media,
-        reputation_community,
-        reputation_career,
-        wealth_tier,
-        tech_access,
-        social_leverage,
-        social_protection,
-        social_vulnerability,
-        pressure_identity: pressure.identity.round() as i32,
-        pressure_moral: pressure.moral.round() as i32,
-        pressure_institutional: pressure.institutional.round() as i32,
-        pressure_resource: pressure.resource.round() as i32,
-        pressure_temporal: pressure.temporal.round() as i32,
-        pressure_psychological: pressure.psychological.round() as i32,
-    }
-}
-
-fn select_storylet_for_turn<'a>(
-    library: &'a StoryletLibrary,
-    alignment: Alignment,
-    storylet_state: &mut StoryletState,
-    ctx: &StoryletContext,
-) -> Option<&'a Storylet> {
-    for storylet in library.for_alignment(alignment) {
-        if !storylet_passes_state_gates(storylet, storylet_state) {
-            continue;
-        }
-        if !evaluate_storylet(storylet, ctx).eligible {
-            continue;
-        }
-        storylet_state.fired.insert(storylet.id.clone());
-        if is_punctuation_storylet(storylet) {
-            storylet_state.punctuation_cooldown = DEFAULT_PUNCTUATION_COOLDOWN_TURNS;
-        }
-        return Some(storylet);
-    }
-    None
-}
-
-fn evaluate_storylet(storylet: &Storylet, ctx: &StoryletContext) -> StoryletEligibility {
-    let mut matched = Vec::new();
-    for condition in &storylet.preconditions {
-        if eval_condition(condition, ctx) {
-            matched.push(condition.clone());
-        } else {
-            return StoryletEligibility {
-                eligible: false,
-                matched: Vec::new(),
-            };
-        }
-    }
-    StoryletEligibility {
-        eligible: true,
-        matched,
-    }
-}
-
-fn eval_condition(condition: &str, ctx: &StoryletContext) -> bool {
-    let cond = condition.trim();
-    if cond.is_empty() {
-        return true;
-    }
-    if cond == "time.is_day" {
-        return ctx.is_day;
-    }
-    if cond == "time.is_night" {
-        return !ctx.is_day;
-    }
-    if cond == "signatures.visible" {
-        return ctx.has_visible_signatures;
-    }
-    if let Some(flag) = cond.strip_prefix("flag.") {
-        return ctx.flags.contains(flag);
-    }
-
-    let parts: Vec<&str> = cond.split_whitespace().collect();
-    if parts.len() != 3 {
-        return false;
-    }
-
-    let left = parts[0];
-    let op = parts[1];
-    let right = parts[2];
-
-    match left {
-        "alignment" => {
-            let Some(expected) = parse_alignment(right) else {
-                return false;
-            };
-            match op {
-                "==" => ctx.alignment == expected,
-                "!=" => ctx.alignment != expected,
-                _ => false,
-            }
-        }
-        "persona" => {
-            let Some(expected) = parse_persona_type(right) else {
-                return false;
-            };
-            match op {
-                "==" => ctx.active_persona == Some(expected),
-                "!=" => ctx.active_persona != Some(expected),
-                _ => false,
-            }
-        }
-        "endgame.state" => {
-            let Some(expected) = parse_endgame_state(right) else {
-                return false;
-            };
-            match op {
-                "==" => ctx.endgame_state == Some(expected),
-                "!=" => ctx.endgame_state != Some(expected),
-                _ => false,
-            }
-        }
-        _ => {
-            let Some(left_value) = numeric_metric(left, ctx) else {
-                return false;
-            };
-            let Ok(right_value) = right.parse::<i32>() else {
-                return false;
-            };
-            compare_numeric(left_value, right_value, op)
-        }
-    }
-}
-
-fn parse_alignment(value: &str) -> Option<Alignment> {
-    match value.to_ascii_uppercase().as_str() {
-        "NEUTRAL" => Some(Alignment::Neutral),
-        "HERO" => Some(Alignment::Hero),
-        "VIGILANTE" => Some(Alignment::Vigilante),
-        "VILLAIN" => Some(Alignment::Villain),
-        _ => None,
-    }
-}
-
-fn parse_persona_type(value: &str) -> Option<PersonaType> {
-    match value.to_ascii_uppercase().as_str() {
-        "CIVILIAN" => Some(PersonaType::Civilian),
-        "MASKED" => Some(PersonaType::Masked),
-        _ => None,
-    }
-}
-
-fn parse_endgame_state(value: &str) -> Option<TransformationState> {
-    match value.to_ascii_uppercase().as_str() {
-        "EXPOSED" => Some(TransformationState::Exposed),
-        "REGISTRATION" => Some(TransformationState::Registration),
-        "COSMIC_JUDGEMENT" | "COSMIC_JUDGMENT" => Some(TransformationState::CosmicJudgement),
-        "ASCENSION" => Some(TransformationState::Ascension),
-        "EXILE" => Some(TransformationState::Exile),
-        _ => None,
-    }
-}
-
-fn numeric_metric(key: &str, ctx: &StoryletContext) -> Option<i32> {
-    match key {
-        "public.suspicion" => Some(ctx.public_suspicion),
-        "civilian.suspicion" => Some(ctx.civilian_suspicion),
-        "wanted.level" => Some(ctx.wanted_level),
-        "exposure.risk" => Some(ctx.exposure_risk),
-        "heat" => Some(ctx.heat),
-        "surveillance.level" => Some(ctx.surveillance_level),
-        "gang.pressure" | "crime.pressure" => Some(ctx.crime_pressure),
-        "case.progress" => Some(ctx.case_progress),
-        "stress" => Some(ctx.stress),
-        "reputation" => Some(ctx.reputation),
-        "reputation.media" => Some(ctx.reputation_media),
-        "reputation.community" => Some(ctx.reputation_community),
-        "reputation.career" => Some(ctx.reputation_career),
-        "wealth.tier" => Some(ctx.wealth_tier),
-        "tech.access" => Some(ctx.tech_access),
-        "social.leverage" => Some(ctx.social_leverage),
-        "social.protection" => Some(ctx.social_protection),
-        "social.vulnerability" => Some(ctx.social_vulnerability),
-        "pressure.identity" => Some(ctx.pressure_identity),
-        "pressure.moral" => Some(ctx.pressure_moral),
-        "pressure.institutional" => Some(ctx.pressure_institutional),
-        "pressure.resource" => Some(ctx.pressure_resource),
-        "pressure.temporal" => Some(ctx.pressure_temporal),
-        "pressure.psychological" => Some(ctx.pressure_psychological),
-        _ => None,
-    }
-}
-
-fn compare_numeric(left: i32, right: i32, op: &str) -> bool {
-    match op {
-        ">=" => left >= right,
-        "<=" => left <= right,
-        ">" => left > right,
-        "<" => left < right,
-        "==" => left == right,
-        "!=" => left != right,
-        _ => false,
-    }
-}
-
-fn print_tick_summary(
-    world: &WorldState,
-    stack: &PersonaStack,
-    city: &CityState,
-    cases: &CaseRegistry,
-    pressure: &PressureState,
-    endgame_state: &EndgameState,
-) {
-    let location = city.locations.get(&city.active_location);
-    let (heat, response, crime) = location
-        .map(|loc| (loc.heat, loc.response, loc.crime_pressure))
-        .unwrap_or((
-            0,
-            superhero_universe::simulation::city::HeatResponse::None,
-            0,
-        ));
-    println!(
-        "Turn {} | heat={} response={:?} crime_pressure={}",
-        world.turn, heat, response, crime
-    );
-    if let Some(active) = stack.active_persona() {
-        println!(
-            "Active persona {} ({:?}) -> public={} civilian={} wanted={} exposure={}",
-            active.persona_id,
-            active.persona_type,
-            active.suspicion.public_suspicion,
-            active.suspicion.civilian_suspicion,
-            active.suspicion.wanted_level,
-            active.suspicion.exposure_risk
-        );
-    }
-    let max_progress = cases
-        .cases
-        .iter()
-        .filter(|case| case.location_id == city.active_location)
-        .map(|case| case.progress)
-        .max()
-        .unwrap_or(0);
-    if max_progress > 0 {
-        println!("Case progress (max): {}", max_progress);
-    }
-    println!(
-        "Pressure: temporal={:.1} identity={:.1} institutional={:.1} moral={:.1} resource={:.1} psychological={:.1}",
-        pressure.temporal,
-        pressure.identity,
-        pressure.institutional,
-        pressure.moral,
-        pressure.resource,
-        pressure.psychological
-    );
-    let modifiers = endgame_state.modifiers();
-    println!(
-        "Endgame: {} | modifiers cost={:.2} risk={:.2}",
-        endgame_state.label(),
-        modifiers.cost_scale,
-        modifiers.risk_scale
-    );
-}
-
-fn print_origin_paths(paths: &[OriginPathDefinition], seed: u64) {
-    if paths.is_empty() {
-        println!("No origin paths available for seed {}.", seed);
-        return;
-    }
-    println!("Origin paths (seed={}):", seed);
-    for path in paths {
-        println!("  {} - {}: {}", path.id, path.label, path.summary);
-        if !path.stages.is_empty() {
-            let stage_ids: Vec<&str> = path.stages.iter().map(|stage| stage.id.as_str()).collect();
-            println!("    stages: {}", stage_ids.join(" -> "));
-        }
-    }
-}
-
-fn print_origin_path_status(state: &OriginQuestState, catalog: &OriginPathCatalog) {
-    let Some(path_id) = state.path_id.as_deref() else {
-        println!("Origin path: none selected.");
-        if !state.discovered_paths.is_empty() {
-            println!("Discovered origin paths:");
-            for id in &state.discovered_paths {
-                if let Some(path) = catalog.paths.iter().find(|path| path.id == *id) {
-                    println!("  {} - {}", path.id, path.label);
-                } else {
-                    println!("  {}", id);
-                }
-            }
-        }
-        return;
-    };
-    let path = catalog.paths.iter().find(|path| path.id == path_id);
-    let Some(path) = path else {
-        println!("Origin path: {} (missing definition)", path_id);
-        return;
-    };
-    println!("Origin path: {} - {}", path.label, path.summary);
-    if state.completed {
-        println!(
-            "  status: complete ({} stages)",
-            state.completed_stages.len()
-        );
-        return;
-    }
-    if let Some(stage) = current_origin_stage(state, catalog) {
-        let needed = stage.requirement.progress_needed.max(1);
-        println!(
-            "  stage: {} ({}) progress {}/{}",
-            stage.id, stage.label, state.stage_progress, needed
-        );
-    } else {
-        println!("  stage: none");
-    }
-}
-
-fn apply_origin_rewards(rewards: &[OriginStageReward], pressure: &mut PressureState) {
-    if rewards.is_empty() {
-        return;
-    }
-    for reward in rewards {
-        if reward.reputation_delta != 0 {
-            println!("Origin reward: reputation {}", reward.reputation_delta);
-        }
-        if reward.pressure_delta.temporal != 0.0
-            || reward.pressure_delta.identity != 0.0
-            || reward.pressure_delta.institutional != 0.0
-            || reward.pressure_delta.moral != 0.0
-            || reward.pressure_delta.resource != 0.0
-            || reward.pressure_delta.psychological != 0.0
-        {
-            pressure.temporal =
-                (pressure.temporal + reward.pressure_delta.temporal).clamp(0.0, 100.0);
-            pressure.identity =
-                (pressure.identity + reward.pressure_delta.identity).clamp(0.0, 100.0);
-            pressure.institutional =
-                (pressure.institutional + reward.pressure_delta.institutional).clamp(0.0, 100.0);
-            pressure.moral = (pressure.moral + reward.pressure_delta.moral).clamp(0.0, 100.0);
-            pressure.resource =
-                (pressure.resource + reward.pressure_delta.resource).clamp(0.0, 100.0);
-            pressure.psychological =
-                (pressure.psychological + reward.pressure_delta.psychological).clamp(0.0, 100.0);
-            println!("Origin reward: pressure updated.");
-        }
-        if !reward.mutation_tags.is_empty() {
-            println!(
-                "Origin reward: mutations {}",
-                reward.mutation_tags.join(", ")
-            );
-        }
-        if let Some(notes) = reward.notes.as_deref() {
-            println!("Origin reward: {}", notes);
-        }
-    }
-}
-
-fn print_power_assignment(repo: &dyn PowerRepository, assignment: &PowerAssignmentResult) {
-    if let Some(baseline) = assignment.baseline {
-        println!("Baseline power: {}", power_label(repo, baseline));
-    } else {
-        println!("Baseline power: none");
-    }
-
-    if assignment.expressions.is_empty() {
-        println!("Expression powers: none");
-    } else {
-        println!(
-            "Expression powers: {}",
-            assignment
-                .expressions
-                .iter()
-                .map(|id| power_label(repo, *id))
-                .collect::<Vec<String>>()
-                .join(", ")
-        );
-    }
-
-    if assignment.omni.is_empty() {
-        println!("Omni powers: none");
-    } else {
-        println!(
-            "Omni powers: {}",
-            assignment
-                .omni
-                .iter()
-                .map(|id| power_label(repo, *id))
-                .collect::<Vec<String>>()
-                .join(", ")
-        );
-    }
-
-    for note in &assignment.notes {
-        println!("Note: {}", note);
-    }
-}
-
-fn power_label(repo: &dyn PowerRepository, power_id: PowerId) -> String {
-    match repo.power_info(power_id) {
-        Ok(Some(info)) => format!("{} (id={})", info.name, power_id.0),
-        Ok(None) => format!("Unknown power (id={})", power_id.0),
-        Err(_) => format!("Power lookup failed (id={})", power_id.0),
-    }
-}
-
-fn print_cosmic_constants(catalog: &CosmicConstantsCatalog) {
-    if catalog.constants.is_empty() {
-        println!("Cosmic constants: none loaded");
-        return;
-    }
-    println!("Cosmic constants:");
-    for constant in &catalog.constants {
-        println!(
-            "  {} ({}) | role={} | bodies={} | hive_mind={}",
-            constant.label,
-            constant.id,
-            constant.role,
-            constant.body_count,
-            constant.hive_mind
-        );
-        for note in &constant.notes {
-            println!("    {}", note);
-        }
-    }
-}
-
-fn print_omni_registry(registry: &OmniPowerRegistry) {
-    let holders = registry.holder_powers();
-    if holders.is_empty() {
-        println!("Omni registry: no holders assigned.");
-        return;
-    }
-
-    println!("Omni registry:");
-    for (holder_id, powers) in holders {
-        println!("  {} -> {}", holder_id, powers.join(", "));
-    }
-    let universes = registry.universe_holders();
-    if !universes.is_empty() {
-        println!("Omni universes:");
-        for (universe_id, holder_id) in universes {
-            println!("  {} -> {}", universe_id, holder_id);
-        }
+    if !report.is_healthy() {
+        std::process::exit(1);
     }
 }
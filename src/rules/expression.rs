@@ -2,6 +2,7 @@ use std::str::FromStr;
 
 use serde_json::Value;
 
+use crate::components::persona::{Alignment, PersonaType};
 use crate::rules::cost::CostSpec;
 use crate::rules::power::{ExpressionId, PowerId};
 use crate::rules::signature::SignatureSpec;
@@ -46,17 +47,49 @@ pub struct ExpressionText {
     pub tooltip_short: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AllowedTime {
+    DayOnly,
+    NightOnly,
+    #[default]
+    Any,
+}
+
+impl AllowedTime {
+    pub fn allows(self, is_day: bool) -> bool {
+        match self {
+            AllowedTime::DayOnly => is_day,
+            AllowedTime::NightOnly => !is_day,
+            AllowedTime::Any => true,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Constraints {
     pub requires_contact: bool,
     pub requires_los: bool,
+    /// Effective range: full effect up to this distance.
     pub range_m: Option<i64>,
+    /// Furthest usable distance; past `range_m` the effect falls off.
+    pub max_range_m: Option<i64>,
     pub radius_m: Option<i64>,
     pub cooldown: Option<i64>,
     pub duration_turns: Option<i64>,
+    pub requires_persona: Option<PersonaType>,
+    pub allowed_alignments: Vec<Alignment>,
+    pub allowed_time: AllowedTime,
+    /// Alignment this expression leans toward when evolutions are picked.
+    pub alignment_affinity: Option<Alignment>,
 }
 
 impl Constraints {
+    /// Furthest distance the expression can be used at, if it is ranged.
+    pub fn usable_range(&self) -> Option<i64> {
+        let range = self.range_m?;
+        Some(self.max_range_m.map_or(range, |max| max.max(range)))
+    }
+
     pub fn from_json(value: &Value) -> Self {
         Self {
             requires_contact: value
@@ -68,13 +101,148 @@ impl Constraints {
                 .and_then(Value::as_bool)
                 .unwrap_or(false),
             range_m: value.get("range_m").and_then(Value::as_i64),
+            max_range_m: value.get("max_range_m").and_then(Value::as_i64),
             radius_m: value.get("radius_m").and_then(Value::as_i64),
             cooldown: value.get("cooldown").and_then(Value::as_i64),
             duration_turns: value.get("duration_turns").and_then(Value::as_i64),
+            requires_persona: value
+                .get("requires_persona")
+                .and_then(|raw| serde_json::from_value(raw.clone()).ok()),
+            allowed_alignments: value
+                .get("allowed_alignments")
+                .and_then(|raw| serde_json::from_value(raw.clone()).ok())
+                .unwrap_or_default(),
+            allowed_time: value
+                .get("allowed_time")
+                .and_then(Value::as_str)
+                .and_then(|raw| AllowedTime::from_str(raw).ok())
+                .unwrap_or_default(),
+            alignment_affinity: value
+                .get("alignment_affinity")
+                .and_then(|raw| serde_json::from_value(raw.clone()).ok()),
+        }
+    }
+
+    /// An expression gated on persona or alignment stays locked when the
+    /// caller cannot say who is using it.
+    pub fn allows_identity(
+        &self,
+        persona_type: Option<PersonaType>,
+        alignment: Option<Alignment>,
+    ) -> bool {
+        if let Some(required) = self.requires_persona {
+            if persona_type != Some(required) {
+                return false;
+            }
+        }
+        if !self.allowed_alignments.is_empty()
+            && !alignment.is_some_and(|alignment| self.allowed_alignments.contains(&alignment))
+        {
+            return false;
+        }
+        true
+    }
+
+    /// Checks the fields `from_json` would otherwise drop when they fail to parse.
+    pub fn validate_json(id: &ExpressionId, value: &Value) -> Result<(), ExpressionError> {
+        let invalid = |field: &str, raw: &Value| {
+            ExpressionError::InvalidConstraint(id.clone(), format!("{} = {}", field, raw))
+        };
+        if let Some(raw) = value.get("requires_persona") {
+            serde_json::from_value::<PersonaType>(raw.clone())
+                .map_err(|_| invalid("requires_persona", raw))?;
+        }
+        if let Some(raw) = value.get("allowed_alignments") {
+            serde_json::from_value::<Vec<Alignment>>(raw.clone())
+                .map_err(|_| invalid("allowed_alignments", raw))?;
+        }
+        if let Some(raw) = value.get("alignment_affinity") {
+            serde_json::from_value::<Alignment>(raw.clone())
+                .map_err(|_| invalid("alignment_affinity", raw))?;
+        }
+        if let Some(raw) = value.get("allowed_time") {
+            raw.as_str()
+                .and_then(|time| AllowedTime::from_str(time).ok())
+                .ok_or_else(|| invalid("allowed_time", raw))?;
+        }
+        Ok(())
+    }
+}
+
+/// Location field an expression can change directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnvironmentField {
+    Heat,
+    CrimePressure,
+    PolicePresence,
+    SurveillanceLevel,
+    LockdownLevel,
+    Economy,
+}
+
+impl EnvironmentField {
+    pub fn label(self) -> &'static str {
+        match self {
+            EnvironmentField::Heat => "heat",
+            EnvironmentField::CrimePressure => "crime_pressure",
+            EnvironmentField::PolicePresence => "police_presence",
+            EnvironmentField::SurveillanceLevel => "surveillance_level",
+            EnvironmentField::LockdownLevel => "lockdown_level",
+            EnvironmentField::Economy => "economy",
         }
     }
 }
 
+pub const MAX_ENVIRONMENT_DELTA: i32 = 100;
+
+/// A direct change to the location an expression is used at, e.g. an EMP
+/// cutting surveillance. Read from `environment_effects` in the constraints
+/// JSON as `[{"field": "surveillance_level", "delta": -30}]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnvironmentEffect {
+    pub field: EnvironmentField,
+    pub delta: i32,
+}
+
+impl EnvironmentEffect {
+    pub fn list_from_json(id: &ExpressionId, value: &Value) -> Result<Vec<Self>, ExpressionError> {
+        let Some(raw) = value.get("environment_effects") else {
+            return Ok(Vec::new());
+        };
+        let invalid = |reason: String| ExpressionError::InvalidEnvironmentEffect(id.clone(), reason);
+        let entries = raw
+            .as_array()
+            .ok_or_else(|| invalid("environment_effects must be a list".to_string()))?;
+        entries
+            .iter()
+            .map(|entry| {
+                let field = entry
+                    .get("field")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| invalid("effect is missing a field".to_string()))?;
+                let field = EnvironmentField::from_str(field)
+                    .map_err(|_| invalid(format!("unknown location field {}", field)))?;
+                let delta = entry
+                    .get("delta")
+                    .and_then(Value::as_i64)
+                    .ok_or_else(|| invalid(format!("{} effect is missing a delta", field.label())))?;
+                if delta == 0 || delta.abs() > MAX_ENVIRONMENT_DELTA as i64 {
+                    return Err(invalid(format!(
+                        "{} delta {} must be non-zero and within {}",
+                        field.label(),
+                        delta,
+                        MAX_ENVIRONMENT_DELTA
+                    )));
+                }
+                Ok(EnvironmentEffect {
+                    field,
+                    delta: delta as i32,
+                })
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ExpressionDef {
     pub id: ExpressionId,
@@ -86,12 +254,15 @@ pub struct ExpressionDef {
     pub text: ExpressionText,
     pub costs: Vec<CostSpec>,
     pub signatures: Vec<SignatureSpec>,
+    pub environment_effects: Vec<EnvironmentEffect>,
 }
 
 #[derive(Debug)]
 pub enum ExpressionError {
     MissingCosts(ExpressionId),
     MissingSignatures(ExpressionId),
+    InvalidEnvironmentEffect(ExpressionId, String),
+    InvalidConstraint(ExpressionId, String),
 }
 
 impl std::fmt::Display for ExpressionError {
@@ -101,6 +272,20 @@ impl std::fmt::Display for ExpressionError {
             ExpressionError::MissingSignatures(id) => {
                 write!(f, "expression {} missing signatures", id.0)
             }
+            ExpressionError::InvalidEnvironmentEffect(id, reason) => {
+                write!(
+                    f,
+                    "expression {} has an invalid environment effect: {}",
+                    id.0, reason
+                )
+            }
+            ExpressionError::InvalidConstraint(id, reason) => {
+                write!(
+                    f,
+                    "expression {} has an invalid constraint: {}",
+                    id.0, reason
+                )
+            }
         }
     }
 }
@@ -108,6 +293,17 @@ impl std::fmt::Display for ExpressionError {
 impl std::error::Error for ExpressionError {}
 
 impl ExpressionDef {
+    /// Explicit affinity, or the sole alignment allowed to use the expression.
+    pub fn alignment_affinity(&self) -> Option<Alignment> {
+        if self.constraints.alignment_affinity.is_some() {
+            return self.constraints.alignment_affinity;
+        }
+        match self.constraints.allowed_alignments.as_slice() {
+            [only] => Some(*only),
+            _ => None,
+        }
+    }
+
     pub fn validate_defaults(&self) -> Result<(), ExpressionError> {
         if self.form != ExpressionForm::Passive && self.costs.is_empty() {
             return Err(ExpressionError::MissingCosts(self.id.clone()));
@@ -189,3 +385,36 @@ impl FromStr for Scale {
         }
     }
 }
+
+impl FromStr for EnvironmentField {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "heat" => Ok(EnvironmentField::Heat),
+            "crime_pressure" => Ok(EnvironmentField::CrimePressure),
+            "police_presence" => Ok(EnvironmentField::PolicePresence),
+            "surveillance_level" => Ok(EnvironmentField::SurveillanceLevel),
+            "lockdown_level" => Ok(EnvironmentField::LockdownLevel),
+            "economy" => Ok(EnvironmentField::Economy),
+            _ => Err(ParseEnumError {
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl FromStr for AllowedTime {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "DAY_ONLY" => Ok(AllowedTime::DayOnly),
+            "NIGHT_ONLY" => Ok(AllowedTime::NightOnly),
+            "ANY" => Ok(AllowedTime::Any),
+            _ => Err(ParseEnumError {
+                value: s.to_string(),
+            }),
+        }
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MasteryStage {
     Raw,
     Controlled,
@@ -7,16 +9,78 @@ pub enum MasteryStage {
     Iconic,
 }
 
+/// Uses needed to reach each stage, lowest first.
+const STAGE_THRESHOLDS: [(MasteryStage, u32); 5] = [
+    (MasteryStage::Raw, 0),
+    (MasteryStage::Controlled, 5),
+    (MasteryStage::Precise, 12),
+    (MasteryStage::Silent, 24),
+    (MasteryStage::Iconic, 40),
+];
+
+impl MasteryStage {
+    /// Total uses at which this stage is reached.
+    pub fn min_uses(self) -> u32 {
+        STAGE_THRESHOLDS
+            .iter()
+            .find(|(stage, _)| *stage == self)
+            .map(|(_, uses)| *uses)
+            .unwrap_or(0)
+    }
+
+    /// Total uses at which the stage after the one reached by `uses` begins,
+    /// or None once Iconic.
+    pub fn next_threshold(uses: u32) -> Option<u32> {
+        Self::next_stage(uses).map(|(_, threshold)| threshold)
+    }
+
+    /// The next stage for `uses` and how many more uses it takes to get there.
+    pub fn uses_to_next(uses: u32) -> Option<(MasteryStage, u32)> {
+        Self::next_stage(uses).map(|(stage, threshold)| (stage, threshold - uses))
+    }
+
+    fn next_stage(uses: u32) -> Option<(MasteryStage, u32)> {
+        STAGE_THRESHOLDS
+            .iter()
+            .copied()
+            .find(|(_, threshold)| *threshold > uses)
+    }
+}
+
 pub fn stage_from_uses(uses: u32) -> MasteryStage {
-    if uses >= 40 {
-        MasteryStage::Iconic
-    } else if uses >= 24 {
-        MasteryStage::Silent
-    } else if uses >= 12 {
-        MasteryStage::Precise
-    } else if uses >= 5 {
-        MasteryStage::Controlled
-    } else {
-        MasteryStage::Raw
+    STAGE_THRESHOLDS
+        .iter()
+        .rev()
+        .find(|(_, threshold)| uses >= *threshold)
+        .map(|(stage, _)| *stage)
+        .unwrap_or(MasteryStage::Raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_stage_boundary_reports_the_next_threshold() {
+        let cases = [
+            (0, MasteryStage::Raw, Some((MasteryStage::Controlled, 5))),
+            (4, MasteryStage::Raw, Some((MasteryStage::Controlled, 1))),
+            (5, MasteryStage::Controlled, Some((MasteryStage::Precise, 7))),
+            (11, MasteryStage::Controlled, Some((MasteryStage::Precise, 1))),
+            (12, MasteryStage::Precise, Some((MasteryStage::Silent, 12))),
+            (23, MasteryStage::Precise, Some((MasteryStage::Silent, 1))),
+            (24, MasteryStage::Silent, Some((MasteryStage::Iconic, 16))),
+            (39, MasteryStage::Silent, Some((MasteryStage::Iconic, 1))),
+            (40, MasteryStage::Iconic, None),
+            (95, MasteryStage::Iconic, None),
+        ];
+        for (uses, stage, next) in cases {
+            assert_eq!(stage_from_uses(uses), stage, "stage at {} uses", uses);
+            assert_eq!(MasteryStage::uses_to_next(uses), next, "next after {} uses", uses);
+            assert_eq!(
+                MasteryStage::next_threshold(uses),
+                next.map(|(stage, _)| stage.min_uses())
+            );
+        }
     }
 }
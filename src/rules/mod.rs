@@ -7,7 +7,8 @@ pub mod use_power;
 
 pub use cost::{CostSpec, CostType};
 pub use expression::{
-    Constraints, Delivery, ExpressionDef, ExpressionError, ExpressionForm, ExpressionText, Scale,
+    AllowedTime, Constraints, Delivery, EnvironmentEffect, EnvironmentField, ExpressionDef,
+    ExpressionError, ExpressionForm, ExpressionText, Scale,
 };
 pub use mastery::{stage_from_uses, MasteryStage};
 pub use power::{ExpressionId, PersonaExpression, PowerId, PowerInfo, PowerRepository, PowerStats};
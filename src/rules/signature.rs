@@ -29,7 +29,7 @@ pub struct SignatureSpec {
     pub persistence_turns: i64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignatureInstance {
     pub signature: SignatureSpec,
     pub remaining_turns: i64,
@@ -37,6 +37,32 @@ pub struct SignatureInstance {
 
 const DEFAULT_SIGNATURE_PERSISTENCE: i64 = 5;
 
+/// Turns of persistence each signature type loses per tick. Heat, sound and
+/// strain fade fast; forensic traces linger for investigators to find.
+const SIGNATURE_DECAY_RATES: [(SignatureType, u32); 13] = [
+    (SignatureType::VisualAnomaly, 2),
+    (SignatureType::EmSpike, 2),
+    (SignatureType::ThermalBloom, 3),
+    (SignatureType::AcousticShock, 3),
+    (SignatureType::ChemicalResidue, 1),
+    (SignatureType::PsychicEcho, 2),
+    (SignatureType::RadiationTrace, 1),
+    (SignatureType::BioMarker, 1),
+    (SignatureType::DimensionalResidue, 1),
+    (SignatureType::GraviticDisturbance, 2),
+    (SignatureType::ArcaneResonance, 1),
+    (SignatureType::CausalImprint, 1),
+    (SignatureType::KineticStress, 2),
+];
+
+pub fn decay_rate(signature_type: SignatureType) -> u32 {
+    SIGNATURE_DECAY_RATES
+        .iter()
+        .find(|(kind, _)| *kind == signature_type)
+        .map(|(_, rate)| *rate)
+        .unwrap_or(1)
+}
+
 impl SignatureSpec {
     pub fn to_instance(&self) -> SignatureInstance {
         let remaining_turns = if self.persistence_turns > 0 {
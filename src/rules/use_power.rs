@@ -1,7 +1,8 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::components::persona::{Alignment, PersonaType};
 use crate::rules::cost::{CostSpec, CostType};
-use crate::rules::expression::ExpressionDef;
+use crate::rules::expression::{AllowedTime, ExpressionDef};
 use crate::rules::mastery::MasteryStage;
 use crate::rules::power::ExpressionId;
 use crate::rules::signature::{SignatureInstance, SignatureSpec};
@@ -14,6 +15,16 @@ pub struct ActorState {
     pub cooldowns: HashMap<ExpressionId, i64>,
 }
 
+impl ActorState {
+    /// Count every cooldown down by one turn, dropping the ones that expire.
+    pub fn tick_cooldowns(&mut self) {
+        self.cooldowns.retain(|_, remaining| {
+            *remaining -= 1;
+            *remaining > 0
+        });
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct WorldState {
     pub turn: u64,
@@ -50,6 +61,9 @@ pub struct UseContext<'a> {
     pub world: &'a WorldState,
     pub mastery: Option<MasteryStage>,
     pub unlocked: Option<&'a HashSet<ExpressionId>>,
+    pub persona_type: Option<PersonaType>,
+    pub alignment: Option<Alignment>,
+    pub is_day: Option<bool>,
 }
 
 #[derive(Debug)]
@@ -59,6 +73,12 @@ pub enum UseError {
     NotEnoughStamina,
     NotEnoughFocus,
     MissingResource,
+    WrongPersona,
+    OutOfRange { have: i64, max: i64 },
+    NeedsLineOfSight,
+    NeedsContact,
+    WrongTime(AllowedTime),
+    /// Fallback for constraints without a dedicated variant.
     ConstraintFailed(&'static str),
 }
 
@@ -68,8 +88,13 @@ pub struct UseResult {
     pub emitted_signatures: Vec<SignatureInstance>,
     pub cooldown_turns: Option<i64>,
     pub mastery_stage: MasteryStage,
+    /// Effect multiplier from range: 1.0 within effective range, lower out to max range.
+    pub falloff: f64,
 }
 
+/// Effect left at the very edge of an expression's max range.
+const MIN_RANGE_FALLOFF: f64 = 0.5;
+
 pub fn can_use(ctx: &UseContext, expr: &ExpressionDef, target: &TargetContext) -> Result<(), UseError> {
     let mastery_stage = ctx.mastery.unwrap_or(MasteryStage::Raw);
     let costs = apply_mastery_costs(&expr.costs, mastery_stage, ctx.world.pressure);
@@ -78,15 +103,29 @@ pub fn can_use(ctx: &UseContext, expr: &ExpressionDef, target: &TargetContext) -
             return Err(UseError::Locked);
         }
     }
+    if !expr
+        .constraints
+        .allows_identity(ctx.persona_type, ctx.alignment)
+    {
+        return Err(UseError::WrongPersona);
+    }
     if expr.constraints.requires_contact && !target.has_contact {
-        return Err(UseError::ConstraintFailed("requires_contact"));
+        return Err(UseError::NeedsContact);
     }
     if expr.constraints.requires_los && !target.has_line_of_sight {
-        return Err(UseError::ConstraintFailed("requires_los"));
+        return Err(UseError::NeedsLineOfSight);
     }
-    if let (Some(range), Some(distance)) = (expr.constraints.range_m, target.distance_m) {
-        if distance > range {
-            return Err(UseError::ConstraintFailed("range"));
+    if let Some(is_day) = ctx.is_day {
+        if !expr.constraints.allowed_time.allows(is_day) {
+            return Err(UseError::WrongTime(expr.constraints.allowed_time));
+        }
+    }
+    if let (Some(max), Some(distance)) = (expr.constraints.usable_range(), target.distance_m) {
+        if distance > max {
+            return Err(UseError::OutOfRange {
+                have: distance,
+                max,
+            });
         }
     }
 
@@ -146,9 +185,13 @@ pub fn use_power(
         ctx.actor.cooldowns.insert(expr.id.clone(), turns);
     }
 
+    let falloff = range_falloff(expr, target.distance_m);
     let emitted_signatures = apply_mastery_signatures(&expr.signatures, mastery_stage)
-        .iter()
-        .map(SignatureSpec::to_instance)
+        .into_iter()
+        .map(|mut sig| {
+            sig.strength = scale_cost_float(sig.strength, falloff);
+            sig.to_instance()
+        })
         .collect();
 
     Ok(UseResult {
@@ -156,9 +199,26 @@ pub fn use_power(
         emitted_signatures,
         cooldown_turns,
         mastery_stage,
+        falloff,
     })
 }
 
+/// Linear falloff from full effect at effective range to `MIN_RANGE_FALLOFF` at max range.
+fn range_falloff(expr: &ExpressionDef, distance: Option<i64>) -> f64 {
+    let (Some(range), Some(max), Some(distance)) = (
+        expr.constraints.range_m,
+        expr.constraints.usable_range(),
+        distance,
+    ) else {
+        return 1.0;
+    };
+    if distance <= range || max <= range {
+        return 1.0;
+    }
+    let past = (distance - range) as f64 / (max - range) as f64;
+    1.0 - (1.0 - MIN_RANGE_FALLOFF) * past.min(1.0)
+}
+
 fn sum_costs(costs: &[CostSpec], cost_type: CostType) -> i64 {
     costs
         .iter()
@@ -257,3 +317,208 @@ fn scale_cost_float(value: i64, scale: f64) -> i64 {
     let scaled = (value as f64 * scale).ceil() as i64;
     scaled.max(1)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::expression::{
+        Constraints, Delivery, ExpressionError, ExpressionForm, ExpressionText, Scale,
+    };
+    use crate::rules::power::PowerId;
+    use crate::rules::signature::SignatureType;
+
+    fn night_only_expression() -> ExpressionDef {
+        expression_with(serde_json::json!({ "allowed_time": "NIGHT_ONLY" }))
+    }
+
+    fn expression_with(constraints: serde_json::Value) -> ExpressionDef {
+        let constraints = Constraints::from_json(&constraints);
+        ExpressionDef {
+            id: ExpressionId("shadow_step".to_string()),
+            power_id: PowerId(1),
+            form: ExpressionForm::Movement,
+            delivery: Delivery::Instant,
+            scale: Scale::Street,
+            constraints,
+            text: ExpressionText {
+                ui_name: "Shadow Step".to_string(),
+                tooltip_short: "Step between shadows.".to_string(),
+            },
+            costs: Vec::new(),
+            signatures: vec![SignatureSpec {
+                signature_type: SignatureType::VisualAnomaly,
+                strength: 1,
+                persistence_turns: 1,
+            }],
+            environment_effects: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn night_only_expression_fails_during_day() {
+        let expr = night_only_expression();
+        let target = TargetContext {
+            distance_m: None,
+            has_line_of_sight: true,
+            has_contact: false,
+            in_public: false,
+            witnesses: 0,
+        };
+        let mut actor = ActorState::default();
+        let world = WorldState::default();
+        let mut ctx = UseContext {
+            actor: &mut actor,
+            world: &world,
+            mastery: None,
+            unlocked: None,
+            persona_type: None,
+            alignment: None,
+            is_day: Some(true),
+        };
+        assert!(matches!(
+            can_use(&ctx, &expr, &target),
+            Err(UseError::WrongTime(AllowedTime::NightOnly))
+        ));
+
+        ctx.is_day = Some(false);
+        assert!(can_use(&ctx, &expr, &target).is_ok());
+    }
+
+    #[test]
+    fn each_constraint_reports_its_own_error() {
+        let expr = expression_with(serde_json::json!({
+            "requires_contact": true,
+            "requires_los": true,
+            "range_m": 10
+        }));
+        let mut target = TargetContext {
+            distance_m: Some(25),
+            has_line_of_sight: true,
+            has_contact: false,
+            in_public: false,
+            witnesses: 0,
+        };
+        let mut actor = ActorState::default();
+        let world = WorldState::default();
+        let ctx = UseContext {
+            actor: &mut actor,
+            world: &world,
+            mastery: None,
+            unlocked: None,
+            persona_type: None,
+            alignment: None,
+            is_day: None,
+        };
+        assert!(matches!(
+            can_use(&ctx, &expr, &target),
+            Err(UseError::NeedsContact)
+        ));
+
+        target.has_contact = true;
+        target.has_line_of_sight = false;
+        assert!(matches!(
+            can_use(&ctx, &expr, &target),
+            Err(UseError::NeedsLineOfSight)
+        ));
+
+        target.has_line_of_sight = true;
+        assert!(matches!(
+            can_use(&ctx, &expr, &target),
+            Err(UseError::OutOfRange { have: 25, max: 10 })
+        ));
+
+        target.distance_m = Some(10);
+        assert!(can_use(&ctx, &expr, &target).is_ok());
+    }
+
+    #[test]
+    fn effect_falls_off_between_effective_and_max_range() {
+        let expr = ExpressionDef {
+            costs: Vec::new(),
+            signatures: vec![SignatureSpec {
+                signature_type: SignatureType::KineticStress,
+                strength: 10,
+                persistence_turns: 2,
+            }],
+            ..expression_with(serde_json::json!({ "range_m": 10, "max_range_m": 30 }))
+        };
+        let world = WorldState::default();
+        let use_at = |distance: i64| {
+            let mut actor = ActorState::default();
+            let mut ctx = UseContext {
+                actor: &mut actor,
+                world: &world,
+                mastery: None,
+                unlocked: None,
+                persona_type: None,
+                alignment: None,
+                is_day: None,
+            };
+            let target = TargetContext {
+                distance_m: Some(distance),
+                has_line_of_sight: true,
+                has_contact: false,
+                in_public: false,
+                witnesses: 0,
+            };
+            use_power(&mut ctx, &expr, &target)
+        };
+
+        let close = use_at(8).expect("within effective range");
+        assert_eq!(close.falloff, 1.0);
+        assert_eq!(close.emitted_signatures[0].signature.strength, 10);
+
+        let far = use_at(20).expect("within max range");
+        assert_eq!(far.falloff, 0.75);
+        assert_eq!(far.emitted_signatures[0].signature.strength, 8);
+
+        assert!(matches!(
+            use_at(31),
+            Err(UseError::OutOfRange { have: 31, max: 30 })
+        ));
+    }
+
+    #[test]
+    fn identity_gated_expressions_stay_locked_for_an_unknown_identity() {
+        let expr = expression_with(serde_json::json!({
+            "requires_persona": "MASKED",
+            "allowed_alignments": ["HERO"]
+        }));
+        let constraints = &expr.constraints;
+        assert!(!constraints.allows_identity(None, Some(Alignment::Hero)));
+        assert!(!constraints.allows_identity(Some(PersonaType::Masked), None));
+        assert!(!constraints.allows_identity(Some(PersonaType::Civilian), Some(Alignment::Hero)));
+        assert!(!constraints.allows_identity(Some(PersonaType::Masked), Some(Alignment::Villain)));
+        assert!(constraints.allows_identity(Some(PersonaType::Masked), Some(Alignment::Hero)));
+
+        let open = expression_with(serde_json::json!({}));
+        assert!(open.constraints.allows_identity(None, None));
+    }
+
+    #[test]
+    fn unparseable_identity_constraints_are_rejected() {
+        let id = ExpressionId("shadow_step".to_string());
+        let valid = serde_json::json!({
+            "requires_persona": "MASKED",
+            "allowed_alignments": ["HERO", "VIGILANTE"],
+            "allowed_time": "NIGHT_ONLY"
+        });
+        assert!(Constraints::validate_json(&id, &valid).is_ok());
+        for bad in [
+            serde_json::json!({ "requires_persona": "MASKD" }),
+            serde_json::json!({ "allowed_alignments": "HERO" }),
+            serde_json::json!({ "allowed_alignments": ["HERO", "PALADIN"] }),
+            serde_json::json!({ "alignment_affinity": 3 }),
+            serde_json::json!({ "allowed_time": "DUSK" }),
+        ] {
+            assert!(
+                matches!(
+                    Constraints::validate_json(&id, &bad),
+                    Err(ExpressionError::InvalidConstraint(_, _))
+                ),
+                "{}",
+                bad
+            );
+        }
+    }
+}
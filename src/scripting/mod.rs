@@ -0,0 +1,6 @@
+pub mod session;
+
+pub use session::{
+    clock_seed, print_civilian_event_collisions, print_save_diagnostics, CommandFlow, Session,
+    COMMAND_HELP,
+};
@@ -0,0 +1,7698 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::components::persona::{
+    neutral_persona_stack, Alignment, Persona, PersonaStack, PersonaType, EXPOSURE_BRINK,
+};
+use crate::components::world::Position;
+use crate::content::{
+    ExpressionId, PowerId, PowerRepository, RepositoryError, SqlitePowerRepository,
+};
+use crate::core::world::ActionIntent;
+use crate::data::alien_generation::load_alien_generation_catalog;
+use crate::data::cosmic_constants::{load_cosmic_constants, CosmicConstantsCatalog};
+use crate::data::civilian_events::{
+    load_civilian_event_catalogs, CivilianEventCollision, CivilianStorylet,
+};
+use crate::data::endgame_events::{load_endgame_event_catalog, EndgameEvent};
+use crate::data::global_events::{load_global_event_catalog, GlobalEventDefinition};
+use crate::data::nemesis::load_nemesis_action_catalog;
+use crate::data::omni_powers::{load_omni_powers, OmniPowerCatalog};
+use crate::data::storylets::{load_storylet_catalog, Storylet, StoryletHandoff};
+use crate::rules::{
+    can_use, use_power, ActorState, CostType, EnvironmentEffect, PressureModifiers, TargetContext,
+    UseContext, WorldState,
+};
+use crate::simulation::agents::{
+    tick_agents, trigger_ambush, Agent, AgentEvent, AgentEventLog, AgentRegistry, AmbushSetting,
+};
+use crate::simulation::alien::{format_alien_profile, generate_alien_species};
+use crate::simulation::cosmic::OmniPowerRegistry;
+use crate::simulation::case::{CaseEventLog, CaseRegistry, CaseStatus, CaseTargetType};
+use crate::simulation::city::{
+    CityEventLog, CityId, CityState, LocationId, LocationTag, TravelError,
+};
+use crate::simulation::civilian::{
+    apply_civilian_effects, apply_combat_injuries, parse_wealth_profile, queue_event, restock_supplies,
+    find_civilian_event, note_combat_spotlight, parse_civilian_skill, render_civilian_text,
+    resolve_civilian_choice, spend_favor, spend_intel, take_media_fallout, take_network_log,
+    set_wealth_profile, tick_civilian_economy, tick_civilian_life, AutoChoicePreferences,
+    CivilianChoiceContext, CivilianEvent, CivilianEventCategory, CivilianEventError,
+    CivilianEventSettings, CivilianResolution, CivilianSkill, CivilianState,
+    CrimeAttempt, CrimeOutcome, FavorError, FavorKind, IntelError, LegacyRecord,
+    WealthProfileSwitchError,
+    SUPPLY_POOLS,
+};
+use crate::simulation::crafting::{
+    craft_gadget, craft_success_chance, sync_gadget_unlocks, CraftError, GadgetOutput,
+    GadgetRecipeCatalog,
+};
+use crate::simulation::combat::{
+    CapturedOpponent, CombatBystanders, CombatConsequence, CombatConsequences, CombatEnd, CombatIntent, CombatPressureDelta,
+    CombatReputationDelta,
+    CombatScale, CombatStaging, CombatState, CombatWave, DifficultySetting,
+};
+use crate::simulation::cast::{
+    current_year_from_day, mark_incarcerated, tick_cast_aging, PersistentCharacter,
+    PromotionCandidate, PromotionReason,
+};
+use crate::simulation::endgame::{
+    apply_transformation_event, endgame_phase_label, evaluate_transformation,
+    evaluate_transformation_debug, EndgameState, TransformationState, TriggerStatus,
+};
+use crate::simulation::economy::{EconomyTickResult, WealthTier};
+use crate::simulation::evidence::WorldEvidence;
+use crate::simulation::growth::{
+    record_expression_use, select_evolution_candidate, GrowthState,
+};
+use crate::simulation::identity_evidence::{
+    combat_consequence_modifiers, IdentityEvidenceModifiers, IdentityEvidenceStore, PersonaHint,
+};
+use crate::simulation::nemesis::{
+    is_named_nemesis, nemesis_faction, update_nemesis_relationship, NemesisState,
+};
+use crate::simulation::power_assignment::{
+    assign_alien_powers, assign_mutant_powers, classify_mutant_tier, roll_mutant_lineage,
+    seed_mutant_inheritance, MutantInheritanceProfile, PowerAssignmentConfig, PowerAssignmentResult,
+};
+use crate::simulation::origin::{
+    abandon_origin_path, active_origin_stages, apply_origin_effects, load_origin_catalog,
+    load_origin_path_catalog, parse_origin_effects, register_origin_event, select_origin_paths,
+    start_origin_path, tick_origin_path, OriginPathCatalog, OriginPathDefinition, OriginPathEnd,
+    OriginQuestState, OriginStageReward,
+};
+use crate::simulation::pressure::{
+    apply_pressure_modifiers, PressureAxis, PressureModifierConfig, PressureState,
+};
+use crate::simulation::region::{
+    relocate_city, tick_global_events, GlobalEventInstance, GlobalEventLog, GlobalEventState,
+    RegionEventLog, RegionState, RELOCATION_COST_CR,
+};
+use crate::simulation::stats::RunStats;
+use crate::simulation::storylet_state::StoryletState;
+use crate::simulation::storylets::{
+    compare_metric, condition_holds, is_punctuation_storylet, pick_weighted_storylet,
+    resolve_storylet, storylet_has_gate_requirements, StoryletLibrary,
+};
+use crate::simulation::time::GameTime;
+use crate::systems::case::{apply_decoy_evidence, update_cases};
+use crate::systems::civilian::apply_civilian_pressure;
+use crate::systems::combat_loop::{
+    assign_nemesis, assign_opponent_loadouts, combat_post_consequences, combat_preview,
+    civilians_at_scene, combat_report, force_escalate, force_escape, loadout_scale,
+    resolve_combat, schedule_waves, start_combat, CombatSession,
+};
+use crate::systems::event_resolver::{
+    resolve_faction_events, ResolvedFactionEventLog,
+};
+use crate::systems::faction::{
+    run_faction_director, FactionDirector, FactionEventLog,
+};
+use crate::systems::heat::{
+    apply_combat_consequence_heat, apply_environment_effects, apply_signatures, decay_heat,
+    WorldEventLog,
+};
+use crate::systems::persona::{attempt_switch, PersonaSwitchError};
+use crate::systems::pressure::update_pressure;
+use crate::systems::region::{
+    run_global_faction_director, run_region_update, GlobalFactionDirector, GlobalFactionEventLog,
+};
+use crate::systems::suspicion::{
+    apply_crime_suspicion, apply_media_suspicion, apply_suspicion_for_intents,
+    pull_back_from_brink, trigger_exposure_breaches, BrinkRelief, BrinkReliefError,
+    ExposureBreach, BRINK_CASH_COST,
+};
+use crate::systems::tick_stops::{TickStopReason, TickStops};
+use crate::systems::influence::contest_district_control;
+use crate::systems::units::update_units;
+use crate::ui::authoring::render_authoring_dashboard;
+use crate::world::{SaveDiagnostics, WorldDbState, WorldRepository};
+
+/// Appends formatted text to a command's output, one entry per line.
+macro_rules! say {
+    ($out:expr) => {
+        $out.push(String::new())
+    };
+    ($out:expr, $($arg:tt)*) => {
+        emit($out, format!($($arg)*))
+    };
+}
+
+fn emit(out: &mut Vec<String>, text: String) {
+    out.extend(text.split('\n').map(str::to_string));
+}
+
+/// The command summary the REPL prints on start and for `help`.
+pub const COMMAND_HELP: &str = "Commands: stats | power <id> | use <expression_id> | ctx | loc | travel [<location_id>|explore] | relocate [<city_id>] | decoy [<persona>|off] | persona | personas | alignment [status|choose <hero|vigilante|villain>] | alterego <set <name>> | life <new> | legacy | mystats | seed | cast | ambush [off|cautious|normal|brutal] | diagnose | promote <first> <last> [role] [faction_id] | growth [expr|unlock|lock|mastery] | switch <persona_id> | storylets [all] | storylet resolve <storylet_id> <choice_id> | punctuation <on|off|turns> | author | civilian [events [detail]|detail <event_id>|resolve <event_id> <choice_id>|prefs ...|profile <balanced|vigilante|corporate>|skills [hobby <skill>]] | global [events [detail]|detail <event_id>|resolve <event_id> <choice_id>] | origin [paths|choose|status|abandon|event|tick] | alien [generate|detail|powers] [seed] | cosmic [constants|omni] | set <field> <value> | cd | scene | events | cases [intel <case_id>] | endgame [why] | favor [<contact> <intel|heat|job>] | cover [pay|favor <contact>] | craft [recipe] | combat <start|ally|wave|use|intent|tick|log|preview|resolve|force_escape|force_escalate|difficulty> | tick [n|next|rent|crisis|skip [days]|resume|stops [all|none|combat,endgame,punctuation]] | quit";
+
+const DEFAULT_PUNCTUATION_TURNS: i32 = 2;
+const DEFAULT_PUNCTUATION_COOLDOWN_TURNS: i32 = 3;
+const MAX_FAST_FORWARD_TICKS: u32 = 5000;
+const AUTOSAVE_INTERVAL_TICKS: u64 = 10;
+const FAVOR_INTEL_CASE_SETBACK: u32 = 10;
+const FAVOR_HEAT_RELIEF: i32 = 15;
+const MEDIA_HINT_VISUAL_QUALITY: u8 = 35;
+const CRIME_CASE_FACTION: &str = "metro_police";
+
+
+/// Whether the REPL keeps reading commands after one has been dispatched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandFlow {
+    Continue,
+    Quit,
+}
+
+/// Everything a REPL session keeps between commands. The interactive loop and
+/// `run_script` both feed command lines through `dispatch`; whatever a command
+/// prints is buffered until `take_output`.
+pub struct Session {
+    repo: SqlitePowerRepository,
+    world_repo: Box<dyn WorldRepository>,
+    world_seed: u64,
+    characters: Vec<PersistentCharacter>,
+    actor: ActorState,
+    world: WorldState,
+    game_time: GameTime,
+    last_cast_aging_year: i32,
+    city: CityState,
+    city_events: CityEventLog,
+    event_log: WorldEventLog,
+    region: RegionState,
+    region_events: RegionEventLog,
+    global_event_state: GlobalEventState,
+    global_event_log: GlobalEventLog,
+    nemesis_state: NemesisState,
+    persona_stack: PersonaStack,
+    run_stats: RunStats,
+    evidence: WorldEvidence,
+    identity_evidence: IdentityEvidenceStore,
+    pending_decoy: Option<String>,
+    alignment: Alignment,
+    storylet_state: StoryletState,
+    growth: GrowthState,
+    civilian_events: Vec<CivilianStorylet>,
+    storylets: StoryletLibrary,
+    cosmic_constants: Option<CosmicConstantsCatalog>,
+    omni_catalog: OmniPowerCatalog,
+    omni_registry: OmniPowerRegistry,
+    power_assignment_config: PowerAssignmentConfig,
+    origin_paths: OriginPathCatalog,
+    origin_quest: OriginQuestState,
+    player_pos: Position,
+    pressure: PressureState,
+    faction_director: FactionDirector,
+    faction_events: FactionEventLog,
+    resolved_faction_events: ResolvedFactionEventLog,
+    global_faction_director: GlobalFactionDirector,
+    global_faction_events: GlobalFactionEventLog,
+    cases: CaseRegistry,
+    case_log: CaseEventLog,
+    agents: AgentRegistry,
+    agent_events: AgentEventLog,
+    ambush_setting: AmbushSetting,
+    tick_stops: TickStops,
+    resume_ticks: u32,
+    life_ended: bool,
+    combat: CombatState,
+    endgame_state: EndgameState,
+    pressure_curve: PressureModifierConfig,
+    gadget_recipes: GadgetRecipeCatalog,
+    target: TargetContext,
+    civilian_state: CivilianState,
+    endgame_events: Vec<EndgameEvent>,
+    global_events: Vec<GlobalEventDefinition>,
+    /// World turn of the last autosave.
+    last_save_turn: u64,
+    /// Lines printed since the last `take_output`.
+    output: Vec<String>,
+}
+
+impl Session {
+    pub fn open(
+        repo: SqlitePowerRepository,
+        mut world_repo: Box<dyn WorldRepository>,
+        world_db_path: &Path,
+        requested_seed: Option<u64>,
+        civilian_event_paths: &[PathBuf],
+    ) -> Self {
+        let mut output = Vec::new();
+        let out = &mut output;
+        let world_state = match world_repo.load_or_init() {
+            Ok((state, report)) => {
+                for (component, err) in &report.defaulted {
+                    say!(
+                        out,
+                        "World save: {} could not be loaded ({}); using defaults.",
+                        component,
+                        err
+                    );
+                }
+                for tag in &report.preserved {
+                    say!(out, "Preserving unknown location tag {}", tag);
+                }
+                state
+            }
+            Err(err) => {
+                say!(out, "Failed to load world state: {}", err);
+                WorldDbState::default()
+            }
+        };
+        let fallback_seed = requested_seed.unwrap_or_else(clock_seed);
+        let world_seed = match world_repo.ensure_world_seed(fallback_seed) {
+            Ok(seed) => seed,
+            Err(err) => {
+                say!(out, "Failed to record world seed: {}", err);
+                fallback_seed
+            }
+        };
+        if let Some(requested) = requested_seed {
+            if requested != world_seed {
+                say!(
+                    out,
+                    "Note: --seed {} ignored; {} already uses seed {}.",
+                    requested,
+                    world_db_path.display(),
+                    world_seed
+                );
+            }
+        }
+        say!(out, "World seed: {}", world_seed);
+        let characters = match world_repo.load_characters() {
+            Ok(list) => list,
+            Err(err) => {
+                say!(out, "Failed to load characters: {}", err);
+                Vec::new()
+            }
+        };
+
+        print_stats(out, &repo);
+
+        let actor = ActorState {
+            stamina: 10,
+            focus: 10,
+            ..Default::default()
+        };
+        let WorldDbState {
+            world_turn,
+            game_time,
+            city,
+            cases,
+            combat,
+            growth,
+            storylet_state,
+            persona_stack,
+            alignment,
+            civilian_state,
+            omni_registry,
+            run_stats,
+            evidence,
+            identity_evidence,
+            pressure,
+            region,
+            ambush,
+        } = world_state;
+
+        let mut world = WorldState {
+            turn: world_turn,
+            pressure: PressureModifiers::default(),
+        };
+        let last_cast_aging_year = current_year_from_day(game_time.day);
+        let city_events = CityEventLog::default();
+        let event_log = WorldEventLog::default();
+        let mut region = region;
+        let region_events = RegionEventLog::default();
+        let global_event_state = GlobalEventState::default();
+        let global_event_log = GlobalEventLog::default();
+        let mut nemesis_state = NemesisState::default();
+        nemesis_state.sync_resentment(&characters);
+        let pending_decoy: Option<String> = None;
+        let civilian_events = load_civilian_event_library(out, civilian_event_paths);
+        let storylets = load_storylet_library(out, &civilian_events);
+        let cosmic_constants = match load_cosmic_constants("./assets/data/cosmic_constants.json") {
+            Ok(catalog) => Some(catalog),
+            Err(err) => {
+                say!(out, "Failed to load cosmic constants: {}", err);
+                None
+            }
+        };
+        let omni_catalog = match load_omni_powers("./assets/data/omni_powers.json") {
+            Ok(catalog) => catalog,
+            Err(err) => {
+                say!(out, "Failed to load omni powers: {}", err);
+                crate::data::omni_powers::OmniPowerCatalog {
+                    schema_version: 1,
+                    powers: Vec::new(),
+                }
+            }
+        };
+        let power_assignment_config = PowerAssignmentConfig::default();
+        let origin_paths = match load_origin_path_catalog("./assets/data/origin_paths.json") {
+            Ok(catalog) => catalog,
+            Err(err) => {
+                say!(out, "Failed to load origin paths: {}", err);
+                OriginPathCatalog::default()
+            }
+        };
+        let origin_quest = OriginQuestState::default();
+        let player_pos = Position { x: 0, y: 0 };
+        let mut pressure = pressure;
+        let faction_director = match FactionDirector::load_default() {
+            Ok(director) => director,
+            Err(err) => {
+                say!(out, "Failed to load faction data: {}", err);
+                FactionDirector::default()
+            }
+        };
+        let faction_events = FactionEventLog::default();
+        let resolved_faction_events = ResolvedFactionEventLog::default();
+        let mut global_faction_director = GlobalFactionDirector::load_default();
+        let mut global_faction_events = GlobalFactionEventLog::default();
+        let case_log = CaseEventLog::default();
+        let mut agents = match AgentRegistry::load_default() {
+            Ok(registry) => registry,
+            Err(err) => {
+                say!(out, "Failed to load agent data: {}", err);
+                AgentRegistry::default()
+            }
+        };
+        agents.restore_ambush_cooldowns(&ambush);
+        let agent_events = AgentEventLog::default();
+        let ambush_setting = ambush.setting;
+        let tick_stops = TickStops::default();
+        let resume_ticks = 0u32;
+        let life_ended = civilian_state.life_ended;
+        let endgame_state = EndgameState::default();
+        let pressure_curve = match PressureModifierConfig::load_default() {
+            Ok(config) => config,
+            Err(err) => {
+                say!(out, "Failed to load pressure modifiers: {}", err);
+                PressureModifierConfig::default()
+            }
+        };
+        let gadget_recipes = match GadgetRecipeCatalog::load_default() {
+            Ok(catalog) => catalog,
+            Err(err) => {
+                say!(out, "Failed to load gadget recipes: {}", err);
+                GadgetRecipeCatalog { recipes: Vec::new() }
+            }
+        };
+        let target = TargetContext {
+            distance_m: Some(10),
+            has_line_of_sight: true,
+            has_contact: false,
+            in_public: true,
+            witnesses: 0,
+        };
+        update_pressure(&mut pressure, &city, &evidence, &cases, &game_time);
+        apply_civilian_pressure(&civilian_state, &mut pressure);
+        apply_pressure_modifiers(&mut world, &pressure, &pressure_curve, &endgame_state);
+        region.update_from_city(&city);
+        region.update_global_pressure(&pressure);
+        run_global_faction_director(
+            &mut global_faction_director,
+            &region,
+            &mut global_faction_events,
+        );
+
+        let endgame_events = load_endgame_event_library(out);
+        let global_events = load_global_event_library(out);
+
+        Session {
+            repo,
+            world_repo,
+            world_seed,
+            characters,
+            actor,
+            world,
+            game_time,
+            last_cast_aging_year,
+            city,
+            city_events,
+            event_log,
+            region,
+            region_events,
+            global_event_state,
+            global_event_log,
+            nemesis_state,
+            persona_stack,
+            run_stats,
+            evidence,
+            identity_evidence,
+            pending_decoy,
+            alignment,
+            storylet_state,
+            growth,
+            civilian_events,
+            storylets,
+            cosmic_constants,
+            omni_catalog,
+            omni_registry,
+            power_assignment_config,
+            origin_paths,
+            origin_quest,
+            player_pos,
+            pressure,
+            faction_director,
+            faction_events,
+            resolved_faction_events,
+            global_faction_director,
+            global_faction_events,
+            cases,
+            case_log,
+            agents,
+            agent_events,
+            ambush_setting,
+            tick_stops,
+            resume_ticks,
+            life_ended,
+            combat,
+            endgame_state,
+            pressure_curve,
+            gadget_recipes,
+            target,
+            civilian_state,
+            endgame_events,
+            global_events,
+            last_save_turn: world_turn,
+            output,
+        }
+    }
+
+    /// Runs one command, marks the save dirty unless the command only reads,
+    /// then autosaves once enough turns have passed.
+    pub fn dispatch(&mut self, input: &str) -> CommandFlow {
+        if input.trim().is_empty() {
+            return CommandFlow::Continue;
+        }
+        let flow = self.run_command(input);
+        if !is_read_only_command(input) {
+            self.world_repo.mark_dirty();
+        }
+        if self.world.turn >= self.last_save_turn + AUTOSAVE_INTERVAL_TICKS {
+            self.persist();
+        }
+        flow
+    }
+
+    fn run_command(&mut self, input: &str) -> CommandFlow {
+        let Session {
+            repo,
+            world_repo,
+            world_seed,
+            characters,
+            actor,
+            world,
+            game_time,
+            last_cast_aging_year,
+            city,
+            city_events,
+            event_log,
+            region,
+            region_events,
+            global_event_state,
+            global_event_log,
+            nemesis_state,
+            persona_stack,
+            run_stats,
+            evidence,
+            identity_evidence,
+            pending_decoy,
+            alignment,
+            storylet_state,
+            growth,
+            civilian_events,
+            storylets,
+            cosmic_constants,
+            omni_catalog,
+            omni_registry,
+            power_assignment_config,
+            origin_paths,
+            origin_quest,
+            player_pos,
+            pressure,
+            faction_director,
+            faction_events,
+            resolved_faction_events,
+            global_faction_director,
+            global_faction_events,
+            cases,
+            case_log,
+            agents,
+            agent_events,
+            ambush_setting,
+            tick_stops,
+            resume_ticks,
+            life_ended,
+            combat,
+            endgame_state,
+            pressure_curve,
+            gadget_recipes,
+            target,
+            civilian_state,
+            endgame_events,
+            global_events,
+            output,
+            ..
+        } = self;
+        let out = output;
+        let repo: &SqlitePowerRepository = repo;
+        let world_seed = *world_seed;
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            return CommandFlow::Continue;
+        }
+
+        let mut parts = trimmed.split_whitespace();
+        let cmd = parts.next().unwrap_or("").to_lowercase();
+
+        if *life_ended && !matches!(cmd.as_str(), "quit" | "exit" | "help" | "life" | "legacy" | "mystats" | "seed") {
+            say!(out, "This life has ended. Use `life new` to start over or `legacy` to review past lives.");
+            return CommandFlow::Continue;
+        }
+
+        for expr_id in sync_gadget_unlocks(civilian_state, growth, game_time.day) {
+            say!(
+                out,
+                "Gadget spent: expression {} is no longer available.",
+                expr_id.0
+            );
+        }
+        apply_media_fallout(
+            out,
+            civilian_state,
+            persona_stack,
+            *alignment,
+            identity_evidence,
+            city,
+            world.turn,
+        );
+        for line in take_network_log(civilian_state) {
+            say!(out, "Network: {}", line);
+        }
+        for breach in trigger_exposure_breaches(persona_stack, cases, civilian_state) {
+            growth.reputation.infamy += breach.infamy;
+            print_exposure_breach(out, &breach);
+            if let Some(event) = breach.endgame_event() {
+                if let Some(update) =
+                    apply_transformation_event(endgame_state, storylet_state, event)
+                {
+                    apply_pressure_modifiers(world, pressure, pressure_curve, endgame_state);
+                    say!(
+                        out,
+                        "Endgame triggered ({:?}): {}",
+                        update.event.trigger, update.narrative
+                    );
+                }
+            }
+        }
+        note_run_stats(out, run_stats, cases, persona_stack, civilian_state);
+
+        match cmd.as_str() {
+            "quit" | "exit" => return CommandFlow::Quit,
+            "help" => {
+                say!(out, "{}", COMMAND_HELP);
+            }
+            "stats" => {
+                print_stats(out, repo);
+                print_supplies(out, actor, civilian_state);
+            }
+            "mystats" => {
+                print_run_stats(out, run_stats);
+            }
+            "power" | "list" => {
+                if let Some(id_raw) = parts.next() {
+                    match id_raw.parse::<i64>() {
+                        Ok(power_id) => {
+                            print_power(out, repo, power_id);
+                        }
+                        Err(_) => say!(out, "Invalid power_id: {}", id_raw),
+                    }
+                } else {
+                    say!(out, "Usage: power <power_id>");
+                }
+            }
+            "ctx" => {
+                print_context(out, target, world, city, pressure, endgame_state);
+            }
+            "loc" => {
+                print_location(out, city);
+            }
+            "travel" => match parts.next() {
+                None => print_known_locations(out, city),
+                Some("explore") => {
+                    let Some(location_id) = city.next_undiscovered() else {
+                        say!(out, "You already know every corner of the city.");
+                        return CommandFlow::Continue;
+                    };
+                    city.discover(location_id);
+                    say!(out, "You explore and find location {}.", location_id.0);
+                    travel_player(out, city, player_pos, location_id);
+                }
+                Some(raw) => {
+                    let Ok(id) = raw.parse::<u32>() else {
+                        say!(out, "Usage: travel [<location_id>|explore]");
+                        return CommandFlow::Continue;
+                    };
+                    travel_player(out, city, player_pos, LocationId(id));
+                }
+            },
+            "decoy" => match parts.next() {
+                None => match pending_decoy {
+                    Some(decoy) => say!(out, "Your next power use will point at {}.", decoy),
+                    None => say!(out, "No decoy armed. Usage: decoy [<persona>|off]"),
+                },
+                Some("off") => {
+                    *pending_decoy = None;
+                    say!(out, "Decoy disarmed.");
+                }
+                Some(decoy) => {
+                    let is_active = persona_stack
+                        .active_persona()
+                        .is_some_and(|persona| persona.persona_id.eq_ignore_ascii_case(decoy));
+                    if is_active {
+                        say!(out, "You can't frame the persona you're wearing.");
+                        return CommandFlow::Continue;
+                    }
+                    *pending_decoy = Some(decoy.to_string());
+                    say!(
+                        out,
+                        "Your next power use will leave evidence pointing at {}.",
+                        decoy
+                    );
+                }
+            },
+            "relocate" => {
+                let Some(raw) = parts.next() else {
+                    print_relocation_options(out, region, city);
+                    return CommandFlow::Continue;
+                };
+                let Ok(id) = raw.parse::<u32>() else {
+                    say!(out, "Usage: relocate [<city_id>]");
+                    return CommandFlow::Continue;
+                };
+                if combat.active {
+                    say!(out, "You can't move cities in the middle of a fight.");
+                    return CommandFlow::Continue;
+                }
+                match relocate_city(
+                    region,
+                    city,
+                    cases,
+                    evidence,
+                    civilian_state,
+                    CityId(id),
+                ) {
+                    Ok(paid) => {
+                        combat.location_id = city.active_location;
+                        if let Some(pos) = city.position_for_location(city.active_location) {
+                            *player_pos = pos;
+                        }
+                        say!(
+                            out,
+                            "You pack up and move to city {} ({} cr). Local heat and open cases stay behind.",
+                            id, paid
+                        );
+                        print_location(out, city);
+                    }
+                    Err(err) => say!(out, "Relocate failed: {}", err),
+                }
+            }
+            "persona" => {
+                print_persona_state(out, persona_stack, *alignment, city, cases);
+            }
+            "personas" => {
+                print_persona_stack(out, persona_stack, world.turn);
+            }
+            "alignment" => {
+                let sub = parts.next().unwrap_or("").to_lowercase();
+                match sub.as_str() {
+                    "" | "status" => {
+                        print_alignment_status(
+                            out,
+                            *alignment,
+                            origin_quest,
+                            growth,
+                            civilian_state,
+                        );
+                    }
+                    "choose" => {
+                        let Some(choice_raw) = parts.next() else {
+                            say!(out, "Usage: alignment choose <hero|vigilante|villain>");
+                            return CommandFlow::Continue;
+                        };
+                        if *alignment != Alignment::Neutral {
+                            say!(out, "Alignment already chosen: {:?}", alignment);
+                            return CommandFlow::Continue;
+                        }
+                        let Some(choice) = parse_alignment(choice_raw) else {
+                            say!(out, "Unknown alignment: {}", choice_raw);
+                            return CommandFlow::Continue;
+                        };
+                        if choice == Alignment::Neutral {
+                            say!(out, "Choose hero, vigilante, or villain.");
+                            return CommandFlow::Continue;
+                        }
+                        let status =
+                            alignment_unlock_status(origin_quest, growth, civilian_state);
+                        if !status.unlocked {
+                            say!(
+                                out,
+                                "Alignment choice locked: power_unlocked={} wealth_unlocked={}",
+                                status.has_power, status.has_wealth
+                            );
+                            return CommandFlow::Continue;
+                        }
+                        apply_alignment_choice(alignment, persona_stack, choice);
+                        storylet_state.flags.remove("alignment_choice_unlocked");
+                        storylet_state
+                            .flags
+                            .insert("alignment_chosen".to_string(), true);
+                        say!(out, "Alignment set to {:?}.", alignment);
+                        print_persona_state(out, persona_stack, *alignment, city, cases);
+                    }
+                    _ => {
+                        say!(
+                            out,
+                            "Usage: alignment [status|choose <hero|vigilante|villain>]"
+                        );
+                    }
+                }
+            }
+            "alterego" => {
+                let sub = parts.next().unwrap_or("").to_lowercase();
+                match sub.as_str() {
+                    "set" => {
+                        let Some(name) = parts.next() else {
+                            say!(out, "Usage: alterego set <name>");
+                            return CommandFlow::Continue;
+                        };
+                        if *alignment == Alignment::Neutral {
+                            say!(out, "Alignment is neutral. Choose alignment first.");
+                            return CommandFlow::Continue;
+                        }
+                        if set_masked_label(persona_stack, name) {
+                            say!(out, "Alter ego set to {}.", name);
+                        } else {
+                            say!(out, "No masked persona available.");
+                        }
+                    }
+                    _ => {
+                        say!(out, "Usage: alterego set <name>");
+                    }
+                }
+            }
+            "life" => {
+                let sub = parts.next().unwrap_or("").to_lowercase();
+                match sub.as_str() {
+                    "new" => {
+                        start_new_life(
+                            out,
+                            actor,
+                            growth,
+                            origin_quest,
+                            persona_stack,
+                            alignment,
+                            civilian_state,
+                            player_pos,
+                            target,
+                            storylet_state,
+                            game_time,
+                            repo,
+                            world.turn,
+                        );
+                        update_pressure(pressure, city, evidence, cases, game_time);
+                        apply_civilian_pressure(civilian_state, pressure);
+                        apply_pressure_modifiers(world, pressure, pressure_curve, endgame_state);
+                        *life_ended = false;
+                        say!(out, "New life started. Alignment reset to Neutral.");
+                    }
+                    _ => {
+                        say!(out, "Usage: life new");
+                    }
+                }
+            }
+            "seed" => {
+                print_seed(out, world_seed, world, game_time);
+            }
+            "legacy" => {
+                print_legacy(out, &civilian_state.legacy);
+            }
+            "cast" => {
+                print_cast(out, characters);
+            }
+            "ambush" => match parts.next() {
+                None => say!(out, "Ambush setting: {:?}", ambush_setting),
+                Some(raw) => match AmbushSetting::parse(raw) {
+                    Some(setting) => {
+                        *ambush_setting = setting;
+                        say!(out, "Ambush setting -> {:?}", ambush_setting);
+                    }
+                    None => say!(out, "Usage: ambush [off|cautious|normal|brutal]"),
+                },
+            },
+            "diagnose" => match world_repo.diagnose() {
+                Ok(report) => print_save_diagnostics(out, &report),
+                Err(err) => say!(out, "Failed to diagnose save: {}", err),
+            },
+            "promote" => {
+                let Some(first) = parts.next() else {
+                    say!(out, "Usage: promote <first> <last> [role] [faction_id]");
+                    return CommandFlow::Continue;
+                };
+                let Some(last) = parts.next() else {
+                    say!(out, "Usage: promote <first> <last> [role] [faction_id]");
+                    return CommandFlow::Continue;
+                };
+                let role = parts.next().unwrap_or("CIVILIAN");
+                let faction_id = parts.next().map(str::to_string);
+                let scope_id = format!("city:{}", city.city_id.0);
+                let candidate = PromotionCandidate {
+                    scope_id,
+                    first_name: first.to_string(),
+                    last_name: last.to_string(),
+                    role_type: role.to_string(),
+                    faction_id,
+                    rank: None,
+                    persona_kind: Some("CIVILIAN".to_string()),
+                    persona_label: Some("Civilian".to_string()),
+                    reason: PromotionReason::Manual,
+                };
+                match world_repo.promote_candidate(&candidate, world.turn) {
+                    Ok(character) => {
+                        say!(
+                            out,
+                            "Promoted {} {} ({}) -> {}",
+                            character.first_name,
+                            character.last_name,
+                            candidate.role_type,
+                            character.character_id
+                        );
+                        characters.push(character);
+                    }
+                    Err(err) => say!(out, "Failed to promote: {}", err),
+                }
+            }
+            "growth" => {
+                let sub = parts.next();
+                match sub {
+                    None => print_growth_state(out, growth),
+                    Some("expr") => {
+                        if let Some(expr_id) = parts.next() {
+                            print_growth_expr(out, growth, expr_id);
+                        } else {
+                            say!(out, "Usage: growth expr <expression_id>");
+                        }
+                    }
+                    Some("unlock") => {
+                        if let Some(expr_id) = parts.next() {
+                            let expr_id = ExpressionId(expr_id.to_string());
+                            match growth.unlock_expression(repo, &expr_id) {
+                                Ok(true) => say!(out, "Unlocked expression {}", expr_id.0),
+                                Ok(false) => {
+                                    say!(out, "Expression {} is already unlocked.", expr_id.0)
+                                }
+                                Err(err) => say!(out, "Cannot unlock: {}", err),
+                            }
+                        } else {
+                            say!(out, "Usage: growth unlock <expression_id>");
+                        }
+                    }
+                    Some("lock") => {
+                        if let Some(expr_id) = parts.next() {
+                            let expr_id = ExpressionId(expr_id.to_string());
+                            match growth.lock_expression(repo, &expr_id) {
+                                Ok(true) => say!(out, "Locked expression {}", expr_id.0),
+                                Ok(false) => say!(out, "Expression {} is not unlocked.", expr_id.0),
+                                Err(err) => say!(out, "Cannot lock: {}", err),
+                            }
+                        } else {
+                            say!(out, "Usage: growth lock <expression_id>");
+                        }
+                    }
+                    Some("mastery") => {
+                        if let (Some(expr_id), Some(uses_raw)) = (parts.next(), parts.next()) {
+                            if let Ok(uses) = uses_raw.parse::<u32>() {
+                                seed_mastery(out, growth, expr_id, uses);
+                            } else {
+                                say!(out, "Usage: growth mastery <expression_id> <uses>");
+                            }
+                        } else {
+                            say!(out, "Usage: growth mastery <expression_id> <uses>");
+                        }
+                    }
+                    Some(_) => {
+                        say!(out, "Usage: growth [expr|unlock|lock|mastery]");
+                    }
+                }
+            }
+            "set" => {
+                let field = parts.next().unwrap_or("");
+                let value = parts.next().unwrap_or("");
+                if update_context(target, field, value).is_err() {
+                    say!(out, "Usage: set dist <m> | set los <on|off> | set contact <on|off> | set public <on|off> | set witnesses <n>");
+                }
+            }
+            "switch" => {
+                if let Some(persona_id) = parts.next() {
+                    let intents = vec![ActionIntent::SwitchPersona {
+                        entity_id: 1,
+                        persona_id: persona_id.to_string(),
+                    }];
+                    handle_switch(
+                        out,
+                        persona_id,
+                        persona_stack,
+                        world.turn,
+                        city,
+                        evidence,
+                        target,
+                    );
+                    apply_suspicion_for_intents(
+                        persona_stack,
+                        *alignment,
+                        player_pos,
+                        city,
+                        cases,
+                        identity_evidence,
+                        &intents,
+                        1,
+                    );
+                    print_persona_state(out, persona_stack, *alignment, city, cases);
+                } else {
+                    say!(out, "Usage: switch <persona_id>");
+                }
+            }
+            "use" => {
+                if let Some(expr_raw) = parts.next() {
+                    let expr_id = ExpressionId(expr_raw.to_string());
+                    match repo.expression(&expr_id) {
+                        Ok(expr) => {
+                            let mut ctx = UseContext {
+                                actor,
+                                world,
+                                mastery: growth.mastery.get(&expr.id).map(|entry| entry.stage),
+                                unlocked: Some(&growth.unlocked_expressions),
+                                persona_type: persona_stack
+                                    .active_persona()
+                                    .map(|persona| persona.persona_type),
+                                alignment: Some(*alignment),
+                                is_day: Some(game_time.is_day),
+                            };
+                            match can_use(&ctx, &expr, target) {
+                                Ok(_) => match use_power(&mut ctx, &expr, target) {
+                                    Ok(result) => {
+                                        print_use_result(out, &result);
+                                        let location_id = city.active_location;
+                                        SignatureFallout {
+                                            city,
+                                            city_events,
+                                            evidence,
+                                            identity_evidence,
+                                            faction_director,
+                                            faction_events,
+                                            resolved_faction_events,
+                                            cases,
+                                            case_log,
+                                            persona_stack,
+                                            alignment: *alignment,
+                                            player_pos,
+                                            event_log,
+                                        }
+                                        .apply(
+                                            out,
+                                            SignatureAction {
+                                                signatures: &result.emitted_signatures,
+                                                environment_effects: &expr.environment_effects,
+                                                location_id,
+                                                turn: world.turn,
+                                                witnesses: target.witnesses,
+                                                in_public: target.in_public,
+                                                persona_hint: PersonaHint::Unknown,
+                                                attributed_persona: pending_decoy.as_deref(),
+                                                identity_modifiers: None,
+                                            },
+                                        );
+                                        if let Some(decoy) = pending_decoy.take() {
+                                            say!(out, "Evidence planted pointing at {}.", decoy);
+                                        }
+                                        update_pressure(
+                                            pressure,
+                                            city,
+                                            evidence,
+                                            cases,
+                                            game_time,
+                                        );
+                                        apply_civilian_pressure(civilian_state, pressure);
+                                        apply_pressure_modifiers(
+                                            world,
+                                            pressure,
+                                            pressure_curve,
+                                            endgame_state,
+                                        );
+                                        run_region_update(
+                                            region,
+                                            city,
+                                            pressure,
+                                            city_events,
+                                            region_events,
+                                        );
+                                        run_global_faction_director(
+                                            global_faction_director,
+                                            region,
+                                            global_faction_events,
+                                        );
+                                        if let Some(line) = handle_endgame_transition(
+                                            cases,
+                                            pressure,
+                                            resolved_faction_events,
+                                            world,
+                                            storylet_state,
+                                            endgame_state,
+                                            pressure_curve,
+                                        ) {
+                                            say!(out, "{}", line);
+                                        }
+                                        print_event_log(out, event_log);
+                                        say!(
+                                            out,
+                                            "Actor: stamina={}, focus={}, cooldowns={}",
+                                            actor.stamina,
+                                            actor.focus,
+                                            actor.cooldowns.len()
+                                        );
+                                        apply_growth_on_use(
+                                            out,
+                                            growth,
+                                            &expr,
+                                            repo,
+                                            storylet_state,
+                                            *alignment,
+                                        );
+                                    }
+                                    Err(err) => say!(out, "use_power failed: {:?}", err),
+                                },
+                                Err(err) => print_use_error(out, &err, &expr, target, &ctx),
+                            }
+                        }
+                        Err(err) => print_repository_error(out, &err),
+                    }
+                } else {
+                    say!(out, "Usage: use <expression_id>");
+                }
+            }
+            "cd" => {
+                print_cooldowns(out, actor);
+                print_supplies(out, actor, civilian_state);
+            }
+            "scene" => {
+                print_scene(out, evidence);
+            }
+            "storylets" => {
+                let all = matches!(parts.next(), Some("all"));
+                if all {
+                    list_storylets_all(out, storylets, *alignment);
+                } else {
+                    list_storylets_available(
+                        out,
+                        storylets,
+                        *alignment,
+                        persona_stack,
+                        storylet_state,
+                        endgame_state,
+                        city,
+                        evidence,
+                        cases,
+                        pressure,
+                        civilian_state,
+                        game_time,
+                    );
+                }
+            }
+            "storylet" => {
+                let (Some("resolve"), Some(storylet_id), Some(choice_id)) =
+                    (parts.next(), parts.next(), parts.next())
+                else {
+                    say!(out, "Usage: storylet resolve <storylet_id> <choice_id>");
+                    return CommandFlow::Continue;
+                };
+                match resolve_storylet(
+                    storylets,
+                    storylet_state,
+                    pressure,
+                    persona_stack,
+                    storylet_id,
+                    choice_id,
+                ) {
+                    Ok(resolution) => {
+                        say!(
+                            out,
+                            "Resolved {} -> {}",
+                            storylet_id,
+                            resolution.choice_text
+                        );
+                        if resolution.applied.is_empty() {
+                            say!(out, "No storylet effects applied.");
+                        } else {
+                            say!(out, "Applied storylet effects:");
+                            for entry in resolution.applied {
+                                say!(out, "  {}", entry);
+                            }
+                        }
+                        apply_pressure_modifiers(world, pressure, pressure_curve, endgame_state);
+                    }
+                    Err(err) => say!(out, "Storylet resolve failed: {}", err),
+                }
+            }
+            "punctuation" => {
+                let action = parts.next();
+                match action {
+                    None => {
+                        if storylet_state.punctuation.only {
+                            say!(
+                                out,
+                                "Punctuation layer: ON ({} turns remaining)",
+                                storylet_state.punctuation.remaining_turns
+                            );
+                        } else {
+                            say!(out, "Punctuation layer: OFF");
+                        }
+                    }
+                    Some("off") => {
+                        storylet_state.punctuation.clear();
+                        say!(out, "Punctuation layer disabled.");
+                    }
+                    Some("on") => {
+                        let turns = parts
+                            .next()
+                            .and_then(|raw| raw.parse::<i32>().ok())
+                            .unwrap_or(DEFAULT_PUNCTUATION_TURNS);
+                        storylet_state.punctuation.activate(turns);
+                        say!(
+                            out,
+                            "Punctuation layer enabled for {} turns.",
+                            storylet_state.punctuation.remaining_turns
+                        );
+                    }
+                    Some(raw) => {
+                        if let Ok(turns) = raw.parse::<i32>() {
+                            storylet_state.punctuation.activate(turns);
+                            say!(
+                                out,
+                                "Punctuation layer enabled for {} turns.",
+                                storylet_state.punctuation.remaining_turns
+                            );
+                        } else {
+                            say!(out, "Usage: punctuation <on|off|turns>");
+                        }
+                    }
+                }
+            }
+            "author" => {
+                let origin_catalog = match load_origin_catalog("./assets/data/origins.json") {
+                    Ok(catalog) => catalog,
+                    Err(err) => {
+                        say!(out, "Failed to load origins: {}", err);
+                        return CommandFlow::Continue;
+                    }
+                };
+                let nemesis_catalog =
+                    match load_nemesis_action_catalog("./assets/data/nemesis_actions.json") {
+                        Ok(catalog) => catalog,
+                        Err(err) => {
+                            say!(out, "Failed to load nemesis actions: {}", err);
+                            return CommandFlow::Continue;
+                        }
+                    };
+                let panel = render_authoring_dashboard(
+                    storylets,
+                    civilian_events,
+                    endgame_events,
+                    endgame_state,
+                    &origin_catalog,
+                    origin_paths,
+                    &nemesis_catalog,
+                    region,
+                    global_events,
+                    global_event_state,
+                );
+                say!(out, "{}", panel);
+            }
+            "civilian" => {
+                let sub = parts.next();
+                match sub {
+                    None => {
+                        print_civilian_status(out, civilian_state, game_time);
+                        print_pending_civilian_events(
+                            out,
+                            civilian_state,
+                            civilian_events,
+                            game_time,
+                            false,
+                        );
+                    }
+                    Some("events") => {
+                        let show_details = match parts.next().map(|value| value.to_lowercase()) {
+                            None => false,
+                            Some(value) if value == "detail" || value == "details" => true,
+                            Some(_) => {
+                                say!(out, "Usage: civilian events [detail]");
+                                return CommandFlow::Continue;
+                            }
+                        };
+                        print_pending_civilian_events(
+                            out,
+                            civilian_state,
+                            civilian_events,
+                            game_time,
+                            show_details,
+                        );
+                    }
+                    Some("detail") | Some("details") => {
+                        let Some(event_id) = parts.next() else {
+                            say!(out, "Usage: civilian detail <event_id>");
+                            return CommandFlow::Continue;
+                        };
+                        print_civilian_event_detail(
+                            out,
+                            civilian_state,
+                            civilian_events,
+                            game_time,
+                            event_id,
+                        );
+                    }
+                    Some("resolve") => {
+                        let Some(event_id) = parts.next() else {
+                            say!(out, "Usage: civilian resolve <event_id> <choice_id>");
+                            return CommandFlow::Continue;
+                        };
+                        let Some(choice_id) = parts.next() else {
+                            say!(out, "Usage: civilian resolve <event_id> <choice_id>");
+                            return CommandFlow::Continue;
+                        };
+                        let context = CivilianChoiceContext {
+                            library: civilian_events,
+                            city,
+                            seed: roll_seed(world_seed, world.turn),
+                        };
+                        let crime = resolve_civilian_event(
+                            out,
+                            civilian_state,
+                            context,
+                            origin_quest,
+                            origin_paths,
+                            pressure,
+                            event_id,
+                            choice_id,
+                            true,
+                        )
+                        .and_then(|resolution| resolution.crime);
+                        if let Some(attempt) = crime {
+                            print_crime_attempt(out, &attempt);
+                            SignatureFallout {
+                                city,
+                                city_events,
+                                evidence,
+                                identity_evidence,
+                                faction_director,
+                                faction_events,
+                                resolved_faction_events,
+                                cases,
+                                case_log,
+                                persona_stack,
+                                alignment: *alignment,
+                                player_pos,
+                                event_log,
+                            }
+                            .apply(
+                                out,
+                                SignatureAction {
+                                    signatures: &attempt.signatures,
+                                    environment_effects: &[],
+                                    location_id: attempt.location_id,
+                                    turn: world.turn,
+                                    witnesses: target.witnesses,
+                                    in_public: target.in_public,
+                                    persona_hint: PersonaHint::Civilian,
+                                    attributed_persona: None,
+                                    identity_modifiers: None,
+                                },
+                            );
+                            apply_crime_suspicion(
+                                persona_stack,
+                                *alignment,
+                                attempt.wanted_delta,
+                                attempt.civilian_suspicion_delta,
+                            );
+                            if attempt.opens_case
+                                && !cases.has_active_case(CRIME_CASE_FACTION, attempt.location_id)
+                            {
+                                let pattern = attempt
+                                    .signatures
+                                    .iter()
+                                    .map(|sig| sig.signature.signature_type)
+                                    .collect();
+                                let case_id = cases.create_case(
+                                    CRIME_CASE_FACTION.to_string(),
+                                    attempt.location_id,
+                                    pattern,
+                                    false,
+                                );
+                                if let Some(case) =
+                                    cases.cases.iter_mut().find(|case| case.case_id == case_id)
+                                {
+                                    case.target_type = CaseTargetType::CivilianLink;
+                                }
+                                case_log.0.push(format!(
+                                    "Case {} opened by {} at location {}",
+                                    case_id, CRIME_CASE_FACTION, attempt.location_id.0
+                                ));
+                                say!(out, "Police opened case #{} on the job.", case_id);
+                            }
+                            print_event_log(out, event_log);
+                        }
+                        apply_civilian_pressure(civilian_state, pressure);
+                    }
+                    Some("prefs") => {
+                        let sub = parts.next();
+                        match sub {
+                            None | Some("show") => {
+                                print_civilian_preferences(out, civilian_state);
+                            }
+                            Some("reset") => {
+                                civilian_state.event_settings = CivilianEventSettings::default();
+                                civilian_state.auto_choices = AutoChoicePreferences::default();
+                                say!(out, "Civilian prefs reset to defaults.");
+                            }
+                            Some("repeat") => {
+                                let Some(days_raw) = parts.next() else {
+                                    say!(out, "Usage: civilian prefs repeat <days>");
+                                    return CommandFlow::Continue;
+                                };
+                                let Ok(days) = days_raw.parse::<u32>() else {
+                                    say!(out, "Invalid days: {}", days_raw);
+                                    return CommandFlow::Continue;
+                                };
+                                civilian_state.event_settings.suppress_repeat_days = days;
+                                say!(out, "Repeat suppression set to {} days.", days);
+                            }
+                            Some("min_effect") => {
+                                let Some(value_raw) = parts.next() else {
+                                    say!(out, "Usage: civilian prefs min_effect <value>");
+                                    return CommandFlow::Continue;
+                                };
+                                let Ok(value) = value_raw.parse::<i32>() else {
+                                    say!(out, "Invalid value: {}", value_raw);
+                                    return CommandFlow::Continue;
+                                };
+                                civilian_state.event_settings.min_effect_magnitude = value.max(0);
+                                say!(
+                                    out,
+                                    "Min effect magnitude set to {}.",
+                                    civilian_state.event_settings.min_effect_magnitude
+                                );
+                            }
+                            Some("mute") => {
+                                let Some(category_raw) = parts.next() else {
+                                    say!(out, "Usage: civilian prefs mute <category> [on|off]");
+                                    return CommandFlow::Continue;
+                                };
+                                let Some(category) = parse_event_category(category_raw) else {
+                                    say!(out, "Unknown category: {}", category_raw);
+                                    return CommandFlow::Continue;
+                                };
+                                let enabled = match parts.next().map(|v| v.to_ascii_lowercase()) {
+                                    None => true,
+                                    Some(value) if value == "on" => true,
+                                    Some(value) if value == "off" => false,
+                                    Some(_) => {
+                                        say!(out, "Usage: civilian prefs mute <category> [on|off]");
+                                        return CommandFlow::Continue;
+                                    }
+                                };
+                                let muted = &mut civilian_state.event_settings.muted_categories;
+                                if enabled {
+                                    if !muted.contains(&category) {
+                                        muted.push(category);
+                                    }
+                                } else {
+                                    muted.retain(|entry| entry != &category);
+                                }
+                                say!(
+                                    out,
+                                    "Muted {}: {}.",
+                                    event_category_label(category),
+                                    if enabled { "on" } else { "off" }
+                                );
+                            }
+                            Some("auto") => {
+                                let Some(rule) = parts.next() else {
+                                    say!(out, "Usage: civilian prefs auto <rent|school|rest> <value|on|off>");
+                                    return CommandFlow::Continue;
+                                };
+                                match rule.to_ascii_lowercase().as_str() {
+                                    "rent" => {
+                                        let Some(value_raw) = parts.next() else {
+                                            say!(out, "Usage: civilian prefs auto rent <cash|off>");
+                                            return CommandFlow::Continue;
+                                        };
+                                        if value_raw.eq_ignore_ascii_case("off") {
+                                            civilian_state.auto_choices.pay_rent_if_cash_at_least = None;
+                                            say!(out, "Auto rent pay disabled.");
+                                            return CommandFlow::Continue;
+                                        }
+                                        let Ok(value) = value_raw.parse::<i32>() else {
+                                            say!(out, "Invalid cash value: {}", value_raw);
+                                            return CommandFlow::Continue;
+                                        };
+                                        civilian_state.auto_choices.pay_rent_if_cash_at_least =
+                                            Some(value.max(0));
+                                        say!(out, "Auto rent pay set to cash >= {}.", value.max(0));
+                                    }
+                                    "school" => {
+                                        let Some(value_raw) = parts.next() else {
+                                            say!(out, "Usage: civilian prefs auto school <on|off>");
+                                            return CommandFlow::Continue;
+                                        };
+                                        let enabled = match value_raw.to_ascii_lowercase().as_str() {
+                                            "on" => true,
+                                            "off" => false,
+                                            _ => {
+                                                say!(
+                                                    out,
+                                                    "Usage: civilian prefs auto school <on|off>"
+                                                );
+                                                return CommandFlow::Continue;
+                                            }
+                                        };
+                                        civilian_state.auto_choices.attend_school = enabled;
+                                        say!(
+                                            out,
+                                            "Auto school attendance {}.",
+                                            if enabled { "enabled" } else { "disabled" }
+                                        );
+                                    }
+                                    "rest" => {
+                                        let Some(value_raw) = parts.next() else {
+                                            say!(
+                                                out,
+                                                "Usage: civilian prefs auto rest <sleep_debt|off>"
+                                            );
+                                            return CommandFlow::Continue;
+                                        };
+                                        if value_raw.eq_ignore_ascii_case("off") {
+                                            civilian_state.auto_choices.rest_if_sleep_debt_at_least = None;
+                                            say!(out, "Auto rest disabled.");
+                                            return CommandFlow::Continue;
+                                        }
+                                        let Ok(value) = value_raw.parse::<i32>() else {
+                                            say!(out, "Invalid sleep debt value: {}", value_raw);
+                                            return CommandFlow::Continue;
+                                        };
+                                        civilian_state.auto_choices.rest_if_sleep_debt_at_least = Some(value.max(0));
+                                        say!(
+                                            out,
+                                            "Auto rest set to sleep_debt >= {}.",
+                                            value.max(0)
+                                        );
+                                    }
+                                    _ => {
+                                        say!(out, "Usage: civilian prefs auto <rent|school|rest> <value|on|off>");
+                                    }
+                                }
+                            }
+                            Some(_) => {
+                                say!(out, "Usage: civilian prefs [show|reset|repeat <days>|min_effect <value>|mute <category> [on|off]|auto <rent|school|rest> <value|on|off>]");
+                            }
+                        }
+                    }
+                    Some("profile") => {
+                        let Some(profile) = parts.next() else {
+                            say!(
+                                out,
+                                "Usage: civilian profile <balanced|vigilante|corporate>"
+                            );
+                            return CommandFlow::Continue;
+                        };
+                        let Some(profile) = parse_wealth_profile(profile) else {
+                            say!(out, "Unknown wealth profile: {}", profile);
+                            return CommandFlow::Continue;
+                        };
+                        let applied = match set_wealth_profile(civilian_state, profile) {
+                            Ok(applied) => applied,
+                            Err(WealthProfileSwitchError::AlreadyActive) => {
+                                say!(out, "Wealth profile is already {}.", profile.label());
+                                return CommandFlow::Continue;
+                            }
+                            Err(WealthProfileSwitchError::Cooldown { days_left }) => {
+                                say!(
+                                    out,
+                                    "Wealth profile was changed recently; {} day(s) until the next switch.",
+                                    days_left
+                                );
+                                return CommandFlow::Continue;
+                            }
+                        };
+                        say!(out, "Applied effects:");
+                        for entry in applied {
+                            say!(out, "  {}", entry);
+                        }
+                        apply_civilian_pressure(civilian_state, pressure);
+                        apply_pressure_modifiers(world, pressure, pressure_curve, endgame_state);
+                    }
+                    Some("skills") => match parts.next() {
+                        None => print_civilian_skills(out, civilian_state),
+                        Some("hobby") => {
+                            let Some(skill) = parts.next().and_then(parse_civilian_skill) else {
+                                say!(out, "Usage: civilian skills hobby <investigation|chemistry|engineering|streetwise>");
+                                return CommandFlow::Continue;
+                            };
+                            civilian_state.hobby_skill = skill;
+                            say!(out, "Hobby sessions now train {}.", skill.label());
+                        }
+                        Some(_) => {
+                            say!(out, "Usage: civilian skills [hobby <skill>]");
+                        }
+                    },
+                    Some(_) => {
+                        say!(out, "Usage: civilian [events [detail]|detail <event_id>|resolve <event_id> <choice_id>|prefs ...|profile <balanced|vigilante|corporate>|skills [hobby <skill>]]");
+                    }
+                }
+            }
+            "global" => {
+                let sub = parts.next();
+                match sub {
+                    None => {
+                        print_global_escalation(out, region);
+                        print_pending_global_events(
+                            out,
+                            global_event_state,
+                            global_events,
+                            game_time,
+                            false,
+                        );
+                    }
+                    Some("events") => {
+                        let show_details = match parts.next().map(|value| value.to_lowercase()) {
+                            None => false,
+                            Some(value) if value == "detail" || value == "details" => true,
+                            Some(_) => {
+                                say!(out, "Usage: global events [detail]");
+                                return CommandFlow::Continue;
+                            }
+                        };
+                        print_pending_global_events(
+                            out,
+                            global_event_state,
+                            global_events,
+                            game_time,
+                            show_details,
+                        );
+                    }
+                    Some("detail") | Some("details") => {
+                        let Some(event_id) = parts.next() else {
+                            say!(out, "Usage: global detail <event_id>");
+                            return CommandFlow::Continue;
+                        };
+                        print_global_event_detail(
+                            out,
+                            global_event_state,
+                            global_events,
+                            game_time,
+                            event_id,
+                        );
+                    }
+                    Some("resolve") => {
+                        let Some(event_id) = parts.next() else {
+                            say!(out, "Usage: global resolve <event_id> <choice_id>");
+                            return CommandFlow::Continue;
+                        };
+                        let Some(choice_id) = parts.next() else {
+                            say!(out, "Usage: global resolve <event_id> <choice_id>");
+                            return CommandFlow::Continue;
+                        };
+                        let Some(event_def) = find_global_event(global_events, event_id) else {
+                            say!(out, "Unknown global event: {}", event_id);
+                            return CommandFlow::Continue;
+                        };
+                        resolve_global_event(
+                            out,
+                            global_event_state,
+                            event_def,
+                            pressure,
+                            nemesis_state,
+                            storylet_state,
+                            choice_id,
+                        );
+                        apply_pressure_modifiers(world, pressure, pressure_curve, endgame_state);
+                    }
+                    Some(_) => {
+                        say!(out, "Usage: global [events [detail]|detail <event_id>|resolve <event_id> <choice_id>]");
+                    }
+                }
+            }
+            "events" => {
+                print_faction_events(out, resolved_faction_events);
+            }
+            "cases" => match parts.next() {
+                None => {
+                    print_cases(out, cases, city, civilian_state.sees_case_details());
+                    print_case_log(out, case_log);
+                }
+                Some("intel") => {
+                    let Some(case_id) = parts.next().and_then(|raw| raw.parse::<u32>().ok())
+                    else {
+                        say!(out, "Usage: cases intel <case_id>");
+                        return CommandFlow::Continue;
+                    };
+                    match spend_intel(civilian_state, cases, case_id, game_time.tick) {
+                        Ok(applied) => {
+                            for line in applied {
+                                say!(out, "Intel: {}", line);
+                            }
+                            print_cases(out, cases, city, civilian_state.sees_case_details());
+                        }
+                        Err(IntelError::UnknownCase) => say!(out, "No case {}.", case_id),
+                        Err(IntelError::CaseClosed) => {
+                            say!(out, "Case {} is closed; nothing left to learn.", case_id)
+                        }
+                        Err(IntelError::NoSource) => say!(
+                            out,
+                            "No Media or Underground contact has the pull to dig into case {}.",
+                            case_id
+                        ),
+                        Err(IntelError::Favor(err)) => print_favor_error(out, &err, "Your contact"),
+                    }
+                }
+                Some(_) => say!(out, "Usage: cases [intel <case_id>]"),
+            },
+            "craft" => {
+                let Some(recipe_id) = parts.next() else {
+                    print_gadget_recipes(out, gadget_recipes, civilian_state);
+                    return CommandFlow::Continue;
+                };
+                let Some(recipe) = gadget_recipes.get(recipe_id) else {
+                    say!(out, "Unknown recipe: {} (see `craft`)", recipe_id);
+                    return CommandFlow::Continue;
+                };
+                match craft_gadget(
+                    civilian_state,
+                    actor,
+                    recipe,
+                    roll_seed(world_seed, world.turn),
+                ) {
+                    Ok(outcome) => {
+                        let verdict = if outcome.success {
+                            "works"
+                        } else if outcome.injured {
+                            "blows up in your hands"
+                        } else {
+                            "falls apart"
+                        };
+                        say!(
+                            out,
+                            "{} {} ({}% chance).",
+                            recipe.name,
+                            verdict,
+                            outcome.chance
+                        );
+                        for line in outcome.applied {
+                            say!(out, "  {}", line);
+                        }
+                        sync_gadget_unlocks(civilian_state, growth, game_time.day);
+                    }
+                    Err(CraftError::SkillTooLow { skill, need, have }) => say!(
+                        out,
+                        "{} needs {} level {} (you have {}).",
+                        recipe.name,
+                        skill.label(),
+                        need,
+                        have
+                    ),
+                    Err(CraftError::NotEnoughCash { need, have }) => {
+                        say!(
+                            out,
+                            "{} costs {} cash (you have {}).",
+                            recipe.name,
+                            need,
+                            have
+                        )
+                    }
+                    Err(CraftError::MissingMaterials { pool, need, have }) => say!(
+                        out,
+                        "{} needs {} {} (you have {}).",
+                        recipe.name, need, pool, have
+                    ),
+                }
+            }
+            "favor" => {
+                let args: Vec<&str> = parts.collect();
+                let Some((kind_raw, name_parts)) = args.split_last() else {
+                    print_favor_status(out, civilian_state);
+                    return CommandFlow::Continue;
+                };
+                let Some(kind) = FavorKind::parse(kind_raw).filter(|_| !name_parts.is_empty())
+                else {
+                    say!(out, "Usage: favor <contact> <intel|heat|job>");
+                    return CommandFlow::Continue;
+                };
+                let contact_name = name_parts.join(" ");
+                match spend_favor(civilian_state, &contact_name, kind, game_time.tick) {
+                    Ok(applied) => {
+                        for line in applied {
+                            say!(out, "Favor: {}", line);
+                        }
+                        apply_favor(out, kind, cases, city);
+                    }
+                    Err(err) => print_favor_error(out, &err, &contact_name),
+                }
+            }
+            "cover" => {
+                let relief = match parts.next() {
+                    None => {
+                        print_cover_status(out, persona_stack);
+                        return CommandFlow::Continue;
+                    }
+                    Some("pay") => BrinkRelief::Cash,
+                    Some("favor") => {
+                        let contact = parts.collect::<Vec<_>>().join(" ");
+                        if contact.is_empty() {
+                            say!(out, "Usage: cover [pay|favor <contact>]");
+                            return CommandFlow::Continue;
+                        }
+                        BrinkRelief::Favor { contact }
+                    }
+                    Some(_) => {
+                        say!(out, "Usage: cover [pay|favor <contact>]");
+                        return CommandFlow::Continue;
+                    }
+                };
+                match pull_back_from_brink(
+                    persona_stack,
+                    civilian_state,
+                    &relief,
+                    game_time.tick,
+                ) {
+                    Ok(applied) => {
+                        for line in applied {
+                            say!(out, "Cover: {}", line);
+                        }
+                    }
+                    Err(BrinkReliefError::Favor(err)) => {
+                        let BrinkRelief::Favor { contact } = &relief else {
+                            return CommandFlow::Continue;
+                        };
+                        print_favor_error(out, &err, contact);
+                    }
+                    Err(err) => print_brink_relief_error(out, &err),
+                }
+            }
+            "endgame" => match parts.next() {
+                None => say!(out, "Endgame: {}", endgame_state.label()),
+                Some("why") => print_endgame_why(
+                    out,
+                    endgame_state,
+                    &evaluate_transformation_debug(cases, pressure, resolved_faction_events),
+                ),
+                Some(_) => say!(out, "Usage: endgame [why]"),
+            },
+            "origin" => {
+                let sub = parts.next().unwrap_or("").to_lowercase();
+                match sub.as_str() {
+                    "paths" => {
+                        let seed = parts
+                            .next()
+                            .and_then(|raw| raw.parse::<u64>().ok())
+                            .unwrap_or(world.turn);
+                        let count = parts
+                            .next()
+                            .and_then(|raw| raw.parse::<usize>().ok())
+                            .unwrap_or(3);
+                        let options = select_origin_paths(origin_paths, None, seed, count);
+                        print_origin_paths(out, &options, seed);
+                    }
+                    "choose" => {
+                        let Some(path_id) = parts.next() else {
+                            say!(out, "Usage: origin choose <path_id>");
+                            return CommandFlow::Continue;
+                        };
+                        match start_origin_path(origin_quest, origin_paths, path_id) {
+                            Ok(path) => {
+                                say!(
+                                    out,
+                                    "Origin path selected: {} - {}",
+                                    path.label,
+                                    path.summary
+                                );
+                                print_origin_path_status(out, origin_quest, origin_paths);
+                            }
+                            Err(err) => say!(out, "Failed to start origin path: {}", err),
+                        }
+                    }
+                    "status" => {
+                        print_origin_path_status(out, origin_quest, origin_paths);
+                    }
+                    "abandon" => match abandon_origin_path(origin_quest, origin_paths) {
+                        Ok(penalty) => {
+                            apply_origin_rewards(out, &[penalty], pressure);
+                            print_origin_path_status(out, origin_quest, origin_paths);
+                        }
+                        Err(err) => say!(out, "Failed to abandon origin path: {}", err),
+                    },
+                    "event" => {
+                        let Some(event_tag) = parts.next() else {
+                            say!(out, "Usage: origin event <tag>");
+                            return CommandFlow::Continue;
+                        };
+                        let rewards =
+                            register_origin_event(origin_quest, origin_paths, event_tag);
+                        apply_origin_rewards(out, &rewards, pressure);
+                        apply_pressure_modifiers(world, pressure, pressure_curve, endgame_state);
+                        if !rewards.is_empty() {
+                            print_origin_path_status(out, origin_quest, origin_paths);
+                        }
+                    }
+                    "tick" => {
+                        let count = parts
+                            .next()
+                            .and_then(|raw| raw.parse::<u32>().ok())
+                            .unwrap_or(1);
+                        for _ in 0..count {
+                            let rewards = tick_origin_path(origin_quest, origin_paths, pressure);
+                            apply_origin_rewards(out, &rewards, pressure);
+                            apply_pressure_modifiers(world, pressure, pressure_curve, endgame_state);
+                            if !rewards.is_empty() {
+                                print_origin_path_status(out, origin_quest, origin_paths);
+                            }
+                        }
+                    }
+                    "" => {
+                        say!(
+                            out,
+                            "Usage: origin [paths|choose|status|abandon|event|tick]"
+                        );
+                    }
+                    _ => {
+                        say!(
+                            out,
+                            "Usage: origin [paths|choose|status|abandon|event|tick]"
+                        );
+                    }
+                }
+            }
+            "alien" => {
+                let sub = parts.next().unwrap_or("generate").to_lowercase();
+                let seed = parts
+                    .next()
+                    .and_then(|raw| raw.parse::<u64>().ok())
+                    .unwrap_or(world.turn);
+                let catalog =
+                    match load_alien_generation_catalog("./assets/data/alien_generation.json") {
+                        Ok(catalog) => catalog,
+                        Err(err) => {
+                            say!(out, "Failed to load alien generator: {}", err);
+                            return CommandFlow::Continue;
+                        }
+                    };
+                let profile = generate_alien_species(&catalog, seed);
+                match sub.as_str() {
+                    "generate" => {
+                        for line in format_alien_profile(&profile, false) {
+                            say!(out, "{}", line);
+                        }
+                    }
+                    "detail" => {
+                        for line in format_alien_profile(&profile, true) {
+                            say!(out, "{}", line);
+                        }
+                    }
+                    "powers" => {
+                        for line in format_alien_profile(&profile, false) {
+                            say!(out, "{}", line);
+                        }
+                        let holder_id = format!("alien:{}", seed);
+                        match assign_alien_powers(
+                            repo,
+                            &profile,
+                            omni_catalog,
+                            omni_registry,
+                            &holder_id,
+                            "prime",
+                            seed,
+                            power_assignment_config.clone(),
+                        ) {
+                            Ok(assignment) => {
+                                print_power_assignment(out, repo, &assignment);
+                            }
+                            Err(err) => {
+                                say!(out, "Failed to assign alien powers: {}", err);
+                            }
+                        }
+                    }
+                    _ => {
+                        say!(out, "Usage: alien [generate|detail|powers] [seed]");
+                    }
+                }
+            }
+            "cosmic" => {
+                let sub = parts.next().unwrap_or("constants").to_lowercase();
+                match sub.as_str() {
+                    "constants" => {
+                        if let Some(catalog) = cosmic_constants {
+                            print_cosmic_constants(out, catalog);
+                        } else {
+                            say!(out, "Cosmic constants not loaded.");
+                        }
+                    }
+                    "omni" => {
+                        print_omni_registry(out, omni_registry);
+                    }
+                    _ => {
+                        say!(out, "Usage: cosmic [constants|omni]");
+                    }
+                }
+            }
+            "combat" => {
+                let sub = parts.next().unwrap_or("").to_lowercase();
+                match sub.as_str() {
+                    "start" => {
+                        let label = parts.next().unwrap_or("incident");
+                        let scale = parts
+                            .next()
+                            .and_then(CombatScale::parse)
+                            .unwrap_or(CombatScale::Street);
+                        let opponent_count = parts
+                            .next()
+                            .and_then(|v| v.parse::<u32>().ok())
+                            .unwrap_or(2);
+                        let player_name = persona_stack
+                            .active_persona()
+                            .map(|p| p.label.clone())
+                            .unwrap_or_else(|| "Player".to_string());
+                        start_combat(
+                            combat,
+                            CombatStaging::for_active_location(city),
+                            label,
+                            scale,
+                            &player_name,
+                            opponent_count,
+                            roll_seed(world_seed, world.turn),
+                        );
+                        let pool = opponent_loadout_pool(out, repo, scale);
+                        assign_opponent_loadouts(combat, &pool);
+                        if let Some(nemesis_raw) = parts.next() {
+                            match characters
+                                .iter()
+                                .find(|character| character.character_id == nemesis_raw)
+                            {
+                                Some(character) if is_named_nemesis(character) => {
+                                    let name =
+                                        format!("{} {}", character.first_name, character.last_name);
+                                    assign_nemesis(combat, &character.character_id, &name);
+                                }
+                                Some(_) => {
+                                    say!(out, "Character {} is not a nemesis.", nemesis_raw);
+                                }
+                                None => say!(out, "Unknown character {}.", nemesis_raw),
+                            }
+                        }
+                        print_combat_status(out, combat);
+                    }
+                    "ally" => {
+                        if !combat.active {
+                            say!(out, "No active combat. Use `combat start <label>` first.");
+                            return CommandFlow::Continue;
+                        }
+                        let name = parts.collect::<Vec<_>>().join(" ");
+                        let name = if name.is_empty() {
+                            format!("Backup {}", combat.allies().count() + 1)
+                        } else {
+                            name
+                        };
+                        combat.add_ally(&name);
+                        say!(out, "{} joins the fight as backup.", name);
+                        print_combat_status(out, combat);
+                    }
+                    "wave" => {
+                        if !combat.active {
+                            say!(out, "No active combat. Use `combat start <label>` first.");
+                            return CommandFlow::Continue;
+                        }
+                        let delay = parts.next().and_then(|v| v.parse::<u64>().ok());
+                        let count = parts.next().and_then(|v| v.parse::<u32>().ok());
+                        let (Some(delay), Some(count)) = (delay, count) else {
+                            say!(out, "Usage: combat wave <delay_ticks> <opponents> [scale]");
+                            return CommandFlow::Continue;
+                        };
+                        let scale = parts
+                            .next()
+                            .and_then(CombatScale::parse)
+                            .unwrap_or(combat.scale);
+                        let wave = CombatWave {
+                            delay_ticks: combat.tick + delay,
+                            opponent_count: count,
+                            scale,
+                        };
+                        schedule_waves(combat, &[wave]);
+                        print_combat_status(out, combat);
+                    }
+                    "use" => {
+                        if !combat.active {
+                            say!(out, "No active combat. Use `combat start <label>` first.");
+                        } else if let Some(expr_raw) = parts.next() {
+                            combat.pending_player_expression =
+                                Some(ExpressionId(expr_raw.to_string()));
+                            say!(out, "Queued expression {} for combat.", expr_raw);
+                        } else {
+                            say!(out, "Usage: combat use <expression_id>");
+                        }
+                    }
+                    "intent" => {
+                        if !combat.active {
+                            say!(out, "No active combat. Use `combat start <label>` first.");
+                        } else if let Some(raw) = parts.next() {
+                            match parse_combat_intent(raw) {
+                                Some(intent) => {
+                                    if let Some(player) = combat.player_mut() {
+                                        player.intent = intent;
+                                        combat
+                                            .log
+                                            .push(format!("Player intent set to {:?}.", intent));
+                                    }
+                                    say!(out, "Player intent -> {:?}.", intent);
+                                }
+                                None => {
+                                    say!(out, "Usage: combat intent <attack|escape|hold|capture>");
+                                }
+                            }
+                        } else {
+                            say!(out, "Usage: combat intent <attack|escape|hold|capture>");
+                        }
+                    }
+                    "tick" => {
+                        if !combat.active {
+                            say!(out, "No active combat. Use `combat start <label>` first.");
+                        } else {
+                            let count = parts
+                                .next()
+                                .and_then(|v| v.parse::<u32>().ok())
+                                .unwrap_or(1);
+                            for _ in 0..count {
+                                let expr_def = match combat.pending_player_expression.as_ref() {
+                                    Some(expr_id) => match repo.expression(expr_id) {
+                                        Ok(expr) => Some(expr),
+                                        Err(err) => {
+                                            print_repository_error(out, &err);
+                                            None
+                                        }
+                                    },
+                                    None => None,
+                                };
+                                let opponent_exprs = load_opponent_loadouts(combat, repo);
+                                refresh_combat_bystanders(combat, city, agents, game_time);
+                                let tick_result = CombatSession {
+                                    persona_type: persona_stack
+                                        .active_persona()
+                                        .map(|p| p.persona_type),
+                                    alignment: Some(*alignment),
+                                    is_day: Some(game_time.is_day),
+                                    ..CombatSession::new(combat, actor, world, target)
+                                        .with_growth(growth)
+                                        .with_opponent_expressions(&opponent_exprs)
+                                }
+                                .tick(expr_def.as_ref());
+
+                                if tick_result.used_success {
+                                    if let Some(expr) = expr_def.as_ref() {
+                                        apply_growth_on_use(
+                                            out,
+                                            growth,
+                                            expr,
+                                            repo,
+                                            storylet_state,
+                                            *alignment,
+                                        );
+                                    }
+                                }
+
+                                let environment_effects = expr_def
+                                    .as_ref()
+                                    .filter(|_| tick_result.used_success)
+                                    .map_or(&[][..], |expr| expr.environment_effects.as_slice());
+                                if !tick_result.emitted_signatures.is_empty()
+                                    || !environment_effects.is_empty()
+                                {
+                                    let witnesses = target.witnesses.saturating_add(2);
+                                    SignatureFallout {
+                                        city,
+                                        city_events,
+                                        evidence,
+                                        identity_evidence,
+                                        faction_director,
+                                        faction_events,
+                                        resolved_faction_events,
+                                        cases,
+                                        case_log,
+                                        persona_stack,
+                                        alignment: *alignment,
+                                        player_pos,
+                                        event_log,
+                                    }
+                                    .apply(
+                                        out,
+                                        SignatureAction {
+                                            signatures: &tick_result.emitted_signatures,
+                                            environment_effects,
+                                            location_id: combat.location_id,
+                                            turn: world.turn,
+                                            witnesses,
+                                            in_public: target.in_public,
+                                            persona_hint: PersonaHint::Unknown,
+                                            attributed_persona: None,
+                                            identity_modifiers: None,
+                                        },
+                                    );
+                                }
+
+                                world.turn += 1;
+                                actor.tick_cooldowns();
+                                update_units(city);
+                                evidence.tick_decay();
+                                identity_evidence.tick_decay(1);
+                                decay_heat(city, cases, city_events);
+                                game_time.advance();
+                                tick_civilian_life(civilian_state, game_time, world_seed);
+                                maybe_awaken_mutant_powers(
+                                    out,
+                                    civilian_state,
+                                    repo,
+                                    omni_catalog,
+                                    omni_registry,
+                                    power_assignment_config,
+                                    world.turn,
+                                );
+                                storylet_state.tick();
+                                update_pressure(
+                                    pressure,
+                                    city,
+                                    evidence,
+                                    cases,
+                                    game_time,
+                                );
+                                apply_civilian_pressure(civilian_state, pressure);
+                                apply_pressure_modifiers(world, pressure, pressure_curve, endgame_state);
+                                run_region_update(
+                                    region,
+                                    city,
+                                    pressure,
+                                    city_events,
+                                    region_events,
+                                );
+                                run_global_faction_director(
+                                    global_faction_director,
+                                    region,
+                                    global_faction_events,
+                                );
+                                let end_reason = tick_result.ended;
+                                if let Some(end_reason) = end_reason {
+                                    let consequences = tick_result
+                                        .post_combat_consequences
+                                        .unwrap_or_else(|| {
+                                            combat_post_consequences(
+                                                combat,
+                                                end_reason,
+                                                target,
+                                                *alignment,
+                                            )
+                                        });
+                                    note_combat_spotlight(
+                                        civilian_state,
+                                        consequences.combat_consequence,
+                                        game_time.day,
+                                    );
+                                    run_stats.record_combat_end(
+                                        end_reason,
+                                        civilians_at_scene(combat, target),
+                                        consequences.combat_consequence,
+                                    );
+                                    out.extend(record_nemesis_encounter(
+                                        combat,
+                                        end_reason,
+                                        consequences.combat_consequence,
+                                        characters,
+                                        world_repo.as_mut(),
+                                        nemesis_state,
+                                        world.turn,
+                                    ));
+                                    record_combat_captures(
+                                        out,
+                                        combat,
+                                        &consequences.captured,
+                                        characters,
+                                        world_repo.as_mut(),
+                                        city,
+                                        world.turn,
+                                    );
+                                    CombatFallout {
+                                        signatures: SignatureFallout {
+                                            city,
+                                            city_events,
+                                            evidence,
+                                            identity_evidence,
+                                            faction_director,
+                                            faction_events,
+                                            resolved_faction_events,
+                                            cases,
+                                            case_log,
+                                            persona_stack,
+                                            alignment: *alignment,
+                                            player_pos,
+                                            event_log,
+                                        },
+                                        world,
+                                        target,
+                                        pressure,
+                                        endgame_state,
+                                        pressure_curve,
+                                        growth,
+                                    }
+                                    .apply(
+                                        out,
+                                        end_reason,
+                                        consequences,
+                                        combat.location_id,
+                                    );
+                                }
+                                if let Some(line) = handle_endgame_transition(
+                                    cases,
+                                    pressure,
+                                    resolved_faction_events,
+                                    world,
+                                    storylet_state,
+                                    endgame_state,
+                                    pressure_curve,
+                                ) {
+                                    say!(out, "{}", line);
+                                }
+                                if let Some(end_reason) = end_reason {
+                                    if end_reason == CombatEnd::PlayerDefeated {
+                                        start_new_life(
+                                            out,
+                                            actor,
+                                            growth,
+                                            origin_quest,
+                                            persona_stack,
+                                            alignment,
+                                            civilian_state,
+                                            player_pos,
+                                            target,
+                                            storylet_state,
+                                            game_time,
+                                            repo,
+                                            world.turn,
+                                        );
+                                        update_pressure(
+                                            pressure,
+                                            city,
+                                            evidence,
+                                            cases,
+                                            game_time,
+                                        );
+                                        apply_civilian_pressure(civilian_state, pressure);
+                                        apply_pressure_modifiers(
+                                            world,
+                                            pressure,
+                                            pressure_curve,
+                                            endgame_state,
+                                        );
+                                        say!(out, "You died. Starting a new life.");
+                                    }
+                                    say!(out, "Combat ended: {}", format_combat_end(end_reason));
+                                    apply_combat_wounds(out, civilian_state, combat);
+                                    *life_ended |= process_civilian_death(
+                                        out,
+                                        civilian_state,
+                                        *alignment,
+                                        combat,
+                                        run_stats,
+                                    );
+                                    break;
+                                }
+                            }
+                            print_combat_status(out, combat);
+                            print_event_log(out, event_log);
+                        }
+                    }
+                    "log" => {
+                        print_combat_log(out, combat);
+                    }
+                    "preview" => {
+                        print_combat_preview(out, combat);
+                    }
+                    "resolve" => {
+                        if let Some(end_reason) = resolve_combat(combat) {
+                            refresh_combat_bystanders(combat, city, agents, game_time);
+                            let consequences =
+                                combat_post_consequences(combat, end_reason, target, *alignment);
+                            note_combat_spotlight(
+                                civilian_state,
+                                consequences.combat_consequence,
+                                game_time.day,
+                            );
+                            run_stats.record_combat_end(
+                                end_reason,
+                                civilians_at_scene(combat, target),
+                                consequences.combat_consequence,
+                            );
+                            out.extend(record_nemesis_encounter(
+                                combat,
+                                end_reason,
+                                consequences.combat_consequence,
+                                characters,
+                                world_repo.as_mut(),
+                                nemesis_state,
+                                world.turn,
+                            ));
+                            record_combat_captures(
+                                out,
+                                combat,
+                                &consequences.captured,
+                                characters,
+                                world_repo.as_mut(),
+                                city,
+                                world.turn,
+                            );
+                            CombatFallout {
+                                signatures: SignatureFallout {
+                                    city,
+                                    city_events,
+                                    evidence,
+                                    identity_evidence,
+                                    faction_director,
+                                    faction_events,
+                                    resolved_faction_events,
+                                    cases,
+                                    case_log,
+                                    persona_stack,
+                                    alignment: *alignment,
+                                    player_pos,
+                                    event_log,
+                                },
+                                world,
+                                target,
+                                pressure,
+                                endgame_state,
+                                pressure_curve,
+                                growth,
+                            }
+                            .apply(
+                                out,
+                                end_reason,
+                                consequences,
+                                combat.location_id,
+                            );
+                            say!(out, "Combat ended: {}", format_combat_end(end_reason));
+                            apply_combat_wounds(out, civilian_state, combat);
+                            *life_ended |= process_civilian_death(
+                                out,
+                                civilian_state,
+                                *alignment,
+                                combat,
+                                run_stats,
+                            );
+                        } else {
+                            say!(out, "No active combat.");
+                        }
+                    }
+                    "force_escape" => {
+                        if let Some(end_reason) = force_escape(combat) {
+                            refresh_combat_bystanders(combat, city, agents, game_time);
+                            let consequences =
+                                combat_post_consequences(combat, end_reason, target, *alignment);
+                            note_combat_spotlight(
+                                civilian_state,
+                                consequences.combat_consequence,
+                                game_time.day,
+                            );
+                            run_stats.record_combat_end(
+                                end_reason,
+                                civilians_at_scene(combat, target),
+                                consequences.combat_consequence,
+                            );
+                            out.extend(record_nemesis_encounter(
+                                combat,
+                                end_reason,
+                                consequences.combat_consequence,
+                                characters,
+                                world_repo.as_mut(),
+                                nemesis_state,
+                                world.turn,
+                            ));
+                            record_combat_captures(
+                                out,
+                                combat,
+                                &consequences.captured,
+                                characters,
+                                world_repo.as_mut(),
+                                city,
+                                world.turn,
+                            );
+                            CombatFallout {
+                                signatures: SignatureFallout {
+                                    city,
+                                    city_events,
+                                    evidence,
+                                    identity_evidence,
+                                    faction_director,
+                                    faction_events,
+                                    resolved_faction_events,
+                                    cases,
+                                    case_log,
+                                    persona_stack,
+                                    alignment: *alignment,
+                                    player_pos,
+                                    event_log,
+                                },
+                                world,
+                                target,
+                                pressure,
+                                endgame_state,
+                                pressure_curve,
+                                growth,
+                            }
+                            .apply(
+                                out,
+                                end_reason,
+                                consequences,
+                                combat.location_id,
+                            );
+                            say!(out, "Combat ended: {}", format_combat_end(end_reason));
+                            apply_combat_wounds(out, civilian_state, combat);
+                            *life_ended |= process_civilian_death(
+                                out,
+                                civilian_state,
+                                *alignment,
+                                combat,
+                                run_stats,
+                            );
+                        } else {
+                            say!(out, "No active combat.");
+                        }
+                    }
+                    "force_escalate" => match force_escalate(combat) {
+                        Some(escalation) if escalation.added_opponents.is_empty() => {
+                            say!(
+                                out,
+                                "Combat escalated {:?} -> {:?}.",
+                                escalation.from,
+                                escalation.to
+                            );
+                        }
+                        Some(escalation) => say!(
+                            out,
+                            "Combat escalated {:?} -> {:?}; joining: {}.",
+                            escalation.from,
+                            escalation.to,
+                            escalation.added_opponents.join(", ")
+                        ),
+                        None => say!(out, "No escalation available."),
+                    },
+                    "difficulty" => match parts.next() {
+                        None => say!(out, "Combat difficulty: {:?}", combat.difficulty),
+                        Some(raw) => match DifficultySetting::parse(raw) {
+                            Some(difficulty) => {
+                                combat.difficulty = difficulty;
+                                say!(out, "Combat difficulty -> {:?}.", difficulty);
+                            }
+                            None => say!(
+                                out,
+                                "Usage: combat difficulty [passive|standard|aggressive|tactical]"
+                            ),
+                        },
+                    },
+                    _ => {
+                        say!(out, "Usage: combat start <label> [scale] [opponents] [nemesis_id] | combat ally [name] | combat wave <delay_ticks> <opponents> [scale] | combat use <expression_id> | combat intent <attack|escape|hold|capture> | combat tick [n] | combat log | combat preview | combat resolve | combat force_escape | combat force_escalate | combat difficulty [passive|standard|aggressive|tactical]");
+                    }
+                }
+            }
+            "tick" => {
+                let next = parts.next();
+                let tick_mode = match next {
+                    None => TickMode::Count(1),
+                    Some(raw) => {
+                        if let Ok(count) = raw.parse::<u32>() {
+                            TickMode::Count(count)
+                        } else {
+                            match raw.to_ascii_lowercase().as_str() {
+                                "next" => TickMode::NextEvent,
+                                "rent" => TickMode::NextRent,
+                                "resume" if *resume_ticks > 0 => TickMode::Count(*resume_ticks),
+                                "resume" => {
+                                    say!(out, "Nothing to resume.");
+                                    return CommandFlow::Continue;
+                                }
+                                "stops" => {
+                                    match parts.next() {
+                                        None => {}
+                                        Some(raw) => match TickStops::parse(raw) {
+                                            Some(stops) => *tick_stops = stops,
+                                            None => {
+                                                say!(out, "Usage: tick stops [all|none|combat,endgame,punctuation]");
+                                                return CommandFlow::Continue;
+                                            }
+                                        },
+                                    }
+                                    say!(
+                                        out,
+                                        "Tick stops -> combat={} endgame={} punctuation={} (death always stops)",
+                                        tick_stops.combat, tick_stops.endgame, tick_stops.punctuation
+                                    );
+                                    return CommandFlow::Continue;
+                                }
+                                "crisis" => TickMode::CrisisOnly,
+                                "skip" => {
+                                    let days = parts
+                                        .next()
+                                        .and_then(|value| value.parse::<u32>().ok())
+                                        .unwrap_or(1)
+                                        .max(1);
+                                    TickMode::SkipDays(days)
+                                }
+                                _ => {
+                                    say!(out, "Usage: tick [n|next|rent|crisis|skip [days]|resume|stops ...]");
+                                    return CommandFlow::Continue;
+                                }
+                            }
+                        }
+                    }
+                };
+                let auto_mode = match tick_mode {
+                    TickMode::NextRent => AutoResolveMode::SkipRent,
+                    TickMode::CrisisOnly | TickMode::SkipDays(_) => AutoResolveMode::SkipCrises,
+                    TickMode::Count(_) | TickMode::NextEvent => AutoResolveMode::Normal,
+                };
+                let context = CivilianChoiceContext {
+                    library: civilian_events,
+                    city,
+                    seed: roll_seed(world_seed, world.turn),
+                };
+                let auto_resolved = auto_resolve_civilian_events(
+                    out,
+                    civilian_state,
+                    context,
+                    origin_quest,
+                    origin_paths,
+                    pressure,
+                    auto_mode,
+                );
+                if auto_resolved > 0 {
+                    apply_civilian_pressure(civilian_state, pressure);
+                    apply_pressure_modifiers(world, pressure, pressure_curve, endgame_state);
+                }
+                let mut stop_reason = None;
+                if !matches!(tick_mode, TickMode::Count(_)) {
+                    stop_reason = match tick_mode {
+                        TickMode::NextRent if has_rent_due_pending(civilian_state) => {
+                            Some(TickStopReason::RentDue)
+                        }
+                        TickMode::NextEvent
+                            if has_visible_pending_civilian_events(
+                                civilian_state,
+                                civilian_events,
+                                game_time,
+                            ) || !global_event_state.pending.is_empty() =>
+                        {
+                            Some(TickStopReason::PendingEvent)
+                        }
+                        TickMode::CrisisOnly | TickMode::SkipDays(_)
+                            if has_crisis_pending(civilian_state)
+                                || !global_event_state.pending.is_empty() =>
+                        {
+                            Some(TickStopReason::Crisis)
+                        }
+                        _ => None,
+                    };
+                }
+                let mut ticks_run = 0u32;
+                let mut death_pending = false;
+                let mut economy_days = Vec::new();
+                let mut restock_days = Vec::new();
+                if stop_reason.is_none() {
+                    let target_ticks = match tick_mode {
+                        TickMode::Count(count) => count,
+                        TickMode::SkipDays(days) => days.saturating_mul(24),
+                        _ => MAX_FAST_FORWARD_TICKS,
+                    };
+                    for _ in 0..target_ticks {
+                        let mut storylet_triggered = false;
+                        let report = WorldTick {
+                            world,
+                            actor,
+                            evidence,
+                            identity_evidence,
+                            city,
+                            city_events,
+                            faction_director,
+                            faction_events,
+                            resolved_faction_events,
+                            cases,
+                            case_log,
+                            agents,
+                            agent_events,
+                            persona_stack,
+                            storylets,
+                            civilian_events,
+                            alignment: *alignment,
+                            player_pos,
+                            game_time,
+                            civilian_state,
+                            storylet_state,
+                            endgame_state,
+                            pressure_curve,
+                            pressure,
+                            region,
+                            region_events,
+                            global_event_state,
+                            global_event_log,
+                            global_events,
+                            global_faction_director,
+                            global_faction_events,
+                            origin_quest,
+                            growth,
+                            origin_paths,
+                            world_seed,
+                        }
+                        .run(out, 1, auto_mode, &mut storylet_triggered);
+                        death_pending = report.death_pending;
+                        economy_days.extend(report.economy);
+                        restock_days.extend(report.restocks);
+                        maybe_awaken_mutant_powers(
+                            out,
+                            civilian_state,
+                            repo,
+                            omni_catalog,
+                            omni_registry,
+                            power_assignment_config,
+                            world.turn,
+                        );
+                        ticks_run += 1;
+                        if death_pending {
+                            stop_reason = Some(TickStopReason::Death);
+                            break;
+                        }
+                        if dispatch_storylet_handoffs(
+                            out,
+                            &report.storylet_handoffs,
+                            combat,
+                            city,
+                            persona_stack,
+                            repo,
+                            roll_seed(world_seed, world.turn),
+                        ) {
+                            stop_reason = Some(TickStopReason::Storylet);
+                            break;
+                        }
+                        let endgame_line = handle_endgame_transition(
+                            cases,
+                            pressure,
+                            resolved_faction_events,
+                            world,
+                            storylet_state,
+                            endgame_state,
+                            pressure_curve,
+                        );
+                        let endgame_changed = endgame_line.is_some();
+                        if let Some(line) = endgame_line {
+                            say!(out, "{}", line);
+                        }
+                        stop_reason = tick_stops
+                            .interrupt(endgame_changed, report.punctuation_storylet.is_some());
+                        if stop_reason.is_some() {
+                            break;
+                        }
+                        stop_reason = tick_stops.ambush(|| {
+                            if combat.active {
+                                return false;
+                            }
+                            let attacker = ambush_attacker(
+                                agents,
+                                persona_stack,
+                                city,
+                                *ambush_setting,
+                                world.turn,
+                            );
+                            let (Some(agent), Some(persona)) =
+                                (attacker, persona_stack.active_persona())
+                            else {
+                                return false;
+                            };
+                            let seed = roll_seed(world_seed, world.turn);
+                            start_ambush(out, combat, &agent, persona, city, repo, seed);
+                            true
+                        });
+                        if stop_reason.is_some() {
+                            break;
+                        }
+                        match tick_mode {
+                            TickMode::Count(_) => {
+                                if ticks_run >= target_ticks {
+                                    stop_reason = Some(TickStopReason::Completed);
+                                    break;
+                                }
+                            }
+                            TickMode::NextRent => {
+                                if has_rent_due_pending(civilian_state) {
+                                    stop_reason = Some(TickStopReason::RentDue);
+                                    break;
+                                }
+                            }
+                            TickMode::NextEvent => {
+                                if storylet_triggered {
+                                    stop_reason = Some(TickStopReason::Storylet);
+                                    break;
+                                }
+                                if has_visible_pending_civilian_events(
+                                    civilian_state,
+                                    civilian_events,
+                                    game_time,
+                                ) || !global_event_state.pending.is_empty()
+                                {
+                                    stop_reason = Some(TickStopReason::PendingEvent);
+                                    break;
+                                }
+                            }
+                            TickMode::CrisisOnly => {
+                                if has_crisis_pending(civilian_state)
+                                    || !global_event_state.pending.is_empty()
+                                {
+                                    stop_reason = Some(TickStopReason::Crisis);
+                                    break;
+                                }
+                            }
+                            TickMode::SkipDays(_) => {
+                                if has_crisis_pending(civilian_state)
+                                    || !global_event_state.pending.is_empty()
+                                {
+                                    stop_reason = Some(TickStopReason::Crisis);
+                                    break;
+                                }
+                                if ticks_run >= target_ticks {
+                                    stop_reason = Some(TickStopReason::Completed);
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    if stop_reason.is_none() && !matches!(tick_mode, TickMode::Count(_)) {
+                        stop_reason = Some(TickStopReason::MaxTicks);
+                    }
+                }
+                *resume_ticks = 0;
+                if let TickMode::Count(count) = tick_mode {
+                    let reason = stop_reason.unwrap_or(TickStopReason::Completed);
+                    *resume_ticks = reason.ticks_to_resume(count, ticks_run);
+                    if reason != TickStopReason::Completed {
+                        say!(
+                            out,
+                            "Stopped after {} turns: {} ({} remaining, `tick resume` to continue).",
+                            ticks_run,
+                            reason.label(),
+                            resume_ticks
+                        );
+                    }
+                } else {
+                    let reason = stop_reason.unwrap_or(TickStopReason::Completed);
+                    say!(
+                        out,
+                        "Tick stop: {} ({} ticks).",
+                        reason.label(),
+                        ticks_run
+                    );
+                }
+                print_economy_days(out, &economy_days);
+                print_restock_days(out, &restock_days);
+                if death_pending {
+                    *life_ended |=
+                        process_civilian_death(out, civilian_state, *alignment, combat, run_stats);
+                }
+                if let Some(line) = handle_endgame_transition(
+                    cases,
+                    pressure,
+                    resolved_faction_events,
+                    world,
+                    storylet_state,
+                    endgame_state,
+                    pressure_curve,
+                ) {
+                    say!(out, "{}", line);
+                }
+                apply_cast_aging(
+                    out,
+                    characters,
+                    world_repo.as_mut(),
+                    game_time.day,
+                    world.turn,
+                    last_cast_aging_year,
+                );
+                print_tick_summary(
+                    out,
+                    world,
+                    persona_stack,
+                    city,
+                    cases,
+                    pressure,
+                    endgame_state,
+                );
+                print_case_log(out, case_log);
+            }
+            _ => {
+                say!(out, "Unknown command. Type 'help'.");
+            }
+        }
+        CommandFlow::Continue
+    }
+
+    /// Runs REPL command lines and returns everything they printed, one entry
+    /// per line. Stops early at `quit`.
+    pub fn run_script(&mut self, commands: &[&str]) -> Vec<String> {
+        for command in commands {
+            if self.dispatch(command) == CommandFlow::Quit {
+                break;
+            }
+        }
+        self.take_output()
+    }
+
+    /// Drains the lines printed since the last call.
+    pub fn take_output(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.output)
+    }
+
+    /// The world turn the session has reached.
+    pub fn world_turn(&self) -> u64 {
+        self.world.turn
+    }
+
+    pub fn persist(&mut self) {
+        self.last_save_turn = self.world.turn;
+        let state = self.world_db_state();
+        if let Err(err) = self.world_repo.save_if_dirty(&state) {
+            say!(&mut self.output, "Failed to persist world state: {}", err);
+        }
+    }
+
+    fn world_db_state(&self) -> WorldDbState {
+        WorldDbState {
+            world_turn: self.world.turn,
+            game_time: self.game_time.clone(),
+            city: self.city.clone(),
+            cases: self.cases.clone(),
+            combat: self.combat.clone(),
+            growth: self.growth.clone(),
+            civilian_state: self.civilian_state.clone(),
+            storylet_state: self.storylet_state.clone(),
+            persona_stack: self.persona_stack.clone(),
+            alignment: self.alignment,
+            omni_registry: self.omni_registry.clone(),
+            run_stats: self.run_stats.clone(),
+            evidence: self.evidence.clone(),
+            identity_evidence: self.identity_evidence.clone(),
+            pressure: self.pressure.clone(),
+            region: self.region.clone(),
+            ambush: self.agents.ambush_state(self.ambush_setting),
+        }
+    }
+}
+
+/// Commands that only print state. Anything not listed here may change the world
+/// and marks the save dirty. The bookkeeping `run_command` does before every
+/// command is derived from saved state, so a reload replays it.
+fn is_read_only_command(input: &str) -> bool {
+    let mut parts = input.split_whitespace();
+    let cmd = parts.next().unwrap_or("").to_lowercase();
+    let sub = parts.next().map(str::to_lowercase);
+    match (cmd.as_str(), sub.as_deref()) {
+        (
+            "quit" | "exit" | "help" | "stats" | "mystats" | "power" | "list" | "ctx" | "loc"
+            | "persona" | "personas" | "seed" | "legacy" | "cast" | "diagnose" | "cd" | "scene"
+            | "storylets" | "events" | "endgame" | "cosmic" | "author",
+            _,
+        ) => true,
+        (
+            "travel" | "decoy" | "ambush" | "growth" | "cases" | "craft" | "favor" | "cover"
+            | "punctuation" | "civilian" | "global",
+            None,
+        ) => true,
+        ("alignment", None | Some("status")) => true,
+        ("civilian" | "global", Some("events" | "detail")) => true,
+        ("origin", Some("paths" | "status")) => true,
+        ("alien", None | Some("generate" | "detail")) => true,
+        ("combat", Some("log" | "preview")) => true,
+        ("tick", Some("stops")) => parts.next().is_none(),
+        _ => false,
+    }
+}
+
+fn opponent_loadout_pool(
+    out: &mut Vec<String>,
+    repo: &dyn PowerRepository,
+    scale: CombatScale,
+) -> Vec<ExpressionId> {
+    let mut pool = match repo.expressions_by_scale(loadout_scale(scale), 64) {
+        Ok(exprs) => exprs,
+        Err(err) => {
+            say!(out, "Opponent loadouts unavailable: {}", err);
+            return Vec::new();
+        }
+    };
+    if pool.is_empty() && scale != CombatScale::Street {
+        pool = repo
+            .expressions_by_scale(loadout_scale(CombatScale::Street), 64)
+            .unwrap_or_default();
+    }
+    pool.into_iter()
+        .filter(|expr| {
+            expr.form != crate::rules::ExpressionForm::Passive
+                && !expr.signatures.is_empty()
+        })
+        .map(|expr| expr.id)
+        .collect()
+}
+
+fn load_opponent_loadouts(
+    combat: &CombatState,
+    repo: &dyn PowerRepository,
+) -> Vec<crate::rules::ExpressionDef> {
+    let mut ids: Vec<&ExpressionId> = combat
+        .combatants
+        .iter()
+        .flat_map(|c| c.loadout.iter())
+        .collect();
+    ids.sort_by(|a, b| a.0.cmp(&b.0));
+    ids.dedup();
+    ids.into_iter()
+        .filter_map(|expr_id| repo.expression(expr_id).ok())
+        .collect()
+}
+
+fn apply_growth_on_use(
+    out: &mut Vec<String>,
+    growth: &mut GrowthState,
+    expr: &crate::rules::ExpressionDef,
+    repo: &dyn PowerRepository,
+    storylet_state: &mut StoryletState,
+    alignment: Alignment,
+) {
+    let stage_change = record_expression_use(growth, expr);
+    if let Some(stage) = stage_change {
+        say!(out, "Mastery advanced: {} -> {:?}", expr.id.0, stage);
+        if let Some(flag) = mastery_stage_flag(stage) {
+            storylet_state.flags.insert(flag.to_string(), true);
+            storylet_state
+                .flags
+                .insert(format!("{}_{}", flag, expr.id.0), true);
+        }
+        if let Ok(candidates) = repo.expressions_for_power(expr.power_id) {
+            if let Some(unlocked) =
+                select_evolution_candidate(
+                expr,
+                &candidates,
+                &growth.unlocked_expressions,
+                Some(alignment),
+            ) {
+                growth.unlocked_expressions.insert(unlocked.clone());
+                say!(out, "Evolution unlocked expression {}", unlocked.0);
+            }
+        }
+    }
+}
+
+fn mastery_stage_flag(stage: crate::rules::MasteryStage) -> Option<&'static str> {
+    match stage {
+        crate::rules::MasteryStage::Precise => Some("mastery_precise"),
+        crate::rules::MasteryStage::Silent => Some("mastery_silent"),
+        crate::rules::MasteryStage::Iconic => Some("mastery_iconic"),
+        _ => None,
+    }
+}
+
+fn print_growth_expr(out: &mut Vec<String>, growth: &GrowthState, expr_id: &str) {
+    let expr_id = ExpressionId(expr_id.to_string());
+    let unlocked = growth.unlocked_expressions.contains(&expr_id);
+    match growth.mastery.get(&expr_id) {
+        Some(entry) => {
+            say!(
+                out,
+                "Expression {} -> unlocked={} stage={:?} uses={} ({})",
+                expr_id.0,
+                unlocked,
+                entry.stage,
+                entry.uses,
+                mastery_progress_label(entry.uses)
+            );
+        }
+        None => {
+            say!(
+                out,
+                "Expression {} -> unlocked={} stage=RAW uses=0 ({})",
+                expr_id.0,
+                unlocked,
+                mastery_progress_label(0)
+            );
+        }
+    }
+}
+
+fn mastery_progress_label(uses: u32) -> String {
+    match crate::rules::MasteryStage::uses_to_next(uses) {
+        Some((stage, remaining)) => format!("{} uses to {:?}", remaining, stage),
+        None => "fully mastered".to_string(),
+    }
+}
+
+fn seed_mastery(out: &mut Vec<String>, growth: &mut GrowthState, expr_id: &str, uses: u32) {
+    let expr_id = ExpressionId(expr_id.to_string());
+    let stage = crate::rules::stage_from_uses(uses);
+    growth.unlocked_expressions.insert(expr_id.clone());
+    growth.mastery.insert(
+        expr_id.clone(),
+        crate::simulation::growth::ExpressionMastery { stage, uses },
+    );
+    say!(
+        out,
+        "Mastery seeded: {} -> {:?} (uses={})",
+        expr_id.0, stage, uses
+    );
+}
+
+
+
+
+pub fn clock_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+/// Mixes the world seed into a per-turn roll seed. Saves without a recorded seed
+/// use 0, which keeps their rolls keyed on the turn alone.
+fn roll_seed(world_seed: u64, turn: u64) -> u64 {
+    world_seed ^ turn
+}
+
+fn print_seed(out: &mut Vec<String>, world_seed: u64, world: &WorldState, game_time: &GameTime) {
+    say!(out, "World seed: {}", world_seed);
+    say!(
+        out,
+        "RNG position: turn {} (day {}, tick {})",
+        world.turn,
+        game_time.day,
+        game_time.tick
+    );
+    say!(
+        out,
+        "For bug reports, attach this seed and the commands you entered."
+    );
+}
+
+fn print_stats(out: &mut Vec<String>, repo: &dyn PowerRepository) {
+    match repo.stats() {
+        Ok(stats) => say!(
+            out,
+            "Stats: powers={}, expressions={}, acquisition_profiles={}",
+            stats.power_count, stats.expression_count, stats.acquisition_count
+        ),
+        Err(err) => say!(out, "Stats unavailable: {}", err),
+    }
+}
+
+fn print_power(out: &mut Vec<String>, repo: &dyn PowerRepository, power_id: i64) {
+    let info = match repo.power_info(PowerId(power_id)) {
+        Ok(info) => info,
+        Err(err) => {
+            say!(out, "Failed to query power: {}", err);
+            return;
+        }
+    };
+
+    let Some(info) = info else {
+        say!(out, "No power found for id {}", power_id);
+        return;
+    };
+
+    say!(out, "Power [{}]: {}", power_id, info.name);
+    if let Some(overview) = info.overview {
+        if !overview.trim().is_empty() {
+            say!(out, "Overview: {}", overview.trim());
+        }
+    }
+    if let Some(description) = info.description {
+        if !description.trim().is_empty() {
+            say!(out, "Description: {}", description.trim());
+        }
+    }
+    if let Some(short) = info.text_short {
+        if !short.trim().is_empty() {
+            say!(out, "Short: {}", short.trim());
+        }
+    }
+    if let Some(mech) = info.text_mechanical {
+        if !mech.trim().is_empty() {
+            say!(out, "Mechanical: {}", mech.trim());
+        }
+    }
+
+    match repo.expressions_for_power(PowerId(power_id)) {
+        Ok(expressions) => {
+            say!(out, "Expressions:");
+            for expr in expressions {
+                say!(
+                    out,
+                    "  [{}] {} ({:?}/{:?}/{:?})",
+                    expr.id.0, expr.text.ui_name, expr.form, expr.delivery, expr.scale
+                );
+            }
+        }
+        Err(err) => say!(out, "Failed to load expressions: {}", err),
+    }
+
+    match repo.acquisition_profiles(PowerId(power_id)) {
+        Ok(profiles) if profiles.is_empty() => say!(out, "Acquisition: none"),
+        Ok(profiles) => {
+            say!(out, "Acquisition:");
+            for profile in profiles {
+                let origin = match (profile.origin_class, profile.origin_subtype) {
+                    (Some(class), Some(subtype)) => format!("{}/{}", class, subtype),
+                    (Some(class), None) => class,
+                    _ => "ANY".to_string(),
+                };
+                let mut requires = Vec::new();
+                if let Some(kind) = profile.requires_entity_kind {
+                    requires.push(format!("entity={}", kind));
+                }
+                if let Some(tags) = profile.requires_tags_any {
+                    requires.push(format!("tags_any={}", tags));
+                }
+                if let Some(tags) = profile.requires_tags_all {
+                    requires.push(format!("tags_all={}", tags));
+                }
+                let requires = if requires.is_empty() {
+                    "none".to_string()
+                } else {
+                    requires.join(" ")
+                };
+                say!(
+                    out,
+                    "  {} via {}/{} rarity={} requires={}",
+                    origin,
+                    profile.delivery_channel,
+                    profile.event_kind,
+                    profile.rarity_weight,
+                    requires
+                );
+            }
+        }
+        Err(err) => say!(out, "Failed to load acquisition profiles: {}", err),
+    }
+}
+
+fn print_use_result(out: &mut Vec<String>, result: &crate::rules::UseResult) {
+    say!(out, "Mastery: {:?}", result.mastery_stage);
+    if result.falloff < 1.0 {
+        say!(out, "Range falloff: {:.0}% effect", result.falloff * 100.0);
+    }
+    let mut stamina = 0;
+    let mut focus = 0;
+    let mut resource = 0;
+    let mut cooldown = None;
+    for cost in &result.applied_costs {
+        match cost.cost_type {
+            CostType::Stamina => stamina += cost.value.unwrap_or(0),
+            CostType::Focus => focus += cost.value.unwrap_or(0),
+            CostType::Resource => resource += cost.value.unwrap_or(0),
+            CostType::Cooldown => cooldown = cost.value.or(cooldown),
+            CostType::Risk => {}
+        }
+    }
+    say!(
+        out,
+        "Costs: stamina={}, focus={}, resource={}, cooldown={:?}",
+        stamina, focus, resource, cooldown
+    );
+
+    if result.emitted_signatures.is_empty() {
+        say!(out, "Signatures: none");
+    } else {
+        say!(out, "Signatures:");
+        for sig in &result.emitted_signatures {
+            say!(
+                out,
+                "  {:?} strength={} persistence={}",
+                sig.signature.signature_type, sig.signature.strength, sig.remaining_turns
+            );
+        }
+    }
+}
+
+/// Starts any fights storylets escalated into during the tick; civilian
+/// handoffs are queued inside `tick_world`. Returns true when combat started.
+fn dispatch_storylet_handoffs(
+    out: &mut Vec<String>,
+    handoffs: &[StoryletHandoff],
+    combat: &mut CombatState,
+    city: &CityState,
+    persona_stack: &PersonaStack,
+    repo: &SqlitePowerRepository,
+    seed: u64,
+) -> bool {
+    let mut started = false;
+    for handoff in handoffs {
+        match handoff {
+            StoryletHandoff::QueueCivilian(_) => {}
+            StoryletHandoff::StartCombat { scale, opponents } => {
+                if combat.active {
+                    continue;
+                }
+                let player_name = persona_stack
+                    .active_persona()
+                    .map(|p| p.label.clone())
+                    .unwrap_or_else(|| "Player".to_string());
+                start_combat(
+                    combat,
+                    CombatStaging::for_active_location(city),
+                    "storylet",
+                    *scale,
+                    &player_name,
+                    *opponents,
+                    seed,
+                );
+                let pool = opponent_loadout_pool(out, repo, *scale);
+                assign_opponent_loadouts(combat, &pool);
+                say!(out, "Storylet escalated into combat.");
+                print_combat_status(out, combat);
+                started = true;
+            }
+        }
+    }
+    started
+}
+
+fn print_turf_events(out: &mut Vec<String>, city_events: &CityEventLog) {
+    for event in &city_events.0 {
+        match &event.kind {
+            crate::simulation::city::CityEventKind::TurfEroded {
+                faction_id,
+                influence,
+            } => say!(
+                out,
+                "Turf: police pressure pushes {} back at location {} (influence {})",
+                faction_id, event.location_id.0, influence
+            ),
+            crate::simulation::city::CityEventKind::PatrolSuppressed {
+                faction_id,
+                influence,
+            } => say!(
+                out,
+                "Turf: patrol stonewalled on {} ground at location {} (influence {})",
+                faction_id, event.location_id.0, influence
+            ),
+            crate::simulation::city::CityEventKind::HeatResponseChanged { .. }
+            | crate::simulation::city::CityEventKind::EnvironmentAltered { .. } => {}
+        }
+    }
+}
+
+fn print_event_log(out: &mut Vec<String>, log: &mut WorldEventLog) {
+    if log.0.is_empty() {
+        return;
+    }
+    say!(out, "World events:");
+    for entry in log.0.drain(..) {
+        say!(out, "  {}", entry);
+    }
+}
+
+fn print_context(
+    out: &mut Vec<String>,
+    target: &TargetContext,
+    world: &WorldState,
+    city: &CityState,
+    pressure: &PressureState,
+    endgame_state: &EndgameState,
+) {
+    let active = city
+        .locations
+        .get(&city.active_location)
+        .map(|loc| (loc.heat, loc.response))
+        .unwrap_or((0, crate::simulation::city::HeatResponse::None));
+    say!(
+        out,
+        "Context: turn={}, dist_m={:?}, los={}, contact={}, public={}, witnesses={}, location={}, heat={}, response={:?}",
+        world.turn,
+        target.distance_m,
+        target.has_line_of_sight,
+        target.has_contact,
+        target.in_public,
+        target.witnesses,
+        city.active_location.0,
+        active.0,
+        active.1
+    );
+    say!(
+        out,
+        "Pressure: temporal={:.1} identity={:.1} institutional={:.1} moral={:.1} resource={:.1} psychological={:.1}",
+        pressure.temporal,
+        pressure.identity,
+        pressure.institutional,
+        pressure.moral,
+        pressure.resource,
+        pressure.psychological
+    );
+    let modifiers = endgame_state.modifiers();
+    say!(
+        out,
+        "Endgame: {} | modifiers cost={:.2} risk={:.2}",
+        endgame_state.label(),
+        modifiers.cost_scale,
+        modifiers.risk_scale
+    );
+}
+
+fn print_gadget_recipes(
+    out: &mut Vec<String>,
+    catalog: &GadgetRecipeCatalog,
+    civilian: &CivilianState,
+) {
+    say!(out, "Gadget recipes:");
+    for recipe in &catalog.recipes {
+        let materials: Vec<String> = recipe
+            .materials
+            .iter()
+            .map(|(pool, amount)| format!("{} {}", amount, pool))
+            .collect();
+        let output = match &recipe.output {
+            GadgetOutput::Resource { pool, amount } => format!("+{} {}", amount, pool),
+            GadgetOutput::Expression {
+                expression_id,
+                days,
+            } => format!("expression {} for {} days", expression_id, days),
+        };
+        say!(
+            out,
+            "  {} ({}): {} L{} | {} cash, {} -> {} | {}% chance",
+            recipe.id,
+            recipe.name,
+            recipe.skill.label(),
+            recipe.min_level,
+            recipe.cash_cost,
+            if materials.is_empty() {
+                "no materials".to_string()
+            } else {
+                materials.join(", ")
+            },
+            output,
+            craft_success_chance(civilian, recipe)
+        );
+    }
+    for unlock in &civilian.gadget_unlocks {
+        say!(
+            out,
+            "  Active gadget: {} lends {} until day {}",
+            unlock.recipe_id, unlock.expression_id, unlock.expires_day
+        );
+    }
+}
+
+fn print_favor_status(out: &mut Vec<String>, state: &CivilianState) {
+    say!(
+        out,
+        "Favors available: {} (owed back: {})",
+        state.available_favors(),
+        state.outstanding_favor_debt()
+    );
+    for contact in &state.contacts {
+        say!(
+            out,
+            "  {} [{:?}] influence={} bond={} debt={}",
+            contact.name, contact.domain, contact.influence, contact.bond, contact.favor_debt
+        );
+    }
+}
+
+fn print_favor_error(out: &mut Vec<String>, err: &FavorError, contact_name: &str) {
+    match err {
+        FavorError::UnknownContact => say!(out, "No contact named {}.", contact_name),
+        FavorError::NoFavorsLeft => {
+            say!(
+                out,
+                "No favors left to call in; debts are forgiven one a week."
+            )
+        }
+        FavorError::NotInfluential { need, have } => say!(
+            out,
+            "{} lacks the pull for that (influence {} < {}).",
+            contact_name, have, need
+        ),
+        FavorError::BondTooWeak { need, have } => say!(
+            out,
+            "{} won't stick their neck out yet (bond {} < {}).",
+            contact_name, have, need
+        ),
+        FavorError::WrongCircle(domain) => say!(
+            out,
+            "{} moves in {:?} circles and can't help with that.",
+            contact_name, domain
+        ),
+    }
+}
+
+fn print_cover_status(out: &mut Vec<String>, stack: &PersonaStack) {
+    let Some(active) = stack.active_persona() else {
+        say!(out, "No active persona.");
+        return;
+    };
+    let status = if active.burned {
+        "burned"
+    } else if active.is_on_brink() {
+        "on the brink"
+    } else {
+        "holding"
+    };
+    say!(
+        out,
+        "Cover for {}: exposure={} ({})",
+        active.label, active.suspicion.exposure_risk, status
+    );
+    if active.is_on_brink() {
+        say!(
+            out,
+            "  `cover pay` spends {} CR, `cover favor <contact>` calls in heat relief.",
+            BRINK_CASH_COST
+        );
+    }
+}
+
+fn print_brink_relief_error(out: &mut Vec<String>, err: &BrinkReliefError) {
+    match err {
+        BrinkReliefError::NoActivePersona => say!(out, "No active persona."),
+        BrinkReliefError::Burned => say!(out, "That cover is already blown."),
+        BrinkReliefError::NotOnBrink { exposure } => say!(
+            out,
+            "Exposure is {}; there is nothing to bury until it nears {}.",
+            exposure,
+            EXPOSURE_BRINK
+        ),
+        BrinkReliefError::NotEnoughCash { need, have } => {
+            say!(
+                out,
+                "Burying the trail costs {} CR; you have {}.",
+                need,
+                have
+            )
+        }
+        BrinkReliefError::Favor(err) => print_favor_error(out, err, "That contact"),
+    }
+}
+
+fn print_exposure_breach(out: &mut Vec<String>, breach: &ExposureBreach) {
+    say!(
+        out,
+        "Exposure breach: {} is burned. The press runs the name (media +{}, infamy +{}).",
+        breach.label, breach.media_boost, breach.infamy
+    );
+    if !breach.cases_named.is_empty() {
+        say!(
+            out,
+            "  {} case(s) now know who they are chasing.",
+            breach.cases_named.len()
+        );
+    }
+}
+
+/// Applies the world-side half of a favor; job referrals are handled entirely
+/// in the civilian layer.
+fn apply_favor(
+    out: &mut Vec<String>,
+    kind: FavorKind,
+    cases: &mut CaseRegistry,
+    city: &mut CityState,
+) {
+    match kind {
+        FavorKind::Intel => {
+            if let Some(location_id) = city.next_undiscovered() {
+                city.discover(location_id);
+                say!(
+                    out,
+                    "Favor: your contact points you to location {}.",
+                    location_id.0
+                );
+            }
+            let Some(case) = cases
+                .cases
+                .iter_mut()
+                .filter(|case| case.status == CaseStatus::Active)
+                .max_by_key(|case| case.progress)
+            else {
+                say!(out, "Favor: no active investigations to dig into.");
+                return;
+            };
+            let before = case.progress;
+            case.progress = case.progress.saturating_sub(FAVOR_INTEL_CASE_SETBACK);
+            case.revealed = true;
+            say!(
+                out,
+                "Favor: intel on case {} ({} at location {}); progress {} -> {}.",
+                case.case_id, case.faction_id, case.location_id.0, before, case.progress
+            );
+        }
+        FavorKind::HeatRelief => {
+            if let Some(location) = city.locations.get_mut(&city.active_location) {
+                let before = location.heat;
+                location.heat = (location.heat - FAVOR_HEAT_RELIEF).max(0);
+                say!(
+                    out,
+                    "Favor: heat {} -> {} at location {}.",
+                    before,
+                    location.heat,
+                    location.id.0
+                );
+            }
+        }
+        FavorKind::JobReferral => {}
+    }
+}
+
+fn print_endgame_why(
+    out: &mut Vec<String>,
+    endgame_state: &EndgameState,
+    statuses: &[TriggerStatus],
+) {
+    say!(out, "Endgame: {}", endgame_state.label());
+    let firing = statuses.iter().find(|status| status.met());
+    for status in statuses {
+        let note = if firing == Some(status) {
+            "fires next evaluation".to_string()
+        } else if status.met() {
+            "met, but an earlier trigger takes priority".to_string()
+        } else {
+            format!("{:.1} short", status.shortfall())
+        };
+        say!(
+            out,
+            "  {} ({:?}): {} {:.1}/{:.1} -> {}",
+            endgame_phase_label(Some(status.state)),
+            status.trigger,
+            status.measure,
+            status.current,
+            status.threshold,
+            note
+        );
+    }
+    if firing.is_some_and(|status| endgame_state.phase == Some(status.state)) {
+        say!(
+            out,
+            "  Already in this phase; the trigger will not fire again."
+        );
+    }
+}
+
+fn print_combat_status(out: &mut Vec<String>, state: &CombatState) {
+    if !state.active {
+        say!(out, "Combat: inactive");
+        return;
+    }
+    let report = combat_report(state);
+    say!(
+        out,
+        "Combat: {:?} | difficulty={:?} | tick={} | opponents={} | allies={} | player_stress={}/{} | escape={}/{}",
+        report.scale,
+        state.difficulty,
+        report.tick,
+        report.opponents,
+        report.allies,
+        report.player_stress,
+        report.player_stress_cap,
+        report.escape_progress,
+        report.escape_threshold
+    );
+    if let Some(distance) = state.distance_m {
+        say!(out, "  Distance to opponents: {}m", distance);
+    }
+    for wave in &state.pending_waves {
+        say!(
+            out,
+            "  Wave pending: {} opponent(s) at tick {} ({:?})",
+            wave.opponent_count, wave.delay_ticks, wave.scale
+        );
+    }
+    for ally in state.allies() {
+        let status = if ally.is_defeated() { " [down]" } else { "" };
+        say!(
+            out,
+            "  Ally {}: stress={}/{}{}",
+            ally.name, ally.stress, ally.stress_cap, status
+        );
+    }
+}
+
+/// The hostile agent who springs an ambush on the active persona this turn, if any.
+fn ambush_attacker(
+    agents: &mut AgentRegistry,
+    persona_stack: &PersonaStack,
+    city: &CityState,
+    setting: AmbushSetting,
+    turn: u64,
+) -> Option<Agent> {
+    let persona = persona_stack.active_persona()?;
+    trigger_ambush(
+        agents,
+        city.active_location,
+        persona.suspicion.exposure_risk,
+        setting,
+        turn,
+    )
+}
+
+fn start_ambush(
+    out: &mut Vec<String>,
+    combat: &mut CombatState,
+    agent: &Agent,
+    persona: &Persona,
+    city: &CityState,
+    repo: &dyn PowerRepository,
+    seed: u64,
+) {
+    let exposure = persona.suspicion.exposure_risk;
+    let opponent_count = if exposure >= 80 { 2 } else { 1 };
+    start_combat(
+        combat,
+        CombatStaging::for_active_location(city),
+        &format!("ambush:{}", agent.name),
+        CombatScale::Street,
+        &persona.label,
+        opponent_count,
+        seed ^ (agent.id as u64).wrapping_mul(0x9E37_79B9),
+    );
+    if let Some(opponent) = combat.opponents_mut().next() {
+        opponent.name = agent.name.clone();
+    }
+    let pool = opponent_loadout_pool(out, repo, CombatScale::Street);
+    assign_opponent_loadouts(combat, &pool);
+    say!(
+        out,
+        "Ambush! {} jumps you at location {} (exposure={}).",
+        agent.name, city.active_location.0, exposure
+    );
+    print_combat_status(out, combat);
+}
+
+fn print_combat_preview(out: &mut Vec<String>, state: &CombatState) {
+    if !state.active {
+        say!(out, "Combat: inactive");
+        return;
+    }
+    let preview = combat_preview(state);
+    let escape = if preview.escaping {
+        format!("escaping, {} tick(s) to break away", preview.escape_ticks_remaining)
+    } else {
+        format!(
+            "not escaping, {} tick(s) of escape needed",
+            preview.escape_ticks_remaining
+        )
+    };
+    say!(
+        out,
+        "Combat preview: {} | stress headroom={} | breaking opponents={}",
+        escape, preview.player_stress_headroom, preview.breaking_opponents
+    );
+}
+
+fn print_combat_log(out: &mut Vec<String>, state: &CombatState) {
+    if state.log.is_empty() {
+        say!(out, "Combat log: empty");
+        return;
+    }
+    say!(out, "Combat log:");
+    for entry in state.log.iter() {
+        say!(out, "  {}", entry);
+    }
+}
+
+fn format_combat_end(end: CombatEnd) -> &'static str {
+    match end {
+        CombatEnd::PlayerEscaped => "player escaped",
+        CombatEnd::PlayerDefeated => "player defeated",
+        CombatEnd::OpponentsDefeated => "opponents defeated",
+        CombatEnd::OpponentsSurrendered => "opponents surrendered",
+        CombatEnd::Resolved => "resolved",
+    }
+}
+
+fn parse_combat_intent(raw: &str) -> Option<CombatIntent> {
+    match raw.to_lowercase().as_str() {
+        "attack" => Some(CombatIntent::Attack),
+        "escape" => Some(CombatIntent::Escape),
+        "hold" => Some(CombatIntent::Hold),
+        "capture" => Some(CombatIntent::Capture),
+        _ => None,
+    }
+}
+
+fn print_persona_state(
+    out: &mut Vec<String>,
+    stack: &PersonaStack,
+    alignment: Alignment,
+    city: &CityState,
+    cases: &CaseRegistry,
+) {
+    say!(
+        out,
+        "Alignment: {:?} | Active persona: {}",
+        alignment, stack.active_persona_id
+    );
+    if let Some(active) = stack.active_persona() {
+        say!(
+            out,
+            "Active type: {:?} | Label: {}",
+            active.persona_type, active.label
+        );
+    }
+    for persona in &stack.personas {
+        say!(
+            out,
+            "Persona {} ({:?}) -> public={} civilian={} wanted={} exposure={}{}",
+            persona.persona_id,
+            persona.persona_type,
+            persona.suspicion.public_suspicion,
+            persona.suspicion.civilian_suspicion,
+            persona.suspicion.wanted_level,
+            persona.suspicion.exposure_risk,
+            if persona.burned {
+                " [burned]"
+            } else if persona.is_on_brink() {
+                " [on the brink]"
+            } else {
+                ""
+            }
+        );
+    }
+    let active_location = city.active_location;
+    let max_progress = cases
+        .cases
+        .iter()
+        .filter(|case| case.location_id == active_location)
+        .map(|case| case.progress)
+        .max()
+        .unwrap_or(0);
+    if let Some(location) = city.locations.get(&active_location) {
+        say!(
+            out,
+            "Pressure: heat={} cases_max_progress={}",
+            location.heat, max_progress
+        );
+    }
+}
+
+fn print_growth_state(out: &mut Vec<String>, growth: &GrowthState) {
+    say!(
+        out,
+        "Growth: mastery={} unlocked={} pressure_resistance={}",
+        growth.mastery.len(),
+        growth.unlocked_expressions.len(),
+        growth.pressure_resistance
+    );
+    say!(
+        out,
+        "Reputation: trust={} fear={} infamy={} symbolism={}",
+        growth.reputation.trust,
+        growth.reputation.fear,
+        growth.reputation.infamy,
+        growth.reputation.symbolism
+    );
+    let mut mastery: Vec<_> = growth.mastery.iter().collect();
+    mastery.sort_by(|a, b| a.0 .0.cmp(&b.0 .0));
+    for (expr_id, entry) in mastery {
+        say!(
+            out,
+            "  {} | {:?} | uses={} | {}",
+            expr_id.0,
+            entry.stage,
+            entry.uses,
+            mastery_progress_label(entry.uses)
+        );
+    }
+}
+
+fn print_persona_stack(out: &mut Vec<String>, stack: &PersonaStack, turn: u64) {
+    say!(out, "Persona stack:");
+    for persona in &stack.personas {
+        let cooldown = stack.next_switch_tick.saturating_sub(turn);
+        say!(
+            out,
+            "  {} | {:?} | label={} | switch_cd={} turns",
+            persona.persona_id, persona.persona_type, persona.label, cooldown
+        );
+    }
+}
+
+struct AlignmentUnlockStatus {
+    has_power: bool,
+    has_wealth: bool,
+    unlocked: bool,
+}
+
+fn alignment_unlock_status(
+    origin_quest: &OriginQuestState,
+    growth: &GrowthState,
+    civilian_state: &CivilianState,
+) -> AlignmentUnlockStatus {
+    let has_power = origin_quest.completed || !growth.unlocked_expressions.is_empty();
+    let has_wealth =
+        civilian_state.wealth.tier.rank() >= WealthTier::Affluent.rank();
+    AlignmentUnlockStatus {
+        has_power,
+        has_wealth,
+        unlocked: has_power || has_wealth,
+    }
+}
+
+fn print_alignment_status(
+    out: &mut Vec<String>,
+    alignment: Alignment,
+    origin_quest: &OriginQuestState,
+    growth: &GrowthState,
+    civilian_state: &CivilianState,
+) {
+    say!(out, "Alignment: {:?}", alignment);
+    if alignment != Alignment::Neutral {
+        return;
+    }
+    let status = alignment_unlock_status(origin_quest, growth, civilian_state);
+    say!(
+        out,
+        "Alignment choice: power_unlocked={} wealth_unlocked={} -> {}",
+        status.has_power,
+        status.has_wealth,
+        if status.unlocked { "available" } else { "locked" }
+    );
+}
+
+fn apply_alignment_choice(
+    alignment: &mut Alignment,
+    persona_stack: &mut PersonaStack,
+    choice: Alignment,
+) {
+    *alignment = choice;
+    *persona_stack = PersonaStack::for_alignment(choice);
+}
+
+fn set_masked_label(stack: &mut PersonaStack, label: &str) -> bool {
+    if let Some(masked) = stack
+        .personas
+        .iter_mut()
+        .find(|persona| persona.persona_type == PersonaType::Masked)
+    {
+        masked.label = label.to_string();
+        return true;
+    }
+    false
+}
+
+fn initialize_mutant_lineage(
+    out: &mut Vec<String>,
+    civilian_state: &mut CivilianState,
+    repo: &dyn PowerRepository,
+    seed: u64,
+) {
+    if civilian_state.life.mutant_gene {
+        if civilian_state.mutant_profile.parent_power_ids.is_empty() {
+            match seed_mutant_inheritance(repo, seed, 1) {
+                Ok(inheritance) => {
+                    civilian_state.mutant_profile.omega_parent = inheritance.omega_parent;
+                    civilian_state.mutant_profile.parent_power_ids = inheritance
+                        .parent_powers
+                        .iter()
+                        .map(|power| power.0)
+                        .collect();
+                }
+                Err(err) => {
+                    say!(out, "Failed to seed mutant inheritance: {}", err);
+                }
+            }
+        }
+        return;
+    }
+    if !civilian_state.mutant_profile.parent_power_ids.is_empty() {
+        civilian_state.life.mutant_gene = true;
+        return;
+    }
+
+    match roll_mutant_lineage(repo, seed) {
+        Ok(outcome) => {
+            if outcome.mutant_gene {
+                civilian_state.life.mutant_gene = true;
+                if let Some(inheritance) = outcome.inheritance {
+                    civilian_state.mutant_profile.omega_parent = inheritance.omega_parent;
+                    civilian_state.mutant_profile.parent_power_ids = inheritance
+                        .parent_powers
+                        .iter()
+                        .map(|power| power.0)
+                        .collect();
+                }
+            }
+        }
+        Err(err) => {
+            say!(out, "Failed to roll mutant lineage: {}", err);
+        }
+    }
+}
+
+fn start_new_life(
+    out: &mut Vec<String>,
+    actor: &mut ActorState,
+    growth: &mut GrowthState,
+    origin_quest: &mut OriginQuestState,
+    persona_stack: &mut PersonaStack,
+    alignment: &mut Alignment,
+    civilian_state: &mut CivilianState,
+    player_pos: &mut Position,
+    target: &mut TargetContext,
+    storylet_state: &mut StoryletState,
+    game_time: &GameTime,
+    repo: &dyn PowerRepository,
+    seed: u64,
+) {
+    *actor = ActorState {
+        stamina: 10,
+        focus: 10,
+        ..Default::default()
+    };
+    *growth = GrowthState::default();
+    *origin_quest = OriginQuestState::default();
+    let legacy = std::mem::take(&mut civilian_state.legacy);
+    *civilian_state = CivilianState::default();
+    civilian_state.legacy = legacy;
+    civilian_state.life.birth_day = game_time.day;
+    *alignment = Alignment::Neutral;
+    *persona_stack = neutral_persona_stack();
+    *player_pos = Position { x: 0, y: 0 };
+    *target = TargetContext {
+        distance_m: Some(10),
+        has_line_of_sight: true,
+        has_contact: false,
+        in_public: true,
+        witnesses: 0,
+    };
+    storylet_state.flags.remove("alignment_choice_unlocked");
+    storylet_state.flags.remove("alignment_chosen");
+    storylet_state.fired.remove("alignment_choice_prompt");
+    storylet_state.cooldowns.remove("alignment_choice_prompt");
+    storylet_state.flags.remove("life.mutation_ready");
+    storylet_state.fired.remove("life_mutation_spark");
+    storylet_state.cooldowns.remove("life_mutation_spark");
+    apply_legacy_perks(civilian_state, storylet_state);
+    initialize_mutant_lineage(out, civilian_state, repo, seed);
+}
+
+fn apply_legacy_perks(
+    civilian_state: &mut CivilianState,
+    storylet_state: &mut StoryletState,
+) {
+    let Some(record) = civilian_state.legacy.last() else {
+        return;
+    };
+    let mut effects = Vec::new();
+
+    if record.wealth_tier.rank() >= WealthTier::Affluent.rank() {
+        effects.push("cash:+60".to_string());
+        storylet_state
+            .flags
+            .insert("legacy.rumor.wealth".to_string(), true);
+    }
+    if record.reputation.community >= 70 {
+        effects.push("community:+4".to_string());
+        storylet_state
+            .flags
+            .insert("legacy.rumor.community".to_string(), true);
+    }
+    if record.reputation.media >= 70 {
+        effects.push("media:+4".to_string());
+        storylet_state
+            .flags
+            .insert("legacy.rumor.media".to_string(), true);
+    }
+    if record.reputation.career >= 70 {
+        effects.push("career:+4".to_string());
+        storylet_state
+            .flags
+            .insert("legacy.rumor.career".to_string(), true);
+    }
+    if record.career_level >= 4 {
+        effects.push("career_xp:+8".to_string());
+    }
+    if record
+        .achievements
+        .iter()
+        .any(|entry| entry.eq_ignore_ascii_case("Power broker"))
+    {
+        effects.push("favors:+2".to_string());
+        storylet_state
+            .flags
+            .insert("legacy.rumor.power".to_string(), true);
+    }
+    if record
+        .achievements
+        .iter()
+        .any(|entry| entry.eq_ignore_ascii_case("Local influence"))
+    {
+        effects.push("access:+2".to_string());
+        storylet_state
+            .flags
+            .insert("legacy.rumor.influence".to_string(), true);
+    }
+    if record.alignment != "Neutral" {
+        storylet_state
+            .flags
+            .insert("legacy.rumor.masked".to_string(), true);
+    }
+
+    if !effects.is_empty() {
+        apply_civilian_effects(civilian_state, &effects);
+    }
+}
+
+fn note_run_stats(
+    out: &mut Vec<String>,
+    run_stats: &mut RunStats,
+    cases: &CaseRegistry,
+    persona_stack: &PersonaStack,
+    civilian_state: &CivilianState,
+) {
+    run_stats.note_cases(cases);
+    for label in run_stats.note_personas(persona_stack) {
+        say!(out, "Persona burned: {} is fully exposed.", label);
+    }
+    if let Some(tier) = run_stats.note_wealth(civilian_state) {
+        say!(out, "New wealth peak: {}.", tier.label());
+    }
+}
+
+fn print_run_stats(out: &mut Vec<String>, run_stats: &RunStats) {
+    say!(
+        out,
+        "Fights: won={} lost={} escaped={}",
+        run_stats.fights_won, run_stats.fights_lost, run_stats.fights_escaped
+    );
+    say!(
+        out,
+        "Civilians: saved={} casualties={}",
+        run_stats.civilians_saved, run_stats.civilian_casualties
+    );
+    say!(out, "Cases resolved: {}", run_stats.cases_resolved);
+    if run_stats.burned_personas.is_empty() {
+        say!(out, "Personas burned: none");
+    } else {
+        say!(
+            out,
+            "Personas burned: {} ({})",
+            run_stats.burned_personas.len(),
+            run_stats.burned_personas.join(", ")
+        );
+    }
+    say!(
+        out,
+        "Peak wealth: {} cr ({})",
+        run_stats.peak_net_worth,
+        run_stats
+            .peak_wealth_tier
+            .map(|tier| tier.label())
+            .unwrap_or("none")
+    );
+    let achievements = run_stats.achievements();
+    if !achievements.is_empty() {
+        say!(out, "Achievements: {}", achievements.join(", "));
+    }
+}
+
+fn apply_combat_wounds(
+    out: &mut Vec<String>,
+    civilian_state: &mut CivilianState,
+    combat: &CombatState,
+) {
+    let Some(player) = combat.player() else {
+        return;
+    };
+    let added = apply_combat_injuries(civilian_state, player.stress);
+    if added > 0 {
+        say!(
+            out,
+            "The fight follows you home: injuries +{} (now {}).",
+            added, civilian_state.health.injuries
+        );
+    }
+}
+
+fn process_civilian_death(
+    out: &mut Vec<String>,
+    civilian_state: &mut CivilianState,
+    alignment: Alignment,
+    combat: &CombatState,
+    run_stats: &RunStats,
+) -> bool {
+    if combat.active {
+        return false;
+    }
+    let Some(death) = civilian_state.pending_death.take() else {
+        return false;
+    };
+    let alignment_label = format!("{:?}", alignment);
+    let record = civilian_state.record_legacy(&alignment_label, &death.reason, death.day, run_stats);
+    say!(
+        out,
+        "You died on day {} at age {} ({}).",
+        death.day,
+        death.age_years,
+        death.reason
+    );
+    print_legacy_record(out, &record);
+    say!(
+        out,
+        "This life has ended. Use `life new` to start over or `legacy` to review past lives."
+    );
+    true
+}
+
+fn print_legacy_record(out: &mut Vec<String>, record: &LegacyRecord) {
+    say!(
+        out,
+        "  Legacy: day={} age={} stage={:?} alignment={} wealth={} career={:?} L{} reputation(career={} community={} media={}) death={}",
+        record.day,
+        record.age_years,
+        record.life_stage,
+        record.alignment,
+        record.wealth_tier.label(),
+        record.career_role,
+        record.career_level,
+        record.reputation.career,
+        record.reputation.community,
+        record.reputation.media,
+        record.death_reason
+    );
+    if !record.achievements.is_empty() {
+        say!(out, "  Achievements: {}", record.achievements.join(", "));
+    }
+}
+
+fn print_legacy(out: &mut Vec<String>, legacy: &[LegacyRecord]) {
+    if legacy.is_empty() {
+        say!(out, "No past lives recorded.");
+        return;
+    }
+    say!(out, "Legacy ({} lives):", legacy.len());
+    for record in legacy {
+        print_legacy_record(out, record);
+    }
+}
+
+fn maybe_awaken_mutant_powers(
+    out: &mut Vec<String>,
+    civilian_state: &mut CivilianState,
+    repo: &dyn PowerRepository,
+    omni_catalog: &OmniPowerCatalog,
+    omni_registry: &mut OmniPowerRegistry,
+    config: &PowerAssignmentConfig,
+    seed: u64,
+) {
+    if !civilian_state.life.mutant_gene
+        || !civilian_state.life.mutation_ready
+        || civilian_state.mutant_profile.awakened
+    {
+        return;
+    }
+
+    if civilian_state.mutant_profile.parent_power_ids.is_empty() {
+        match seed_mutant_inheritance(repo, seed, 1) {
+            Ok(inheritance) => {
+                civilian_state.mutant_profile.omega_parent = inheritance.omega_parent;
+                civilian_state.mutant_profile.parent_power_ids = inheritance
+                    .parent_powers
+                    .iter()
+                    .map(|power| power.0)
+                    .collect();
+            }
+            Err(err) => {
+                say!(out, "Failed to seed mutant inheritance: {}", err);
+            }
+        }
+    }
+
+    let parent_powers: Vec<PowerId> = civilian_state
+        .mutant_profile
+        .parent_power_ids
+        .iter()
+        .map(|power_id| PowerId(*power_id))
+        .collect();
+    let inheritance = MutantInheritanceProfile {
+        parent_powers,
+        omega_parent: civilian_state.mutant_profile.omega_parent,
+    };
+
+    match assign_mutant_powers(
+        repo,
+        omni_catalog,
+        omni_registry,
+        "player",
+        "prime",
+        seed,
+        config.clone(),
+        Some(inheritance),
+    ) {
+        Ok(assignment) => {
+            civilian_state.mutant_profile.awakened = true;
+            civilian_state.mutant_profile.baseline_power_id =
+                assignment.baseline.map(|power| power.0);
+            civilian_state.mutant_profile.expression_power_ids = assignment
+                .expressions
+                .iter()
+                .map(|power| power.0)
+                .collect();
+            civilian_state.mutant_profile.omni_power_ids = assignment
+                .omni
+                .iter()
+                .map(|power| power.0)
+                .collect();
+
+            if let Ok(tier) = classify_mutant_tier(
+                repo,
+                assignment.baseline,
+                &assignment.expressions,
+                &assignment.omni,
+                civilian_state.mutant_profile.omega_parent,
+            ) {
+                civilian_state.mutant_profile.tier = tier;
+            }
+
+            let baseline_count = if assignment.baseline.is_some() { 1 } else { 0 };
+            say!(
+                out,
+                "Mutation awakened: tier={} baseline={} expressions={} omni={}",
+                civilian_state.mutant_profile.tier_label(),
+                baseline_count,
+                assignment.expressions.len(),
+                assignment.omni.len()
+            );
+        }
+        Err(err) => {
+            say!(out, "Failed to assign mutant powers: {}", err);
+        }
+    }
+}
+
+fn handle_switch(
+    out: &mut Vec<String>,
+    persona_id: &str,
+    stack: &mut PersonaStack,
+    turn: u64,
+    city: &CityState,
+    evidence: &WorldEvidence,
+    target: &TargetContext,
+) {
+    let location_id = city.active_location;
+    let Some(location) = city.locations.get(&location_id) else {
+        say!(out, "Unknown location.");
+        return;
+    };
+    let witnesses = if target.in_public {
+        target.witnesses
+    } else {
+        0
+    };
+    let has_visual = evidence.iter().any(|event| {
+        event.location_id == location_id
+            && event.signature.signature.signature_type
+                == crate::rules::SignatureType::VisualAnomaly
+    });
+
+    match attempt_switch(stack, persona_id, turn, location, witnesses, has_visual) {
+        Ok(result) => {
+            say!(
+                out,
+                "Switch succeeded: {} -> {:?}",
+                result.new_persona_id, result.new_persona_type
+            );
+            if result.suspicion_applied {
+                say!(out, "Suspicion increased due to risky switch.");
+            }
+        }
+        Err(err) => {
+            print_switch_error(out, err);
+        }
+    }
+}
+
+fn print_switch_error(out: &mut Vec<String>, err: PersonaSwitchError) {
+    let reason = match err {
+        PersonaSwitchError::UnknownPersona => "Unknown persona",
+        PersonaSwitchError::AlreadyActive => "Already active",
+        PersonaSwitchError::SwitchBlockedByWitnesses => "Blocked by witnesses",
+        PersonaSwitchError::SwitchBlockedByLocation => "Blocked by location",
+        PersonaSwitchError::SwitchOnCooldown => "Switch on cooldown",
+    };
+    say!(out, "Switch failed: {}", reason);
+}
+
+fn print_known_locations(out: &mut Vec<String>, city: &CityState) {
+    say!(out, "Known locations:");
+    for location_id in city.discovered_locations() {
+        let location = &city.locations[&location_id];
+        let marker = if location_id == city.active_location { " (here)" } else { "" };
+        say!(
+            out,
+            "  {} {:?} heat={}{}",
+            location_id.0, location.tags, location.heat, marker
+        );
+    }
+    let hidden = city.locations.len() - city.discovered_locations().len();
+    if hidden > 0 {
+        say!(
+            out,
+            "  ...and {} place(s) you have yet to find (`travel explore`).",
+            hidden
+        );
+    }
+}
+
+fn travel_player(
+    out: &mut Vec<String>,
+    city: &mut CityState,
+    player_pos: &mut Position,
+    location_id: LocationId,
+) {
+    match city.travel_to(location_id) {
+        Ok(()) => {
+            if let Some(pos) = city.position_for_location(location_id) {
+                *player_pos = pos;
+            }
+            print_location(out, city);
+        }
+        Err(TravelError::UnknownLocation) | Err(TravelError::Undiscovered) => {
+            say!(out, "You don't know a location {}.", location_id.0);
+        }
+    }
+}
+
+/// Active cases in places the player has not found yet surface them through
+/// the case log.
+fn reveal_case_locations(out: &mut Vec<String>, city: &mut CityState, cases: &CaseRegistry) {
+    for case in &cases.cases {
+        if case.status == CaseStatus::Active && city.discover(case.location_id) {
+            say!(
+                out,
+                "Case #{} ({}) puts location {} on your map.",
+                case.case_id, case.faction_id, case.location_id.0
+            );
+        }
+    }
+}
+
+fn print_relocation_options(out: &mut Vec<String>, region: &RegionState, city: &CityState) {
+    say!(out, "City {} (region {})", city.city_id.0, city.region_id.0);
+    let targets: Vec<String> = region
+        .relocation_targets(city)
+        .iter()
+        .map(|id| id.0.to_string())
+        .collect();
+    if targets.is_empty() {
+        say!(out, "No other cities in this region.");
+        return;
+    }
+    say!(
+        out,
+        "Cities you could move to: {} (costs {} cr)",
+        targets.join(", "),
+        RELOCATION_COST_CR
+    );
+}
+
+fn print_location(out: &mut Vec<String>, city: &CityState) {
+    let Some(location) = city.locations.get(&city.active_location) else {
+        say!(out, "Location not found.");
+        return;
+    };
+    let known: Vec<String> = city
+        .discovered_locations()
+        .iter()
+        .map(|id| id.0.to_string())
+        .collect();
+    say!(out, "Known locations: {}", known.join(", "));
+    say!(
+        out,
+        "Location {} | heat={} crime_pressure={} economy={} witness_memory={} response={:?}",
+        location.id.0,
+        location.heat,
+        location.crime_pressure,
+        location.economy,
+        location.witness_memory,
+        location.response
+    );
+    say!(
+        out,
+        "Security: police_presence={} surveillance_level={} lockdown_level={}",
+        location.police_presence, location.surveillance_level, location.lockdown_level
+    );
+    say!(
+        out,
+        "Units: police_units={} investigators={} gang_units={}",
+        location.police_units, location.investigators, location.gang_units
+    );
+    if !location.faction_influence.is_empty() {
+        say!(out, "Influence:");
+        for (faction, influence) in &location.faction_influence {
+            say!(out, "  {} -> {}", faction, influence);
+        }
+    }
+}
+
+fn print_faction_events(out: &mut Vec<String>, events: &mut ResolvedFactionEventLog) {
+    if events.0.is_empty() {
+        say!(out, "Faction events: none");
+        return;
+    }
+    say!(out, "Faction events:");
+    for event in events.0.drain(..) {
+        say!(
+            out,
+            "  {} -> {} at loc {} (level {})",
+            event.faction_id, event.faction_type_id, event.location_id.0, event.level
+        );
+        for action in event.actions {
+            say!(out, "    action {}", action.kind);
+        }
+    }
+}
+
+/// Case progress, target and matched signatures stay fogged until intel
+/// reveals the case or the player's underground ties see everything.
+fn print_cases(out: &mut Vec<String>, cases: &CaseRegistry, city: &CityState, sees_details: bool) {
+    if cases.cases.is_empty() {
+        say!(out, "Cases: none");
+        return;
+    }
+    say!(out, "Cases:");
+    for case in &cases.cases {
+        let known = sees_details || case.revealed || case.status != CaseStatus::Active;
+        if !known {
+            say!(
+                out,
+                "  Case {} | faction={} loc={} progress={} status={:?} (`cases intel {}` to dig)",
+                case.case_id,
+                case.faction_id,
+                case.location_id.0,
+                case_progress_band(case.progress),
+                case.status,
+                case.case_id
+            );
+            if let Some(suspect) = &case.suspect_persona {
+                say!(out, "    chasing decoy: {}", suspect);
+            }
+            continue;
+        }
+        say!(
+            out,
+            "  Case {} | faction={} loc={} progress={} status={:?} target={:?}",
+            case.case_id,
+            case.faction_id,
+            case.location_id.0,
+            case.progress,
+            case.status,
+            case.target_type
+        );
+        if let Some(suspect) = &case.suspect_persona {
+            say!(out, "    chasing decoy: {}", suspect);
+        }
+        if !case.signature_pattern.is_empty() {
+            say!(out, "    signatures: {:?}", case.signature_pattern);
+        }
+        let heat = city
+            .locations
+            .get(&case.location_id)
+            .map(|location| location.heat)
+            .unwrap_or(0);
+        if let Some(hold) = case.resolution_hold(heat) {
+            say!(out, "    not resolving: {}", hold);
+        }
+    }
+}
+
+fn case_progress_band(progress: u32) -> &'static str {
+    match progress {
+        0..=29 => "cold",
+        30..=59 => "warm",
+        _ => "hot",
+    }
+}
+
+fn print_case_log(out: &mut Vec<String>, log: &mut CaseEventLog) {
+    if log.0.is_empty() {
+        return;
+    }
+    say!(out, "Case events:");
+    for entry in log.0.drain(..) {
+        say!(out, "  {}", entry);
+    }
+}
+
+fn update_context(target: &mut TargetContext, field: &str, value: &str) -> Result<(), ()> {
+    match field {
+        "dist" | "distance" => {
+            let dist = value.parse::<i64>().map_err(|_| ())?;
+            target.distance_m = Some(dist);
+            Ok(())
+        }
+        "los" => match value {
+            "on" | "true" => {
+                target.has_line_of_sight = true;
+                Ok(())
+            }
+            "off" | "false" => {
+                target.has_line_of_sight = false;
+                Ok(())
+            }
+            _ => Err(()),
+        },
+        "contact" => match value {
+            "on" | "true" => {
+                target.has_contact = true;
+                Ok(())
+            }
+            "off" | "false" => {
+                target.has_contact = false;
+                Ok(())
+            }
+            _ => Err(()),
+        },
+        "public" => match value {
+            "on" | "true" => {
+                target.in_public = true;
+                Ok(())
+            }
+            "off" | "false" => {
+                target.in_public = false;
+                Ok(())
+            }
+            _ => Err(()),
+        },
+        "witnesses" => {
+            let count = value.parse::<u32>().map_err(|_| ())?;
+            target.witnesses = count;
+            Ok(())
+        }
+        _ => Err(()),
+    }
+}
+
+fn print_cooldowns(out: &mut Vec<String>, actor: &ActorState) {
+    if actor.cooldowns.is_empty() {
+        say!(out, "Cooldowns: none");
+        return;
+    }
+    say!(out, "Cooldowns:");
+    for (expr_id, turns) in actor.cooldowns.iter() {
+        say!(out, "  {} -> {}", expr_id.0, turns);
+    }
+}
+
+fn print_scene(out: &mut Vec<String>, scene: &WorldEvidence) {
+    if scene.is_empty() {
+        say!(out, "Scene evidence: none");
+        return;
+    }
+    say!(out, "Scene evidence:");
+    for item in scene.iter() {
+        say!(
+            out,
+            "  loc={} {:?} strength={} remaining={}",
+            item.location_id.0,
+            item.signature.signature.signature_type,
+            item.signature.signature.strength,
+            item.signature.remaining_turns
+        );
+    }
+}
+
+fn print_civilian_status(out: &mut Vec<String>, state: &CivilianState, time: &GameTime) {
+    let pressure = state.pressure_targets();
+    let rewards = state.effective_rewards();
+    let (career_xp, career_needed) = state.career_progress();
+    say!(out, "Civilian status @ {}:", time.to_string());
+    say!(
+        out,
+        "  Job: {:?} ({:?} L{} {:?} shift | satisfaction={} stability={} | career_xp {}/{})",
+        state.job_status,
+        state.job.role,
+        state.job.level,
+        state.job.shift,
+        state.job.satisfaction,
+        state.job.stability,
+        career_xp,
+        career_needed
+    );
+    say!(
+        out,
+        "  Life: age={} stage={:?} mutant_gene={} mutation_ready={}",
+        state.life.age_years,
+        state.life.life_stage,
+        state.life.mutant_gene,
+        state.life.mutation_ready
+    );
+    if state.life.mutant_gene || state.mutant_profile.awakened {
+        let baseline_count = if state.mutant_profile.baseline_power_id.is_some() {
+            1
+        } else {
+            0
+        };
+        say!(
+            out,
+            "  Mutant: awakened={} tier={} parent_powers={} baseline={} expressions={} omni={}",
+            state.mutant_profile.awakened,
+            state.mutant_profile.tier_label(),
+            state.mutant_profile.parent_power_ids.len(),
+            baseline_count,
+            state.mutant_profile.expression_power_ids.len(),
+            state.mutant_profile.omni_power_ids.len()
+        );
+    }
+    say!(
+        out,
+        "  Mortality: risk={} legacy_count={}",
+        state.life.mortality_risk,
+        state.legacy.len()
+    );
+    say!(
+        out,
+        "  Risk: mistake={} tech_access={}",
+        state.mistake_risk,
+        state.tech_access_score()
+    );
+    say!(
+        out,
+        "  Education: level={:?} progress={} attendance={} dropout_risk={} credits={} enrolled={}",
+        state.education.level,
+        state.education.progress,
+        state.education.attendance,
+        state.education.dropout_risk,
+        state.education.credits,
+        state.education.is_enrolled
+    );
+    print_civilian_skills(out, state);
+    say!(
+        out,
+        "  Health: stress={} sleep_debt={} fitness={} injuries={}",
+        state.health.stress,
+        state.health.sleep_debt,
+        state.health.fitness,
+        state.health.injuries
+    );
+    say!(out, "  Routine: {}", state.routine_summary());
+    say!(
+        out,
+        "  Finances: cash={} debt={} rent={} rent_due_in={} wage={}",
+        state.finances.cash,
+        state.finances.debt,
+        state.finances.rent,
+        state.finances.rent_due_in,
+        state.finances.wage
+    );
+    say!(
+        out,
+        "  Housing: neighborhood={} rent={} stability={} safety={} privacy={} relocation_cd={}",
+        state.housing.neighborhood_id,
+        state.housing.rent,
+        state.housing.stability,
+        state.housing.safety,
+        state.housing.privacy,
+        state.housing.relocation_cooldown
+    );
+    say!(
+        out,
+        "  Wealth: tier={:?} profile={} net_worth={}CR investments={}CR liquidity={:.2} income={}CR upkeep={}CR",
+        state.wealth.tier,
+        state.wealth_profile.label(),
+        state.net_worth_cr(),
+        state.wealth.investments_cr,
+        state.wealth.liquidity,
+        state.wealth.income_per_tick,
+        state.wealth.upkeep_per_tick
+    );
+    say!(
+        out,
+        "  Social: support={} strain={} obligation={}",
+        state.social.support, state.social.strain, state.social.obligation
+    );
+    say!(
+        out,
+        "  Reputation: career={} community={} media={}",
+        state.reputation.career, state.reputation.community, state.reputation.media
+    );
+    say!(out, "  Civilian tier: {:?}", state.civilian_tier);
+    say!(
+        out,
+        "  Social web: professional={} community={} media={} underground={}",
+        state.social_web.professional,
+        state.social_web.community,
+        state.social_web.media,
+        state.social_web.underground
+    );
+    say!(
+        out,
+        "  Rewards: income_boost={} safehouse={} access={} intel={} favors={}",
+        rewards.income_boost, rewards.safehouse, rewards.access, rewards.intel, rewards.favors
+    );
+    if !state.network_rewards.is_empty() {
+        say!(
+            out,
+            "  Network bonus: income_boost={} safehouse={} access={} intel={} favors={}",
+            state.network_rewards.income_boost,
+            state.network_rewards.safehouse,
+            state.network_rewards.access,
+            state.network_rewards.intel,
+            state.network_rewards.favors
+        );
+    }
+    if state.contacts.is_empty() {
+        say!(out, "  Contacts: none");
+    } else {
+        say!(out, "  Contacts:");
+        for contact in &state.contacts {
+            say!(
+                out,
+                "    {} -> {:?} {:?} {:?} (bond={} influence={} last_day={} favor_debt={})",
+                contact.name,
+                contact.domain,
+                contact.relation_type,
+                contact.level,
+                contact.bond,
+                contact.influence,
+                contact.last_interaction_day,
+                contact.favor_debt
+            );
+        }
+    }
+    say!(
+        out,
+        "  Civilian pressure targets: temporal={:.1} resource={:.1} moral={:.1} identity={:.1}",
+        pressure.temporal, pressure.resource, pressure.moral, pressure.identity
+    );
+}
+
+fn print_civilian_skills(out: &mut Vec<String>, state: &CivilianState) {
+    let skills: Vec<String> = CivilianSkill::ALL
+        .iter()
+        .map(|skill| {
+            format!(
+                "{}={} (L{})",
+                skill.label(),
+                state.skill(*skill),
+                state.skill_level(*skill)
+            )
+        })
+        .collect();
+    say!(
+        out,
+        "  Skills: {} | hobby trains {}",
+        skills.join(" "),
+        state.hobby_skill.label()
+    );
+}
+
+fn print_civilian_preferences(out: &mut Vec<String>, state: &CivilianState) {
+    let settings = &state.event_settings;
+    let muted: Vec<&str> = settings
+        .muted_categories
+        .iter()
+        .map(|category| event_category_label(*category))
+        .collect();
+    let muted_label = if muted.is_empty() {
+        "none".to_string()
+    } else {
+        muted.join(", ")
+    };
+    let rent_auto = match state.auto_choices.pay_rent_if_cash_at_least {
+        Some(value) => format!("cash >= {}", value),
+        None => "off".to_string(),
+    };
+    let rest_auto = match state.auto_choices.rest_if_sleep_debt_at_least {
+        Some(value) => format!("sleep_debt >= {}", value),
+        None => "off".to_string(),
+    };
+    say!(out, "Civilian prefs:");
+    say!(
+        out,
+        "  Filters: repeat_suppress_days={} min_effect_magnitude={} muted=[{}]",
+        settings.suppress_repeat_days, settings.min_effect_magnitude, muted_label
+    );
+    say!(
+        out,
+        "  Auto: rent_pay={} school_attend={} rest_if_sleep_debt={}",
+        rent_auto,
+        if state.auto_choices.attend_school {
+            "on"
+        } else {
+            "off"
+        },
+        rest_auto
+    );
+}
+
+
+
+fn event_category_label(category: CivilianEventCategory) -> &'static str {
+    match category {
+        CivilianEventCategory::Work => "work",
+        CivilianEventCategory::School => "school",
+        CivilianEventCategory::Health => "health",
+        CivilianEventCategory::Housing => "housing",
+        CivilianEventCategory::Social => "social",
+        CivilianEventCategory::Opportunity => "opportunity",
+        CivilianEventCategory::Crime => "crime",
+        CivilianEventCategory::Routine => "routine",
+        CivilianEventCategory::Media => "media",
+        CivilianEventCategory::Other => "other",
+    }
+}
+
+fn parse_event_category(value: &str) -> Option<CivilianEventCategory> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "work" => Some(CivilianEventCategory::Work),
+        "school" => Some(CivilianEventCategory::School),
+        "health" => Some(CivilianEventCategory::Health),
+        "housing" => Some(CivilianEventCategory::Housing),
+        "social" => Some(CivilianEventCategory::Social),
+        "opportunity" | "opp" => Some(CivilianEventCategory::Opportunity),
+        "crime" => Some(CivilianEventCategory::Crime),
+        "routine" => Some(CivilianEventCategory::Routine),
+        "media" | "press" => Some(CivilianEventCategory::Media),
+        "other" => Some(CivilianEventCategory::Other),
+        _ => None,
+    }
+}
+
+fn event_category_for_id(event_id: &str) -> CivilianEventCategory {
+    match event_id {
+        "civilian_work_shift" => CivilianEventCategory::Work,
+        "civilian_school_day" => CivilianEventCategory::School,
+        "civilian_hobby_session" => CivilianEventCategory::Routine,
+        "civilian_health_checkin" => CivilianEventCategory::Health,
+        "civilian_rent_due" | "civilian_relocation_offer" => CivilianEventCategory::Housing,
+        "civilian_crime_quick_hit" => CivilianEventCategory::Crime,
+        "civilian_media_interview" | "civilian_media_expose" | "civilian_media_puff_piece" => {
+            CivilianEventCategory::Media
+        }
+        "civilian_job_offer"
+        | "civilian_deadline_crunch"
+        | "civilian_side_gig"
+        | "civilian_mentor_session"
+        | "civilian_colleague_project" => CivilianEventCategory::Opportunity,
+        "civilian_relationship_checkin"
+        | "civilian_contact_checkin"
+        | "civilian_contact_family"
+        | "civilian_contact_mentor"
+        | "civilian_contact_colleague"
+        | "civilian_contact_romance"
+        | "civilian_contact_rival"
+        | "civilian_family_obligation"
+        | "civilian_romance_date"
+        | "civilian_rival_runin"
+        | "civilian_social_favor"
+        | "civilian_trust_test"
+        | "civilian_community_meeting"
+        | "civilian_civic_protest"
+        | "civilian_safehouse_tip" => CivilianEventCategory::Social,
+        _ => CivilianEventCategory::Other,
+    }
+}
+
+fn is_crisis_event_id(event_id: &str) -> bool {
+    matches!(
+        event_id,
+        "civilian_rent_due"
+            | "civilian_relocation_offer"
+            | "civilian_health_checkin"
+            | "civilian_crime_quick_hit"
+    )
+}
+
+fn effect_magnitude(effects: &[String]) -> i32 {
+    let mut total = 0i32;
+    for effect in effects {
+        for part in effect.split(':') {
+            if let Ok(value) = part.trim().parse::<i32>() {
+                total = total.saturating_add(value.abs());
+            }
+        }
+    }
+    total
+}
+
+fn event_effect_magnitude(def: &CivilianStorylet) -> i32 {
+    let base = effect_magnitude(&def.effects);
+    let choice_max = def
+        .choices
+        .iter()
+        .map(|choice| effect_magnitude(&choice.effects))
+        .max()
+        .unwrap_or(0);
+    base.saturating_add(choice_max)
+}
+
+fn should_show_civilian_event(
+    state: &CivilianState,
+    def: &CivilianStorylet,
+    event: &CivilianEvent,
+    time: &GameTime,
+) -> bool {
+    if is_crisis_event_id(&event.storylet_id) {
+        return true;
+    }
+    let settings = &state.event_settings;
+    let category = event_category_for_id(&event.storylet_id);
+    if settings.is_muted(category) && default_choice_for_event(&event.storylet_id).is_some() {
+        return false;
+    }
+    if settings.min_effect_magnitude > 0
+        && event_effect_magnitude(def) < settings.min_effect_magnitude
+    {
+        return false;
+    }
+    if settings.suppress_repeat_days > 0 {
+        if let Some(last_day) = state.last_event_seen_day(&def.id) {
+            let since = time.day.saturating_sub(last_day);
+            if since < settings.suppress_repeat_days {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+
+
+fn print_civilian_event_details(
+    out: &mut Vec<String>,
+    state: &CivilianState,
+    event: &CivilianEvent,
+    def: &CivilianStorylet,
+) {
+    if def.details.is_empty() {
+        return;
+    }
+    say!(out, "    details:");
+    for detail in &def.details {
+        let line = render_civilian_text(detail, state, event);
+        say!(out, "      - {}", line);
+    }
+}
+
+fn print_civilian_event_entry(
+    out: &mut Vec<String>,
+    state: &CivilianState,
+    event: &CivilianEvent,
+    def: &CivilianStorylet,
+    time: &GameTime,
+    show_details: bool,
+) {
+    let age = time.tick.saturating_sub(event.created_tick);
+    let title = render_civilian_text(&def.title, state, event);
+    let summary = render_civilian_text(&def.text_stub, state, event);
+    say!(out, "  {} | {} | queued {} ticks ago", def.id, title, age);
+    say!(out, "    {}", summary);
+    if show_details {
+        print_civilian_event_details(out, state, event, def);
+    }
+    for choice in &def.choices {
+        let text = render_civilian_text(&choice.text, state, event);
+        say!(out, "    [{}] {}", choice.id, text);
+    }
+    if !show_details && !def.details.is_empty() {
+        say!(out, "    (see more: civilian detail {})", def.id);
+    }
+}
+
+fn print_pending_civilian_events(
+    out: &mut Vec<String>,
+    state: &mut CivilianState,
+    library: &[CivilianStorylet],
+    time: &GameTime,
+    show_details: bool,
+) {
+    if state.pending_events.is_empty() {
+        say!(out, "Civilian events: none");
+        return;
+    }
+    say!(out, "Civilian events:");
+    let pending = state.pending_events.clone();
+    let mut suppressed = 0usize;
+    for event in &pending {
+        match find_civilian_event(library, &event.storylet_id) {
+            Some(def) => {
+                if !should_show_civilian_event(state, def, event, time) {
+                    suppressed += 1;
+                    continue;
+                }
+                print_civilian_event_entry(out, state, event, def, time, show_details);
+                state.mark_event_seen(&def.id, time.day);
+            }
+            None => {
+                let age = time.tick.saturating_sub(event.created_tick);
+                say!(
+                    out,
+                    "  {} | (missing definition) | queued {} ticks ago",
+                    event.storylet_id, age
+                );
+            }
+        }
+    }
+    if suppressed > 0 {
+        say!(out, "  (suppressed {} events by filters)", suppressed);
+    }
+}
+
+fn print_civilian_event_detail(
+    out: &mut Vec<String>,
+    state: &mut CivilianState,
+    library: &[CivilianStorylet],
+    time: &GameTime,
+    event_id: &str,
+) {
+    let Some(event) = state
+        .pending_events
+        .iter()
+        .find(|event| event.storylet_id == event_id)
+    else {
+        say!(out, "Civilian event not pending: {}", event_id);
+        return;
+    };
+    let Some(def) = find_civilian_event(library, event_id) else {
+        say!(out, "Civilian event missing definition: {}", event_id);
+        return;
+    };
+    say!(out, "Civilian event detail:");
+    print_civilian_event_entry(out, state, event, def, time, true);
+    state.mark_event_seen(&def.id, time.day);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AutoResolveMode {
+    Normal,
+    SkipRent,
+    SkipCrises,
+}
+
+fn auto_resolve_allowed(event_id: &str, mode: AutoResolveMode) -> bool {
+    match mode {
+        AutoResolveMode::Normal => true,
+        AutoResolveMode::SkipRent => event_id != "civilian_rent_due",
+        AutoResolveMode::SkipCrises => !is_crisis_event_id(event_id),
+    }
+}
+
+fn default_choice_for_event(event_id: &str) -> Option<&'static str> {
+    match event_id {
+        "civilian_work_shift" => Some("work"),
+        "civilian_school_day" => Some("attend"),
+        _ => None,
+    }
+}
+
+fn select_auto_choice(
+    state: &CivilianState,
+    event_id: &str,
+) -> Option<&'static str> {
+    let category = event_category_for_id(event_id);
+    if state.event_settings.is_muted(category) {
+        if let Some(choice) = default_choice_for_event(event_id) {
+            return Some(choice);
+        }
+    }
+    match event_id {
+        "civilian_rent_due" => {
+            let threshold = state.auto_choices.pay_rent_if_cash_at_least?;
+            if state.finances.cash >= threshold && state.finances.cash >= state.finances.rent {
+                Some("pay")
+            } else {
+                None
+            }
+        }
+        "civilian_school_day" => {
+            if state.auto_choices.attend_school {
+                Some("attend")
+            } else {
+                None
+            }
+        }
+        "civilian_health_checkin" => {
+            let threshold = state.auto_choices.rest_if_sleep_debt_at_least?;
+            if state.health.sleep_debt >= threshold {
+                Some("rest")
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+fn auto_resolve_civilian_events(
+    out: &mut Vec<String>,
+    state: &mut CivilianState,
+    context: CivilianChoiceContext,
+    origin_quest: &mut OriginQuestState,
+    origin_paths: &OriginPathCatalog,
+    pressure: &mut PressureState,
+    mode: AutoResolveMode,
+) -> usize {
+    if state.pending_events.is_empty() {
+        return 0;
+    }
+    let mut resolved = 0usize;
+    let mut index = 0usize;
+    while index < state.pending_events.len() {
+        let event_id = state.pending_events[index].storylet_id.clone();
+        if !auto_resolve_allowed(&event_id, mode) {
+            index += 1;
+            continue;
+        }
+        let Some(choice_id) = select_auto_choice(state, &event_id) else {
+            index += 1;
+            continue;
+        };
+        let Some(def) = find_civilian_event(context.library, &event_id) else {
+            index += 1;
+            continue;
+        };
+        if !def.choices.iter().any(|choice| choice.id == choice_id) {
+            index += 1;
+            continue;
+        }
+        resolve_civilian_event(
+            out,
+            state,
+            context,
+            origin_quest,
+            origin_paths,
+            pressure,
+            &event_id,
+            choice_id,
+            false,
+        );
+        resolved += 1;
+    }
+    resolved
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TickMode {
+    Count(u32),
+    NextEvent,
+    NextRent,
+    CrisisOnly,
+    SkipDays(u32),
+}
+
+#[derive(Debug, Default)]
+struct TickReport {
+    death_pending: bool,
+    economy: Vec<(u32, EconomyTickResult)>,
+    restocks: Vec<(u32, Vec<String>)>,
+    storylet_handoffs: Vec<StoryletHandoff>,
+    punctuation_storylet: Option<String>,
+}
+
+fn has_visible_pending_civilian_events(
+    state: &CivilianState,
+    library: &[CivilianStorylet],
+    time: &GameTime,
+) -> bool {
+    for event in &state.pending_events {
+        match find_civilian_event(library, &event.storylet_id) {
+            Some(def) => {
+                if should_show_civilian_event(state, def, event, time) {
+                    return true;
+                }
+            }
+            None => return true,
+        }
+    }
+    false
+}
+
+fn has_crisis_pending(state: &CivilianState) -> bool {
+    state
+        .pending_events
+        .iter()
+        .any(|event| is_crisis_event_id(&event.storylet_id))
+}
+
+fn has_rent_due_pending(state: &CivilianState) -> bool {
+    state
+        .pending_events
+        .iter()
+        .any(|event| event.storylet_id == "civilian_rent_due")
+}
+
+fn resolve_civilian_event(
+    out: &mut Vec<String>,
+    state: &mut CivilianState,
+    context: CivilianChoiceContext,
+    origin_quest: &mut OriginQuestState,
+    origin_paths: &OriginPathCatalog,
+    pressure: &mut PressureState,
+    event_id: &str,
+    choice_id: &str,
+    announce: bool,
+) -> Option<CivilianResolution> {
+    let resolution = match resolve_civilian_choice(state, context, event_id, choice_id) {
+        Ok(resolution) => resolution,
+        Err(err) => {
+            if announce {
+                match err {
+                    CivilianEventError::UnknownChoice {
+                        event_id,
+                        choice_id,
+                    } => say!(out, "Unknown choice {} for event {}", choice_id, event_id),
+                    _ => say!(out, "Unknown civilian event: {}", event_id),
+                }
+            }
+            return None;
+        }
+    };
+    let origin_effects = apply_origin_effects(
+        parse_origin_effects(&resolution.effects),
+        origin_quest,
+        origin_paths,
+    );
+    if announce {
+        say!(
+            out,
+            "Resolved {} -> {}",
+            resolution.title,
+            resolution.choice_text
+        );
+        if resolution.applied.is_empty() {
+            say!(out, "No civilian effects applied.");
+        } else {
+            say!(out, "Applied effects:");
+            for entry in &resolution.applied {
+                say!(out, "  {}", entry);
+            }
+        }
+        if !origin_effects.messages.is_empty() {
+            say!(out, "Origin updates:");
+            for entry in origin_effects.messages {
+                say!(out, "  {}", entry);
+            }
+        }
+    }
+    apply_origin_rewards(out, origin_effects.rewards.as_slice(), pressure);
+    Some(resolution)
+}
+
+pub fn print_save_diagnostics(out: &mut Vec<String>, report: &SaveDiagnostics) {
+    let version = |value: Option<i64>| {
+        value
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| "missing".to_string())
+    };
+    say!(
+        out,
+        "Save diagnostics: schema_version={} save_version={} healthy={}",
+        version(report.schema_version),
+        version(report.save_version),
+        report.is_healthy()
+    );
+    say!(out, "Tables:");
+    for (table, count) in &report.table_counts {
+        say!(out, "  {} rows={}", table, count);
+    }
+    if report.orphaned_rows.is_empty() {
+        say!(out, "Orphaned rows: none");
+    } else {
+        say!(out, "Orphaned rows:");
+        for entry in &report.orphaned_rows {
+            say!(out, "  {}", entry);
+        }
+    }
+    if report.invalid_values.is_empty() {
+        say!(out, "Invalid values: none");
+    } else {
+        say!(out, "Invalid values:");
+        for entry in &report.invalid_values {
+            say!(out, "  {}", entry);
+        }
+    }
+    if !report.preserved_values.is_empty() {
+        say!(out, "Unknown values kept on load:");
+        for entry in &report.preserved_values {
+            say!(out, "  {}", entry);
+        }
+    }
+}
+
+fn print_crime_attempt(out: &mut Vec<String>, attempt: &CrimeAttempt) {
+    match attempt.outcome {
+        CrimeOutcome::Clean => say!(
+            out,
+            "The job went clean ({}% odds): +{} cr.",
+            attempt.success_chance, attempt.cash_gained
+        ),
+        CrimeOutcome::Injured => say!(
+            out,
+            "The job went sideways ({}% odds): hurt getting out, +{} cr.",
+            attempt.success_chance, attempt.cash_gained
+        ),
+        CrimeOutcome::Arrested => say!(
+            out,
+            "The job was botched ({}% odds): arrested, fined {} cr.",
+            attempt.success_chance, attempt.fine_paid
+        ),
+    }
+}
+
+fn resolve_global_event(
+    out: &mut Vec<String>,
+    state: &mut GlobalEventState,
+    event_def: &GlobalEventDefinition,
+    pressure: &mut PressureState,
+    nemesis: &mut NemesisState,
+    storylet_state: &mut StoryletState,
+    choice_id: &str,
+) {
+    let event_id = event_def.id.as_str();
+    let Some(choice) = event_def
+        .choices
+        .iter()
+        .find(|choice| choice.id == choice_id)
+    else {
+        say!(out, "Unknown choice {} for event {}", choice_id, event_id);
+        return;
+    };
+
+    let mut applied = Vec::new();
+    let mut effects = Vec::new();
+    effects.extend(event_def.effects.iter().cloned());
+    effects.extend(choice.effects.iter().cloned());
+    applied.extend(apply_global_event_effects(
+        &effects,
+        pressure,
+        nemesis,
+        storylet_state,
+    ));
+
+    if let Some(index) = state
+        .pending
+        .iter()
+        .position(|event| event.event_id == event_id)
+    {
+        state.pending.remove(index);
+    }
+    state.fired.insert(event_def.id.clone());
+    storylet_state
+        .flags
+        .insert(format!("global_event_{}", event_def.id), true);
+    storylet_state
+        .flags
+        .insert("global_event".to_string(), true);
+
+    say!(out, "Resolved {} -> {}", event_def.title, choice.text);
+    if applied.is_empty() {
+        say!(out, "No global effects applied.");
+    } else {
+        say!(out, "Applied global effects:");
+        for entry in applied {
+            say!(out, "  {}", entry);
+        }
+    }
+}
+
+fn find_global_event<'a>(
+    catalog: &'a [GlobalEventDefinition],
+    event_id: &str,
+) -> Option<&'a GlobalEventDefinition> {
+    catalog.iter().find(|event| event.id == event_id)
+}
+
+fn apply_global_event_effects(
+    effects: &[String],
+    pressure: &mut PressureState,
+    nemesis: &mut NemesisState,
+    storylet_state: &mut StoryletState,
+) -> Vec<String> {
+    let mut applied = Vec::new();
+    for effect in effects {
+        let mut parts = effect.split(':');
+        let key = parts.next().unwrap_or("").trim();
+        match key {
+            "pressure.temporal" => {
+                apply_pressure_delta(&mut pressure.temporal, parts.next(), "pressure.temporal", &mut applied);
+            }
+            "pressure.identity" => {
+                apply_pressure_delta(&mut pressure.identity, parts.next(), "pressure.identity", &mut applied);
+            }
+            "pressure.institutional" => {
+                apply_pressure_delta(
+                    &mut pressure.institutional,
+                    parts.next(),
+                    "pressure.institutional",
+                    &mut applied,
+                );
+            }
+            "pressure.moral" => {
+                apply_pressure_delta(&mut pressure.moral, parts.next(), "pressure.moral", &mut applied);
+            }
+            "pressure.resource" => {
+                apply_pressure_delta(
+                    &mut pressure.resource,
+                    parts.next(),
+                    "pressure.resource",
+                    &mut applied,
+                );
+            }
+            "pressure.psychological" => {
+                apply_pressure_delta(
+                    &mut pressure.psychological,
+                    parts.next(),
+                    "pressure.psychological",
+                    &mut applied,
+                );
+            }
+            "nemesis.threat" => {
+                if let Some(delta_raw) = parts.next() {
+                    if let Ok(delta) = delta_raw.trim().parse::<i32>() {
+                        nemesis.apply_global_threat(delta);
+                        applied.push(format!("nemesis threat {:+}", delta));
+                    }
+                }
+            }
+            "storylet.flag" => {
+                if let Some(flag) = parts.next().map(str::trim).filter(|flag| !flag.is_empty()) {
+                    match parts.next().and_then(|raw| raw.trim().parse::<i32>().ok()) {
+                        Some(turns) => {
+                            storylet_state.set_timed_flag(flag, turns);
+                            applied.push(format!("storylet flag {} ({} turns)", flag, turns));
+                        }
+                        None => {
+                            storylet_state.set_flag(flag);
+                            applied.push(format!("storylet flag {}", flag));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    applied
+}
+
+fn apply_pressure_delta(
+    target: &mut f32,
+    value: Option<&str>,
+    label: &str,
+    applied: &mut Vec<String>,
+) {
+    let Some(value) = value else {
+        return;
+    };
+    if let Ok(delta) = value.trim().parse::<f32>() {
+        *target = (*target + delta).clamp(0.0, 100.0);
+        applied.push(format!("{} {:+.1}", label, delta));
+    }
+}
+
+fn set_storylet_flag(state: &mut StoryletState, flag: &str, enabled: bool) {
+    if enabled {
+        state.set_flag(flag);
+    } else {
+        state.clear_flag(flag);
+    }
+}
+
+fn update_social_storylet_flags(civilian: &CivilianState, storylet_state: &mut StoryletState) {
+    let leverage = civilian.social_leverage_score();
+    let protection = civilian.social_protection_score();
+    let vulnerability = civilian.social_vulnerability_score();
+    set_storylet_flag(storylet_state, "social.leverage", leverage >= 60);
+    set_storylet_flag(storylet_state, "social.protected", protection >= 60);
+    set_storylet_flag(storylet_state, "social.vulnerable", vulnerability >= 60);
+}
+
+fn update_reputation_storylet_flags(civilian: &CivilianState, storylet_state: &mut StoryletState) {
+    let public_rep = civilian.public_reputation_score();
+    set_storylet_flag(storylet_state, "reputation.public_high", public_rep >= 70);
+    set_storylet_flag(storylet_state, "reputation.public_low", public_rep <= 30);
+    set_storylet_flag(
+        storylet_state,
+        "reputation.community_high",
+        civilian.reputation.community >= 70,
+    );
+    set_storylet_flag(
+        storylet_state,
+        "reputation.career_high",
+        civilian.reputation.career >= 70,
+    );
+}
+
+fn update_tech_storylet_flags(civilian: &CivilianState, storylet_state: &mut StoryletState) {
+    let tier_rank = civilian.wealth.tier.rank();
+    set_storylet_flag(
+        storylet_state,
+        "tech.access_basic",
+        tier_rank >= WealthTier::Working.rank(),
+    );
+    set_storylet_flag(
+        storylet_state,
+        "tech.access_advanced",
+        tier_rank >= WealthTier::Affluent.rank(),
+    );
+    set_storylet_flag(
+        storylet_state,
+        "tech.access_prototype",
+        tier_rank >= WealthTier::Wealthy.rank(),
+    );
+    set_storylet_flag(
+        storylet_state,
+        "tech.access_arsenal",
+        tier_rank >= WealthTier::UltraWealthy.rank(),
+    );
+}
+
+fn apply_public_reputation_attention(
+    civilian: &mut CivilianState,
+    city: &mut CityState,
+    time: &GameTime,
+) {
+    if civilian.last_reputation_heat_day == time.day {
+        return;
+    }
+    civilian.last_reputation_heat_day = time.day;
+    let public_rep = civilian.public_reputation_score();
+    if public_rep < 70 || time.day % 7 != 0 {
+        return;
+    }
+    let bonus = 1 + ((public_rep - 70) / 20).clamp(0, 2);
+    if let Some(location) = city.locations.get_mut(&city.active_location) {
+        location.heat = (location.heat + bonus).clamp(0, 100);
+    }
+}
+
+fn apply_media_fallout(
+    out: &mut Vec<String>,
+    civilian: &mut CivilianState,
+    persona_stack: &mut PersonaStack,
+    alignment: Alignment,
+    identity_evidence: &mut IdentityEvidenceStore,
+    city: &CityState,
+    turn: u64,
+) {
+    let fallout = take_media_fallout(civilian);
+    if fallout.is_empty() {
+        return;
+    }
+    if fallout.public_suspicion != 0 || fallout.wanted_level != 0 {
+        apply_media_suspicion(
+            persona_stack,
+            alignment,
+            fallout.public_suspicion,
+            fallout.wanted_level,
+        );
+        say!(
+            out,
+            "Press coverage: public suspicion {:+}, wanted {:+}.",
+            fallout.public_suspicion, fallout.wanted_level
+        );
+    }
+    for _ in 0..fallout.persona_hints {
+        identity_evidence.record(
+            city.active_location,
+            turn,
+            Vec::new(),
+            1,
+            MEDIA_HINT_VISUAL_QUALITY,
+            PersonaHint::Civilian,
+            vec!["on-record slip in the press".to_string()],
+        );
+        say!(
+            out,
+            "The press caught a detail that ties your civilian life to the mask."
+        );
+    }
+}
+
+fn apply_civilian_mistake_consequences(
+    civilian: &mut CivilianState,
+    city: &mut CityState,
+    pressure: &mut PressureState,
+    time: &GameTime,
+) {
+    if civilian.last_mistake_day == time.day {
+        return;
+    }
+    if civilian.mistake_risk < 65 {
+        return;
+    }
+    civilian.last_mistake_day = time.day;
+    let public_rep = civilian.public_reputation_score();
+    let visibility_bonus = if public_rep >= 85 {
+        2
+    } else if public_rep >= 70 {
+        1
+    } else {
+        0
+    };
+    let heat_delta = ((civilian.mistake_risk - 55) / 15).clamp(1, 6) + visibility_bonus;
+    if let Some(location) = city.locations.get_mut(&city.active_location) {
+        location.heat = (location.heat + heat_delta).clamp(0, 100);
+    }
+    let identity_delta = (civilian.mistake_risk as f32 * 0.05).clamp(1.0, 8.0);
+    pressure.identity = (pressure.identity + identity_delta).clamp(0.0, 100.0);
+}
+
+/// Updates the nemesis' feelings after a fight and describes where they now stand.
+/// Returns the lines to report: a failed save, if any, then the nemesis's new
+/// standing toward the player.
+fn record_nemesis_encounter(
+    combat: &CombatState,
+    end: CombatEnd,
+    consequence: CombatConsequence,
+    characters: &mut [PersistentCharacter],
+    world_repo: &mut dyn WorldRepository,
+    nemesis_state: &mut NemesisState,
+    current_tick: u64,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    let Some(character) = combat.nemesis_id.as_deref().and_then(|nemesis_id| {
+        characters
+            .iter_mut()
+            .find(|character| character.character_id == nemesis_id)
+    }) else {
+        return lines;
+    };
+    let relation = update_nemesis_relationship(character, end, consequence, current_tick);
+    let (fear, resentment) = (relation.fear, relation.resentment);
+    if let Some(faction_id) = nemesis_faction(character) {
+        nemesis_state.record_resentment(faction_id, resentment);
+    }
+    if let Err(err) = world_repo.upsert_character(character) {
+        say!(
+            &mut lines,
+            "Failed to persist character {}: {}",
+            character.character_id, err
+        );
+    }
+    say!(
+        &mut lines,
+        "{} {} now holds fear={} resentment={} toward you.",
+        character.first_name, character.last_name, fear, resentment
+    );
+    lines
+}
+
+fn record_combat_captures(
+    out: &mut Vec<String>,
+    combat: &CombatState,
+    captured: &[CapturedOpponent],
+    characters: &mut Vec<PersistentCharacter>,
+    world_repo: &mut dyn WorldRepository,
+    city: &CityState,
+    current_tick: u64,
+) {
+    let scope_id = format!("city:{}", city.city_id.0);
+    let faction_id = strongest_faction_at(city, combat.location_id);
+    for combatant in captured {
+        let nemesis = combat.nemesis_id.as_deref().and_then(|nemesis_id| {
+            characters.iter_mut().find(|character| {
+                character.character_id == nemesis_id
+                    && format!("{} {}", character.first_name, character.last_name)
+                        == combatant.name
+            })
+        });
+        if let Some(character) = nemesis {
+            mark_incarcerated(character, current_tick);
+            say!(
+                out,
+                "{} {} is taken into custody.",
+                character.first_name, character.last_name
+            );
+            if let Err(err) = world_repo.upsert_character(character) {
+                say!(
+                    out,
+                    "Failed to persist character {}: {}",
+                    character.character_id, err
+                );
+            }
+            continue;
+        }
+        let known = characters.iter_mut().find(|character| {
+            character
+                .personas
+                .iter()
+                .any(|persona| persona.label == combatant.name)
+        });
+        if let Some(character) = known {
+            mark_incarcerated(character, current_tick);
+            say!(
+                out,
+                "{} is already known as {} {} ({}) and goes back into custody.",
+                combatant.name, character.first_name, character.last_name, character.character_id
+            );
+            if let Err(err) = world_repo.upsert_character(character) {
+                say!(
+                    out,
+                    "Failed to persist character {}: {}",
+                    character.character_id, err
+                );
+            }
+            continue;
+        }
+        let seed = combat.rng_state ^ (combatant.combatant_id as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        let candidate = PromotionCandidate {
+            faction_id: faction_id.clone(),
+            persona_kind: Some("MASKED".to_string()),
+            persona_label: Some(combatant.name.clone()),
+            ..PromotionCandidate::captured(scope_id.clone(), seed >> 16)
+        };
+        match world_repo.record_capture(&candidate, current_tick) {
+            Ok(character) => {
+                say!(
+                    out,
+                    "{} is taken into custody as {} {} ({}).",
+                    combatant.name,
+                    character.first_name,
+                    character.last_name,
+                    character.character_id
+                );
+                characters.push(character);
+            }
+            Err(err) => say!(out, "Failed to record capture of {}: {}", combatant.name, err),
+        }
+    }
+}
+
+/// The faction with the most sway over a location, which a captured opponent
+/// is assumed to have been working for.
+fn strongest_faction_at(city: &CityState, location_id: LocationId) -> Option<String> {
+    city.locations
+        .get(&location_id)?
+        .faction_influence
+        .iter()
+        .filter(|(_, influence)| **influence > 0)
+        .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+        .map(|(id, _)| id.clone())
+}
+
+fn apply_cast_aging(
+    out: &mut Vec<String>,
+    characters: &mut Vec<PersistentCharacter>,
+    world_repo: &mut dyn WorldRepository,
+    current_day: u32,
+    current_tick: u64,
+    last_cast_aging_year: &mut i32,
+) {
+    let current_year = current_year_from_day(current_day);
+    if current_year == *last_cast_aging_year {
+        return;
+    }
+    *last_cast_aging_year = current_year;
+    let report = tick_cast_aging(characters, current_year, current_tick);
+    if report.changed_ids.is_empty() {
+        return;
+    }
+    for character in characters.iter() {
+        if report.changed_ids.contains(&character.character_id) {
+            if let Err(err) = world_repo.upsert_character(character) {
+                say!(
+                    out,
+                    "Failed to persist character {}: {}",
+                    character.character_id, err
+                );
+            }
+        }
+    }
+    if !report.retired.is_empty() || !report.deceased.is_empty() {
+        say!(
+            out,
+            "Cast aging: retired={} deceased={}",
+            report.retired.len(),
+            report.deceased.len()
+        );
+    }
+}
+
+
+fn record_identity_evidence(
+    identity: &mut IdentityEvidenceStore,
+    city: &mut CityState,
+    location_id: crate::simulation::city::LocationId,
+    turn: u64,
+    signatures: &[crate::rules::SignatureInstance],
+    witnesses: u32,
+    persona_hint: PersonaHint,
+    attributed_persona: Option<&str>,
+    modifiers: Option<IdentityEvidenceModifiers>,
+) {
+    let (surveillance, in_public, familiarity) = city
+        .locations
+        .get(&location_id)
+        .map(|loc| {
+            (
+                loc.surveillance_level,
+                loc.tags
+                    .contains(&crate::simulation::city::LocationTag::Public),
+                loc.witness_familiarity(),
+            )
+        })
+        .unwrap_or((0, true, 0));
+    let modifiers = modifiers.unwrap_or_default();
+    let witness_count = if in_public {
+        witnesses
+            .max(1)
+            .saturating_add(modifiers.witness_bonus)
+    } else {
+        0
+    };
+    let visual_quality = (surveillance
+        + (witness_count as i32 * 10)
+        + modifiers.visual_bonus
+        + familiarity)
+        .clamp(0, 100) as u8;
+    if witness_count > 0 && !signatures.is_empty() {
+        if let Some(location) = city.locations.get_mut(&location_id) {
+            location.remember_sighting();
+        }
+    }
+    for sig in signatures {
+        let evidence_id = identity.record(
+            location_id,
+            turn,
+            vec![sig.signature.signature_type],
+            witness_count,
+            visual_quality,
+            persona_hint,
+            Vec::new(),
+        );
+        if let Some(persona_id) = attributed_persona {
+            identity.attribute(evidence_id, persona_id);
+        }
+    }
+}
+
+fn apply_agent_events(
+    agent_events: &AgentEventLog,
+    turn: u64,
+    city: &mut CityState,
+    city_events: &mut CityEventLog,
+    evidence: &mut WorldEvidence,
+    identity_evidence: &mut IdentityEvidenceStore,
+    event_log: &mut WorldEventLog,
+) {
+    for event in &agent_events.0 {
+        if let AgentEvent::Incident {
+            location_id,
+            signatures,
+            ..
+        } = event
+        {
+            apply_agent_incident(
+                signatures,
+                *location_id,
+                turn,
+                city,
+                city_events,
+                evidence,
+                identity_evidence,
+                event_log,
+            );
+        }
+    }
+}
+
+fn apply_agent_incident(
+    signatures: &[crate::rules::SignatureInstance],
+    location_id: crate::simulation::city::LocationId,
+    turn: u64,
+    city: &mut CityState,
+    city_events: &mut CityEventLog,
+    evidence: &mut WorldEvidence,
+    identity_evidence: &mut IdentityEvidenceStore,
+    event_log: &mut WorldEventLog,
+) {
+    if signatures.is_empty() {
+        return;
+    }
+
+    let (in_public, witnesses) = city
+        .locations
+        .get(&location_id)
+        .map(|location| {
+            let in_public = location.tags.contains(&LocationTag::Public);
+            let witnesses = if in_public {
+                2 + (location.surveillance_level / 20).max(0) as u32
+            } else {
+                0
+            };
+            (in_public, witnesses)
+        })
+        .unwrap_or((true, 0));
+
+    evidence.emit(location_id, signatures);
+    apply_signatures(
+        city,
+        location_id,
+        signatures,
+        witnesses,
+        in_public,
+        event_log,
+        city_events,
+    );
+    record_identity_evidence(
+        identity_evidence,
+        city,
+        location_id,
+        turn,
+        signatures,
+        witnesses,
+        PersonaHint::Unknown,
+        None,
+        None,
+    );
+}
+
+/// The investigation state an action's emitted signatures ripple through.
+struct SignatureFallout<'a> {
+    city: &'a mut CityState,
+    city_events: &'a mut CityEventLog,
+    evidence: &'a mut WorldEvidence,
+    identity_evidence: &'a mut IdentityEvidenceStore,
+    faction_director: &'a mut FactionDirector,
+    faction_events: &'a mut FactionEventLog,
+    resolved_faction_events: &'a mut ResolvedFactionEventLog,
+    cases: &'a mut CaseRegistry,
+    case_log: &'a mut CaseEventLog,
+    persona_stack: &'a mut PersonaStack,
+    alignment: Alignment,
+    player_pos: &'a Position,
+    event_log: &'a mut WorldEventLog,
+}
+
+/// What one action emitted, where, and who saw it.
+struct SignatureAction<'a> {
+    signatures: &'a [crate::rules::SignatureInstance],
+    environment_effects: &'a [EnvironmentEffect],
+    location_id: LocationId,
+    turn: u64,
+    witnesses: u32,
+    in_public: bool,
+    persona_hint: PersonaHint,
+    attributed_persona: Option<&'a str>,
+    identity_modifiers: Option<IdentityEvidenceModifiers>,
+}
+
+impl SignatureFallout<'_> {
+    fn apply(self, out: &mut Vec<String>, action: SignatureAction<'_>) {
+        let SignatureFallout {
+            city,
+            city_events,
+            evidence,
+            identity_evidence,
+            faction_director,
+            faction_events,
+            resolved_faction_events,
+            cases,
+            case_log,
+            persona_stack,
+            alignment,
+            player_pos,
+            event_log,
+        } = self;
+        let SignatureAction {
+            signatures,
+            environment_effects,
+            location_id,
+            turn,
+            witnesses,
+            in_public,
+            persona_hint,
+            attributed_persona,
+            identity_modifiers,
+        } = action;
+        evidence.emit(location_id, signatures);
+        apply_signatures(
+            city,
+            location_id,
+            signatures,
+            witnesses,
+            in_public,
+            event_log,
+            city_events,
+        );
+        apply_environment_effects(city, location_id, environment_effects, event_log, city_events);
+        record_identity_evidence(
+            identity_evidence,
+            city,
+            location_id,
+            turn,
+            signatures,
+            witnesses,
+            persona_hint,
+            attributed_persona,
+            identity_modifiers,
+        );
+        if let Some(decoy) = attributed_persona {
+            if apply_decoy_evidence(cases, identity_evidence, location_id, decoy, turn, case_log) {
+                say!(
+                    out,
+                    "Investigators saw through the evidence pointing at {}.",
+                    decoy
+                );
+            }
+        }
+        run_faction_director(faction_director, city, evidence, faction_events);
+        resolve_faction_events(
+            faction_events,
+            resolved_faction_events,
+            city,
+            evidence,
+            cases,
+            case_log,
+            None,
+        );
+        update_cases(
+            cases,
+            city,
+            evidence,
+            identity_evidence,
+            &faction_director.faction_perception(),
+            case_log,
+        );
+        apply_suspicion_for_intents(
+            persona_stack,
+            alignment,
+            player_pos,
+            city,
+            cases,
+            identity_evidence,
+            &[],
+            1,
+        );
+    }
+}
+
+fn apply_combat_pressure_delta(pressure: &mut PressureState, delta: CombatPressureDelta) {
+    pressure.temporal = (pressure.temporal + delta.temporal).clamp(0.0, 100.0);
+    pressure.identity = (pressure.identity + delta.identity).clamp(0.0, 100.0);
+    pressure.institutional = (pressure.institutional + delta.institutional).clamp(0.0, 100.0);
+    pressure.moral = (pressure.moral + delta.moral).clamp(0.0, 100.0);
+    pressure.resource = (pressure.resource + delta.resource).clamp(0.0, 100.0);
+    pressure.psychological = (pressure.psychological + delta.psychological).clamp(0.0, 100.0);
+}
+
+fn combat_case_progress_summary(
+    cases: &CaseRegistry,
+    location_id: crate::simulation::city::LocationId,
+) -> Vec<String> {
+    cases
+        .cases
+        .iter()
+        .filter(|case| case.location_id == location_id)
+        .map(|case| format!("case#{}:{}", case.case_id, case.progress))
+        .collect()
+}
+
+fn refresh_combat_bystanders(
+    combat: &mut CombatState,
+    city: &CityState,
+    agents: &AgentRegistry,
+    game_time: &GameTime,
+) {
+    combat.bystanders = CombatBystanders {
+        population_density: city
+            .locations
+            .get(&combat.location_id)
+            .map(|location| location.population_density(game_time.is_day, game_time.weather()))
+            .unwrap_or(0),
+        agents_present: agents.agents_at(combat.location_id),
+    };
+}
+
+/// A finished fight's signatures plus the pressure, growth and world turn its
+/// outcome shifts.
+struct CombatFallout<'a> {
+    signatures: SignatureFallout<'a>,
+    world: &'a mut WorldState,
+    target: &'a TargetContext,
+    pressure: &'a mut PressureState,
+    endgame_state: &'a EndgameState,
+    pressure_curve: &'a PressureModifierConfig,
+    growth: &'a mut GrowthState,
+}
+
+impl CombatFallout<'_> {
+    fn apply(
+        self,
+        out: &mut Vec<String>,
+        end: CombatEnd,
+        consequences: CombatConsequences,
+        location_id: LocationId,
+    ) {
+        let CombatFallout {
+            signatures:
+                SignatureFallout {
+                    city,
+                    city_events,
+                    evidence,
+                    identity_evidence,
+                    faction_director,
+                    faction_events,
+                    resolved_faction_events,
+                    cases,
+                    case_log,
+                    persona_stack,
+                    alignment,
+                    player_pos,
+                    event_log,
+                },
+            world,
+            target,
+            pressure,
+            endgame_state,
+            pressure_curve,
+            growth,
+        } = self;
+        if !consequences.signatures.is_empty() {
+            let witnesses = consequences.witnesses;
+            let identity_modifiers = combat_consequence_modifiers(consequences.combat_consequence);
+            SignatureFallout {
+                city,
+                city_events,
+                evidence,
+                identity_evidence,
+                faction_director,
+                faction_events,
+                resolved_faction_events,
+                cases,
+                case_log,
+                persona_stack,
+                alignment,
+                player_pos,
+                event_log,
+            }
+            .apply(
+                out,
+                SignatureAction {
+                    signatures: &consequences.signatures,
+                    environment_effects: &[],
+                    location_id,
+                    turn: world.turn,
+                    witnesses,
+                    in_public: target.in_public,
+                    persona_hint: PersonaHint::Unknown,
+                    attributed_persona: None,
+                    identity_modifiers: Some(identity_modifiers),
+                },
+            );
+        }
+
+        apply_combat_consequence_heat(
+            city,
+            location_id,
+            consequences.combat_consequence,
+            event_log,
+            city_events,
+        );
+
+        apply_combat_pressure_delta(pressure, consequences.pressure_delta);
+        apply_pressure_modifiers(world, pressure, pressure_curve, endgame_state);
+        let reputation = consequences.reputation;
+        growth.reputation.trust += reputation.trust;
+        growth.reputation.fear += reputation.fear;
+        growth.reputation.infamy += reputation.infamy;
+        growth.reputation.symbolism += reputation.symbolism;
+        if reputation != CombatReputationDelta::default() {
+            say!(
+                out,
+                "Crowd reaction: trust {:+} fear {:+} infamy {:+} symbolism {:+}",
+                reputation.trust, reputation.fear, reputation.infamy, reputation.symbolism
+            );
+        }
+
+        let case_summary = combat_case_progress_summary(cases, location_id);
+        say!(
+            out,
+            "Combat fallout: end={:?} signatures={} witnesses={} casualties={} consequence(p={} c={} n={}) pressure(t={:.1} id={:.1} inst={:.1} moral={:.1} res={:.1} psy={:.1}) evidence={} identity_evidence={} cases=[{}]",
+            end,
+            consequences.signatures.len(),
+            consequences.witnesses,
+            consequences.combat_consequence.civilian_casualties,
+            consequences.combat_consequence.publicness,
+            consequences.combat_consequence.collateral,
+            consequences.combat_consequence.notoriety,
+            pressure.temporal,
+            pressure.identity,
+            pressure.institutional,
+            pressure.moral,
+            pressure.resource,
+            pressure.psychological,
+            evidence.len(),
+            identity_evidence.items.len(),
+            case_summary.join(", ")
+        );
+    }
+}
+
+/// Everything one world turn advances, borrowed from the session.
+struct WorldTick<'a> {
+    world: &'a mut WorldState,
+    actor: &'a mut ActorState,
+    evidence: &'a mut WorldEvidence,
+    identity_evidence: &'a mut IdentityEvidenceStore,
+    city: &'a mut CityState,
+    city_events: &'a mut CityEventLog,
+    faction_director: &'a mut FactionDirector,
+    faction_events: &'a mut FactionEventLog,
+    resolved_faction_events: &'a mut ResolvedFactionEventLog,
+    cases: &'a mut CaseRegistry,
+    case_log: &'a mut CaseEventLog,
+    agents: &'a mut AgentRegistry,
+    agent_events: &'a mut AgentEventLog,
+    persona_stack: &'a mut PersonaStack,
+    storylets: &'a StoryletLibrary,
+    civilian_events: &'a [CivilianStorylet],
+    alignment: Alignment,
+    player_pos: &'a mut Position,
+    game_time: &'a mut GameTime,
+    civilian_state: &'a mut CivilianState,
+    storylet_state: &'a mut StoryletState,
+    endgame_state: &'a mut EndgameState,
+    pressure_curve: &'a PressureModifierConfig,
+    pressure: &'a mut PressureState,
+    region: &'a mut RegionState,
+    region_events: &'a mut RegionEventLog,
+    global_event_state: &'a mut GlobalEventState,
+    global_event_log: &'a mut GlobalEventLog,
+    global_events: &'a [GlobalEventDefinition],
+    global_faction_director: &'a mut GlobalFactionDirector,
+    global_faction_events: &'a mut GlobalFactionEventLog,
+    origin_quest: &'a mut OriginQuestState,
+    growth: &'a GrowthState,
+    origin_paths: &'a OriginPathCatalog,
+    world_seed: u64,
+}
+
+impl WorldTick<'_> {
+    fn run(
+        self,
+        out: &mut Vec<String>,
+        turns: u32,
+        auto_mode: AutoResolveMode,
+        storylet_triggered: &mut bool,
+    ) -> TickReport {
+        let WorldTick {
+            world,
+            actor,
+            evidence: scene,
+            identity_evidence,
+            city,
+            city_events,
+            faction_director,
+            faction_events,
+            resolved_faction_events,
+            cases,
+            case_log,
+            agents,
+            agent_events,
+            persona_stack,
+            storylets,
+            civilian_events,
+            alignment,
+            player_pos: position,
+            game_time,
+            civilian_state,
+            storylet_state,
+            endgame_state,
+            pressure_curve,
+            pressure,
+            region,
+            region_events,
+            global_event_state,
+            global_event_log,
+            global_events,
+            global_faction_director,
+            global_faction_events,
+            origin_quest,
+            growth,
+            origin_paths,
+            world_seed,
+        } = self;
+        let mut agent_event_log = WorldEventLog::default();
+        let mut report = TickReport::default();
+        for _ in 0..turns {
+            world.turn += 1;
+            actor.tick_cooldowns();
+            update_units(city);
+            scene.tick_decay();
+            identity_evidence.tick_decay(1);
+            decay_heat(city, cases, city_events);
+            game_time.advance();
+            tick_agents(agents, city, game_time, agent_events);
+            agent_event_log.0.clear();
+            apply_agent_events(
+                agent_events,
+                world.turn,
+                city,
+                city_events,
+                scene,
+                identity_evidence,
+                &mut agent_event_log,
+            );
+            tick_civilian_life(civilian_state, game_time, world_seed);
+            if civilian_state.pending_death.is_some() {
+                report.death_pending = true;
+                break;
+            }
+            if let Some(result) = tick_civilian_economy(civilian_state, game_time, world_seed) {
+                report.economy.push((game_time.day, result));
+            }
+            let restocked = restock_supplies(civilian_state, actor, game_time.day);
+            if !restocked.is_empty() {
+                report.restocks.push((game_time.day, restocked));
+            }
+            let context = CivilianChoiceContext {
+                library: civilian_events,
+                city,
+                seed: roll_seed(world_seed, world.turn),
+            };
+            auto_resolve_civilian_events(
+                out,
+                civilian_state,
+                context,
+                origin_quest,
+                origin_paths,
+                pressure,
+                auto_mode,
+            );
+            storylet_state.tick();
+            let rewards = tick_origin_path(origin_quest, origin_paths, pressure);
+            apply_origin_rewards(out, rewards.as_slice(), pressure);
+            if alignment == Alignment::Neutral {
+                let status = alignment_unlock_status(origin_quest, growth, civilian_state);
+                if status.unlocked
+                    && !storylet_state
+                        .flags
+                        .contains_key("alignment_choice_unlocked")
+                {
+                    storylet_state
+                        .flags
+                        .insert("alignment_choice_unlocked".to_string(), true);
+                    say!(
+                        out,
+                        "Alignment choice unlocked. Use `alignment choose <hero|vigilante|villain>`."
+                    );
+                }
+            }
+            if civilian_state.life.mutation_ready
+                && !storylet_state.flags.contains_key("life.mutation_ready")
+            {
+                storylet_state
+                    .flags
+                    .insert("life.mutation_ready".to_string(), true);
+                say!(out, "Mutation potential awakened.");
+            }
+            run_faction_director(faction_director, city, scene, faction_events);
+            resolve_faction_events(
+                faction_events,
+                resolved_faction_events,
+                city,
+                scene,
+                cases,
+                case_log,
+                Some(&mut *civilian_state),
+            );
+            contest_district_control(
+                city,
+                agents,
+                agent_events,
+                &faction_director.gang_faction_ids(),
+                city_events,
+            );
+            update_cases(
+                cases,
+                city,
+                scene,
+                identity_evidence,
+                &faction_director.faction_perception(),
+                case_log,
+            );
+            reveal_case_locations(out, city, cases);
+            apply_suspicion_for_intents(
+                persona_stack,
+                alignment,
+                position,
+                city,
+                cases,
+                identity_evidence,
+                &[],
+                1,
+            );
+            update_pressure(pressure, city, scene, cases, game_time);
+            apply_civilian_pressure(civilian_state, pressure);
+            apply_public_reputation_attention(civilian_state, city, game_time);
+            apply_civilian_mistake_consequences(civilian_state, city, pressure, game_time);
+            update_social_storylet_flags(civilian_state, storylet_state);
+            update_reputation_storylet_flags(civilian_state, storylet_state);
+            update_tech_storylet_flags(civilian_state, storylet_state);
+            set_storylet_flag(
+                storylet_state,
+                "civilian.mistake_recent",
+                civilian_state.last_mistake_day == game_time.day,
+            );
+            apply_pressure_modifiers(world, pressure, pressure_curve, endgame_state);
+            let ctx = build_storylet_context(
+                alignment,
+                persona_stack,
+                storylet_state,
+                endgame_state,
+                city,
+                scene,
+                cases,
+                pressure,
+                civilian_state,
+                game_time,
+            );
+            if let Some(storylet) = select_storylet_for_turn(
+                storylets,
+                alignment,
+                storylet_state,
+                &ctx,
+                roll_seed(world_seed, world.turn),
+            ) {
+                say!(
+                    out,
+                    "Storylet triggered: {} | {}",
+                    storylet.id,
+                    storylet.text_stub
+                );
+                for choice in &storylet.choices {
+                    say!(
+                        out,
+                        "  storylet resolve {} {} | {}",
+                        storylet.id,
+                        choice.id,
+                        choice.text
+                    );
+                }
+                for handoff in storylet.handoffs() {
+                    match handoff {
+                        StoryletHandoff::QueueCivilian(event_id) => {
+                            queue_event(civilian_state, &event_id, game_time.tick);
+                            say!(out, "Storylet queued civilian event {}.", event_id);
+                        }
+                        StoryletHandoff::StartCombat { .. } => {
+                            report.storylet_handoffs.push(handoff)
+                        }
+                    }
+                }
+                if is_punctuation_storylet(storylet) {
+                    report.punctuation_storylet = Some(storylet.id.clone());
+                }
+                *storylet_triggered = true;
+            }
+            print_turf_events(out, city_events);
+            run_region_update(region, city, pressure, city_events, region_events);
+            tick_global_events(
+                global_event_state,
+                global_events,
+                region,
+                game_time,
+                global_event_log,
+            );
+            run_global_faction_director(global_faction_director, region, global_faction_events);
+        }
+        report
+    }
+}
+
+/// Applies a newly earned endgame transformation and returns its announcement.
+fn handle_endgame_transition(
+    cases: &CaseRegistry,
+    pressure: &PressureState,
+    faction_events: &ResolvedFactionEventLog,
+    world: &mut WorldState,
+    storylet_state: &mut StoryletState,
+    endgame_state: &mut EndgameState,
+    pressure_curve: &PressureModifierConfig,
+) -> Option<String> {
+    let event = evaluate_transformation(cases, pressure, faction_events)?;
+    let update = apply_transformation_event(endgame_state, storylet_state, event)?;
+    apply_pressure_modifiers(world, pressure, pressure_curve, endgame_state);
+    Some(format!(
+        "Endgame triggered ({:?}): {}",
+        update.event.trigger, update.narrative
+    ))
+}
+
+fn print_use_error(
+    out: &mut Vec<String>,
+    err: &crate::rules::UseError,
+    expr: &crate::rules::ExpressionDef,
+    target: &TargetContext,
+    ctx: &UseContext,
+) {
+    let actor = &*ctx.actor;
+    say!(out, "can_use failed: {:?}", err);
+    match err {
+        crate::rules::UseError::Locked => {
+            say!(
+                out,
+                "Expression is locked. Use `growth unlock <expression_id>`."
+            );
+        }
+        crate::rules::UseError::OutOfRange { have, max } => {
+            say!(
+                out,
+                "Range: target is {}m away, expression reaches {}m. Close the distance (`set dist <m>`).",
+                have, max
+            );
+        }
+        crate::rules::UseError::NeedsLineOfSight => {
+            say!(
+                out,
+                "Line of sight required. Get a clear view of the target (`set los on`)."
+            );
+        }
+        crate::rules::UseError::NeedsContact => {
+            say!(
+                out,
+                "Physical contact required. Close in on the target (`set contact on`)."
+            );
+        }
+        crate::rules::UseError::WrongTime(allowed) => {
+            say!(
+                out,
+                "Time of day: allowed_time={:?} | is_day={:?}. Wait with `tick next`.",
+                allowed, ctx.is_day
+            );
+        }
+        crate::rules::UseError::ConstraintFailed(reason) => {
+            say!(
+                out,
+                "Constraint: {} | requires_los={} requires_contact={} range_m={:?} max_range_m={:?} allowed_time={:?}",
+                reason,
+                expr.constraints.requires_los,
+                expr.constraints.requires_contact,
+                expr.constraints.range_m,
+                expr.constraints.max_range_m,
+                expr.constraints.allowed_time
+            );
+            say!(
+                out,
+                "Context: dist_m={:?} los={} contact={} is_day={:?}",
+                target.distance_m, target.has_line_of_sight, target.has_contact, ctx.is_day
+            );
+        }
+        crate::rules::UseError::WrongPersona => {
+            say!(
+                out,
+                "Identity: requires_persona={:?} allowed_alignments={:?} | active persona={:?} alignment={:?}",
+                expr.constraints.requires_persona,
+                expr.constraints.allowed_alignments,
+                ctx.persona_type,
+                ctx.alignment
+            );
+        }
+        crate::rules::UseError::OnCooldown => {
+            if let Some(turns) = actor.cooldowns.get(&expr.id) {
+                say!(out, "Cooldown remaining: {}", turns);
+            }
+        }
+        crate::rules::UseError::NotEnoughStamina => {
+            let required = sum_costs(&expr.costs, CostType::Stamina);
+            say!(out, "Stamina: have={}, need={}", actor.stamina, required);
+        }
+        crate::rules::UseError::NotEnoughFocus => {
+            let required = sum_costs(&expr.costs, CostType::Focus);
+            say!(out, "Focus: have={}, need={}", actor.focus, required);
+        }
+        crate::rules::UseError::MissingResource => {
+            let required = sum_costs(&expr.costs, CostType::Resource);
+            let available = actor.resources.get("resource").copied().unwrap_or(0);
+            say!(out, "Resource: have={}, need={}", available, required);
+        }
+    }
+}
+
+fn sum_costs(costs: &[crate::rules::CostSpec], cost_type: CostType) -> i64 {
+    costs
+        .iter()
+        .filter(|c| c.cost_type == cost_type)
+        .filter_map(|c| c.value)
+        .sum()
+}
+
+fn load_storylet_library(
+    out: &mut Vec<String>,
+    civilian_events: &[CivilianStorylet],
+) -> StoryletLibrary {
+    let civilian_event_ids: HashSet<String> =
+        civilian_events.iter().map(|event| event.id.clone()).collect();
+    StoryletLibrary {
+        hero: load_storylet_file(out, "./assets/data/storylets_hero.json", &civilian_event_ids),
+        vigilante: load_storylet_file(
+            out,
+            "./assets/data/storylets_vigilante.json",
+            &civilian_event_ids,
+        ),
+        villain: load_storylet_file(
+            out,
+            "./assets/data/storylets_villain.json",
+            &civilian_event_ids,
+        ),
+        endgame_hero: load_endgame_storylet_file(
+            out,
+            "./assets/data/storylets_endgame_hero.json",
+            &civilian_event_ids,
+        ),
+        endgame_vigilante: load_endgame_storylet_file(
+            out,
+            "./assets/data/storylets_endgame_vigilante.json",
+            &civilian_event_ids,
+        ),
+        endgame_villain: load_endgame_storylet_file(
+            out,
+            "./assets/data/storylets_endgame_villain.json",
+            &civilian_event_ids,
+        ),
+    }
+}
+
+/// Endgame pools are optional; a missing file is an empty pool.
+fn load_endgame_storylet_file(
+    out: &mut Vec<String>,
+    path: &str,
+    civilian_event_ids: &HashSet<String>,
+) -> Vec<Storylet> {
+    if !Path::new(path).exists() {
+        return Vec::new();
+    }
+    load_storylet_file(out, path, civilian_event_ids)
+}
+
+fn load_storylet_file(
+    out: &mut Vec<String>,
+    path: &str,
+    civilian_event_ids: &HashSet<String>,
+) -> Vec<Storylet> {
+    match load_storylet_catalog(path).and_then(|catalog| {
+        catalog.validate_handoffs(civilian_event_ids)?;
+        Ok(catalog)
+    }) {
+        Ok(catalog) => catalog.storylets,
+        Err(err) => {
+            say!(out, "Failed to load storylets from {}: {}", path, err);
+            Vec::new()
+        }
+    }
+}
+
+fn load_civilian_event_library(out: &mut Vec<String>, paths: &[PathBuf]) -> Vec<CivilianStorylet> {
+    match load_civilian_event_catalogs(paths) {
+        Ok(merged) => {
+            print_civilian_event_collisions(out, &merged.collisions);
+            merged.events
+        }
+        Err(err) => {
+            say!(out, "Failed to load civilian events: {}", err);
+            Vec::new()
+        }
+    }
+}
+
+pub fn print_civilian_event_collisions(
+    out: &mut Vec<String>,
+    collisions: &[CivilianEventCollision],
+) {
+    for collision in collisions {
+        say!(
+            out,
+            "Civilian event {} from {} overrides the one in {}.",
+            collision.event_id, collision.replaced_by, collision.replaced_from
+        );
+    }
+}
+
+fn load_endgame_event_library(out: &mut Vec<String>) -> Vec<EndgameEvent> {
+    match load_endgame_event_catalog("./assets/data/endgame_events.json") {
+        Ok(catalog) => catalog.events,
+        Err(err) => {
+            say!(out, "Failed to load endgame events: {}", err);
+            Vec::new()
+        }
+    }
+}
+
+fn load_global_event_library(out: &mut Vec<String>) -> Vec<GlobalEventDefinition> {
+    match load_global_event_catalog("./assets/data/global_events.json") {
+        Ok(catalog) => catalog.events,
+        Err(err) => {
+            say!(out, "Failed to load global events: {}", err);
+            Vec::new()
+        }
+    }
+}
+
+fn print_global_escalation(out: &mut Vec<String>, region: &RegionState) {
+    say!(
+        out,
+        "Global escalation: {:?} (pressure {:.1})",
+        region.global_pressure.escalation, region.global_pressure.total
+    );
+}
+
+fn print_pending_global_events(
+    out: &mut Vec<String>,
+    state: &GlobalEventState,
+    catalog: &[GlobalEventDefinition],
+    time: &GameTime,
+    show_details: bool,
+) {
+    if state.pending.is_empty() {
+        say!(out, "Global events: none");
+        return;
+    }
+    say!(out, "Global events:");
+    for event in &state.pending {
+        match find_global_event(catalog, &event.event_id) {
+            Some(def) => {
+                print_global_event_entry(out, event, def, time, show_details);
+            }
+            None => {
+                let age = time.tick.saturating_sub(event.created_tick);
+                say!(
+                    out,
+                    "  {} | (missing definition) | queued {} ticks ago",
+                    event.event_id, age
+                );
+            }
+        }
+    }
+}
+
+fn print_global_event_entry(
+    out: &mut Vec<String>,
+    event: &GlobalEventInstance,
+    def: &GlobalEventDefinition,
+    time: &GameTime,
+    show_details: bool,
+) {
+    let age = time.tick.saturating_sub(event.created_tick);
+    say!(
+        out,
+        "  {} | {} | queued {} ticks ago",
+        def.id,
+        def.title,
+        age
+    );
+    say!(out, "    {}", def.text_stub);
+    if show_details && !def.details.is_empty() {
+        say!(out, "    details:");
+        for detail in &def.details {
+            say!(out, "      - {}", detail);
+        }
+    }
+    for choice in &def.choices {
+        say!(out, "    [{}] {}", choice.id, choice.text);
+    }
+    if !show_details && !def.details.is_empty() {
+        say!(out, "    (see more: global detail {})", def.id);
+    }
+}
+
+fn print_global_event_detail(
+    out: &mut Vec<String>,
+    state: &GlobalEventState,
+    catalog: &[GlobalEventDefinition],
+    time: &GameTime,
+    event_id: &str,
+) {
+    let Some(event) = state
+        .pending
+        .iter()
+        .find(|event| event.event_id == event_id)
+    else {
+        say!(out, "Global event not pending: {}", event_id);
+        return;
+    };
+    let Some(def) = find_global_event(catalog, event_id) else {
+        say!(out, "Global event missing definition: {}", event_id);
+        return;
+    };
+    say!(out, "Global event detail:");
+    print_global_event_entry(out, event, def, time, true);
+}
+
+fn print_cast(out: &mut Vec<String>, characters: &[PersistentCharacter]) {
+    say!(out, "Characters: {}", characters.len());
+    for (idx, character) in characters.iter().take(10).enumerate() {
+        let role = character
+            .roles
+            .first()
+            .map(|role| role.role_type.as_str())
+            .unwrap_or("UNKNOWN");
+        say!(
+            out,
+            "  {}. {} {} ({}) id={}",
+            idx + 1,
+            character.first_name,
+            character.last_name,
+            role,
+            character.character_id
+        );
+    }
+    if characters.len() > 10 {
+        say!(out, "  ...");
+    }
+}
+
+struct StoryletContext {
+    alignment: Alignment,
+    active_persona: Option<PersonaType>,
+    flags: HashSet<String>,
+    endgame_state: Option<TransformationState>,
+    public_suspicion: i32,
+    civilian_suspicion: i32,
+    wanted_level: i32,
+    exposure_risk: i32,
+    heat: i32,
+    surveillance_level: i32,
+    crime_pressure: i32,
+    case_progress: i32,
+    has_visible_signatures: bool,
+    is_day: bool,
+    stress: i32,
+    reputation: i32,
+    reputation_media: i32,
+    reputation_community: i32,
+    reputation_career: i32,
+    wealth_tier: i32,
+    tech_access: i32,
+    social_leverage: i32,
+    social_protection: i32,
+    social_vulnerability: i32,
+    pressure_identity: i32,
+    pressure_moral: i32,
+    pressure_institutional: i32,
+    pressure_resource: i32,
+    pressure_temporal: i32,
+    pressure_psychological: i32,
+    /// Rounded per-tick slope of each axis, ordered like `PressureAxis::ALL`.
+    pressure_trends: [i32; 6],
+}
+
+struct StoryletEligibility {
+    eligible: bool,
+    matched: Vec<String>,
+}
+
+fn list_storylets_all(out: &mut Vec<String>, library: &StoryletLibrary, alignment: Alignment) {
+    let storylets = library.for_alignment(alignment);
+    if storylets.is_empty() {
+        say!(out, "Storylets: none");
+        return;
+    }
+    say!(out, "Storylets ({:?}):", alignment);
+    for storylet in storylets {
+        let prereqs = if storylet.preconditions.is_empty() {
+            "always".to_string()
+        } else {
+            storylet.preconditions.join(" & ")
+        };
+        say!(
+            out,
+            "  {} | {:?} | {}",
+            storylet.id,
+            storylet.category,
+            prereqs
+        );
+    }
+    let endgame = library.endgame_for_alignment(alignment);
+    if !endgame.is_empty() {
+        say!(out, "Endgame storylets ({:?}):", alignment);
+        for storylet in endgame {
+            say!(
+                out,
+                "  {} | {:?} | {}",
+                storylet.id,
+                storylet.category,
+                storylet.preconditions.join(" & ")
+            );
+        }
+    }
+}
+
+fn list_storylets_available(
+    out: &mut Vec<String>,
+    library: &StoryletLibrary,
+    alignment: Alignment,
+    persona_stack: &PersonaStack,
+    storylet_state: &StoryletState,
+    endgame_state: &EndgameState,
+    city: &CityState,
+    evidence: &WorldEvidence,
+    cases: &CaseRegistry,
+    pressure: &PressureState,
+    civilian_state: &CivilianState,
+    game_time: &GameTime,
+) {
+    let ctx = build_storylet_context(
+        alignment,
+        persona_stack,
+        storylet_state,
+        endgame_state,
+        city,
+        evidence,
+        cases,
+        pressure,
+        civilian_state,
+        game_time,
+    );
+    let mut count = 0;
+    if storylet_state.punctuation.only {
+        say!(
+            out,
+            "Punctuation layer active ({} turns remaining).",
+            storylet_state.punctuation.remaining_turns
+        );
+    }
+    if storylet_state.punctuation_cooldown > 0 {
+        say!(
+            out,
+            "Punctuation cooldown active ({} turns remaining).",
+            storylet_state.punctuation_cooldown
+        );
+    }
+    say!(out, "Storylets available:");
+    for storylet in library.candidates(alignment, endgame_state.phase.is_some()) {
+        if !storylet_passes_state_gates(storylet, storylet_state) {
+            continue;
+        }
+        let eval = evaluate_storylet(storylet, &ctx);
+        if !eval.eligible {
+            continue;
+        }
+        let reason = if eval.matched.is_empty() {
+            "always".to_string()
+        } else {
+            eval.matched.join(" & ")
+        };
+        say!(
+            out,
+            "  {} | {:?} | {}",
+            storylet.id,
+            storylet.category,
+            reason
+        );
+        count += 1;
+    }
+    if count == 0 {
+        say!(out, "  (none)");
+    }
+}
+
+fn storylet_passes_state_gates(storylet: &Storylet, storylet_state: &StoryletState) -> bool {
+    if storylet_state.fired.contains(&storylet.id) {
+        return false;
+    }
+    if storylet_state
+        .cooldowns
+        .get(&storylet.id)
+        .copied()
+        .unwrap_or(0)
+        > 0
+    {
+        return false;
+    }
+    if !storylet_has_gate_requirements(storylet) {
+        return false;
+    }
+    if storylet_state.punctuation.only && !is_punctuation_storylet(storylet) {
+        return false;
+    }
+    if storylet_state.punctuation_cooldown > 0 && is_punctuation_storylet(storylet) {
+        return false;
+    }
+    true
+}
+
+fn build_storylet_context(
+    alignment: Alignment,
+    persona_stack: &PersonaStack,
+    storylet_state: &StoryletState,
+    endgame_state: &EndgameState,
+    city: &CityState,
+    evidence: &WorldEvidence,
+    cases: &CaseRegistry,
+    pressure: &PressureState,
+    civilian_state: &CivilianState,
+    game_time: &GameTime,
+) -> StoryletContext {
+    let active_persona = persona_stack.active_persona();
+    let (public_suspicion, civilian_suspicion, wanted_level, exposure_risk) = active_persona
+        .map(|persona| {
+            (
+                persona.suspicion.public_suspicion as i32,
+                persona.suspicion.civilian_suspicion as i32,
+                persona.suspicion.wanted_level as i32,
+                persona.suspicion.exposure_risk as i32,
+            )
+        })
+        .unwrap_or((0, 0, 0, 0));
+
+    let location = city.locations.get(&city.active_location);
+    let heat = location.map(|loc| loc.heat).unwrap_or(0);
+    let surveillance_level = location.map(|loc| loc.surveillance_level).unwrap_or(0);
+    let crime_pressure = location.map(|loc| loc.crime_pressure).unwrap_or(0);
+
+    let case_progress = cases
+        .cases
+        .iter()
+        .filter(|case| case.location_id == city.active_location)
+        .map(|case| case.progress as i32)
+        .max()
+        .unwrap_or(0);
+
+    let has_visible_signatures = evidence.iter().any(|event| {
+        event.location_id == city.active_location && event.signature.remaining_turns > 0
+    });
+    let flags = storylet_state
+        .flags
+        .iter()
+        .filter(|(_, enabled)| **enabled)
+        .map(|(flag, _)| flag.clone())
+        .collect();
+
+    let reputation = civilian_state.public_reputation_score();
+    let reputation_media = civilian_state.reputation.media;
+    let reputation_community = civilian_state.reputation.community;
+    let reputation_career = civilian_state.reputation.career;
+    let wealth_tier = civilian_state.wealth.tier.rank() as i32;
+    let tech_access = civilian_state.tech_access_score();
+    let social_leverage = civilian_state.social_leverage_score();
+    let social_protection = civilian_state.social_protection_score();
+    let social_vulnerability = civilian_state.social_vulnerability_score();
+
+    StoryletContext {
+        alignment,
+        active_persona: active_persona.map(|persona| persona.persona_type),
+        flags,
+        endgame_state: endgame_state.phase,
+        public_suspicion,
+        civilian_suspicion,
+        wanted_level,
+        exposure_risk,
+        heat,
+        surveillance_level,
+        crime_pressure,
+        case_progress,
+        has_visible_signatures,
+        is_day: game_time.is_day,
+        stress: 0,
+        reputation,
+        reputation_media,
+        reputation_community,
+        reputation_career,
+        wealth_tier,
+        tech_access,
+        social_leverage,
+        social_protection,
+        social_vulnerability,
+        pressure_identity: pressure.identity.round() as i32,
+        pressure_moral: pressure.moral.round() as i32,
+        pressure_institutional: pressure.institutional.round() as i32,
+        pressure_resource: pressure.resource.round() as i32,
+        pressure_temporal: pressure.temporal.round() as i32,
+        pressure_psychological: pressure.psychological.round() as i32,
+        pressure_trends: PressureAxis::ALL.map(|axis| pressure.trend(axis).round() as i32),
+    }
+}
+
+/// Draws a weighted storylet among every eligible one in the first tier that
+/// has any, seeded from `seed` so a replayed turn picks the same storylet.
+fn select_storylet_for_turn<'a>(
+    library: &'a StoryletLibrary,
+    alignment: Alignment,
+    storylet_state: &mut StoryletState,
+    ctx: &StoryletContext,
+    seed: u64,
+) -> Option<&'a Storylet> {
+    for tier in library.tiers(alignment, ctx.endgame_state.is_some()) {
+        let eligible: Vec<&Storylet> = tier
+            .iter()
+            .filter(|storylet| storylet_passes_state_gates(storylet, storylet_state))
+            .filter(|storylet| evaluate_storylet(storylet, ctx).eligible)
+            .collect();
+        let Some(storylet) = pick_weighted_storylet(&eligible, seed) else {
+            continue;
+        };
+        storylet_state.fired.insert(storylet.id.clone());
+        if is_punctuation_storylet(storylet) {
+            storylet_state.punctuation_cooldown = DEFAULT_PUNCTUATION_COOLDOWN_TURNS;
+        }
+        return Some(storylet);
+    }
+    None
+}
+
+fn evaluate_storylet(storylet: &Storylet, ctx: &StoryletContext) -> StoryletEligibility {
+    let mut matched = Vec::new();
+    for condition in &storylet.preconditions {
+        if eval_condition(condition, ctx) {
+            matched.push(condition.clone());
+        } else {
+            return StoryletEligibility {
+                eligible: false,
+                matched: Vec::new(),
+            };
+        }
+    }
+    StoryletEligibility {
+        eligible: true,
+        matched,
+    }
+}
+
+/// Each precondition may OR several terms with `|`; see `condition_terms`.
+fn eval_condition(condition: &str, ctx: &StoryletContext) -> bool {
+    condition_holds(condition, |term| eval_condition_term(term, ctx))
+}
+
+fn eval_condition_term(cond: &str, ctx: &StoryletContext) -> bool {
+    if cond == "time.is_day" {
+        return ctx.is_day;
+    }
+    if cond == "time.is_night" {
+        return !ctx.is_day;
+    }
+    if cond == "signatures.visible" {
+        return ctx.has_visible_signatures;
+    }
+    if let Some(flag) = cond.strip_prefix("flag.") {
+        return ctx.flags.contains(flag);
+    }
+    if let Some(axis) = cond.strip_prefix("pressure.") {
+        if let Some(axis) = axis.strip_suffix(".rising") {
+            return pressure_trend(axis, ctx).is_some_and(|trend| trend > 0);
+        }
+        if let Some(axis) = axis.strip_suffix(".falling") {
+            return pressure_trend(axis, ctx).is_some_and(|trend| trend < 0);
+        }
+    }
+
+    let parts: Vec<&str> = cond.split_whitespace().collect();
+    if parts.len() != 3 {
+        return false;
+    }
+
+    let left = parts[0];
+    let op = parts[1];
+    let right = parts[2];
+
+    match left {
+        "alignment" => {
+            let Some(expected) = parse_alignment(right) else {
+                return false;
+            };
+            match op {
+                "==" => ctx.alignment == expected,
+                "!=" => ctx.alignment != expected,
+                _ => false,
+            }
+        }
+        "persona" => {
+            let Some(expected) = parse_persona_type(right) else {
+                return false;
+            };
+            match op {
+                "==" => ctx.active_persona == Some(expected),
+                "!=" => ctx.active_persona != Some(expected),
+                _ => false,
+            }
+        }
+        "endgame.state" => {
+            let Some(expected) = parse_endgame_state(right) else {
+                return false;
+            };
+            match op {
+                "==" => ctx.endgame_state == Some(expected),
+                "!=" => ctx.endgame_state != Some(expected),
+                _ => false,
+            }
+        }
+        _ => {
+            let Some(left_value) = numeric_metric(left, ctx) else {
+                return false;
+            };
+            compare_metric(left_value, op, right)
+        }
+    }
+}
+
+fn parse_alignment(value: &str) -> Option<Alignment> {
+    match value.to_ascii_uppercase().as_str() {
+        "NEUTRAL" => Some(Alignment::Neutral),
+        "HERO" => Some(Alignment::Hero),
+        "VIGILANTE" => Some(Alignment::Vigilante),
+        "VILLAIN" => Some(Alignment::Villain),
+        _ => None,
+    }
+}
+
+fn parse_persona_type(value: &str) -> Option<PersonaType> {
+    match value.to_ascii_uppercase().as_str() {
+        "CIVILIAN" => Some(PersonaType::Civilian),
+        "MASKED" => Some(PersonaType::Masked),
+        _ => None,
+    }
+}
+
+fn parse_endgame_state(value: &str) -> Option<TransformationState> {
+    match value.to_ascii_uppercase().as_str() {
+        "EXPOSED" => Some(TransformationState::Exposed),
+        "REGISTRATION" => Some(TransformationState::Registration),
+        "COSMIC_JUDGEMENT" | "COSMIC_JUDGMENT" => Some(TransformationState::CosmicJudgement),
+        "ASCENSION" => Some(TransformationState::Ascension),
+        "EXILE" => Some(TransformationState::Exile),
+        _ => None,
+    }
+}
+
+fn numeric_metric(key: &str, ctx: &StoryletContext) -> Option<i32> {
+    match key {
+        "public.suspicion" => Some(ctx.public_suspicion),
+        "civilian.suspicion" => Some(ctx.civilian_suspicion),
+        "wanted.level" => Some(ctx.wanted_level),
+        "exposure.risk" => Some(ctx.exposure_risk),
+        "heat" => Some(ctx.heat),
+        "surveillance.level" => Some(ctx.surveillance_level),
+        "gang.pressure" | "crime.pressure" => Some(ctx.crime_pressure),
+        "case.progress" => Some(ctx.case_progress),
+        "stress" => Some(ctx.stress),
+        "reputation" => Some(ctx.reputation),
+        "reputation.media" => Some(ctx.reputation_media),
+        "reputation.community" => Some(ctx.reputation_community),
+        "reputation.career" => Some(ctx.reputation_career),
+        "wealth.tier" => Some(ctx.wealth_tier),
+        "tech.access" => Some(ctx.tech_access),
+        "social.leverage" => Some(ctx.social_leverage),
+        "social.protection" => Some(ctx.social_protection),
+        "social.vulnerability" => Some(ctx.social_vulnerability),
+        "pressure.identity" => Some(ctx.pressure_identity),
+        "pressure.moral" => Some(ctx.pressure_moral),
+        "pressure.institutional" => Some(ctx.pressure_institutional),
+        "pressure.resource" => Some(ctx.pressure_resource),
+        "pressure.temporal" => Some(ctx.pressure_temporal),
+        "pressure.psychological" => Some(ctx.pressure_psychological),
+        _ => key
+            .strip_prefix("pressure.")
+            .and_then(|rest| rest.strip_suffix(".trend"))
+            .and_then(|axis| pressure_trend(axis, ctx)),
+    }
+}
+
+fn pressure_trend(axis: &str, ctx: &StoryletContext) -> Option<i32> {
+    PressureAxis::from_key(axis).map(|axis| ctx.pressure_trends[axis.index()])
+}
+
+fn print_economy_days(out: &mut Vec<String>, days: &[(u32, EconomyTickResult)]) {
+    for (day, result) in days {
+        say!(
+            out,
+            "Economy day {}: income={}CR upkeep={}CR net={:+}CR balance={}CR tier={}",
+            day,
+            result.income_cr,
+            result.upkeep_cr,
+            result.net_cr,
+            result.balance_cr,
+            result.tier.label()
+        );
+    }
+}
+
+fn print_restock_days(out: &mut Vec<String>, days: &[(u32, Vec<String>)]) {
+    for (day, restocked) in days {
+        say!(out, "Restock day {}: {}", day, restocked.join(", "));
+    }
+}
+
+fn print_supplies(out: &mut Vec<String>, actor: &ActorState, civilian: &CivilianState) {
+    let cap = civilian.supply_cap();
+    let pools: Vec<String> = SUPPLY_POOLS
+        .iter()
+        .map(|pool| {
+            format!(
+                "{}={}/{}",
+                pool,
+                actor.resources.get(*pool).copied().unwrap_or(0),
+                cap
+            )
+        })
+        .collect();
+    say!(
+        out,
+        "Supplies: {} | safehouse={}",
+        pools.join(" "),
+        civilian.has_safehouse()
+    );
+}
+
+fn print_tick_summary(
+    out: &mut Vec<String>,
+    world: &WorldState,
+    stack: &PersonaStack,
+    city: &CityState,
+    cases: &CaseRegistry,
+    pressure: &PressureState,
+    endgame_state: &EndgameState,
+) {
+    let location = city.locations.get(&city.active_location);
+    let (heat, response, crime) = location
+        .map(|loc| (loc.heat, loc.response, loc.crime_pressure))
+        .unwrap_or((
+            0,
+            crate::simulation::city::HeatResponse::None,
+            0,
+        ));
+    say!(
+        out,
+        "Turn {} | heat={} response={:?} crime_pressure={}",
+        world.turn, heat, response, crime
+    );
+    if let Some(active) = stack.active_persona() {
+        say!(
+            out,
+            "Active persona {} ({:?}) -> public={} civilian={} wanted={} exposure={}",
+            active.persona_id,
+            active.persona_type,
+            active.suspicion.public_suspicion,
+            active.suspicion.civilian_suspicion,
+            active.suspicion.wanted_level,
+            active.suspicion.exposure_risk
+        );
+    }
+    let max_progress = cases
+        .cases
+        .iter()
+        .filter(|case| case.location_id == city.active_location)
+        .map(|case| case.progress)
+        .max()
+        .unwrap_or(0);
+    if max_progress > 0 {
+        say!(out, "Case progress (max): {}", max_progress);
+    }
+    say!(
+        out,
+        "Pressure: temporal={:.1} identity={:.1} institutional={:.1} moral={:.1} resource={:.1} psychological={:.1}",
+        pressure.temporal,
+        pressure.identity,
+        pressure.institutional,
+        pressure.moral,
+        pressure.resource,
+        pressure.psychological
+    );
+    let modifiers = endgame_state.modifiers();
+    say!(
+        out,
+        "Endgame: {} | modifiers cost={:.2} risk={:.2}",
+        endgame_state.label(),
+        modifiers.cost_scale,
+        modifiers.risk_scale
+    );
+}
+
+fn print_origin_paths(out: &mut Vec<String>, paths: &[OriginPathDefinition], seed: u64) {
+    if paths.is_empty() {
+        say!(out, "No origin paths available for seed {}.", seed);
+        return;
+    }
+    say!(out, "Origin paths (seed={}):", seed);
+    for path in paths {
+        say!(out, "  {} - {}: {}", path.id, path.label, path.summary);
+        if !path.stages.is_empty() {
+            let stage_ids: Vec<&str> = path.stages.iter().map(|stage| stage.id.as_str()).collect();
+            say!(out, "    stages: {}", stage_ids.join(" -> "));
+        }
+    }
+}
+
+fn print_origin_path_status(
+    out: &mut Vec<String>,
+    state: &OriginQuestState,
+    catalog: &OriginPathCatalog,
+) {
+    let Some(path_id) = state.path_id.as_deref() else {
+        say!(out, "Origin path: none selected.");
+        if !state.discovered_paths.is_empty() {
+            say!(out, "Discovered origin paths:");
+            for id in &state.discovered_paths {
+                if let Some(path) = catalog.paths.iter().find(|path| path.id == *id) {
+                    say!(out, "  {} - {}", path.id, path.label);
+                } else {
+                    say!(out, "  {}", id);
+                }
+            }
+        }
+        return;
+    };
+    let path = catalog.paths.iter().find(|path| path.id == path_id);
+    let Some(path) = path else {
+        say!(out, "Origin path: {} (missing definition)", path_id);
+        return;
+    };
+    say!(out, "Origin path: {} - {}", path.label, path.summary);
+    match &state.ended {
+        Some(OriginPathEnd::Failed { stage_id }) => {
+            say!(out, "  status: failed at stage {}", stage_id);
+            return;
+        }
+        Some(OriginPathEnd::Abandoned { stage_id }) => {
+            say!(
+                out,
+                "  status: abandoned at stage {} ({} stages complete)",
+                stage_id,
+                state.completed_stages.len()
+            );
+            return;
+        }
+        None => {}
+    }
+    if state.completed {
+        say!(
+            out,
+            "  status: complete ({} stages)",
+            state.completed_stages.len()
+        );
+        return;
+    }
+    let stages = active_origin_stages(state, catalog);
+    if stages.is_empty() {
+        say!(out, "  stage: none");
+    }
+    for stage in stages {
+        let needed = stage.requirement.progress_needed.max(1);
+        let progress = state
+            .active_stages
+            .get(&stage.id)
+            .map(|entry| entry.progress)
+            .unwrap_or(0);
+        say!(
+            out,
+            "  stage: {} ({}) progress {}/{}",
+            stage.id, stage.label, progress, needed
+        );
+    }
+}
+
+fn apply_origin_rewards(
+    out: &mut Vec<String>,
+    rewards: &[OriginStageReward],
+    pressure: &mut PressureState,
+) {
+    if rewards.is_empty() {
+        return;
+    }
+    for reward in rewards {
+        if reward.reputation_delta != 0 {
+            say!(out, "Origin reward: reputation {}", reward.reputation_delta);
+        }
+        if reward.pressure_delta.temporal != 0.0
+            || reward.pressure_delta.identity != 0.0
+            || reward.pressure_delta.institutional != 0.0
+            || reward.pressure_delta.moral != 0.0
+            || reward.pressure_delta.resource != 0.0
+            || reward.pressure_delta.psychological != 0.0
+        {
+            pressure.temporal =
+                (pressure.temporal + reward.pressure_delta.temporal).clamp(0.0, 100.0);
+            pressure.identity =
+                (pressure.identity + reward.pressure_delta.identity).clamp(0.0, 100.0);
+            pressure.institutional =
+                (pressure.institutional + reward.pressure_delta.institutional).clamp(0.0, 100.0);
+            pressure.moral = (pressure.moral + reward.pressure_delta.moral).clamp(0.0, 100.0);
+            pressure.resource =
+                (pressure.resource + reward.pressure_delta.resource).clamp(0.0, 100.0);
+            pressure.psychological =
+                (pressure.psychological + reward.pressure_delta.psychological).clamp(0.0, 100.0);
+            say!(out, "Origin reward: pressure updated.");
+        }
+        if !reward.mutation_tags.is_empty() {
+            say!(
+                out,
+                "Origin reward: mutations {}",
+                reward.mutation_tags.join(", ")
+            );
+        }
+        if let Some(notes) = reward.notes.as_deref() {
+            say!(out, "Origin reward: {}", notes);
+        }
+    }
+}
+
+fn print_power_assignment(
+    out: &mut Vec<String>,
+    repo: &dyn PowerRepository,
+    assignment: &PowerAssignmentResult,
+) {
+    if let Some(baseline) = assignment.baseline {
+        say!(out, "Baseline power: {}", power_label(repo, baseline));
+    } else {
+        say!(out, "Baseline power: none");
+    }
+
+    if assignment.expressions.is_empty() {
+        say!(out, "Expression powers: none");
+    } else {
+        say!(
+            out,
+            "Expression powers: {}",
+            assignment
+                .expressions
+                .iter()
+                .map(|id| power_label(repo, *id))
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+    }
+
+    if assignment.omni.is_empty() {
+        say!(out, "Omni powers: none");
+    } else {
+        say!(
+            out,
+            "Omni powers: {}",
+            assignment
+                .omni
+                .iter()
+                .map(|id| power_label(repo, *id))
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+    }
+
+    for note in &assignment.notes {
+        say!(out, "Note: {}", note);
+    }
+}
+
+fn print_repository_error(out: &mut Vec<String>, err: &RepositoryError) {
+    match err {
+        RepositoryError::NotFound { kind, id } => say!(out, "The {} {} doesn't exist.", kind, id),
+        _ => say!(out, "Content database problem: {}", err),
+    }
+}
+
+fn power_label(repo: &dyn PowerRepository, power_id: PowerId) -> String {
+    match repo.power_info(power_id) {
+        Ok(Some(info)) => format!("{} (id={})", info.name, power_id.0),
+        Ok(None) => format!("Unknown power (id={})", power_id.0),
+        Err(_) => format!("Power lookup failed (id={})", power_id.0),
+    }
+}
+
+fn print_cosmic_constants(out: &mut Vec<String>, catalog: &CosmicConstantsCatalog) {
+    if catalog.constants.is_empty() {
+        say!(out, "Cosmic constants: none loaded");
+        return;
+    }
+    say!(out, "Cosmic constants:");
+    for constant in &catalog.constants {
+        say!(
+            out,
+            "  {} ({}) | role={} | bodies={} | hive_mind={}",
+            constant.label,
+            constant.id,
+            constant.role,
+            constant.body_count,
+            constant.hive_mind
+        );
+        for note in &constant.notes {
+            say!(out, "    {}", note);
+        }
+    }
+}
+
+fn print_omni_registry(out: &mut Vec<String>, registry: &OmniPowerRegistry) {
+    let holders = registry.holder_powers();
+    if holders.is_empty() {
+        say!(out, "Omni registry: no holders assigned.");
+        return;
+    }
+
+    say!(out, "Omni registry:");
+    for (holder_id, powers) in holders {
+        say!(out, "  {} -> {}", holder_id, powers.join(", "));
+    }
+    let universes = registry.universe_holders();
+    if !universes.is_empty() {
+        say!(out, "Omni universes:");
+        for (universe_id, holder_id) in universes {
+            say!(out, "  {} -> {}", universe_id, holder_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_commands_that_can_change_the_world_dirty_the_save() {
+        let reads = [
+            "stats",
+            "loc",
+            "cases",
+            "civilian events",
+            "combat log",
+            "tick stops",
+        ];
+        for input in reads {
+            assert!(is_read_only_command(input), "{} only reads", input);
+        }
+        let writes = [
+            "tick",
+            "travel 2",
+            "cases intel 1",
+            "civilian resolve a b",
+            "tick stops none",
+        ];
+        for input in writes {
+            assert!(!is_read_only_command(input), "{} can write", input);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
 
@@ -11,6 +11,7 @@ use crate::simulation::time::GameTime;
 
 const DEFAULT_AGENTS_PATH: &str = "./assets/data/agents.json";
 const DAYS_PER_YEAR: u32 = 336;
+const AMBUSH_COOLDOWN_TICKS: u64 = 24;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentCatalog {
@@ -29,6 +30,11 @@ pub struct AgentRole {
     pub incident_cooldown: u64,
     #[serde(default)]
     pub incident_signatures: Vec<SignatureSpec>,
+    #[serde(default)]
+    pub hostile: bool,
+    /// Patrols for the law; their presence pushes back gang influence.
+    #[serde(default)]
+    pub law_enforcement: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,10 +84,61 @@ pub struct AgentState {
     pub schedule: AgentSchedule,
     pub current_location: LocationId,
     pub last_incident_tick: u64,
+    pub last_ambush_tick: Option<u64>,
     pub age_years: u32,
     pub birth_day: u32,
 }
 
+/// How readily hostile agents jump an exposed player sharing their location.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AmbushSetting {
+    #[default]
+    Off,
+    Cautious,
+    Normal,
+    Brutal,
+}
+
+impl AmbushSetting {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "off" => Some(AmbushSetting::Off),
+            "cautious" => Some(AmbushSetting::Cautious),
+            "normal" => Some(AmbushSetting::Normal),
+            "brutal" => Some(AmbushSetting::Brutal),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AmbushSetting::Off => "off",
+            AmbushSetting::Cautious => "cautious",
+            AmbushSetting::Normal => "normal",
+            AmbushSetting::Brutal => "brutal",
+        }
+    }
+
+    /// Minimum exposure risk before an ambush fires; `None` disables ambushes.
+    pub fn exposure_threshold(self) -> Option<u8> {
+        match self {
+            AmbushSetting::Off => None,
+            AmbushSetting::Cautious => Some(70),
+            AmbushSetting::Normal => Some(50),
+            AmbushSetting::Brutal => Some(30),
+        }
+    }
+}
+
+/// The saved side of ambushes: the chosen setting and each agent's last ambush
+/// tick, keyed by agent id, so a reload keeps both the tuning and the cooldowns.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AmbushState {
+    pub setting: AmbushSetting,
+    pub last_ambush_ticks: BTreeMap<u32, u64>,
+}
+
 #[derive(Resource, Debug, Default, Clone)]
 pub struct AgentRegistry {
     pub roles: HashMap<String, AgentRole>,
@@ -181,6 +238,7 @@ impl AgentRegistry {
                 schedule,
                 current_location: home_location,
                 last_incident_tick: 0,
+                last_ambush_tick: None,
                 age_years,
                 birth_day: 0,
             });
@@ -203,7 +261,7 @@ pub fn tick_agents(
         let target_location = default_target_location(
             state.schedule.home_location,
             state.schedule.haunt_location,
-            time.hour,
+            time.clock_hour(),
         );
 
         if state.goal.target_location != target_location {
@@ -250,6 +308,86 @@ pub fn tick_agents(
     }
 }
 
+impl AgentRegistry {
+    pub fn agents_at(&self, location_id: LocationId) -> u32 {
+        self.agents
+            .iter()
+            .filter(|state| state.current_location == location_id)
+            .count() as u32
+    }
+
+    pub fn is_law_enforcement(&self, agent_id: u32) -> bool {
+        self.agents
+            .iter()
+            .find(|state| state.agent.id == agent_id)
+            .and_then(|state| self.roles.get(&state.agent.role_id))
+            .map(|role| role.law_enforcement)
+            .unwrap_or(false)
+    }
+
+    pub fn law_agents_at(&self, location_id: LocationId) -> u32 {
+        self.agents
+            .iter()
+            .filter(|state| state.current_location == location_id)
+            .filter(|state| {
+                self.roles
+                    .get(&state.agent.role_id)
+                    .map(|role| role.law_enforcement)
+                    .unwrap_or(false)
+            })
+            .count() as u32
+    }
+
+    /// Each agent's last ambush tick, for saving alongside `setting`.
+    pub fn ambush_state(&self, setting: AmbushSetting) -> AmbushState {
+        AmbushState {
+            setting,
+            last_ambush_ticks: self
+                .agents
+                .iter()
+                .filter_map(|state| state.last_ambush_tick.map(|tick| (state.agent.id, tick)))
+                .collect(),
+        }
+    }
+
+    /// Puts saved ambush cooldowns back on the agents they belong to. Agents
+    /// missing from the save keep no cooldown.
+    pub fn restore_ambush_cooldowns(&mut self, ambush: &AmbushState) {
+        for state in &mut self.agents {
+            state.last_ambush_tick = ambush.last_ambush_ticks.get(&state.agent.id).copied();
+        }
+    }
+}
+
+/// Picks a hostile agent at `location_id` to jump the player when exposure is
+/// past the setting's threshold, and puts that agent on ambush cooldown.
+pub fn trigger_ambush(
+    registry: &mut AgentRegistry,
+    location_id: LocationId,
+    exposure: u8,
+    setting: AmbushSetting,
+    tick: u64,
+) -> Option<Agent> {
+    let threshold = setting.exposure_threshold()?;
+    if exposure < threshold {
+        return None;
+    }
+    let roles = &registry.roles;
+    let state = registry.agents.iter_mut().find(|state| {
+        state.current_location == location_id
+            && roles
+                .get(&state.agent.role_id)
+                .map(|role| role.hostile)
+                .unwrap_or(false)
+            && state
+                .last_ambush_tick
+                .map(|last| tick.saturating_sub(last) >= AMBUSH_COOLDOWN_TICKS)
+                .unwrap_or(true)
+    })?;
+    state.last_ambush_tick = Some(tick);
+    Some(state.agent.clone())
+}
+
 pub fn tick_agents_system(
     mut registry: ResMut<AgentRegistry>,
     city: Res<CityState>,
@@ -1,22 +1,23 @@
 use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::rules::signature::SignatureType;
 use crate::simulation::city::LocationId;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CaseStatus {
     Active,
     Resolved,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CaseTargetType {
     UnknownMasked,
     KnownMasked,
     CivilianLink,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Case {
     pub case_id: u32,
     pub faction_id: String,
@@ -28,9 +29,65 @@ pub struct Case {
     pub status: CaseStatus,
     pub milestone: u8,
     pub pressure_actions: Vec<String>,
+    /// Decoy persona this case is chasing; None follows the real trail.
+    #[serde(default)]
+    pub suspect_persona: Option<String>,
+    /// The player has intel on this case's progress and matched signatures.
+    #[serde(default)]
+    pub revealed: bool,
+}
+
+/// Location heat at or above which a heat-locked case stays open.
+pub const HEAT_LOCK_THRESHOLD: i32 = 40;
+
+/// Why an active case has not resolved yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseHold {
+    Progress { progress: u32 },
+    HeatLocked { heat: i32, threshold: i32 },
 }
 
-#[derive(Resource, Debug, Default, Clone)]
+impl std::fmt::Display for CaseHold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CaseHold::Progress { progress } => write!(f, "progress {}/100", progress),
+            CaseHold::HeatLocked { heat, threshold } => write!(
+                f,
+                "heat-locked until location heat drops below {} (now {})",
+                threshold, heat
+            ),
+        }
+    }
+}
+
+impl Case {
+    /// Heat-locked cases slow heat decay at their location and cannot resolve
+    /// while that location stays hot.
+    pub fn is_heat_locked(&self) -> bool {
+        self.heat_lock && self.status == CaseStatus::Active
+    }
+
+    /// What keeps an active case from resolving, given its location's heat.
+    pub fn resolution_hold(&self, location_heat: i32) -> Option<CaseHold> {
+        if self.status != CaseStatus::Active {
+            return None;
+        }
+        if self.progress < 100 {
+            return Some(CaseHold::Progress {
+                progress: self.progress,
+            });
+        }
+        if self.is_heat_locked() && location_heat >= HEAT_LOCK_THRESHOLD {
+            return Some(CaseHold::HeatLocked {
+                heat: location_heat,
+                threshold: HEAT_LOCK_THRESHOLD,
+            });
+        }
+        None
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
 pub struct CaseRegistry {
     pub cases: Vec<Case>,
     next_id: u32,
@@ -40,6 +97,11 @@ pub struct CaseRegistry {
 pub struct CaseEventLog(pub Vec<String>);
 
 impl CaseRegistry {
+    /// Drops every case but keeps the id counter, so ids stay unique for the run.
+    pub fn clear_cases(&mut self) {
+        self.cases.clear();
+    }
+
     pub fn create_case(
         &mut self,
         faction_id: String,
@@ -60,10 +122,16 @@ impl CaseRegistry {
             status: CaseStatus::Active,
             milestone: 0,
             pressure_actions: Vec::new(),
+            suspect_persona: None,
+            revealed: false,
         });
         case_id
     }
 
+    pub fn case_by_id_mut(&mut self, case_id: u32) -> Option<&mut Case> {
+        self.cases.iter_mut().find(|case| case.case_id == case_id)
+    }
+
     pub fn find_case_mut(
         &mut self,
         faction_id: &str,
@@ -82,9 +150,16 @@ impl CaseRegistry {
         })
     }
 
+    pub fn active_case_count(&self, faction_id: &str) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| case.faction_id == faction_id && case.status == CaseStatus::Active)
+            .count()
+    }
+
     pub fn any_heat_lock(&self, location_id: LocationId) -> bool {
         self.cases.iter().any(|case| {
-            case.location_id == location_id && case.status == CaseStatus::Active && case.heat_lock
+            case.location_id == location_id && case.is_heat_locked()
         })
     }
 
@@ -83,6 +83,7 @@ pub enum PromotionReason {
     MediaRecognition,
     Recurrence,
     Manual,
+    Captured,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -139,6 +140,47 @@ impl PromotionCandidate {
     }
 }
 
+const CAPTURED_FIRST_NAMES: [&str; 8] = [
+    "Danny", "Rosa", "Marcus", "Tess", "Vic", "Lena", "Eddie", "Nadia",
+];
+const CAPTURED_LAST_NAMES: [&str; 8] = [
+    "Kowalski", "Moreno", "Hale", "Brandt", "Okafor", "Lindqvist", "Reyes", "Park",
+];
+
+impl PromotionCandidate {
+    /// A generated opponent taken alive, given a stable name so later events can
+    /// refer back to them.
+    pub fn captured(scope_id: String, seed: u64) -> Self {
+        let first = CAPTURED_FIRST_NAMES[(seed % CAPTURED_FIRST_NAMES.len() as u64) as usize];
+        let last = CAPTURED_LAST_NAMES
+            [((seed >> 8) % CAPTURED_LAST_NAMES.len() as u64) as usize];
+        Self {
+            scope_id,
+            first_name: first.to_string(),
+            last_name: last.to_string(),
+            role_type: "OPPONENT".to_string(),
+            faction_id: None,
+            rank: None,
+            persona_kind: Some("CIVILIAN".to_string()),
+            persona_label: Some("Civilian".to_string()),
+            reason: PromotionReason::Captured,
+        }
+    }
+}
+
+/// Ends the character's current roles at `current_tick` and opens an
+/// INCARCERATED role; a later escape can close it again.
+pub fn mark_incarcerated(character: &mut PersistentCharacter, current_tick: u64) {
+    if has_active_role(character, "INCARCERATED") {
+        return;
+    }
+    mark_character_status(character, "INCARCERATED", current_tick);
+}
+
+pub fn is_incarcerated(character: &PersistentCharacter) -> bool {
+    has_active_role(character, "INCARCERATED")
+}
+
 pub fn current_year_from_day(day: u32) -> i32 {
     let years = day.saturating_sub(1) / DAYS_PER_YEAR;
     DEFAULT_START_YEAR + years as i32
@@ -4,7 +4,9 @@ use bevy_ecs::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::components::world::Position;
+use crate::rules::expression::{EnvironmentEffect, EnvironmentField};
 use crate::simulation::region::{ContinentId, CountryId, RegionId};
+use crate::simulation::time::Weather;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct CityId(pub u32);
@@ -28,6 +30,38 @@ pub enum HeatResponse {
     FactionAttention,
 }
 
+/// Heat cutoffs at which a location escalates to each response level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeatResponseConfig {
+    pub patrol: i32,
+    pub investigation: i32,
+    pub faction_attention: i32,
+}
+
+impl Default for HeatResponseConfig {
+    fn default() -> Self {
+        Self {
+            patrol: 30,
+            investigation: 50,
+            faction_attention: 70,
+        }
+    }
+}
+
+impl HeatResponseConfig {
+    pub fn response_for(&self, heat: i32) -> HeatResponse {
+        if heat >= self.faction_attention {
+            HeatResponse::FactionAttention
+        } else if heat >= self.investigation {
+            HeatResponse::Investigation
+        } else if heat >= self.patrol {
+            HeatResponse::PolicePatrol
+        } else {
+            HeatResponse::None
+        }
+    }
+}
+
 #[derive(Resource, Debug, Default)]
 pub struct CityEventLog(pub Vec<CityEvent>);
 
@@ -41,12 +75,26 @@ pub struct CityEvent {
 #[derive(Debug, Clone)]
 pub enum CityEventKind {
     HeatResponseChanged { response: HeatResponse },
+    /// Police pressure pushed a gang's hold on the location down a band.
+    TurfEroded { faction_id: String, influence: u16 },
+    /// A gang's hold blunted a patrol that walked onto its turf.
+    PatrolSuppressed { faction_id: String, influence: u16 },
+    /// An expression changed a location field directly.
+    EnvironmentAltered {
+        field: EnvironmentField,
+        delta: i32,
+        value: i32,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LocationState {
     pub id: LocationId,
     pub tags: Vec<LocationTag>,
+    /// Tags this build does not recognise, kept verbatim so a round-trip
+    /// through an older build does not drop them.
+    #[serde(default)]
+    pub raw_tags: Vec<String>,
     pub heat: i32,
     pub crime_pressure: i32,
     pub police_presence: i32,
@@ -55,11 +103,106 @@ pub struct LocationState {
     pub police_units: u8,
     pub investigators: u8,
     pub gang_units: u8,
+    #[serde(default = "default_location_economy")]
+    pub economy: i32,
+    /// How well the locals remember recent sightings; repeat activity here
+    /// yields sharper identity evidence.
+    #[serde(default)]
+    pub witness_memory: i32,
+    /// Whether the player knows this location exists. Undiscovered locations
+    /// still simulate; they are just hidden from the player's views.
+    #[serde(default = "default_discovered")]
+    pub discovered: bool,
     pub faction_influence: HashMap<String, u16>,
     pub response: HeatResponse,
 }
 
-#[derive(Resource, Debug, Clone)]
+pub const DEFAULT_LOCATION_ECONOMY: i32 = 50;
+pub const WITNESS_MEMORY_MAX: i32 = 240;
+const WITNESS_MEMORY_PER_SIGHTING: i32 = 24;
+const WITNESS_MEMORY_RECOGNIZED: i32 = 120;
+/// A neighbor picks up this fraction of the crime-pressure gap each tick.
+const CONTAGION_CRIME_DIVISOR: i32 = 8;
+/// A neighbor tightens surveillance by this fraction of the heat gap each tick.
+const CONTAGION_SURVEILLANCE_DIVISOR: i32 = 10;
+/// Most any one location can gain per field from all its neighbors in a tick.
+const CONTAGION_MAX_STEP: i32 = 3;
+
+fn default_discovered() -> bool {
+    true
+}
+
+fn default_location_economy() -> i32 {
+    DEFAULT_LOCATION_ECONOMY
+}
+
+impl LocationState {
+    /// Visual quality bonus from regulars who have seen the suspect here before.
+    pub fn witness_familiarity(&self) -> i32 {
+        self.witness_memory.clamp(0, WITNESS_MEMORY_MAX) / 8
+    }
+
+    /// True once repeat sightings are enough for locals to recognize a masked regular.
+    pub fn witnesses_recognize(&self) -> bool {
+        self.witness_memory >= WITNESS_MEMORY_RECOGNIZED
+    }
+
+    pub fn remember_sighting(&mut self) {
+        self.witness_memory =
+            (self.witness_memory + WITNESS_MEMORY_PER_SIGHTING).min(WITNESS_MEMORY_MAX);
+    }
+
+    pub fn fade_witness_memory(&mut self) {
+        self.witness_memory = (self.witness_memory - 1).max(0);
+    }
+
+    /// Applies an expression's direct change, clamped to 0-100. Returns the
+    /// change actually made.
+    pub fn apply_environment_effect(&mut self, effect: EnvironmentEffect) -> i32 {
+        let slot = match effect.field {
+            EnvironmentField::Heat => &mut self.heat,
+            EnvironmentField::CrimePressure => &mut self.crime_pressure,
+            EnvironmentField::PolicePresence => &mut self.police_presence,
+            EnvironmentField::SurveillanceLevel => &mut self.surveillance_level,
+            EnvironmentField::LockdownLevel => &mut self.lockdown_level,
+            EnvironmentField::Economy => &mut self.economy,
+        };
+        let before = *slot;
+        *slot = (before + effect.delta).clamp(0, 100);
+        *slot - before
+    }
+
+    pub fn environment_value(&self, field: EnvironmentField) -> i32 {
+        match field {
+            EnvironmentField::Heat => self.heat,
+            EnvironmentField::CrimePressure => self.crime_pressure,
+            EnvironmentField::PolicePresence => self.police_presence,
+            EnvironmentField::SurveillanceLevel => self.surveillance_level,
+            EnvironmentField::LockdownLevel => self.lockdown_level,
+            EnvironmentField::Economy => self.economy,
+        }
+    }
+
+    /// Rough share of bystanders around at this hour and in this weather, 0-100.
+    pub fn population_density(&self, is_day: bool, weather: Weather) -> i32 {
+        let base = self
+            .tags
+            .iter()
+            .map(|tag| match tag {
+                LocationTag::Public => 70,
+                LocationTag::Residential => 45,
+                LocationTag::Industrial => 20,
+                LocationTag::HighSecurity => 15,
+            })
+            .max()
+            .unwrap_or(30);
+        let density = base + (self.economy - DEFAULT_LOCATION_ECONOMY) / 5;
+        let density = if is_day { density } else { density / 2 };
+        (density * weather.crowd_percent() / 100).clamp(0, 100)
+    }
+}
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
 pub struct CityState {
     pub city_id: CityId,
     pub region_id: RegionId,
@@ -67,6 +210,16 @@ pub struct CityState {
     pub continent_id: ContinentId,
     pub locations: HashMap<LocationId, LocationState>,
     pub active_location: LocationId,
+    pub heat_response: HeatResponseConfig,
+    /// Locations each location borders; a location missing here is isolated.
+    #[serde(default)]
+    pub adjacency: HashMap<LocationId, Vec<LocationId>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TravelError {
+    UnknownLocation,
+    Undiscovered,
 }
 
 impl CityState {
@@ -78,6 +231,127 @@ impl CityState {
             (false, false) => LocationId(4),
         }
     }
+
+    /// A position inside the given location, the inverse of `location_for_position`.
+    pub fn position_for_location(&self, location_id: LocationId) -> Option<Position> {
+        match location_id.0 {
+            1 => Some(Position { x: 0, y: 0 }),
+            2 => Some(Position { x: -1, y: 0 }),
+            3 => Some(Position { x: 0, y: -1 }),
+            4 => Some(Position { x: -1, y: -1 }),
+            _ => None,
+        }
+    }
+
+    pub fn is_discovered(&self, location_id: LocationId) -> bool {
+        self.locations
+            .get(&location_id)
+            .map(|location| location.discovered)
+            .unwrap_or(false)
+    }
+
+    pub fn discovered_locations(&self) -> Vec<LocationId> {
+        let mut ids: Vec<LocationId> = self
+            .locations
+            .values()
+            .filter(|location| location.discovered)
+            .map(|location| location.id)
+            .collect();
+        ids.sort_by_key(|id| id.0);
+        ids
+    }
+
+    pub fn next_undiscovered(&self) -> Option<LocationId> {
+        self.locations
+            .values()
+            .filter(|location| !location.discovered)
+            .map(|location| location.id)
+            .min_by_key(|id| id.0)
+    }
+
+    /// Marks a location as known; returns true if it was newly discovered.
+    pub fn discover(&mut self, location_id: LocationId) -> bool {
+        match self.locations.get_mut(&location_id) {
+            Some(location) if !location.discovered => {
+                location.discovered = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Bleeds pressure from hot locations into cooler neighbors. Crime pressure
+    /// and surveillance only ever move toward the hotter side's level, and by
+    /// at most a few points a tick, so spreading cannot feed on itself.
+    pub fn spread_pressure(&mut self) {
+        // Gain per neighbor, and the hottest source level it may rise to.
+        let mut crime_gain: HashMap<LocationId, (i32, i32)> = HashMap::new();
+        let mut surveillance_gain: HashMap<LocationId, (i32, i32)> = HashMap::new();
+        for (source_id, neighbors) in &self.adjacency {
+            let Some(source) = self.locations.get(source_id) else {
+                continue;
+            };
+            for neighbor_id in neighbors {
+                let Some(neighbor) = self.locations.get(neighbor_id) else {
+                    continue;
+                };
+                let crime_gap = source.crime_pressure - neighbor.crime_pressure;
+                if crime_gap > 0 {
+                    let entry = crime_gain.entry(*neighbor_id).or_default();
+                    entry.0 += crime_gap / CONTAGION_CRIME_DIVISOR;
+                    entry.1 = entry.1.max(source.crime_pressure);
+                }
+                let heat_gap = source.heat - neighbor.heat;
+                if heat_gap > 0 && neighbor.surveillance_level < source.heat {
+                    let entry = surveillance_gain.entry(*neighbor_id).or_default();
+                    entry.0 += heat_gap / CONTAGION_SURVEILLANCE_DIVISOR;
+                    entry.1 = entry.1.max(source.heat);
+                }
+            }
+        }
+        for (location_id, (gain, ceiling)) in crime_gain {
+            if let Some(location) = self.locations.get_mut(&location_id) {
+                location.crime_pressure = (location.crime_pressure + gain.min(CONTAGION_MAX_STEP))
+                    .min(ceiling)
+                    .clamp(0, 100);
+            }
+        }
+        for (location_id, (gain, ceiling)) in surveillance_gain {
+            if let Some(location) = self.locations.get_mut(&location_id) {
+                location.surveillance_level = (location.surveillance_level
+                    + gain.min(CONTAGION_MAX_STEP))
+                .min(ceiling)
+                .clamp(0, 100);
+            }
+        }
+    }
+
+    /// Moves the player to a location they already know about.
+    pub fn travel_to(&mut self, location_id: LocationId) -> Result<(), TravelError> {
+        let Some(location) = self.locations.get(&location_id) else {
+            return Err(TravelError::UnknownLocation);
+        };
+        if !location.discovered {
+            return Err(TravelError::Undiscovered);
+        }
+        self.active_location = location_id;
+        Ok(())
+    }
+}
+
+impl CityState {
+    /// A fresh city under `city_id` in the same region: default locations, no
+    /// local heat, and the same heat response curve.
+    pub fn relocated(&self, city_id: CityId) -> CityState {
+        CityState {
+            city_id,
+            region_id: self.region_id,
+            country_id: self.country_id,
+            continent_id: self.continent_id,
+            heat_response: self.heat_response,
+            ..CityState::default()
+        }
+    }
 }
 
 impl Default for CityState {
@@ -89,6 +363,7 @@ impl Default for CityState {
             LocationState {
                 id: LocationId(1),
                 tags: vec![LocationTag::Public],
+                raw_tags: Vec::new(),
                 heat: 0,
                 crime_pressure: 8,
                 police_presence: 20,
@@ -97,6 +372,9 @@ impl Default for CityState {
                 police_units: 1,
                 investigators: 0,
                 gang_units: 0,
+                economy: 55,
+                witness_memory: 0,
+                discovered: true,
                 faction_influence: HashMap::new(),
                 response: HeatResponse::None,
             },
@@ -107,6 +385,7 @@ impl Default for CityState {
             LocationState {
                 id: LocationId(2),
                 tags: vec![LocationTag::Residential],
+                raw_tags: Vec::new(),
                 heat: 0,
                 crime_pressure: 4,
                 police_presence: 15,
@@ -115,6 +394,9 @@ impl Default for CityState {
                 police_units: 1,
                 investigators: 0,
                 gang_units: 0,
+                economy: 50,
+                witness_memory: 0,
+                discovered: true,
                 faction_influence: HashMap::new(),
                 response: HeatResponse::None,
             },
@@ -125,6 +407,7 @@ impl Default for CityState {
             LocationState {
                 id: LocationId(3),
                 tags: vec![LocationTag::Industrial],
+                raw_tags: Vec::new(),
                 heat: 0,
                 crime_pressure: 10,
                 police_presence: 10,
@@ -133,6 +416,9 @@ impl Default for CityState {
                 police_units: 0,
                 investigators: 0,
                 gang_units: 1,
+                economy: 40,
+                witness_memory: 0,
+                discovered: false,
                 faction_influence: HashMap::new(),
                 response: HeatResponse::None,
             },
@@ -143,6 +429,7 @@ impl Default for CityState {
             LocationState {
                 id: LocationId(4),
                 tags: vec![LocationTag::HighSecurity],
+                raw_tags: Vec::new(),
                 heat: 0,
                 crime_pressure: 12,
                 police_presence: 35,
@@ -151,6 +438,9 @@ impl Default for CityState {
                 police_units: 2,
                 investigators: 0,
                 gang_units: 0,
+                economy: 65,
+                witness_memory: 0,
+                discovered: false,
                 faction_influence: HashMap::new(),
                 response: HeatResponse::None,
             },
@@ -163,6 +453,111 @@ impl Default for CityState {
             continent_id: ContinentId(1),
             locations,
             active_location: LocationId(1),
+            heat_response: HeatResponseConfig::default(),
+            adjacency: default_adjacency(),
+        }
+    }
+}
+
+/// The default city's four quarters, each bordering the two it shares an edge with.
+pub fn default_adjacency() -> HashMap<LocationId, Vec<LocationId>> {
+    [(1, [2, 3]), (2, [1, 4]), (3, [1, 4]), (4, [2, 3])]
+        .into_iter()
+        .map(|(id, neighbors)| (LocationId(id), neighbors.map(LocationId).to_vec()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggressive_curve_escalates_earlier() {
+        let aggressive = HeatResponseConfig {
+            patrol: 15,
+            investigation: 35,
+            faction_attention: 55,
+        };
+        assert_eq!(aggressive.response_for(40), HeatResponse::Investigation);
+        assert_eq!(
+            HeatResponseConfig::default().response_for(40),
+            HeatResponse::PolicePatrol
+        );
+    }
+
+    #[test]
+    fn bad_weather_thins_the_crowd() {
+        let city = CityState::default();
+        let location = &city.locations[&LocationId(1)];
+        let clear = location.population_density(true, Weather::Clear);
+        assert!(clear > 0);
+        assert!(location.population_density(true, Weather::Rain) < clear);
+        assert!(
+            location.population_density(true, Weather::Storm)
+                < location.population_density(true, Weather::Rain)
+        );
+        assert!(location.population_density(false, Weather::Clear) < clear);
+    }
+
+    #[test]
+    fn repeat_sightings_build_witness_memory() {
+        let mut city = CityState::default();
+        let location = city.locations.get_mut(&LocationId(1)).unwrap();
+        assert_eq!(location.witness_familiarity(), 0);
+        for _ in 0..5 {
+            location.remember_sighting();
+        }
+        assert!(location.witnesses_recognize());
+        assert_eq!(location.witness_familiarity(), 15);
+        location.fade_witness_memory();
+        assert!(!location.witnesses_recognize());
+    }
+
+    #[test]
+    fn travel_needs_discovery() {
+        let mut city = CityState::default();
+        assert_eq!(city.discovered_locations(), vec![LocationId(1), LocationId(2)]);
+        assert_eq!(city.travel_to(LocationId(3)), Err(TravelError::Undiscovered));
+        assert_eq!(city.travel_to(LocationId(9)), Err(TravelError::UnknownLocation));
+
+        assert_eq!(city.next_undiscovered(), Some(LocationId(3)));
+        assert!(city.discover(LocationId(3)));
+        assert!(!city.discover(LocationId(3)));
+        assert_eq!(city.travel_to(LocationId(3)), Ok(()));
+        assert_eq!(city.active_location, LocationId(3));
+    }
+
+    #[test]
+    fn pressure_bleeds_into_neighbors_but_not_isolated_locations() {
+        let mut city = CityState::default();
+        city.adjacency.remove(&LocationId(4));
+        for neighbors in city.adjacency.values_mut() {
+            neighbors.retain(|id| *id != LocationId(4));
+        }
+        for location in city.locations.values_mut() {
+            location.heat = 0;
+            location.crime_pressure = 10;
+            location.surveillance_level = 10;
+        }
+        let hot = city.locations.get_mut(&LocationId(1)).unwrap();
+        hot.heat = 90;
+        hot.crime_pressure = 90;
+        let isolated_before = city.locations[&LocationId(4)].clone();
+
+        city.spread_pressure();
+        let neighbor = &city.locations[&LocationId(2)];
+        assert_eq!(neighbor.crime_pressure, 10 + CONTAGION_MAX_STEP);
+        assert_eq!(neighbor.surveillance_level, 10 + CONTAGION_MAX_STEP);
+        let isolated = &city.locations[&LocationId(4)];
+        assert_eq!(isolated.crime_pressure, isolated_before.crime_pressure);
+        assert_eq!(isolated.surveillance_level, isolated_before.surveillance_level);
+
+        for _ in 0..200 {
+            city.spread_pressure();
         }
+        let neighbor = &city.locations[&LocationId(2)];
+        assert!(neighbor.crime_pressure < 90);
+        assert!(neighbor.surveillance_level <= 90);
+        assert_eq!(city.locations[&LocationId(1)].crime_pressure, 90);
     }
 }
@@ -1,7 +1,15 @@
 use bevy_ecs::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-
+use std::collections::{BTreeMap, HashMap};
+
+use crate::data::civilian_events::CivilianStorylet;
+use crate::rules::signature::{SignatureInstance, SignatureSpec, SignatureType};
+use crate::rules::use_power::ActorState;
+use crate::simulation::case::CaseRegistry;
+use crate::simulation::city::{CityState, LocationId};
+use crate::simulation::stats::RunStats;
+use crate::simulation::combat::CombatConsequence;
+use crate::simulation::crafting::GadgetUnlock;
 use crate::simulation::economy::{
     clamp_liquidity, default_liquidity_for_tier, lifestyle_upkeep, EconomyTickResult, Wealth,
     WealthProfile, WealthTier,
@@ -28,6 +36,9 @@ pub struct CivilianState {
     pub pending_death: Option<DeathRecord>,
     #[serde(default)]
     pub legacy: Vec<LegacyRecord>,
+    /// Set once a death is recorded to the legacy; only a new life clears it.
+    #[serde(default)]
+    pub life_ended: bool,
     pub finances: CivilianFinances,
     #[serde(default)]
     pub housing: HousingState,
@@ -62,6 +73,53 @@ pub struct CivilianState {
     pub last_health_day: u32,
     pub last_economy_day: u32,
     pub last_job_offer_day: u32,
+    #[serde(default)]
+    pub last_contact_day: u32,
+    #[serde(default)]
+    pub last_profile_switch_day: Option<u32>,
+    #[serde(default)]
+    pub last_restock_day: u32,
+    /// Portable skills that survive job changes, 0-100 each.
+    #[serde(default)]
+    pub skills: BTreeMap<CivilianSkill, i32>,
+    /// Skill trained by committed hobby sessions.
+    #[serde(default)]
+    pub hobby_skill: CivilianSkill,
+    #[serde(default)]
+    pub gadget_unlocks: Vec<GadgetUnlock>,
+    #[serde(default)]
+    pub last_media_event_day: u32,
+    /// Last day a public fight keeps the press interested in the player.
+    #[serde(default)]
+    pub spotlight_until_day: u32,
+    /// Persona-side consequences of media choices, drained by the caller.
+    #[serde(default)]
+    pub media_fallout: MediaFallout,
+    #[serde(default)]
+    pub last_introduction_day: u32,
+    /// Introductions made through close contacts, drained by the caller.
+    #[serde(default)]
+    pub network_log: Vec<String>,
+    /// First work day of the current run of job stability below the floor.
+    #[serde(default)]
+    pub low_job_stability_since: Option<u32>,
+    #[serde(default)]
+    pub job_warning_day: Option<u32>,
+    #[serde(default)]
+    pub job_loss_day: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaFallout {
+    pub public_suspicion: i32,
+    pub wanted_level: i32,
+    pub persona_hints: u32,
+}
+
+impl MediaFallout {
+    pub fn is_empty(&self) -> bool {
+        *self == MediaFallout::default()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -145,6 +203,36 @@ pub enum JobRole {
     Farmer,
 }
 
+/// When the civilian's work block falls in the day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WorkShift {
+    #[default]
+    Day,
+    Night,
+    /// Alternates day and night weeks.
+    Rotating,
+}
+
+impl WorkShift {
+    /// Usual shift for a role; emergency services cover the night.
+    pub fn for_role(role: JobRole) -> Self {
+        match role {
+            JobRole::Nurse | JobRole::PoliceOfficer | JobRole::Paramedic => WorkShift::Night,
+            JobRole::Firefighter | JobRole::Pilot => WorkShift::Rotating,
+            _ => WorkShift::Day,
+        }
+    }
+
+    /// The shift actually worked on `day`, resolving rotation by week.
+    pub fn on_day(self, day: u32) -> Self {
+        match self {
+            WorkShift::Rotating if (day / 7) % 2 == 1 => WorkShift::Night,
+            WorkShift::Rotating => WorkShift::Day,
+            shift => shift,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LifeStage {
     Child,
@@ -169,7 +257,7 @@ pub struct LifeState {
     pub mutation_ready: bool,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum EducationLevel {
     None,
     Primary,
@@ -190,6 +278,35 @@ pub struct EducationTrack {
     pub last_school_day: u32,
 }
 
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum CivilianSkill {
+    Investigation,
+    Chemistry,
+    #[default]
+    Engineering,
+    Streetwise,
+}
+
+impl CivilianSkill {
+    pub const ALL: [CivilianSkill; 4] = [
+        CivilianSkill::Investigation,
+        CivilianSkill::Chemistry,
+        CivilianSkill::Engineering,
+        CivilianSkill::Streetwise,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CivilianSkill::Investigation => "investigation",
+            CivilianSkill::Chemistry => "chemistry",
+            CivilianSkill::Engineering => "engineering",
+            CivilianSkill::Streetwise => "streetwise",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RoutineActivity {
     Work,
@@ -256,12 +373,163 @@ impl LifeState {
     }
 }
 
+/// Starting conditions for a civilian life. Unset job status and education
+/// follow from the starting age.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CivilianStart {
+    pub age_years: u32,
+    pub role: JobRole,
+    pub job_level: i32,
+    pub job_status: Option<JobStatus>,
+    pub education: Option<EducationLevel>,
+}
+
+impl Default for CivilianStart {
+    fn default() -> Self {
+        Self {
+            age_years: DEFAULT_START_AGE,
+            role: JobRole::Journalist,
+            job_level: 1,
+            job_status: None,
+            education: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CivilianStartError {
+    TooYoungToWork { age_years: u32 },
+    EducationTooAdvanced { age_years: u32, level: EducationLevel },
+    MissingQualification { role: JobRole, required: EducationLevel },
+    InvalidJobLevel { level: i32, max: i32 },
+}
+
+impl std::fmt::Display for CivilianStartError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CivilianStartError::TooYoungToWork { age_years } => {
+                write!(f, "a {}-year-old can't hold a job", age_years)
+            }
+            CivilianStartError::EducationTooAdvanced { age_years, level } => {
+                write!(f, "a {}-year-old can't have reached {:?} education", age_years, level)
+            }
+            CivilianStartError::MissingQualification { role, required } => {
+                write!(f, "{:?} requires {:?} education", role, required)
+            }
+            CivilianStartError::InvalidJobLevel { level, max } => {
+                write!(f, "job level {} is outside 1-{}", level, max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CivilianStartError {}
+
+impl CivilianStart {
+    /// Education track for the start: the age's track, or a lower level the
+    /// civilian has already left.
+    fn education_track(&self) -> Result<EducationTrack, CivilianStartError> {
+        let mut track = EducationTrack::for_age(self.age_years);
+        if let Some(level) = self.education {
+            if level > track.level {
+                return Err(CivilianStartError::EducationTooAdvanced {
+                    age_years: self.age_years,
+                    level,
+                });
+            }
+            if level < track.level {
+                track.level = level;
+                track.is_enrolled = false;
+            }
+        }
+        Ok(track)
+    }
+
+    fn resolved_job_status(&self, education: &EducationTrack) -> JobStatus {
+        if let Some(status) = self.job_status {
+            return status;
+        }
+        if self.age_years < MIN_WORKING_AGE
+            || life_stage_for_age(self.age_years) == LifeStage::Elder
+        {
+            JobStatus::Unemployed
+        } else if education.is_enrolled {
+            JobStatus::PartTime
+        } else {
+            JobStatus::Employed
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), CivilianStartError> {
+        let education = self.education_track()?;
+        let status = self.resolved_job_status(&education);
+        if matches!(status, JobStatus::Unemployed) {
+            return Ok(());
+        }
+        if self.age_years < MIN_WORKING_AGE {
+            return Err(CivilianStartError::TooYoungToWork {
+                age_years: self.age_years,
+            });
+        }
+        let max = if matches!(status, JobStatus::PartTime) {
+            PART_TIME_LEVEL_CAP
+        } else {
+            MAX_JOB_LEVEL
+        };
+        if !(1..=max).contains(&self.job_level) {
+            return Err(CivilianStartError::InvalidJobLevel {
+                level: self.job_level,
+                max,
+            });
+        }
+        let required = required_education(self.role);
+        // Students may work in the field they are still qualifying for.
+        let qualifying = education.is_enrolled && matches!(status, JobStatus::PartTime);
+        if education.level < required && !qualifying {
+            return Err(CivilianStartError::MissingQualification {
+                role: self.role,
+                required,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn required_education(role: JobRole) -> EducationLevel {
+    match role {
+        JobRole::Doctor
+        | JobRole::Dentist
+        | JobRole::Lawyer
+        | JobRole::Pharmacist
+        | JobRole::Architect
+        | JobRole::Scientist => EducationLevel::Graduate,
+        JobRole::Engineer
+        | JobRole::Nurse
+        | JobRole::Teacher
+        | JobRole::Analyst
+        | JobRole::SoftwareDeveloper
+        | JobRole::Accountant
+        | JobRole::SocialWorker
+        | JobRole::Pilot => EducationLevel::Tertiary,
+        _ => EducationLevel::None,
+    }
+}
+
 impl Default for LifeState {
     fn default() -> Self {
         LifeState::new(DEFAULT_START_AGE, 1)
     }
 }
 
+impl HousingState {
+    pub fn home_location(&self) -> LocationId {
+        let location_id = neighborhood_profile(&self.neighborhood_id)
+            .map(|profile| profile.location_id)
+            .unwrap_or(NEIGHBORHOOD_CATALOG[0].location_id);
+        LocationId(location_id)
+    }
+}
+
 impl Default for HousingState {
     fn default() -> Self {
         let profile = neighborhood_profile("midtown").unwrap_or(&NEIGHBORHOOD_CATALOG[0]);
@@ -393,6 +661,8 @@ pub struct CivilianJob {
     pub level: i32,
     pub satisfaction: i32,
     pub stability: i32,
+    #[serde(default)]
+    pub shift: WorkShift,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -417,6 +687,7 @@ pub struct HousingState {
 struct NeighborhoodProfile {
     id: &'static str,
     label: &'static str,
+    location_id: u32,
     rent: i32,
     stability: i32,
     safety: i32,
@@ -427,6 +698,7 @@ const NEIGHBORHOOD_CATALOG: [NeighborhoodProfile; 4] = [
     NeighborhoodProfile {
         id: "midtown",
         label: "Midtown",
+        location_id: 2,
         rent: 80,
         stability: 62,
         safety: 55,
@@ -435,6 +707,7 @@ const NEIGHBORHOOD_CATALOG: [NeighborhoodProfile; 4] = [
     NeighborhoodProfile {
         id: "uptown",
         label: "Uptown",
+        location_id: 4,
         rent: 120,
         stability: 75,
         safety: 78,
@@ -443,6 +716,7 @@ const NEIGHBORHOOD_CATALOG: [NeighborhoodProfile; 4] = [
     NeighborhoodProfile {
         id: "edge",
         label: "Edge District",
+        location_id: 3,
         rent: 60,
         stability: 55,
         safety: 45,
@@ -451,6 +725,7 @@ const NEIGHBORHOOD_CATALOG: [NeighborhoodProfile; 4] = [
     NeighborhoodProfile {
         id: "harbor",
         label: "Harbor Ward",
+        location_id: 1,
         rent: 70,
         stability: 58,
         safety: 50,
@@ -525,12 +800,37 @@ pub enum CivilianTier {
     PowerBroker,
 }
 
+impl CivilianTier {
+    /// Contacts the civilian can keep up with before the weakest drift away.
+    pub fn contact_cap(self) -> usize {
+        match self {
+            CivilianTier::Local => 15,
+            CivilianTier::Connected => 25,
+            CivilianTier::Influential => 40,
+            CivilianTier::PowerBroker => 60,
+        }
+    }
+}
+
 impl Default for CivilianTier {
     fn default() -> Self {
         CivilianTier::Local
     }
 }
 
+impl ContactDomain {
+    /// Circles one step away: work shades into press and community, the
+    /// street into community and press.
+    pub fn adjacent(self) -> [ContactDomain; 2] {
+        match self {
+            ContactDomain::Professional => [ContactDomain::Community, ContactDomain::Media],
+            ContactDomain::Community => [ContactDomain::Professional, ContactDomain::Underground],
+            ContactDomain::Media => [ContactDomain::Professional, ContactDomain::Underground],
+            ContactDomain::Underground => [ContactDomain::Community, ContactDomain::Media],
+        }
+    }
+}
+
 impl Default for ContactDomain {
     fn default() -> Self {
         ContactDomain::Community
@@ -573,6 +873,15 @@ pub struct Contact {
     pub influence: i32,
     #[serde(default)]
     pub last_interaction_day: u32,
+    /// Favors called in from this contact that have not been paid back yet.
+    #[serde(default)]
+    pub favor_debt: i32,
+}
+
+impl Contact {
+    pub fn days_since_interaction(&self, day: u32) -> u32 {
+        day.saturating_sub(self.last_interaction_day)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -594,6 +903,7 @@ pub enum CivilianEventCategory {
     Opportunity,
     Crime,
     Routine,
+    Media,
     Other,
 }
 
@@ -658,35 +968,144 @@ const PART_TIME_LEVEL_CAP: i32 = 4;
 const INCOME_BOOST_CR: i64 = 25;
 const JOB_OFFER_COOLDOWN_DAYS: u32 = 14;
 const JOB_OFFER_UNEMPLOYED_COOLDOWN_DAYS: u32 = 7;
+pub const JOB_OFFER_EVENT: &str = "civilian_job_offer";
+pub const FULL_TIME_OFFER_EVENT: &str = "civilian_full_time_offer";
+pub const JOB_WARNING_EVENT: &str = "civilian_job_warning";
+const FULL_TIME_OFFER_MIN_SATISFACTION: i32 = 60;
+const FULL_TIME_OFFER_MIN_STABILITY: i32 = 60;
+const FULL_TIME_OFFER_CHANCE: u64 = 40;
+/// Job stability below this puts the civilian on notice.
+const JOB_STABILITY_FLOOR: i32 = 30;
+/// Promotion cooldowns spent under the floor before a warning, then a firing.
+const JOB_WARNING_COOLDOWNS: u32 = 1;
+const JOB_LOSS_COOLDOWNS: u32 = 3;
+const JOB_LOSS_SHOCK_DAYS: u32 = 7;
+const JOB_LOSS_TEMPORAL_SHOCK: f32 = 12.0;
+const JOB_LOSS_RESOURCE_SHOCK: f32 = 20.0;
 const DAYS_PER_YEAR: u32 = 336;
+pub const CRIME_QUICK_HIT_EVENT: &str = "civilian_crime_quick_hit";
+const CRIME_TAKE_CHOICE: &str = "take";
+const CRIME_PAYOUT_CR: i32 = 40;
+const CRIME_INJURED_PAYOUT_CR: i32 = 15;
+const CRIME_ARREST_FINE_CR: i32 = 60;
 const DEFAULT_START_AGE: u32 = 16;
+const MIN_WORKING_AGE: u32 = 14;
+const WEALTH_PROFILE_SWITCH_COOLDOWN_DAYS: u32 = 14;
+const VIGILANTE_LIQUIDATION_FEE_PCT: i64 = 10;
+const CORPORATE_RESTRUCTURING_FEE_CR: i32 = 150;
+const BALANCED_UNWIND_FEE_CR: i32 = 40;
+const CONTACT_ACQUISITION_COOLDOWN_DAYS: u32 = 3;
+const FAVOR_DEBT_REPAY_DAYS: u32 = 7;
+const FAVOR_STRAIN_BOND_LOSS: i32 = 8;
+pub const SKILL_POINTS_PER_LEVEL: i32 = 20;
+const SCHOOL_SKILL_GAIN: i32 = 1;
+const STREETWISE_PER_LEVEL_CRIME_BONUS: i32 = 2;
+const CONTACT_ACQUISITION_MAX_CHANCE: u64 = 35;
+const INTRODUCTION_COOLDOWN_DAYS: u32 = 5;
+const INTRODUCTION_MAX_CHANCE: u64 = 45;
+const OVER_CAP_BOND_DRIFT: i32 = 10;
+/// Days a contact can go unseen before the bond starts to fade.
+const CONTACT_NEGLECT_GRACE_DAYS: u32 = 14;
+/// Bond lost per decay step once a contact is neglected.
+const CONTACT_DECAY_RATE: i32 = 1;
+pub const RIVAL_SABOTAGE_EVENT: &str = "civilian_rival_sabotage";
+const RIVAL_SABOTAGE_MIN_INFLUENCE: i32 = 60;
+const RIVAL_SABOTAGE_CHANCE: u64 = 10;
+/// Underground standing at which investigations are an open book to the player.
+pub const UNDERGROUND_CASE_INSIGHT: i32 = 60;
+pub const MEDIA_INTERVIEW_EVENT: &str = "civilian_media_interview";
+pub const MEDIA_EXPOSE_EVENT: &str = "civilian_media_expose";
+pub const MEDIA_PUFF_PIECE_EVENT: &str = "civilian_media_puff_piece";
+pub const MARKET_SWING_EVENT: &str = "civilian_market_swing";
+pub const DEBT_COLLECTOR_EVENT: &str = "civilian_debt_collector";
+/// Interest compounded onto outstanding debt each economy tick.
+const DEBT_INTEREST_RATE: f32 = 0.005;
+/// Debt worth this many days of wages sends collectors calling.
+const DEBT_COLLECTOR_WAGE_DAYS: i32 = 10;
+const DEBT_COLLECTOR_MIN_CR: i32 = 300;
+const DEBT_COLLECTOR_RESOURCE_PRESSURE: f32 = 15.0;
+/// Debt past this forces an eviction to a cheaper neighborhood.
+const DEBT_EVICTION_CAP_CR: i32 = 2_500;
+const EVICTION_RELOCATION_COOLDOWN: i32 = 28;
+const EVICTION_STRAIN: i32 = 8;
+const MEDIA_EVENT_COOLDOWN_DAYS: u32 = 5;
+const MEDIA_INTERVIEW_REPUTATION: i32 = 40;
+const MEDIA_PUFF_PIECE_REPUTATION: i32 = 60;
+const MEDIA_SPOTLIGHT_THRESHOLD: u8 = 40;
+const MEDIA_SPOTLIGHT_DAYS: u32 = 3;
+/// Combat stress below this leaves the civilian body untouched.
+const COMBAT_INJURY_STRESS_THRESHOLD: i32 = 60;
+const PROFESSIONAL_CONTACT_NAMES: [&str; 6] = [
+    "Dana Whitlock",
+    "Omar Vance",
+    "Priya Castell",
+    "Hugo Lindqvist",
+    "Marta Okafor",
+    "Leon Strand",
+];
+const COMMUNITY_CONTACT_NAMES: [&str; 6] = [
+    "Rosa Delgado",
+    "Tom Kettering",
+    "Amara Nwosu",
+    "Father Alvey",
+    "Jun Park",
+    "Beth Marlow",
+];
+const MEDIA_CONTACT_NAMES: [&str; 6] = [
+    "Cass Moreno",
+    "Felix Hart",
+    "Nadia Quill",
+    "Reggie Blume",
+    "Ivy Sato",
+    "Grant Ellery",
+];
+const UNDERGROUND_CONTACT_NAMES: [&str; 6] = [
+    "Silas Crane",
+    "Mags Doyle",
+    "Vic Torres",
+    "Lena Shroud",
+    "Eddie Fence",
+    "Kit Marrow",
+];
 
 impl Default for CivilianState {
     fn default() -> Self {
+        Self::build(&CivilianStart::default())
+    }
+}
+
+impl CivilianState {
+    /// Fresh civilian life from validated starting conditions.
+    pub fn from_start(start: &CivilianStart) -> Result<Self, CivilianStartError> {
+        start.validate()?;
+        Ok(Self::build(start))
+    }
+
+    fn build(start: &CivilianStart) -> Self {
         let cash = 120;
-        let life = LifeState::new(DEFAULT_START_AGE, 1);
-        let education = EducationTrack::for_age(life.age_years);
-        let job_status = if education.is_enrolled {
-            JobStatus::PartTime
-        } else {
-            JobStatus::Employed
-        };
+        let life = LifeState::new(start.age_years, 1);
+        let education = start
+            .education_track()
+            .unwrap_or_else(|_| EducationTrack::for_age(life.age_years));
+        let job_status = start.resolved_job_status(&education);
         let housing = HousingState::default();
-        let wage = career_wage(JobRole::Journalist, 1, job_status);
+        let wage = career_wage(start.role, start.job_level, job_status);
         let mut wealth = Wealth::new(cash as i64);
         wealth.income_per_tick = wage as i64;
         wealth.upkeep_per_tick = lifestyle_upkeep(wealth.tier);
         wealth.liquidity = clamp_liquidity(wealth.liquidity);
-        let routine = build_routine_schedule(job_status, &education, &life);
+        let shift = WorkShift::for_role(start.role);
+        let routine = build_routine_schedule(job_status, shift.on_day(1), &education, &life);
         Self {
             life,
             mutant_profile: MutantProfile::default(),
             job_status,
             job: CivilianJob {
-                role: JobRole::Journalist,
-                level: 1,
+                role: start.role,
+                level: start.job_level.max(1),
                 satisfaction: 52,
                 stability: 48,
+                shift,
             },
             routine,
             education,
@@ -694,6 +1113,7 @@ impl Default for CivilianState {
             mistake_risk: 0,
             pending_death: None,
             legacy: Vec::new(),
+            life_ended: false,
             finances: CivilianFinances {
                 cash,
                 debt: 0,
@@ -734,6 +1154,20 @@ impl Default for CivilianState {
             last_health_day: 0,
             last_economy_day: 0,
             last_job_offer_day: 0,
+            last_contact_day: 0,
+            last_profile_switch_day: None,
+            last_restock_day: 0,
+            skills: BTreeMap::new(),
+            hobby_skill: CivilianSkill::default(),
+            gadget_unlocks: Vec::new(),
+            last_media_event_day: 0,
+            spotlight_until_day: 0,
+            media_fallout: MediaFallout::default(),
+            last_introduction_day: 0,
+            network_log: Vec::new(),
+            low_job_stability_since: None,
+            job_warning_day: None,
+            job_loss_day: None,
         }
     }
 }
@@ -743,6 +1177,55 @@ impl CivilianState {
         self.rewards.combined(&self.network_rewards)
     }
 
+    pub fn skill(&self, skill: CivilianSkill) -> i32 {
+        self.skills.get(&skill).copied().unwrap_or(0)
+    }
+
+    /// Skill level in steps of `SKILL_POINTS_PER_LEVEL`, 0-5.
+    pub fn skill_level(&self, skill: CivilianSkill) -> i32 {
+        self.skill(skill) / SKILL_POINTS_PER_LEVEL
+    }
+
+    /// Engineering level, applied to gadget crafting.
+    pub fn crafting_bonus(&self) -> i32 {
+        self.skill_level(CivilianSkill::Engineering)
+    }
+
+    pub fn has_safehouse(&self) -> bool {
+        self.effective_rewards().safehouse > 0
+    }
+
+    /// Most any supply pool can hold: tech access sets the base, a safehouse
+    /// adds storage.
+    pub fn supply_cap(&self) -> i64 {
+        let storage = if self.has_safehouse() {
+            SAFEHOUSE_SUPPLY_STORAGE
+        } else {
+            0
+        };
+        2 + (self.tech_access_score() / 12) as i64 + storage
+    }
+
+    /// Percent of the gap to cap refilled each day.
+    fn supply_restock_rate(&self) -> i64 {
+        let base = match self.wealth_profile {
+            WealthProfile::Vigilante => 25,
+            WealthProfile::Balanced => 50,
+            WealthProfile::Corporate => 100,
+        };
+        let safehouse = if self.has_safehouse() { 25 } else { 0 };
+        (base + safehouse).min(100)
+    }
+
+    pub fn lose_cash(&mut self, amount: i32) -> i32 {
+        let paid = amount.clamp(0, self.finances.cash.max(0));
+        if paid > 0 {
+            self.finances.cash -= paid;
+            sync_wealth_from_finances(self);
+        }
+        paid
+    }
+
     pub fn net_worth_cr(&self) -> i64 {
         self.wealth.net_worth(self.finances.debt as i64)
     }
@@ -762,6 +1245,15 @@ impl CivilianState {
         self.event_history.insert(event_id.to_string(), day);
     }
 
+    /// Debt at which collectors start calling, scaled to what the job pays.
+    pub fn debt_collector_threshold(&self) -> i32 {
+        (self.finances.wage.max(0) * DEBT_COLLECTOR_WAGE_DAYS).max(DEBT_COLLECTOR_MIN_CR)
+    }
+
+    pub fn debt_in_collections(&self) -> bool {
+        self.finances.debt >= self.debt_collector_threshold()
+    }
+
     pub fn pressure_targets(&self) -> CivilianPressure {
         let rewards = self.effective_rewards();
         let base_time = match self.job_status {
@@ -789,6 +1281,12 @@ impl CivilianState {
         if self.housing.stability < 50 {
             temporal += (50 - self.housing.stability) as f32 * 0.4;
         }
+        let reeling_from_job_loss = self
+            .job_loss_day
+            .is_some_and(|day| self.last_day < day.saturating_add(JOB_LOSS_SHOCK_DAYS));
+        if reeling_from_job_loss {
+            temporal += JOB_LOSS_TEMPORAL_SHOCK;
+        }
         temporal = temporal.clamp(0.0, 100.0);
 
         let mut resource = (self.finances.debt.max(0) as f32 * 0.8).clamp(0.0, 60.0);
@@ -798,6 +1296,12 @@ impl CivilianState {
         if self.finances.rent_due_in <= 2 {
             resource += 12.0;
         }
+        if self.debt_in_collections() {
+            resource += DEBT_COLLECTOR_RESOURCE_PRESSURE;
+        }
+        if reeling_from_job_loss {
+            resource += JOB_LOSS_RESOURCE_SHOCK;
+        }
         if rewards.income_boost > 0 {
             resource -= rewards.income_boost as f32 * 0.6;
         }
@@ -902,8 +1406,9 @@ impl CivilianState {
         alignment_label: &str,
         reason: &str,
         day: u32,
+        stats: &RunStats,
     ) -> LegacyRecord {
-        let achievements = legacy_achievements(self);
+        let achievements = legacy_achievements(self, stats);
         let record = LegacyRecord {
             day,
             age_years: self.life.age_years,
@@ -917,11 +1422,14 @@ impl CivilianState {
             death_reason: reason.to_string(),
         };
         self.legacy.push(record.clone());
+        self.life_ended = true;
         record
     }
 }
 
-pub fn tick_civilian_life(state: &mut CivilianState, time: &GameTime) {
+/// Advances the civilian's day. Every random draw is keyed on `world_seed` and
+/// the day, so a save replays the same contacts, offers and setbacks.
+pub fn tick_civilian_life(state: &mut CivilianState, time: &GameTime, world_seed: u64) {
     if time.day != state.last_day {
         state.last_day = time.day;
         update_age_and_life_stage(state, time);
@@ -939,35 +1447,42 @@ pub fn tick_civilian_life(state: &mut CivilianState, time: &GameTime) {
             queue_event(state, "civilian_crime_quick_hit", time.tick);
         }
         update_housing_state(state, time);
-        update_social_web(state);
+        try_acquire_contact(state, time.day, world_seed);
+        update_social_web(state, time, world_seed);
         update_civilian_tier(state);
         update_network_rewards(state);
+        repay_favor_debts(state, time.day);
         update_mistake_risk(state);
-        if should_queue_job_offer(state, time.day) {
-            queue_event(state, "civilian_job_offer", time.tick);
+        if let Some(event_id) = choose_job_offer_event(state, time.day, world_seed) {
+            queue_event(state, event_id, time.tick);
             state.last_job_offer_day = time.day;
         }
+        if let Some(event_id) = choose_media_event(state, time.day) {
+            queue_event(state, event_id, time.tick);
+            state.last_media_event_day = time.day;
+        }
     }
 
-    let activity = state.routine.activity_at(time.hour);
+    let activity = state.routine.activity_at(time.clock_hour());
     apply_routine_activity(state, activity, time);
     check_for_death(state, time.day);
 
     if let Some(start_hour) = state.routine.first_hour_for(RoutineActivity::Work) {
         if matches!(state.job_status, JobStatus::Employed | JobStatus::PartTime)
-            && time.hour == start_hour
+            && time.reached_hour(start_hour)
             && state.last_work_day != time.day
         {
             state.last_work_day = time.day;
             queue_event(state, "civilian_work_shift", time.tick);
             record_work_shift(state);
+            review_job_performance(state, time);
             apply_career_progression(state, time.day);
         }
     }
 
     if state.education.is_enrolled {
         if let Some(start_hour) = state.routine.first_hour_for(RoutineActivity::School) {
-            if time.hour == start_hour && state.education.last_school_day != time.day {
+            if time.reached_hour(start_hour) && state.education.last_school_day != time.day {
                 queue_event(state, "civilian_school_day", time.tick);
                 state.education.last_school_day = time.day;
                 record_school_session(state);
@@ -976,7 +1491,7 @@ pub fn tick_civilian_life(state: &mut CivilianState, time: &GameTime) {
     }
 
     if let Some(start_hour) = state.routine.first_hour_for(RoutineActivity::Hobby) {
-        if time.hour == start_hour && state.last_hobby_day != time.day {
+        if time.reached_hour(start_hour) && state.last_hobby_day != time.day {
             state.last_hobby_day = time.day;
             queue_event(state, "civilian_hobby_session", time.tick);
         }
@@ -986,17 +1501,18 @@ pub fn tick_civilian_life(state: &mut CivilianState, time: &GameTime) {
         .routine
         .first_hour_for(RoutineActivity::Social)
         .unwrap_or(19);
-    if time.hour == social_hour && state.last_relationship_day != time.day {
+    if time.reached_hour(social_hour) && state.last_relationship_day != time.day {
         state.last_relationship_day = time.day;
         if let Some((event_id, contact_name)) = choose_relationship_event(state) {
             queue_event_with_contact(state, event_id, time.tick, contact_name);
         } else if state.social.support < 60 || state.social.strain > 15 {
             queue_event(state, "civilian_relationship_checkin", time.tick);
         }
+        try_introduction(state, time.day, world_seed);
     }
 
     if (state.health.stress >= 65 || state.health.sleep_debt >= 16)
-        && time.hour == 20
+        && time.reached_hour(20)
         && state.last_health_day != time.day
     {
         state.last_health_day = time.day;
@@ -1007,6 +1523,7 @@ pub fn tick_civilian_life(state: &mut CivilianState, time: &GameTime) {
 pub fn tick_civilian_economy(
     state: &mut CivilianState,
     time: &GameTime,
+    world_seed: u64,
 ) -> Option<EconomyTickResult> {
     if time.day == state.last_economy_day {
         return None;
@@ -1014,6 +1531,16 @@ pub fn tick_civilian_economy(
     state.last_economy_day = time.day;
     refresh_wealth_tier(state);
     update_wealth_profile(state);
+    state.wealth.upkeep_per_tick += time.season().climate_expense(state.wealth.upkeep_per_tick);
+    accrue_debt_interest(state);
+    apply_debt_consequences(state, time);
+    let market_seed = daily_seed(world_seed, MARKET_SWING_EVENT, time.day);
+    let investment = state
+        .wealth
+        .apply_investment_return(state.wealth_profile, market_seed);
+    if investment.market_swing && state.wealth.investments_cr > 0 {
+        queue_event(state, MARKET_SWING_EVENT, time.tick);
+    }
     let result = state.wealth.apply_tick(state.finances.debt as i64);
     sync_finances_from_wealth(state);
     Some(result)
@@ -1123,6 +1650,16 @@ pub fn apply_civilian_effects(state: &mut CivilianState, effects: &[String]) ->
                 );
                 wealth_changed = true;
             }
+            "investments" => {
+                apply_delta_at_i64(
+                    &mut state.wealth.investments_cr,
+                    parts.get(1),
+                    &mut applied,
+                    "investments",
+                );
+                state.wealth.investments_cr = state.wealth.investments_cr.max(0);
+                wealth_changed = true;
+            }
             "liquidity" => {
                 apply_delta_at_f32(
                     &mut state.wealth.liquidity,
@@ -1362,8 +1899,17 @@ pub fn apply_civilian_effects(state: &mut CivilianState, effects: &[String]) ->
             "job_role" => {
                 if let Some(role) = parts.get(1).and_then(|value| parse_job_role(value.trim())) {
                     state.job.role = role;
+                    state.job.shift = WorkShift::for_role(role);
                     applied.push(format!("job role -> {:?}", role));
                     career_changed = true;
+                    routine_changed = true;
+                }
+            }
+            "job_shift" => {
+                if let Some(shift) = parts.get(1).and_then(|value| parse_work_shift(value.trim())) {
+                    state.job.shift = shift;
+                    applied.push(format!("job shift -> {:?}", shift));
+                    routine_changed = true;
                 }
             }
             "job_level" => {
@@ -1394,6 +1940,49 @@ pub fn apply_civilian_effects(state: &mut CivilianState, effects: &[String]) ->
                 );
                 state.job.stability = clamp_metric(state.job.stability);
             }
+            "skill" => {
+                let skill = match parts.get(1).map(|value| value.trim()) {
+                    Some("hobby") => Some(state.hobby_skill),
+                    Some(value) => parse_civilian_skill(value),
+                    None => None,
+                };
+                let delta = parts.get(2).and_then(|value| value.trim().parse::<i32>().ok());
+                if let (Some(skill), Some(delta)) = (skill, delta) {
+                    applied.push(format!("skill {} {:+}", skill.label(), delta));
+                    if let Some(level) = train_skill(state, skill, delta) {
+                        applied.push(format!("{} reached level {}", skill.label(), level));
+                    }
+                }
+            }
+            "public_suspicion" => {
+                apply_delta_at(
+                    &mut state.media_fallout.public_suspicion,
+                    parts.get(1),
+                    &mut applied,
+                    "public_suspicion",
+                );
+            }
+            "wanted" => {
+                apply_delta_at(
+                    &mut state.media_fallout.wanted_level,
+                    parts.get(1),
+                    &mut applied,
+                    "wanted",
+                );
+            }
+            "persona_hint" => {
+                let chance = parts
+                    .get(1)
+                    .and_then(|value| value.trim().parse::<u64>().ok())
+                    .unwrap_or(100);
+                let mut rng = hash_seed(effect)
+                    ^ state.last_day as u64
+                    ^ ((state.reputation.media as u64) << 8);
+                if next_u64(&mut rng) % 100 < chance {
+                    state.media_fallout.persona_hints += 1;
+                    applied.push("persona hint leaked".to_string());
+                }
+            }
             "career_xp" => {
                 apply_delta_at(
                     &mut state.career_xp,
@@ -1591,6 +2180,43 @@ fn apply_career_progression(state: &mut CivilianState, day: u32) {
     sync_career_compensation(state);
 }
 
+/// Stability left under the floor for a promotion cooldown earns a warning;
+/// still there after several more and the civilian is let go.
+fn review_job_performance(state: &mut CivilianState, time: &GameTime) {
+    if matches!(state.job_status, JobStatus::Unemployed) {
+        return;
+    }
+    if state.job.stability >= JOB_STABILITY_FLOOR {
+        state.low_job_stability_since = None;
+        state.job_warning_day = None;
+        return;
+    }
+    let since = *state.low_job_stability_since.get_or_insert(time.day);
+    let days_below = time.day.saturating_sub(since);
+    if state.job_warning_day.is_none() {
+        if days_below >= PROMOTION_COOLDOWN_DAYS * JOB_WARNING_COOLDOWNS {
+            state.job_warning_day = Some(time.day);
+            queue_event(state, JOB_WARNING_EVENT, time.tick);
+        }
+        return;
+    }
+    if days_below >= PROMOTION_COOLDOWN_DAYS * JOB_LOSS_COOLDOWNS {
+        lose_job(state, time.day);
+    }
+}
+
+fn lose_job(state: &mut CivilianState, day: u32) {
+    state.job_status = JobStatus::Unemployed;
+    state.low_job_stability_since = None;
+    state.job_warning_day = None;
+    state.job_loss_day = Some(day);
+    state.job.satisfaction = clamp_metric(state.job.satisfaction - 10);
+    state.reputation.career = clamp_metric(state.reputation.career - 5);
+    state.health.stress = clamp_metric(state.health.stress + 8);
+    sync_career_compensation(state);
+    update_routine_schedule(state);
+}
+
 fn apply_promotion_rewards(state: &mut CivilianState) {
     state.rewards.income_boost = clamp_metric(state.rewards.income_boost + 1);
     if state.job.level >= 3 {
@@ -1604,15 +2230,20 @@ fn apply_promotion_rewards(state: &mut CivilianState) {
     }
 }
 
-fn update_social_web(state: &mut CivilianState) {
+fn update_social_web(state: &mut CivilianState, time: &GameTime, world_seed: u64) {
+    drift_excess_contacts(state);
     let bond_delta = daily_bond_delta(state);
     let reputation = state.reputation.clone();
     let job = state.job.clone();
     let social = state.social.clone();
     let finances = state.finances.clone();
     for contact in &mut state.contacts {
-        if bond_delta != 0 {
-            contact.bond = clamp_metric(contact.bond + bond_delta);
+        let mut delta = bond_delta;
+        if neglect_decays_today(contact, time.day) {
+            delta -= CONTACT_DECAY_RATE;
+        }
+        if delta != 0 {
+            contact.bond = clamp_metric(contact.bond + delta);
             contact.level = relationship_level_from_bond(contact.bond);
         }
         let target = contact_influence_target(contact, &reputation, &job, &social, &finances);
@@ -1622,9 +2253,79 @@ fn update_social_web(state: &mut CivilianState) {
             contact.influence = clamp_metric(contact.influence - 1);
         }
     }
+    if let Some(rival) = choose_rival_saboteur(state, time.day, world_seed) {
+        queue_event_with_contact(state, RIVAL_SABOTAGE_EVENT, time.tick, Some(rival));
+    }
     rebuild_social_web(state);
 }
 
+/// Neglected bonds fade one step at a time, and the stronger the bond the
+/// longer between steps: an Ally drifts far slower than an Acquaintance.
+fn neglect_decays_today(contact: &Contact, day: u32) -> bool {
+    let neglected = contact.days_since_interaction(day);
+    if neglected <= CONTACT_NEGLECT_GRACE_DAYS {
+        return false;
+    }
+    let bond = contact.bond.max(0) as u32;
+    let interval = 1 + bond * bond / 1_000;
+    (neglected - CONTACT_NEGLECT_GRACE_DAYS).is_multiple_of(interval)
+}
+
+/// An influential rival now and then moves against the civilian.
+fn choose_rival_saboteur(state: &CivilianState, day: u32, world_seed: u64) -> Option<String> {
+    let rival = state
+        .contacts
+        .iter()
+        .filter(|contact| {
+            contact.relation_type == RelationType::Rival
+                && contact.influence >= RIVAL_SABOTAGE_MIN_INFLUENCE
+        })
+        .max_by_key(|contact| contact.influence)?;
+    let mut rng = daily_seed(world_seed, RIVAL_SABOTAGE_EVENT, day) ^ hash_seed(&rival.name);
+    ((next_u64(&mut rng) >> 33) % 100 < RIVAL_SABOTAGE_CHANCE).then(|| rival.name.clone())
+}
+
+/// Family, mentors and partners are never let go of just for lack of time.
+fn is_pinned_contact(contact: &Contact) -> bool {
+    matches!(
+        contact.relation_type,
+        RelationType::Family | RelationType::Mentor | RelationType::Romance
+    )
+}
+
+/// Past the tier's contact cap, the weakest unpinned bonds fade each day and
+/// any that have faded to strangers are dropped.
+fn drift_excess_contacts(state: &mut CivilianState) {
+    let cap = state.civilian_tier.contact_cap();
+    let excess = state.contacts.len().saturating_sub(cap);
+    if excess == 0 {
+        return;
+    }
+    let mut weakest: Vec<usize> = (0..state.contacts.len())
+        .filter(|idx| !is_pinned_contact(&state.contacts[*idx]))
+        .collect();
+    weakest.sort_by_key(|idx| (state.contacts[*idx].bond, *idx));
+    weakest.truncate(excess);
+
+    let mut faded = Vec::new();
+    for idx in weakest {
+        let contact = &mut state.contacts[idx];
+        if contact.level == RelationshipLevel::Stranger {
+            faded.push(idx);
+            continue;
+        }
+        contact.bond = clamp_metric(contact.bond - OVER_CAP_BOND_DRIFT);
+        contact.level = relationship_level_from_bond(contact.bond);
+    }
+    faded.sort_unstable();
+    for idx in faded.into_iter().rev() {
+        let contact = state.contacts.remove(idx);
+        state
+            .network_log
+            .push(format!("You have lost touch with {}.", contact.name));
+    }
+}
+
 fn rebuild_social_web(state: &mut CivilianState) {
     let mut professional = 0;
     let mut community = 0;
@@ -1820,8 +2521,8 @@ fn choose_relationship_event(state: &CivilianState) -> Option<(&'static str, Opt
     None
 }
 
-fn legacy_achievements(state: &CivilianState) -> Vec<String> {
-    let mut out = Vec::new();
+fn legacy_achievements(state: &CivilianState, stats: &RunStats) -> Vec<String> {
+    let mut out = stats.achievements();
     if state.job.level >= 4 {
         out.push(format!("Career level {}", state.job.level));
     }
@@ -1851,33 +2552,727 @@ fn legacy_achievements(state: &CivilianState) -> Vec<String> {
     out
 }
 
-fn should_queue_crime_opportunity(state: &CivilianState) -> bool {
-    if state.life.age_years < 16 {
-        return false;
-    }
-    if state.finances.rent_due_in > 1 {
-        return false;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrimeOutcome {
+    Clean,
+    Injured,
+    Arrested,
+}
+
+#[derive(Debug, Clone)]
+pub struct CrimeAttempt {
+    pub outcome: CrimeOutcome,
+    pub location_id: LocationId,
+    pub success_chance: i32,
+    pub cash_gained: i32,
+    pub fine_paid: i32,
+    pub signatures: Vec<SignatureInstance>,
+    pub wanted_delta: i32,
+    pub civilian_suspicion_delta: i32,
+    pub opens_case: bool,
+}
+
+/// Chance out of 100 that a quick hit goes clean. Sloppy habits and a watched
+/// neighbourhood both cut into it.
+pub fn crime_success_chance(state: &CivilianState, city: &CityState) -> i32 {
+    let location = city.locations.get(&city.active_location);
+    let surveillance = location.map(|loc| loc.surveillance_level).unwrap_or(0);
+    let police = location.map(|loc| loc.police_presence).unwrap_or(0);
+    let streetwise = state.skill_level(CivilianSkill::Streetwise) * STREETWISE_PER_LEVEL_CRIME_BONUS;
+    let chance = 85 - state.mistake_risk * 3 / 5 - surveillance / 5 - police / 10 + streetwise;
+    chance.clamp(10, 90)
+}
+
+pub const SUPPLY_POOLS: [&str; 4] = ["resource", "ammo", "gadgets", "chemicals"];
+const SUPPLY_UNIT_COST_CR: i32 = 4;
+const SAFEHOUSE_SUPPLY_STORAGE: i64 = 3;
+
+/// Refills the actor's supply pools once per day, paying for each unit from
+/// cash. Pools stop where the money runs out.
+pub fn restock_supplies(state: &mut CivilianState, actor: &mut ActorState, day: u32) -> Vec<String> {
+    let mut restocked = Vec::new();
+    if day == state.last_restock_day {
+        return restocked;
+    }
+    state.last_restock_day = day;
+    let cap = state.supply_cap();
+    let rate = state.supply_restock_rate();
+    for pool in SUPPLY_POOLS {
+        let current = actor.resources.get(pool).copied().unwrap_or(0);
+        let gap = (cap - current).max(0);
+        if gap == 0 {
+            continue;
+        }
+        let wanted = ((gap * rate + 99) / 100).max(1);
+        let affordable = (state.finances.cash.max(0) / SUPPLY_UNIT_COST_CR) as i64;
+        let units = wanted.min(affordable);
+        if units == 0 {
+            continue;
+        }
+        let paid = state.lose_cash(units as i32 * SUPPLY_UNIT_COST_CR);
+        actor
+            .resources
+            .insert(pool.to_string(), current + units);
+        restocked.push(format!("{} +{} ({} CR)", pool, units, paid));
     }
-    state.finances.cash < state.housing.rent
+    restocked
 }
 
-fn should_queue_job_offer(state: &CivilianState, day: u32) -> bool {
-    let cooldown = if matches!(state.job_status, JobStatus::Unemployed) {
-        JOB_OFFER_UNEMPLOYED_COOLDOWN_DAYS
-    } else {
-        JOB_OFFER_COOLDOWN_DAYS
-    };
-    if day < state.last_job_offer_day.saturating_add(cooldown) {
-        return false;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FavorKind {
+    Intel,
+    HeatRelief,
+    JobReferral,
+}
+
+impl FavorKind {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "intel" => Some(FavorKind::Intel),
+            "heat" => Some(FavorKind::HeatRelief),
+            "job" | "referral" => Some(FavorKind::JobReferral),
+            _ => None,
+        }
     }
-    if matches!(state.job_status, JobStatus::Unemployed) {
-        return true;
+
+    /// Minimum (influence, bond) a contact needs before they can do this favor.
+    fn requirements(self) -> (i32, i32) {
+        match self {
+            FavorKind::Intel => (40, 20),
+            FavorKind::HeatRelief => (55, 35),
+            FavorKind::JobReferral => (35, 30),
+        }
+    }
+
+    fn allows_domain(self, domain: ContactDomain) -> bool {
+        match self {
+            FavorKind::Intel => matches!(domain, ContactDomain::Media | ContactDomain::Underground),
+            FavorKind::HeatRelief => {
+                matches!(domain, ContactDomain::Community | ContactDomain::Underground)
+            }
+            FavorKind::JobReferral => domain == ContactDomain::Professional,
+        }
     }
-    state.job.satisfaction < 55 || state.job.stability < 45
 }
 
-fn refresh_wealth_tier(state: &mut CivilianState) {
-    state.wealth.refresh_tier(state.finances.debt as i64);
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FavorError {
+    UnknownContact,
+    NoFavorsLeft,
+    NotInfluential { need: i32, have: i32 },
+    BondTooWeak { need: i32, have: i32 },
+    WrongCircle(ContactDomain),
+}
+
+impl CivilianState {
+    pub fn outstanding_favor_debt(&self) -> i32 {
+        self.contacts.iter().map(|contact| contact.favor_debt).sum()
+    }
+
+    /// Favors the network can still be asked for before debts are repaid.
+    pub fn available_favors(&self) -> i32 {
+        (self.effective_rewards().favors - self.outstanding_favor_debt()).max(0)
+    }
+
+    /// Strong underground ties hear how every investigation is going.
+    pub fn sees_case_details(&self) -> bool {
+        self.social_web.underground >= UNDERGROUND_CASE_INSIGHT
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntelError {
+    UnknownCase,
+    CaseClosed,
+    NoSource,
+    Favor(FavorError),
+}
+
+/// Reveals an active case's progress and matched signatures. Underground ties
+/// past the insight threshold do it for free; otherwise the most influential
+/// contact who can dig calls in an intel favor.
+pub fn spend_intel(
+    state: &mut CivilianState,
+    cases: &mut CaseRegistry,
+    case_id: u32,
+    tick: u64,
+) -> Result<Vec<String>, IntelError> {
+    let case = cases.case_by_id_mut(case_id).ok_or(IntelError::UnknownCase)?;
+    if case.status != crate::simulation::case::CaseStatus::Active {
+        return Err(IntelError::CaseClosed);
+    }
+    if case.revealed || state.sees_case_details() {
+        case.revealed = true;
+        return Ok(vec!["the street already talks about this one".to_string()]);
+    }
+
+    let (min_influence, min_bond) = FavorKind::Intel.requirements();
+    let source = state
+        .contacts
+        .iter()
+        .filter(|contact| {
+            FavorKind::Intel.allows_domain(contact.domain)
+                && contact.influence >= min_influence
+                && contact.bond >= min_bond
+        })
+        .max_by_key(|contact| contact.influence)
+        .map(|contact| contact.name.clone())
+        .ok_or(IntelError::NoSource)?;
+    let mut applied =
+        spend_favor(state, &source, FavorKind::Intel, tick).map_err(IntelError::Favor)?;
+    case.revealed = true;
+    applied.insert(0, format!("{} digs into case {}", source, case_id));
+    Ok(applied)
+}
+
+/// Calls in a favor from one contact. Intel and heat relief are returned for the
+/// caller to apply to cases and the city; a job referral queues a job offer
+/// here. Each favor adds debt with that contact, and asking past what the bond
+/// will bear strains the relationship.
+pub fn spend_favor(
+    state: &mut CivilianState,
+    contact_name: &str,
+    kind: FavorKind,
+    tick: u64,
+) -> Result<Vec<String>, FavorError> {
+    let available = state.available_favors();
+    let contact = state
+        .contacts
+        .iter()
+        .find(|contact| contact.name.eq_ignore_ascii_case(contact_name))
+        .ok_or(FavorError::UnknownContact)?;
+    if !kind.allows_domain(contact.domain) {
+        return Err(FavorError::WrongCircle(contact.domain));
+    }
+    let (min_influence, min_bond) = kind.requirements();
+    if contact.influence < min_influence {
+        return Err(FavorError::NotInfluential {
+            need: min_influence,
+            have: contact.influence,
+        });
+    }
+    if contact.bond < min_bond {
+        return Err(FavorError::BondTooWeak {
+            need: min_bond,
+            have: contact.bond,
+        });
+    }
+    if available <= 0 {
+        return Err(FavorError::NoFavorsLeft);
+    }
+
+    let day = state.last_day;
+    let mut applied = Vec::new();
+    let contact = state
+        .contacts
+        .iter_mut()
+        .find(|contact| contact.name.eq_ignore_ascii_case(contact_name))
+        .expect("contact checked above");
+    contact.favor_debt += 1;
+    contact.last_interaction_day = day;
+    let name = contact.name.clone();
+    applied.push(format!(
+        "you owe {} a favor ({} outstanding)",
+        name, contact.favor_debt
+    ));
+    if contact.favor_debt > contact.bond / 25 {
+        contact.bond = clamp_metric(contact.bond - FAVOR_STRAIN_BOND_LOSS);
+        contact.level = relationship_level_from_bond(contact.bond);
+        state.social.strain = clamp_metric(state.social.strain + 3);
+        applied.push(format!(
+            "{} feels used (bond -{}, strain +3)",
+            name, FAVOR_STRAIN_BOND_LOSS
+        ));
+    }
+    if kind == FavorKind::JobReferral {
+        state.career_xp = (state.career_xp + 10).min(CAREER_XP_CAP);
+        queue_event(state, JOB_OFFER_EVENT, tick);
+        state.last_job_offer_day = day;
+        applied.push("career xp +10, job offer queued".to_string());
+    }
+    Ok(applied)
+}
+
+/// Contacts forgive one outstanding favor a week.
+fn repay_favor_debts(state: &mut CivilianState, day: u32) {
+    if day == 0 || !day.is_multiple_of(FAVOR_DEBT_REPAY_DAYS) {
+        return;
+    }
+    for contact in state.contacts.iter_mut() {
+        contact.favor_debt = (contact.favor_debt - 1).max(0);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WealthProfileSwitchError {
+    AlreadyActive,
+    Cooldown { days_left: u32 },
+}
+
+/// Switches the wealth profile as a deliberate move: each switch carries a
+/// one-time transition cost and locks further switches for a cooldown.
+pub fn set_wealth_profile(
+    state: &mut CivilianState,
+    profile: WealthProfile,
+) -> Result<Vec<String>, WealthProfileSwitchError> {
+    if state.wealth_profile == profile {
+        return Err(WealthProfileSwitchError::AlreadyActive);
+    }
+    if let Some(last_day) = state.last_profile_switch_day {
+        let ready_day = last_day.saturating_add(WEALTH_PROFILE_SWITCH_COOLDOWN_DAYS);
+        if state.last_day < ready_day {
+            return Err(WealthProfileSwitchError::Cooldown {
+                days_left: ready_day - state.last_day,
+            });
+        }
+    }
+
+    let mut applied = Vec::new();
+    let fee = match profile {
+        WealthProfile::Vigilante => {
+            // Liquidating holdings frees cash for gear but burns career ties.
+            let proceeds = std::mem::take(&mut state.wealth.investments_cr);
+            if proceeds > 0 {
+                state.wealth.current_cr = state.wealth.current_cr.saturating_add(proceeds);
+                sync_finances_from_wealth(state);
+                refresh_wealth_tier(state);
+                applied.push(format!("cash +{} (liquidated investments)", proceeds));
+            }
+            state.reputation.career = clamp_metric(state.reputation.career - 3);
+            applied.push("reputation career -3 (liquidation)".to_string());
+            clamp_i64_to_i32(proceeds * VIGILANTE_LIQUIDATION_FEE_PCT / 100).max(20)
+        }
+        WealthProfile::Corporate => {
+            state.reputation.career = clamp_metric(state.reputation.career + 3);
+            applied.push("reputation career +3 (restructuring)".to_string());
+            CORPORATE_RESTRUCTURING_FEE_CR
+        }
+        WealthProfile::Balanced => BALANCED_UNWIND_FEE_CR,
+    };
+    let paid = state.lose_cash(fee);
+    let unpaid = fee - paid;
+    if unpaid > 0 {
+        state.finances.debt += unpaid;
+        applied.push(format!("debt +{} (unpaid transition fee)", unpaid));
+    }
+    applied.push(format!("cash -{} (transition fee)", paid));
+
+    state.wealth_profile = profile;
+    state.last_profile_switch_day = Some(state.last_day);
+    update_wealth_profile(state);
+    applied.push(format!("wealth profile -> {}", profile.label()));
+    Ok(applied)
+}
+
+pub fn parse_civilian_skill(value: &str) -> Option<CivilianSkill> {
+    match value.to_ascii_lowercase().as_str() {
+        "investigation" => Some(CivilianSkill::Investigation),
+        "chemistry" => Some(CivilianSkill::Chemistry),
+        "engineering" => Some(CivilianSkill::Engineering),
+        "streetwise" => Some(CivilianSkill::Streetwise),
+        _ => None,
+    }
+}
+
+/// Raises a skill, returning the new level if the gain crossed a level boundary.
+pub fn train_skill(state: &mut CivilianState, skill: CivilianSkill, gain: i32) -> Option<i32> {
+    let before = state.skill_level(skill);
+    let entry = state.skills.entry(skill).or_insert(0);
+    *entry = clamp_metric(*entry + gain);
+    let after = state.skill_level(skill);
+    (after > before).then_some(after)
+}
+
+pub fn parse_wealth_profile(value: &str) -> Option<WealthProfile> {
+    match value.to_ascii_lowercase().as_str() {
+        "balanced" => Some(WealthProfile::Balanced),
+        "vigilante" => Some(WealthProfile::Vigilante),
+        "corporate" => Some(WealthProfile::Corporate),
+        _ => None,
+    }
+}
+
+pub fn attempt_crime(state: &mut CivilianState, city: &CityState, seed: u64) -> CrimeAttempt {
+    let location_id = city.active_location;
+    let surveillance = city
+        .locations
+        .get(&location_id)
+        .map(|loc| loc.surveillance_level)
+        .unwrap_or(0);
+    let success_chance = crime_success_chance(state, city);
+    let mut rng = seed ^ hash_seed(CRIME_QUICK_HIT_EVENT) ^ state.last_day as u64;
+    let roll = ((next_u64(&mut rng) >> 33) % 100) as i32;
+    let outcome = if roll < success_chance {
+        CrimeOutcome::Clean
+    } else if roll < success_chance + (100 - success_chance) / 2 {
+        CrimeOutcome::Injured
+    } else {
+        CrimeOutcome::Arrested
+    };
+
+    let mut attempt = CrimeAttempt {
+        outcome,
+        location_id,
+        success_chance,
+        cash_gained: 0,
+        fine_paid: 0,
+        signatures: Vec::new(),
+        wanted_delta: 0,
+        civilian_suspicion_delta: 0,
+        opens_case: false,
+    };
+    match outcome {
+        CrimeOutcome::Clean => {
+            attempt.cash_gained = CRIME_PAYOUT_CR;
+            attempt.signatures = crime_signatures(2, 3);
+            attempt.wanted_delta = 2;
+            attempt.civilian_suspicion_delta = 1;
+            attempt.opens_case = surveillance >= 40;
+            train_skill(state, CivilianSkill::Streetwise, 2);
+        }
+        CrimeOutcome::Injured => {
+            attempt.cash_gained = CRIME_INJURED_PAYOUT_CR;
+            attempt.signatures = crime_signatures(4, 4);
+            attempt.wanted_delta = 4;
+            attempt.civilian_suspicion_delta = 3;
+            attempt.opens_case = surveillance >= 25;
+            state.health.injuries = clamp_metric(state.health.injuries + 2);
+            state.health.stress = clamp_metric(state.health.stress + 8);
+            train_skill(state, CivilianSkill::Streetwise, 1);
+        }
+        CrimeOutcome::Arrested => {
+            attempt.signatures = crime_signatures(6, 5);
+            attempt.wanted_delta = 8;
+            attempt.civilian_suspicion_delta = 6;
+            attempt.opens_case = true;
+            attempt.fine_paid = state.lose_cash(CRIME_ARREST_FINE_CR);
+            let unpaid = CRIME_ARREST_FINE_CR - attempt.fine_paid;
+            state.finances.debt += unpaid;
+            state.reputation.community = clamp_metric(state.reputation.community - 6);
+            state.reputation.career = clamp_metric(state.reputation.career - 4);
+            state.social.strain = clamp_metric(state.social.strain + 6);
+        }
+    }
+    if attempt.cash_gained > 0 {
+        state.finances.cash += attempt.cash_gained;
+        sync_wealth_from_finances(state);
+    }
+    attempt
+}
+
+fn crime_attempt_lines(attempt: &CrimeAttempt) -> Vec<String> {
+    let mut lines = Vec::new();
+    if attempt.cash_gained > 0 {
+        lines.push(format!("cash +{} (quick hit)", attempt.cash_gained));
+    }
+    if matches!(attempt.outcome, CrimeOutcome::Injured) {
+        lines.push("injuries +2 (quick hit)".to_string());
+    }
+    if matches!(attempt.outcome, CrimeOutcome::Arrested) {
+        lines.push(format!("cash -{} (arrest fine)", attempt.fine_paid));
+        let unpaid = CRIME_ARREST_FINE_CR - attempt.fine_paid;
+        if unpaid > 0 {
+            lines.push(format!("debt +{} (unpaid fine)", unpaid));
+        }
+    }
+    lines
+}
+
+fn crime_signatures(strength: i64, persistence_turns: i64) -> Vec<SignatureInstance> {
+    [SignatureType::KineticStress, SignatureType::BioMarker]
+        .into_iter()
+        .map(|signature_type| {
+            SignatureSpec {
+                signature_type,
+                strength,
+                persistence_turns,
+            }
+            .to_instance()
+        })
+        .collect()
+}
+
+fn next_u64(state: &mut u64) -> u64 {
+    *state = state
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1);
+    *state
+}
+
+fn hash_seed(value: &str) -> u64 {
+    let mut hash = 1469598103934665603u64;
+    for byte in value.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(1099511628211);
+    }
+    hash
+}
+
+/// Seeds one kind of daily roll. Mixing in the world seed keeps each save's
+/// civilian life its own while replaying identically from the same seed.
+fn daily_seed(world_seed: u64, label: &str, day: u32) -> u64 {
+    world_seed ^ hash_seed(label) ^ day as u64
+}
+
+fn should_queue_crime_opportunity(state: &CivilianState) -> bool {
+    if state.life.age_years < 16 {
+        return false;
+    }
+    if state.finances.rent_due_in > 1 {
+        return false;
+    }
+    state.finances.cash < state.housing.rent
+}
+
+fn contact_acquisition_chance(state: &CivilianState) -> u64 {
+    let reputation = (state.reputation.career.max(0)
+        + state.reputation.community.max(0)
+        + state.reputation.media.max(0))
+        / 3;
+    let tier_scale = match state.civilian_tier {
+        CivilianTier::Local => 100,
+        CivilianTier::Connected => 125,
+        CivilianTier::Influential => 150,
+        CivilianTier::PowerBroker => 175,
+    };
+    ((reputation as u64 / 4) * tier_scale / 100).min(CONTACT_ACQUISITION_MAX_CHANCE)
+}
+
+fn contact_name_pool(domain: ContactDomain) -> &'static [&'static str] {
+    match domain {
+        ContactDomain::Professional => &PROFESSIONAL_CONTACT_NAMES,
+        ContactDomain::Community => &COMMUNITY_CONTACT_NAMES,
+        ContactDomain::Media => &MEDIA_CONTACT_NAMES,
+        ContactDomain::Underground => &UNDERGROUND_CONTACT_NAMES,
+    }
+}
+
+fn try_acquire_contact(state: &mut CivilianState, day: u32, world_seed: u64) {
+    if day < state.last_contact_day.saturating_add(CONTACT_ACQUISITION_COOLDOWN_DAYS) {
+        return;
+    }
+    let chance = contact_acquisition_chance(state);
+    if chance == 0 {
+        return;
+    }
+    let mut rng = daily_seed(world_seed, "civilian_contact", day);
+    if (next_u64(&mut rng) >> 33) % 100 >= chance {
+        return;
+    }
+
+    let weights = [
+        (ContactDomain::Professional, state.reputation.career.max(0)),
+        (ContactDomain::Community, state.reputation.community.max(0)),
+        (ContactDomain::Media, state.reputation.media.max(0)),
+    ];
+    let total: i32 = weights.iter().map(|(_, weight)| weight).sum();
+    if total <= 0 {
+        return;
+    }
+    let mut roll = ((next_u64(&mut rng) >> 33) % total as u64) as i32;
+    let mut domain = ContactDomain::Professional;
+    for (candidate, weight) in weights {
+        if roll < weight {
+            domain = candidate;
+            break;
+        }
+        roll -= weight;
+    }
+
+    let Some(name) = contact_name_pool(domain)
+        .iter()
+        .find(|name| !state.contacts.iter().any(|contact| contact.name == **name))
+    else {
+        return;
+    };
+    let mut applied = Vec::new();
+    state.upsert_contact(
+        name,
+        RelationshipLevel::Acquaintance,
+        Some(domain),
+        None,
+        &mut applied,
+    );
+    state.last_contact_day = day;
+}
+
+/// A Confidant or Ally may introduce one of their own contacts, from their
+/// circle or a neighbouring one. Better-connected introducers come through more
+/// often and bring better-placed people.
+fn try_introduction(state: &mut CivilianState, day: u32, world_seed: u64) {
+    if state.last_introduction_day != 0
+        && day < state.last_introduction_day.saturating_add(INTRODUCTION_COOLDOWN_DAYS)
+    {
+        return;
+    }
+    let Some(introducer) = state
+        .contacts
+        .iter()
+        .filter(|contact| {
+            matches!(
+                contact.level,
+                RelationshipLevel::Confidant | RelationshipLevel::Ally
+            )
+        })
+        .max_by_key(|contact| contact.influence)
+        .cloned()
+    else {
+        return;
+    };
+    let chance = (introducer.influence.max(0) as u64 / 2).min(INTRODUCTION_MAX_CHANCE);
+    let mut rng =
+        daily_seed(world_seed, "civilian_introduction", day) ^ hash_seed(&introducer.name);
+    if (next_u64(&mut rng) >> 33) % 100 >= chance {
+        return;
+    }
+
+    let [left, right] = introducer.domain.adjacent();
+    let domains = match (next_u64(&mut rng) >> 33) % 4 {
+        0 => [left, introducer.domain, right],
+        1 => [right, introducer.domain, left],
+        _ => [introducer.domain, left, right],
+    };
+    let Some((domain, name)) = domains.into_iter().find_map(|domain| {
+        contact_name_pool(domain)
+            .iter()
+            .find(|name| !state.contacts.iter().any(|contact| contact.name == **name))
+            .map(|name| (domain, *name))
+    }) else {
+        return;
+    };
+    let mut applied = Vec::new();
+    state.upsert_contact(
+        name,
+        RelationshipLevel::Acquaintance,
+        Some(domain),
+        None,
+        &mut applied,
+    );
+    if let Some(contact) = state.contacts.iter_mut().find(|contact| contact.name == name) {
+        contact.influence = clamp_metric(contact.influence.max(introducer.influence / 2));
+    }
+    state.last_introduction_day = day;
+    state.network_log.push(format!(
+        "{} introduced you to {} ({:?}).",
+        introducer.name, name, domain
+    ));
+}
+
+pub fn take_network_log(state: &mut CivilianState) -> Vec<String> {
+    std::mem::take(&mut state.network_log)
+}
+
+/// Marks a fight public or notorious enough to draw press for the next few days.
+pub fn note_combat_spotlight(state: &mut CivilianState, consequence: CombatConsequence, day: u32) {
+    if consequence.publicness.max(consequence.notoriety) >= MEDIA_SPOTLIGHT_THRESHOLD {
+        state.spotlight_until_day = state.spotlight_until_day.max(day + MEDIA_SPOTLIGHT_DAYS);
+    }
+}
+
+/// Carries a rough fight home: stress past the threshold becomes injuries and
+/// lingering stress, and mortality risk is refreshed to match. Returns the
+/// injuries added.
+pub fn apply_combat_injuries(state: &mut CivilianState, player_stress: i32) -> i32 {
+    let excess = player_stress - COMBAT_INJURY_STRESS_THRESHOLD;
+    if excess <= 0 {
+        return 0;
+    }
+    let before = state.health.injuries;
+    state.health.injuries = clamp_metric(before + excess / 3);
+    state.health.stress = clamp_metric(state.health.stress + player_stress / 8);
+    update_mortality_risk(state);
+    state.health.injuries - before
+}
+
+pub fn take_media_fallout(state: &mut CivilianState) -> MediaFallout {
+    std::mem::take(&mut state.media_fallout)
+}
+
+/// Fresh fights bring interviews, or exposés once the press has soured;
+/// a quiet but famous life draws puff pieces.
+fn choose_media_event(state: &CivilianState, day: u32) -> Option<&'static str> {
+    if state.last_media_event_day != 0
+        && day < state.last_media_event_day.saturating_add(MEDIA_EVENT_COOLDOWN_DAYS)
+    {
+        return None;
+    }
+    let media = state.reputation.media;
+    if day <= state.spotlight_until_day {
+        if media >= MEDIA_INTERVIEW_REPUTATION {
+            return Some(MEDIA_INTERVIEW_EVENT);
+        }
+        return Some(MEDIA_EXPOSE_EVENT);
+    }
+    if media >= MEDIA_PUFF_PIECE_REPUTATION {
+        return Some(MEDIA_PUFF_PIECE_EVENT);
+    }
+    None
+}
+
+/// Unhappy or jobless civilians hear from recruiters; a part-timer who has
+/// finished school and is doing well may be asked to go full-time instead.
+fn choose_job_offer_event(
+    state: &CivilianState,
+    day: u32,
+    world_seed: u64,
+) -> Option<&'static str> {
+    let cooldown = if matches!(state.job_status, JobStatus::Unemployed) {
+        JOB_OFFER_UNEMPLOYED_COOLDOWN_DAYS
+    } else {
+        JOB_OFFER_COOLDOWN_DAYS
+    };
+    if day < state.last_job_offer_day.saturating_add(cooldown) {
+        return None;
+    }
+    if matches!(state.job_status, JobStatus::Unemployed) {
+        return Some(JOB_OFFER_EVENT);
+    }
+    if matches!(state.job_status, JobStatus::PartTime)
+        && !state.education.is_enrolled
+        && state.job.satisfaction >= FULL_TIME_OFFER_MIN_SATISFACTION
+        && state.job.stability >= FULL_TIME_OFFER_MIN_STABILITY
+    {
+        let mut rng = daily_seed(world_seed, FULL_TIME_OFFER_EVENT, day);
+        let roll = (next_u64(&mut rng) >> 33) % 100;
+        return (roll < FULL_TIME_OFFER_CHANCE).then_some(FULL_TIME_OFFER_EVENT);
+    }
+    (state.job.satisfaction < 55 || state.job.stability < 45).then_some(JOB_OFFER_EVENT)
+}
+
+fn accrue_debt_interest(state: &mut CivilianState) {
+    if state.finances.debt <= 0 {
+        return;
+    }
+    let interest = (state.finances.debt as f32 * DEBT_INTEREST_RATE).ceil() as i32;
+    state.finances.debt = state.finances.debt.saturating_add(interest.max(1));
+}
+
+/// Collectors call once debt outgrows income; past the hard cap the landlord
+/// stops waiting and the civilian is moved one rung down the rent ladder.
+fn apply_debt_consequences(state: &mut CivilianState, time: &GameTime) {
+    if state.debt_in_collections() {
+        queue_event(state, DEBT_COLLECTOR_EVENT, time.tick);
+    }
+    if state.finances.debt < DEBT_EVICTION_CAP_CR || state.housing.relocation_cooldown > 0 {
+        return;
+    }
+    let current_rent = state.housing.rent;
+    let Some(cheaper) = NEIGHBORHOOD_CATALOG
+        .iter()
+        .filter(|profile| profile.rent < current_rent)
+        .max_by_key(|profile| profile.rent)
+    else {
+        return;
+    };
+    let mut applied = Vec::new();
+    apply_neighborhood_profile(state, cheaper.id, &mut applied);
+    state.housing.relocation_cooldown = EVICTION_RELOCATION_COOLDOWN;
+    state.social.strain = clamp_metric(state.social.strain + EVICTION_STRAIN);
+    sync_housing_rent(state);
+}
+
+fn refresh_wealth_tier(state: &mut CivilianState) {
+    state.wealth.refresh_tier(state.finances.debt as i64);
 }
 
 fn update_wealth_profile(state: &mut CivilianState) {
@@ -2159,10 +3554,222 @@ fn clamp_progress(value: i32) -> i32 {
     value.clamp(0, CAREER_XP_CAP)
 }
 
-fn queue_event(state: &mut CivilianState, storylet_id: &str, created_tick: u64) {
+pub fn queue_event(state: &mut CivilianState, storylet_id: &str, created_tick: u64) {
     queue_event_with_contact(state, storylet_id, created_tick, None);
 }
 
+/// Civilian storylet catalog the life sim resolves pending events against.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CivilianEventLibrary(pub Vec<CivilianStorylet>);
+
+/// A pending civilian event with its text rendered from the catalog.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CivilianEventView {
+    pub event_id: String,
+    pub title: String,
+    pub text: String,
+    pub details: Vec<String>,
+    pub choices: Vec<CivilianChoiceView>,
+    pub contact_name: Option<String>,
+    pub created_tick: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CivilianChoiceView {
+    pub id: String,
+    pub text: String,
+}
+
+/// Outcome of resolving a civilian event choice.
+#[derive(Debug, Clone)]
+pub struct CivilianResolution {
+    pub title: String,
+    pub choice_text: String,
+    /// Event and choice effects after contact placeholders are filled in.
+    pub effects: Vec<String>,
+    /// Human-readable lines for the civilian effects that applied.
+    pub applied: Vec<String>,
+    /// The quick-hit roll, when the choice was to take the job.
+    pub crime: Option<CrimeAttempt>,
+}
+
+/// What resolving a civilian choice draws on besides the civilian state: the
+/// event catalog, and the city and roll seed a crime plays out against.
+#[derive(Debug, Clone, Copy)]
+pub struct CivilianChoiceContext<'a> {
+    pub library: &'a [CivilianStorylet],
+    pub city: &'a CityState,
+    pub seed: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CivilianEventError {
+    UnknownEvent(String),
+    NotPending(String),
+    UnknownChoice { event_id: String, choice_id: String },
+}
+
+impl std::fmt::Display for CivilianEventError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CivilianEventError::UnknownEvent(event_id) => {
+                write!(f, "unknown civilian event {}", event_id)
+            }
+            CivilianEventError::NotPending(event_id) => {
+                write!(f, "civilian event {} is not pending", event_id)
+            }
+            CivilianEventError::UnknownChoice {
+                event_id,
+                choice_id,
+            } => write!(f, "unknown choice {} for event {}", choice_id, event_id),
+        }
+    }
+}
+
+impl std::error::Error for CivilianEventError {}
+
+pub fn find_civilian_event<'a>(
+    library: &'a [CivilianStorylet],
+    event_id: &str,
+) -> Option<&'a CivilianStorylet> {
+    library.iter().find(|event| event.id == event_id)
+}
+
+pub fn contact_domain_label(domain: ContactDomain) -> &'static str {
+    match domain {
+        ContactDomain::Professional => "professional",
+        ContactDomain::Community => "community",
+        ContactDomain::Media => "media",
+        ContactDomain::Underground => "underground",
+    }
+}
+
+pub fn relation_type_label(relation_type: RelationType) -> &'static str {
+    match relation_type {
+        RelationType::Family => "family",
+        RelationType::Mentor => "mentor",
+        RelationType::Rival => "rival",
+        RelationType::Romance => "romance",
+        RelationType::Colleague => "colleague",
+        RelationType::Peer => "peer",
+    }
+}
+
+/// Fills `{contact}`, `{relation}` and `{domain}` placeholders for contact-driven events.
+pub fn render_civilian_text(template: &str, state: &CivilianState, event: &CivilianEvent) -> String {
+    let Some(contact_name) = event.contact_name.as_deref() else {
+        return template.to_string();
+    };
+    let mut output = template
+        .replace("{contact}", contact_name)
+        .replace("{contact_name}", contact_name);
+    if let Some(contact) = state.contacts.iter().find(|entry| entry.name == contact_name) {
+        output = output.replace("{relation}", relation_type_label(contact.relation_type));
+        output = output.replace("{domain}", contact_domain_label(contact.domain));
+    }
+    output
+}
+
+pub fn expand_civilian_effects(
+    effects: &[String],
+    state: &CivilianState,
+    event: &CivilianEvent,
+) -> Vec<String> {
+    effects
+        .iter()
+        .map(|effect| render_civilian_text(effect, state, event))
+        .collect()
+}
+
+/// Pending events in queue order; events missing from the catalog are skipped.
+pub fn pending_civilian_event_views(
+    state: &CivilianState,
+    library: &[CivilianStorylet],
+) -> Vec<CivilianEventView> {
+    state
+        .pending_events
+        .iter()
+        .filter_map(|event| {
+            let def = find_civilian_event(library, &event.storylet_id)?;
+            Some(CivilianEventView {
+                event_id: def.id.clone(),
+                title: render_civilian_text(&def.title, state, event),
+                text: render_civilian_text(&def.text_stub, state, event),
+                details: def
+                    .details
+                    .iter()
+                    .map(|detail| render_civilian_text(detail, state, event))
+                    .collect(),
+                choices: def
+                    .choices
+                    .iter()
+                    .map(|choice| CivilianChoiceView {
+                        id: choice.id.clone(),
+                        text: render_civilian_text(&choice.text, state, event),
+                    })
+                    .collect(),
+                contact_name: event.contact_name.clone(),
+                created_tick: event.created_tick,
+            })
+        })
+        .collect()
+}
+
+/// Applies an event's effects plus the chosen option's, then drops it from the
+/// queue. Events that are not queued still resolve, without contact context.
+pub fn resolve_civilian_choice(
+    state: &mut CivilianState,
+    context: CivilianChoiceContext,
+    event_id: &str,
+    choice_id: &str,
+) -> Result<CivilianResolution, CivilianEventError> {
+    let def = find_civilian_event(context.library, event_id)
+        .ok_or_else(|| CivilianEventError::UnknownEvent(event_id.to_string()))?;
+    let choice = def
+        .choices
+        .iter()
+        .find(|choice| choice.id == choice_id)
+        .ok_or_else(|| CivilianEventError::UnknownChoice {
+            event_id: event_id.to_string(),
+            choice_id: choice_id.to_string(),
+        })?;
+    let pending_event = state
+        .pending_events
+        .iter()
+        .find(|event| event.storylet_id == event_id)
+        .cloned()
+        .unwrap_or(CivilianEvent {
+            storylet_id: event_id.to_string(),
+            created_tick: 0,
+            contact_name: None,
+        });
+    let mut effects = expand_civilian_effects(&def.effects, state, &pending_event);
+    let choice_effects = expand_civilian_effects(&choice.effects, state, &pending_event);
+    let mut applied = apply_civilian_effects(state, &effects);
+    applied.extend(apply_civilian_effects(state, &choice_effects));
+    effects.extend(choice_effects);
+    let crime = (event_id == CRIME_QUICK_HIT_EVENT && choice_id == CRIME_TAKE_CHOICE).then(|| {
+        let attempt = attempt_crime(state, context.city, context.seed);
+        applied.extend(crime_attempt_lines(&attempt));
+        attempt
+    });
+    if let Some(index) = state
+        .pending_events
+        .iter()
+        .position(|event| event.storylet_id == event_id)
+    {
+        state.pending_events.remove(index);
+    }
+    state.mark_event_seen(event_id, state.last_day);
+    Ok(CivilianResolution {
+        title: def.title.clone(),
+        choice_text: choice.text.clone(),
+        effects,
+        applied,
+        crime,
+    })
+}
+
 fn queue_event_with_contact(
     state: &mut CivilianState,
     storylet_id: &str,
@@ -2253,6 +3860,15 @@ fn parse_job_status(value: &str) -> Option<JobStatus> {
     }
 }
 
+fn parse_work_shift(value: &str) -> Option<WorkShift> {
+    match value.to_ascii_lowercase().as_str() {
+        "day" => Some(WorkShift::Day),
+        "night" => Some(WorkShift::Night),
+        "rotating" => Some(WorkShift::Rotating),
+        _ => None,
+    }
+}
+
 fn parse_job_role(value: &str) -> Option<JobRole> {
     match value.to_ascii_lowercase().as_str() {
         "lawyer" => Some(JobRole::Lawyer),
@@ -2418,15 +4034,38 @@ fn align_education_with_life_stage(state: &mut CivilianState) {
 }
 
 fn update_routine_schedule(state: &mut CivilianState) {
-    state.routine = build_routine_schedule(state.job_status, &state.education, &state.life);
+    let shift = state.job.shift.on_day(state.last_day);
+    state.routine =
+        build_routine_schedule(state.job_status, shift, &state.education, &state.life);
 }
 
+/// Night workers sleep through the day instead, so the work block covers the
+/// dark hours and leaves the evening free before the shift.
 fn build_routine_schedule(
     job_status: JobStatus,
+    shift: WorkShift,
     education: &EducationTrack,
     life: &LifeState,
 ) -> RoutineSchedule {
     let mut schedule = RoutineSchedule::default();
+    let night_worker = shift == WorkShift::Night
+        && matches!(job_status, JobStatus::Employed | JobStatus::PartTime)
+        && !matches!(life.life_stage, LifeStage::Child | LifeStage::Teen);
+    if night_worker {
+        // 21:00 to 05:00 stays dark even at the height of summer.
+        let duration = if job_status == JobStatus::PartTime { 4 } else { 8 };
+        schedule.push_block(21, 3, RoutineActivity::Work);
+        schedule.push_block(0, duration - 3, RoutineActivity::Work);
+        schedule.push_block(5, 1, RoutineActivity::Errands);
+        schedule.push_block(6, 10, RoutineActivity::Rest);
+        if education.is_enrolled && education.level == EducationLevel::Tertiary {
+            schedule.push_block(16, 2, RoutineActivity::School);
+        }
+        schedule.push_block(18, 2, RoutineActivity::Hobby);
+        schedule.push_block(20, 1, RoutineActivity::Social);
+        return schedule;
+    }
+
     schedule.push_block(0, 6, RoutineActivity::Rest);
     schedule.push_block(6, 1, RoutineActivity::Errands);
     schedule.push_block(7, 1, RoutineActivity::Rest);
@@ -2521,9 +4160,18 @@ fn apply_routine_activity(state: &mut CivilianState, activity: RoutineActivity,
 fn record_school_session(state: &mut CivilianState) {
     let attendance_gain = if state.health.sleep_debt > 12 { 1 } else { 2 };
     state.education.attendance = clamp_metric(state.education.attendance + attendance_gain);
-    let progress_gain = (2 + (state.education.attendance - 50) / 25).max(1);
+    let academic_level = state
+        .skill_level(CivilianSkill::Investigation)
+        .max(state.skill_level(CivilianSkill::Chemistry));
+    let progress_gain = (2 + (state.education.attendance - 50) / 25).max(1) + academic_level / 2;
     state.education.progress = clamp_metric(state.education.progress + progress_gain);
     state.education.credits = state.education.credits.saturating_add(1);
+    let studied = if state.education.credits % 2 == 0 {
+        CivilianSkill::Investigation
+    } else {
+        CivilianSkill::Chemistry
+    };
+    train_skill(state, studied, SCHOOL_SKILL_GAIN);
     let mut dropout_delta = 0;
     if state.education.attendance < 40 {
         dropout_delta += 2;
@@ -2563,15 +4211,6 @@ fn advance_education_if_ready(state: &mut CivilianState) {
     }
 }
 
-fn parse_wealth_profile(value: &str) -> Option<WealthProfile> {
-    match value.to_ascii_lowercase().as_str() {
-        "balanced" => Some(WealthProfile::Balanced),
-        "vigilante" => Some(WealthProfile::Vigilante),
-        "corporate" => Some(WealthProfile::Corporate),
-        _ => None,
-    }
-}
-
 fn parse_life_stage(value: &str) -> Option<LifeStage> {
     match value.to_ascii_lowercase().as_str() {
         "child" => Some(LifeStage::Child),
@@ -2758,6 +4397,7 @@ impl CivilianState {
             bond,
             influence: 10,
             last_interaction_day: self.last_day,
+            favor_debt: 0,
         });
         applied.push(format!("contact added {} ({:?})", name, level));
     }
@@ -2878,3 +4518,560 @@ fn relationship_level_from_bond(bond: i32) -> RelationshipLevel {
         _ => RelationshipLevel::Ally,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corporate_restock_fills_pools_while_vigilante_trickles() {
+        let mut corporate = CivilianState {
+            wealth_profile: WealthProfile::Corporate,
+            ..CivilianState::default()
+        };
+        corporate.finances.cash = 500;
+        let mut corporate_actor = ActorState::default();
+        restock_supplies(&mut corporate, &mut corporate_actor, 1);
+        let cap = corporate.supply_cap();
+        assert_eq!(corporate_actor.resources["ammo"], cap);
+        assert!(corporate.finances.cash < 500);
+
+        let mut vigilante = CivilianState {
+            wealth_profile: WealthProfile::Vigilante,
+            ..CivilianState::default()
+        };
+        vigilante.finances.cash = 500;
+        let mut vigilante_actor = ActorState::default();
+        restock_supplies(&mut vigilante, &mut vigilante_actor, 1);
+        assert!(vigilante_actor.resources["ammo"] < vigilante.supply_cap());
+
+        let before = vigilante_actor.resources["ammo"];
+        restock_supplies(&mut vigilante, &mut vigilante_actor, 1);
+        assert_eq!(vigilante_actor.resources["ammo"], before);
+    }
+
+    #[test]
+    fn going_vigilante_liquidates_investments_into_cash() {
+        let mut state = CivilianState::default();
+        state.finances.cash = 100;
+        state.wealth.current_cr = 100;
+        state.wealth.investments_cr = 1_000;
+        let career = state.reputation.career;
+
+        let applied = set_wealth_profile(&mut state, WealthProfile::Vigilante).unwrap();
+        assert!(applied.contains(&"cash +1000 (liquidated investments)".to_string()));
+        assert_eq!(state.wealth.investments_cr, 0);
+        assert_eq!(state.finances.cash, 1_000);
+        assert_eq!(state.wealth.current_cr, 1_000);
+        assert_eq!(state.reputation.career, clamp_metric(career - 3));
+    }
+
+    fn contact(name: &str, domain: ContactDomain, bond: i32, influence: i32) -> Contact {
+        Contact {
+            name: name.to_string(),
+            level: relationship_level_from_bond(bond),
+            domain,
+            relation_type: RelationType::default(),
+            bond,
+            influence,
+            last_interaction_day: 0,
+            favor_debt: 0,
+        }
+    }
+
+    #[test]
+    fn confidants_introduce_contacts_from_nearby_circles_on_a_cooldown() {
+        let mut shallow = CivilianState {
+            contacts: vec![contact("Joe Public", ContactDomain::Media, 50, 90)],
+            ..CivilianState::default()
+        };
+        for day in 1..60 {
+            try_introduction(&mut shallow, day, 0);
+        }
+        assert_eq!(shallow.contacts.len(), 1);
+
+        let mut state = CivilianState {
+            contacts: vec![contact("Iris Bell", ContactDomain::Professional, 72, 80)],
+            ..CivilianState::default()
+        };
+        let mut introduced_on = None;
+        for day in 1..60 {
+            try_introduction(&mut state, day, 0);
+            if state.contacts.len() > 1 {
+                introduced_on = Some(day);
+                break;
+            }
+        }
+        let day = introduced_on.expect("a confidant eventually makes an introduction");
+        let newcomer = &state.contacts[1];
+        assert!(
+            newcomer.domain == ContactDomain::Professional
+                || ContactDomain::Professional.adjacent().contains(&newcomer.domain)
+        );
+        assert_eq!(newcomer.level, RelationshipLevel::Acquaintance);
+        assert_eq!(newcomer.influence, 40);
+        let log = take_network_log(&mut state);
+        assert_eq!(log.len(), 1);
+        assert!(log[0].starts_with("Iris Bell introduced you to"));
+
+        for later in day + 1..day + INTRODUCTION_COOLDOWN_DAYS {
+            try_introduction(&mut state, later, 0);
+        }
+        assert_eq!(state.contacts.len(), 2);
+    }
+
+    #[test]
+    fn underground_introductions_bring_underground_names() {
+        let mut state = CivilianState {
+            contacts: vec![contact("Night Owl", ContactDomain::Underground, 72, 90)],
+            ..CivilianState::default()
+        };
+        for day in 1..2_000 {
+            try_introduction(&mut state, day, 0);
+        }
+        let introduced: Vec<_> = state
+            .contacts
+            .iter()
+            .filter(|contact| contact.domain == ContactDomain::Underground)
+            .filter(|contact| contact.name != "Night Owl")
+            .collect();
+        assert!(!introduced.is_empty());
+        for contact in introduced {
+            assert!(
+                UNDERGROUND_CONTACT_NAMES.contains(&contact.name.as_str()),
+                "{}",
+                contact.name
+            );
+        }
+    }
+
+    #[test]
+    fn the_world_seed_decides_when_an_introduction_lands() {
+        let first_introduction = |world_seed: u64| {
+            let mut state = CivilianState {
+                contacts: vec![contact("Iris Bell", ContactDomain::Professional, 72, 80)],
+                ..CivilianState::default()
+            };
+            (1..120).find(|day| {
+                try_introduction(&mut state, *day, world_seed);
+                state.contacts.len() > 1
+            })
+        };
+        let days: Vec<_> = (0..8).map(first_introduction).collect();
+        assert_eq!(days, (0..8).map(first_introduction).collect::<Vec<_>>());
+        assert!(days.iter().any(|day| *day != days[0]), "{:?}", days);
+    }
+
+    #[test]
+    fn favors_respect_contact_standing_and_run_out() {
+        let mut state = CivilianState {
+            rewards: CivilianRewards {
+                favors: 2,
+                ..CivilianRewards::default()
+            },
+            contacts: vec![
+                contact("Weak Link", ContactDomain::Media, 10, 20),
+                contact("Office Pal", ContactDomain::Professional, 60, 60),
+                contact("Night Owl", ContactDomain::Underground, 40, 70),
+            ],
+            ..CivilianState::default()
+        };
+
+        assert!(matches!(
+            spend_favor(&mut state, "weak link", FavorKind::Intel, 1),
+            Err(FavorError::NotInfluential { .. })
+        ));
+        assert!(matches!(
+            spend_favor(&mut state, "Office Pal", FavorKind::HeatRelief, 1),
+            Err(FavorError::WrongCircle(ContactDomain::Professional))
+        ));
+
+        assert!(spend_favor(&mut state, "Night Owl", FavorKind::Intel, 1).is_ok());
+        let owl = state.contacts.iter().find(|c| c.name == "Night Owl").unwrap();
+        assert_eq!(owl.favor_debt, 1);
+        assert_eq!(owl.bond, 40);
+
+        assert!(spend_favor(&mut state, "Night Owl", FavorKind::HeatRelief, 1).is_ok());
+        let owl = state.contacts.iter().find(|c| c.name == "Night Owl").unwrap();
+        assert_eq!(owl.bond, 40 - FAVOR_STRAIN_BOND_LOSS);
+        assert_eq!(state.available_favors(), 0);
+        assert!(matches!(
+            spend_favor(&mut state, "Office Pal", FavorKind::JobReferral, 1),
+            Err(FavorError::NoFavorsLeft)
+        ));
+
+        repay_favor_debts(&mut state, FAVOR_DEBT_REPAY_DAYS);
+        assert_eq!(state.available_favors(), 1);
+    }
+
+    #[test]
+    fn intel_reveals_a_case_through_a_contact_or_underground_ties() {
+        let mut state = CivilianState {
+            rewards: CivilianRewards {
+                favors: 1,
+                ..CivilianRewards::default()
+            },
+            contacts: vec![
+                contact("Office Pal", ContactDomain::Professional, 80, 80),
+                contact("Night Owl", ContactDomain::Underground, 45, 70),
+            ],
+            ..CivilianState::default()
+        };
+        let mut cases = CaseRegistry::default();
+        let first = cases.create_case("police".to_string(), LocationId(1), Vec::new(), false);
+        let second = cases.create_case("police".to_string(), LocationId(2), Vec::new(), false);
+
+        assert_eq!(
+            spend_intel(&mut state, &mut cases, 99, 1),
+            Err(IntelError::UnknownCase)
+        );
+        let applied = spend_intel(&mut state, &mut cases, first, 1).expect("intel favor");
+        assert!(applied[0].starts_with("Night Owl digs into case"));
+        assert!(cases.cases[0].revealed);
+        assert_eq!(state.contacts[1].favor_debt, 1);
+        assert_eq!(
+            spend_intel(&mut state, &mut cases, second, 1),
+            Err(IntelError::Favor(FavorError::NoFavorsLeft))
+        );
+        assert!(!cases.cases[1].revealed);
+
+        state.social_web.underground = UNDERGROUND_CASE_INSIGHT;
+        assert!(spend_intel(&mut state, &mut cases, second, 1).is_ok());
+        assert!(cases.cases[1].revealed);
+        assert_eq!(state.outstanding_favor_debt(), 1);
+    }
+
+    #[test]
+    fn skills_train_from_effects_and_lift_crime_odds() {
+        let mut state = CivilianState {
+            hobby_skill: CivilianSkill::Streetwise,
+            ..CivilianState::default()
+        };
+        let city = CityState::default();
+        let base_chance = crime_success_chance(&state, &city);
+
+        let applied = apply_civilian_effects(&mut state, &["skill:hobby:+20".to_string()]);
+        assert!(applied.iter().any(|line| line == "streetwise reached level 1"));
+        assert_eq!(state.skill_level(CivilianSkill::Streetwise), 1);
+        assert_eq!(
+            crime_success_chance(&state, &city),
+            (base_chance + STREETWISE_PER_LEVEL_CRIME_BONUS).clamp(10, 90)
+        );
+
+        assert_eq!(train_skill(&mut state, CivilianSkill::Chemistry, 150), Some(5));
+        assert_eq!(state.skill(CivilianSkill::Chemistry), 100);
+    }
+
+    #[test]
+    fn public_fights_bring_media_events_with_persona_fallout() {
+        let mut state = CivilianState::default();
+        state.reputation.media = 45;
+        assert_eq!(choose_media_event(&state, 10), None);
+
+        let quiet = CombatConsequence {
+            publicness: 10,
+            collateral: 0,
+            notoriety: 5,
+            civilian_casualties: 0,
+        };
+        note_combat_spotlight(&mut state, quiet, 10);
+        assert_eq!(choose_media_event(&state, 10), None);
+
+        note_combat_spotlight(&mut state, CombatConsequence { publicness: 60, ..quiet }, 10);
+        assert_eq!(choose_media_event(&state, 12), Some(MEDIA_INTERVIEW_EVENT));
+        state.reputation.media = 20;
+        assert_eq!(choose_media_event(&state, 12), Some(MEDIA_EXPOSE_EVENT));
+        assert_eq!(choose_media_event(&state, 14), None);
+
+        state.last_media_event_day = 12;
+        state.reputation.media = 80;
+        assert_eq!(choose_media_event(&state, 15), None);
+        assert_eq!(choose_media_event(&state, 17), Some(MEDIA_PUFF_PIECE_EVENT));
+
+        let applied = apply_civilian_effects(
+            &mut state,
+            &[
+                "media:+8".to_string(),
+                "public_suspicion:+4".to_string(),
+                "persona_hint:100".to_string(),
+            ],
+        );
+        assert!(applied.iter().any(|line| line == "persona hint leaked"));
+        let fallout = take_media_fallout(&mut state);
+        assert_eq!(fallout.public_suspicion, 4);
+        assert_eq!(fallout.persona_hints, 1);
+        assert!(state.media_fallout.is_empty());
+    }
+
+    #[test]
+    fn mid_career_start_has_coherent_routine_and_wage() {
+        let start = CivilianStart {
+            age_years: 45,
+            role: JobRole::Doctor,
+            job_level: 3,
+            ..CivilianStart::default()
+        };
+        let state = CivilianState::from_start(&start).expect("valid start");
+
+        assert_eq!(state.life.life_stage, LifeStage::Mature);
+        assert_eq!(state.education.level, EducationLevel::Graduate);
+        assert!(!state.education.is_enrolled);
+        assert_eq!(state.job_status, JobStatus::Employed);
+        assert_eq!(state.job.role, JobRole::Doctor);
+        assert_eq!(state.job.level, 3);
+        assert_eq!(state.finances.wage, career_wage(JobRole::Doctor, 3, JobStatus::Employed));
+        assert_eq!(state.wealth.income_per_tick, state.finances.wage as i64);
+        assert_eq!(state.routine.activity_at(10), RoutineActivity::Work);
+        assert_eq!(state.routine.first_hour_for(RoutineActivity::Work), Some(9));
+        assert_eq!(state.routine.first_hour_for(RoutineActivity::School), None);
+
+        let retired = CivilianState::from_start(&CivilianStart {
+            age_years: 70,
+            ..CivilianStart::default()
+        })
+        .expect("retired elder");
+        assert_eq!(retired.job_status, JobStatus::Unemployed);
+        assert_eq!(retired.finances.wage, 0);
+
+        assert_eq!(
+            CivilianStart {
+                age_years: 17,
+                job_status: Some(JobStatus::Employed),
+                ..start.clone()
+            }
+            .validate(),
+            Err(CivilianStartError::MissingQualification {
+                role: JobRole::Doctor,
+                required: EducationLevel::Graduate,
+            })
+        );
+        assert_eq!(
+            CivilianStart {
+                age_years: 10,
+                job_status: Some(JobStatus::PartTime),
+                ..CivilianStart::default()
+            }
+            .validate(),
+            Err(CivilianStartError::TooYoungToWork { age_years: 10 })
+        );
+        assert!(matches!(
+            CivilianStart {
+                age_years: 16,
+                education: Some(EducationLevel::Graduate),
+                ..CivilianStart::default()
+            }
+            .validate(),
+            Err(CivilianStartError::EducationTooAdvanced { .. })
+        ));
+    }
+
+    #[test]
+    fn night_shift_schedule_works_in_the_dark() {
+        let start = CivilianStart {
+            age_years: 32,
+            role: JobRole::Nurse,
+            job_level: 2,
+            ..CivilianStart::default()
+        };
+        let mut state = CivilianState::from_start(&start).expect("valid start");
+        assert_eq!(state.job.shift, WorkShift::Night);
+
+        let (sunrise, sunset) = crate::simulation::time::Season::Summer.daylight_hours();
+        let work_hours: Vec<u8> = (0..24)
+            .filter(|hour| state.routine.activity_at(*hour) == RoutineActivity::Work)
+            .collect();
+        assert_eq!(work_hours.len(), 8);
+        assert!(work_hours.iter().all(|hour| *hour < sunrise || *hour >= sunset));
+        assert_eq!(state.routine.activity_at(12), RoutineActivity::Rest);
+
+        let applied = apply_civilian_effects(&mut state, &["job_shift:day".to_string()]);
+        assert_eq!(applied, vec!["job shift -> Day".to_string()]);
+        assert_eq!(state.routine.activity_at(10), RoutineActivity::Work);
+        assert_eq!(state.routine.activity_at(2), RoutineActivity::Rest);
+
+        let restored: CivilianState =
+            serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+        assert_eq!(restored.job.shift, WorkShift::Day);
+    }
+
+    #[test]
+    fn contacts_over_the_cap_drop_the_weakest_unpinned_bond() {
+        let cap = CivilianTier::Local.contact_cap();
+        let mut contacts: Vec<Contact> = (0..cap)
+            .map(|idx| contact(&format!("Friend {}", idx), ContactDomain::Community, 50, 20))
+            .collect();
+        let mut cousin = contact("Cousin Ada", ContactDomain::Community, 5, 10);
+        cousin.relation_type = RelationType::Family;
+        contacts.push(cousin);
+        contacts.push(contact("Old Classmate", ContactDomain::Media, 12, 10));
+        contacts.push(contact("Gym Regular", ContactDomain::Community, 30, 10));
+        let mut state = CivilianState {
+            contacts,
+            ..CivilianState::default()
+        };
+        assert_eq!(state.civilian_tier, CivilianTier::Local);
+
+        drift_excess_contacts(&mut state);
+        let names: Vec<&str> = state.contacts.iter().map(|c| c.name.as_str()).collect();
+        assert!(!names.contains(&"Old Classmate"));
+        assert!(names.contains(&"Cousin Ada"));
+        assert_eq!(state.contacts.len(), cap + 2);
+        let gym = state.contacts.iter().find(|c| c.name == "Gym Regular").unwrap();
+        assert_eq!(gym.bond, 20);
+        assert_eq!(gym.level, RelationshipLevel::Stranger);
+        assert_eq!(take_network_log(&mut state), vec!["You have lost touch with Old Classmate."]);
+
+        drift_excess_contacts(&mut state);
+        assert_eq!(state.contacts.len(), cap + 1);
+        assert!(state.contacts.iter().any(|c| c.name == "Cousin Ada"));
+    }
+
+    #[test]
+    fn a_lost_fight_leaves_the_civilian_injured() {
+        let mut state = CivilianState::default();
+        assert_eq!(apply_combat_injuries(&mut state, 40), 0);
+        assert_eq!(state.health.injuries, 0);
+
+        let stress_before = state.health.stress;
+        let added = apply_combat_injuries(&mut state, 120);
+        assert_eq!(added, 20);
+        assert_eq!(state.health.injuries, 20);
+        assert!(state.health.stress > stress_before);
+
+        for _ in 0..10 {
+            apply_combat_injuries(&mut state, 200);
+        }
+        assert_eq!(state.health.injuries, 100);
+        assert!(state.life.mortality_risk > 0);
+    }
+
+    #[test]
+    fn unpaid_debt_compounds_and_eventually_evicts() {
+        let mut state = CivilianState::default();
+        state.finances.debt = 200;
+        let mut time = GameTime::default();
+        let mut last_debt = state.finances.debt;
+        for day in 2..7 {
+            time.day = day;
+            tick_civilian_economy(&mut state, &time, 0).expect("new economy day");
+            assert!(state.finances.debt > last_debt);
+            last_debt = state.finances.debt;
+        }
+
+        let mut applied = Vec::new();
+        apply_neighborhood_profile(&mut state, "uptown", &mut applied);
+        let uptown_rent = state.housing.rent;
+        state.finances.debt = DEBT_EVICTION_CAP_CR;
+        let before_pressure = state.pressure_targets().resource;
+        time.day += 1;
+        tick_civilian_economy(&mut state, &time, 0).expect("new economy day");
+        assert_eq!(state.housing.neighborhood_id, "midtown");
+        assert!(state.housing.rent < uptown_rent);
+        assert_eq!(state.finances.rent, state.housing.rent);
+        assert!(state
+            .pending_events
+            .iter()
+            .any(|event| event.storylet_id == DEBT_COLLECTOR_EVENT));
+        assert!(before_pressure >= DEBT_COLLECTOR_RESOURCE_PRESSURE);
+
+        time.day += 1;
+        tick_civilian_economy(&mut state, &time, 0).expect("new economy day");
+        assert_eq!(state.housing.neighborhood_id, "midtown");
+    }
+
+    #[test]
+    fn settled_part_timers_get_full_time_offers() {
+        let mut state = CivilianState {
+            job_status: JobStatus::PartTime,
+            ..CivilianState::default()
+        };
+        state.education.is_enrolled = false;
+        state.job.satisfaction = 70;
+        state.job.stability = 70;
+        let offers: Vec<_> = (20..60)
+            .filter_map(|day| choose_job_offer_event(&state, day, 0))
+            .collect();
+        assert!(!offers.is_empty());
+        assert!(offers.iter().all(|event| *event == FULL_TIME_OFFER_EVENT));
+
+        state.education.is_enrolled = true;
+        assert!((20..60).all(|day| choose_job_offer_event(&state, day, 0).is_none()));
+    }
+
+    #[test]
+    fn sagging_stability_brings_a_warning_then_the_sack() {
+        let mut state = CivilianState {
+            job_status: JobStatus::Employed,
+            ..CivilianState::default()
+        };
+        sync_career_compensation(&mut state);
+        assert!(state.finances.wage > 0);
+        state.job.stability = JOB_STABILITY_FLOOR - 5;
+        let mut time = GameTime::default();
+        let calm_pressure = state.pressure_targets();
+
+        let mut warned_on = None;
+        for day in 10..40 {
+            time.day = day;
+            review_job_performance(&mut state, &time);
+            if warned_on.is_none() && state.job_warning_day.is_some() {
+                warned_on = Some(day);
+                assert!(state
+                    .pending_events
+                    .iter()
+                    .any(|event| event.storylet_id == JOB_WARNING_EVENT));
+                assert!(!matches!(state.job_status, JobStatus::Unemployed));
+            }
+            if matches!(state.job_status, JobStatus::Unemployed) {
+                assert!(warned_on.is_some_and(|warned| warned < day));
+                break;
+            }
+        }
+        assert!(matches!(state.job_status, JobStatus::Unemployed));
+        assert_eq!(state.finances.wage, 0);
+
+        state.last_day = time.day;
+        let shocked = state.pressure_targets();
+        assert!(shocked.resource > calm_pressure.resource);
+        state.last_day = time.day + JOB_LOSS_SHOCK_DAYS;
+        assert!(state.pressure_targets().resource < shocked.resource);
+    }
+
+    #[test]
+    fn neglected_friends_fade_while_allies_hold_on() {
+        let mut rival = contact("Vic Sterling", ContactDomain::Professional, 10, 90);
+        rival.relation_type = RelationType::Rival;
+        let mut state = CivilianState {
+            contacts: vec![
+                contact("Sam Ortiz", ContactDomain::Community, 50, 10),
+                contact("Lee Park", ContactDomain::Community, 30, 10),
+                contact("Ray Moss", ContactDomain::Community, 95, 10),
+                rival,
+            ],
+            ..CivilianState::default()
+        };
+        state.social.support = 50;
+        state.social.strain = 0;
+        state.social.obligation = 0;
+        assert_eq!(state.contacts[0].level, RelationshipLevel::Friend);
+
+        let mut time = GameTime::default();
+        let mut sabotaged = false;
+        for day in 1..=45 {
+            time.day = day;
+            update_social_web(&mut state, &time, 0);
+            sabotaged |= state
+                .pending_events
+                .iter()
+                .any(|event| event.storylet_id == RIVAL_SABOTAGE_EVENT);
+        }
+        let find = |name: &str| state.contacts.iter().find(|c| c.name == name).unwrap();
+        assert_eq!(find("Sam Ortiz").level, RelationshipLevel::Acquaintance);
+        assert_eq!(find("Sam Ortiz").days_since_interaction(45), 45);
+        let acquaintance_loss = 30 - find("Lee Park").bond;
+        let ally_loss = 95 - find("Ray Moss").bond;
+        assert!(ally_loss * 5 < acquaintance_loss);
+        assert_eq!(find("Ray Moss").level, RelationshipLevel::Ally);
+        assert!(sabotaged);
+    }
+}
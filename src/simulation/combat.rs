@@ -1,8 +1,20 @@
+use serde::{Deserialize, Serialize};
+
 use crate::rules::power::ExpressionId;
 use crate::rules::signature::SignatureInstance;
-use crate::simulation::city::LocationId;
+use crate::simulation::city::{CityState, LocationId, LocationState, LocationTag};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub const PLAYER_STRESS_CAP: i32 = 120;
+pub const MOOK_STRESS_CAP: i32 = 80;
+pub const NEMESIS_STRESS_CAP: i32 = 160;
+pub const ALLY_STRESS_CAP: i32 = 90;
+pub const DEFAULT_SURRENDER_STRESS_PCT: u8 = 80;
+const ALLY_ID_BASE: u32 = 50;
+const INDUSTRIAL_HAZARD_CHANCE: u64 = 25;
+const HIGH_SECURITY_OPPONENT_BONUS: i32 = 2;
+const PUBLIC_CROWD_WITNESSES: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CombatScale {
     Street,
     District,
@@ -11,7 +23,55 @@ pub enum CombatScale {
     Cosmic,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl CombatScale {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_lowercase().as_str() {
+            "street" => Some(CombatScale::Street),
+            "district" => Some(CombatScale::District),
+            "city" => Some(CombatScale::City),
+            "national" => Some(CombatScale::National),
+            "cosmic" => Some(CombatScale::Cosmic),
+            _ => None,
+        }
+    }
+}
+
+/// A batch of opponents due to join a fight once `delay_ticks` have passed,
+/// escalating it to `scale` if that is bigger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CombatWave {
+    pub delay_ticks: u64,
+    pub opponent_count: u32,
+    pub scale: CombatScale,
+}
+
+/// How opponents pick their intent each tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DifficultySetting {
+    /// Opponents often hold back instead of pressing.
+    Passive,
+    /// Opponents always attack and flee once breaking.
+    #[default]
+    Standard,
+    /// Opponents always attack and rarely flee, even when breaking.
+    Aggressive,
+    /// Opponents probe while the player is fresh and focus fire once they are wounded.
+    Tactical,
+}
+
+impl DifficultySetting {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_lowercase().as_str() {
+            "passive" => Some(DifficultySetting::Passive),
+            "standard" => Some(DifficultySetting::Standard),
+            "aggressive" => Some(DifficultySetting::Aggressive),
+            "tactical" => Some(DifficultySetting::Tactical),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CombatIntent {
     Attack,
     Escape,
@@ -19,7 +79,7 @@ pub enum CombatIntent {
     Capture,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CombatSide {
     Player,
     Opponent,
@@ -31,6 +91,8 @@ pub enum CombatEnd {
     PlayerEscaped,
     PlayerDefeated,
     OpponentsDefeated,
+    /// Worn-down opponents gave up while the player held back or moved to capture.
+    OpponentsSurrendered,
     Resolved,
 }
 
@@ -44,11 +106,105 @@ pub struct CombatPressureDelta {
     pub psychological: f32,
 }
 
+/// Reputation the crowd hands out for how it read the fight.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CombatReputationDelta {
+    pub trust: i32,
+    pub fear: i32,
+    pub infamy: i32,
+    pub symbolism: i32,
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub struct CombatConsequence {
     pub publicness: u8,
     pub collateral: u8,
     pub notoriety: u8,
+    pub civilian_casualties: u8,
+}
+
+/// Who else is around the fight; refreshed from the world before each combat tick.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct CombatBystanders {
+    pub population_density: i32,
+    pub agents_present: u32,
+}
+
+/// Snapshot of the location a fight was staged in, taken when combat starts so
+/// the fight plays out consistently even if the city changes around it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CombatStaging {
+    pub location_id: LocationId,
+    pub tags: Vec<LocationTag>,
+    pub surveillance_level: i32,
+}
+
+impl Default for CombatStaging {
+    fn default() -> Self {
+        Self::at(LocationId(0))
+    }
+}
+
+impl CombatStaging {
+    pub fn at(location_id: LocationId) -> Self {
+        Self {
+            location_id,
+            tags: Vec::new(),
+            surveillance_level: 0,
+        }
+    }
+
+    pub fn from_location(location: &LocationState) -> Self {
+        Self {
+            location_id: location.id,
+            tags: location.tags.clone(),
+            surveillance_level: location.surveillance_level,
+        }
+    }
+
+    /// Stages at the city's active location, falling back to a bare id when the
+    /// location is unknown.
+    pub fn for_active_location(city: &CityState) -> Self {
+        city.locations
+            .get(&city.active_location)
+            .map(Self::from_location)
+            .unwrap_or_else(|| Self::at(city.active_location))
+    }
+
+    pub fn has_tag(&self, tag: LocationTag) -> bool {
+        self.tags.contains(&tag)
+    }
+
+    pub fn hazards(&self) -> HazardProfile {
+        HazardProfile {
+            collateral_chance: if self.has_tag(LocationTag::Industrial) {
+                INDUSTRIAL_HAZARD_CHANCE
+            } else {
+                0
+            },
+            opponent_bonus: if self.has_tag(LocationTag::HighSecurity) {
+                HIGH_SECURITY_OPPONENT_BONUS
+            } else {
+                0
+            },
+            witness_bonus: if self.has_tag(LocationTag::Public) {
+                PUBLIC_CROWD_WITNESSES
+            } else {
+                0
+            },
+        }
+    }
+}
+
+/// What the fight's location does on its own each tick, read from the staging tags.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HazardProfile {
+    /// Percent chance per tick that the site hurts everyone in the fight.
+    pub collateral_chance: u64,
+    /// Extra stress behind every opponent attack.
+    pub opponent_bonus: i32,
+    /// Onlookers added to the witness count.
+    pub witness_bonus: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -56,19 +212,31 @@ pub struct CombatConsequences {
     pub signatures: Vec<SignatureInstance>,
     pub pressure_delta: CombatPressureDelta,
     pub combat_consequence: CombatConsequence,
+    pub witnesses: u32,
+    pub reputation: CombatReputationDelta,
+    /// Named opponents taken alive, ready to join the persistent cast.
+    pub captured: Vec<CapturedOpponent>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedOpponent {
+    pub combatant_id: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Combatant {
     pub id: u32,
     pub name: String,
     pub side: CombatSide,
     pub stress: i32,
+    pub stress_cap: i32,
     pub intent: CombatIntent,
     pub is_player: bool,
+    pub loadout: Vec<ExpressionId>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CombatState {
     pub active: bool,
     pub source: String,
@@ -80,6 +248,30 @@ pub struct CombatState {
     pub pending_player_expression: Option<ExpressionId>,
     pub escape_progress: u8,
     pub rng_state: u64,
+    pub nemesis_id: Option<String>,
+    pub bystanders: CombatBystanders,
+    pub staging: CombatStaging,
+    pub difficulty: DifficultySetting,
+    /// Gap between the player and the opponents; seeded from the target
+    /// context on the first tick and changed by escaping and closing in.
+    #[serde(default)]
+    pub distance_m: Option<i64>,
+    /// Average opponent stress, as a percent of their caps, at which they
+    /// surrender to a player who is holding or capturing.
+    #[serde(default = "default_surrender_stress_pct")]
+    pub surrender_stress_pct: u8,
+    /// Waves still to arrive, soonest first.
+    #[serde(default)]
+    pub pending_waves: Vec<CombatWave>,
+    #[serde(default)]
+    pub hazards: HazardProfile,
+    /// Damage the location itself has done so far, added to the fight's collateral.
+    #[serde(default)]
+    pub hazard_collateral: i32,
+}
+
+fn default_surrender_stress_pct() -> u8 {
+    DEFAULT_SURRENDER_STRESS_PCT
 }
 
 impl Default for CombatState {
@@ -95,11 +287,48 @@ impl Default for CombatState {
             pending_player_expression: None,
             escape_progress: 0,
             rng_state: 0,
+            nemesis_id: None,
+            bystanders: CombatBystanders::default(),
+            staging: CombatStaging::default(),
+            difficulty: DifficultySetting::default(),
+            distance_m: None,
+            surrender_stress_pct: DEFAULT_SURRENDER_STRESS_PCT,
+            pending_waves: Vec::new(),
+            hazards: HazardProfile::default(),
+            hazard_collateral: 0,
         }
     }
 }
 
+impl Combatant {
+    pub fn is_defeated(&self) -> bool {
+        self.stress >= self.stress_cap
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        self.stress * 10 >= self.stress_cap * 7
+    }
+
+    /// False for filler fighters that never got a codename ("Opponent 101").
+    pub fn has_real_name(&self) -> bool {
+        self.name
+            .split_whitespace()
+            .last()
+            .map(|word| word.parse::<u32>().is_err())
+            .unwrap_or(false)
+    }
+}
+
 impl CombatState {
+    /// An idle state whose rolls draw from `seed`, for stepping a hand-built
+    /// fight through an exact, repeatable sequence.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            rng_state: seed,
+            ..Self::default()
+        }
+    }
+
     pub fn player(&self) -> Option<&Combatant> {
         self.combatants.iter().find(|c| c.is_player)
     }
@@ -114,10 +343,45 @@ impl CombatState {
             .filter(|c| c.side == CombatSide::Opponent)
     }
 
+    /// Brings a friendly combatant into the fight and returns their id.
+    pub fn add_ally(&mut self, name: &str) -> u32 {
+        let id = ALLY_ID_BASE + self.allies().count() as u32;
+        self.combatants.push(Combatant {
+            id,
+            name: name.to_string(),
+            side: CombatSide::Ally,
+            stress: 0,
+            stress_cap: ALLY_STRESS_CAP,
+            intent: CombatIntent::Attack,
+            is_player: false,
+            loadout: Vec::new(),
+        });
+        self.log.push(format!("{} joins the fight as backup.", name));
+        id
+    }
+
+    pub fn allies(&self) -> impl Iterator<Item = &Combatant> {
+        self.combatants
+            .iter()
+            .filter(|c| c.side == CombatSide::Ally)
+    }
+
+    pub fn active_ally_count(&self) -> usize {
+        self.allies().filter(|c| !c.is_defeated()).count()
+    }
+
     pub fn active_opponent_count(&self) -> usize {
         self.combatants
             .iter()
-            .filter(|c| c.side == CombatSide::Opponent && c.stress < 100)
+            .filter(|c| c.side == CombatSide::Opponent && !c.is_defeated())
             .count()
     }
+
+    /// Opponents taken down while the player fought to capture; they are marked
+    /// with the Capture intent so they can be told apart from those who fled.
+    pub fn captured_opponents(&self) -> impl Iterator<Item = &Combatant> {
+        self.combatants.iter().filter(|c| {
+            c.side == CombatSide::Opponent && c.intent == CombatIntent::Capture && c.is_defeated()
+        })
+    }
 }
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap};
 
 use serde::{Deserialize, Serialize};
 
@@ -10,7 +10,7 @@ const DEFAULT_OMNI_DENOMINATOR: u64 = 2_000_000;
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct OmniPowerRegistry {
     power_holders: HashMap<String, String>,
-    holder_powers: HashMap<String, HashSet<String>>,
+    holder_powers: HashMap<String, BTreeSet<String>>,
     universe_holders: HashMap<String, String>,
     omnipresent_holder: Option<String>,
 }
@@ -0,0 +1,390 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::rules::power::ExpressionId;
+use crate::rules::use_power::ActorState;
+use crate::simulation::civilian::{
+    train_skill, CivilianSkill, CivilianState, SKILL_POINTS_PER_LEVEL, SUPPLY_POOLS,
+};
+use crate::simulation::growth::GrowthState;
+
+const DEFAULT_GADGET_RECIPES_PATH: &str = "./assets/data/gadget_recipes.json";
+const MAX_SKILL_LEVEL: i32 = 100 / SKILL_POINTS_PER_LEVEL;
+const BASE_CRAFT_CHANCE: i32 = 55;
+const CRAFT_CHANCE_PER_LEVEL: i32 = 10;
+const CRAFT_CHANCE_PER_ENGINEERING_LEVEL: i32 = 3;
+const CRAFT_INJURY: i32 = 2;
+const CRAFT_INJURY_STRESS: i32 = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GadgetRecipeCatalog {
+    pub recipes: Vec<GadgetRecipe>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GadgetRecipe {
+    pub id: String,
+    pub name: String,
+    pub skill: CivilianSkill,
+    #[serde(default)]
+    pub min_level: i32,
+    #[serde(default)]
+    pub cash_cost: i32,
+    /// Supply pool -> units consumed per attempt.
+    #[serde(default)]
+    pub materials: BTreeMap<String, i64>,
+    pub output: GadgetOutput,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GadgetOutput {
+    Resource { pool: String, amount: i64 },
+    Expression { expression_id: String, days: u32 },
+}
+
+/// An expression lent by a crafted gadget until `expires_day`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GadgetUnlock {
+    pub recipe_id: String,
+    pub expression_id: String,
+    pub expires_day: u32,
+    /// True once this gadget added the unlock itself, so expiry only removes
+    /// expressions the player did not already know.
+    #[serde(default)]
+    pub granted: bool,
+}
+
+#[derive(Debug)]
+pub enum GadgetRecipeError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+    Invalid(String),
+}
+
+impl std::fmt::Display for GadgetRecipeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GadgetRecipeError::Io(err) => write!(f, "I/O error: {}", err),
+            GadgetRecipeError::Parse(err) => write!(f, "Parse error: {}", err),
+            GadgetRecipeError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for GadgetRecipeError {}
+
+impl GadgetRecipeCatalog {
+    pub fn load_default() -> Result<Self, GadgetRecipeError> {
+        Self::load_from_path(Path::new(DEFAULT_GADGET_RECIPES_PATH))
+    }
+
+    pub fn load_from_path(path: &Path) -> Result<Self, GadgetRecipeError> {
+        let data = fs::read_to_string(path).map_err(GadgetRecipeError::Io)?;
+        let catalog: Self = serde_json::from_str(&data).map_err(GadgetRecipeError::Parse)?;
+        catalog.validate().map_err(GadgetRecipeError::Invalid)?;
+        Ok(catalog)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let mut seen = HashSet::new();
+        for recipe in &self.recipes {
+            if !seen.insert(recipe.id.as_str()) {
+                return Err(format!("duplicate gadget recipe {}", recipe.id));
+            }
+            if !(0..=MAX_SKILL_LEVEL).contains(&recipe.min_level) || recipe.cash_cost < 0 {
+                return Err(format!("gadget recipe {} has invalid requirements", recipe.id));
+            }
+            for (pool, amount) in &recipe.materials {
+                if !SUPPLY_POOLS.contains(&pool.as_str()) || *amount <= 0 {
+                    return Err(format!(
+                        "gadget recipe {} uses invalid material {}",
+                        recipe.id, pool
+                    ));
+                }
+            }
+            match &recipe.output {
+                GadgetOutput::Resource { pool, amount } => {
+                    if !SUPPLY_POOLS.contains(&pool.as_str()) || *amount <= 0 {
+                        return Err(format!(
+                            "gadget recipe {} produces invalid resource {}",
+                            recipe.id, pool
+                        ));
+                    }
+                }
+                GadgetOutput::Expression {
+                    expression_id,
+                    days,
+                } => {
+                    if expression_id.is_empty() || *days == 0 {
+                        return Err(format!(
+                            "gadget recipe {} needs an expression and a duration",
+                            recipe.id
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&GadgetRecipe> {
+        self.recipes
+            .iter()
+            .find(|recipe| recipe.id.eq_ignore_ascii_case(id))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CraftError {
+    SkillTooLow {
+        skill: CivilianSkill,
+        need: i32,
+        have: i32,
+    },
+    NotEnoughCash {
+        need: i32,
+        have: i32,
+    },
+    MissingMaterials {
+        pool: String,
+        need: i64,
+        have: i64,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct CraftOutcome {
+    pub success: bool,
+    pub injured: bool,
+    pub chance: i32,
+    pub applied: Vec<String>,
+}
+
+/// Each level above the recipe's minimum adds to the odds, and engineering
+/// helps with every build.
+pub fn craft_success_chance(civilian: &CivilianState, recipe: &GadgetRecipe) -> i32 {
+    let margin = civilian.skill_level(recipe.skill) - recipe.min_level;
+    let chance = BASE_CRAFT_CHANCE
+        + margin * CRAFT_CHANCE_PER_LEVEL
+        + civilian.crafting_bonus() * CRAFT_CHANCE_PER_ENGINEERING_LEVEL;
+    chance.clamp(10, 95)
+}
+
+/// Spends cash and supply materials on a recipe. Materials are gone whether or
+/// not the build works; a bad failure also injures the crafter.
+pub fn craft_gadget(
+    civilian: &mut CivilianState,
+    actor: &mut ActorState,
+    recipe: &GadgetRecipe,
+    seed: u64,
+) -> Result<CraftOutcome, CraftError> {
+    let level = civilian.skill_level(recipe.skill);
+    if level < recipe.min_level {
+        return Err(CraftError::SkillTooLow {
+            skill: recipe.skill,
+            need: recipe.min_level,
+            have: level,
+        });
+    }
+    if civilian.finances.cash < recipe.cash_cost {
+        return Err(CraftError::NotEnoughCash {
+            need: recipe.cash_cost,
+            have: civilian.finances.cash,
+        });
+    }
+    for (pool, need) in &recipe.materials {
+        let have = actor.resources.get(pool).copied().unwrap_or(0);
+        if have < *need {
+            return Err(CraftError::MissingMaterials {
+                pool: pool.clone(),
+                need: *need,
+                have,
+            });
+        }
+    }
+
+    let mut applied = Vec::new();
+    if recipe.cash_cost > 0 {
+        civilian.lose_cash(recipe.cash_cost);
+        applied.push(format!("cash -{}", recipe.cash_cost));
+    }
+    for (pool, need) in &recipe.materials {
+        *actor.resources.entry(pool.clone()).or_insert(0) -= need;
+        applied.push(format!("{} -{}", pool, need));
+    }
+
+    let chance = craft_success_chance(civilian, recipe);
+    let mut rng = seed ^ hash_seed(&recipe.id) ^ civilian.last_day as u64;
+    let roll = ((next_u64(&mut rng) >> 33) % 100) as i32;
+    let success = roll < chance;
+    let injured = !success && roll >= chance + (100 - chance) / 2;
+
+    if success {
+        match &recipe.output {
+            GadgetOutput::Resource { pool, amount } => {
+                *actor.resources.entry(pool.clone()).or_insert(0) += amount;
+                applied.push(format!("{} +{}", pool, amount));
+            }
+            GadgetOutput::Expression {
+                expression_id,
+                days,
+            } => {
+                let expires_day = civilian.last_day.saturating_add(*days);
+                civilian.gadget_unlocks.push(GadgetUnlock {
+                    recipe_id: recipe.id.clone(),
+                    expression_id: expression_id.clone(),
+                    expires_day,
+                    granted: false,
+                });
+                applied.push(format!(
+                    "expression {} ready until day {}",
+                    expression_id, expires_day
+                ));
+            }
+        }
+    } else {
+        applied.push("materials wasted".to_string());
+        if injured {
+            civilian.health.injuries = (civilian.health.injuries + CRAFT_INJURY).min(100);
+            civilian.health.stress = (civilian.health.stress + CRAFT_INJURY_STRESS).min(100);
+            applied.push(format!(
+                "injuries +{}, stress +{}",
+                CRAFT_INJURY, CRAFT_INJURY_STRESS
+            ));
+        }
+    }
+    let practice = if success { 2 } else { 1 };
+    if let Some(level) = train_skill(civilian, recipe.skill, practice) {
+        applied.push(format!("{} reached level {}", recipe.skill.label(), level));
+    }
+
+    Ok(CraftOutcome {
+        success,
+        injured,
+        chance,
+        applied,
+    })
+}
+
+/// Grants pending gadget expressions and withdraws the ones past their day.
+/// Returns the expressions that expired.
+pub fn sync_gadget_unlocks(
+    civilian: &mut CivilianState,
+    growth: &mut GrowthState,
+    day: u32,
+) -> Vec<ExpressionId> {
+    let mut expired = Vec::new();
+    civilian.gadget_unlocks.retain(|unlock| {
+        if day < unlock.expires_day {
+            return true;
+        }
+        let expression_id = ExpressionId(unlock.expression_id.clone());
+        if unlock.granted {
+            growth.unlocked_expressions.remove(&expression_id);
+        }
+        expired.push(expression_id);
+        false
+    });
+    for unlock in civilian.gadget_unlocks.iter_mut() {
+        if !unlock.granted
+            && growth
+                .unlocked_expressions
+                .insert(ExpressionId(unlock.expression_id.clone()))
+        {
+            unlock.granted = true;
+        }
+    }
+    expired
+}
+
+fn next_u64(state: &mut u64) -> u64 {
+    *state = state
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1);
+    *state
+}
+
+fn hash_seed(value: &str) -> u64 {
+    let mut hash = 1469598103934665603u64;
+    for byte in value.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(1099511628211);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn smoke_recipe() -> GadgetRecipe {
+        GadgetRecipe {
+            id: "smoke_pellets".to_string(),
+            name: "Smoke Pellets".to_string(),
+            skill: CivilianSkill::Chemistry,
+            min_level: 1,
+            cash_cost: 10,
+            materials: [("chemicals".to_string(), 2)].into_iter().collect(),
+            output: GadgetOutput::Resource {
+                pool: "gadgets".to_string(),
+                amount: 1,
+            },
+        }
+    }
+
+    #[test]
+    fn crafting_checks_skill_and_always_spends_materials() {
+        let recipe = smoke_recipe();
+        let mut civilian = CivilianState::default();
+        let mut actor = ActorState::default();
+        actor.resources.insert("chemicals".to_string(), 3);
+        assert!(matches!(
+            craft_gadget(&mut civilian, &mut actor, &recipe, 1),
+            Err(CraftError::SkillTooLow { need: 1, have: 0, .. })
+        ));
+
+        train_skill(&mut civilian, CivilianSkill::Chemistry, SKILL_POINTS_PER_LEVEL);
+        let cash = civilian.finances.cash;
+        let outcome = craft_gadget(&mut civilian, &mut actor, &recipe, 1).expect("craft");
+        assert_eq!(actor.resources["chemicals"], 1);
+        assert_eq!(civilian.finances.cash, cash - 10);
+        let gadgets = actor.resources.get("gadgets").copied().unwrap_or(0);
+        assert_eq!(gadgets, if outcome.success { 1 } else { 0 });
+
+        assert!(matches!(
+            craft_gadget(&mut civilian, &mut actor, &recipe, 2),
+            Err(CraftError::MissingMaterials { need: 2, have: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn gadget_expression_expires_without_touching_known_unlocks() {
+        let mut civilian = CivilianState::default();
+        let mut growth = GrowthState::default();
+        let known = ExpressionId("known".to_string());
+        growth.unlocked_expressions.insert(known.clone());
+        for id in ["known", "loaned"] {
+            civilian.gadget_unlocks.push(GadgetUnlock {
+                recipe_id: "test".to_string(),
+                expression_id: id.to_string(),
+                expires_day: 5,
+                granted: false,
+            });
+        }
+
+        assert!(sync_gadget_unlocks(&mut civilian, &mut growth, 1).is_empty());
+        assert!(growth
+            .unlocked_expressions
+            .contains(&ExpressionId("loaned".to_string())));
+
+        assert_eq!(sync_gadget_unlocks(&mut civilian, &mut growth, 5).len(), 2);
+        assert!(growth.unlocked_expressions.contains(&known));
+        assert!(!growth
+            .unlocked_expressions
+            .contains(&ExpressionId("loaned".to_string())));
+    }
+}
@@ -60,6 +60,15 @@ pub struct WealthProfileModifiers {
     pub liquidity_cap: f32,
 }
 
+/// Per-tick market behaviour of invested holdings under a wealth profile.
+#[derive(Debug, Clone, Copy)]
+pub struct InvestmentOutlook {
+    pub mean_return: f64,
+    pub volatility: f64,
+    pub swing_chance_pct: u64,
+    pub swing_scale: f64,
+}
+
 impl WealthProfile {
     pub fn label(self) -> &'static str {
         match self {
@@ -91,6 +100,31 @@ impl WealthProfile {
             },
         }
     }
+
+    /// Corporate portfolios chase growth and ride bigger swings; a balanced
+    /// book earns less but barely moves.
+    pub fn investment_outlook(self) -> InvestmentOutlook {
+        match self {
+            WealthProfile::Balanced => InvestmentOutlook {
+                mean_return: 0.0004,
+                volatility: 0.004,
+                swing_chance_pct: 2,
+                swing_scale: 0.03,
+            },
+            WealthProfile::Vigilante => InvestmentOutlook {
+                mean_return: 0.0002,
+                volatility: 0.006,
+                swing_chance_pct: 2,
+                swing_scale: 0.05,
+            },
+            WealthProfile::Corporate => InvestmentOutlook {
+                mean_return: 0.0012,
+                volatility: 0.015,
+                swing_chance_pct: 4,
+                swing_scale: 0.12,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -100,6 +134,8 @@ pub struct Wealth {
     pub income_per_tick: i64,
     pub upkeep_per_tick: i64,
     pub liquidity: f32,
+    #[serde(default)]
+    pub investments_cr: i64,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -111,6 +147,13 @@ pub struct EconomyTickResult {
     pub tier: WealthTier,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvestmentTickResult {
+    pub return_cr: i64,
+    pub balance_cr: i64,
+    pub market_swing: bool,
+}
+
 impl Default for Wealth {
     fn default() -> Self {
         Self::new(0)
@@ -126,11 +169,14 @@ impl Wealth {
             income_per_tick: 0,
             upkeep_per_tick: 0,
             liquidity: default_liquidity_for_tier(tier),
+            investments_cr: 0,
         }
     }
 
     pub fn net_worth(&self, debt_cr: i64) -> i64 {
-        self.current_cr.saturating_sub(debt_cr.max(0))
+        self.current_cr
+            .saturating_add(self.investments_cr.max(0))
+            .saturating_sub(debt_cr.max(0))
     }
 
     pub fn refresh_tier(&mut self, debt_cr: i64) -> WealthTier {
@@ -173,6 +219,49 @@ impl Wealth {
             tier,
         }
     }
+
+    /// Moves invested holdings by one tick of market return for `profile`.
+    /// The same seed always yields the same return; now and then a market
+    /// swing lands on top of the ordinary drift.
+    pub fn apply_investment_return(
+        &mut self,
+        profile: WealthProfile,
+        seed: u64,
+    ) -> InvestmentTickResult {
+        let outlook = profile.investment_outlook();
+        let mut rng = seed;
+        let mut rate = outlook.mean_return + outlook.volatility * unit_spread(&mut rng);
+        // The generator's low bits cycle quickly, so every draw reads the high bits.
+        let market_swing = (next_u64(&mut rng) >> 33) % 100 < outlook.swing_chance_pct;
+        if market_swing {
+            if next_u64(&mut rng) >> 63 == 0 {
+                rate += outlook.swing_scale;
+            } else {
+                rate -= outlook.swing_scale;
+            }
+        }
+        let before = self.investments_cr.max(0);
+        let return_cr = ((before as f64) * rate).round() as i64;
+        self.investments_cr = (before + return_cr).max(0);
+        InvestmentTickResult {
+            return_cr: self.investments_cr - before,
+            balance_cr: self.investments_cr,
+            market_swing,
+        }
+    }
+}
+
+fn next_u64(state: &mut u64) -> u64 {
+    *state = state
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    *state
+}
+
+/// Uniform draw in [-1, 1].
+fn unit_spread(state: &mut u64) -> f64 {
+    let value = (next_u64(state) >> 11) as f64 / (1u64 << 53) as f64;
+    value * 2.0 - 1.0
 }
 
 pub fn wealth_tier_for(net_worth_cr: i64) -> WealthTier {
@@ -224,3 +313,59 @@ pub fn clamp_liquidity(value: f32) -> f32 {
 pub fn can_fund_gadget(wealth: &Wealth, min_tier: WealthTier, cost_cr: i64) -> bool {
     wealth.tier.rank() >= min_tier.rank() && wealth.can_spend(cost_cr)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_profile(profile: WealthProfile) -> (f64, i64) {
+        let mut total = 0.0;
+        let mut worst_drawdown = 0;
+        for run in 0..200u64 {
+            let mut wealth = Wealth::new(0);
+            wealth.investments_cr = 100_000;
+            let mut peak = wealth.investments_cr;
+            for tick in 0..365u64 {
+                let seed = (run << 32) ^ tick.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+                wealth.apply_investment_return(profile, seed);
+                peak = peak.max(wealth.investments_cr);
+                worst_drawdown = worst_drawdown.max(peak - wealth.investments_cr);
+            }
+            total += (wealth.investments_cr - 100_000) as f64;
+        }
+        (total / 200.0, worst_drawdown)
+    }
+
+    #[test]
+    fn corporate_out_earns_balanced_with_deeper_drawdowns() {
+        let (corporate_mean, corporate_drawdown) = run_profile(WealthProfile::Corporate);
+        let (balanced_mean, balanced_drawdown) = run_profile(WealthProfile::Balanced);
+        assert!(corporate_mean > balanced_mean);
+        assert!(balanced_mean > 0.0);
+        assert!(corporate_drawdown > balanced_drawdown);
+
+        let mut first = Wealth::new(0);
+        first.investments_cr = 50_000;
+        let mut second = first.clone();
+        assert_eq!(
+            first.apply_investment_return(WealthProfile::Corporate, 42),
+            second.apply_investment_return(WealthProfile::Corporate, 42)
+        );
+        assert_eq!(first.net_worth(0), first.investments_cr);
+    }
+
+    #[test]
+    fn market_swings_go_both_ways_on_odd_and_even_days() {
+        // Daily seeds differ from one day to the next only in their low bits.
+        let mut directions = [[false; 2]; 2];
+        for day in 0..5_000u64 {
+            let mut wealth = Wealth::new(0);
+            wealth.investments_cr = 100_000;
+            let result = wealth.apply_investment_return(WealthProfile::Corporate, 0xC0FFEE ^ day);
+            if result.market_swing {
+                directions[(day % 2) as usize][(result.return_cr > 0) as usize] = true;
+            }
+        }
+        assert_eq!(directions, [[true; 2]; 2]);
+    }
+}
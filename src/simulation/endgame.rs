@@ -6,6 +6,14 @@ use crate::simulation::pressure::PressureState;
 use crate::simulation::storylet_state::StoryletState;
 use crate::systems::event_resolver::ResolvedFactionEventLog;
 
+const EXPOSED_CASE_PROGRESS: u32 = 90;
+const REGISTRATION_CASE_PROGRESS: u32 = 70;
+const PRESSURE_SPIKE_LEVEL: f32 = 85.0;
+const MORAL_SPIKE_LEVEL: f32 = 80.0;
+const COSMIC_SPIKES_REQUIRED: u32 = 3;
+const ASCENSION_PRESSURE_LEVEL: f32 = 80.0;
+const EXILE_ESCALATIONS_REQUIRED: u32 = 2;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TransformationState {
     Exposed,
@@ -20,6 +28,7 @@ pub enum TransformationTrigger {
     CaseCollapse,
     PressureSpike,
     FactionAttention,
+    IdentityBreach,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -129,6 +138,76 @@ pub fn evaluate_transformation(
     None
 }
 
+/// How close one potential transformation is to firing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggerStatus {
+    pub state: TransformationState,
+    pub trigger: TransformationTrigger,
+    pub measure: &'static str,
+    pub threshold: f32,
+    pub current: f32,
+}
+
+impl TriggerStatus {
+    pub fn met(&self) -> bool {
+        self.current >= self.threshold
+    }
+
+    pub fn shortfall(&self) -> f32 {
+        (self.threshold - self.current).max(0.0)
+    }
+}
+
+/// Reports every transformation `evaluate_transformation` considers, in the
+/// order it checks them, so a missing endgame can be explained.
+pub fn evaluate_transformation_debug(
+    cases: &CaseRegistry,
+    pressure: &PressureState,
+    faction_events: &ResolvedFactionEventLog,
+) -> Vec<TriggerStatus> {
+    let case_progress = max_active_case_progress(cases).unwrap_or(0) as f32;
+    let spikes = pressure_spike_count(pressure) as f32;
+    let ascension = pressure.temporal.min(pressure.resource);
+    let escalations = faction_escalation_count(faction_events) as f32;
+    vec![
+        TriggerStatus {
+            state: TransformationState::Exposed,
+            trigger: TransformationTrigger::CaseCollapse,
+            measure: "highest active case progress",
+            threshold: EXPOSED_CASE_PROGRESS as f32,
+            current: case_progress,
+        },
+        TriggerStatus {
+            state: TransformationState::Registration,
+            trigger: TransformationTrigger::CaseCollapse,
+            measure: "highest active case progress",
+            threshold: REGISTRATION_CASE_PROGRESS as f32,
+            current: case_progress,
+        },
+        TriggerStatus {
+            state: TransformationState::CosmicJudgement,
+            trigger: TransformationTrigger::PressureSpike,
+            measure: "spiking pressures (identity/institutional/psychological 85+, moral 80+)",
+            threshold: COSMIC_SPIKES_REQUIRED as f32,
+            current: spikes,
+        },
+        TriggerStatus {
+            state: TransformationState::Ascension,
+            trigger: TransformationTrigger::PressureSpike,
+            measure: "lower of temporal and resource pressure",
+            threshold: ASCENSION_PRESSURE_LEVEL,
+            current: ascension,
+        },
+        TriggerStatus {
+            state: TransformationState::Exile,
+            trigger: TransformationTrigger::FactionAttention,
+            measure: "critical or max faction escalations",
+            threshold: EXILE_ESCALATIONS_REQUIRED as f32,
+            current: escalations,
+        },
+    ]
+}
+
 pub fn apply_transformation_event(
     endgame_state: &mut EndgameState,
     storylet_state: &mut StoryletState,
@@ -164,67 +243,63 @@ pub fn endgame_phase_label(state: Option<TransformationState>) -> &'static str {
     }
 }
 
-fn evaluate_case_pressure(cases: &CaseRegistry) -> Option<TransformationState> {
-    let active_cases: Vec<_> = cases
+fn max_active_case_progress(cases: &CaseRegistry) -> Option<u32> {
+    cases
         .cases
         .iter()
         .filter(|case| case.status == CaseStatus::Active)
-        .collect();
-    if active_cases.is_empty() {
-        return None;
-    }
-
-    let max_progress = active_cases
-        .iter()
         .map(|case| case.progress)
         .max()
-        .unwrap_or(0);
-    if max_progress >= 90 {
+}
+
+fn evaluate_case_pressure(cases: &CaseRegistry) -> Option<TransformationState> {
+    let max_progress = max_active_case_progress(cases)?;
+    if max_progress >= EXPOSED_CASE_PROGRESS {
         return Some(TransformationState::Exposed);
     }
-    if max_progress >= 70 {
+    if max_progress >= REGISTRATION_CASE_PROGRESS {
         return Some(TransformationState::Registration);
     }
     None
 }
 
-fn evaluate_pressure_state(pressure: &PressureState) -> Option<TransformationState> {
-    let mut spikes = 0;
-    if pressure.identity >= 85.0 {
-        spikes += 1;
-    }
-    if pressure.institutional >= 85.0 {
-        spikes += 1;
-    }
-    if pressure.psychological >= 85.0 {
-        spikes += 1;
-    }
-    if pressure.moral >= 80.0 {
-        spikes += 1;
-    }
+fn pressure_spike_count(pressure: &PressureState) -> u32 {
+    [
+        pressure.identity >= PRESSURE_SPIKE_LEVEL,
+        pressure.institutional >= PRESSURE_SPIKE_LEVEL,
+        pressure.psychological >= PRESSURE_SPIKE_LEVEL,
+        pressure.moral >= MORAL_SPIKE_LEVEL,
+    ]
+    .into_iter()
+    .filter(|spiking| *spiking)
+    .count() as u32
+}
 
-    if spikes >= 3 {
+fn evaluate_pressure_state(pressure: &PressureState) -> Option<TransformationState> {
+    if pressure_spike_count(pressure) >= COSMIC_SPIKES_REQUIRED {
         return Some(TransformationState::CosmicJudgement);
     }
-    if pressure.temporal >= 80.0 && pressure.resource >= 80.0 {
+    if pressure.temporal >= ASCENSION_PRESSURE_LEVEL && pressure.resource >= ASCENSION_PRESSURE_LEVEL
+    {
         return Some(TransformationState::Ascension);
     }
     None
 }
 
+fn faction_escalation_count(faction_events: &ResolvedFactionEventLog) -> u32 {
+    faction_events
+        .0
+        .iter()
+        .filter(|event| {
+            event.level.eq_ignore_ascii_case("critical") || event.level.eq_ignore_ascii_case("max")
+        })
+        .count() as u32
+}
+
 fn evaluate_faction_attention(
     faction_events: &ResolvedFactionEventLog,
 ) -> Option<TransformationState> {
-    let mut escalations = 0;
-    for event in &faction_events.0 {
-        if event.level.eq_ignore_ascii_case("critical")
-            || event.level.eq_ignore_ascii_case("max")
-        {
-            escalations += 1;
-        }
-    }
-
-    if escalations >= 2 {
+    if faction_escalation_count(faction_events) >= EXILE_ESCALATIONS_REQUIRED {
         return Some(TransformationState::Exile);
     }
 
@@ -279,3 +354,41 @@ fn handle_cosmic(storylet_state: &mut StoryletState) -> &'static str {
         .insert("endgame_cosmic".to_string(), true);
     transformation_text(TransformationState::CosmicJudgement)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_statuses_explain_a_missing_transformation() {
+        let cases = CaseRegistry::default();
+        let faction_events = ResolvedFactionEventLog::default();
+        let mut pressure = PressureState {
+            identity: 90.0,
+            moral: 82.0,
+            institutional: 60.0,
+            ..PressureState::default()
+        };
+        assert_eq!(evaluate_transformation(&cases, &pressure, &faction_events), None);
+
+        let statuses = evaluate_transformation_debug(&cases, &pressure, &faction_events);
+        assert_eq!(statuses.len(), 5);
+        let cosmic = statuses
+            .iter()
+            .find(|status| status.state == TransformationState::CosmicJudgement)
+            .unwrap();
+        assert_eq!((cosmic.current, cosmic.threshold), (2.0, 3.0));
+        assert!(!cosmic.met());
+        assert_eq!(cosmic.shortfall(), 1.0);
+
+        pressure.institutional = 88.0;
+        let statuses = evaluate_transformation_debug(&cases, &pressure, &faction_events);
+        assert!(statuses
+            .iter()
+            .any(|status| status.state == TransformationState::CosmicJudgement && status.met()));
+        assert_eq!(
+            evaluate_transformation(&cases, &pressure, &faction_events).map(|event| event.state),
+            Some(TransformationState::CosmicJudgement)
+        );
+    }
+}
@@ -1,27 +1,82 @@
+use std::collections::HashMap;
+
 use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::rules::signature::SignatureInstance;
+use crate::rules::signature::{decay_rate, SignatureInstance, SignatureType};
 use crate::simulation::city::LocationId;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignatureEvent {
     pub location_id: LocationId,
     pub signature: SignatureInstance,
     pub is_new: bool,
 }
 
-#[derive(Resource, Debug, Default)]
+/// Live signatures in the world. The store is only changed through its own
+/// methods so the location index always matches the signatures it holds.
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(from = "EvidenceRecord")]
 pub struct WorldEvidence {
-    pub signatures: Vec<SignatureEvent>,
+    signatures: Vec<SignatureEvent>,
+    /// Live signature counts by location and type, kept in step by `emit` and
+    /// `tick_decay` so case matching never rescans the whole store.
+    #[serde(skip)]
+    by_location: HashMap<LocationId, HashMap<SignatureType, usize>>,
+}
+
+/// The saved form of `WorldEvidence`; the index is rebuilt on the way in.
+#[derive(Deserialize)]
+struct EvidenceRecord {
+    signatures: Vec<SignatureEvent>,
+}
+
+impl From<EvidenceRecord> for WorldEvidence {
+    fn from(record: EvidenceRecord) -> Self {
+        WorldEvidence::from_events(record.signatures)
+    }
 }
 
 impl WorldEvidence {
+    /// Rebuilds a store from saved events, keeping their remaining turns and
+    /// `is_new` flags as they were.
+    pub fn from_events(signatures: Vec<SignatureEvent>) -> Self {
+        let mut evidence = Self {
+            signatures,
+            by_location: HashMap::new(),
+        };
+        evidence.reindex();
+        evidence
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &SignatureEvent> {
+        self.signatures.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.signatures.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.signatures.is_empty()
+    }
+
+    /// Walks every signature, clearing `is_new` and yielding whether each one
+    /// was new. This is the only write access, so the index stays valid.
+    pub fn iter_marking_seen(&mut self) -> impl Iterator<Item = (&SignatureEvent, bool)> {
+        self.signatures.iter_mut().map(|event| {
+            let was_new = std::mem::replace(&mut event.is_new, false);
+            (&*event, was_new)
+        })
+    }
+
     pub fn emit(&mut self, location_id: LocationId, signatures: &[SignatureInstance]) {
         for sig in signatures {
             let mut instance = sig.clone();
             if instance.remaining_turns <= 0 {
                 instance.remaining_turns = 5;
             }
+            self.index(location_id, instance.signature.signature_type);
             self.signatures.push(SignatureEvent {
                 location_id,
                 signature: instance,
@@ -30,14 +85,77 @@ impl WorldEvidence {
         }
     }
 
+    /// Ages every signature by its type's decay rate, dropping spent ones.
     pub fn tick_decay(&mut self) {
         for item in self.signatures.iter_mut() {
-            if item.signature.remaining_turns > 0 {
-                item.signature.remaining_turns -= 1;
+            let rate = decay_rate(item.signature.signature.signature_type) as i64;
+            item.signature.remaining_turns = (item.signature.remaining_turns - rate).max(0);
+        }
+        let mut spent = Vec::new();
+        self.signatures.retain(|s| {
+            let live = s.signature.remaining_turns > 0;
+            if !live {
+                spent.push((s.location_id, s.signature.signature.signature_type));
             }
+            live
+        });
+        for (location_id, signature_type) in spent {
+            self.unindex(location_id, signature_type);
+        }
+    }
+
+    /// Live signatures at `location_id` whose type passes `filter`.
+    pub fn count_at(
+        &self,
+        location_id: LocationId,
+        filter: impl Fn(SignatureType) -> bool,
+    ) -> usize {
+        self.by_location
+            .get(&location_id)
+            .map(|by_type| {
+                by_type
+                    .iter()
+                    .filter(|(signature_type, _)| filter(**signature_type))
+                    .map(|(_, count)| count)
+                    .sum()
+            })
+            .unwrap_or(0)
+    }
+
+    fn reindex(&mut self) {
+        self.by_location.clear();
+        let entries: Vec<_> = self
+            .signatures
+            .iter()
+            .map(|event| (event.location_id, event.signature.signature.signature_type))
+            .collect();
+        for (location_id, signature_type) in entries {
+            self.index(location_id, signature_type);
+        }
+    }
+
+    fn index(&mut self, location_id: LocationId, signature_type: SignatureType) {
+        *self
+            .by_location
+            .entry(location_id)
+            .or_default()
+            .entry(signature_type)
+            .or_default() += 1;
+    }
+
+    fn unindex(&mut self, location_id: LocationId, signature_type: SignatureType) {
+        let Some(by_type) = self.by_location.get_mut(&location_id) else {
+            return;
+        };
+        if let Some(count) = by_type.get_mut(&signature_type) {
+            *count -= 1;
+            if *count == 0 {
+                by_type.remove(&signature_type);
+            }
+        }
+        if by_type.is_empty() {
+            self.by_location.remove(&location_id);
         }
-        self.signatures
-            .retain(|s| s.signature.remaining_turns > 0);
     }
 }
 
@@ -50,3 +168,37 @@ impl EvidenceSink for WorldEvidence {
         WorldEvidence::emit(self, where_, sigs);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::signature::{SignatureSpec, SignatureType};
+
+    fn turns_until_gone(signature_type: SignatureType) -> u32 {
+        let mut evidence = WorldEvidence::default();
+        evidence.emit(
+            LocationId(1),
+            &[SignatureSpec {
+                signature_type,
+                strength: 3,
+                persistence_turns: 9,
+            }
+            .to_instance()],
+        );
+        let mut turns = 0;
+        while !evidence.is_empty() {
+            evidence.tick_decay();
+            turns += 1;
+        }
+        turns
+    }
+
+    #[test]
+    fn thermal_blooms_fade_well_before_chemical_residue() {
+        let thermal = turns_until_gone(SignatureType::ThermalBloom);
+        let chemical = turns_until_gone(SignatureType::ChemicalResidue);
+        assert_eq!(thermal, 3);
+        assert_eq!(chemical, 9);
+        assert!(chemical >= thermal + 5);
+    }
+}
@@ -1,17 +1,21 @@
 use std::collections::{HashMap, HashSet};
 
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::components::persona::Alignment;
+use crate::content::{PowerRepository, RepositoryError};
 use crate::rules::expression::ExpressionForm;
 use crate::rules::mastery::{stage_from_uses, MasteryStage};
 use crate::rules::power::ExpressionId;
 use crate::rules::ExpressionDef;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExpressionMastery {
     pub stage: MasteryStage,
     pub uses: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Reputation {
     pub trust: i32,
     pub fear: i32,
@@ -19,14 +23,25 @@ pub struct Reputation {
     pub symbolism: i32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GrowthState {
     pub mastery: HashMap<ExpressionId, ExpressionMastery>,
+    #[serde(serialize_with = "serialize_sorted_unlocks")]
     pub unlocked_expressions: HashSet<ExpressionId>,
     pub reputation: Reputation,
     pub pressure_resistance: i32,
 }
 
+/// Unlocks are written in id order so exported saves stay byte-for-byte stable.
+fn serialize_sorted_unlocks<S: Serializer>(
+    unlocked: &HashSet<ExpressionId>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut ids: Vec<&ExpressionId> = unlocked.iter().collect();
+    ids.sort_by(|a, b| a.0.cmp(&b.0));
+    serializer.collect_seq(ids)
+}
+
 impl Default for Reputation {
     fn default() -> Self {
         Self {
@@ -49,6 +64,64 @@ impl Default for GrowthState {
     }
 }
 
+/// Why an expression could not be unlocked or locked.
+#[derive(Debug)]
+pub enum ExpressionUnlockError {
+    UnknownExpression(String),
+    Repository(RepositoryError),
+}
+
+impl std::fmt::Display for ExpressionUnlockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpressionUnlockError::UnknownExpression(id) => write!(f, "unknown expression {}", id),
+            ExpressionUnlockError::Repository(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ExpressionUnlockError {}
+
+impl GrowthState {
+    /// Unlocks an expression the content repository knows about.
+    /// Returns false when it was already unlocked.
+    pub fn unlock_expression(
+        &mut self,
+        repo: &dyn PowerRepository,
+        expr_id: &ExpressionId,
+    ) -> Result<bool, ExpressionUnlockError> {
+        ensure_expression_exists(repo, expr_id)?;
+        Ok(self.unlocked_expressions.insert(expr_id.clone()))
+    }
+
+    /// Locks a previously unlocked expression. Stale unlocks for ids the
+    /// repository no longer knows are still removed.
+    pub fn lock_expression(
+        &mut self,
+        repo: &dyn PowerRepository,
+        expr_id: &ExpressionId,
+    ) -> Result<bool, ExpressionUnlockError> {
+        if self.unlocked_expressions.remove(expr_id) {
+            return Ok(true);
+        }
+        ensure_expression_exists(repo, expr_id)?;
+        Ok(false)
+    }
+}
+
+fn ensure_expression_exists(
+    repo: &dyn PowerRepository,
+    expr_id: &ExpressionId,
+) -> Result<(), ExpressionUnlockError> {
+    match repo.expression(expr_id) {
+        Ok(_) => Ok(()),
+        Err(RepositoryError::NotFound { .. }) => {
+            Err(ExpressionUnlockError::UnknownExpression(expr_id.0.clone()))
+        }
+        Err(err) => Err(ExpressionUnlockError::Repository(err)),
+    }
+}
+
 pub fn record_expression_use(growth: &mut GrowthState, expr: &ExpressionDef) -> Option<MasteryStage> {
     growth.unlocked_expressions.insert(expr.id.clone());
 
@@ -72,6 +145,7 @@ pub fn select_evolution_candidate(
     expr: &ExpressionDef,
     candidates: &[ExpressionDef],
     unlocked: &HashSet<ExpressionId>,
+    alignment: Option<Alignment>,
 ) -> Option<ExpressionId> {
     let preferred = preferred_forms(expr.form);
     let mut choices: Vec<&ExpressionDef> = candidates
@@ -84,6 +158,17 @@ pub fn select_evolution_candidate(
         return None;
     }
 
+    if let Some(alignment) = alignment.filter(|alignment| *alignment != Alignment::Neutral) {
+        let aligned: Vec<&ExpressionDef> = choices
+            .iter()
+            .copied()
+            .filter(|candidate| candidate.alignment_affinity() == Some(alignment))
+            .collect();
+        if !aligned.is_empty() {
+            choices = aligned;
+        }
+    }
+
     if !preferred.is_empty() {
         let mut preferred_choices: Vec<&ExpressionDef> = choices
             .iter()
@@ -116,3 +201,150 @@ fn preferred_forms(form: ExpressionForm) -> Vec<ExpressionForm> {
         ExpressionForm::Passive => vec![ExpressionForm::Touch],
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::expression::{Constraints, Delivery, ExpressionText, Scale};
+    use crate::rules::power::PowerId;
+
+    fn expression(id: &str, form: ExpressionForm, constraints: serde_json::Value) -> ExpressionDef {
+        ExpressionDef {
+            id: ExpressionId(id.to_string()),
+            power_id: PowerId(1),
+            form,
+            delivery: Delivery::Instant,
+            scale: Scale::Street,
+            constraints: Constraints::from_json(&constraints),
+            text: ExpressionText {
+                ui_name: id.to_string(),
+                tooltip_short: String::new(),
+            },
+            costs: Vec::new(),
+            signatures: Vec::new(),
+            environment_effects: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn evolution_candidate_follows_alignment_affinity() {
+        let source = expression("kinetic_bolt", ExpressionForm::Projectile, serde_json::json!({}));
+        let candidates = vec![
+            source.clone(),
+            expression(
+                "kinetic_crush",
+                ExpressionForm::Zone,
+                serde_json::json!({ "alignment_affinity": "VILLAIN" }),
+            ),
+            expression(
+                "kinetic_shield",
+                ExpressionForm::Aura,
+                serde_json::json!({ "alignment_affinity": "HERO" }),
+            ),
+            expression("kinetic_arc", ExpressionForm::Zone, serde_json::json!({})),
+        ];
+        let unlocked = HashSet::new();
+        let pick = |alignment| {
+            select_evolution_candidate(&source, &candidates, &unlocked, alignment).map(|id| id.0)
+        };
+
+        assert_eq!(pick(Some(Alignment::Hero)).as_deref(), Some("kinetic_shield"));
+        assert_eq!(pick(Some(Alignment::Villain)).as_deref(), Some("kinetic_crush"));
+        assert_eq!(pick(Some(Alignment::Vigilante)).as_deref(), Some("kinetic_arc"));
+        assert_eq!(pick(None).as_deref(), Some("kinetic_arc"));
+    }
+
+    /// Repository that knows a single expression; growth only ever looks one up.
+    struct OneExpressionRepo(ExpressionDef);
+
+    impl PowerRepository for OneExpressionRepo {
+        fn stats(&self) -> Result<crate::content::PowerStats, Box<dyn std::error::Error>> {
+            unreachable!()
+        }
+        fn expression(&self, expr_id: &ExpressionId) -> Result<ExpressionDef, RepositoryError> {
+            if *expr_id == self.0.id {
+                Ok(self.0.clone())
+            } else {
+                Err(RepositoryError::NotFound {
+                    kind: "expression",
+                    id: expr_id.0.clone(),
+                })
+            }
+        }
+        fn expressions_for_power(&self, _: PowerId) -> Result<Vec<ExpressionDef>, RepositoryError> {
+            unreachable!()
+        }
+        fn expressions_by_scale(
+            &self,
+            _: Scale,
+            _: usize,
+        ) -> Result<Vec<ExpressionDef>, Box<dyn std::error::Error>> {
+            unreachable!()
+        }
+        fn power_info(
+            &self,
+            _: PowerId,
+        ) -> Result<Option<crate::content::PowerInfo>, RepositoryError> {
+            unreachable!()
+        }
+        fn power_id_by_name(&self, _: &str) -> Result<Option<PowerId>, Box<dyn std::error::Error>> {
+            unreachable!()
+        }
+        fn power_tags(&self, _: PowerId) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+            unreachable!()
+        }
+        fn power_ids_by_tags(
+            &self,
+            _: &[String],
+            _: &[String],
+            _: &[String],
+        ) -> Result<Vec<PowerId>, Box<dyn std::error::Error>> {
+            unreachable!()
+        }
+        fn expressions_for_persona(
+            &self,
+            _: &str,
+        ) -> Result<Vec<crate::content::PersonaExpression>, Box<dyn std::error::Error>> {
+            unreachable!()
+        }
+        fn acquisition_profiles_for_origin(
+            &self,
+            _: &str,
+            _: &str,
+        ) -> Result<Vec<crate::content::OriginAcquisitionProfile>, Box<dyn std::error::Error>>
+        {
+            unreachable!()
+        }
+        fn acquisition_profiles(
+            &self,
+            _: PowerId,
+        ) -> Result<Vec<crate::content::PowerAcquisitionProfile>, Box<dyn std::error::Error>>
+        {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn unlock_rejects_unknown_expression_ids() {
+        let repo = OneExpressionRepo(expression(
+            "spark_jab",
+            ExpressionForm::Beam,
+            serde_json::json!({}),
+        ));
+        let known = repo.0.id.clone();
+        let mut growth = GrowthState::default();
+
+        let bogus = ExpressionId("no_such_expression".to_string());
+        assert!(matches!(
+            growth.unlock_expression(&repo, &bogus),
+            Err(ExpressionUnlockError::UnknownExpression(_))
+        ));
+        assert!(growth.unlocked_expressions.is_empty());
+
+        assert!(growth.unlock_expression(&repo, &known).expect("unlock"));
+        assert!(!growth.unlock_expression(&repo, &known).expect("relock"));
+        assert!(growth.lock_expression(&repo, &known).expect("lock"));
+        assert!(!growth.lock_expression(&repo, &known).expect("already locked"));
+        assert!(growth.lock_expression(&repo, &bogus).is_err());
+    }
+}
@@ -1,17 +1,26 @@
 use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::rules::signature::SignatureType;
 use crate::simulation::city::LocationId;
 use crate::simulation::combat::CombatConsequence;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Confidence gained from a first sighting; each repeat adds a step more.
+const CORROBORATION_BASE_GAIN: u8 = 10;
+const CORROBORATION_STEP_GAIN: u8 = 5;
+/// Repeats past this many stop adding extra weight.
+const CORROBORATION_MAX_REPEATS: u32 = 4;
+/// Confidence lost per quiet turn.
+const CORROBORATION_DECAY_PER_TURN: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PersonaHint {
     Civilian,
     Masked,
     Unknown,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IdentityEvidenceItem {
     pub evidence_id: u32,
     pub location_id: LocationId,
@@ -21,12 +30,44 @@ pub struct IdentityEvidenceItem {
     pub visual_quality: u8,
     pub suspect_features: Vec<String>,
     pub persona_hint: PersonaHint,
+    /// Persona the evidence was planted to point at, when not the real actor.
+    pub attributed_persona: Option<String>,
+    /// Planted evidence investigators have seen through.
+    pub debunked: bool,
+}
+
+impl IdentityEvidenceItem {
+    /// Persona investigators read this evidence as pointing to; None is the
+    /// real trail. Debunked decoys fold back into the real trail.
+    pub fn suspected_persona(&self) -> Option<&str> {
+        if self.debunked {
+            None
+        } else {
+            self.attributed_persona.as_deref()
+        }
+    }
+}
+
+/// Repeated sightings of one kind of suspect at one location. Confidence builds
+/// with each corroborating sighting and fades while the trail goes quiet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SightingTrail {
+    pub location_id: LocationId,
+    pub persona_hint: PersonaHint,
+    pub sightings: u32,
+    pub confidence: u8,
+    /// Tick of the latest counted sighting; evidence from the same tick is one
+    /// sighting however many signatures it carries.
+    #[serde(default)]
+    pub last_sighting_tick: Option<u64>,
 }
 
-#[derive(Resource, Debug, Default)]
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
 pub struct IdentityEvidenceStore {
     pub items: Vec<IdentityEvidenceItem>,
     next_id: u32,
+    #[serde(default)]
+    pub trails: Vec<SightingTrail>,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -36,6 +77,11 @@ pub struct IdentityEvidenceModifiers {
 }
 
 impl IdentityEvidenceStore {
+    pub fn sync_next_id(&mut self) {
+        let max_id = self.items.iter().map(|item| item.evidence_id).max().unwrap_or(0);
+        self.next_id = max_id;
+    }
+
     pub fn record(
         &mut self,
         location_id: LocationId,
@@ -57,9 +103,107 @@ impl IdentityEvidenceStore {
             visual_quality,
             suspect_features,
             persona_hint,
+            attributed_persona: None,
+            debunked: false,
         });
+        self.corroborate(location_id, persona_hint, time_tick, visual_quality);
         evidence_id
     }
+
+    fn corroborate(
+        &mut self,
+        location_id: LocationId,
+        persona_hint: PersonaHint,
+        time_tick: u64,
+        visual_quality: u8,
+    ) {
+        let index = match self.trails.iter().position(|trail| {
+            trail.location_id == location_id && trail.persona_hint == persona_hint
+        }) {
+            Some(index) => index,
+            None => {
+                self.trails.push(SightingTrail {
+                    location_id,
+                    persona_hint,
+                    sightings: 0,
+                    confidence: 0,
+                    last_sighting_tick: None,
+                });
+                self.trails.len() - 1
+            }
+        };
+        let trail = &mut self.trails[index];
+        if trail.last_sighting_tick == Some(time_tick) {
+            return;
+        }
+        trail.last_sighting_tick = Some(time_tick);
+        let repeats = trail.sightings.min(CORROBORATION_MAX_REPEATS) as u8;
+        let gain = CORROBORATION_BASE_GAIN + repeats * CORROBORATION_STEP_GAIN + visual_quality / 20;
+        trail.sightings += 1;
+        trail.confidence = trail.confidence.saturating_add(gain).min(100);
+    }
+
+    /// Fades every trail by `turns` quiet turns, dropping those gone cold.
+    pub fn tick_decay(&mut self, turns: u64) {
+        let loss = turns.saturating_mul(CORROBORATION_DECAY_PER_TURN as u64).min(u8::MAX as u64) as u8;
+        for trail in self.trails.iter_mut() {
+            trail.confidence = trail.confidence.saturating_sub(loss);
+        }
+        self.trails.retain(|trail| trail.confidence > 0);
+    }
+
+    /// How sure investigators are, 0-100, that sightings of this kind of
+    /// suspect are the same person; the strongest trail in the city counts.
+    pub fn persona_confidence(&self, persona_hint: &PersonaHint) -> u8 {
+        self.trails
+            .iter()
+            .filter(|trail| trail.persona_hint == *persona_hint)
+            .map(|trail| trail.confidence)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Confidence of the trail for this kind of suspect at one location.
+    pub fn trail_confidence(&self, location_id: LocationId, persona_hint: &PersonaHint) -> u8 {
+        self.trails
+            .iter()
+            .find(|trail| trail.location_id == location_id && trail.persona_hint == *persona_hint)
+            .map(|trail| trail.confidence)
+            .unwrap_or(0)
+    }
+
+    /// Points an existing item at a decoy persona.
+    pub fn attribute(&mut self, evidence_id: u32, persona_id: &str) -> bool {
+        let Some(item) = self
+            .items
+            .iter_mut()
+            .find(|item| item.evidence_id == evidence_id)
+        else {
+            return false;
+        };
+        item.attributed_persona = Some(persona_id.to_string());
+        true
+    }
+
+    /// Planted items still pointing at `persona_id`.
+    pub fn decoy_count(&self, persona_id: &str) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.suspected_persona() == Some(persona_id))
+            .count()
+    }
+
+    /// Marks every live decoy for `persona_id` as debunked. Returns how many.
+    pub fn debunk(&mut self, persona_id: &str) -> usize {
+        let mut debunked = 0;
+        for item in &mut self.items {
+            if item.suspected_persona() == Some(persona_id) {
+                item.debunked = true;
+                debunked += 1;
+            }
+        }
+        debunked
+    }
 }
 
 pub fn combat_consequence_modifiers(consequence: CombatConsequence) -> IdentityEvidenceModifiers {
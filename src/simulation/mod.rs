@@ -13,9 +13,11 @@ pub mod growth;
 pub mod pressure;
 pub mod origin;
 pub mod civilian;
+pub mod crafting;
 pub mod nemesis;
 pub mod endgame;
 pub mod economy;
 pub mod alien;
 pub mod cosmic;
 pub mod power_assignment;
+pub mod stats;
@@ -3,7 +3,16 @@ use std::collections::HashMap;
 use bevy_ecs::prelude::*;
 
 use crate::rules::signature::SignatureType;
+use crate::simulation::cast::{CharacterRelationship, PersistentCharacter};
 use crate::simulation::city::LocationId;
+use crate::simulation::combat::{CombatConsequence, CombatEnd};
+
+pub const PLAYER_CHARACTER_ID: &str = "player";
+pub const NEMESIS_ROLE: &str = "NEMESIS";
+pub const NEMESIS_RELATION: &str = "NEMESIS";
+
+const FEAR_PER_CASUALTY: i32 = 2;
+const RESENTMENT_PER_CASUALTY: i32 = 2;
 
 #[derive(Debug, Clone, Default)]
 pub struct NemesisSignaturePattern {
@@ -107,6 +116,7 @@ pub struct NemesisState {
     pub candidates: Vec<NemesisCandidate>,
     pub thresholds: Vec<NemesisAdaptationThreshold>,
     pub global_threat: i32,
+    pub resentment: HashMap<String, i32>,
 }
 
 impl Default for NemesisState {
@@ -134,6 +144,7 @@ impl Default for NemesisState {
                 },
             ],
             global_threat: 0,
+            resentment: HashMap::new(),
         }
     }
 }
@@ -142,4 +153,120 @@ impl NemesisState {
     pub fn apply_global_threat(&mut self, delta: i32) {
         self.global_threat = (self.global_threat + delta).clamp(0, 50);
     }
+
+    pub fn record_resentment(&mut self, faction_id: &str, resentment: i32) {
+        self.resentment
+            .insert(faction_id.to_string(), resentment.clamp(0, 100));
+    }
+
+    pub fn sync_resentment(&mut self, characters: &[PersistentCharacter]) {
+        for character in characters {
+            let Some(faction_id) = nemesis_faction(character) else {
+                continue;
+            };
+            if let Some(relation) = character.relationships.iter().find(|relation| {
+                relation.other_character_id == PLAYER_CHARACTER_ID
+                    && relation.relation_type == NEMESIS_RELATION
+                    && relation.end_tick.is_none()
+            }) {
+                self.record_resentment(faction_id, relation.resentment);
+            }
+        }
+    }
+
+    pub fn resentment_for(&self, faction_id: &str) -> i32 {
+        self.resentment.get(faction_id).copied().unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NemesisRelationshipDelta {
+    pub fear: i32,
+    pub resentment: i32,
+    pub trust: i32,
+}
+
+pub fn is_named_nemesis(character: &PersistentCharacter) -> bool {
+    character
+        .roles
+        .iter()
+        .any(|role| role.role_type == NEMESIS_ROLE && role.end_tick.is_none())
+}
+
+pub fn nemesis_faction(character: &PersistentCharacter) -> Option<&str> {
+    character
+        .roles
+        .iter()
+        .find(|role| role.role_type == NEMESIS_ROLE && role.end_tick.is_none())
+        .and_then(|role| role.faction_id.as_deref())
+}
+
+/// Moves a named nemesis's feelings towards the player after a clash. Beating
+/// them breeds resentment and fear; beating the player emboldens them. Fights
+/// that cost civilian lives push both further, a step per casualty.
+pub fn update_nemesis_relationship(
+    character: &mut PersistentCharacter,
+    end: CombatEnd,
+    consequence: CombatConsequence,
+    tick: u64,
+) -> &CharacterRelationship {
+    let mut delta = match end {
+        CombatEnd::OpponentsDefeated => NemesisRelationshipDelta {
+            fear: 6,
+            resentment: 12,
+            trust: -2,
+        },
+        CombatEnd::PlayerDefeated => NemesisRelationshipDelta {
+            fear: -6,
+            resentment: 3,
+            trust: -1,
+        },
+        CombatEnd::PlayerEscaped => NemesisRelationshipDelta {
+            fear: -2,
+            resentment: 6,
+            trust: 0,
+        },
+        CombatEnd::OpponentsSurrendered => NemesisRelationshipDelta {
+            fear: 3,
+            resentment: 5,
+            trust: 2,
+        },
+        CombatEnd::Resolved => NemesisRelationshipDelta {
+            fear: 0,
+            resentment: 2,
+            trust: 1,
+        },
+    };
+    let casualties = consequence.civilian_casualties as i32;
+    delta.fear += casualties * FEAR_PER_CASUALTY;
+    delta.resentment += casualties * RESENTMENT_PER_CASUALTY;
+
+    let index = match character.relationships.iter().position(|relation| {
+        relation.other_character_id == PLAYER_CHARACTER_ID
+            && relation.relation_type == NEMESIS_RELATION
+            && relation.end_tick.is_none()
+    }) {
+        Some(index) => index,
+        None => {
+            character.relationships.push(CharacterRelationship {
+                other_character_id: PLAYER_CHARACTER_ID.to_string(),
+                relation_type: NEMESIS_RELATION.to_string(),
+                trust: 0,
+                fear: 0,
+                resentment: 0,
+                is_public: false,
+                start_tick: tick,
+                end_tick: None,
+            });
+            character.relationships.len() - 1
+        }
+    };
+    let relation = &mut character.relationships[index];
+    relation.fear = (relation.fear + delta.fear).clamp(0, 100);
+    relation.resentment = (relation.resentment + delta.resentment).clamp(0, 100);
+    relation.trust = (relation.trust + delta.trust).clamp(-100, 100);
+    if consequence.publicness >= 50 {
+        relation.is_public = true;
+    }
+    relation
 }
@@ -1,21 +1,25 @@
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::path::Path;
 
 use bevy_ecs::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::content::{OriginAcquisitionProfile, PowerRepository, SqlitePowerRepository};
+use crate::content::{
+    OriginAcquisitionProfile, PowerRepository, SqlitePowerRepository, DEFAULT_CONTENT_DB_PATH,
+};
 use crate::components::identity::SuperIdentity;
 use crate::components::world::Position;
 use crate::rules::signature::{SignatureInstance, SignatureSpec, SignatureType};
 use crate::simulation::cast::CharacterPower;
 use crate::simulation::city::CityState;
 use crate::simulation::evidence::WorldEvidence;
-use crate::simulation::pressure::PressureState;
+use crate::simulation::pressure::{PressureAxis, PressureState};
 
 const DEFAULT_ORIGINS_PATH: &str = "./assets/data/origins.json";
 const DEFAULT_ORIGIN_PATHS_PATH: &str = "./assets/data/origin_paths.json";
-const DEFAULT_CONTENT_DB_PATH: &str = "./assets/db/content_v1.db";
+/// Reputation lost for walking away from an origin path mid-way.
+pub const ORIGIN_ABANDON_REPUTATION_PENALTY: i32 = -2;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OriginCatalog {
@@ -64,10 +68,33 @@ pub struct OriginPathStage {
     pub id: String,
     pub label: String,
     pub summary: String,
+    /// Stages that must complete before this one opens. When no stage in a
+    /// path declares any, the path runs linearly in listed order.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
     #[serde(default)]
     pub requirement: OriginStageRequirement,
     #[serde(default)]
     pub reward: OriginStageReward,
+    #[serde(default)]
+    pub failure_requirement: Option<OriginFailureRequirement>,
+}
+
+/// Conditions that fail a stage if they are breached before its progress is
+/// met. Checked on each tick of the path.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct OriginFailureRequirement {
+    /// Ticks the stage may take before it fails.
+    #[serde(default)]
+    pub deadline_ticks: Option<u32>,
+    /// Axis whose pressure fails the stage once it reaches `pressure_max`.
+    #[serde(default)]
+    pub pressure_axis: Option<PressureAxis>,
+    #[serde(default)]
+    pub pressure_max: Option<f32>,
+    /// Applied instead of the stage reward when the stage fails.
+    #[serde(default)]
+    pub penalty: OriginStageReward,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -156,13 +183,35 @@ pub struct OriginState {
 #[derive(Resource, Debug, Clone, Default)]
 pub struct OriginQuestState {
     pub path_id: Option<String>,
-    pub stage_index: usize,
-    pub stage_progress: u32,
+    /// Every open stage, keyed by stage id. Linear paths have at most one.
+    pub active_stages: BTreeMap<String, OriginStageProgress>,
     pub completed: bool,
+    /// Set once the path ends without completing; it no longer advances.
+    pub ended: Option<OriginPathEnd>,
     pub completed_stages: Vec<String>,
     pub discovered_paths: Vec<String>,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OriginStageProgress {
+    pub progress: u32,
+    /// Ticks spent open, for failure deadlines.
+    pub ticks: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OriginPathEnd {
+    Failed { stage_id: String },
+    Abandoned { stage_id: String },
+}
+
+impl OriginQuestState {
+    /// A path is selected and has neither completed nor ended.
+    pub fn is_active(&self) -> bool {
+        self.path_id.is_some() && !self.completed && self.ended.is_none()
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct OriginEffectReport {
     pub event_tags: Vec<String>,
@@ -181,6 +230,7 @@ pub enum OriginError {
     Parse(serde_json::Error),
     Repo(String),
     NotFound(String),
+    Invalid(String),
 }
 
 impl std::fmt::Display for OriginError {
@@ -190,6 +240,7 @@ impl std::fmt::Display for OriginError {
             OriginError::Parse(err) => write!(f, "Parse error: {}", err),
             OriginError::Repo(err) => write!(f, "Repository error: {}", err),
             OriginError::NotFound(message) => write!(f, "Not found: {}", message),
+            OriginError::Invalid(message) => write!(f, "Invalid data: {}", message),
         }
     }
 }
@@ -242,12 +293,10 @@ pub fn apply_origin_effects(
 
     if quest.path_id.is_some() {
         for tag in report.event_tags {
-            let before_stage = quest.stage_index;
-            let before_progress = quest.stage_progress;
+            let before_stages = quest.active_stages.clone();
             let before_completed = quest.completed_stages.len();
             let mut rewards = register_origin_event(quest, catalog, &tag);
-            if before_stage != quest.stage_index
-                || before_progress != quest.stage_progress
+            if before_stages != quest.active_stages
                 || before_completed != quest.completed_stages.len()
             {
                 application
@@ -393,7 +442,50 @@ pub fn load_origin_catalog(path: &str) -> Result<OriginCatalog, OriginError> {
 
 pub fn load_origin_path_catalog(path: &str) -> Result<OriginPathCatalog, OriginError> {
     let data = fs::read_to_string(path).map_err(OriginError::Io)?;
-    serde_json::from_str(&data).map_err(OriginError::Parse)
+    let catalog: OriginPathCatalog = serde_json::from_str(&data).map_err(OriginError::Parse)?;
+    catalog.validate()?;
+    Ok(catalog)
+}
+
+impl OriginPathCatalog {
+    /// Stage dependencies must name a stage listed earlier in the same path,
+    /// which also rules out cycles.
+    pub fn validate(&self) -> Result<(), OriginError> {
+        for path in &self.paths {
+            let mut seen = HashSet::new();
+            for stage in &path.stages {
+                if let Some(missing) = stage
+                    .depends_on
+                    .iter()
+                    .find(|dependency| !seen.contains(dependency.as_str()))
+                {
+                    return Err(OriginError::Invalid(format!(
+                        "origin path {} stage {} depends on {}, which is not an earlier stage",
+                        path.id, stage.id, missing
+                    )));
+                }
+                seen.insert(stage.id.as_str());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl OriginPathDefinition {
+    fn is_linear(&self) -> bool {
+        self.stages.iter().all(|stage| stage.depends_on.is_empty())
+    }
+
+    /// Whether the stage at `index` may open once `completed` have finished.
+    fn stage_unblocked(&self, index: usize, completed: &[String]) -> bool {
+        if self.is_linear() {
+            return index == 0 || completed.contains(&self.stages[index - 1].id);
+        }
+        self.stages[index]
+            .depends_on
+            .iter()
+            .all(|dependency| completed.contains(dependency))
+    }
 }
 
 pub fn load_default_origin_path_catalog() -> Result<OriginPathCatalog, OriginError> {
@@ -438,27 +530,130 @@ pub fn start_origin_path(
         )));
     };
     state.path_id = Some(path.id.clone());
-    state.stage_index = 0;
-    state.stage_progress = 0;
+    state.active_stages.clear();
     state.completed = false;
+    state.ended = None;
     state.completed_stages.clear();
+    open_unblocked_stages(state, path);
     Ok(path.clone())
 }
 
+/// The first open stage in listed order; None once the path has completed,
+/// failed or been abandoned.
 pub fn current_origin_stage<'a>(
     state: &OriginQuestState,
     catalog: &'a OriginPathCatalog,
 ) -> Option<&'a OriginPathStage> {
-    let path_id = state.path_id.as_deref()?;
-    let path = catalog.paths.iter().find(|path| path.id == path_id)?;
-    path.stages.get(state.stage_index)
+    active_origin_stages(state, catalog).into_iter().next()
+}
+
+/// Every open stage in listed order. Parallel paths can have several.
+pub fn active_origin_stages<'a>(
+    state: &OriginQuestState,
+    catalog: &'a OriginPathCatalog,
+) -> Vec<&'a OriginPathStage> {
+    if state.ended.is_some() {
+        return Vec::new();
+    }
+    let Some(path) = state
+        .path_id
+        .as_deref()
+        .and_then(|path_id| catalog.paths.iter().find(|path| path.id == path_id))
+    else {
+        return Vec::new();
+    };
+    path.stages
+        .iter()
+        .filter(|stage| state.active_stages.contains_key(&stage.id))
+        .collect()
 }
 
+/// Advances every open stage by one tick. An open stage still short of its
+/// progress after the tick fails the path if its deadline has passed or its
+/// pressure ceiling is reached; the returned rewards then end with that
+/// stage's penalty.
 pub fn tick_origin_path(
     state: &mut OriginQuestState,
     catalog: &OriginPathCatalog,
+    pressure: &PressureState,
 ) -> Vec<OriginStageReward> {
-    advance_origin_path(state, catalog, OriginAdvance::Tick)
+    if state.is_active() {
+        for stage in state.active_stages.values_mut() {
+            stage.ticks = stage.ticks.saturating_add(1);
+        }
+    }
+    let mut rewards = advance_origin_path(state, catalog, OriginAdvance::Tick);
+    if let Some(penalty) = check_origin_failure(state, catalog, pressure) {
+        rewards.push(penalty);
+    }
+    rewards
+}
+
+/// Walks away from the active path, returning the reputation penalty to apply.
+pub fn abandon_origin_path(
+    state: &mut OriginQuestState,
+    catalog: &OriginPathCatalog,
+) -> Result<OriginStageReward, OriginError> {
+    if !state.is_active() {
+        return Err(OriginError::NotFound("active origin path".to_string()));
+    }
+    let stage_id = current_origin_stage(state, catalog)
+        .map(|stage| stage.id.clone())
+        .unwrap_or_default();
+    state.ended = Some(OriginPathEnd::Abandoned { stage_id });
+    Ok(OriginStageReward {
+        reputation_delta: ORIGIN_ABANDON_REPUTATION_PENALTY,
+        notes: Some("Abandoned the origin path.".to_string()),
+        ..OriginStageReward::default()
+    })
+}
+
+fn check_origin_failure(
+    state: &mut OriginQuestState,
+    catalog: &OriginPathCatalog,
+    pressure: &PressureState,
+) -> Option<OriginStageReward> {
+    if !state.is_active() {
+        return None;
+    }
+    let (stage, failure) = active_origin_stages(state, catalog)
+        .into_iter()
+        .filter_map(|stage| Some((stage, stage.failure_requirement.as_ref()?)))
+        .find(|(stage, failure)| {
+            let ticks = state
+                .active_stages
+                .get(&stage.id)
+                .map(|progress| progress.ticks)
+                .unwrap_or(0);
+            let past_deadline = failure
+                .deadline_ticks
+                .is_some_and(|deadline| ticks >= deadline);
+            let over_pressure = match (failure.pressure_axis, failure.pressure_max) {
+                (Some(axis), Some(max)) => pressure.axis(axis) >= max,
+                _ => false,
+            };
+            past_deadline || over_pressure
+        })?;
+    state.ended = Some(OriginPathEnd::Failed {
+        stage_id: stage.id.clone(),
+    });
+    Some(failure.penalty.clone())
+}
+
+/// Opens every stage whose dependencies have completed and that is neither
+/// open nor complete yet.
+fn open_unblocked_stages(state: &mut OriginQuestState, path: &OriginPathDefinition) {
+    for (index, stage) in path.stages.iter().enumerate() {
+        if state.active_stages.contains_key(&stage.id) || state.completed_stages.contains(&stage.id)
+        {
+            continue;
+        }
+        if path.stage_unblocked(index, &state.completed_stages) {
+            state
+                .active_stages
+                .insert(stage.id.clone(), OriginStageProgress::default());
+        }
+    }
 }
 
 pub fn register_origin_event(
@@ -606,7 +801,7 @@ fn advance_origin_path(
     advance: OriginAdvance<'_>,
 ) -> Vec<OriginStageReward> {
     let mut rewards = Vec::new();
-    if state.completed {
+    if state.completed || state.ended.is_some() {
         return rewards;
     }
     let path_id = match state.path_id.as_deref() {
@@ -616,44 +811,50 @@ fn advance_origin_path(
     let Some(path) = catalog.paths.iter().find(|path| path.id == path_id) else {
         return rewards;
     };
-    if state.stage_index >= path.stages.len() {
+    open_unblocked_stages(state, path);
+    if state.active_stages.is_empty() {
         state.completed = true;
         return rewards;
     }
-    let stage = &path.stages[state.stage_index];
-    let progress_delta = match advance {
-        OriginAdvance::Tick => stage.requirement.progress_per_tick,
-        OriginAdvance::Event(tag) => {
-            if stage
-                .requirement
-                .event_tags
-                .iter()
-                .any(|entry| entry.eq_ignore_ascii_case(tag))
-            {
-                stage.requirement.event_progress.max(1)
-            } else {
-                0
+    for stage in &path.stages {
+        let Some(entry) = state.active_stages.get_mut(&stage.id) else {
+            continue;
+        };
+        let progress_delta = match advance {
+            OriginAdvance::Tick => stage.requirement.progress_per_tick,
+            OriginAdvance::Event(tag) => {
+                if stage
+                    .requirement
+                    .event_tags
+                    .iter()
+                    .any(|entry| entry.eq_ignore_ascii_case(tag))
+                {
+                    stage.requirement.event_progress.max(1)
+                } else {
+                    0
+                }
             }
-        }
-    };
-    if progress_delta == 0 {
-        return rewards;
+        };
+        entry.progress = entry.progress.saturating_add(progress_delta);
     }
-    state.stage_progress = state.stage_progress.saturating_add(progress_delta);
-    loop {
-        if state.stage_index >= path.stages.len() {
-            state.completed = true;
-            break;
-        }
-        let stage = &path.stages[state.stage_index];
+    for stage in &path.stages {
         let needed = stage.requirement.progress_needed.max(1);
-        if state.stage_progress < needed {
-            break;
+        let done = state
+            .active_stages
+            .get(&stage.id)
+            .is_some_and(|entry| entry.progress >= needed);
+        if !done {
+            continue;
         }
+        state.active_stages.remove(&stage.id);
         state.completed_stages.push(stage.id.clone());
         rewards.push(stage.reward.clone());
-        state.stage_index = state.stage_index.saturating_add(1);
-        state.stage_progress = 0;
+    }
+    if !rewards.is_empty() {
+        open_unblocked_stages(state, path);
+        if state.active_stages.is_empty() {
+            state.completed = true;
+        }
     }
     rewards
 }
@@ -661,18 +862,19 @@ fn advance_origin_path(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::content::PowerId;
 
     #[test]
     fn weighted_choice_stays_in_bounds() {
         let profiles = vec![
-            AcquisitionProfile {
+            OriginAcquisitionProfile {
                 acq_id: "a".to_string(),
-                power_id: 1,
+                power_id: PowerId(1),
                 rarity_weight: 10,
             },
-            AcquisitionProfile {
+            OriginAcquisitionProfile {
                 acq_id: "b".to_string(),
-                power_id: 2,
+                power_id: PowerId(2),
                 rarity_weight: 1,
             },
         ];
@@ -680,4 +882,165 @@ mod tests {
         let idx = weighted_choice_index(&profiles, &mut seed);
         assert!(idx < profiles.len());
     }
+
+    fn deadline_catalog() -> OriginPathCatalog {
+        serde_json::from_value(serde_json::json!({
+            "paths": [{
+                "id": "lab_escape",
+                "label": "Lab Escape",
+                "summary": "Get out before the facility locks down.",
+                "stages": [
+                    {
+                        "id": "vents",
+                        "label": "Vents",
+                        "summary": "Crawl through the ducts.",
+                        "requirement": { "progress_needed": 2, "progress_per_tick": 1 }
+                    },
+                    {
+                        "id": "keycard",
+                        "label": "Keycard",
+                        "summary": "Lift a keycard before the shift change.",
+                        "requirement": { "progress_needed": 1, "event_tags": ["KEYCARD"] },
+                        "failure_requirement": {
+                            "deadline_ticks": 3,
+                            "pressure_axis": "institutional",
+                            "pressure_max": 80.0,
+                            "penalty": { "reputation_delta": -3 }
+                        }
+                    }
+                ]
+            }]
+        }))
+        .expect("catalog")
+    }
+
+    #[test]
+    fn missing_a_stage_deadline_fails_the_path() {
+        let catalog = deadline_catalog();
+        let pressure = PressureState::default();
+        let mut quest = OriginQuestState::default();
+        start_origin_path(&mut quest, &catalog, "lab_escape").expect("start");
+
+        tick_origin_path(&mut quest, &catalog, &pressure);
+        let rewards = tick_origin_path(&mut quest, &catalog, &pressure);
+        assert_eq!(rewards.len(), 1);
+        assert_eq!(
+            current_origin_stage(&quest, &catalog).unwrap().id,
+            "keycard"
+        );
+
+        assert!(tick_origin_path(&mut quest, &catalog, &pressure).is_empty());
+        assert!(tick_origin_path(&mut quest, &catalog, &pressure).is_empty());
+        let penalty = tick_origin_path(&mut quest, &catalog, &pressure);
+        assert_eq!(penalty.len(), 1);
+        assert_eq!(penalty[0].reputation_delta, -3);
+        assert_eq!(
+            quest.ended,
+            Some(OriginPathEnd::Failed {
+                stage_id: "keycard".to_string()
+            })
+        );
+        assert!(current_origin_stage(&quest, &catalog).is_none());
+        assert!(register_origin_event(&mut quest, &catalog, "KEYCARD").is_empty());
+        assert!(!quest.completed);
+
+        start_origin_path(&mut quest, &catalog, "lab_escape").expect("restart");
+        let spiking = PressureState {
+            institutional: 85.0,
+            ..PressureState::default()
+        };
+        tick_origin_path(&mut quest, &catalog, &spiking);
+        assert!(
+            quest.ended.is_none(),
+            "the first stage has no failure requirement"
+        );
+        let penalty = tick_origin_path(&mut quest, &catalog, &spiking);
+        assert_eq!(
+            penalty.last().map(|reward| reward.reputation_delta),
+            Some(-3)
+        );
+        assert!(matches!(quest.ended, Some(OriginPathEnd::Failed { .. })));
+    }
+
+    #[test]
+    fn abandoning_mid_path_stops_progress_and_costs_reputation() {
+        let catalog = deadline_catalog();
+        let pressure = PressureState::default();
+        let mut quest = OriginQuestState::default();
+        assert!(abandon_origin_path(&mut quest, &catalog).is_err());
+
+        start_origin_path(&mut quest, &catalog, "lab_escape").expect("start");
+        tick_origin_path(&mut quest, &catalog, &pressure);
+        let penalty = abandon_origin_path(&mut quest, &catalog).expect("abandon");
+        assert_eq!(penalty.reputation_delta, ORIGIN_ABANDON_REPUTATION_PENALTY);
+        assert_eq!(
+            quest.ended,
+            Some(OriginPathEnd::Abandoned {
+                stage_id: "vents".to_string()
+            })
+        );
+        assert!(current_origin_stage(&quest, &catalog).is_none());
+        assert!(tick_origin_path(&mut quest, &catalog, &pressure).is_empty());
+        assert_eq!(quest.active_stages["vents"].progress, 1);
+        assert!(abandon_origin_path(&mut quest, &catalog).is_err());
+    }
+
+    #[test]
+    fn parallel_stages_take_progress_from_different_events_in_one_turn() {
+        let catalog: OriginPathCatalog = serde_json::from_value(serde_json::json!({
+            "paths": [{
+                "id": "twin_leads",
+                "label": "Twin Leads",
+                "summary": "Chase two leads at once.",
+                "stages": [
+                    {
+                        "id": "docks",
+                        "label": "Docks",
+                        "summary": "Shake down the dock crews.",
+                        "requirement": { "progress_needed": 1, "event_tags": ["DOCKS"] }
+                    },
+                    {
+                        "id": "archive",
+                        "label": "Archive",
+                        "summary": "Dig through the city archive.",
+                        "requirement": { "progress_needed": 2, "event_tags": ["ARCHIVE"] }
+                    },
+                    {
+                        "id": "confront",
+                        "label": "Confront",
+                        "summary": "Bring both leads together.",
+                        "depends_on": ["docks", "archive"],
+                        "requirement": { "progress_needed": 1, "progress_per_tick": 1 }
+                    }
+                ]
+            }]
+        }))
+        .expect("catalog");
+        catalog.validate().expect("valid graph");
+        let pressure = PressureState::default();
+        let mut quest = OriginQuestState::default();
+        start_origin_path(&mut quest, &catalog, "twin_leads").expect("start");
+        let open = |quest: &OriginQuestState| {
+            active_origin_stages(quest, &catalog)
+                .into_iter()
+                .map(|stage| stage.id.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(open(&quest), vec!["docks", "archive"]);
+
+        assert!(tick_origin_path(&mut quest, &catalog, &pressure).is_empty());
+        let mut fire = |tag| register_origin_event(&mut quest, &catalog, tag).len();
+        assert_eq!(fire("ARCHIVE"), 0);
+        assert_eq!(fire("DOCKS"), 1);
+        assert_eq!(fire("ARCHIVE"), 1);
+        assert_eq!(open(&quest), vec!["confront"]);
+
+        assert_eq!(tick_origin_path(&mut quest, &catalog, &pressure).len(), 1);
+        assert!(quest.completed);
+        assert_eq!(quest.completed_stages, vec!["docks", "archive", "confront"]);
+
+        let mut broken = catalog.clone();
+        broken.paths[0].stages[0].depends_on = vec!["confront".to_string()];
+        assert!(matches!(broken.validate(), Err(OriginError::Invalid(_))));
+    }
 }
@@ -1,8 +1,61 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+
 use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::rules::use_power::{PressureModifiers, WorldState};
+use crate::simulation::endgame::EndgameState;
+
+const DEFAULT_PRESSURE_MODIFIERS_PATH: &str = "./assets/data/pressure_modifiers.json";
+
+/// Ticks of per-axis readings kept for trend queries.
+pub const PRESSURE_HISTORY_LEN: usize = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PressureAxis {
+    Temporal,
+    Identity,
+    Institutional,
+    Moral,
+    Resource,
+    Psychological,
+}
+
+impl PressureAxis {
+    pub const ALL: [PressureAxis; 6] = [
+        PressureAxis::Temporal,
+        PressureAxis::Identity,
+        PressureAxis::Institutional,
+        PressureAxis::Moral,
+        PressureAxis::Resource,
+        PressureAxis::Psychological,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            PressureAxis::Temporal => "temporal",
+            PressureAxis::Identity => "identity",
+            PressureAxis::Institutional => "institutional",
+            PressureAxis::Moral => "moral",
+            PressureAxis::Resource => "resource",
+            PressureAxis::Psychological => "psychological",
+        }
+    }
 
-use crate::rules::use_power::PressureModifiers;
+    /// Position of this axis in `ALL` and in each history reading.
+    pub fn index(self) -> usize {
+        self as usize
+    }
 
-#[derive(Resource, Debug, Clone, Copy)]
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|axis| axis.key() == key)
+    }
+}
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
 pub struct PressureState {
     pub temporal: f32,
     pub identity: f32,
@@ -10,6 +63,9 @@ pub struct PressureState {
     pub moral: f32,
     pub resource: f32,
     pub psychological: f32,
+    /// Readings per tick, oldest first, ordered like `PressureAxis::ALL`.
+    #[serde(default)]
+    pub history: VecDeque<[f32; 6]>,
 }
 
 impl Default for PressureState {
@@ -21,20 +77,274 @@ impl Default for PressureState {
             moral: 0.0,
             resource: 0.0,
             psychological: 0.0,
+            history: VecDeque::new(),
+        }
+    }
+}
+
+/// Per-channel weights; a weight of 1.0 lets that channel alone saturate the blend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PressureChannelWeights {
+    pub temporal: f32,
+    pub identity: f32,
+    pub institutional: f32,
+    pub moral: f32,
+    pub resource: f32,
+    pub psychological: f32,
+}
+
+impl PressureChannelWeights {
+    fn blend(&self, pressure: &PressureState) -> f32 {
+        let weighted = pressure.temporal * self.temporal
+            + pressure.identity * self.identity
+            + pressure.institutional * self.institutional
+            + pressure.moral * self.moral
+            + pressure.resource * self.resource
+            + pressure.psychological * self.psychological;
+        (weighted / 100.0).clamp(0.0, 1.0)
+    }
+
+    fn is_valid(&self) -> bool {
+        [
+            self.temporal,
+            self.identity,
+            self.institutional,
+            self.moral,
+            self.resource,
+            self.psychological,
+        ]
+        .iter()
+        .all(|weight| weight.is_finite() && *weight >= 0.0)
+    }
+}
+
+/// Curve from pressure channels to the base cost/risk scales, before endgame
+/// modifiers are layered on top.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PressureModifierConfig {
+    pub cost_weights: PressureChannelWeights,
+    pub risk_weights: PressureChannelWeights,
+    pub max_cost_bonus: f64,
+    pub max_risk_bonus: f64,
+}
+
+impl Default for PressureModifierConfig {
+    fn default() -> Self {
+        Self {
+            cost_weights: PressureChannelWeights {
+                temporal: 0.5,
+                resource: 0.5,
+                ..PressureChannelWeights::default()
+            },
+            risk_weights: PressureChannelWeights {
+                identity: 0.25,
+                institutional: 0.25,
+                moral: 0.25,
+                psychological: 0.25,
+                ..PressureChannelWeights::default()
+            },
+            max_cost_bonus: 0.15,
+            max_risk_bonus: 0.2,
         }
     }
 }
 
+#[derive(Debug)]
+pub enum PressureConfigError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+    Invalid(String),
+}
+
+impl std::fmt::Display for PressureConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PressureConfigError::Io(err) => write!(f, "I/O error: {}", err),
+            PressureConfigError::Parse(err) => write!(f, "Parse error: {}", err),
+            PressureConfigError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for PressureConfigError {}
+
+impl PressureModifierConfig {
+    pub fn load_default() -> Result<Self, PressureConfigError> {
+        Self::load_from_path(Path::new(DEFAULT_PRESSURE_MODIFIERS_PATH))
+    }
+
+    pub fn load_from_path(path: &Path) -> Result<Self, PressureConfigError> {
+        let data = fs::read_to_string(path).map_err(PressureConfigError::Io)?;
+        let config: Self = serde_json::from_str(&data).map_err(PressureConfigError::Parse)?;
+        config.validate().map_err(PressureConfigError::Invalid)?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.cost_weights.is_valid() || !self.risk_weights.is_valid() {
+            return Err("pressure modifier weights must be finite and non-negative".to_string());
+        }
+        if !(self.max_cost_bonus.is_finite() && self.max_risk_bonus.is_finite()) {
+            return Err("pressure modifier bonuses must be finite".to_string());
+        }
+        if self.max_cost_bonus <= -1.0 || self.max_risk_bonus <= -1.0 {
+            return Err("pressure modifier bonuses must stay above -1.0".to_string());
+        }
+        Ok(())
+    }
+}
+
 impl PressureState {
+    pub fn axis(&self, axis: PressureAxis) -> f32 {
+        match axis {
+            PressureAxis::Temporal => self.temporal,
+            PressureAxis::Identity => self.identity,
+            PressureAxis::Institutional => self.institutional,
+            PressureAxis::Moral => self.moral,
+            PressureAxis::Resource => self.resource,
+            PressureAxis::Psychological => self.psychological,
+        }
+    }
+
+    pub fn axis_mut(&mut self, axis: PressureAxis) -> &mut f32 {
+        match axis {
+            PressureAxis::Temporal => &mut self.temporal,
+            PressureAxis::Identity => &mut self.identity,
+            PressureAxis::Institutional => &mut self.institutional,
+            PressureAxis::Moral => &mut self.moral,
+            PressureAxis::Resource => &mut self.resource,
+            PressureAxis::Psychological => &mut self.psychological,
+        }
+    }
+
+    /// Appends the current readings, dropping the oldest once the buffer
+    /// holds `PRESSURE_HISTORY_LEN` ticks.
+    pub fn record_history(&mut self) {
+        if self.history.len() >= PRESSURE_HISTORY_LEN {
+            self.history.pop_front();
+        }
+        let reading = PressureAxis::ALL.map(|axis| self.axis(axis));
+        self.history.push_back(reading);
+    }
+
+    /// Least-squares slope of `axis` over the recorded history, in points per
+    /// tick. Zero until at least two ticks have been recorded.
+    pub fn trend(&self, axis: PressureAxis) -> f32 {
+        let samples = self.history.len();
+        if samples < 2 {
+            return 0.0;
+        }
+        let index = axis.index();
+        let mean_x = (samples - 1) as f32 / 2.0;
+        let mean_y = self
+            .history
+            .iter()
+            .map(|reading| reading[index])
+            .sum::<f32>()
+            / samples as f32;
+        let (covariance, variance) = self.history.iter().enumerate().fold(
+            (0.0, 0.0),
+            |(covariance, variance), (tick, reading)| {
+                let dx = tick as f32 - mean_x;
+                (
+                    covariance + dx * (reading[index] - mean_y),
+                    variance + dx * dx,
+                )
+            },
+        );
+        covariance / variance
+    }
+
     pub fn to_modifiers(&self) -> PressureModifiers {
-        let cost_pressure = ((self.resource + self.temporal) / 200.0).clamp(0.0, 1.0);
-        let risk_pressure = ((self.identity + self.institutional + self.moral + self.psychological)
-            / 400.0)
-            .clamp(0.0, 1.0);
+        self.to_modifiers_with(&PressureModifierConfig::default())
+    }
+
+    pub fn to_modifiers_with(&self, config: &PressureModifierConfig) -> PressureModifiers {
+        let cost_pressure = config.cost_weights.blend(self);
+        let risk_pressure = config.risk_weights.blend(self);
 
         PressureModifiers {
-            cost_scale: 1.0 + cost_pressure as f64 * 0.15,
-            risk_scale: 1.0 + risk_pressure as f64 * 0.2,
+            cost_scale: 1.0 + cost_pressure as f64 * config.max_cost_bonus,
+            risk_scale: 1.0 + risk_pressure as f64 * config.max_risk_bonus,
         }
     }
 }
+
+/// Recomputes the world's cost/risk scales from pressure, the configured curve
+/// and the current endgame phase.
+pub fn apply_pressure_modifiers(
+    world: &mut WorldState,
+    pressure: &PressureState,
+    config: &PressureModifierConfig,
+    endgame_state: &EndgameState,
+) {
+    let base = pressure.to_modifiers_with(config);
+    world.pressure = endgame_state.apply_modifiers(base);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_curve_changes_world_pressure_for_same_state() {
+        let pressure = PressureState {
+            moral: 80.0,
+            resource: 20.0,
+            ..PressureState::default()
+        };
+        let endgame = EndgameState::default();
+
+        let mut default_world = WorldState::default();
+        let default_curve = PressureModifierConfig::default();
+        apply_pressure_modifiers(&mut default_world, &pressure, &default_curve, &endgame);
+        assert!((default_world.pressure.cost_scale - 1.015).abs() < 1e-6);
+
+        let moral_costs = PressureModifierConfig {
+            cost_weights: PressureChannelWeights {
+                moral: 1.0,
+                ..PressureChannelWeights::default()
+            },
+            ..PressureModifierConfig::default()
+        };
+        assert!(moral_costs.validate().is_ok());
+        let mut tuned_world = WorldState::default();
+        apply_pressure_modifiers(&mut tuned_world, &pressure, &moral_costs, &endgame);
+        assert!((tuned_world.pressure.cost_scale - 1.12).abs() < 1e-6);
+        assert_eq!(
+            tuned_world.pressure.risk_scale,
+            default_world.pressure.risk_scale
+        );
+    }
+
+    #[test]
+    fn trend_follows_the_last_readings_and_survives_a_round_trip() {
+        let mut pressure = PressureState::default();
+        assert_eq!(pressure.trend(PressureAxis::Moral), 0.0);
+
+        for tick in 0..(PRESSURE_HISTORY_LEN + 10) {
+            pressure.moral = if tick < 10 {
+                50.0
+            } else {
+                (tick - 10) as f32 * 2.0
+            };
+            pressure.identity = 60.0 - tick as f32;
+            pressure.record_history();
+        }
+        assert_eq!(pressure.history.len(), PRESSURE_HISTORY_LEN);
+        assert!((pressure.trend(PressureAxis::Moral) - 2.0).abs() < 1e-4);
+        assert!((pressure.trend(PressureAxis::Identity) + 1.0).abs() < 1e-4);
+        assert_eq!(pressure.trend(PressureAxis::Temporal), 0.0);
+
+        let json = serde_json::to_string(&pressure).expect("serialize");
+        let restored: PressureState = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(restored.history, pressure.history);
+        let legacy: PressureState = serde_json::from_str(
+            r#"{"temporal":1.0,"identity":2.0,"institutional":3.0,"moral":4.0,"resource":5.0,"psychological":6.0}"#,
+        )
+        .expect("legacy state");
+        assert!(legacy.history.is_empty());
+    }
+}
@@ -3,9 +3,15 @@ use std::collections::{HashMap, HashSet};
 use bevy_ecs::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::simulation::case::CaseRegistry;
 use crate::simulation::city::{CityEvent, CityEventKind, CityId, CityState, LocationId};
+use crate::simulation::civilian::CivilianState;
+use crate::simulation::evidence::WorldEvidence;
 use crate::simulation::pressure::PressureState;
 
+/// Cash it takes to uproot a life and set up in another city.
+pub const RELOCATION_COST_CR: i32 = 2_000;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RegionId(pub u32);
 
@@ -15,7 +21,7 @@ pub struct CountryId(pub u32);
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ContinentId(pub u32);
 
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
 pub struct RegionState {
     pub continents: HashMap<ContinentId, ContinentProfile>,
     pub countries: HashMap<CountryId, CountryProfile>,
@@ -23,7 +29,7 @@ pub struct RegionState {
     pub global_pressure: GlobalPressure,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegionProfile {
     pub id: RegionId,
     pub name: String,
@@ -35,7 +41,7 @@ pub struct RegionProfile {
     pub escalation: RegionEscalation,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CountryProfile {
     pub id: CountryId,
     pub name: String,
@@ -46,7 +52,7 @@ pub struct CountryProfile {
     pub escalation: RegionEscalation,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContinentProfile {
     pub id: ContinentId,
     pub name: String,
@@ -56,7 +62,7 @@ pub struct ContinentProfile {
     pub escalation: RegionEscalation,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RegionEscalation {
     Stable,
     Alert,
@@ -93,7 +99,7 @@ impl GlobalEscalation {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct GlobalPressure {
     pub total: f32,
     pub escalation: GlobalEscalation,
@@ -113,6 +119,13 @@ pub struct RegionEvent {
 #[derive(Debug, Clone)]
 pub enum RegionEventKind {
     CityHeatResponseChanged { response: crate::simulation::city::HeatResponse },
+    CityTurfEroded { faction_id: String, influence: u16 },
+    CityPatrolSuppressed { faction_id: String, influence: u16 },
+    CityEnvironmentAltered {
+        field: crate::rules::expression::EnvironmentField,
+        delta: i32,
+        value: i32,
+    },
 }
 
 #[derive(Resource, Debug, Default)]
@@ -184,7 +197,7 @@ impl Default for RegionState {
                 name: "Metro Region".to_string(),
                 country_id: CountryId(1),
                 continent_id: ContinentId(1),
-                city_ids: vec![CityId(1)],
+                city_ids: vec![CityId(1), CityId(2), CityId(3)],
                 heat_average: 0.0,
                 crime_pressure_average: 0.0,
                 escalation: RegionEscalation::Stable,
@@ -215,7 +228,45 @@ impl Default for GlobalEventState {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelocationError {
+    SameCity,
+    UnknownCity(CityId),
+    CannotAfford { cost: i32, cash: i32 },
+}
+
+impl std::fmt::Display for RelocationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RelocationError::SameCity => write!(f, "you already live in that city"),
+            RelocationError::UnknownCity(city_id) => {
+                write!(f, "city {} is not in this region", city_id.0)
+            }
+            RelocationError::CannotAfford { cost, cash } => {
+                write!(f, "moving costs {} cr and you have {} cr", cost, cash)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RelocationError {}
+
 impl RegionState {
+    /// Other cities in the active city's region.
+    pub fn relocation_targets(&self, city: &CityState) -> Vec<CityId> {
+        self.regions
+            .get(&city.region_id)
+            .map(|profile| {
+                profile
+                    .city_ids
+                    .iter()
+                    .copied()
+                    .filter(|city_id| *city_id != city.city_id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     pub fn update_from_city(&mut self, city: &CityState) {
         let region_entry = self
             .regions
@@ -370,6 +421,29 @@ pub fn propagate_city_event(
         CityEventKind::HeatResponseChanged { response } => {
             RegionEventKind::CityHeatResponseChanged { response }
         }
+        CityEventKind::TurfEroded {
+            faction_id,
+            influence,
+        } => RegionEventKind::CityTurfEroded {
+            faction_id,
+            influence,
+        },
+        CityEventKind::PatrolSuppressed {
+            faction_id,
+            influence,
+        } => RegionEventKind::CityPatrolSuppressed {
+            faction_id,
+            influence,
+        },
+        CityEventKind::EnvironmentAltered {
+            field,
+            delta,
+            value,
+        } => RegionEventKind::CityEnvironmentAltered {
+            field,
+            delta,
+            value,
+        },
     };
 
     RegionEvent {
@@ -380,6 +454,37 @@ pub fn propagate_city_event(
     }
 }
 
+/// Move the player's base to another city in the region. Personas, growth and
+/// money come along; local heat, open cases and scene evidence stay behind.
+/// Returns the cash spent.
+pub fn relocate_city(
+    region: &RegionState,
+    city: &mut CityState,
+    cases: &mut CaseRegistry,
+    evidence: &mut WorldEvidence,
+    civilian: &mut CivilianState,
+    destination: CityId,
+) -> Result<i32, RelocationError> {
+    if destination == city.city_id {
+        return Err(RelocationError::SameCity);
+    }
+    if !region.relocation_targets(city).contains(&destination) {
+        return Err(RelocationError::UnknownCity(destination));
+    }
+    let cash = civilian.finances.cash;
+    if cash < RELOCATION_COST_CR {
+        return Err(RelocationError::CannotAfford {
+            cost: RELOCATION_COST_CR,
+            cash,
+        });
+    }
+    let paid = civilian.lose_cash(RELOCATION_COST_CR);
+    *city = city.relocated(destination);
+    cases.clear_cases();
+    *evidence = WorldEvidence::default();
+    Ok(paid)
+}
+
 pub fn tick_global_events(
     state: &mut GlobalEventState,
     catalog: &[crate::data::global_events::GlobalEventDefinition],
@@ -480,3 +585,51 @@ fn tick_global_cooldowns(state: &mut GlobalEventState) {
         state.cooldowns.remove(&id);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relocating_charges_cash_and_leaves_local_heat_and_cases_behind() {
+        let region = RegionState::default();
+        let mut city = CityState::default();
+        city.locations.get_mut(&LocationId(1)).unwrap().heat = 80;
+        city.heat_response.patrol = 20;
+        let mut cases = CaseRegistry::default();
+        cases.create_case("metro_police".to_string(), LocationId(1), Vec::new(), false);
+        let mut evidence = WorldEvidence::default();
+        let mut civilian = CivilianState::default();
+        civilian.finances.cash = RELOCATION_COST_CR + 500;
+        let old_case_id = cases.cases[0].case_id;
+
+        assert_eq!(
+            relocate_city(&region, &mut city, &mut cases, &mut evidence, &mut civilian, CityId(1)),
+            Err(RelocationError::SameCity)
+        );
+        assert_eq!(
+            relocate_city(&region, &mut city, &mut cases, &mut evidence, &mut civilian, CityId(9)),
+            Err(RelocationError::UnknownCity(CityId(9)))
+        );
+
+        let paid =
+            relocate_city(&region, &mut city, &mut cases, &mut evidence, &mut civilian, CityId(2))
+                .expect("relocate");
+        assert_eq!(paid, RELOCATION_COST_CR);
+        assert_eq!(civilian.finances.cash, 500);
+        assert_eq!(city.city_id, CityId(2));
+        assert_eq!(city.region_id, RegionId(1));
+        assert_eq!(city.heat_response.patrol, 20);
+        assert!(city.locations.values().all(|location| location.heat == 0));
+        assert!(cases.cases.is_empty());
+        assert!(evidence.is_empty());
+
+        let new_case_id =
+            cases.create_case("metro_police".to_string(), LocationId(1), Vec::new(), false);
+        assert!(new_case_id > old_case_id);
+        assert!(matches!(
+            relocate_city(&region, &mut city, &mut cases, &mut evidence, &mut civilian, CityId(3)),
+            Err(RelocationError::CannotAfford { .. })
+        ));
+    }
+}
@@ -0,0 +1,180 @@
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::components::persona::PersonaStack;
+use crate::simulation::case::{CaseRegistry, CaseStatus};
+use crate::simulation::civilian::CivilianState;
+use crate::simulation::combat::{CombatConsequence, CombatEnd};
+use crate::simulation::economy::WealthTier;
+
+const FIGHTS_WON_ACHIEVEMENT: u32 = 10;
+const CIVILIANS_SAVED_ACHIEVEMENT: u32 = 25;
+const CASES_RESOLVED_ACHIEVEMENT: u32 = 5;
+
+/// Lifetime tallies for the whole run, kept across civilian lives.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunStats {
+    #[serde(default)]
+    pub fights_won: u32,
+    #[serde(default)]
+    pub fights_lost: u32,
+    #[serde(default)]
+    pub fights_escaped: u32,
+    #[serde(default)]
+    pub civilians_saved: u32,
+    #[serde(default)]
+    pub civilian_casualties: u32,
+    #[serde(default)]
+    pub cases_resolved: u32,
+    #[serde(default)]
+    pub resolved_case_ids: Vec<u32>,
+    #[serde(default)]
+    pub burned_personas: Vec<String>,
+    #[serde(default)]
+    pub peak_net_worth: i64,
+    #[serde(default)]
+    pub peak_wealth_tier: Option<WealthTier>,
+}
+
+impl RunStats {
+    /// Tally a finished fight. Civilians at the scene count as saved when the
+    /// player wins, less any the fight itself hurt.
+    pub fn record_combat_end(
+        &mut self,
+        end: CombatEnd,
+        civilians_present: u32,
+        consequence: CombatConsequence,
+    ) {
+        let casualties = consequence.civilian_casualties as u32;
+        match end {
+            CombatEnd::OpponentsDefeated
+            | CombatEnd::OpponentsSurrendered
+            | CombatEnd::Resolved => {
+                self.fights_won += 1;
+                self.civilians_saved += civilians_present.saturating_sub(casualties);
+            }
+            CombatEnd::PlayerDefeated => self.fights_lost += 1,
+            CombatEnd::PlayerEscaped => self.fights_escaped += 1,
+        }
+        self.civilian_casualties += casualties;
+    }
+
+    pub fn record_fight_won(&mut self) {
+        self.fights_won += 1;
+    }
+
+    /// Counts cases resolved since the last call. Returns how many were new.
+    pub fn note_cases(&mut self, cases: &CaseRegistry) -> u32 {
+        let mut resolved = 0;
+        for case in &cases.cases {
+            if case.status == CaseStatus::Resolved && !self.resolved_case_ids.contains(&case.case_id) {
+                self.resolved_case_ids.push(case.case_id);
+                resolved += 1;
+            }
+        }
+        self.cases_resolved += resolved;
+        resolved
+    }
+
+    /// Records burned personas; each persona counts once. Returns the labels
+    /// of personas first seen burned by this call.
+    pub fn note_personas(&mut self, stack: &PersonaStack) -> Vec<String> {
+        let mut burned = Vec::new();
+        for persona in &stack.personas {
+            if persona.burned && !self.burned_personas.contains(&persona.persona_id)
+            {
+                self.burned_personas.push(persona.persona_id.clone());
+                burned.push(persona.label.clone());
+            }
+        }
+        burned
+    }
+
+    /// Tracks peak net worth and wealth tier. Returns the tier when it is a new high.
+    pub fn note_wealth(&mut self, civilian: &CivilianState) -> Option<WealthTier> {
+        self.peak_net_worth = self.peak_net_worth.max(civilian.net_worth_cr());
+        let tier = civilian.wealth.tier;
+        let is_new_peak = self
+            .peak_wealth_tier
+            .map(|peak| tier.rank() > peak.rank())
+            .unwrap_or(true);
+        if !is_new_peak {
+            return None;
+        }
+        let first = self.peak_wealth_tier.is_none();
+        self.peak_wealth_tier = Some(tier);
+        (!first).then_some(tier)
+    }
+
+    /// Run milestones worth listing on a legacy record.
+    pub fn achievements(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        if self.fights_won >= FIGHTS_WON_ACHIEVEMENT {
+            out.push(format!("{} fights won", self.fights_won));
+        }
+        if self.civilians_saved >= CIVILIANS_SAVED_ACHIEVEMENT {
+            out.push(format!("{} civilians saved", self.civilians_saved));
+        }
+        if self.cases_resolved >= CASES_RESOLVED_ACHIEVEMENT {
+            out.push(format!("Outlasted {} investigations", self.cases_resolved));
+        }
+        if !self.burned_personas.is_empty() {
+            out.push(format!("Burned {} persona(s)", self.burned_personas.len()));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::case::CaseEventLog;
+    use crate::simulation::city::{CityState, LocationId};
+    use crate::simulation::evidence::WorldEvidence;
+    use crate::simulation::identity_evidence::IdentityEvidenceStore;
+    use crate::systems::case::update_cases;
+
+    #[test]
+    fn combat_wins_and_case_resolutions_increment_their_counters() {
+        let mut stats = RunStats::default();
+        stats.record_combat_end(
+            CombatEnd::OpponentsDefeated,
+            4,
+            CombatConsequence {
+                civilian_casualties: 1,
+                ..CombatConsequence::default()
+            },
+        );
+        assert_eq!(stats.fights_won, 1);
+        assert_eq!(stats.civilians_saved, 3);
+        assert_eq!(stats.civilian_casualties, 1);
+        assert_eq!(stats.fights_lost, 0);
+        assert_eq!(stats.cases_resolved, 0);
+
+        let mut city = CityState::default();
+        let location_id = LocationId(1);
+        {
+            let location = city.locations.get_mut(&location_id).unwrap();
+            location.investigators = 10;
+            location.heat = 0;
+        }
+        let mut cases = CaseRegistry::default();
+        cases.create_case("metro_police".to_string(), location_id, Vec::new(), false);
+        let mut log = CaseEventLog::default();
+        for _ in 0..5 {
+            update_cases(
+                &mut cases,
+                &city,
+                &WorldEvidence::default(),
+                &IdentityEvidenceStore::default(),
+                &Default::default(),
+                &mut log,
+            );
+            stats.note_cases(&cases);
+        }
+        assert_eq!(cases.cases[0].status, CaseStatus::Resolved);
+        assert_eq!(stats.cases_resolved, 1);
+        assert_eq!(stats.note_cases(&cases), 0);
+        assert_eq!(stats.fights_won, 1);
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap};
 
 use bevy_ecs::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -43,11 +43,14 @@ impl StoryletPunctuationState {
 #[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StoryletState {
     #[serde(default)]
-    pub fired: HashSet<String>,
+    pub fired: BTreeSet<String>,
     #[serde(default)]
     pub cooldowns: HashMap<String, i32>,
     #[serde(default)]
     pub flags: HashMap<String, bool>,
+    /// Turns left before a timed flag clears itself.
+    #[serde(default)]
+    pub flag_expiry: HashMap<String, i32>,
     #[serde(default)]
     pub punctuation: StoryletPunctuationState,
     #[serde(default)]
@@ -55,6 +58,23 @@ pub struct StoryletState {
 }
 
 impl StoryletState {
+    /// Sets a flag that stays until removed.
+    pub fn set_flag(&mut self, flag: &str) {
+        self.flags.insert(flag.to_string(), true);
+        self.flag_expiry.remove(flag);
+    }
+
+    /// Sets a flag that `tick` clears after `turns` turns.
+    pub fn set_timed_flag(&mut self, flag: &str, turns: i32) {
+        self.flags.insert(flag.to_string(), true);
+        self.flag_expiry.insert(flag.to_string(), turns.max(1));
+    }
+
+    pub fn clear_flag(&mut self, flag: &str) {
+        self.flags.remove(flag);
+        self.flag_expiry.remove(flag);
+    }
+
     pub fn tick(&mut self) {
         let mut to_clear = Vec::new();
         for (id, turns) in self.cooldowns.iter_mut() {
@@ -68,9 +88,44 @@ impl StoryletState {
         for id in to_clear {
             self.cooldowns.remove(&id);
         }
+        let mut expired = Vec::new();
+        for (flag, turns) in self.flag_expiry.iter_mut() {
+            *turns -= 1;
+            if *turns <= 0 {
+                expired.push(flag.clone());
+            }
+        }
+        for flag in expired {
+            self.clear_flag(&flag);
+        }
         self.punctuation.tick();
         if self.punctuation_cooldown > 0 {
             self.punctuation_cooldown -= 1;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timed_flag_clears_after_its_window() {
+        let mut state = StoryletState::default();
+        state.set_timed_flag("recent_rescue", 3);
+        state.set_flag("mastery_precise");
+        for _ in 0..2 {
+            state.tick();
+        }
+        assert_eq!(state.flags.get("recent_rescue"), Some(&true));
+        state.tick();
+        assert!(!state.flags.contains_key("recent_rescue"));
+        assert!(state.flag_expiry.is_empty());
+        assert_eq!(state.flags.get("mastery_precise"), Some(&true));
+
+        state.set_timed_flag("mastery_precise", 1);
+        state.set_flag("mastery_precise");
+        state.tick();
+        assert_eq!(state.flags.get("mastery_precise"), Some(&true));
+    }
+}
@@ -1,13 +1,20 @@
 use bevy_ecs::prelude::*;
 
-use crate::components::persona::Alignment;
+use crate::components::persona::{Alignment, PersonaStack, SuspicionDelta};
 use crate::data::storylets::{Storylet, StoryletCategory};
+use crate::simulation::pressure::{PressureAxis, PressureState};
+use crate::simulation::storylet_state::StoryletState;
 
 #[derive(Resource, Debug, Default, Clone)]
 pub struct StoryletLibrary {
     pub hero: Vec<Storylet>,
     pub vigilante: Vec<Storylet>,
     pub villain: Vec<Storylet>,
+    /// Phase-specific arcs offered ahead of the main pool while an endgame
+    /// transformation is active. Empty when no endgame file was authored.
+    pub endgame_hero: Vec<Storylet>,
+    pub endgame_vigilante: Vec<Storylet>,
+    pub endgame_villain: Vec<Storylet>,
 }
 
 impl StoryletLibrary {
@@ -19,6 +26,205 @@ impl StoryletLibrary {
             Alignment::Villain => &self.villain,
         }
     }
+
+    pub fn endgame_for_alignment(&self, alignment: Alignment) -> &[Storylet] {
+        match alignment {
+            Alignment::Neutral => &self.endgame_hero,
+            Alignment::Hero => &self.endgame_hero,
+            Alignment::Vigilante => &self.endgame_vigilante,
+            Alignment::Villain => &self.endgame_villain,
+        }
+    }
+
+    /// Every storylet a turn may consider, tier by tier.
+    pub fn candidates(
+        &self,
+        alignment: Alignment,
+        endgame_active: bool,
+    ) -> impl Iterator<Item = &Storylet> {
+        self.tiers(alignment, endgame_active).into_iter().flatten()
+    }
+
+    /// Selection tiers for a turn: the endgame pool while a transformation is
+    /// active, then the alignment pool. A turn draws from the first tier with
+    /// an eligible storylet.
+    pub fn tiers(&self, alignment: Alignment, endgame_active: bool) -> [&[Storylet]; 2] {
+        let endgame = if endgame_active {
+            self.endgame_for_alignment(alignment)
+        } else {
+            &[]
+        };
+        [endgame, self.for_alignment(alignment)]
+    }
+
+    pub fn find(&self, storylet_id: &str) -> Option<&Storylet> {
+        self.all().find(|storylet| storylet.id == storylet_id)
+    }
+
+    pub fn all(&self) -> impl Iterator<Item = &Storylet> {
+        self.hero
+            .iter()
+            .chain(&self.vigilante)
+            .chain(&self.villain)
+            .chain(&self.endgame_hero)
+            .chain(&self.endgame_vigilante)
+            .chain(&self.endgame_villain)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoryletResolution {
+    pub text_stub: String,
+    pub choice_text: String,
+    /// Human-readable lines for the effects that applied.
+    pub applied: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoryletResolveError {
+    UnknownStorylet(String),
+    UnknownChoice {
+        storylet_id: String,
+        choice_id: String,
+    },
+}
+
+impl std::fmt::Display for StoryletResolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StoryletResolveError::UnknownStorylet(storylet_id) => {
+                write!(f, "unknown storylet {}", storylet_id)
+            }
+            StoryletResolveError::UnknownChoice {
+                storylet_id,
+                choice_id,
+            } => write!(
+                f,
+                "unknown choice {} for storylet {}",
+                choice_id, storylet_id
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StoryletResolveError {}
+
+/// Applies a storylet choice's effects. Effects read `key:value` or `key value`:
+/// `storylet.flag:<flag>[:turns]`, `pressure.<axis>:<delta>`, and
+/// `public.suspicion`, `civilian.suspicion`, `wanted.level` or `exposure.risk`
+/// with a delta for the active persona. Other keys belong to other systems and
+/// are skipped here.
+pub fn resolve_storylet(
+    library: &StoryletLibrary,
+    storylet_state: &mut StoryletState,
+    pressure: &mut PressureState,
+    persona_stack: &mut PersonaStack,
+    storylet_id: &str,
+    choice_id: &str,
+) -> Result<StoryletResolution, StoryletResolveError> {
+    let storylet = library
+        .find(storylet_id)
+        .ok_or_else(|| StoryletResolveError::UnknownStorylet(storylet_id.to_string()))?;
+    let choice = storylet
+        .choices
+        .iter()
+        .find(|choice| choice.id == choice_id)
+        .ok_or_else(|| StoryletResolveError::UnknownChoice {
+            storylet_id: storylet_id.to_string(),
+            choice_id: choice_id.to_string(),
+        })?;
+    let applied = choice
+        .effects
+        .iter()
+        .filter_map(|effect| apply_storylet_effect(effect, storylet_state, pressure, persona_stack))
+        .collect();
+    Ok(StoryletResolution {
+        text_stub: storylet.text_stub.clone(),
+        choice_text: choice.text.clone(),
+        applied,
+    })
+}
+
+fn apply_storylet_effect(
+    effect: &str,
+    storylet_state: &mut StoryletState,
+    pressure: &mut PressureState,
+    persona_stack: &mut PersonaStack,
+) -> Option<String> {
+    let effect = effect.trim();
+    let (key, value) = effect
+        .split_once(':')
+        .or_else(|| effect.split_once(char::is_whitespace))?;
+    let value = value.trim();
+    if key == "storylet.flag" {
+        let (flag, turns) = match value.split_once(':') {
+            Some((flag, turns)) => (flag.trim(), turns.trim().parse::<i32>().ok()),
+            None => (value, None),
+        };
+        if flag.is_empty() {
+            return None;
+        }
+        return Some(match turns {
+            Some(turns) => {
+                storylet_state.set_timed_flag(flag, turns);
+                format!("storylet flag {} ({} turns)", flag, turns)
+            }
+            None => {
+                storylet_state.set_flag(flag);
+                format!("storylet flag {}", flag)
+            }
+        });
+    }
+    if let Some(axis) = key
+        .strip_prefix("pressure.")
+        .and_then(PressureAxis::from_key)
+    {
+        let delta = value.parse::<f32>().ok()?;
+        let target = pressure.axis_mut(axis);
+        *target = (*target + delta).clamp(0.0, 100.0);
+        return Some(format!("{} {:+.1}", key, delta));
+    }
+    let amount = value.parse::<i32>().ok()?;
+    let mut delta = SuspicionDelta::default();
+    match key {
+        "public.suspicion" => delta.public_suspicion = amount,
+        "civilian.suspicion" => delta.civilian_suspicion = amount,
+        "wanted.level" => delta.wanted_level = amount,
+        "exposure.risk" => delta.exposure_risk = amount,
+        _ => return None,
+    }
+    let persona = persona_stack.active_persona_mut()?;
+    persona.suspicion.apply_delta(&delta);
+    Some(format!("{} {} {:+}", persona.persona_id, key, amount))
+}
+
+/// Draws one of `eligible` with odds proportional to each storylet's weight.
+/// The same seed always draws the same storylet.
+pub fn pick_weighted_storylet<'a>(eligible: &[&'a Storylet], seed: u64) -> Option<&'a Storylet> {
+    let total: u64 = eligible
+        .iter()
+        .map(|storylet| storylet.selection_weight() as u64)
+        .sum();
+    if total == 0 {
+        return None;
+    }
+    let mut state = seed;
+    let mut roll = (next_u64(&mut state) >> 11) % total;
+    for storylet in eligible {
+        let weight = storylet.selection_weight() as u64;
+        if roll < weight {
+            return Some(storylet);
+        }
+        roll -= weight;
+    }
+    None
+}
+
+fn next_u64(state: &mut u64) -> u64 {
+    *state = state
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    *state
 }
 
 pub fn is_punctuation_storylet(storylet: &Storylet) -> bool {
@@ -52,18 +258,65 @@ pub fn storylet_threshold_keys(storylet: &Storylet) -> Vec<String> {
     storylet
         .preconditions
         .iter()
-        .filter_map(|condition| threshold_key(condition))
+        .flat_map(|condition| condition_terms(condition))
+        .filter_map(threshold_key)
         .collect()
 }
 
+/// The OR terms of one precondition: `"pressure.moral >= 60 | heat in 30..50"`
+/// holds when any term does. Blank terms are skipped.
+pub fn condition_terms(condition: &str) -> impl Iterator<Item = &str> {
+    condition
+        .split('|')
+        .map(str::trim)
+        .filter(|term| !term.is_empty())
+}
+
+/// Whether a precondition holds, given how to judge a single term. A blank
+/// precondition always holds.
+pub fn condition_holds(condition: &str, term_holds: impl FnMut(&str) -> bool) -> bool {
+    let mut terms = condition_terms(condition).peekable();
+    terms.peek().is_none() || terms.any(term_holds)
+}
+
+/// Inclusive bounds of a `low..high` operand, as in `heat in 30..50`.
+pub fn parse_condition_range(value: &str) -> Option<(i32, i32)> {
+    let (low, high) = value.split_once("..")?;
+    let low = low.parse::<i32>().ok()?;
+    let high = high.parse::<i32>().ok()?;
+    (low <= high).then_some((low, high))
+}
+
+/// Compares a metric against a condition operand. `in` takes a range; every
+/// other operator takes an integer.
+pub fn compare_metric(left: i32, op: &str, right: &str) -> bool {
+    if op == "in" {
+        return parse_condition_range(right)
+            .map(|(low, high)| (low..=high).contains(&left))
+            .unwrap_or(false);
+    }
+    let Ok(right) = right.parse::<i32>() else {
+        return false;
+    };
+    match op {
+        ">=" => left >= right,
+        "<=" => left <= right,
+        ">" => left > right,
+        "<" => left < right,
+        "==" => left == right,
+        "!=" => left != right,
+        _ => false,
+    }
+}
+
 fn condition_has_threshold(condition: &str) -> bool {
-    threshold_key(condition).is_some()
+    condition_terms(condition).any(|term| threshold_key(term).is_some())
 }
 
 fn storylet_has_state_gate(storylet: &Storylet) -> bool {
     storylet.preconditions.iter().any(|condition| {
-        let cond = condition.trim();
-        cond.starts_with("flag.") || cond.starts_with("endgame.state")
+        condition_terms(condition)
+            .any(|term| term.starts_with("flag.") || term.starts_with("endgame.state"))
     })
 }
 
@@ -87,11 +340,231 @@ fn threshold_key(condition: &str) -> Option<String> {
     if matches!(left, "alignment" | "persona") {
         return None;
     }
-    if !matches!(op, ">=" | "<=" | ">" | "<" | "==" | "!=") {
-        return None;
+    let valid_operand = match op {
+        ">=" | "<=" | ">" | "<" | "==" | "!=" => right.parse::<i32>().is_ok(),
+        "in" => parse_condition_range(right).is_some(),
+        _ => false,
+    };
+    valid_operand.then(|| left.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storylet(id: &str) -> Storylet {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "category": "INSTITUTIONAL_PRESSURE",
+            "text_stub": "stub",
+            "choices": []
+        }))
+        .expect("storylet")
     }
-    if right.parse::<i32>().is_err() {
-        return None;
+
+    #[test]
+    fn endgame_pool_leads_only_during_a_transformation() {
+        let library = StoryletLibrary {
+            vigilante: vec![storylet("street_beat")],
+            endgame_vigilante: vec![storylet("registration_raid")],
+            endgame_hero: vec![storylet("hero_hearing")],
+            ..StoryletLibrary::default()
+        };
+        let ids = |endgame_active| {
+            library
+                .candidates(Alignment::Vigilante, endgame_active)
+                .map(|storylet| storylet.id.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(false), vec!["street_beat"]);
+        assert_eq!(ids(true), vec!["registration_raid", "street_beat"]);
+    }
+
+    fn holds(preconditions: &[&str], metrics: &[(&str, i32)]) -> bool {
+        preconditions.iter().all(|condition| {
+            condition_holds(condition, |term| {
+                let parts: Vec<&str> = term.split_whitespace().collect();
+                let [left, op, right] = parts[..] else {
+                    return false;
+                };
+                metrics
+                    .iter()
+                    .find(|(key, _)| *key == left)
+                    .map(|(_, value)| compare_metric(*value, op, right))
+                    .unwrap_or(false)
+            })
+        })
+    }
+
+    #[test]
+    fn preconditions_and_together_while_pipes_or_within_one() {
+        let metrics = [
+            ("pressure.moral", 40),
+            ("pressure.institutional", 75),
+            ("heat", 30),
+        ];
+        assert!(holds(
+            &["pressure.moral >= 60 | pressure.institutional >= 70"],
+            &metrics
+        ));
+        assert!(!holds(
+            &["pressure.moral >= 60 | pressure.institutional >= 80"],
+            &metrics
+        ));
+        assert!(holds(
+            &[
+                "pressure.moral >= 60 | pressure.institutional >= 70",
+                "heat in 30..50"
+            ],
+            &metrics
+        ));
+        assert!(!holds(
+            &[
+                "pressure.moral >= 60 | pressure.institutional >= 70",
+                "heat in 31..50"
+            ],
+            &metrics
+        ));
+        assert!(holds(&["heat >= 30"], &metrics));
+        assert!(holds(&["", " | heat == 30 | "], &metrics));
+    }
+
+    #[test]
+    fn ranges_are_inclusive_and_reject_malformed_bounds() {
+        assert_eq!(parse_condition_range("30..50"), Some((30, 50)));
+        assert_eq!(parse_condition_range("-5..5"), Some((-5, 5)));
+        assert_eq!(parse_condition_range("50..30"), None);
+        assert_eq!(parse_condition_range("30..."), None);
+        assert_eq!(parse_condition_range("30"), None);
+        assert!(compare_metric(50, "in", "30..50"));
+        assert!(!compare_metric(51, "in", "30..50"));
+        assert!(!compare_metric(40, "in", "forty..50"));
+
+        let mut gated = storylet("late_shift");
+        gated.preconditions = vec!["flag.night_owl | heat in 30..50".to_string()];
+        assert_eq!(storylet_threshold_keys(&gated), vec!["heat".to_string()]);
+        assert!(storylet_has_state_gate(&gated));
+    }
+
+    #[test]
+    fn weighted_picks_follow_the_weights_over_many_turns() {
+        let mut common = storylet("common");
+        common.weight = Some(6);
+        let default_weight = storylet("default_weight");
+        let mut rare = storylet("rare");
+        rare.weight = Some(3);
+        let eligible = [&common, &default_weight, &rare];
+
+        let draws = 10_000u64;
+        let mut counts = std::collections::HashMap::new();
+        for turn in 0..draws {
+            let picked = pick_weighted_storylet(&eligible, 0xC0FFEE ^ turn).expect("pick");
+            *counts.entry(picked.id.as_str()).or_insert(0u64) += 1;
+        }
+        for (id, weight) in [("common", 6.0), ("default_weight", 1.0), ("rare", 3.0)] {
+            let share = counts[id] as f64 / draws as f64;
+            assert!((share - weight / 10.0).abs() < 0.03, "{id} drew {share}");
+        }
+        assert_eq!(
+            pick_weighted_storylet(&eligible, 42).map(|storylet| &storylet.id),
+            pick_weighted_storylet(&eligible, 42).map(|storylet| &storylet.id)
+        );
+        assert!(pick_weighted_storylet(&[], 42).is_none());
+    }
+
+    fn rooftop_library() -> StoryletLibrary {
+        let mut rooftop = storylet("rooftop_meet");
+        rooftop.choices = serde_json::from_value(serde_json::json!([
+            {
+                "id": "listen",
+                "text": "Hear the informant out.",
+                "effects": [
+                    "storylet.flag:informant_trusted",
+                    "storylet.flag:informant_nearby:3",
+                    "pressure.moral:+4",
+                    "public.suspicion +5",
+                    "heat +2"
+                ]
+            }
+        ]))
+        .expect("choices");
+        StoryletLibrary {
+            vigilante: vec![rooftop],
+            ..StoryletLibrary::default()
+        }
+    }
+
+    #[test]
+    fn resolving_a_choice_sets_flags_pressure_and_suspicion() {
+        let library = rooftop_library();
+        let mut storylet_state = StoryletState::default();
+        let mut pressure = PressureState::default();
+        let mut personas = crate::components::persona::vigilante_persona_stack();
+        let before = personas
+            .active_persona()
+            .unwrap()
+            .suspicion
+            .public_suspicion;
+
+        let resolution = resolve_storylet(
+            &library,
+            &mut storylet_state,
+            &mut pressure,
+            &mut personas,
+            "rooftop_meet",
+            "listen",
+        )
+        .expect("resolve");
+
+        assert_eq!(storylet_state.flags.get("informant_trusted"), Some(&true));
+        assert_eq!(storylet_state.flag_expiry.get("informant_nearby"), Some(&3));
+        assert_eq!(pressure.moral, 4.0);
+        assert_eq!(
+            personas
+                .active_persona()
+                .unwrap()
+                .suspicion
+                .public_suspicion,
+            before + 5
+        );
+        assert_eq!(resolution.applied.len(), 4);
+    }
+
+    #[test]
+    fn unknown_storylets_and_choices_are_rejected_without_effects() {
+        let library = rooftop_library();
+        let mut storylet_state = StoryletState::default();
+        let mut pressure = PressureState::default();
+        let mut personas = crate::components::persona::vigilante_persona_stack();
+
+        let err = resolve_storylet(
+            &library,
+            &mut storylet_state,
+            &mut pressure,
+            &mut personas,
+            "rooftop_meet",
+            "walk_away",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            StoryletResolveError::UnknownChoice {
+                storylet_id: "rooftop_meet".to_string(),
+                choice_id: "walk_away".to_string(),
+            }
+        );
+        assert_eq!(
+            resolve_storylet(
+                &library,
+                &mut storylet_state,
+                &mut pressure,
+                &mut personas,
+                "missing",
+                "listen",
+            ),
+            Err(StoryletResolveError::UnknownStorylet("missing".to_string()))
+        );
+        assert!(storylet_state.flags.is_empty());
+        assert_eq!(pressure.moral, 0.0);
     }
-    Some(left.to_string())
 }
@@ -10,37 +10,253 @@ pub struct GameTime {
     pub week: u32,
     pub month: u32,
     pub is_day: bool,
+    #[serde(default)]
+    pub config: TimeConfig,
 }
 
-impl Default for GameTime {
+/// Clock cadence. `hour` counts up to `hours_per_day`; hour-gated logic reads
+/// `clock_hour()`, which maps that back onto a 24-hour clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeConfig {
+    pub hours_per_tick: u8,
+    pub hours_per_day: u8,
+}
+
+impl Default for TimeConfig {
     fn default() -> Self {
-        let hour = 8;
         Self {
+            hours_per_tick: 1,
+            hours_per_day: 24,
+        }
+    }
+}
+
+impl TimeConfig {
+    /// One tick per day, for coarse long-run simulations.
+    pub fn daily() -> Self {
+        Self {
+            hours_per_tick: 24,
+            hours_per_day: 24,
+        }
+    }
+}
+
+pub const MONTHS_PER_YEAR: u32 = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Season {
+    Winter,
+    Spring,
+    Summer,
+    Autumn,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weather {
+    Clear,
+    Rain,
+    Storm,
+    Snow,
+    Heatwave,
+}
+
+impl Weather {
+    /// Share of the usual street crowd that stays out in this weather, in percent.
+    pub fn crowd_percent(self) -> i32 {
+        match self {
+            Weather::Clear => 100,
+            Weather::Heatwave => 80,
+            Weather::Rain => 70,
+            Weather::Snow => 55,
+            Weather::Storm => 40,
+        }
+    }
+}
+
+/// Percent chance of each non-clear weather on a given day; the remainder is clear.
+#[derive(Debug, Clone, Copy)]
+pub struct WeatherOdds {
+    pub rain: u8,
+    pub storm: u8,
+    pub snow: u8,
+    pub heatwave: u8,
+}
+
+impl Season {
+    pub fn from_month(month: u32) -> Self {
+        match (month.max(1) - 1) % MONTHS_PER_YEAR + 1 {
+            12 | 1 | 2 => Season::Winter,
+            3..=5 => Season::Spring,
+            6..=8 => Season::Summer,
+            _ => Season::Autumn,
+        }
+    }
+
+    /// Sunrise and sunset hours; winter days are short, summer days long.
+    pub fn daylight_hours(self) -> (u8, u8) {
+        match self {
+            Season::Winter => (8, 16),
+            Season::Spring => (6, 18),
+            Season::Summer => (5, 20),
+            Season::Autumn => (7, 17),
+        }
+    }
+
+    pub fn weather_odds(self) -> WeatherOdds {
+        match self {
+            Season::Winter => WeatherOdds {
+                rain: 15,
+                storm: 5,
+                snow: 30,
+                heatwave: 0,
+            },
+            Season::Spring => WeatherOdds {
+                rain: 35,
+                storm: 10,
+                snow: 2,
+                heatwave: 0,
+            },
+            Season::Summer => WeatherOdds {
+                rain: 10,
+                storm: 15,
+                snow: 0,
+                heatwave: 20,
+            },
+            Season::Autumn => WeatherOdds {
+                rain: 30,
+                storm: 10,
+                snow: 5,
+                heatwave: 0,
+            },
+        }
+    }
+
+    /// Extra daily spend on heating or cooling on top of lifestyle upkeep.
+    pub fn climate_expense(self, upkeep: i64) -> i64 {
+        match self {
+            Season::Winter => upkeep * 15 / 100 + 6,
+            Season::Summer => upkeep * 8 / 100 + 3,
+            Season::Spring | Season::Autumn => 0,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Season::Winter => "Winter",
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Autumn => "Autumn",
+        }
+    }
+}
+
+impl Default for GameTime {
+    fn default() -> Self {
+        Self::with_config(TimeConfig::default())
+    }
+}
+
+fn is_daylight(season: Season, hour: u8) -> bool {
+    let (sunrise, sunset) = season.daylight_hours();
+    hour >= sunrise && hour < sunset
+}
+
+impl GameTime {
+    pub fn with_config(config: TimeConfig) -> Self {
+        let config = TimeConfig {
+            hours_per_tick: config.hours_per_tick.max(1),
+            hours_per_day: config.hours_per_day.max(1),
+        };
+        let mut time = Self {
             tick: 0,
             day: 1,
-            hour,
+            hour: 0,
             week: 1,
             month: 1,
-            is_day: hour >= 6 && hour < 18,
+            is_day: false,
+            config,
+        };
+        time.hour = time.hour_for_clock(8);
+        time.is_day = is_daylight(time.season(), time.clock_hour());
+        time
+    }
+
+    /// Current hour mapped onto a 24-hour clock.
+    pub fn clock_hour(&self) -> u8 {
+        let hours_per_day = self.config.hours_per_day.max(1) as u32;
+        (self.hour as u32 * 24 / hours_per_day).min(23) as u8
+    }
+
+    fn hour_for_clock(&self, clock_hour: u8) -> u8 {
+        let hours_per_day = self.config.hours_per_day.max(1) as u32;
+        (clock_hour as u32 * hours_per_day / 24) as u8
+    }
+
+    /// True when `clock_hour` fell inside the span covered by the latest tick,
+    /// so hour-gated events still fire when a tick skips over their hour.
+    pub fn reached_hour(&self, clock_hour: u8) -> bool {
+        let hours_per_day = self.config.hours_per_day.max(1) as u32;
+        let span = (self.config.hours_per_tick as u32 * 24).div_ceil(hours_per_day);
+        if span >= 24 {
+            return true;
         }
+        let current = self.clock_hour() as u32;
+        let elapsed = (current + 24 - clock_hour as u32 % 24) % 24;
+        elapsed < span
     }
-}
 
-impl GameTime {
     pub fn to_string(&self) -> String {
         let phase = if self.is_day { "Day" } else { "Night" };
         format!(
-            "Day {}, Week {}, Month {}, {:02}:00 ({})",
-            self.day, self.week, self.month, self.hour, phase
+            "Day {}, Week {}, Month {} ({}, {:?}), {:02}:00 ({})",
+            self.day,
+            self.week,
+            self.month,
+            self.season().label(),
+            self.weather(),
+            self.clock_hour(),
+            phase
         )
     }
 
+    pub fn season(&self) -> Season {
+        Season::from_month(self.month)
+    }
+
+    /// Weather for the current day, rolled from the season's odds so it stays
+    /// stable for the whole day.
+    pub fn weather(&self) -> Weather {
+        let odds = self.season().weather_odds();
+        let mut hash = 1469598103934665603u64 ^ self.day as u64;
+        hash = hash.wrapping_mul(1099511628211);
+        hash ^= hash >> 29;
+        let roll = (hash % 100) as u8;
+        let mut threshold = odds.snow;
+        if roll < threshold {
+            return Weather::Snow;
+        }
+        threshold += odds.storm;
+        if roll < threshold {
+            return Weather::Storm;
+        }
+        threshold += odds.rain;
+        if roll < threshold {
+            return Weather::Rain;
+        }
+        threshold += odds.heatwave;
+        if roll < threshold {
+            return Weather::Heatwave;
+        }
+        Weather::Clear
+    }
+
     pub fn advance(&mut self) {
         self.tick += 1;
-        self.hour += 1;
+        let hours_per_day = self.config.hours_per_day.max(1) as u32;
+        let mut hour = self.hour as u32 + self.config.hours_per_tick.max(1) as u32;
 
-        if self.hour >= 24 {
-            self.hour = 0;
+        while hour >= hours_per_day {
+            hour -= hours_per_day;
             self.day += 1;
             if self.day % 7 == 0 {
                 self.week += 1;
@@ -50,12 +266,93 @@ impl GameTime {
             }
         }
 
-        self.is_day = self.hour >= 6 && self.hour < 18;
+        self.hour = hour as u8;
+        self.is_day = is_daylight(self.season(), self.clock_hour());
     }
 }
 
-/// System: Advances the clock.
-/// 1 Tick = 1 Hour for this prototype (can be scaled later).
+/// System: Advances the clock by one tick of the configured cadence.
 pub fn advance_time_system(mut time: ResMut<GameTime>) {
     time.advance();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_hour_ticks_advance_days() {
+        let mut time = GameTime::with_config(TimeConfig {
+            hours_per_tick: 2,
+            hours_per_day: 24,
+        });
+        assert_eq!(time.hour, 8);
+        for _ in 0..8 {
+            time.advance();
+        }
+        assert_eq!(time.day, 2);
+        assert_eq!(time.hour, 0);
+        for _ in 0..12 {
+            time.advance();
+        }
+        assert_eq!(time.day, 3);
+        assert_eq!(time.tick, 20);
+
+        time.advance();
+        assert_eq!(time.hour, 2);
+        assert!(time.reached_hour(1));
+        assert!(time.reached_hour(2));
+        assert!(!time.reached_hour(3));
+    }
+
+    #[test]
+    fn seasons_set_night_length_and_climate_costs() {
+        let night_hours =
+            |season: Season| (0..24).filter(|hour| !is_daylight(season, *hour)).count();
+        assert_eq!(night_hours(Season::Winter), 16);
+        assert_eq!(night_hours(Season::Summer), 9);
+        assert!(night_hours(Season::Spring) < night_hours(Season::Winter));
+
+        let mut time = GameTime::default();
+        time.hour = time.hour_for_clock(6);
+        time.month = 1;
+        time.advance();
+        assert_eq!(time.season(), Season::Winter);
+        assert!(!time.is_day, "07:00 is still dark in winter");
+        time.hour = time.hour_for_clock(6);
+        time.month = 6;
+        time.advance();
+        assert_eq!(time.season(), Season::Summer);
+        assert!(time.is_day, "07:00 is light in summer");
+
+        assert_eq!(Season::Winter.climate_expense(100), 21);
+        assert_eq!(Season::Summer.climate_expense(100), 11);
+        assert_eq!(Season::Spring.climate_expense(100), 0);
+        assert_eq!(Season::Autumn.climate_expense(100), 0);
+    }
+
+    #[test]
+    fn weather_follows_the_season_and_holds_for_the_day() {
+        let mut time = GameTime::default();
+        let mut summer = Vec::new();
+        time.month = 6;
+        for day in 1..=60 {
+            time.day = day;
+            summer.push(time.weather());
+            time.hour = 23;
+            assert_eq!(time.weather(), summer[summer.len() - 1]);
+        }
+        assert!(!summer.contains(&Weather::Snow));
+        assert!(summer.contains(&Weather::Heatwave));
+
+        time.month = 1;
+        let winter: Vec<Weather> = (1..=60)
+            .map(|day| {
+                time.day = day;
+                time.weather()
+            })
+            .collect();
+        assert!(!winter.contains(&Weather::Heatwave));
+        assert!(winter.contains(&Weather::Snow));
+    }
+}
@@ -1,84 +1,201 @@
+use std::collections::{HashMap, HashSet};
+
 use bevy_ecs::prelude::*;
 
+use crate::rules::signature::SignatureType;
 use crate::simulation::case::{CaseEventLog, CaseRegistry, CaseStatus, CaseTargetType};
-use crate::simulation::city::CityState;
+use crate::simulation::city::{CityState, LocationId};
 use crate::simulation::evidence::WorldEvidence;
-use crate::simulation::identity_evidence::IdentityEvidenceStore;
+use crate::simulation::identity_evidence::{
+    IdentityEvidenceItem, IdentityEvidenceStore, PersonaHint,
+};
+use crate::systems::faction::FactionDirector;
+
+/// Decoy plants a persona can take before investigators start to doubt them.
+const DECOY_SAFE_PLANTS: usize = 2;
+const DECOY_DEBUNK_CHANCE_PER_PLANT: u64 = 20;
+const DECOY_DEBUNK_MAX_CHANCE: u64 = 90;
+/// Active cases one faction can staff at once; extras fold into each other.
+pub const MAX_ACTIVE_CASES_PER_FACTION: usize = 3;
+/// Corroboration investigators need before naming a masked suspect.
+pub const MASKED_LINK_CONFIDENCE: u8 = 40;
 
 pub fn case_progress_system(
     mut cases: ResMut<CaseRegistry>,
     city: Res<CityState>,
     evidence: Res<WorldEvidence>,
     identity: Res<IdentityEvidenceStore>,
+    director: Res<FactionDirector>,
     mut log: ResMut<CaseEventLog>,
 ) {
-    update_cases(&mut cases, &city, &evidence, &identity, &mut log);
+    let perception = director.faction_perception();
+    update_cases(&mut cases, &city, &evidence, &identity, &perception, &mut log);
 }
 
+/// Advances active cases. `perception` maps a faction to the signature types
+/// it can detect; signatures outside that set never advance its cases, and
+/// factions missing from the map perceive every type.
 pub fn update_cases(
     cases: &mut CaseRegistry,
     city: &CityState,
     evidence: &WorldEvidence,
     identity: &IdentityEvidenceStore,
+    perception: &HashMap<String, HashSet<SignatureType>>,
     log: &mut CaseEventLog,
 ) {
+    consolidate_faction_cases(cases, log);
+    let index = EvidenceIndex::build(evidence, identity);
+
     for case in cases.cases.iter_mut() {
         if case.status != CaseStatus::Active {
             continue;
         }
 
-        let investigators = city
+        let (investigators, recognized, heat) = city
             .locations
             .get(&case.location_id)
-            .map(|loc| loc.investigators as u32)
-            .unwrap_or(0);
+            .map(|loc| (loc.investigators as u32, loc.witnesses_recognize(), loc.heat))
+            .unwrap_or((0, false, 0));
 
         let mut delta = investigators * 2;
-        let matches = count_matching_signatures(evidence, case);
+        let perceived = perception.get(&case.faction_id);
+        let matches = index.matching_signatures(case, perceived);
         if matches > 0 {
             delta += matches.min(3) as u32 * 2;
         }
 
-        let evidence_hits = count_matching_evidence(identity, case);
+        let evidence_hits = index.matching_evidence(case, perceived);
         if evidence_hits > 0 {
             delta += evidence_hits.min(3) as u32 * 2;
         }
 
-        if delta == 0 {
+        // Cases held at full progress by a heat lock still need rechecking.
+        if delta == 0 && case.progress < 100 {
             continue;
         }
 
         case.progress = (case.progress + delta).min(100);
-        update_case_milestones(case, log);
+        let corroborated = identity.trail_confidence(case.location_id, &PersonaHint::Masked)
+            >= MASKED_LINK_CONFIDENCE;
+        update_case_milestones(case, recognized, corroborated, heat, log);
     }
 }
 
-fn count_matching_signatures(evidence: &WorldEvidence, case: &crate::simulation::case::Case) -> usize {
-    evidence
-        .signatures
+/// Folds each over-cap faction's lowest-progress active case into the next
+/// lowest one chasing the same suspect, until the faction is back at the cap.
+fn consolidate_faction_cases(cases: &mut CaseRegistry, log: &mut CaseEventLog) {
+    let mut factions: Vec<String> = cases
+        .cases
         .iter()
-        .filter(|event| {
-            event.location_id == case.location_id
-                && case
-                    .signature_pattern
-                    .contains(&event.signature.signature.signature_type)
+        .filter(|case| case.status == CaseStatus::Active)
+        .map(|case| case.faction_id.clone())
+        .collect();
+    factions.sort();
+    factions.dedup();
+
+    for faction_id in factions {
+        while cases.active_case_count(&faction_id) > MAX_ACTIVE_CASES_PER_FACTION {
+            let mut active: Vec<usize> = cases
+                .cases
+                .iter()
+                .enumerate()
+                .filter(|(_, case)| {
+                    case.faction_id == faction_id && case.status == CaseStatus::Active
+                })
+                .map(|(idx, _)| idx)
+                .collect();
+            active.sort_by_key(|idx| (cases.cases[*idx].progress, cases.cases[*idx].case_id));
+
+            let pair = active.iter().enumerate().find_map(|(pos, &absorbed)| {
+                active[pos + 1..]
+                    .iter()
+                    .find(|&&idx| {
+                        cases.cases[idx].suspect_persona == cases.cases[absorbed].suspect_persona
+                    })
+                    .map(|&survivor| (absorbed, survivor))
+            });
+            let Some((absorbed, survivor)) = pair else {
+                break;
+            };
+
+            let folded = cases.cases.remove(absorbed);
+            let survivor = if survivor > absorbed { survivor - 1 } else { survivor };
+            let case = &mut cases.cases[survivor];
+            for signature in folded.signature_pattern {
+                if !case.signature_pattern.contains(&signature) {
+                    case.signature_pattern.push(signature);
+                }
+            }
+            case.progress = (case.progress + folded.progress / 2).min(100);
+            case.heat_lock |= folded.heat_lock;
+            log.0.push(format!(
+                "Case {} folded into case {}: {} is stretched thin",
+                folded.case_id, case.case_id, faction_id
+            ));
+        }
+    }
+}
+
+fn can_perceive(perceived: Option<&HashSet<SignatureType>>, signature: &SignatureType) -> bool {
+    perceived.is_none_or(|types| types.contains(signature))
+}
+
+/// Evidence as each case sees it from its own location. Signatures come from
+/// the store's location index, which emits and decay keep current; identity
+/// evidence is a much smaller store and is grouped once per update.
+/// Cases keep no last-evaluated turn: a match counts the signatures still live
+/// at the location, and decay shrinks that count, so replaying only new emits
+/// would drift from the full scan.
+struct EvidenceIndex<'a> {
+    evidence: &'a WorldEvidence,
+    items: HashMap<LocationId, Vec<&'a IdentityEvidenceItem>>,
+}
+
+impl<'a> EvidenceIndex<'a> {
+    fn build(evidence: &'a WorldEvidence, identity: &'a IdentityEvidenceStore) -> Self {
+        let mut items: HashMap<LocationId, Vec<&IdentityEvidenceItem>> = HashMap::new();
+        for item in &identity.items {
+            items.entry(item.location_id).or_default().push(item);
+        }
+        Self { evidence, items }
+    }
+
+    fn matching_signatures(
+        &self,
+        case: &crate::simulation::case::Case,
+        perceived: Option<&HashSet<SignatureType>>,
+    ) -> usize {
+        self.evidence.count_at(case.location_id, |signature_type| {
+            case.signature_pattern.contains(&signature_type)
+                && can_perceive(perceived, &signature_type)
         })
-        .count()
+    }
+
+    fn matching_evidence(
+        &self,
+        case: &crate::simulation::case::Case,
+        perceived: Option<&HashSet<SignatureType>>,
+    ) -> usize {
+        self.items
+            .get(&case.location_id)
+            .map(|items| count_matching_evidence(items, case, perceived))
+            .unwrap_or(0)
+    }
 }
 
 fn count_matching_evidence(
-    identity: &IdentityEvidenceStore,
+    items: &[&IdentityEvidenceItem],
     case: &crate::simulation::case::Case,
+    perceived: Option<&HashSet<SignatureType>>,
 ) -> usize {
-    identity
-        .items
+    items
         .iter()
-        .filter(|item| item.location_id == case.location_id)
+        .filter(|item| item.suspected_persona() == case.suspect_persona.as_deref())
         .filter(|item| {
             let signature_match = item
                 .signatures
                 .iter()
-                .any(|sig| case.signature_pattern.contains(sig));
+                .any(|sig| case.signature_pattern.contains(sig) && can_perceive(perceived, sig));
             if !signature_match {
                 return false;
             }
@@ -91,7 +208,97 @@ fn count_matching_evidence(
         .count()
 }
 
-fn update_case_milestones(case: &mut crate::simulation::case::Case, log: &mut CaseEventLog) {
+/// Splits every real-trail case at `location_id` with a twin chasing `decoy`,
+/// then rolls whether the decoy has been leaned on too hard. A debunked decoy
+/// folds its twins' progress back into the real cases. Returns true when the
+/// decoy was debunked.
+pub fn apply_decoy_evidence(
+    cases: &mut CaseRegistry,
+    identity: &mut IdentityEvidenceStore,
+    location_id: LocationId,
+    decoy: &str,
+    turn: u64,
+    log: &mut CaseEventLog,
+) -> bool {
+    let real: Vec<(String, Vec<SignatureType>, bool)> = cases
+        .cases
+        .iter()
+        .filter(|case| {
+            case.status == CaseStatus::Active
+                && case.location_id == location_id
+                && case.suspect_persona.is_none()
+        })
+        .map(|case| (case.faction_id.clone(), case.signature_pattern.clone(), case.heat_lock))
+        .collect();
+    for (faction_id, pattern, heat_lock) in real {
+        let has_twin = cases.cases.iter().any(|case| {
+            case.status == CaseStatus::Active
+                && case.location_id == location_id
+                && case.faction_id == faction_id
+                && case.suspect_persona.as_deref() == Some(decoy)
+        });
+        if has_twin {
+            continue;
+        }
+        let case_id = cases.create_case(faction_id, location_id, pattern, heat_lock);
+        if let Some(case) = cases.cases.iter_mut().find(|case| case.case_id == case_id) {
+            case.suspect_persona = Some(decoy.to_string());
+        }
+        log.0.push(format!("Case {}: a second suspect emerges", case_id));
+    }
+
+    let planted = identity.decoy_count(decoy);
+    if planted <= DECOY_SAFE_PLANTS {
+        return false;
+    }
+    let chance = ((planted - DECOY_SAFE_PLANTS) as u64 * DECOY_DEBUNK_CHANCE_PER_PLANT)
+        .min(DECOY_DEBUNK_MAX_CHANCE);
+    if decoy_roll(turn, decoy) >= chance {
+        return false;
+    }
+
+    identity.debunk(decoy);
+    let twins: Vec<(u32, String, LocationId, u32)> = cases
+        .cases
+        .iter()
+        .filter(|case| case.suspect_persona.as_deref() == Some(decoy))
+        .map(|case| (case.case_id, case.faction_id.clone(), case.location_id, case.progress))
+        .collect();
+    for (case_id, faction_id, twin_location, progress) in twins {
+        if let Some(real) = cases.cases.iter_mut().find(|case| {
+            case.status == CaseStatus::Active
+                && case.location_id == twin_location
+                && case.faction_id == faction_id
+                && case.suspect_persona.is_none()
+        }) {
+            real.progress = (real.progress + progress).min(100);
+        }
+        log.0.push(format!(
+            "Case {}: decoy evidence debunked, suspicion merges back",
+            case_id
+        ));
+    }
+    cases
+        .cases
+        .retain(|case| case.suspect_persona.as_deref() != Some(decoy));
+    true
+}
+
+fn decoy_roll(turn: u64, decoy: &str) -> u64 {
+    let mut state = decoy.bytes().fold(turn ^ 0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+    (state >> 33) % 100
+}
+
+fn update_case_milestones(
+    case: &mut crate::simulation::case::Case,
+    recognized: bool,
+    corroborated: bool,
+    location_heat: i32,
+    log: &mut CaseEventLog,
+) {
     if case.progress >= 30 && case.milestone < 1 {
         case.milestone = 1;
         case.pressure_actions.push("PROFILE_FORMED".to_string());
@@ -100,14 +307,30 @@ fn update_case_milestones(case: &mut crate::simulation::case::Case, log: &mut Ca
             case.case_id
         ));
     }
+    if recognized
+        && corroborated
+        && case.milestone >= 1
+        && case.target_type == CaseTargetType::UnknownMasked
+    {
+        // Locals have seen the same masked figure often enough to describe them.
+        case.target_type = CaseTargetType::KnownMasked;
+        log.0.push(format!(
+            "Case {}: witnesses recognize a masked regular",
+            case.case_id
+        ));
+    }
     if case.progress >= 60 && case.milestone < 2 {
         case.milestone = 2;
-        if case.target_type == CaseTargetType::UnknownMasked {
-            case.target_type = CaseTargetType::KnownMasked;
-        }
         case.pressure_actions.push("ACTIVE_OPERATIONS".to_string());
         log.0.push(format!("Case {}: search warrant ready", case.case_id));
     }
+    if corroborated && case.milestone >= 2 && case.target_type == CaseTargetType::UnknownMasked {
+        case.target_type = CaseTargetType::KnownMasked;
+        log.0.push(format!(
+            "Case {}: repeated sightings link a masked suspect",
+            case.case_id
+        ));
+    }
     if case.progress >= 85 && case.milestone < 3 {
         case.milestone = 3;
         if case.target_type != CaseTargetType::CivilianLink {
@@ -119,9 +342,436 @@ fn update_case_milestones(case: &mut crate::simulation::case::Case, log: &mut Ca
             case.case_id
         ));
     }
-    if case.progress >= 100 && case.status == CaseStatus::Active {
+    if case.status == CaseStatus::Active && case.resolution_hold(location_heat).is_none() {
         case.status = CaseStatus::Resolved;
         case.pressure_actions.push("CONVERGENCE".to_string());
         log.0.push(format!("Case {}: resolved", case.case_id));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::signature::{SignatureInstance, SignatureSpec};
+    use crate::simulation::city::LocationId;
+
+    #[test]
+    fn arcane_signatures_only_advance_occult_cases() {
+        let director = FactionDirector::load_default().expect("faction data");
+        let perception = director.faction_perception();
+        let mut city = CityState::default();
+        let location_id = LocationId(1);
+        city.locations.get_mut(&location_id).unwrap().investigators = 0;
+
+        let mut cases = CaseRegistry::default();
+        let pattern = vec![SignatureType::ArcaneResonance];
+        let police = cases.create_case("metro_police".to_string(), location_id, pattern.clone(), false);
+        let occult = cases.create_case("arcane_order".to_string(), location_id, pattern, false);
+
+        let mut evidence = WorldEvidence::default();
+        evidence.emit(
+            location_id,
+            &[SignatureInstance {
+                signature: SignatureSpec {
+                    signature_type: SignatureType::ArcaneResonance,
+                    strength: 5,
+                    persistence_turns: 3,
+                },
+                remaining_turns: 3,
+            }],
+        );
+
+        let identity = IdentityEvidenceStore::default();
+        let mut log = CaseEventLog::default();
+        update_cases(&mut cases, &city, &evidence, &identity, &perception, &mut log);
+
+        let progress = |id: u32| cases.cases.iter().find(|case| case.case_id == id).unwrap().progress;
+        assert_eq!(progress(police), 0);
+        assert!(progress(occult) > 0);
+    }
+
+    #[test]
+    fn decoy_evidence_advances_the_decoy_case_until_it_is_debunked() {
+        let mut city = CityState::default();
+        let location_id = LocationId(1);
+        city.locations.get_mut(&location_id).unwrap().investigators = 0;
+        let mut cases = CaseRegistry::default();
+        let pattern = vec![SignatureType::ThermalBloom];
+        let real = cases.create_case("metro_police".to_string(), location_id, pattern, false);
+        let evidence = WorldEvidence::default();
+        let perception = HashMap::new();
+        let mut identity = IdentityEvidenceStore::default();
+        let mut log = CaseEventLog::default();
+
+        let planted = identity.record(
+            location_id,
+            1,
+            vec![SignatureType::ThermalBloom],
+            2,
+            30,
+            crate::simulation::identity_evidence::PersonaHint::Masked,
+            Vec::new(),
+        );
+        assert!(identity.attribute(planted, "ember_jack"));
+        assert!(!apply_decoy_evidence(&mut cases, &mut identity, location_id, "ember_jack", 1, &mut log));
+        update_cases(&mut cases, &city, &evidence, &identity, &perception, &mut log);
+
+        let decoy = cases
+            .cases
+            .iter()
+            .find(|case| case.suspect_persona.as_deref() == Some("ember_jack"))
+            .expect("decoy twin case");
+        let decoy_id = decoy.case_id;
+        assert!(decoy.progress > 0);
+        let progress = |cases: &CaseRegistry, id: u32| {
+            cases.cases.iter().find(|case| case.case_id == id).map(|case| case.progress)
+        };
+        assert_eq!(progress(&cases, real), Some(0));
+
+        let mut debunked = false;
+        for turn in 2..40 {
+            let id = identity.record(
+                location_id,
+                turn,
+                vec![SignatureType::ThermalBloom],
+                2,
+                30,
+                crate::simulation::identity_evidence::PersonaHint::Masked,
+                Vec::new(),
+            );
+            identity.attribute(id, "ember_jack");
+            if apply_decoy_evidence(&mut cases, &mut identity, location_id, "ember_jack", turn, &mut log) {
+                debunked = true;
+                break;
+            }
+        }
+        assert!(debunked, "leaning on a decoy eventually gets it debunked");
+        assert_eq!(progress(&cases, decoy_id), None);
+        assert!(progress(&cases, real).unwrap() > 0);
+        assert_eq!(identity.decoy_count("ember_jack"), 0);
+    }
+
+    #[test]
+    fn heat_locked_case_waits_for_heat_to_drop() {
+        let mut city = CityState::default();
+        let location_id = LocationId(1);
+        {
+            let location = city.locations.get_mut(&location_id).unwrap();
+            location.investigators = 5;
+            location.heat = 70;
+        }
+        let mut cases = CaseRegistry::default();
+        cases.create_case("metro_police".to_string(), location_id, Vec::new(), true);
+        let evidence = WorldEvidence::default();
+        let identity = IdentityEvidenceStore::default();
+        let perception = HashMap::new();
+        let mut log = CaseEventLog::default();
+
+        for _ in 0..12 {
+            update_cases(&mut cases, &city, &evidence, &identity, &perception, &mut log);
+        }
+        let case = &cases.cases[0];
+        assert_eq!(case.progress, 100);
+        assert_eq!(case.status, CaseStatus::Active);
+        assert_eq!(
+            case.resolution_hold(70),
+            Some(crate::simulation::case::CaseHold::HeatLocked {
+                heat: 70,
+                threshold: crate::simulation::case::HEAT_LOCK_THRESHOLD,
+            })
+        );
+
+        {
+            let location = city.locations.get_mut(&location_id).unwrap();
+            location.investigators = 0;
+            location.heat = 20;
+        }
+        update_cases(&mut cases, &city, &evidence, &identity, &perception, &mut log);
+        assert_eq!(cases.cases[0].status, CaseStatus::Resolved);
+        assert!(!cases.cases[0].is_heat_locked());
+    }
+
+    #[test]
+    fn faction_active_cases_stay_at_the_cap() {
+        let mut city = CityState::default();
+        let mut cases = CaseRegistry::default();
+        let mut evidence = WorldEvidence::default();
+        let pattern = vec![SignatureType::ThermalBloom];
+        let location_ids: Vec<LocationId> = city.locations.keys().copied().collect();
+        for round in 0..3 {
+            for location_id in &location_ids {
+                city.locations.get_mut(location_id).unwrap().investigators = 0;
+                cases.create_case(
+                    "metro_police".to_string(),
+                    *location_id,
+                    pattern.clone(),
+                    false,
+                );
+                evidence.emit(
+                    *location_id,
+                    &[SignatureInstance {
+                        signature: SignatureSpec {
+                            signature_type: SignatureType::ThermalBloom,
+                            strength: 4 + round,
+                            persistence_turns: 3,
+                        },
+                        remaining_turns: 3,
+                    }],
+                );
+            }
+        }
+        let created = cases.cases.len();
+        assert!(created > MAX_ACTIVE_CASES_PER_FACTION);
+
+        let identity = IdentityEvidenceStore::default();
+        let mut log = CaseEventLog::default();
+        update_cases(&mut cases, &city, &evidence, &identity, &HashMap::new(), &mut log);
+
+        assert_eq!(
+            cases.active_case_count("metro_police"),
+            MAX_ACTIVE_CASES_PER_FACTION
+        );
+        assert_eq!(cases.cases.len(), MAX_ACTIVE_CASES_PER_FACTION);
+        assert_eq!(
+            log.0.iter().filter(|line| line.contains("folded into")).count(),
+            created - MAX_ACTIVE_CASES_PER_FACTION
+        );
+        assert!(cases.cases.iter().all(|case| case.progress > 0));
+    }
+
+    #[test]
+    fn a_masked_suspect_is_only_linked_after_corroborated_sightings() {
+        let mut city = CityState::default();
+        let location_id = LocationId(1);
+        city.locations.get_mut(&location_id).unwrap().investigators = 0;
+        let mut cases = CaseRegistry::default();
+        let pattern = vec![SignatureType::ThermalBloom];
+        cases.create_case("metro_police".to_string(), location_id, pattern, false);
+        cases.cases[0].progress = 70;
+        let evidence = WorldEvidence::default();
+        let mut identity = IdentityEvidenceStore::default();
+        let mut log = CaseEventLog::default();
+        let sight = |identity: &mut IdentityEvidenceStore, tick: u64| {
+            identity.record(
+                location_id,
+                tick,
+                vec![SignatureType::ThermalBloom],
+                6,
+                100,
+                PersonaHint::Masked,
+                Vec::new(),
+            );
+        };
+
+        sight(&mut identity, 1);
+        update_cases(&mut cases, &city, &evidence, &identity, &HashMap::new(), &mut log);
+        assert!(cases.cases[0].milestone >= 2);
+        assert_eq!(cases.cases[0].target_type, CaseTargetType::UnknownMasked);
+
+        sight(&mut identity, 2);
+        sight(&mut identity, 3);
+        assert!(identity.persona_confidence(&PersonaHint::Masked) >= MASKED_LINK_CONFIDENCE);
+        update_cases(&mut cases, &city, &evidence, &identity, &HashMap::new(), &mut log);
+        assert_eq!(cases.cases[0].target_type, CaseTargetType::KnownMasked);
+
+        identity.tick_decay(100);
+        assert_eq!(identity.persona_confidence(&PersonaHint::Masked), 0);
+    }
+
+    #[test]
+    fn one_sighting_with_many_signatures_is_not_corroboration() {
+        let mut identity = IdentityEvidenceStore::default();
+        for sig in [
+            SignatureType::ThermalBloom,
+            SignatureType::EmSpike,
+            SignatureType::AcousticShock,
+        ] {
+            identity.record(LocationId(1), 5, vec![sig], 6, 100, PersonaHint::Masked, Vec::new());
+        }
+        assert!(identity.persona_confidence(&PersonaHint::Masked) < MASKED_LINK_CONFIDENCE);
+        assert_eq!(identity.trails[0].sightings, 1);
+    }
+
+    #[test]
+    fn a_trail_elsewhere_does_not_link_a_masked_suspect() {
+        let mut city = CityState::default();
+        city.locations.get_mut(&LocationId(2)).unwrap().investigators = 1;
+        let mut cases = CaseRegistry::default();
+        let pattern = vec![SignatureType::ThermalBloom];
+        cases.create_case("metro_police".to_string(), LocationId(2), pattern, false);
+        cases.cases[0].progress = 70;
+        let evidence = WorldEvidence::default();
+        let mut identity = IdentityEvidenceStore::default();
+        let mut log = CaseEventLog::default();
+        for tick in 1..=4 {
+            identity.record(
+                LocationId(1),
+                tick,
+                vec![SignatureType::ThermalBloom],
+                6,
+                100,
+                PersonaHint::Masked,
+                Vec::new(),
+            );
+        }
+        assert!(
+            identity.trail_confidence(LocationId(1), &PersonaHint::Masked)
+                >= MASKED_LINK_CONFIDENCE
+        );
+
+        update_cases(&mut cases, &city, &evidence, &identity, &HashMap::new(), &mut log);
+        assert!(cases.cases[0].milestone >= 2);
+        assert_eq!(cases.cases[0].target_type, CaseTargetType::UnknownMasked);
+    }
+
+    fn full_scan_matches(evidence: &WorldEvidence, case: &crate::simulation::case::Case) -> usize {
+        evidence
+            .iter()
+            .filter(|event| {
+                event.location_id == case.location_id
+                    && case
+                        .signature_pattern
+                        .contains(&event.signature.signature.signature_type)
+            })
+            .count()
+    }
+
+    #[test]
+    fn indexed_matching_agrees_with_a_full_scan_through_emits_and_decay() {
+        let kinds = [
+            SignatureType::EmSpike,
+            SignatureType::ThermalBloom,
+            SignatureType::AcousticShock,
+            SignatureType::ArcaneResonance,
+        ];
+        let emit = |evidence: &mut WorldEvidence, n: u32| {
+            evidence.emit(
+                LocationId(n % 20),
+                &[SignatureInstance {
+                    signature: SignatureSpec {
+                        signature_type: kinds[n as usize % kinds.len()],
+                        strength: 3,
+                        persistence_turns: 1 + (n % 6) as i64,
+                    },
+                    remaining_turns: 1 + (n % 6) as i64,
+                }],
+            );
+        };
+        let mut evidence = WorldEvidence::default();
+        for n in 0..600u32 {
+            emit(&mut evidence, n);
+        }
+        let mut cases = CaseRegistry::default();
+        for n in 0..200u32 {
+            let pattern = vec![kinds[n as usize % kinds.len()], kinds[(n as usize + 1) % kinds.len()]];
+            cases.create_case(format!("faction_{n}"), LocationId(n % 20), pattern, false);
+        }
+        let identity = IdentityEvidenceStore::default();
+
+        for round in 0..8u32 {
+            let index = EvidenceIndex::build(&evidence, &identity);
+            for case in &cases.cases {
+                assert_eq!(
+                    index.matching_signatures(case, None),
+                    full_scan_matches(&evidence, case)
+                );
+            }
+            evidence.tick_decay();
+            for n in 0..25 {
+                emit(&mut evidence, round * 31 + n);
+            }
+        }
+
+        while !evidence.is_empty() {
+            evidence.tick_decay();
+        }
+        for location in 0..20 {
+            assert_eq!(evidence.count_at(LocationId(location), |_| true), 0);
+        }
+
+        // Stores rebuilt from saved events, as the world DB and a JSON import do.
+        let mut source = WorldEvidence::default();
+        for n in 0..40u32 {
+            emit(&mut source, n);
+        }
+        let json = serde_json::to_string(&source).expect("export evidence");
+        let imported: WorldEvidence = serde_json::from_str(&json).expect("import evidence");
+        let mut loaded = WorldEvidence::from_events(source.iter().cloned().collect());
+        for case in &cases.cases {
+            let index = EvidenceIndex::build(&loaded, &identity);
+            assert_eq!(
+                index.matching_signatures(case, None),
+                full_scan_matches(&loaded, case)
+            );
+            let index = EvidenceIndex::build(&imported, &identity);
+            assert_eq!(
+                index.matching_signatures(case, None),
+                full_scan_matches(&imported, case)
+            );
+        }
+        loaded.tick_decay();
+        for case in &cases.cases {
+            let index = EvidenceIndex::build(&loaded, &identity);
+            assert_eq!(
+                index.matching_signatures(case, None),
+                full_scan_matches(&loaded, case)
+            );
+        }
+    }
+
+    #[test]
+    fn indexed_counts_beat_a_full_scan_at_a_few_hundred_signatures() {
+        let kinds = [
+            SignatureType::EmSpike,
+            SignatureType::ThermalBloom,
+            SignatureType::AcousticShock,
+            SignatureType::ArcaneResonance,
+        ];
+        let mut evidence = WorldEvidence::default();
+        for n in 0..500u32 {
+            evidence.emit(
+                LocationId(n % 20),
+                &[SignatureSpec {
+                    signature_type: kinds[n as usize % kinds.len()],
+                    strength: 3,
+                    persistence_turns: 9,
+                }
+                .to_instance()],
+            );
+        }
+        let mut cases = CaseRegistry::default();
+        for n in 0..100u32 {
+            let pattern = vec![kinds[n as usize % kinds.len()], kinds[(n as usize + 1) % kinds.len()]];
+            cases.create_case(format!("faction_{n}"), LocationId(n % 20), pattern, false);
+        }
+        let identity = IdentityEvidenceStore::default();
+        let index = EvidenceIndex::build(&evidence, &identity);
+
+        // Best of several runs, so a busy machine slows both sides alike.
+        let time = |count: &dyn Fn(&crate::simulation::case::Case) -> usize| {
+            (0..5)
+                .map(|_| {
+                    let started = std::time::Instant::now();
+                    let mut total = 0;
+                    for _ in 0..20 {
+                        for case in &cases.cases {
+                            total += count(std::hint::black_box(case));
+                        }
+                    }
+                    std::hint::black_box(total);
+                    started.elapsed()
+                })
+                .min()
+                .unwrap()
+        };
+        let indexed = time(&|case| index.matching_signatures(case, None));
+        let scanned = time(&|case| full_scan_matches(&evidence, case));
+        assert!(
+            indexed * 4 < scanned,
+            "indexed {:?} vs full scan {:?} over {} signatures",
+            indexed,
+            scanned,
+            evidence.len()
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use bevy_ecs::prelude::*;
 
+use crate::core::world::WorldSeed;
 use crate::simulation::civilian::{tick_civilian_life, CivilianState};
 use crate::simulation::pressure::PressureState;
 use crate::simulation::time::GameTime;
@@ -8,8 +9,9 @@ pub fn civilian_system(
     mut civilian: ResMut<CivilianState>,
     time: Res<GameTime>,
     mut pressure: ResMut<PressureState>,
+    seed: Res<WorldSeed>,
 ) {
-    tick_civilian_life(&mut civilian, &time);
+    tick_civilian_life(&mut civilian, &time, seed.0);
     apply_civilian_pressure(&civilian, &mut pressure);
 }
 
@@ -3,6 +3,7 @@ use bevy_ecs::prelude::*;
 use crate::components::combat::Health;
 use crate::components::world::EntityId;
 use crate::core::world::{ActionIntent, ActionQueue};
+use crate::simulation::stats::RunStats;
 
 /// Resource capturing the most recent combat entries.
 #[derive(Resource, Default, Debug)]
@@ -14,6 +15,7 @@ const BASE_DAMAGE: i32 = 10;
 pub fn combat_system(
     intents: Res<ActionQueue>,
     mut log: ResMut<CombatLog>,
+    mut stats: ResMut<RunStats>,
     mut healths: Query<(&EntityId, &mut Health)>,
 ) {
     log.0.clear();
@@ -28,7 +30,9 @@ pub fn combat_system(
             if let Some(tid) = target_id {
                 for (entity_id, mut health) in healths.iter_mut() {
                     if entity_id.0 == *tid {
-                        apply_damage(entity_id.0, &mut health, BASE_DAMAGE, &mut log.0);
+                        if apply_damage(entity_id.0, &mut health, BASE_DAMAGE, &mut log.0) {
+                            stats.record_fight_won();
+                        }
                         applied = true;
                         break;
                     }
@@ -36,7 +40,9 @@ pub fn combat_system(
             } else {
                 for (entity_id, mut health) in healths.iter_mut() {
                     if entity_id.0 != *attacker_id {
-                        apply_damage(entity_id.0, &mut health, BASE_DAMAGE, &mut log.0);
+                        if apply_damage(entity_id.0, &mut health, BASE_DAMAGE, &mut log.0) {
+                            stats.record_fight_won();
+                        }
                         applied = true;
                         break;
                     }
@@ -51,7 +57,9 @@ pub fn combat_system(
     }
 }
 
-fn apply_damage(target_uid: u32, health: &mut Health, amount: i32, log: &mut Vec<String>) {
+/// Returns true when this hit is the one that defeats the target.
+fn apply_damage(target_uid: u32, health: &mut Health, amount: i32, log: &mut Vec<String>) -> bool {
+    let was_standing = health.current > 0;
     health.current = (health.current - amount).max(0);
     if health.current == 0 {
         log.push(format!("Entity {} is defeated.", target_uid));
@@ -64,4 +72,5 @@ fn apply_damage(target_uid: u32, health: &mut Health, amount: i32, log: &mut Vec
             health.max
         ));
     }
+    was_standing && health.current == 0
 }
@@ -1,5 +1,6 @@
 use bevy_ecs::prelude::*;
 
+use crate::core::world::WorldSeed;
 use crate::simulation::civilian::{tick_civilian_economy, CivilianState};
 use crate::simulation::economy::{can_fund_gadget, WealthTier};
 use crate::simulation::time::GameTime;
@@ -59,6 +60,34 @@ pub fn attempt_gadget_purchase(
 }
 
 /// Runs the daily economy tick (income + upkeep) for civilian finances.
-pub fn economy_system(mut civilian: ResMut<CivilianState>, time: Res<GameTime>) {
-    tick_civilian_economy(&mut civilian, &time);
+pub fn economy_system(
+    mut civilian: ResMut<CivilianState>,
+    time: Res<GameTime>,
+    seed: Res<WorldSeed>,
+) {
+    tick_civilian_economy(&mut civilian, &time, seed.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn economy_day_boundary_produces_result() {
+        let mut civilian = CivilianState::default();
+        let mut time = GameTime::default();
+        assert!(tick_civilian_economy(&mut civilian, &time, 0).is_some());
+        time.advance();
+        assert!(tick_civilian_economy(&mut civilian, &time, 0).is_none());
+
+        let day = time.day;
+        while time.day == day {
+            time.advance();
+        }
+        let result = tick_civilian_economy(&mut civilian, &time, 0)
+            .expect("new economy day should produce a result");
+        assert_eq!(result.net_cr, result.income_cr - result.upkeep_cr);
+        assert_eq!(result.balance_cr, civilian.wealth.current_cr);
+        assert!(result.income_cr > 0);
+    }
 }
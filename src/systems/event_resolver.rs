@@ -1,10 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use bevy_ecs::prelude::*;
 
 use crate::rules::signature::SignatureType;
 use crate::simulation::case::{CaseEventLog, CaseRegistry};
-use crate::simulation::city::{CityState, LocationId};
+use crate::simulation::city::{CityState, LocationId, LocationState, DEFAULT_LOCATION_ECONOMY};
+use crate::simulation::civilian::CivilianState;
 use crate::simulation::evidence::WorldEvidence;
 use crate::systems::faction::{FactionEvent, FactionEventLog};
 
@@ -26,6 +27,7 @@ pub fn event_resolver_system(
         &evidence,
         &mut cases,
         &mut case_log,
+        None,
     );
 }
 
@@ -36,13 +38,17 @@ pub fn resolve_faction_events(
     evidence: &WorldEvidence,
     cases: &mut CaseRegistry,
     case_log: &mut CaseEventLog,
+    mut civilian: Option<&mut CivilianState>,
 ) {
     resolved.0.clear();
 
+    let active_location = city.active_location;
+    let mut shaken = HashSet::new();
     for event in events.0.drain(..) {
         let Some(location) = city.locations.get_mut(&event.location_id) else {
             continue;
         };
+        let economy_before = location.economy;
 
         for action in &event.actions {
             match action.kind.as_str() {
@@ -81,10 +87,67 @@ pub fn resolve_faction_events(
                 "PROXY_CRIME" => {
                     location.gang_units = location.gang_units.saturating_add(1);
                     location.crime_pressure = (location.crime_pressure + 5).clamp(0, 100);
+                    adjust_economy(location, -2);
+                }
+                "EXTORTION" => {
+                    location.crime_pressure = (location.crime_pressure + 4).clamp(0, 100);
+                    adjust_economy(location, -4);
+                    let take = action
+                        .params
+                        .get("take")
+                        .and_then(|value| value.as_i64())
+                        .unwrap_or(DEFAULT_EXTORTION_TAKE) as i32;
+                    if event.location_id == active_location {
+                        if let Some(civilian) = civilian.as_deref_mut() {
+                            if civilian.housing.home_location() == event.location_id {
+                                let paid = civilian.lose_cash(take);
+                                if paid > 0 {
+                                    case_log.0.push(format!(
+                                        "{} extorted {} cash from you at location {}",
+                                        event.faction_id, paid, event.location_id.0
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                }
+                "PROTECTION" => {
+                    location.crime_pressure = (location.crime_pressure - 2).clamp(0, 100);
+                    let entry = location
+                        .faction_influence
+                        .entry(event.faction_id.clone())
+                        .or_insert(0);
+                    *entry = entry.saturating_add(5);
+                }
+                "EXPAND_TERRITORY" => {
+                    location.gang_units = location.gang_units.saturating_add(1);
+                    adjust_economy(location, -1);
+                    let entry = location
+                        .faction_influence
+                        .entry(event.faction_id.clone())
+                        .or_insert(0);
+                    *entry = entry.saturating_add(3);
+                }
+                "HIRE_ENFORCER" => {
+                    location.gang_units = location.gang_units.saturating_add(1);
+                    location.crime_pressure = (location.crime_pressure + 3).clamp(0, 100);
+                }
+                "ORDER_HIT" => {
+                    location.crime_pressure = (location.crime_pressure + 6).clamp(0, 100);
+                    adjust_economy(location, -2);
+                }
+                "START_EVACUATION" => {
+                    adjust_economy(location, -3);
+                }
+                "DEPLOY_SURVEILLANCE" | "START_MONITORING" => {
+                    location.surveillance_level = (location.surveillance_level + 5).clamp(0, 100);
                 }
                 _ => {}
             }
         }
+        if location.economy < economy_before {
+            shaken.insert(event.location_id);
+        }
 
         let entry = location
             .faction_influence
@@ -94,6 +157,30 @@ pub fn resolve_faction_events(
 
         resolved.0.push(event);
     }
+
+    for (location_id, location) in city.locations.iter_mut() {
+        if !shaken.contains(location_id) {
+            recover_economy(location);
+        }
+    }
+}
+
+const DEFAULT_EXTORTION_TAKE: i64 = 15;
+
+/// How far a depressed district's economy climbs back toward the city
+/// baseline on each resolution pass where no faction action hurt it.
+/// Districts above the baseline keep their authored standing.
+const ECONOMY_RECOVERY_PER_PASS: i32 = 1;
+
+fn adjust_economy(location: &mut LocationState, delta: i32) {
+    location.economy = (location.economy + delta).clamp(0, 100);
+}
+
+fn recover_economy(location: &mut LocationState) {
+    if location.economy < DEFAULT_LOCATION_ECONOMY {
+        location.economy =
+            (location.economy + ECONOMY_RECOVERY_PER_PASS).min(DEFAULT_LOCATION_ECONOMY);
+    }
 }
 
 fn derive_signature_pattern(
@@ -101,7 +188,7 @@ fn derive_signature_pattern(
     location_id: LocationId,
 ) -> Vec<SignatureType> {
     let mut counts: HashMap<SignatureType, u32> = HashMap::new();
-    for event in evidence.signatures.iter() {
+    for event in evidence.iter() {
         if event.location_id == location_id {
             *counts
                 .entry(event.signature.signature.signature_type)
@@ -113,3 +200,59 @@ fn derive_signature_pattern(
     entries.sort_by(|a, b| b.1.cmp(&a.1));
     entries.into_iter().take(3).map(|(sig, _)| sig).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::factions::ResponseAction;
+
+    fn extortion_at(location_id: LocationId) -> FactionEvent {
+        FactionEvent {
+            faction_id: "test_gang".to_string(),
+            faction_type_id: "gang".to_string(),
+            location_id,
+            level: "high".to_string(),
+            actions: vec![ResponseAction {
+                kind: "EXTORTION".to_string(),
+                params: serde_json::Value::Null,
+            }],
+        }
+    }
+
+    #[test]
+    fn district_economies_recover_toward_baseline_between_hits() {
+        let mut city = CityState::default();
+        let hit = LocationId(1);
+        let boomed = *city.locations.keys().find(|id| **id != hit).expect("second location");
+        city.locations.get_mut(&hit).unwrap().economy = DEFAULT_LOCATION_ECONOMY;
+        city.locations.get_mut(&boomed).unwrap().economy = DEFAULT_LOCATION_ECONOMY + 2;
+        let evidence = WorldEvidence::default();
+        let mut cases = CaseRegistry::default();
+        let mut case_log = CaseEventLog::default();
+        let mut resolved = ResolvedFactionEventLog::default();
+        let mut resolve = |city: &mut CityState, events: Vec<FactionEvent>| {
+            resolve_faction_events(
+                &mut FactionEventLog(events),
+                &mut resolved,
+                city,
+                &evidence,
+                &mut cases,
+                &mut case_log,
+                None,
+            );
+        };
+
+        for _ in 0..3 {
+            resolve(&mut city, vec![extortion_at(hit)]);
+        }
+        assert_eq!(city.locations[&hit].economy, DEFAULT_LOCATION_ECONOMY - 12);
+        assert_eq!(city.locations[&boomed].economy, DEFAULT_LOCATION_ECONOMY + 2);
+
+        resolve(&mut city, Vec::new());
+        assert_eq!(city.locations[&hit].economy, DEFAULT_LOCATION_ECONOMY - 11);
+        for _ in 0..20 {
+            resolve(&mut city, Vec::new());
+        }
+        assert_eq!(city.locations[&hit].economy, DEFAULT_LOCATION_ECONOMY);
+    }
+}
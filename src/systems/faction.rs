@@ -69,6 +69,71 @@ impl FactionDirector {
             last_levels: HashMap::new(),
         }
     }
+
+    /// Layers authored faction types and instances over the loaded ones, replacing
+    /// entries that share an id; `replace` drops the existing instances first.
+    pub fn merge_scenario(
+        &mut self,
+        types: Vec<FactionTypeDefinition>,
+        instances: Vec<FactionInstanceDefinition>,
+        replace: bool,
+    ) -> Result<(), DataError> {
+        for def in types {
+            self.types.insert(def.id.clone(), def);
+        }
+        if replace {
+            self.instances.clear();
+            self.last_levels.clear();
+        }
+        for instance in instances {
+            if !self.types.contains_key(&instance.type_id) {
+                return Err(DataError::Validation(format!(
+                    "faction instance {} references unknown type_id {}",
+                    instance.id, instance.type_id
+                )));
+            }
+            match self.instances.iter_mut().find(|existing| existing.id == instance.id) {
+                Some(existing) => *existing = instance,
+                None => self.instances.push(instance),
+            }
+        }
+        Ok(())
+    }
+
+    /// Factions that contest street control: any instance or type tagged `gang`.
+    pub fn gang_faction_ids(&self) -> HashSet<String> {
+        self.instances
+            .iter()
+            .filter(|instance| {
+                instance.tags.iter().any(|tag| tag == "gang")
+                    || self
+                        .types
+                        .get(&instance.type_id)
+                        .map(|def| def.tags.iter().any(|tag| tag == "gang"))
+                        .unwrap_or(false)
+            })
+            .map(|instance| instance.id.clone())
+            .collect()
+    }
+
+    /// Signature types each faction can perceive, from its detection profile.
+    /// Factions with an empty profile are left out and perceive everything.
+    pub fn faction_perception(&self) -> HashMap<String, HashSet<SignatureType>> {
+        self.instances
+            .iter()
+            .filter_map(|instance| {
+                let type_def = self.types.get(&instance.type_id)?;
+                let detection = detection_profile_owned(instance, type_def);
+                if detection.signature_types.is_empty() {
+                    return None;
+                }
+                Some((
+                    instance.id.clone(),
+                    detection.signature_types.into_iter().collect(),
+                ))
+            })
+            .collect()
+    }
 }
 
 pub fn faction_director_system(
@@ -154,7 +219,7 @@ fn collect_signatures_by_location(
     evidence: &WorldEvidence,
 ) -> HashMap<LocationId, HashSet<SignatureType>> {
     let mut out: HashMap<LocationId, HashSet<SignatureType>> = HashMap::new();
-    for event in evidence.signatures.iter() {
+    for event in evidence.iter() {
         out.entry(event.location_id)
             .or_default()
             .insert(event.signature.signature.signature_type);
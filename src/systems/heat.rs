@@ -1,10 +1,11 @@
 use bevy_ecs::prelude::*;
 
 use crate::components::world::{Player, Position};
+use crate::rules::expression::EnvironmentEffect;
 use crate::rules::signature::{SignatureInstance, SignatureType};
 use crate::simulation::case::CaseRegistry;
 use crate::simulation::city::{
-    CityEvent, CityEventKind, CityEventLog, CityId, CityState, HeatResponse, LocationId,
+    CityEvent, CityEventKind, CityEventLog, CityId, CityState, HeatResponseConfig, LocationId,
     LocationTag,
 };
 use crate::simulation::combat::CombatConsequence;
@@ -37,8 +38,8 @@ pub fn signature_heat_system(
 ) {
     log.0.clear();
 
-    for event in evidence.signatures.iter_mut() {
-        let (in_public, surveillance_level, witness_count) = city
+    for (event, was_new) in evidence.iter_marking_seen() {
+        let (in_public, surveillance_level, witness_count, familiarity) = city
             .locations
             .get(&event.location_id)
             .map(|location| {
@@ -48,9 +49,14 @@ pub fn signature_heat_system(
                 } else {
                     0
                 };
-                (in_public, location.surveillance_level, witness_count)
+                (
+                    in_public,
+                    location.surveillance_level,
+                    witness_count,
+                    location.witness_familiarity(),
+                )
             })
-            .unwrap_or((true, 0, 0));
+            .unwrap_or((true, 0, 0, 0));
         apply_signatures(
             &mut city,
             event.location_id,
@@ -61,10 +67,14 @@ pub fn signature_heat_system(
             &mut city_events,
         );
 
-        if event.is_new {
-            event.is_new = false;
-            let visual_quality = (surveillance_level as i32 + (witness_count as i32 * 10))
+        if was_new {
+            let visual_quality = (surveillance_level + (witness_count as i32 * 10) + familiarity)
                 .clamp(0, 100) as u8;
+            if witness_count > 0 {
+                if let Some(location) = city.locations.get_mut(&event.location_id) {
+                    location.remember_sighting();
+                }
+            }
             identity.record(
                 event.location_id,
                 time.tick,
@@ -78,6 +88,7 @@ pub fn signature_heat_system(
     }
 
     evidence.tick_decay();
+    identity.tick_decay(1);
 }
 
 /// System: passive heat decay across all locations.
@@ -111,15 +122,57 @@ pub fn apply_signatures(
     }
 
     let city_id = city.city_id;
+    let config = city.heat_response;
     if let Some(location) = city.locations.get_mut(&location_id) {
         location.heat = (location.heat + total_delta).clamp(0, 100);
-        update_response(location, log, city_id, city_events);
+        update_response(location, config, log, city_id, city_events);
     }
 }
 
+/// Applies an expression's direct environment effects at a location and logs
+/// each change that moved a value.
+pub fn apply_environment_effects(
+    city: &mut CityState,
+    location_id: LocationId,
+    effects: &[EnvironmentEffect],
+    log: &mut WorldEventLog,
+    city_events: &mut CityEventLog,
+) {
+    let city_id = city.city_id;
+    let config = city.heat_response;
+    let Some(location) = city.locations.get_mut(&location_id) else {
+        return;
+    };
+    for effect in effects {
+        let delta = location.apply_environment_effect(*effect);
+        if delta == 0 {
+            continue;
+        }
+        let value = location.environment_value(effect.field);
+        log.0.push(format!(
+            "Location {} {} {:+} -> {}",
+            location_id.0,
+            effect.field.label(),
+            delta,
+            value
+        ));
+        city_events.0.push(CityEvent {
+            city_id,
+            location_id,
+            kind: CityEventKind::EnvironmentAltered {
+                field: effect.field,
+                delta,
+                value,
+            },
+        });
+    }
+    update_response(location, config, log, city_id, city_events);
+}
+
 pub fn decay_heat(city: &mut CityState, cases: &CaseRegistry, city_events: &mut CityEventLog) {
     let mut log = WorldEventLog::default();
     let city_id = city.city_id;
+    let config = city.heat_response;
     for location in city.locations.values_mut() {
         let mut decay: i32 = 1;
         if location.police_presence >= 30 {
@@ -135,7 +188,8 @@ pub fn decay_heat(city: &mut CityState, cases: &CaseRegistry, city_events: &mut
             decay = decay.saturating_sub(1);
         }
         location.heat = (location.heat - decay).max(0);
-        update_response(location, &mut log, city_id, city_events);
+        location.fade_witness_memory();
+        update_response(location, config, &mut log, city_id, city_events);
     }
 }
 
@@ -157,17 +211,18 @@ pub fn apply_combat_consequence_heat(
 
     if let Some(location) = city.locations.get_mut(&location_id) {
         location.heat = (location.heat + total_delta).clamp(0, 100);
-        update_response(location, log, city.city_id, city_events);
+        update_response(location, city.heat_response, log, city.city_id, city_events);
     }
 }
 
 fn update_response(
     location: &mut crate::simulation::city::LocationState,
+    config: HeatResponseConfig,
     log: &mut WorldEventLog,
     city_id: CityId,
     city_events: &mut CityEventLog,
 ) {
-    let next = response_for_heat(location.heat);
+    let next = config.response_for(location.heat);
     if next != location.response {
         location.response = next;
         log.0.push(format!(
@@ -203,14 +258,53 @@ fn heat_from_signature(signature: &SignatureInstance) -> i32 {
     }
 }
 
-fn response_for_heat(heat: i32) -> HeatResponse {
-    if heat >= 70 {
-        HeatResponse::FactionAttention
-    } else if heat >= 50 {
-        HeatResponse::Investigation
-    } else if heat >= 30 {
-        HeatResponse::PolicePatrol
-    } else {
-        HeatResponse::None
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::expression::EnvironmentField;
+    use crate::rules::power::ExpressionId;
+
+    #[test]
+    fn emp_expression_cuts_surveillance_and_logs_the_change() {
+        let id = ExpressionId("emp_burst".to_string());
+        let effects = EnvironmentEffect::list_from_json(
+            &id,
+            &serde_json::json!({
+                "environment_effects": [{ "field": "surveillance_level", "delta": -40 }]
+            }),
+        )
+        .expect("valid effects");
+        assert_eq!(
+            effects,
+            vec![EnvironmentEffect {
+                field: EnvironmentField::SurveillanceLevel,
+                delta: -40,
+            }]
+        );
+
+        let mut city = CityState::default();
+        let location_id = LocationId(1);
+        city.locations.get_mut(&location_id).unwrap().surveillance_level = 25;
+        let mut log = WorldEventLog::default();
+        let mut city_events = CityEventLog::default();
+        apply_environment_effects(&mut city, location_id, &effects, &mut log, &mut city_events);
+
+        assert_eq!(city.locations[&location_id].surveillance_level, 0);
+        assert!(matches!(
+            city_events.0.last().map(|event| &event.kind),
+            Some(CityEventKind::EnvironmentAltered {
+                field: EnvironmentField::SurveillanceLevel,
+                delta: -25,
+                value: 0,
+            })
+        ));
+
+        let unknown = EnvironmentEffect::list_from_json(
+            &id,
+            &serde_json::json!({
+                "environment_effects": [{ "field": "weather", "delta": 5 }]
+            }),
+        );
+        assert!(unknown.is_err());
     }
 }
@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+
+use bevy_ecs::prelude::*;
+
+use crate::simulation::agents::{AgentEvent, AgentEventLog, AgentRegistry};
+use crate::simulation::city::{CityEvent, CityEventKind, CityEventLog, CityState, LocationState};
+use crate::systems::faction::FactionDirector;
+
+/// Gang influence at which the streets stop cooperating with patrols.
+pub const GANG_SUPPRESSION_INFLUENCE: u16 = 60;
+const LAW_PRESSURE_FLOOR: i32 = 30;
+const LAW_PRESSURE_PER_EROSION: i32 = 20;
+const MAX_EROSION_PER_TICK: i32 = 3;
+const LAW_AGENT_PRESSURE: i32 = 15;
+const POLICE_UNIT_PRESSURE: i32 = 5;
+const INFLUENCE_BAND: u16 = 10;
+
+/// System: lets police pressure and gang influence push against each other.
+pub fn district_control_system(
+    mut city: ResMut<CityState>,
+    agents: Res<AgentRegistry>,
+    agent_events: Res<AgentEventLog>,
+    director: Res<FactionDirector>,
+    mut city_events: ResMut<CityEventLog>,
+) {
+    let gangs = director.gang_faction_ids();
+    contest_district_control(&mut city, &agents, &agent_events, &gangs, &mut city_events);
+}
+
+/// Erodes gang influence where police presence, units and patrolling agents
+/// are strong, while a dominant gang halves that pressure on its own turf.
+pub fn contest_district_control(
+    city: &mut CityState,
+    agents: &AgentRegistry,
+    agent_events: &AgentEventLog,
+    gang_factions: &HashSet<String>,
+    city_events: &mut CityEventLog,
+) {
+    if gang_factions.is_empty() {
+        return;
+    }
+    let city_id = city.city_id;
+
+    for event in &agent_events.0 {
+        let AgentEvent::EnteredLocation {
+            agent_id,
+            location_id,
+        } = event
+        else {
+            continue;
+        };
+        if !agents.is_law_enforcement(*agent_id) {
+            continue;
+        }
+        let Some(location) = city.locations.get(location_id) else {
+            continue;
+        };
+        if let Some((faction_id, influence)) = dominant_gang(location, gang_factions) {
+            if influence >= GANG_SUPPRESSION_INFLUENCE {
+                city_events.0.push(CityEvent {
+                    city_id,
+                    location_id: *location_id,
+                    kind: CityEventKind::PatrolSuppressed {
+                        faction_id,
+                        influence,
+                    },
+                });
+            }
+        }
+    }
+
+    let mut location_ids: Vec<_> = city.locations.keys().copied().collect();
+    location_ids.sort_by_key(|id| id.0);
+    for location_id in location_ids {
+        let law_agents = agents.law_agents_at(location_id) as i32;
+        let Some(location) = city.locations.get_mut(&location_id) else {
+            continue;
+        };
+        let mut law_pressure = location.police_presence
+            + location.police_units as i32 * POLICE_UNIT_PRESSURE
+            + law_agents * LAW_AGENT_PRESSURE;
+        if dominant_gang(location, gang_factions)
+            .map(|(_, influence)| influence >= GANG_SUPPRESSION_INFLUENCE)
+            .unwrap_or(false)
+        {
+            law_pressure /= 2;
+        }
+        let erosion = ((law_pressure - LAW_PRESSURE_FLOOR) / LAW_PRESSURE_PER_EROSION)
+            .clamp(0, MAX_EROSION_PER_TICK) as u16;
+        if erosion == 0 {
+            continue;
+        }
+
+        let mut factions: Vec<String> = location
+            .faction_influence
+            .keys()
+            .filter(|id| gang_factions.contains(*id))
+            .cloned()
+            .collect();
+        factions.sort();
+        for faction_id in factions {
+            let Some(entry) = location.faction_influence.get_mut(&faction_id) else {
+                continue;
+            };
+            let before = *entry;
+            *entry = entry.saturating_sub(erosion);
+            if *entry / INFLUENCE_BAND < before / INFLUENCE_BAND {
+                city_events.0.push(CityEvent {
+                    city_id,
+                    location_id,
+                    kind: CityEventKind::TurfEroded {
+                        faction_id,
+                        influence: *entry,
+                    },
+                });
+            }
+        }
+    }
+}
+
+fn dominant_gang(location: &LocationState, gang_factions: &HashSet<String>) -> Option<(String, u16)> {
+    location
+        .faction_influence
+        .iter()
+        .filter(|(id, _)| gang_factions.contains(*id))
+        .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+        .map(|(id, influence)| (id.clone(), *influence))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::city::LocationId;
+
+    #[test]
+    fn police_pressure_erodes_turf_unless_gang_dominates() {
+        let mut city = CityState::default();
+        let gangs: HashSet<String> = ["city_gang".to_string()].into_iter().collect();
+        let agents = AgentRegistry::default();
+        let agent_events = AgentEventLog::default();
+        let mut city_events = CityEventLog::default();
+        {
+            let location = city.locations.get_mut(&LocationId(1)).unwrap();
+            location.police_presence = 70;
+            location.police_units = 0;
+            location.faction_influence.insert("city_gang".to_string(), 41);
+        }
+
+        contest_district_control(&mut city, &agents, &agent_events, &gangs, &mut city_events);
+        assert_eq!(city.locations[&LocationId(1)].faction_influence["city_gang"], 39);
+        assert!(matches!(
+            city_events.0.last().map(|event| &event.kind),
+            Some(CityEventKind::TurfEroded { influence: 39, .. })
+        ));
+
+        city.locations
+            .get_mut(&LocationId(1))
+            .unwrap()
+            .faction_influence
+            .insert("city_gang".to_string(), 80);
+        contest_district_control(&mut city, &agents, &agent_events, &gangs, &mut city_events);
+        assert_eq!(city.locations[&LocationId(1)].faction_influence["city_gang"], 80);
+    }
+}
@@ -7,10 +7,13 @@ pub mod event_resolver;
 pub mod faction;
 pub mod region;
 pub mod heat;
+pub mod influence;
 pub mod nemesis;
 pub mod persona;
 pub mod pressure;
+pub mod stats;
 pub mod suspicion;
+pub mod tick_stops;
 pub mod units;
 
 use bevy_ecs::prelude::*;
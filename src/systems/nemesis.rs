@@ -138,6 +138,7 @@ pub fn run_nemesis_system(
     }
 
     let mut actions_to_apply: Vec<NemesisActionTrigger> = Vec::new();
+    let resentment_by_faction = state.resentment.clone();
 
     for candidate in state.candidates.iter_mut() {
         let Some(snapshot) = case_snapshots.iter().find(|case| {
@@ -195,10 +196,15 @@ pub fn run_nemesis_system(
         }
 
         let focus = build_counter_focus(candidate);
+        let resentment = resentment_by_faction
+            .get(&candidate.faction_id)
+            .copied()
+            .unwrap_or(0);
         let Some(action) = select_action(
             director,
             candidate.heat,
             effective_progress,
+            resentment,
             focus.as_ref(),
         ) else {
             continue;
@@ -275,12 +281,14 @@ fn select_action(
     director: &NemesisDirector,
     heat: i32,
     case_progress: u32,
+    resentment: i32,
     focus: Option<&NemesisCounterFocus>,
 ) -> Option<NemesisActionDefinition> {
     let mut candidates: Vec<&NemesisActionDefinition> = director
         .actions
         .iter()
         .filter(|action| action.min_heat as i32 <= heat && action.min_case_progress <= case_progress)
+        .filter(|action| action.min_resentment <= resentment)
         .collect();
     if let Some(focus) = focus {
         let focused: Vec<&NemesisActionDefinition> = candidates
@@ -292,7 +300,9 @@ fn select_action(
             candidates = focused;
         }
     }
-    candidates.sort_by_key(|action| (action.min_heat, action.min_case_progress));
+    candidates.sort_by_key(|action| {
+        (action.min_resentment, action.min_heat, action.min_case_progress)
+    });
     candidates.last().map(|action| (*action).clone())
 }
 
@@ -445,3 +455,65 @@ fn apply_global_case_boost(progress: u32, global_threat: i32) -> u32 {
     let boost = (global_threat / 2).max(0) as u32;
     (progress + boost).min(100)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::cast::{CharacterRole, ImportanceTier, PersistentCharacter};
+    use crate::simulation::combat::{CombatConsequence, CombatEnd};
+    use crate::simulation::nemesis::{update_nemesis_relationship, NEMESIS_ROLE};
+
+    /// Resentment the nemesis' faction holds after three lost fights that
+    /// each left `casualties` civilians dead.
+    fn resentment_after_three_defeats(casualties: u8) -> i32 {
+        let mut character = PersistentCharacter {
+            character_id: "nemesis_1".to_string(),
+            scope_id: "city:1".to_string(),
+            first_name: "Vera".to_string(),
+            last_name: "Kross".to_string(),
+            birth_year: None,
+            ancestry: None,
+            nationality: None,
+            importance_tier: ImportanceTier::A,
+            created_at_tick: 0,
+            roles: vec![CharacterRole {
+                role_type: NEMESIS_ROLE.to_string(),
+                faction_id: Some("syndicate".to_string()),
+                rank: None,
+                start_tick: 0,
+                end_tick: None,
+            }],
+            personas: Vec::new(),
+            powers: Vec::new(),
+            relationships: Vec::new(),
+        };
+        let mut state = NemesisState::default();
+        let consequence = CombatConsequence {
+            civilian_casualties: casualties,
+            ..CombatConsequence::default()
+        };
+        for tick in 0..3 {
+            let relation = update_nemesis_relationship(
+                &mut character,
+                CombatEnd::OpponentsDefeated,
+                consequence,
+                tick,
+            );
+            state.record_resentment("syndicate", relation.resentment);
+        }
+        state.resentment_for("syndicate")
+    }
+
+    #[test]
+    fn bloody_defeats_push_a_nemesis_into_a_vendetta() {
+        let director = NemesisDirector::load_default().expect("load nemesis actions");
+        let clean = resentment_after_three_defeats(0);
+        let bloody = resentment_after_three_defeats(5);
+        assert_eq!(clean, 36);
+        assert_eq!(bloody, 66);
+
+        assert!(select_action(&director, 20, 10, clean, None).is_none());
+        let action = select_action(&director, 20, 10, bloody, None).expect("vendetta");
+        assert_eq!(action.id, "VENDETTA_STRIKE");
+    }
+}
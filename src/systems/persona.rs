@@ -73,7 +73,7 @@ pub fn persona_switch_system(
             } else {
                 0
             };
-            let has_visual_anomaly = evidence.signatures.iter().any(|event| {
+            let has_visual_anomaly = evidence.iter().any(|event| {
                 event.location_id == location_id
                     && event.signature.signature.signature_type == SignatureType::VisualAnomaly
             });
@@ -31,7 +31,6 @@ pub fn update_pressure(
         .unwrap_or(0) as f32;
 
     let evidence_strength: f32 = evidence
-        .signatures
         .iter()
         .filter(|event| event.location_id == location_id)
         .map(|event| event.signature.signature.strength as f32)
@@ -48,7 +47,7 @@ pub fn update_pressure(
         max_case_progress = max_case_progress.max(case.progress as f32);
     }
 
-    let time_pressure = (time.hour as f32 / 23.0) * 100.0;
+    let time_pressure = (time.clock_hour() as f32 / 23.0) * 100.0;
     let case_pressure = (max_case_progress + active_case_count * 8.0).clamp(0.0, 100.0);
 
     let temporal_target = (time_pressure + heat * 0.1).clamp(0.0, 100.0);
@@ -68,6 +67,7 @@ pub fn update_pressure(
     pressure.moral = approach(pressure.moral, moral_target, step);
     pressure.resource = approach(pressure.resource, resource_target, step);
     pressure.psychological = approach(pressure.psychological, psychological_target, step);
+    pressure.record_history();
 }
 
 fn approach(current: f32, target: f32, step: f32) -> f32 {
@@ -142,14 +142,14 @@ impl GlobalFactionDirector {
 
 pub fn region_system(
     mut region: ResMut<RegionState>,
-    city: Res<CityState>,
+    mut city: ResMut<CityState>,
     pressure: Res<PressureState>,
     mut city_events: ResMut<CityEventLog>,
     mut region_events: ResMut<RegionEventLog>,
 ) {
     run_region_update(
         &mut region,
-        &city,
+        &mut city,
         &pressure,
         &mut city_events,
         &mut region_events,
@@ -158,11 +158,12 @@ pub fn region_system(
 
 pub fn run_region_update(
     region: &mut RegionState,
-    city: &CityState,
+    city: &mut CityState,
     pressure: &PressureState,
     city_events: &mut CityEventLog,
     region_events: &mut RegionEventLog,
 ) {
+    city.spread_pressure();
     region.update_from_city(city);
     region.update_global_pressure(pressure);
 
@@ -0,0 +1,21 @@
+use bevy_ecs::prelude::*;
+
+use crate::components::persona::PersonaStack;
+use crate::components::world::Player;
+use crate::simulation::case::CaseRegistry;
+use crate::simulation::civilian::CivilianState;
+use crate::simulation::stats::RunStats;
+
+/// System: folds case resolutions, persona exposure and wealth peaks into the run tallies.
+pub fn run_stats_system(
+    cases: Res<CaseRegistry>,
+    civilian: Res<CivilianState>,
+    personas: Query<&PersonaStack, With<Player>>,
+    mut stats: ResMut<RunStats>,
+) {
+    stats.note_cases(&cases);
+    for stack in personas.iter() {
+        stats.note_personas(stack);
+    }
+    stats.note_wealth(&civilian);
+}
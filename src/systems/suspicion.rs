@@ -3,12 +3,25 @@ use std::collections::HashMap;
 use bevy_ecs::prelude::*;
 
 use crate::components::identity::CivilianIdentity;
-use crate::components::persona::{Alignment, PersonaStack, PersonaType, RiskModifiers, SuspicionDelta};
+use crate::components::persona::{
+    Alignment, PersonaStack, PersonaType, RiskModifiers, SuspicionDelta, EXPOSURE_MAX,
+};
 use crate::components::world::{EntityId, Position};
 use crate::core::world::{ActionIntent, ActionQueue};
-use crate::simulation::case::CaseRegistry;
+use crate::simulation::case::{CaseRegistry, CaseStatus, CaseTargetType};
 use crate::simulation::city::CityState;
+use crate::simulation::civilian::{spend_favor, CivilianState, FavorError, FavorKind};
+use crate::simulation::endgame::{TransformationEvent, TransformationState, TransformationTrigger};
 use crate::simulation::identity_evidence::{IdentityEvidenceStore, PersonaHint};
+use crate::systems::persona::PersonaEventLog;
+
+/// Media reputation a persona's name buys when the press runs it.
+const BREACH_MEDIA_BOOST: i32 = 15;
+const BREACH_INFAMY: i32 = 10;
+/// Cash it takes to bury a trail that is close to breaking.
+pub const BRINK_CASH_COST: i32 = 400;
+const BRINK_CASH_RELIEF: i32 = 15;
+const BRINK_FAVOR_RELIEF: i32 = 25;
 
 /// System: adjusts persona suspicion based on intents, environment, and active cases.
 pub fn suspicion_system(
@@ -44,6 +57,24 @@ pub fn suspicion_system(
     }
 }
 
+/// System: burns personas whose exposure has maxed out.
+pub fn exposure_breach_system(
+    mut cases: ResMut<CaseRegistry>,
+    mut civilian: ResMut<CivilianState>,
+    mut log: ResMut<PersonaEventLog>,
+    mut personas: Query<&mut PersonaStack>,
+) {
+    for mut stack in personas.iter_mut() {
+        for breach in trigger_exposure_breaches(&mut stack, &mut cases, &mut civilian) {
+            log.0.push(format!(
+                "Persona {} burned: the press names them and {} case(s) now know who they chase.",
+                breach.label,
+                breach.cases_named.len()
+            ));
+        }
+    }
+}
+
 pub fn apply_suspicion_for_intents(
     stack: &mut PersonaStack,
     alignment: Alignment,
@@ -61,6 +92,177 @@ pub fn apply_suspicion_for_intents(
     apply_suspicion_to_stack(stack, alignment, location, cases, identity, delta);
 }
 
+/// Applies suspicion from a crime committed under the active persona, scaled by
+/// the same persona and alignment modifiers as intent-driven suspicion.
+pub fn apply_crime_suspicion(
+    stack: &mut PersonaStack,
+    alignment: Alignment,
+    wanted_level: i32,
+    civilian_suspicion: i32,
+) {
+    let Some(active_persona) = stack.active_persona_mut() else {
+        return;
+    };
+    let combined_mod = merge_modifiers(
+        &active_persona.risk_modifiers,
+        &alignment.suspicion_multiplier(),
+    );
+    let delta = SuspicionDelta {
+        wanted_level,
+        civilian_suspicion,
+        exposure_risk: wanted_level / 2,
+        ..SuspicionDelta::default()
+    };
+    let scaled = scale_delta(delta, &combined_mod);
+    active_persona.suspicion.apply_delta(&scaled);
+}
+
+/// Applies the public suspicion and wanted level stirred up by press coverage.
+pub fn apply_media_suspicion(
+    stack: &mut PersonaStack,
+    alignment: Alignment,
+    public_suspicion: i32,
+    wanted_level: i32,
+) {
+    let Some(active_persona) = stack.active_persona_mut() else {
+        return;
+    };
+    let combined_mod = merge_modifiers(
+        &active_persona.risk_modifiers,
+        &alignment.suspicion_multiplier(),
+    );
+    let delta = SuspicionDelta {
+        public_suspicion,
+        wanted_level,
+        ..SuspicionDelta::default()
+    };
+    let scaled = scale_delta(delta, &combined_mod);
+    active_persona.suspicion.apply_delta(&scaled);
+}
+
+/// A persona whose exposure maxed out and whose name is now public.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExposureBreach {
+    pub persona_id: String,
+    pub label: String,
+    pub persona_type: PersonaType,
+    /// Active cases that now know who they are chasing.
+    pub cases_named: Vec<u32>,
+    pub media_boost: i32,
+    /// Infamy for the caller to add to the player's growth reputation.
+    pub infamy: i32,
+}
+
+impl ExposureBreach {
+    /// A named mask pushes the Exposed endgame; a named civilian does not.
+    pub fn endgame_event(&self) -> Option<TransformationEvent> {
+        (self.persona_type == PersonaType::Masked).then_some(TransformationEvent {
+            state: TransformationState::Exposed,
+            trigger: TransformationTrigger::IdentityBreach,
+        })
+    }
+}
+
+/// Burns each persona whose exposure has maxed out. The cases chasing it learn
+/// who they are after and the press runs the name. Each persona burns once.
+pub fn trigger_exposure_breaches(
+    stack: &mut PersonaStack,
+    cases: &mut CaseRegistry,
+    civilian: &mut CivilianState,
+) -> Vec<ExposureBreach> {
+    let mut breaches = Vec::new();
+    for persona in stack.personas.iter_mut() {
+        if persona.burned || persona.suspicion.exposure_risk < EXPOSURE_MAX {
+            continue;
+        }
+        persona.burned = true;
+        let chased = match persona.persona_type {
+            PersonaType::Masked => CaseTargetType::UnknownMasked,
+            PersonaType::Civilian => CaseTargetType::CivilianLink,
+        };
+        let mut cases_named = Vec::new();
+        for case in cases.cases.iter_mut() {
+            if case.status == CaseStatus::Active && case.target_type == chased {
+                case.target_type = CaseTargetType::KnownMasked;
+                cases_named.push(case.case_id);
+            }
+        }
+        civilian.reputation.media = (civilian.reputation.media + BREACH_MEDIA_BOOST).min(100);
+        breaches.push(ExposureBreach {
+            persona_id: persona.persona_id.clone(),
+            label: persona.label.clone(),
+            persona_type: persona.persona_type,
+            cases_named,
+            media_boost: BREACH_MEDIA_BOOST,
+            infamy: BREACH_INFAMY,
+        });
+    }
+    breaches
+}
+
+/// How the player buys the active persona some distance from a breach.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrinkRelief {
+    Favor { contact: String },
+    Cash,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrinkReliefError {
+    NoActivePersona,
+    Burned,
+    NotOnBrink { exposure: u8 },
+    NotEnoughCash { need: i32, have: i32 },
+    Favor(FavorError),
+}
+
+/// Spends a heat-relief favor or cash to bury the active persona's trail while
+/// it is on the brink of burning. Returns what was applied.
+pub fn pull_back_from_brink(
+    stack: &mut PersonaStack,
+    civilian: &mut CivilianState,
+    relief: &BrinkRelief,
+    tick: u64,
+) -> Result<Vec<String>, BrinkReliefError> {
+    let persona = stack
+        .active_persona_mut()
+        .ok_or(BrinkReliefError::NoActivePersona)?;
+    if persona.burned {
+        return Err(BrinkReliefError::Burned);
+    }
+    if !persona.is_on_brink() {
+        return Err(BrinkReliefError::NotOnBrink {
+            exposure: persona.suspicion.exposure_risk,
+        });
+    }
+    let (mut applied, relief) = match relief {
+        BrinkRelief::Favor { contact } => (
+            spend_favor(civilian, contact, FavorKind::HeatRelief, tick)
+                .map_err(BrinkReliefError::Favor)?,
+            BRINK_FAVOR_RELIEF,
+        ),
+        BrinkRelief::Cash => {
+            if civilian.finances.cash < BRINK_CASH_COST {
+                return Err(BrinkReliefError::NotEnoughCash {
+                    need: BRINK_CASH_COST,
+                    have: civilian.finances.cash,
+                });
+            }
+            civilian.lose_cash(BRINK_CASH_COST);
+            (vec![format!("paid {} cr to make records disappear", BRINK_CASH_COST)], BRINK_CASH_RELIEF)
+        }
+    };
+    persona.suspicion.apply_delta(&SuspicionDelta {
+        exposure_risk: -relief,
+        ..SuspicionDelta::default()
+    });
+    applied.push(format!(
+        "{} exposure -{} (now {})",
+        persona.label, relief, persona.suspicion.exposure_risk
+    ));
+    Ok(applied)
+}
+
 fn apply_suspicion_to_stack(
     stack: &mut PersonaStack,
     alignment: Alignment,
@@ -235,3 +437,45 @@ fn evidence_pressure(
 
     delta
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::persona::{hero_persona_stack, EXPOSURE_BRINK};
+    use crate::simulation::city::LocationId;
+
+    #[test]
+    fn maxed_exposure_burns_the_persona_and_names_its_cases() {
+        let mut stack = hero_persona_stack();
+        stack.active_persona_id = "masked".to_string();
+        let mut civilian = CivilianState::default();
+        civilian.finances.cash = BRINK_CASH_COST;
+        let mut cases = CaseRegistry::default();
+        cases.create_case("metro_police".to_string(), LocationId(1), Vec::new(), false);
+        cases.cases[0].target_type = CaseTargetType::UnknownMasked;
+        let media_before = civilian.reputation.media;
+
+        stack.active_persona_mut().unwrap().suspicion.exposure_risk = EXPOSURE_BRINK + 5;
+        let applied = pull_back_from_brink(&mut stack, &mut civilian, &BrinkRelief::Cash, 1)
+            .expect("cash buys distance");
+        assert!(!applied.is_empty());
+        assert_eq!(civilian.finances.cash, 0);
+        assert!(stack.active_persona().unwrap().suspicion.exposure_risk < EXPOSURE_BRINK);
+        assert!(trigger_exposure_breaches(&mut stack, &mut cases, &mut civilian).is_empty());
+
+        stack.active_persona_mut().unwrap().suspicion.exposure_risk = EXPOSURE_MAX;
+        let breaches = trigger_exposure_breaches(&mut stack, &mut cases, &mut civilian);
+        assert_eq!(breaches.len(), 1);
+        assert_eq!(breaches[0].persona_id, "masked");
+        assert_eq!(breaches[0].cases_named, vec![cases.cases[0].case_id]);
+        assert!(breaches[0].endgame_event().is_some());
+        assert_eq!(cases.cases[0].target_type, CaseTargetType::KnownMasked);
+        assert_eq!(civilian.reputation.media, media_before + BREACH_MEDIA_BOOST);
+        assert!(stack.active_persona().unwrap().burned);
+        assert_eq!(
+            pull_back_from_brink(&mut stack, &mut civilian, &BrinkRelief::Cash, 2),
+            Err(BrinkReliefError::Burned)
+        );
+        assert!(trigger_exposure_breaches(&mut stack, &mut cases, &mut civilian).is_empty());
+    }
+}
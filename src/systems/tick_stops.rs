@@ -0,0 +1,189 @@
+/// Why a batch tick ended before or at its target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TickStopReason {
+    Completed,
+    PendingEvent,
+    RentDue,
+    Crisis,
+    Storylet,
+    Death,
+    Ambush,
+    Endgame,
+    Punctuation,
+    MaxTicks,
+}
+
+impl TickStopReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            TickStopReason::Completed => "completed",
+            TickStopReason::PendingEvent => "pending event",
+            TickStopReason::RentDue => "rent due",
+            TickStopReason::Crisis => "crisis",
+            TickStopReason::Storylet => "storylet",
+            TickStopReason::Death => "death",
+            TickStopReason::Ambush => "ambush",
+            TickStopReason::Endgame => "endgame triggered",
+            TickStopReason::Punctuation => "punctuation storylet",
+            TickStopReason::MaxTicks => "max ticks",
+        }
+    }
+
+    /// Turns left for `tick resume` once a counted batch of `requested` turns
+    /// stopped after `ticks_run`; a batch that ran to completion leaves none.
+    pub fn ticks_to_resume(self, requested: u32, ticks_run: u32) -> u32 {
+        if self == TickStopReason::Completed {
+            0
+        } else {
+            requested.saturating_sub(ticks_run)
+        }
+    }
+}
+
+/// Which significant events interrupt a batch tick; death always does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TickStops {
+    pub combat: bool,
+    pub endgame: bool,
+    pub punctuation: bool,
+}
+
+impl Default for TickStops {
+    fn default() -> Self {
+        Self {
+            combat: true,
+            endgame: true,
+            punctuation: true,
+        }
+    }
+}
+
+impl TickStops {
+    /// Reads a comma-separated list of classes, or `all` / `none`.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let mut stops = TickStops {
+            combat: false,
+            endgame: false,
+            punctuation: false,
+        };
+        for class in raw
+            .split(',')
+            .map(|class| class.trim().to_ascii_lowercase())
+        {
+            match class.as_str() {
+                "all" => stops = TickStops::default(),
+                "none" => {}
+                "combat" => stops.combat = true,
+                "endgame" => stops.endgame = true,
+                "punctuation" => stops.punctuation = true,
+                _ => return None,
+            }
+        }
+        Some(stops)
+    }
+
+    /// The stop for an endgame shift or punctuation storylet this tick, if
+    /// that class is watched. An endgame shift wins when both happen.
+    pub fn interrupt(&self, endgame_changed: bool, punctuation: bool) -> Option<TickStopReason> {
+        if endgame_changed && self.endgame {
+            Some(TickStopReason::Endgame)
+        } else if punctuation && self.punctuation {
+            Some(TickStopReason::Punctuation)
+        } else {
+            None
+        }
+    }
+
+    /// Rolls for an ambush only while combat stops are on, so a batch that
+    /// ignores combat is never dragged into one.
+    pub fn ambush(&self, spring_ambush: impl FnOnce() -> bool) -> Option<TickStopReason> {
+        (self.combat && spring_ambush()).then_some(TickStopReason::Ambush)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stop_classes_parse_from_a_list() {
+        assert_eq!(TickStops::parse("all"), Some(TickStops::default()));
+        assert_eq!(
+            TickStops::parse("none"),
+            Some(TickStops {
+                combat: false,
+                endgame: false,
+                punctuation: false,
+            })
+        );
+        assert_eq!(
+            TickStops::parse("Combat, punctuation"),
+            Some(TickStops {
+                combat: true,
+                endgame: false,
+                punctuation: true,
+            })
+        );
+        assert_eq!(TickStops::parse("combat,weather"), None);
+    }
+
+    #[test]
+    fn combat_stops_gate_the_ambush_roll() {
+        let watching = TickStops::default();
+        assert_eq!(watching.ambush(|| true), Some(TickStopReason::Ambush));
+        assert_eq!(watching.ambush(|| false), None);
+
+        let ignoring = TickStops::parse("endgame,punctuation").unwrap();
+        let mut rolled = false;
+        let stop = ignoring.ambush(|| {
+            rolled = true;
+            true
+        });
+        assert_eq!(stop, None);
+        assert!(!rolled, "no ambush is sprung while combat stops are off");
+    }
+
+    #[test]
+    fn endgame_stops_only_when_watched() {
+        let watching = TickStops::parse("endgame").unwrap();
+        assert_eq!(
+            watching.interrupt(true, false),
+            Some(TickStopReason::Endgame)
+        );
+        assert_eq!(watching.interrupt(false, false), None);
+        assert_eq!(
+            TickStops::parse("none").unwrap().interrupt(true, false),
+            None
+        );
+        assert_eq!(
+            TickStops::default().interrupt(true, true),
+            Some(TickStopReason::Endgame)
+        );
+    }
+
+    #[test]
+    fn punctuation_stops_only_when_watched() {
+        let watching = TickStops::parse("punctuation").unwrap();
+        assert_eq!(
+            watching.interrupt(false, true),
+            Some(TickStopReason::Punctuation)
+        );
+        assert_eq!(
+            watching.interrupt(true, true),
+            Some(TickStopReason::Punctuation)
+        );
+        assert_eq!(
+            TickStops::parse("combat,endgame")
+                .unwrap()
+                .interrupt(false, true),
+            None
+        );
+    }
+
+    #[test]
+    fn an_early_stop_leaves_the_rest_to_resume() {
+        assert_eq!(TickStopReason::Ambush.ticks_to_resume(10, 4), 6);
+        assert_eq!(TickStopReason::Completed.ticks_to_resume(10, 10), 0);
+        assert_eq!(TickStopReason::Death.ticks_to_resume(10, 3), 7);
+    }
+}
@@ -1,16 +1,91 @@
 use bevy_ecs::prelude::*;
 
-use crate::simulation::city::{CityState, LocationId};
+use crate::simulation::city::{CityState, LocationId, LocationState, LocationTag};
+
+/// Heat at which a district starts tightening its lockdown.
+pub const LOCKDOWN_HEAT: i32 = 70;
+/// Heat below which lockdown eases and surplus units stand down.
+pub const STAND_DOWN_HEAT: i32 = 35;
+const LOCKDOWN_RISE: i32 = 5;
+const LOCKDOWN_EASE: i32 = 2;
+const HIGH_SECURITY_LOCKDOWN_FLOOR: i32 = 10;
+const MAX_POLICE_UNITS: u8 = 8;
+const MAX_INVESTIGATORS: u8 = 4;
+const MAX_GANG_UNITS: u8 = 5;
+const GANG_GROWTH_POLICE_CEILING: i32 = 1;
+const GANG_PRESSURE_PER_UNIT: i32 = 10;
+const GANG_ATTRITION_MARGIN: u8 = 3;
 
 pub fn unit_movement_system(mut city: ResMut<CityState>) {
     update_units(&mut city);
 }
 
 pub fn update_units(city: &mut CityState) {
+    for location in city.locations.values_mut() {
+        adjust_lockdown(location);
+        recruit_security(location);
+        adjust_gang_units(location);
+    }
     apply_presence_effects(city);
     shift_gang_units(city);
 }
 
+/// Sustained heat tightens lockdown a step per tick; it eases once heat falls.
+fn adjust_lockdown(location: &mut LocationState) {
+    if location.heat >= LOCKDOWN_HEAT {
+        location.lockdown_level = (location.lockdown_level + LOCKDOWN_RISE).clamp(0, 100);
+    } else if location.heat < STAND_DOWN_HEAT {
+        let floor = if location.tags.contains(&LocationTag::HighSecurity) {
+            HIGH_SECURITY_LOCKDOWN_FLOOR
+        } else {
+            0
+        };
+        if location.lockdown_level > floor {
+            location.lockdown_level = (location.lockdown_level - LOCKDOWN_EASE).max(floor);
+        }
+    }
+}
+
+/// Police units and investigators move one step per tick toward a target set
+/// by heat and lockdown; surplus only stands down once heat has cooled.
+fn recruit_security(location: &mut LocationState) {
+    let police_target = ((location.police_presence + 5) / 20
+        + location.heat / 25
+        + location.lockdown_level / 25)
+        .clamp(0, MAX_POLICE_UNITS as i32) as u8;
+    let investigator_target = (location.heat / 30 + location.lockdown_level / 40)
+        .clamp(0, MAX_INVESTIGATORS as i32) as u8;
+    let cooled = location.heat < STAND_DOWN_HEAT;
+    location.police_units = step_toward(location.police_units, police_target, cooled);
+    location.investigators = step_toward(location.investigators, investigator_target, cooled);
+}
+
+fn step_toward(current: u8, target: u8, may_decline: bool) -> u8 {
+    if current < target {
+        current + 1
+    } else if current > target && may_decline {
+        current - 1
+    } else {
+        current
+    }
+}
+
+/// Gangs recruit where police are thin and crime pays, and lose members
+/// where police clearly outnumber them.
+fn adjust_gang_units(location: &mut LocationState) {
+    let police_effect = location.police_units as i32 + (location.police_presence / 20);
+    if police_effect <= GANG_GROWTH_POLICE_CEILING
+        && location.gang_units < MAX_GANG_UNITS
+        && location.crime_pressure >= (location.gang_units as i32 + 1) * GANG_PRESSURE_PER_UNIT
+    {
+        location.gang_units += 1;
+    } else if location.gang_units > 0
+        && location.police_units >= location.gang_units + GANG_ATTRITION_MARGIN
+    {
+        location.gang_units -= 1;
+    }
+}
+
 fn apply_presence_effects(city: &mut CityState) {
     for location in city.locations.values_mut() {
         let police_effect = location.police_units as i32 + (location.police_presence / 20);
@@ -47,3 +122,56 @@ fn shift_gang_units(city: &mut CityState) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heat_recruits_security_that_stands_down_as_it_cools() {
+        let mut city = CityState::default();
+        let id = LocationId(1);
+        city.locations.get_mut(&id).unwrap().heat = 90;
+
+        update_units(&mut city);
+        let first = city.locations[&id].clone();
+        assert_eq!(first.lockdown_level, LOCKDOWN_RISE);
+        assert_eq!(first.police_units, 2);
+        assert_eq!(first.investigators, 1);
+
+        for _ in 0..5 {
+            update_units(&mut city);
+        }
+        let peak = city.locations[&id].clone();
+        assert!(peak.lockdown_level > first.lockdown_level);
+        assert!(peak.police_units > first.police_units);
+        assert!(peak.investigators > first.investigators);
+
+        city.locations.get_mut(&id).unwrap().heat = 50;
+        update_units(&mut city);
+        assert_eq!(city.locations[&id].police_units, peak.police_units);
+
+        city.locations.get_mut(&id).unwrap().heat = 0;
+        for _ in 0..20 {
+            update_units(&mut city);
+        }
+        let cooled = &city.locations[&id];
+        assert_eq!(cooled.lockdown_level, 0);
+        assert_eq!(cooled.police_units, 1);
+        assert_eq!(cooled.investigators, 0);
+    }
+
+    #[test]
+    fn gangs_grow_where_police_are_thin() {
+        let mut location = CityState::default().locations[&LocationId(3)].clone();
+        location.crime_pressure = 25;
+        adjust_gang_units(&mut location);
+        assert_eq!(location.gang_units, 2);
+        adjust_gang_units(&mut location);
+        assert_eq!(location.gang_units, 2);
+
+        location.police_units = 5;
+        adjust_gang_units(&mut location);
+        assert_eq!(location.gang_units, 1);
+    }
+}
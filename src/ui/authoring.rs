@@ -31,7 +31,10 @@ pub fn render_authoring_dashboard(
     let hero_count = storylets.hero.len();
     let vigilante_count = storylets.vigilante.len();
     let villain_count = storylets.villain.len();
-    let total_storylets = hero_count + vigilante_count + villain_count;
+    let endgame_count = storylets.endgame_hero.len()
+        + storylets.endgame_vigilante.len()
+        + storylets.endgame_villain.len();
+    let total_storylets = hero_count + vigilante_count + villain_count + endgame_count;
 
     let mut category_counts: HashMap<StoryletCategory, usize> = HashMap::new();
     let mut punctuation_count = 0usize;
@@ -40,12 +43,7 @@ pub fn render_authoring_dashboard(
     let mut threshold_counts: HashMap<String, usize> = HashMap::new();
     let mut threshold_storylets = 0usize;
     let mut ungated_storylets = 0usize;
-    for storylet in storylets
-        .hero
-        .iter()
-        .chain(storylets.vigilante.iter())
-        .chain(storylets.villain.iter())
-    {
+    for storylet in storylets.all() {
         *category_counts.entry(storylet.category).or_insert(0) += 1;
         if is_punctuation_storylet(storylet) {
             punctuation_count += 1;
@@ -78,6 +76,7 @@ pub fn render_authoring_dashboard(
     output.push_str(&format!("  Hero: {}\n", hero_count));
     output.push_str(&format!("  Vigilante: {}\n", vigilante_count));
     output.push_str(&format!("  Villain: {}\n", villain_count));
+    output.push_str(&format!("  Endgame: {}\n", endgame_count));
     output.push_str("  Categories:\n");
     let mut categories: Vec<(StoryletCategory, usize)> = category_counts.into_iter().collect();
     categories.sort_by_key(|(category, _)| format!("{:?}", category));
@@ -0,0 +1,175 @@
+//! Line-delimited JSON protocol for driving a `Game` over stdin/stdout.
+//!
+//! Each request is one JSON object per line, tagged by `cmd`:
+//! - `{"cmd":"wait"}`, `{"cmd":"rest"}`, `{"cmd":"interact"}`
+//! - `{"cmd":"move","dx":1,"dy":0}`
+//! - `{"cmd":"attack","target":3}` (`target` is optional)
+//! - `{"cmd":"persona","persona_id":"masked"}`
+//! - `{"cmd":"use","expr":"<expression_id>"}` uses a power expression
+//! - `{"cmd":"snapshot"}` reports the world without ticking
+//! - `{"cmd":"events"}` lists pending civilian events
+//! - `{"cmd":"resolve","event_id":"...","choice_id":"..."}`
+//! - `{"cmd":"new_life"}` starts over after a death
+//!
+//! Each response is one JSON object per line, tagged by `type`:
+//! - `{"type":"snapshot","locked":false,"legacy":null,"snapshot":{...}}`
+//! - `{"type":"used","locked":false,"legacy":null,"used":{...,"snapshot":{...}}}`
+//! - `{"type":"events","events":[...]}`
+//! - `{"type":"resolved","applied":["support +5"]}`
+//! - `{"type":"error","message":"..."}`
+//!
+//! Action commands, `use` included, tick the simulation once and answer with a
+//! snapshot.
+
+use std::io::{self, BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::world::{ActionIntent, ExpressionUse, Game, Snapshot};
+use crate::simulation::civilian::{CivilianEventView, LegacyRecord};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum JsonCommand {
+    Wait,
+    Rest,
+    Interact,
+    Move { dx: i32, dy: i32 },
+    Attack {
+        #[serde(default)]
+        target: Option<u32>,
+    },
+    Persona { persona_id: String },
+    Use { expr: String },
+    Snapshot,
+    Events,
+    Resolve { event_id: String, choice_id: String },
+    NewLife,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JsonResponse {
+    Snapshot {
+        locked: bool,
+        legacy: Option<LegacyRecord>,
+        snapshot: Box<Snapshot>,
+    },
+    Used {
+        locked: bool,
+        legacy: Option<LegacyRecord>,
+        used: Box<ExpressionUse>,
+    },
+    Events { events: Vec<CivilianEventView> },
+    Resolved { applied: Vec<String> },
+    Error { message: String },
+}
+
+/// Parse one request line and apply it to the game.
+pub fn handle_json_line(game: &mut Game, line: &str) -> JsonResponse {
+    match serde_json::from_str::<JsonCommand>(line) {
+        Ok(command) => handle_json_command(game, command),
+        Err(err) => JsonResponse::Error {
+            message: format!("invalid command: {}", err),
+        },
+    }
+}
+
+pub fn handle_json_command(game: &mut Game, command: JsonCommand) -> JsonResponse {
+    let player_id = game.get_player_id();
+    let intent = match command {
+        JsonCommand::Wait => ActionIntent::Wait,
+        JsonCommand::Rest => ActionIntent::Rest {
+            entity_id: player_id,
+        },
+        JsonCommand::Interact => ActionIntent::Interact {
+            entity_id: player_id,
+        },
+        JsonCommand::Move { dx, dy } => ActionIntent::Move {
+            entity_id: player_id,
+            dx,
+            dy,
+        },
+        JsonCommand::Attack { target } => ActionIntent::Attack {
+            attacker_id: player_id,
+            target_id: target,
+        },
+        JsonCommand::Persona { persona_id } => ActionIntent::SwitchPersona {
+            entity_id: player_id,
+            persona_id,
+        },
+        JsonCommand::Use { expr } => {
+            return match game.use_expression(&expr) {
+                Ok(used) => JsonResponse::Used {
+                    locked: game.is_locked(),
+                    legacy: game.final_legacy().cloned(),
+                    used: Box::new(used),
+                },
+                Err(err) => JsonResponse::Error {
+                    message: err.to_string(),
+                },
+            };
+        }
+        JsonCommand::Snapshot => {
+            let snapshot = game.snapshot();
+            return snapshot_response(game, snapshot);
+        }
+        JsonCommand::Events => {
+            return JsonResponse::Events {
+                events: game.pending_civilian_events(),
+            };
+        }
+        JsonCommand::Resolve {
+            event_id,
+            choice_id,
+        } => {
+            return match game.resolve_civilian_event(&event_id, &choice_id) {
+                Ok(applied) => JsonResponse::Resolved { applied },
+                Err(err) => JsonResponse::Error {
+                    message: err.to_string(),
+                },
+            };
+        }
+        JsonCommand::NewLife => {
+            game.start_new_life();
+            let snapshot = game.snapshot();
+            return snapshot_response(game, snapshot);
+        }
+    };
+
+    if game.is_locked() {
+        return JsonResponse::Error {
+            message: "this life has ended; send new_life to continue".to_string(),
+        };
+    }
+    let snapshot = game.tick(vec![intent]);
+    snapshot_response(game, snapshot)
+}
+
+/// Answer every request line from `input` with one response line on `output`.
+pub fn run_json_session<R: BufRead, W: Write>(
+    game: &mut Game,
+    input: R,
+    mut output: W,
+) -> io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let response = handle_json_line(game, trimmed);
+        let encoded = serde_json::to_string(&response).map_err(io::Error::other)?;
+        writeln!(output, "{}", encoded)?;
+        output.flush()?;
+    }
+    Ok(())
+}
+
+fn snapshot_response(game: &Game, snapshot: Snapshot) -> JsonResponse {
+    JsonResponse::Snapshot {
+        locked: game.is_locked(),
+        legacy: game.final_legacy().cloned(),
+        snapshot: Box::new(snapshot),
+    }
+}
@@ -1 +1,2 @@
 pub mod authoring;
+pub mod json;
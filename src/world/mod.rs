@@ -2,4 +2,4 @@ pub mod repository;
 pub mod sqlite;
 
 pub use repository::WorldRepository;
-pub use sqlite::{WorldDb, WorldDbError, WorldDbState};
+pub use sqlite::{LoadReport, SaveDiagnostics, WorldDb, WorldDbError, WorldDbState};
@@ -1,17 +1,28 @@
 use crate::simulation::cast::{PersistentCharacter, PromotionCandidate};
-use crate::world::sqlite::WorldDbState;
+use crate::world::sqlite::{LoadReport, SaveDiagnostics, WorldDbState};
 
 pub trait WorldRepository {
-    fn load_or_init(&mut self) -> Result<WorldDbState, Box<dyn std::error::Error>>;
+    fn load_or_init(&mut self) -> Result<(WorldDbState, LoadReport), Box<dyn std::error::Error>>;
     fn save_state(&mut self, state: &WorldDbState) -> Result<(), Box<dyn std::error::Error>>;
+    fn mark_dirty(&mut self);
+    fn is_dirty(&self) -> bool;
+    /// Writes only when changes were marked since the last save.
+    fn save_if_dirty(&mut self, state: &WorldDbState) -> Result<bool, Box<dyn std::error::Error>>;
     fn load_characters(&self) -> Result<Vec<PersistentCharacter>, Box<dyn std::error::Error>>;
     fn upsert_character(
         &mut self,
         character: &PersistentCharacter,
     ) -> Result<(), Box<dyn std::error::Error>>;
+    fn diagnose(&self) -> Result<SaveDiagnostics, Box<dyn std::error::Error>>;
+    fn ensure_world_seed(&mut self, fallback: u64) -> Result<u64, Box<dyn std::error::Error>>;
     fn promote_candidate(
         &mut self,
         candidate: &PromotionCandidate,
         created_at_tick: u64,
     ) -> Result<PersistentCharacter, Box<dyn std::error::Error>>;
+    fn record_capture(
+        &mut self,
+        candidate: &PromotionCandidate,
+        captured_at_tick: u64,
+    ) -> Result<PersistentCharacter, Box<dyn std::error::Error>>;
 }
@@ -1,43 +1,65 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::Path;
 
-use rusqlite::{params, Connection, OptionalExtension};
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
+use serde::{Deserialize, Serialize};
 use serde_json;
 
 use crate::components::persona::{neutral_persona_stack, Alignment, PersonaStack};
 use crate::rules::mastery::MasteryStage;
 use crate::rules::power::ExpressionId;
-use crate::rules::signature::SignatureType;
+use crate::rules::signature::{SignatureInstance, SignatureSpec, SignatureType};
+use crate::simulation::agents::{AmbushSetting, AmbushState};
 use crate::simulation::case::{Case, CaseRegistry, CaseStatus, CaseTargetType};
 use crate::simulation::civilian::CivilianState;
 use crate::simulation::cast::{
-    CharacterPersona, CharacterPower, CharacterRelationship, CharacterRole, PersistentCharacter,
-    PromotionCandidate,
+    mark_incarcerated, CharacterPersona, CharacterPower, CharacterRelationship, CharacterRole,
+    PersistentCharacter, PromotionCandidate,
 };
 use crate::simulation::city::{
-    CityId, CityState, HeatResponse, LocationId, LocationState, LocationTag,
+    default_adjacency, CityId, CityState, HeatResponseConfig, LocationId, LocationState, LocationTag,
 };
-use crate::simulation::combat::{CombatIntent, CombatScale, CombatSide, CombatState, Combatant};
+use crate::simulation::combat::{
+    CombatIntent, CombatScale, CombatSide, CombatStaging, CombatState, Combatant,
+    DifficultySetting,
+};
+use crate::simulation::evidence::{SignatureEvent, WorldEvidence};
 use crate::simulation::growth::{ExpressionMastery, GrowthState, Reputation};
-use crate::simulation::region::{ContinentId, CountryId, RegionId};
+use crate::simulation::identity_evidence::{
+    IdentityEvidenceItem, IdentityEvidenceStore, PersonaHint, SightingTrail,
+};
+use crate::simulation::pressure::PressureState;
+use crate::simulation::region::{
+    ContinentId, CountryId, GlobalEscalation, GlobalPressure, RegionEscalation, RegionId,
+    RegionState,
+};
+use crate::simulation::stats::RunStats;
 use crate::simulation::storylet_state::StoryletState;
-use crate::simulation::time::GameTime;
+use crate::simulation::time::{GameTime, TimeConfig};
 use crate::simulation::cosmic::OmniPowerRegistry;
 
-const WORLD_SCHEMA_VERSION: i64 = 7;
+const WORLD_SCHEMA_VERSION: i64 = 36;
 const WORLD_SAVE_VERSION: i64 = 1;
+/// Only the tail of a long fight's log is kept on disk.
+const COMBAT_LOG_SAVE_LIMIT: usize = 200;
 
 const WORLD_DB_SCHEMA: &str = r#"
 CREATE TABLE IF NOT EXISTS world_meta (
   id INTEGER PRIMARY KEY CHECK (id = 1),
   schema_version INTEGER NOT NULL,
-  save_version INTEGER NOT NULL
+  save_version INTEGER NOT NULL,
+  world_seed INTEGER
 );
 
 CREATE TABLE IF NOT EXISTS world_state (
   id INTEGER PRIMARY KEY CHECK (id = 1),
   world_turn INTEGER NOT NULL,
-  active_location INTEGER NOT NULL
+  active_location INTEGER NOT NULL,
+  heat_patrol INTEGER NOT NULL DEFAULT 30,
+  heat_investigation INTEGER NOT NULL DEFAULT 50,
+  heat_faction_attention INTEGER NOT NULL DEFAULT 70,
+  city_id INTEGER NOT NULL DEFAULT 1,
+  region_id INTEGER NOT NULL DEFAULT 1
 );
 
 CREATE TABLE IF NOT EXISTS world_time (
@@ -47,7 +69,9 @@ CREATE TABLE IF NOT EXISTS world_time (
   hour INTEGER NOT NULL,
   week INTEGER NOT NULL,
   month INTEGER NOT NULL,
-  is_day INTEGER NOT NULL
+  is_day INTEGER NOT NULL,
+  hours_per_tick INTEGER NOT NULL DEFAULT 1,
+  hours_per_day INTEGER NOT NULL DEFAULT 24
 );
 
 CREATE TABLE IF NOT EXISTS persona_state (
@@ -66,6 +90,17 @@ CREATE TABLE IF NOT EXISTS omni_registry (
   state_json TEXT NOT NULL
 );
 
+CREATE TABLE IF NOT EXISTS run_stats (
+  id INTEGER PRIMARY KEY CHECK (id = 1),
+  stats_json TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS snapshots (
+  world_turn INTEGER PRIMARY KEY,
+  save_version INTEGER NOT NULL,
+  state_json TEXT NOT NULL
+);
+
 CREATE TABLE IF NOT EXISTS locations (
   location_id INTEGER PRIMARY KEY,
   heat INTEGER NOT NULL,
@@ -75,7 +110,10 @@ CREATE TABLE IF NOT EXISTS locations (
   lockdown_level INTEGER NOT NULL,
   police_units INTEGER NOT NULL,
   investigators INTEGER NOT NULL,
-  gang_units INTEGER NOT NULL
+  gang_units INTEGER NOT NULL,
+  economy INTEGER NOT NULL DEFAULT 50,
+  witness_memory INTEGER NOT NULL DEFAULT 0,
+  discovered INTEGER NOT NULL DEFAULT 1
 );
 
 CREATE TABLE IF NOT EXISTS location_tags (
@@ -84,6 +122,12 @@ CREATE TABLE IF NOT EXISTS location_tags (
   PRIMARY KEY (location_id, tag)
 );
 
+CREATE TABLE IF NOT EXISTS location_adjacency (
+  location_id INTEGER NOT NULL,
+  neighbor_id INTEGER NOT NULL,
+  PRIMARY KEY (location_id, neighbor_id)
+);
+
 CREATE TABLE IF NOT EXISTS location_faction_influence (
   location_id INTEGER NOT NULL,
   faction_id TEXT NOT NULL,
@@ -99,7 +143,9 @@ CREATE TABLE IF NOT EXISTS cases (
   progress INTEGER NOT NULL,
   heat_lock INTEGER NOT NULL,
   status TEXT NOT NULL,
-  milestone INTEGER NOT NULL
+  milestone INTEGER NOT NULL,
+  suspect_persona TEXT,
+  revealed INTEGER NOT NULL DEFAULT 0
 );
 
 CREATE TABLE IF NOT EXISTS case_signatures (
@@ -114,6 +160,75 @@ CREATE TABLE IF NOT EXISTS case_pressure_actions (
   PRIMARY KEY (case_id, action)
 );
 
+CREATE TABLE IF NOT EXISTS pressure_state (
+  id INTEGER PRIMARY KEY CHECK (id = 1),
+  temporal REAL NOT NULL,
+  identity REAL NOT NULL,
+  institutional REAL NOT NULL,
+  moral REAL NOT NULL,
+  resource REAL NOT NULL,
+  psychological REAL NOT NULL,
+  history TEXT NOT NULL DEFAULT '[]'
+);
+
+CREATE TABLE IF NOT EXISTS region_pressure (
+  id INTEGER PRIMARY KEY CHECK (id = 1),
+  total REAL NOT NULL,
+  escalation TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS region_profiles (
+  scope TEXT NOT NULL,
+  profile_id INTEGER NOT NULL,
+  heat_average REAL NOT NULL,
+  crime_pressure_average REAL NOT NULL,
+  escalation TEXT NOT NULL,
+  PRIMARY KEY (scope, profile_id)
+);
+
+CREATE TABLE IF NOT EXISTS evidence_signatures (
+  seq INTEGER PRIMARY KEY,
+  location_id INTEGER NOT NULL,
+  signature_type TEXT NOT NULL,
+  strength INTEGER NOT NULL,
+  persistence_turns INTEGER NOT NULL,
+  remaining_turns INTEGER NOT NULL,
+  is_new INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS identity_evidence (
+  evidence_id INTEGER PRIMARY KEY,
+  location_id INTEGER NOT NULL,
+  time_tick INTEGER NOT NULL,
+  witness_count INTEGER NOT NULL,
+  visual_quality INTEGER NOT NULL,
+  persona_hint TEXT NOT NULL,
+  attributed_persona TEXT,
+  debunked INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS identity_evidence_signatures (
+  evidence_id INTEGER NOT NULL,
+  signature_type TEXT NOT NULL,
+  PRIMARY KEY (evidence_id, signature_type)
+);
+
+CREATE TABLE IF NOT EXISTS identity_evidence_features (
+  evidence_id INTEGER NOT NULL,
+  seq INTEGER NOT NULL,
+  feature TEXT NOT NULL,
+  PRIMARY KEY (evidence_id, seq)
+);
+
+CREATE TABLE IF NOT EXISTS identity_sighting_trails (
+  location_id INTEGER NOT NULL,
+  persona_hint TEXT NOT NULL,
+  sightings INTEGER NOT NULL,
+  confidence INTEGER NOT NULL,
+  last_sighting_tick INTEGER,
+  PRIMARY KEY (location_id, persona_hint)
+);
+
 CREATE TABLE IF NOT EXISTS storylet_fired (
   storylet_id TEXT PRIMARY KEY
 );
@@ -125,7 +240,8 @@ CREATE TABLE IF NOT EXISTS storylet_cooldowns (
 
 CREATE TABLE IF NOT EXISTS storylet_flags (
   flag_key TEXT PRIMARY KEY,
-  flag_value INTEGER NOT NULL
+  flag_value INTEGER NOT NULL,
+  expires_in INTEGER
 );
 
 CREATE TABLE IF NOT EXISTS storylet_punctuation (
@@ -134,6 +250,16 @@ CREATE TABLE IF NOT EXISTS storylet_punctuation (
   turns INTEGER NOT NULL
 );
 
+CREATE TABLE IF NOT EXISTS ambush_state (
+  id INTEGER PRIMARY KEY CHECK (id = 1),
+  setting TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS agent_ambush_cooldowns (
+  agent_id INTEGER PRIMARY KEY,
+  last_ambush_tick INTEGER NOT NULL
+);
+
 CREATE TABLE IF NOT EXISTS growth_state (
   id INTEGER PRIMARY KEY CHECK (id = 1),
   pressure_resistance INTEGER NOT NULL,
@@ -161,7 +287,15 @@ CREATE TABLE IF NOT EXISTS combat_state (
   scale TEXT NOT NULL,
   tick INTEGER NOT NULL,
   escape_progress INTEGER NOT NULL,
-  pending_expression_id TEXT
+  pending_expression_id TEXT,
+  nemesis_id TEXT,
+  staging_tags TEXT NOT NULL DEFAULT '',
+  staging_surveillance INTEGER NOT NULL DEFAULT 0,
+  difficulty TEXT NOT NULL DEFAULT 'STANDARD',
+  distance_m INTEGER,
+  pending_waves TEXT NOT NULL DEFAULT '[]',
+  hazard_collateral INTEGER NOT NULL DEFAULT 0,
+  rng_state INTEGER NOT NULL DEFAULT 0
 );
 
 CREATE TABLE IF NOT EXISTS combatants (
@@ -170,11 +304,27 @@ CREATE TABLE IF NOT EXISTS combatants (
   name TEXT NOT NULL,
   side TEXT NOT NULL,
   stress INTEGER NOT NULL,
+  stress_cap INTEGER NOT NULL DEFAULT 100,
   intent TEXT NOT NULL,
   is_player INTEGER NOT NULL,
   PRIMARY KEY (combat_id, combatant_id)
 );
 
+CREATE TABLE IF NOT EXISTS combatant_loadouts (
+  combat_id INTEGER NOT NULL,
+  combatant_id INTEGER NOT NULL,
+  slot INTEGER NOT NULL,
+  expression_id TEXT NOT NULL,
+  PRIMARY KEY (combat_id, combatant_id, slot)
+);
+
+CREATE TABLE IF NOT EXISTS combat_log (
+  combat_id INTEGER NOT NULL,
+  seq INTEGER NOT NULL,
+  entry TEXT NOT NULL,
+  PRIMARY KEY (combat_id, seq)
+);
+
 CREATE TABLE IF NOT EXISTS characters (
   character_id TEXT PRIMARY KEY,
   scope_id TEXT NOT NULL,
@@ -229,18 +379,8 @@ CREATE TABLE IF NOT EXISTS character_relationships (
 pub enum WorldDbError {
     Sqlite(rusqlite::Error),
     InvalidData(String),
-}
-
-fn response_for_heat(heat: i32) -> HeatResponse {
-    if heat >= 70 {
-        HeatResponse::FactionAttention
-    } else if heat >= 50 {
-        HeatResponse::Investigation
-    } else if heat >= 30 {
-        HeatResponse::PolicePatrol
-    } else {
-        HeatResponse::None
-    }
+    /// A write was attempted through a handle from `WorldDb::open_readonly`.
+    ReadOnly,
 }
 
 fn alignment_to_str(alignment: Alignment) -> &'static str {
@@ -299,6 +439,68 @@ fn case_status_from_str(value: &str) -> Result<CaseStatus, WorldDbError> {
     }
 }
 
+fn region_escalation_to_str(escalation: RegionEscalation) -> &'static str {
+    match escalation {
+        RegionEscalation::Stable => "STABLE",
+        RegionEscalation::Alert => "ALERT",
+        RegionEscalation::Emergency => "EMERGENCY",
+    }
+}
+
+fn region_escalation_from_str(value: &str) -> Result<RegionEscalation, WorldDbError> {
+    match value {
+        "STABLE" => Ok(RegionEscalation::Stable),
+        "ALERT" => Ok(RegionEscalation::Alert),
+        "EMERGENCY" => Ok(RegionEscalation::Emergency),
+        _ => Err(WorldDbError::InvalidData(format!(
+            "unknown region escalation {}",
+            value
+        ))),
+    }
+}
+
+fn global_escalation_to_str(escalation: GlobalEscalation) -> &'static str {
+    match escalation {
+        GlobalEscalation::Stable => "STABLE",
+        GlobalEscalation::Tense => "TENSE",
+        GlobalEscalation::Crisis => "CRISIS",
+        GlobalEscalation::Cosmic => "COSMIC",
+    }
+}
+
+fn global_escalation_from_str(value: &str) -> Result<GlobalEscalation, WorldDbError> {
+    match value {
+        "STABLE" => Ok(GlobalEscalation::Stable),
+        "TENSE" => Ok(GlobalEscalation::Tense),
+        "CRISIS" => Ok(GlobalEscalation::Crisis),
+        "COSMIC" => Ok(GlobalEscalation::Cosmic),
+        _ => Err(WorldDbError::InvalidData(format!(
+            "unknown global escalation {}",
+            value
+        ))),
+    }
+}
+
+fn persona_hint_to_str(hint: PersonaHint) -> &'static str {
+    match hint {
+        PersonaHint::Civilian => "CIVILIAN",
+        PersonaHint::Masked => "MASKED",
+        PersonaHint::Unknown => "UNKNOWN",
+    }
+}
+
+fn persona_hint_from_str(value: &str) -> Result<PersonaHint, WorldDbError> {
+    match value {
+        "CIVILIAN" => Ok(PersonaHint::Civilian),
+        "MASKED" => Ok(PersonaHint::Masked),
+        "UNKNOWN" => Ok(PersonaHint::Unknown),
+        _ => Err(WorldDbError::InvalidData(format!(
+            "unknown persona hint {}",
+            value
+        ))),
+    }
+}
+
 fn case_target_to_str(target: CaseTargetType) -> &'static str {
     match target {
         CaseTargetType::UnknownMasked => "UNKNOWN_MASKED",
@@ -408,6 +610,11 @@ fn combat_scale_to_str(scale: CombatScale) -> &'static str {
     }
 }
 
+fn ambush_setting_from_str(value: &str) -> Result<AmbushSetting, WorldDbError> {
+    AmbushSetting::parse(value)
+        .ok_or_else(|| WorldDbError::InvalidData(format!("unknown ambush setting {}", value)))
+}
+
 fn combat_scale_from_str(value: &str) -> Result<CombatScale, WorldDbError> {
     match value {
         "STREET" => Ok(CombatScale::Street),
@@ -422,6 +629,28 @@ fn combat_scale_from_str(value: &str) -> Result<CombatScale, WorldDbError> {
     }
 }
 
+fn combat_difficulty_to_str(difficulty: DifficultySetting) -> &'static str {
+    match difficulty {
+        DifficultySetting::Passive => "PASSIVE",
+        DifficultySetting::Standard => "STANDARD",
+        DifficultySetting::Aggressive => "AGGRESSIVE",
+        DifficultySetting::Tactical => "TACTICAL",
+    }
+}
+
+fn combat_difficulty_from_str(value: &str) -> Result<DifficultySetting, WorldDbError> {
+    match value {
+        "PASSIVE" => Ok(DifficultySetting::Passive),
+        "STANDARD" => Ok(DifficultySetting::Standard),
+        "AGGRESSIVE" => Ok(DifficultySetting::Aggressive),
+        "TACTICAL" => Ok(DifficultySetting::Tactical),
+        _ => Err(WorldDbError::InvalidData(format!(
+            "unknown combat difficulty {}",
+            value
+        ))),
+    }
+}
+
 fn combat_intent_to_str(intent: CombatIntent) -> &'static str {
     match intent {
         CombatIntent::Attack => "ATTACK",
@@ -464,11 +693,22 @@ fn combat_side_from_str(value: &str) -> Result<CombatSide, WorldDbError> {
     }
 }
 
+fn decode_snapshot(save_version: i64, json: &str) -> Result<WorldDbState, WorldDbError> {
+    if save_version != WORLD_SAVE_VERSION {
+        return Err(WorldDbError::InvalidData(format!(
+            "snapshot save version {} does not match {}",
+            save_version, WORLD_SAVE_VERSION
+        )));
+    }
+    serde_json::from_str(json).map_err(|err| WorldDbError::InvalidData(err.to_string()))
+}
+
 impl std::fmt::Display for WorldDbError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             WorldDbError::Sqlite(err) => write!(f, "sqlite error: {}", err),
             WorldDbError::InvalidData(message) => write!(f, "{}", message),
+            WorldDbError::ReadOnly => write!(f, "world db was opened read-only"),
         }
     }
 }
@@ -481,7 +721,7 @@ impl From<rusqlite::Error> for WorldDbError {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorldDbState {
     pub world_turn: u64,
     pub game_time: GameTime,
@@ -494,6 +734,17 @@ pub struct WorldDbState {
     pub alignment: Alignment,
     pub civilian_state: CivilianState,
     pub omni_registry: OmniPowerRegistry,
+    pub run_stats: RunStats,
+    #[serde(default)]
+    pub evidence: WorldEvidence,
+    #[serde(default)]
+    pub identity_evidence: IdentityEvidenceStore,
+    #[serde(default)]
+    pub pressure: PressureState,
+    #[serde(default)]
+    pub region: RegionState,
+    #[serde(default)]
+    pub ambush: AmbushState,
 }
 
 impl Default for WorldDbState {
@@ -513,56 +764,404 @@ impl Default for WorldDbState {
             alignment: Alignment::Neutral,
             civilian_state: CivilianState::default(),
             omni_registry: OmniPowerRegistry::default(),
+            run_stats: RunStats::default(),
+            evidence: WorldEvidence::default(),
+            identity_evidence: IdentityEvidenceStore::default(),
+            pressure: PressureState::default(),
+            region: RegionState::default(),
+            ambush: AmbushState::default(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct WorldJsonExport<'a> {
+    save_version: i64,
+    state: &'a WorldDbState,
+}
+
+#[derive(Deserialize)]
+struct WorldJsonImport {
+    save_version: i64,
+    state: WorldDbState,
+}
+
+impl WorldDbState {
+    /// Human-readable export for debugging and sharing repros. Map keys are
+    /// written in sorted order so the same state always exports the same text.
+    pub fn to_json(&self) -> Result<String, WorldDbError> {
+        let value = serde_json::to_value(WorldJsonExport {
+            save_version: WORLD_SAVE_VERSION,
+            state: self,
+        })
+        .map_err(|err| WorldDbError::InvalidData(err.to_string()))?;
+        serde_json::to_string_pretty(&value)
+            .map_err(|err| WorldDbError::InvalidData(err.to_string()))
+    }
+
+    /// Reads a `to_json` export. Location response levels are derived from
+    /// heat, so saving the result and loading it back recomputes them.
+    pub fn from_json(json: &str) -> Result<Self, WorldDbError> {
+        let import: WorldJsonImport =
+            serde_json::from_str(json).map_err(|err| WorldDbError::InvalidData(err.to_string()))?;
+        if import.save_version != WORLD_SAVE_VERSION {
+            return Err(WorldDbError::InvalidData(format!(
+                "world export save version {} does not match {}",
+                import.save_version, WORLD_SAVE_VERSION
+            )));
         }
+        Ok(import.state)
     }
 }
 
 pub struct WorldDb {
     conn: Connection,
+    read_only: bool,
+    /// Set by `mark_dirty`, cleared by every successful `save_state`.
+    dirty: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SaveDiagnostics {
+    pub schema_version: Option<i64>,
+    pub save_version: Option<i64>,
+    pub table_counts: Vec<(String, i64)>,
+    pub orphaned_rows: Vec<String>,
+    pub invalid_values: Vec<String>,
+    /// Values this build doesn't know but keeps on load, such as location tags
+    /// from newer content. They don't make a save unhealthy.
+    pub preserved_values: Vec<String>,
+}
+
+impl SaveDiagnostics {
+    pub fn is_healthy(&self) -> bool {
+        self.schema_version == Some(WORLD_SCHEMA_VERSION)
+            && self.save_version == Some(WORLD_SAVE_VERSION)
+            && self.orphaned_rows.is_empty()
+            && self.invalid_values.is_empty()
+    }
+}
+
+/// Which parts of a save loaded cleanly and which fell back to defaults.
+/// `preserved` lists location tags this build doesn't know but kept as written.
+#[derive(Debug, Clone, Default)]
+pub struct LoadReport {
+    pub recovered: Vec<&'static str>,
+    pub defaulted: Vec<(&'static str, String)>,
+    pub preserved: Vec<String>,
+}
+
+impl LoadReport {
+    pub fn is_clean(&self) -> bool {
+        self.defaulted.is_empty()
+    }
+
+    /// Keeps a loaded component, or records the error and uses `fallback`.
+    fn component<T>(
+        &mut self,
+        name: &'static str,
+        loaded: Result<T, WorldDbError>,
+        fallback: impl FnOnce() -> T,
+    ) -> T {
+        match loaded {
+            Ok(value) => {
+                self.recovered.push(name);
+                value
+            }
+            Err(err) => {
+                self.defaulted.push((name, err.to_string()));
+                fallback()
+            }
+        }
+    }
+}
+
+const ORPHAN_CHECKS: &[(&str, &str, &str, &str)] = &[
+    ("location_tags", "location_id", "locations", "location_id"),
+    ("location_faction_influence", "location_id", "locations", "location_id"),
+    ("location_adjacency", "location_id", "locations", "location_id"),
+    ("case_signatures", "case_id", "cases", "case_id"),
+    ("case_pressure_actions", "case_id", "cases", "case_id"),
+    ("combatants", "combat_id", "combat_state", "id"),
+    ("combat_log", "combat_id", "combat_state", "id"),
+    ("character_roles", "character_id", "characters", "character_id"),
+    ("character_personas", "character_id", "characters", "character_id"),
+    ("character_powers", "character_id", "characters", "character_id"),
+    ("character_relationships", "a_character_id", "characters", "character_id"),
+];
+
+/// A table, one of its enum columns, and whether a stored value parses.
+type EnumCheck = (&'static str, &'static str, fn(&str) -> bool);
+
+fn diagnose_connection(conn: &Connection) -> Result<SaveDiagnostics, WorldDbError> {
+    let mut report = SaveDiagnostics::default();
+    let mut stmt = conn.prepare(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )?;
+    let tables = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<HashSet<_>, _>>()?;
+    let has = |table: &str| tables.contains(table);
+
+    if has("world_meta") {
+        if let Some((schema_version, save_version)) = conn
+            .query_row(
+                "SELECT schema_version, save_version FROM world_meta WHERE id = 1",
+                [],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)),
+            )
+            .optional()?
+        {
+            report.schema_version = Some(schema_version);
+            report.save_version = Some(save_version);
+        }
+    }
+
+    let mut names: Vec<&String> = tables.iter().collect();
+    names.sort();
+    for table in names {
+        let count = conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+            row.get::<_, i64>(0)
+        })?;
+        report.table_counts.push((table.clone(), count));
+    }
+
+    for (child, child_key, parent, parent_key) in ORPHAN_CHECKS {
+        if !has(child) || !has(parent) {
+            continue;
+        }
+        let count = conn.query_row(
+            &format!(
+                "SELECT COUNT(*) FROM {child} WHERE {child_key} NOT IN (SELECT {parent_key} FROM {parent})"
+            ),
+            [],
+            |row| row.get::<_, i64>(0),
+        )?;
+        if count > 0 {
+            report
+                .orphaned_rows
+                .push(format!("{} rows in {} with no {} parent", count, child, parent));
+        }
+    }
+    if has("combatant_loadouts") && has("combatants") {
+        let loadout_orphans = conn.query_row(
+            "SELECT COUNT(*) FROM combatant_loadouts l WHERE NOT EXISTS (SELECT 1 FROM combatants c WHERE c.combat_id = l.combat_id AND c.combatant_id = l.combatant_id)",
+            [],
+            |row| row.get::<_, i64>(0),
+        )?;
+        if loadout_orphans > 0 {
+            report.orphaned_rows.push(format!(
+                "{} rows in combatant_loadouts with no combatants parent",
+                loadout_orphans
+            ));
+        }
+    }
+
+    let enum_checks: [EnumCheck; 11] = [
+        ("persona_state", "alignment", |value| alignment_from_str(value).is_some()),
+        ("cases", "status", |value| case_status_from_str(value).is_ok()),
+        ("cases", "target_type", |value| case_target_from_str(value).is_ok()),
+        ("case_signatures", "signature_type", |value| {
+            signature_type_from_str(value).is_some()
+        }),
+        ("expression_mastery", "stage", |value| mastery_stage_from_str(value).is_ok()),
+        ("combat_state", "scale", |value| combat_scale_from_str(value).is_ok()),
+        ("combat_state", "difficulty", |value| combat_difficulty_from_str(value).is_ok()),
+        ("combatants", "side", |value| combat_side_from_str(value).is_ok()),
+        ("combatants", "intent", |value| combat_intent_from_str(value).is_ok()),
+        ("characters", "importance_tier", |value| importance_from_str(value).is_ok()),
+        ("ambush_state", "setting", |value| {
+            ambush_setting_from_str(value).is_ok()
+        }),
+    ];
+    for (table, column, parses) in enum_checks {
+        if has(table) {
+            let unknown = unparsed_enum_values(conn, table, column, parses)?;
+            report.invalid_values.extend(unknown);
+        }
+    }
+    // Unknown location tags survive a load (see `load_location_tags`), so they
+    // are listed without faulting the save.
+    if has("location_tags") {
+        let unknown = unparsed_enum_values(conn, "location_tags", "tag", |value| {
+            location_tag_from_str(value).is_some()
+        })?;
+        report.preserved_values.extend(unknown);
+    }
+    Ok(report)
+}
+
+fn unparsed_enum_values(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    parses: fn(&str) -> bool,
+) -> Result<Vec<String>, WorldDbError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {column}, COUNT(*) FROM {table} GROUP BY {column}"
+    ))?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+    })?;
+    let mut unknown = Vec::new();
+    for row in rows {
+        let (value, count) = row?;
+        if !parses(&value) {
+            unknown.push(format!("{}.{} = {:?} ({} rows)", table, column, value, count));
+        }
+    }
+    Ok(unknown)
 }
 
 impl WorldDb {
     pub fn open(path: impl AsRef<Path>) -> Result<Self, WorldDbError> {
         let conn = Connection::open(path)?;
-        let mut db = Self { conn };
+        // WAL keeps an interrupted save's transaction out of the main file, and
+        // FULL syncs each commit so the last completed save survives power loss.
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.pragma_update(None, "synchronous", "FULL")?;
+        let mut db = Self {
+            conn,
+            read_only: false,
+            dirty: false,
+        };
         db.conn.execute_batch(WORLD_DB_SCHEMA)?;
+        db.ensure_columns()?;
         db.ensure_world_meta()?;
         Ok(db)
     }
 
-    pub fn load_or_init(&mut self) -> Result<WorldDbState, WorldDbError> {
-        if let Some(state) = self.load_state()? {
-            Ok(state)
+    /// Opens an existing save for inspection only. No schema or migration work is
+    /// run, so a dashboard can tail a live save (including one in WAL mode) while
+    /// the game keeps writing; load_* methods work and save_* methods return
+    /// `WorldDbError::ReadOnly`.
+    pub fn open_readonly(path: impl AsRef<Path>) -> Result<Self, WorldDbError> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        let db = Self {
+            conn,
+            read_only: true,
+            dirty: false,
+        };
+        let schema_version = db
+            .conn
+            .query_row(
+                "SELECT schema_version FROM world_meta WHERE id = 1",
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .optional()?;
+        if schema_version != Some(WORLD_SCHEMA_VERSION) {
+            return Err(WorldDbError::InvalidData(format!(
+                "read-only open needs schema {}, found {:?}; open the save in the game once to migrate it",
+                WORLD_SCHEMA_VERSION, schema_version
+            )));
+        }
+        Ok(db)
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    fn ensure_writable(&self) -> Result<(), WorldDbError> {
+        if self.read_only {
+            return Err(WorldDbError::ReadOnly);
+        }
+        Ok(())
+    }
+
+    pub fn load_or_init(&mut self) -> Result<(WorldDbState, LoadReport), WorldDbError> {
+        if let Some(loaded) = self.load_state()? {
+            Ok(loaded)
         } else {
             let state = WorldDbState::default();
             self.save_state(&state)?;
-            Ok(state)
+            Ok((state, LoadReport::default()))
         }
     }
 
-    pub fn load_state(&self) -> Result<Option<WorldDbState>, WorldDbError> {
+    /// Loads the saved world. Only a missing or unreadable world_state row is
+    /// fatal; any other component that fails to load is replaced by its default
+    /// and noted in the report.
+    pub fn load_state(&self) -> Result<Option<(WorldDbState, LoadReport)>, WorldDbError> {
         let mut stmt = self
             .conn
-            .prepare("SELECT world_turn, active_location FROM world_state WHERE id = 1")?;
+            .prepare("SELECT world_turn, active_location, heat_patrol, heat_investigation, heat_faction_attention, city_id, region_id FROM world_state WHERE id = 1")?;
         let mut rows = stmt.query([])?;
         let Some(row) = rows.next()? else {
             return Ok(None);
         };
         let world_turn: u64 = row.get::<_, i64>(0)? as u64;
         let active_location = LocationId(row.get::<_, i64>(1)? as u32);
-
-        let game_time = self.load_game_time()?;
-        let (persona_stack, alignment) = self.load_persona_state()?;
-        let mut city = self.load_city()?;
+        let heat_response = HeatResponseConfig {
+            patrol: row.get::<_, i64>(2)? as i32,
+            investigation: row.get::<_, i64>(3)? as i32,
+            faction_attention: row.get::<_, i64>(4)? as i32,
+        };
+        let city_id = CityId(row.get::<_, i64>(5)? as u32);
+        let region_id = RegionId(row.get::<_, i64>(6)? as u32);
+
+        let mut report = LoadReport::default();
+        let game_time = report.component("world_time", self.load_game_time(), GameTime::default);
+        let (persona_stack, alignment) =
+            report.component("persona_state", self.load_persona_state(), || {
+                (neutral_persona_stack(), Alignment::Neutral)
+            });
+        let mut city = report.component("city", self.load_city(heat_response), || CityState {
+            heat_response,
+            ..CityState::default()
+        });
         city.active_location = active_location;
-        let cases = self.load_cases()?;
-        let combat = self.load_combat_state(active_location)?;
-        let growth = self.load_growth_state()?;
-        let storylet_state = self.load_storylet_state()?;
-        let civilian_state = self.load_civilian_state()?;
-        let omni_registry = self.load_omni_registry()?;
-
-        Ok(Some(WorldDbState {
+        city.city_id = city_id;
+        city.region_id = region_id;
+        report.preserved = city
+            .locations
+            .values()
+            .flat_map(|location| location.raw_tags.iter().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let cases = report.component("cases", self.load_cases(), CaseRegistry::default);
+        let combat = report.component(
+            "combat_state",
+            self.load_combat_state(active_location),
+            || CombatState {
+                location_id: active_location,
+                ..CombatState::default()
+            },
+        );
+        let growth = report.component("growth_state", self.load_growth_state(), GrowthState::default);
+        let storylet_state = report.component(
+            "storylet_state",
+            self.load_storylet_state(),
+            StoryletState::default,
+        );
+        let civilian_state = report.component(
+            "civilian_state",
+            self.load_civilian_state(),
+            CivilianState::default,
+        );
+        let omni_registry = report.component(
+            "omni_registry",
+            self.load_omni_registry(),
+            OmniPowerRegistry::default,
+        );
+        let run_stats = report.component("run_stats", self.load_run_stats(), RunStats::default);
+        let evidence = report.component("evidence", self.load_evidence(), WorldEvidence::default);
+        let identity_evidence = report.component(
+            "identity_evidence",
+            self.load_identity_evidence(),
+            IdentityEvidenceStore::default,
+        );
+        let pressure =
+            report.component("pressure_state", self.load_pressure_state(), PressureState::default);
+        let region = report.component("region_state", self.load_region_state(), RegionState::default);
+        let ambush = report.component("ambush_state", self.load_ambush_state(), AmbushState::default);
+
+        let state = WorldDbState {
             world_turn,
             game_time,
             city,
@@ -574,28 +1173,72 @@ impl WorldDb {
             alignment,
             civilian_state,
             omni_registry,
-        }))
+            run_stats,
+            evidence,
+            identity_evidence,
+            pressure,
+            region,
+            ambush,
+        };
+        Ok(Some((state, report)))
     }
 
+    /// Writes the whole state in one transaction, so a crash or failed write
+    /// mid-save rolls back to the previous save.
     pub fn save_state(&mut self, state: &WorldDbState) -> Result<(), WorldDbError> {
-        let tx = self.conn.transaction()?;
+        self.ensure_writable()?;
+        Self::write_state(&mut self.conn, state)?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Saves only if something was marked dirty since the last save; returns
+    /// whether a write happened.
+    pub fn save_if_dirty(&mut self, state: &WorldDbState) -> Result<bool, WorldDbError> {
+        if !self.dirty {
+            return Ok(false);
+        }
+        self.save_state(state)?;
+        Ok(true)
+    }
+
+    fn write_state(conn: &mut Connection, state: &WorldDbState) -> Result<(), WorldDbError> {
+        let tx = conn.transaction()?;
 
         tx.execute("DELETE FROM world_state", [])?;
         tx.execute(
-            "INSERT INTO world_state (id, world_turn, active_location) VALUES (1, ?1, ?2)",
-            params![state.world_turn as i64, state.city.active_location.0 as i64],
+            "INSERT INTO world_state (id, world_turn, active_location, heat_patrol, heat_investigation, heat_faction_attention, city_id, region_id) VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                state.world_turn as i64,
+                state.city.active_location.0 as i64,
+                state.city.heat_response.patrol,
+                state.city.heat_response.investigation,
+                state.city.heat_response.faction_attention,
+                state.city.city_id.0 as i64,
+                state.city.region_id.0 as i64,
+            ],
         )?;
 
         tx.execute("DELETE FROM world_time", [])?;
         tx.execute(
-            "INSERT INTO world_time (id, tick, day, hour, week, month, is_day) VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT INTO world_time (id, tick, day, hour, week, month, is_day, hours_per_tick, hours_per_day) VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             params![
                 state.game_time.tick as i64,
                 state.game_time.day as i64,
                 state.game_time.hour as i64,
                 state.game_time.week as i64,
                 state.game_time.month as i64,
-                if state.game_time.is_day { 1 } else { 0 }
+                if state.game_time.is_day { 1 } else { 0 },
+                state.game_time.config.hours_per_tick as i64,
+                state.game_time.config.hours_per_day as i64
             ],
         )?;
 
@@ -623,12 +1266,29 @@ impl WorldDb {
             params![omni_json],
         )?;
 
+        tx.execute("DELETE FROM run_stats", [])?;
+        let stats_json = serde_json::to_string(&state.run_stats)
+            .map_err(|err| WorldDbError::InvalidData(err.to_string()))?;
+        tx.execute(
+            "INSERT INTO run_stats (id, stats_json) VALUES (1, ?1)",
+            params![stats_json],
+        )?;
+
         tx.execute("DELETE FROM locations", [])?;
         tx.execute("DELETE FROM location_tags", [])?;
         tx.execute("DELETE FROM location_faction_influence", [])?;
+        tx.execute("DELETE FROM location_adjacency", [])?;
+        for (location_id, neighbors) in &state.city.adjacency {
+            for neighbor_id in neighbors {
+                tx.execute(
+                    "INSERT OR IGNORE INTO location_adjacency (location_id, neighbor_id) VALUES (?1, ?2)",
+                    params![location_id.0 as i64, neighbor_id.0 as i64],
+                )?;
+            }
+        }
         for location in state.city.locations.values() {
             tx.execute(
-                "INSERT INTO locations (location_id, heat, crime_pressure, police_presence, surveillance_level, lockdown_level, police_units, investigators, gang_units) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                "INSERT INTO locations (location_id, heat, crime_pressure, police_presence, surveillance_level, lockdown_level, police_units, investigators, gang_units, economy, witness_memory, discovered) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
                 params![
                     location.id.0 as i64,
                     location.heat,
@@ -638,7 +1298,10 @@ impl WorldDb {
                     location.lockdown_level,
                     location.police_units as i64,
                     location.investigators as i64,
-                    location.gang_units as i64
+                    location.gang_units as i64,
+                    location.economy,
+                    location.witness_memory,
+                    if location.discovered { 1 } else { 0 }
                 ],
             )?;
             for tag in &location.tags {
@@ -647,6 +1310,12 @@ impl WorldDb {
                     params![location.id.0 as i64, location_tag_to_str(*tag)],
                 )?;
             }
+            for tag in &location.raw_tags {
+                tx.execute(
+                    "INSERT OR IGNORE INTO location_tags (location_id, tag) VALUES (?1, ?2)",
+                    params![location.id.0 as i64, tag],
+                )?;
+            }
             for (faction_id, influence) in &location.faction_influence {
                 tx.execute(
                     "INSERT INTO location_faction_influence (location_id, faction_id, influence) VALUES (?1, ?2, ?3)",
@@ -660,7 +1329,7 @@ impl WorldDb {
         tx.execute("DELETE FROM case_pressure_actions", [])?;
         for case in &state.cases.cases {
             tx.execute(
-                "INSERT INTO cases (case_id, faction_id, location_id, target_type, progress, heat_lock, status, milestone) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                "INSERT INTO cases (case_id, faction_id, location_id, target_type, progress, heat_lock, status, milestone, suspect_persona, revealed) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
                 params![
                     case.case_id as i64,
                     case.faction_id,
@@ -669,7 +1338,9 @@ impl WorldDb {
                     case.progress as i64,
                     if case.heat_lock { 1 } else { 0 },
                     case_status_to_str(case.status),
-                    case.milestone as i64
+                    case.milestone as i64,
+                    case.suspect_persona.as_deref(),
+                    if case.revealed { 1 } else { 0 }
                 ],
             )?;
             for sig in &case.signature_pattern {
@@ -686,6 +1357,116 @@ impl WorldDb {
             }
         }
 
+        tx.execute("DELETE FROM pressure_state", [])?;
+        let pressure_history_json = serde_json::to_string(&state.pressure.history)
+            .map_err(|err| WorldDbError::InvalidData(err.to_string()))?;
+        tx.execute(
+            "INSERT INTO pressure_state (id, temporal, identity, institutional, moral, resource, psychological, history) VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                state.pressure.temporal as f64,
+                state.pressure.identity as f64,
+                state.pressure.institutional as f64,
+                state.pressure.moral as f64,
+                state.pressure.resource as f64,
+                state.pressure.psychological as f64,
+                pressure_history_json,
+            ],
+        )?;
+
+        tx.execute("DELETE FROM region_pressure", [])?;
+        tx.execute("DELETE FROM region_profiles", [])?;
+        tx.execute(
+            "INSERT INTO region_pressure (id, total, escalation) VALUES (1, ?1, ?2)",
+            params![
+                state.region.global_pressure.total as f64,
+                global_escalation_to_str(state.region.global_pressure.escalation),
+            ],
+        )?;
+        let profiles = state
+            .region
+            .regions
+            .values()
+            .map(|p| ("REGION", p.id.0, p.heat_average, p.crime_pressure_average, p.escalation))
+            .chain(state.region.countries.values().map(|p| {
+                ("COUNTRY", p.id.0, p.heat_average, p.crime_pressure_average, p.escalation)
+            }))
+            .chain(state.region.continents.values().map(|p| {
+                ("CONTINENT", p.id.0, p.heat_average, p.crime_pressure_average, p.escalation)
+            }));
+        for (scope, profile_id, heat_average, crime_pressure_average, escalation) in profiles {
+            tx.execute(
+                "INSERT INTO region_profiles (scope, profile_id, heat_average, crime_pressure_average, escalation) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    scope,
+                    profile_id as i64,
+                    heat_average as f64,
+                    crime_pressure_average as f64,
+                    region_escalation_to_str(escalation),
+                ],
+            )?;
+        }
+
+        tx.execute("DELETE FROM evidence_signatures", [])?;
+        for (seq, event) in state.evidence.iter().enumerate() {
+            let spec = &event.signature.signature;
+            tx.execute(
+                "INSERT INTO evidence_signatures (seq, location_id, signature_type, strength, persistence_turns, remaining_turns, is_new) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    seq as i64,
+                    event.location_id.0 as i64,
+                    signature_type_to_str(spec.signature_type),
+                    spec.strength,
+                    spec.persistence_turns,
+                    event.signature.remaining_turns,
+                    if event.is_new { 1 } else { 0 }
+                ],
+            )?;
+        }
+
+        tx.execute("DELETE FROM identity_evidence", [])?;
+        tx.execute("DELETE FROM identity_evidence_signatures", [])?;
+        tx.execute("DELETE FROM identity_evidence_features", [])?;
+        for item in &state.identity_evidence.items {
+            tx.execute(
+                "INSERT INTO identity_evidence (evidence_id, location_id, time_tick, witness_count, visual_quality, persona_hint, attributed_persona, debunked) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    item.evidence_id as i64,
+                    item.location_id.0 as i64,
+                    item.time_tick as i64,
+                    item.witness_count as i64,
+                    item.visual_quality as i64,
+                    persona_hint_to_str(item.persona_hint),
+                    item.attributed_persona.as_deref(),
+                    if item.debunked { 1 } else { 0 }
+                ],
+            )?;
+            for sig in &item.signatures {
+                tx.execute(
+                    "INSERT OR IGNORE INTO identity_evidence_signatures (evidence_id, signature_type) VALUES (?1, ?2)",
+                    params![item.evidence_id as i64, signature_type_to_str(*sig)],
+                )?;
+            }
+            for (seq, feature) in item.suspect_features.iter().enumerate() {
+                tx.execute(
+                    "INSERT INTO identity_evidence_features (evidence_id, seq, feature) VALUES (?1, ?2, ?3)",
+                    params![item.evidence_id as i64, seq as i64, feature],
+                )?;
+            }
+        }
+        tx.execute("DELETE FROM identity_sighting_trails", [])?;
+        for trail in &state.identity_evidence.trails {
+            tx.execute(
+                "INSERT INTO identity_sighting_trails (location_id, persona_hint, sightings, confidence, last_sighting_tick) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    trail.location_id.0 as i64,
+                    persona_hint_to_str(trail.persona_hint),
+                    trail.sightings as i64,
+                    trail.confidence as i64,
+                    trail.last_sighting_tick.map(|tick| tick as i64)
+                ],
+            )?;
+        }
+
         tx.execute("DELETE FROM storylet_fired", [])?;
         tx.execute("DELETE FROM storylet_cooldowns", [])?;
         tx.execute("DELETE FROM storylet_flags", [])?;
@@ -704,8 +1485,12 @@ impl WorldDb {
         }
         for (flag, value) in &state.storylet_state.flags {
             tx.execute(
-                "INSERT INTO storylet_flags (flag_key, flag_value) VALUES (?1, ?2)",
-                params![flag, if *value { 1 } else { 0 }],
+                "INSERT INTO storylet_flags (flag_key, flag_value, expires_in) VALUES (?1, ?2, ?3)",
+                params![
+                    flag,
+                    if *value { 1 } else { 0 },
+                    state.storylet_state.flag_expiry.get(flag).map(|turns| *turns as i64)
+                ],
             )?;
         }
         tx.execute(
@@ -720,6 +1505,19 @@ impl WorldDb {
             ],
         )?;
 
+        tx.execute("DELETE FROM ambush_state", [])?;
+        tx.execute("DELETE FROM agent_ambush_cooldowns", [])?;
+        tx.execute(
+            "INSERT INTO ambush_state (id, setting) VALUES (1, ?1)",
+            params![state.ambush.setting.as_str()],
+        )?;
+        for (agent_id, tick) in &state.ambush.last_ambush_ticks {
+            tx.execute(
+                "INSERT INTO agent_ambush_cooldowns (agent_id, last_ambush_tick) VALUES (?1, ?2)",
+                params![*agent_id as i64, *tick as i64],
+            )?;
+        }
+
         tx.execute("DELETE FROM growth_state", [])?;
         tx.execute("DELETE FROM expression_mastery", [])?;
         tx.execute("DELETE FROM unlocked_expressions", [])?;
@@ -751,9 +1549,13 @@ impl WorldDb {
         }
 
         tx.execute("DELETE FROM combat_state", [])?;
+        let pending_waves_json = serde_json::to_string(&state.combat.pending_waves)
+            .map_err(|err| WorldDbError::InvalidData(err.to_string()))?;
         tx.execute("DELETE FROM combatants", [])?;
+        tx.execute("DELETE FROM combatant_loadouts", [])?;
+        tx.execute("DELETE FROM combat_log", [])?;
         tx.execute(
-            "INSERT INTO combat_state (id, active, source, location_id, scale, tick, escape_progress, pending_expression_id) VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT INTO combat_state (id, active, source, location_id, scale, tick, escape_progress, pending_expression_id, nemesis_id, staging_tags, staging_surveillance, difficulty, distance_m, pending_waves, hazard_collateral, rng_state) VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
             params![
                 if state.combat.active { 1 } else { 0 },
                 state.combat.source.as_str(),
@@ -766,26 +1568,148 @@ impl WorldDb {
                     .pending_player_expression
                     .as_ref()
                     .map(|id| id.0.clone()),
+                state.combat.nemesis_id.as_deref(),
+                state
+                    .combat
+                    .staging
+                    .tags
+                    .iter()
+                    .map(|tag| location_tag_to_str(*tag))
+                    .collect::<Vec<_>>()
+                    .join(","),
+                state.combat.staging.surveillance_level,
+                combat_difficulty_to_str(state.combat.difficulty),
+                state.combat.distance_m,
+                pending_waves_json,
+                state.combat.hazard_collateral,
+                state.combat.rng_state as i64,
             ],
         )?;
         for combatant in &state.combat.combatants {
             tx.execute(
-                "INSERT INTO combatants (combat_id, combatant_id, name, side, stress, intent, is_player) VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6)",
+                "INSERT INTO combatants (combat_id, combatant_id, name, side, stress, stress_cap, intent, is_player) VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7)",
                 params![
                     combatant.id as i64,
                     combatant.name.as_str(),
                     combat_side_to_str(combatant.side),
                     combatant.stress,
+                    combatant.stress_cap,
                     combat_intent_to_str(combatant.intent),
                     if combatant.is_player { 1 } else { 0 },
                 ],
             )?;
+            for (slot, expr_id) in combatant.loadout.iter().enumerate() {
+                tx.execute(
+                    "INSERT INTO combatant_loadouts (combat_id, combatant_id, slot, expression_id) VALUES (1, ?1, ?2, ?3)",
+                    params![combatant.id as i64, slot as i64, expr_id.0.as_str()],
+                )?;
+            }
+        }
+        let log_start = state.combat.log.len().saturating_sub(COMBAT_LOG_SAVE_LIMIT);
+        for (seq, entry) in state.combat.log[log_start..].iter().enumerate() {
+            tx.execute(
+                "INSERT INTO combat_log (combat_id, seq, entry) VALUES (1, ?1, ?2)",
+                params![seq as i64, entry.as_str()],
+            )?;
         }
 
         tx.commit()?;
         Ok(())
     }
 
+    /// Writes the whole state as one JSON blob keyed by its turn, replacing any
+    /// snapshot already taken that turn. Snapshots are quick checkpoints; the
+    /// normalized tables written by `save_state` stay the canonical save.
+    pub fn save_snapshot(&mut self, state: &WorldDbState) -> Result<(), WorldDbError> {
+        self.ensure_writable()?;
+        let json =
+            serde_json::to_string(state).map_err(|err| WorldDbError::InvalidData(err.to_string()))?;
+        self.conn.execute(
+            "INSERT OR REPLACE INTO snapshots (world_turn, save_version, state_json) VALUES (?1, ?2, ?3)",
+            params![state.world_turn as i64, WORLD_SAVE_VERSION, json],
+        )?;
+        Ok(())
+    }
+
+    pub fn load_snapshot(&self, world_turn: u64) -> Result<Option<WorldDbState>, WorldDbError> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT save_version, state_json FROM snapshots WHERE world_turn = ?1",
+                params![world_turn as i64],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()?;
+        row.map(|(save_version, json)| decode_snapshot(save_version, &json))
+            .transpose()
+    }
+
+    /// The most recent snapshot, if any have been taken.
+    pub fn latest_snapshot(&self) -> Result<Option<WorldDbState>, WorldDbError> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT save_version, state_json FROM snapshots ORDER BY world_turn DESC LIMIT 1",
+                [],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()?;
+        row.map(|(save_version, json)| decode_snapshot(save_version, &json))
+            .transpose()
+    }
+
+    fn ensure_columns(&self) -> Result<(), WorldDbError> {
+        self.ensure_column("world_meta", "world_seed", "INTEGER")?;
+        self.ensure_column("combat_state", "staging_tags", "TEXT NOT NULL DEFAULT ''")?;
+        self.ensure_column(
+            "combat_state",
+            "staging_surveillance",
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
+        self.ensure_column("combatants", "stress_cap", "INTEGER NOT NULL DEFAULT 100")?;
+        self.ensure_column("locations", "economy", "INTEGER NOT NULL DEFAULT 50")?;
+        self.ensure_column("locations", "witness_memory", "INTEGER NOT NULL DEFAULT 0")?;
+        self.ensure_column("locations", "discovered", "INTEGER NOT NULL DEFAULT 1")?;
+        self.ensure_column("combat_state", "nemesis_id", "TEXT")?;
+        self.ensure_column("combat_state", "difficulty", "TEXT NOT NULL DEFAULT 'STANDARD'")?;
+        self.ensure_column("combat_state", "distance_m", "INTEGER")?;
+        self.ensure_column("combat_state", "pending_waves", "TEXT NOT NULL DEFAULT '[]'")?;
+        self.ensure_column("combat_state", "hazard_collateral", "INTEGER NOT NULL DEFAULT 0")?;
+        self.ensure_column("combat_state", "rng_state", "INTEGER NOT NULL DEFAULT 0")?;
+        self.ensure_column("world_state", "heat_patrol", "INTEGER NOT NULL DEFAULT 30")?;
+        self.ensure_column("world_state", "heat_investigation", "INTEGER NOT NULL DEFAULT 50")?;
+        self.ensure_column(
+            "world_state",
+            "heat_faction_attention",
+            "INTEGER NOT NULL DEFAULT 70",
+        )?;
+        self.ensure_column("world_state", "city_id", "INTEGER NOT NULL DEFAULT 1")?;
+        self.ensure_column("cases", "suspect_persona", "TEXT")?;
+        self.ensure_column("cases", "revealed", "INTEGER NOT NULL DEFAULT 0")?;
+        self.ensure_column("storylet_flags", "expires_in", "INTEGER")?;
+        self.ensure_column("world_state", "region_id", "INTEGER NOT NULL DEFAULT 1")?;
+        self.ensure_column("pressure_state", "history", "TEXT NOT NULL DEFAULT '[]'")?;
+        self.ensure_column("identity_sighting_trails", "last_sighting_tick", "INTEGER")?;
+        self.ensure_column("world_time", "hours_per_tick", "INTEGER NOT NULL DEFAULT 1")?;
+        self.ensure_column("world_time", "hours_per_day", "INTEGER NOT NULL DEFAULT 24")?;
+        Ok(())
+    }
+
+    fn ensure_column(&self, table: &str, column: &str, definition: &str) -> Result<(), WorldDbError> {
+        let mut stmt = self.conn.prepare(&format!("PRAGMA table_info({})", table))?;
+        let columns = stmt.query_map([], |row| row.get::<_, String>(1))?;
+        for name in columns {
+            if name? == column {
+                return Ok(());
+            }
+        }
+        self.conn.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )?;
+        Ok(())
+    }
+
     fn ensure_world_meta(&mut self) -> Result<(), WorldDbError> {
         let meta = self
             .conn
@@ -801,12 +1725,7 @@ impl WorldDb {
                 if schema_version == WORLD_SCHEMA_VERSION && save_version == WORLD_SAVE_VERSION {
                     return Ok(());
                 }
-                if (schema_version == 1
-                    || schema_version == 2
-                    || schema_version == 3
-                    || schema_version == 4
-                    || schema_version == 5
-                    || schema_version == 6)
+                if (1..WORLD_SCHEMA_VERSION).contains(&schema_version)
                     && save_version == WORLD_SAVE_VERSION
                 {
                     self.conn.execute(
@@ -828,7 +1747,50 @@ impl WorldDb {
             }
         }
 
-        Ok(())
+        Ok(())
+    }
+
+    /// Returns the seed recorded for this world, or None for saves created before
+    /// seeds were persisted.
+    pub fn world_seed(&self) -> Result<Option<u64>, WorldDbError> {
+        let seed = self
+            .conn
+            .query_row("SELECT world_seed FROM world_meta WHERE id = 1", [], |row| {
+                row.get::<_, Option<i64>>(0)
+            })
+            .optional()?
+            .flatten();
+        Ok(seed.map(|value| value as u64))
+    }
+
+    /// Returns the recorded world seed, storing `fallback` first if none exists yet.
+    pub fn ensure_world_seed(&mut self, fallback: u64) -> Result<u64, WorldDbError> {
+        if let Some(seed) = self.world_seed()? {
+            return Ok(seed);
+        }
+        self.ensure_writable()?;
+        self.conn.execute(
+            "UPDATE world_meta SET world_seed = ?1 WHERE id = 1",
+            params![fallback as i64],
+        )?;
+        Ok(fallback)
+    }
+
+    /// Inspects the save without loading it into game state, so a save that fails
+    /// with InvalidData can be broken down into the specific rows at fault.
+    pub fn diagnose(&self) -> Result<SaveDiagnostics, WorldDbError> {
+        diagnose_connection(&self.conn)
+    }
+
+    /// Diagnoses the save at `path` through a plain read-only connection. Unlike
+    /// `open`, nothing is created or migrated, so a save that won't open is
+    /// reported as it sits on disk.
+    pub fn diagnose_path(path: impl AsRef<Path>) -> Result<SaveDiagnostics, WorldDbError> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        diagnose_connection(&conn)
     }
 
     pub fn load_characters(&self) -> Result<Vec<PersistentCharacter>, WorldDbError> {
@@ -894,6 +1856,7 @@ impl WorldDb {
         &mut self,
         character: &PersistentCharacter,
     ) -> Result<(), WorldDbError> {
+        self.ensure_writable()?;
         let tx = self.conn.transaction()?;
         tx.execute(
             "INSERT OR REPLACE INTO characters (character_id, scope_id, first_name, last_name, birth_year, ancestry, nationality, importance_tier, created_at_tick) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
@@ -990,12 +1953,28 @@ impl WorldDb {
         candidate: &PromotionCandidate,
         created_at_tick: u64,
     ) -> Result<PersistentCharacter, WorldDbError> {
+        self.ensure_writable()?;
         let character_id = self.next_character_id()?;
         let character = candidate.to_character(character_id, created_at_tick);
         self.upsert_character(&character)?;
         Ok(character)
     }
 
+    /// Promotes a captured opponent and records them as incarcerated from the
+    /// capture tick onward.
+    pub fn record_capture(
+        &mut self,
+        candidate: &PromotionCandidate,
+        captured_at_tick: u64,
+    ) -> Result<PersistentCharacter, WorldDbError> {
+        self.ensure_writable()?;
+        let character_id = self.next_character_id()?;
+        let mut character = candidate.to_character(character_id, captured_at_tick);
+        mark_incarcerated(&mut character, captured_at_tick);
+        self.upsert_character(&character)?;
+        Ok(character)
+    }
+
     fn next_character_id(&self) -> Result<String, WorldDbError> {
         let next_id: i64 = self.conn.query_row(
             "SELECT COALESCE(MAX(rowid), 0) + 1 FROM characters",
@@ -1008,7 +1987,9 @@ impl WorldDb {
     fn load_game_time(&self) -> Result<GameTime, WorldDbError> {
         let mut stmt = self
             .conn
-            .prepare("SELECT tick, day, hour, week, month, is_day FROM world_time WHERE id = 1")?;
+            .prepare(
+                "SELECT tick, day, hour, week, month, is_day, hours_per_tick, hours_per_day FROM world_time WHERE id = 1",
+            )?;
         let mut rows = stmt.query([])?;
         let Some(row) = rows.next()? else {
             return Ok(GameTime::default());
@@ -1019,6 +2000,10 @@ impl WorldDb {
         let week = row.get::<_, i64>(3)? as u32;
         let month = row.get::<_, i64>(4)? as u32;
         let is_day = row.get::<_, i64>(5)? != 0;
+        let config = TimeConfig {
+            hours_per_tick: row.get::<_, i64>(6)?.clamp(1, u8::MAX as i64) as u8,
+            hours_per_day: row.get::<_, i64>(7)?.clamp(1, u8::MAX as i64) as u8,
+        };
         Ok(GameTime {
             tick,
             day,
@@ -1026,6 +2011,7 @@ impl WorldDb {
             week,
             month,
             is_day,
+            config,
         })
     }
 
@@ -1083,6 +2069,21 @@ impl WorldDb {
         serde_json::from_str(&json).map_err(|err| WorldDbError::InvalidData(err.to_string()))
     }
 
+    fn load_run_stats(&self) -> Result<RunStats, WorldDbError> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT stats_json FROM run_stats WHERE id = 1",
+                [],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?;
+        let Some(json) = row else {
+            return Ok(RunStats::default());
+        };
+        serde_json::from_str(&json).map_err(|err| WorldDbError::InvalidData(err.to_string()))
+    }
+
     fn load_growth_state(&self) -> Result<GrowthState, WorldDbError> {
         let row = self
             .conn
@@ -1161,7 +2162,7 @@ impl WorldDb {
         let row = self
             .conn
             .query_row(
-                "SELECT active, source, location_id, scale, tick, escape_progress, pending_expression_id FROM combat_state WHERE id = 1",
+                "SELECT active, source, location_id, scale, tick, escape_progress, pending_expression_id, nemesis_id, staging_tags, staging_surveillance, difficulty, distance_m, pending_waves, hazard_collateral, rng_state FROM combat_state WHERE id = 1",
                 [],
                 |row| {
                     Ok((
@@ -1172,6 +2173,14 @@ impl WorldDb {
                         row.get::<_, i64>(4)?,
                         row.get::<_, i64>(5)?,
                         row.get::<_, Option<String>>(6)?,
+                        row.get::<_, Option<String>>(7)?,
+                        row.get::<_, String>(8)?,
+                        row.get::<_, i64>(9)?,
+                        row.get::<_, String>(10)?,
+                        row.get::<_, Option<i64>>(11)?,
+                        row.get::<_, String>(12)?,
+                        row.get::<_, i64>(13)? as i32,
+                        row.get::<_, i64>(14)? as u64,
                     ))
                 },
             )
@@ -1180,7 +2189,23 @@ impl WorldDb {
         let mut state = CombatState::default();
         state.location_id = active_location;
 
-        let Some((active, source, location_id, scale, tick, escape_progress, pending_expr)) = row
+        let Some((
+            active,
+            source,
+            location_id,
+            scale,
+            tick,
+            escape_progress,
+            pending_expr,
+            nemesis_id,
+            staging_tags,
+            staging_surveillance,
+            difficulty,
+            distance_m,
+            pending_waves,
+            hazard_collateral,
+            rng_state,
+        )) = row
         else {
             return Ok(state);
         };
@@ -1189,17 +2214,47 @@ impl WorldDb {
         state.source = source;
         state.location_id = LocationId(location_id as u32);
         state.scale = combat_scale_from_str(&scale)?;
+        state.difficulty = combat_difficulty_from_str(&difficulty)?;
         state.tick = tick as u64;
         state.escape_progress = escape_progress as u8;
         state.pending_player_expression = pending_expr.map(ExpressionId);
+        state.nemesis_id = nemesis_id;
+        state.distance_m = distance_m;
+        state.pending_waves = serde_json::from_str(&pending_waves)
+            .map_err(|err| WorldDbError::InvalidData(err.to_string()))?;
+        state.staging = CombatStaging {
+            location_id: state.location_id,
+            tags: staging_tags
+                .split(',')
+                .filter_map(location_tag_from_str)
+                .collect(),
+            surveillance_level: staging_surveillance as i32,
+        };
+        state.hazards = state.staging.hazards();
+        state.hazard_collateral = hazard_collateral;
+        state.rng_state = rng_state;
         state.combatants = self.load_combatants()?;
+        state.log = self.load_combat_log()?;
         Ok(state)
     }
 
+    /// Saves from before the log was persisted simply come back with an empty log.
+    fn load_combat_log(&self) -> Result<Vec<String>, WorldDbError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT entry FROM combat_log WHERE combat_id = 1 ORDER BY seq")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
     fn load_combatants(&self) -> Result<Vec<Combatant>, WorldDbError> {
         let mut out = Vec::new();
         let mut stmt = self.conn.prepare(
-            "SELECT combatant_id, name, side, stress, intent, is_player FROM combatants WHERE combat_id = 1 ORDER BY combatant_id",
+            "SELECT combatant_id, name, side, stress, stress_cap, intent, is_player FROM combatants WHERE combat_id = 1 ORDER BY combatant_id",
         )?;
         let rows = stmt.query_map([], |row| {
             Ok((
@@ -1207,27 +2262,42 @@ impl WorldDb {
                 row.get::<_, String>(1)?,
                 row.get::<_, String>(2)?,
                 row.get::<_, i64>(3)? as i32,
-                row.get::<_, String>(4)?,
-                row.get::<_, i64>(5)? != 0,
+                row.get::<_, i64>(4)? as i32,
+                row.get::<_, String>(5)?,
+                row.get::<_, i64>(6)? != 0,
             ))
         })?;
         for row in rows {
-            let (id, name, side, stress, intent, is_player) = row?;
+            let (id, name, side, stress, stress_cap, intent, is_player) = row?;
             out.push(Combatant {
                 id,
                 name,
                 side: combat_side_from_str(&side)?,
                 stress,
+                stress_cap,
                 intent: combat_intent_from_str(&intent)?,
                 is_player,
+                loadout: self.load_combatant_loadout(id)?,
             });
         }
         Ok(out)
     }
 
-    fn load_city(&self) -> Result<CityState, WorldDbError> {
+    fn load_combatant_loadout(&self, combatant_id: u32) -> Result<Vec<ExpressionId>, WorldDbError> {
+        let mut out = Vec::new();
+        let mut stmt = self.conn.prepare(
+            "SELECT expression_id FROM combatant_loadouts WHERE combat_id = 1 AND combatant_id = ?1 ORDER BY slot",
+        )?;
+        let rows = stmt.query_map(params![combatant_id as i64], |row| row.get::<_, String>(0))?;
+        for row in rows {
+            out.push(ExpressionId(row?));
+        }
+        Ok(out)
+    }
+
+    fn load_city(&self, heat_response: HeatResponseConfig) -> Result<CityState, WorldDbError> {
         let mut stmt = self.conn.prepare(
-            "SELECT location_id, heat, crime_pressure, police_presence, surveillance_level, lockdown_level, police_units, investigators, gang_units FROM locations",
+            "SELECT location_id, heat, crime_pressure, police_presence, surveillance_level, lockdown_level, police_units, investigators, gang_units, economy, witness_memory, discovered FROM locations",
         )?;
         let mut rows = stmt.query([])?;
         let mut locations: HashMap<LocationId, LocationState> = HashMap::new();
@@ -1241,8 +2311,11 @@ impl WorldDb {
             let police_units = row.get::<_, i64>(6)? as u8;
             let investigators = row.get::<_, i64>(7)? as u8;
             let gang_units = row.get::<_, i64>(8)? as u8;
+            let economy = row.get::<_, i64>(9)? as i32;
+            let witness_memory = row.get::<_, i64>(10)? as i32;
+            let discovered = row.get::<_, i64>(11)? != 0;
 
-            let tags = self.load_location_tags(location_id)?;
+            let (tags, raw_tags) = self.load_location_tags(location_id)?;
             let influence = self.load_location_influence(location_id)?;
 
             locations.insert(
@@ -1250,6 +2323,7 @@ impl WorldDb {
                 LocationState {
                     id: location_id,
                     tags,
+                    raw_tags,
                     heat,
                     crime_pressure,
                     police_presence,
@@ -1258,14 +2332,20 @@ impl WorldDb {
                     police_units,
                     investigators,
                     gang_units,
+                    economy,
+                    witness_memory,
+                    discovered,
                     faction_influence: influence,
-                    response: response_for_heat(heat),
+                    response: heat_response.response_for(heat),
                 },
             );
         }
 
         if locations.is_empty() {
-            return Ok(CityState::default());
+            return Ok(CityState {
+                heat_response,
+                ..CityState::default()
+            });
         }
 
         Ok(CityState {
@@ -1275,14 +2355,39 @@ impl WorldDb {
             continent_id: ContinentId(1),
             locations,
             active_location: LocationId(1),
+            heat_response,
+            adjacency: self.load_location_adjacency()?,
         })
     }
 
+    /// Saves from before adjacency was stored come back with the default layout.
+    fn load_location_adjacency(&self) -> Result<HashMap<LocationId, Vec<LocationId>>, WorldDbError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT location_id, neighbor_id FROM location_adjacency ORDER BY location_id, neighbor_id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)? as u32, row.get::<_, i64>(1)? as u32))
+        })?;
+        let mut adjacency: HashMap<LocationId, Vec<LocationId>> = HashMap::new();
+        for row in rows {
+            let (location_id, neighbor_id) = row?;
+            adjacency
+                .entry(LocationId(location_id))
+                .or_default()
+                .push(LocationId(neighbor_id));
+        }
+        if adjacency.is_empty() {
+            return Ok(default_adjacency());
+        }
+        Ok(adjacency)
+    }
+
     fn load_location_tags(
         &self,
         location_id: LocationId,
-    ) -> Result<Vec<LocationTag>, WorldDbError> {
+    ) -> Result<(Vec<LocationTag>, Vec<String>), WorldDbError> {
         let mut tags = Vec::new();
+        let mut raw_tags = Vec::new();
         let mut stmt = self
             .conn
             .prepare("SELECT tag FROM location_tags WHERE location_id = ?1")?;
@@ -1291,11 +2396,13 @@ impl WorldDb {
             Ok(tag)
         })?;
         for row in rows {
-            if let Some(tag) = location_tag_from_str(&row?) {
-                tags.push(tag);
+            let raw = row?;
+            match location_tag_from_str(&raw) {
+                Some(tag) => tags.push(tag),
+                None => raw_tags.push(raw),
             }
         }
-        Ok(tags)
+        Ok((tags, raw_tags))
     }
 
     fn load_location_influence(
@@ -1321,7 +2428,7 @@ impl WorldDb {
     fn load_cases(&self) -> Result<CaseRegistry, WorldDbError> {
         let mut registry = CaseRegistry::default();
         let mut stmt = self.conn.prepare(
-            "SELECT case_id, faction_id, location_id, target_type, progress, heat_lock, status, milestone FROM cases",
+            "SELECT case_id, faction_id, location_id, target_type, progress, heat_lock, status, milestone, suspect_persona, revealed FROM cases",
         )?;
         let rows = stmt.query_map([], |row| {
             Ok((
@@ -1333,6 +2440,8 @@ impl WorldDb {
                 row.get::<_, i64>(5)? != 0,
                 row.get::<_, String>(6)?,
                 row.get::<_, i64>(7)? as u8,
+                row.get::<_, Option<String>>(8)?,
+                row.get::<_, i64>(9)? != 0,
             ))
         })?;
 
@@ -1346,6 +2455,8 @@ impl WorldDb {
                 heat_lock,
                 status,
                 milestone,
+                suspect_persona,
+                revealed,
             ) = row?;
             let target_type = case_target_from_str(&target_type)?;
             let status = case_status_from_str(&status)?;
@@ -1362,12 +2473,220 @@ impl WorldDb {
                 status,
                 milestone,
                 pressure_actions,
+                suspect_persona,
+                revealed,
             });
         }
         registry.sync_next_id();
         Ok(registry)
     }
 
+    fn load_pressure_state(&self) -> Result<PressureState, WorldDbError> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT temporal, identity, institutional, moral, resource, psychological, history FROM pressure_state WHERE id = 1",
+                [],
+                |row| {
+                    Ok((
+                        PressureState {
+                            temporal: row.get::<_, f64>(0)? as f32,
+                            identity: row.get::<_, f64>(1)? as f32,
+                            institutional: row.get::<_, f64>(2)? as f32,
+                            moral: row.get::<_, f64>(3)? as f32,
+                            resource: row.get::<_, f64>(4)? as f32,
+                            psychological: row.get::<_, f64>(5)? as f32,
+                            history: Default::default(),
+                        },
+                        row.get::<_, String>(6)?,
+                    ))
+                },
+            )
+            .optional()?;
+        let Some((mut pressure, history)) = row else {
+            return Ok(PressureState::default());
+        };
+        pressure.history = serde_json::from_str(&history)
+            .map_err(|err| WorldDbError::InvalidData(err.to_string()))?;
+        Ok(pressure)
+    }
+
+    /// Overlays saved momentum onto the default region layout; profiles the
+    /// layout no longer has are skipped.
+    fn load_region_state(&self) -> Result<RegionState, WorldDbError> {
+        let mut region = RegionState::default();
+        let global = self
+            .conn
+            .query_row(
+                "SELECT total, escalation FROM region_pressure WHERE id = 1",
+                [],
+                |row| Ok((row.get::<_, f64>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()?;
+        if let Some((total, escalation)) = global {
+            region.global_pressure = GlobalPressure {
+                total: total as f32,
+                escalation: global_escalation_from_str(&escalation)?,
+            };
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT scope, profile_id, heat_average, crime_pressure_average, escalation FROM region_profiles",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)? as u32,
+                row.get::<_, f64>(2)? as f32,
+                row.get::<_, f64>(3)? as f32,
+                row.get::<_, String>(4)?,
+            ))
+        })?;
+        for row in rows {
+            let (scope, profile_id, heat_average, crime_pressure_average, escalation) = row?;
+            let escalation = region_escalation_from_str(&escalation)?;
+            let target = match scope.as_str() {
+                "REGION" => region.regions.get_mut(&RegionId(profile_id)).map(|p| {
+                    (&mut p.heat_average, &mut p.crime_pressure_average, &mut p.escalation)
+                }),
+                "COUNTRY" => region.countries.get_mut(&CountryId(profile_id)).map(|p| {
+                    (&mut p.heat_average, &mut p.crime_pressure_average, &mut p.escalation)
+                }),
+                "CONTINENT" => region.continents.get_mut(&ContinentId(profile_id)).map(|p| {
+                    (&mut p.heat_average, &mut p.crime_pressure_average, &mut p.escalation)
+                }),
+                _ => None,
+            };
+            if let Some((heat, crime, level)) = target {
+                *heat = heat_average;
+                *crime = crime_pressure_average;
+                *level = escalation;
+            }
+        }
+        Ok(region)
+    }
+
+    fn load_evidence(&self) -> Result<WorldEvidence, WorldDbError> {
+        let mut signatures = Vec::new();
+        let mut stmt = self.conn.prepare(
+            "SELECT location_id, signature_type, strength, persistence_turns, remaining_turns, is_new FROM evidence_signatures ORDER BY seq",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                LocationId(row.get::<_, i64>(0)? as u32),
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+                row.get::<_, i64>(4)?,
+                row.get::<_, i64>(5)? != 0,
+            ))
+        })?;
+        for row in rows {
+            let (location_id, signature_type, strength, persistence_turns, remaining_turns, is_new) =
+                row?;
+            let Some(signature_type) = signature_type_from_str(&signature_type) else {
+                continue;
+            };
+            signatures.push(SignatureEvent {
+                location_id,
+                signature: SignatureInstance {
+                    signature: SignatureSpec {
+                        signature_type,
+                        strength,
+                        persistence_turns,
+                    },
+                    remaining_turns,
+                },
+                is_new,
+            });
+        }
+        Ok(WorldEvidence::from_events(signatures))
+    }
+
+    fn load_identity_evidence(&self) -> Result<IdentityEvidenceStore, WorldDbError> {
+        let mut store = IdentityEvidenceStore::default();
+        let mut stmt = self.conn.prepare(
+            "SELECT evidence_id, location_id, time_tick, witness_count, visual_quality, persona_hint, attributed_persona, debunked FROM identity_evidence ORDER BY evidence_id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)? as u32,
+                LocationId(row.get::<_, i64>(1)? as u32),
+                row.get::<_, i64>(2)? as u64,
+                row.get::<_, i64>(3)? as u32,
+                row.get::<_, i64>(4)? as u8,
+                row.get::<_, String>(5)?,
+                row.get::<_, Option<String>>(6)?,
+                row.get::<_, i64>(7)? != 0,
+            ))
+        })?;
+        for row in rows {
+            let (
+                evidence_id,
+                location_id,
+                time_tick,
+                witness_count,
+                visual_quality,
+                persona_hint,
+                attributed_persona,
+                debunked,
+            ) = row?;
+            let mut signatures = Vec::new();
+            let mut sig_stmt = self.conn.prepare(
+                "SELECT signature_type FROM identity_evidence_signatures WHERE evidence_id = ?1",
+            )?;
+            let sig_rows =
+                sig_stmt.query_map(params![evidence_id as i64], |row| row.get::<_, String>(0))?;
+            for sig in sig_rows {
+                if let Some(sig) = signature_type_from_str(&sig?) {
+                    signatures.push(sig);
+                }
+            }
+            let mut feature_stmt = self.conn.prepare(
+                "SELECT feature FROM identity_evidence_features WHERE evidence_id = ?1 ORDER BY seq",
+            )?;
+            let suspect_features = feature_stmt
+                .query_map(params![evidence_id as i64], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            store.items.push(IdentityEvidenceItem {
+                evidence_id,
+                location_id,
+                time_tick,
+                signatures,
+                witness_count,
+                visual_quality,
+                suspect_features,
+                persona_hint: persona_hint_from_str(&persona_hint)?,
+                attributed_persona,
+                debunked,
+            });
+        }
+        store.sync_next_id();
+        let mut trail_stmt = self.conn.prepare(
+            "SELECT location_id, persona_hint, sightings, confidence, last_sighting_tick FROM identity_sighting_trails ORDER BY location_id, persona_hint",
+        )?;
+        let trails = trail_stmt.query_map([], |row| {
+            Ok((
+                LocationId(row.get::<_, i64>(0)? as u32),
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)? as u32,
+                row.get::<_, i64>(3)?.clamp(0, 100) as u8,
+                row.get::<_, Option<i64>>(4)?.map(|tick| tick.max(0) as u64),
+            ))
+        })?;
+        for trail in trails {
+            let (location_id, persona_hint, sightings, confidence, last_sighting_tick) = trail?;
+            store.trails.push(SightingTrail {
+                location_id,
+                persona_hint: persona_hint_from_str(&persona_hint)?,
+                sightings,
+                confidence,
+                last_sighting_tick,
+            });
+        }
+        Ok(store)
+    }
+
     fn load_case_signatures(&self, case_id: u32) -> Result<Vec<SignatureType>, WorldDbError> {
         let mut out = Vec::new();
         let mut stmt = self
@@ -1421,12 +2740,19 @@ impl WorldDb {
 
         let mut stmt = self
             .conn
-            .prepare("SELECT flag_key, flag_value FROM storylet_flags")?;
+            .prepare("SELECT flag_key, flag_value, expires_in FROM storylet_flags")?;
         let rows = stmt.query_map([], |row| {
-            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? != 0))
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)? != 0,
+                row.get::<_, Option<i64>>(2)?,
+            ))
         })?;
         for row in rows {
-            let (key, value) = row?;
+            let (key, value, expires_in) = row?;
+            if let Some(turns) = expires_in {
+                state.flag_expiry.insert(key.clone(), turns as i32);
+            }
             state.flags.insert(key, value);
         }
 
@@ -1446,6 +2772,30 @@ impl WorldDb {
         Ok(state)
     }
 
+    fn load_ambush_state(&self) -> Result<AmbushState, WorldDbError> {
+        let mut ambush = AmbushState::default();
+        if let Some(setting) = self
+            .conn
+            .query_row("SELECT setting FROM ambush_state WHERE id = 1", [], |row| {
+                row.get::<_, String>(0)
+            })
+            .optional()?
+        {
+            ambush.setting = ambush_setting_from_str(&setting)?;
+        }
+        let mut stmt = self
+            .conn
+            .prepare("SELECT agent_id, last_ambush_tick FROM agent_ambush_cooldowns")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)? as u32, row.get::<_, i64>(1)? as u64))
+        })?;
+        for row in rows {
+            let (agent_id, tick) = row?;
+            ambush.last_ambush_ticks.insert(agent_id, tick);
+        }
+        Ok(ambush)
+    }
+
     fn load_roles(&self) -> Result<HashMap<String, Vec<CharacterRole>>, WorldDbError> {
         let mut map: HashMap<String, Vec<CharacterRole>> = HashMap::new();
         let mut stmt = self.conn.prepare(
@@ -1547,7 +2897,7 @@ impl WorldDb {
 }
 
 impl crate::world::repository::WorldRepository for WorldDb {
-    fn load_or_init(&mut self) -> Result<WorldDbState, Box<dyn std::error::Error>> {
+    fn load_or_init(&mut self) -> Result<(WorldDbState, LoadReport), Box<dyn std::error::Error>> {
         Ok(WorldDb::load_or_init(self)?)
     }
 
@@ -1555,6 +2905,18 @@ impl crate::world::repository::WorldRepository for WorldDb {
         Ok(WorldDb::save_state(self, state)?)
     }
 
+    fn mark_dirty(&mut self) {
+        WorldDb::mark_dirty(self)
+    }
+
+    fn is_dirty(&self) -> bool {
+        WorldDb::is_dirty(self)
+    }
+
+    fn save_if_dirty(&mut self, state: &WorldDbState) -> Result<bool, Box<dyn std::error::Error>> {
+        Ok(WorldDb::save_if_dirty(self, state)?)
+    }
+
     fn load_characters(&self) -> Result<Vec<PersistentCharacter>, Box<dyn std::error::Error>> {
         Ok(WorldDb::load_characters(self)?)
     }
@@ -1566,6 +2928,14 @@ impl crate::world::repository::WorldRepository for WorldDb {
         Ok(WorldDb::upsert_character(self, character)?)
     }
 
+    fn diagnose(&self) -> Result<SaveDiagnostics, Box<dyn std::error::Error>> {
+        Ok(WorldDb::diagnose(self)?)
+    }
+
+    fn ensure_world_seed(&mut self, fallback: u64) -> Result<u64, Box<dyn std::error::Error>> {
+        Ok(WorldDb::ensure_world_seed(self, fallback)?)
+    }
+
     fn promote_candidate(
         &mut self,
         candidate: &PromotionCandidate,
@@ -1577,4 +2947,570 @@ impl crate::world::repository::WorldRepository for WorldDb {
             created_at_tick,
         )?)
     }
+
+    fn record_capture(
+        &mut self,
+        candidate: &PromotionCandidate,
+        captured_at_tick: u64,
+    ) -> Result<PersistentCharacter, Box<dyn std::error::Error>> {
+        Ok(WorldDb::record_capture(self, candidate, captured_at_tick)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::pressure::PressureAxis;
+    use std::path::PathBuf;
+
+    /// A save file in the temp dir, removed along with its sidecar files when
+    /// the test ends, whether it passes or panics.
+    struct TempSave(PathBuf);
+
+    impl TempSave {
+        fn new(name: &str) -> Self {
+            let save = Self(std::env::temp_dir().join(format!(
+                "collapsing_worlds_{}_{}.db",
+                name,
+                std::process::id()
+            )));
+            save.remove();
+            save
+        }
+
+        fn path(&self) -> &Path {
+            &self.0
+        }
+
+        fn remove(&self) {
+            for suffix in ["", "-wal", "-shm", "-journal"] {
+                let mut file = self.0.as_os_str().to_owned();
+                file.push(suffix);
+                let _ = std::fs::remove_file(PathBuf::from(file));
+            }
+        }
+    }
+
+    impl Drop for TempSave {
+        fn drop(&mut self) {
+            self.remove();
+        }
+    }
+
+    #[test]
+    fn unknown_location_tag_survives_load_and_save() {
+        let save = TempSave::new("tags");
+        let mut db = WorldDb::open(save.path()).expect("open world db");
+        db.load_or_init().expect("init world db");
+        db.conn
+            .execute(
+                "INSERT INTO location_tags (location_id, tag) VALUES (1, 'WATERFRONT')",
+                [],
+            )
+            .expect("insert unknown tag");
+
+        let (state, report) = db.load_state().expect("load").expect("state present");
+        let location = &state.city.locations[&LocationId(1)];
+        assert_eq!(location.raw_tags, vec!["WATERFRONT".to_string()]);
+        assert_eq!(report.preserved, vec!["WATERFRONT".to_string()]);
+        assert!(report.is_clean());
+        db.save_state(&state).expect("save");
+
+        let tags: Vec<String> = db
+            .conn
+            .prepare("SELECT tag FROM location_tags WHERE location_id = 1 ORDER BY tag")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(tags, vec!["PUBLIC".to_string(), "WATERFRONT".to_string()]);
+
+    }
+
+    #[test]
+    fn diagnosing_a_save_path_reports_it_as_found_without_migrating() {
+        let save = TempSave::new("diagnose");
+        {
+            let mut db = WorldDb::open(save.path()).expect("open world db");
+            db.load_or_init().expect("init world db");
+            db.conn
+                .execute_batch(
+                    "INSERT INTO location_tags (location_id, tag) VALUES (1, 'WATERFRONT');
+                     UPDATE world_meta SET schema_version = 1 WHERE id = 1;
+                     DROP TABLE combat_log;",
+                )
+                .expect("age the save");
+        }
+
+        let report = WorldDb::diagnose_path(save.path()).expect("diagnose");
+        assert_eq!(report.schema_version, Some(1));
+        assert!(!report.is_healthy());
+        assert!(report.invalid_values.is_empty(), "{:?}", report.invalid_values);
+        assert_eq!(
+            report.preserved_values,
+            vec!["location_tags.tag = \"WATERFRONT\" (1 rows)".to_string()]
+        );
+        assert!(report.table_counts.iter().all(|(table, _)| table != "combat_log"));
+
+        let conn = Connection::open(save.path()).expect("reopen raw");
+        let schema_version: i64 = conn
+            .query_row("SELECT schema_version FROM world_meta WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("schema version");
+        assert_eq!(schema_version, 1);
+        let combat_log_tables: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'combat_log'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("table lookup");
+        assert_eq!(combat_log_tables, 0);
+        assert!(WorldDb::diagnose_path(save.path().with_extension("missing")).is_err());
+    }
+
+    #[test]
+    fn location_adjacency_round_trips_and_defaults_for_old_saves() {
+        let save = TempSave::new("adjacency");
+        let mut db = WorldDb::open(save.path()).expect("open world db");
+        let (mut state, _) = db.load_or_init().expect("init world db");
+        assert_eq!(state.city.adjacency, default_adjacency());
+
+        state.city.adjacency = HashMap::from([
+            (LocationId(1), vec![LocationId(2)]),
+            (LocationId(2), vec![LocationId(1)]),
+        ]);
+        db.save_state(&state).expect("save");
+        let (loaded, _) = db.load_state().expect("load").expect("state present");
+        assert_eq!(loaded.city.adjacency, state.city.adjacency);
+
+        db.conn
+            .execute("DELETE FROM location_adjacency", [])
+            .expect("clear adjacency");
+        let (loaded, _) = db.load_state().expect("load").expect("state present");
+        assert_eq!(loaded.city.adjacency, default_adjacency());
+
+    }
+
+    #[test]
+    fn corrupt_combat_row_defaults_only_the_combat_component() {
+        let save = TempSave::new("partial");
+        let mut db = WorldDb::open(save.path()).expect("open world db");
+        let (mut state, _) = db.load_or_init().expect("init world db");
+        state.world_turn = 12;
+        state.city.locations.get_mut(&LocationId(1)).unwrap().heat = 44;
+        state.cases.create_case("metro_police".to_string(), LocationId(1), Vec::new(), false);
+        state.cases.cases[0].revealed = true;
+        state.growth.reputation.trust = 9;
+        db.save_state(&state).expect("save");
+        db.conn
+            .execute("UPDATE combat_state SET scale = 'GALACTIC' WHERE id = 1", [])
+            .expect("corrupt combat scale");
+
+        let (loaded, report) = db.load_or_init().expect("partial load");
+        assert!(!report.is_clean());
+        assert_eq!(report.defaulted.len(), 1);
+        assert_eq!(report.defaulted[0].0, "combat_state");
+        assert!(report.recovered.contains(&"city"));
+        assert_eq!(loaded.world_turn, 12);
+        assert_eq!(loaded.city.locations[&LocationId(1)].heat, 44);
+        assert_eq!(loaded.cases.cases.len(), 1);
+        assert!(loaded.cases.cases[0].revealed);
+        assert_eq!(loaded.growth.reputation.trust, 9);
+        assert!(!loaded.combat.active);
+        assert_eq!(loaded.combat.location_id, loaded.city.active_location);
+
+    }
+
+    #[test]
+    fn world_seed_is_kept_across_reopen() {
+        let save = TempSave::new("seed");
+        let mut db = WorldDb::open(save.path()).expect("open world db");
+        assert_eq!(db.world_seed().expect("read seed"), None);
+        assert_eq!(db.ensure_world_seed(412).expect("store seed"), 412);
+        drop(db);
+
+        let mut db = WorldDb::open(save.path()).expect("reopen world db");
+        assert_eq!(db.ensure_world_seed(7).expect("keep seed"), 412);
+        let (mut state, _) = db.load_or_init().expect("init world db");
+        state.world_turn += 3;
+        db.save_state(&state).expect("save");
+        drop(db);
+
+        let mut db = WorldDb::open(save.path()).expect("reopen after a save");
+        assert_eq!(db.ensure_world_seed(9).expect("keep seed"), 412);
+    }
+
+    #[test]
+    fn time_cadence_is_kept_across_reopen() {
+        let save = TempSave::new("time_config");
+        let mut db = WorldDb::open(save.path()).expect("open world db");
+        let (mut state, _) = db.load_or_init().expect("init world db");
+        state.game_time = GameTime::with_config(TimeConfig::daily());
+        state.game_time.advance();
+        db.save_state(&state).expect("save");
+        drop(db);
+
+        let mut db = WorldDb::open(save.path()).expect("reopen world db");
+        let (loaded, _) = db.load_or_init().expect("load world db");
+        assert_eq!(loaded.game_time.config, TimeConfig::daily());
+        assert_eq!(loaded.game_time.day, state.game_time.day);
+    }
+
+    #[test]
+    fn ambush_setting_and_cooldowns_are_kept_across_reopen() {
+        let save = TempSave::new("ambush");
+        let mut db = WorldDb::open(save.path()).expect("open world db");
+        let (mut state, _) = db.load_or_init().expect("init world db");
+        state.ambush.setting = AmbushSetting::Brutal;
+        state.ambush.last_ambush_ticks.insert(3, 40);
+        state.ambush.last_ambush_ticks.insert(7, 52);
+        db.save_state(&state).expect("save");
+        drop(db);
+
+        let mut db = WorldDb::open(save.path()).expect("reopen world db");
+        let (loaded, report) = db.load_or_init().expect("load world db");
+        assert!(report.is_clean());
+        assert_eq!(loaded.ambush, state.ambush);
+    }
+
+    #[test]
+    fn readonly_handle_loads_but_refuses_saves() {
+        let save = TempSave::new("readonly");
+        let mut db = WorldDb::open(save.path()).expect("open world db");
+        let (state, _) = db.load_or_init().expect("init world db");
+
+        let mut reader = WorldDb::open_readonly(save.path()).expect("open read-only");
+        assert!(reader.is_read_only());
+        let (loaded, _) = reader.load_state().expect("load").expect("state present");
+        assert_eq!(loaded.world_turn, state.world_turn);
+        assert!(matches!(
+            reader.save_state(&loaded),
+            Err(WorldDbError::ReadOnly)
+        ));
+
+        let next = WorldDbState {
+            world_turn: state.world_turn + 1,
+            ..state
+        };
+        db.save_state(&next).expect("writer still saves");
+        let (tailed, _) = reader.load_state().expect("load").expect("state present");
+        assert_eq!(
+            tailed.world_turn, next.world_turn,
+            "readers see new saves without reopening"
+        );
+    }
+
+    #[test]
+    fn failed_saves_leave_the_previous_save_intact() {
+        let save = TempSave::new("crash_safe");
+        let mut db = WorldDb::open(save.path()).expect("open world db");
+        let mut state = WorldDbState {
+            world_turn: 5,
+            ..WorldDbState::default()
+        };
+        assert!(!db.save_if_dirty(&state).expect("clean save"));
+        db.mark_dirty();
+        assert!(db.save_if_dirty(&state).expect("dirty save"));
+        assert!(!db.is_dirty());
+
+        // A write that fails partway through rolls back and keeps the dirty flag.
+        state.world_turn = 6;
+        db.conn
+            .execute_batch(
+                "CREATE TEMP TRIGGER fail_save BEFORE INSERT ON cases
+                 BEGIN SELECT RAISE(ABORT, 'disk full'); END;",
+            )
+            .expect("install failing trigger");
+        state
+            .cases
+            .create_case("metro_police".to_string(), LocationId(1), Vec::new(), false);
+        db.mark_dirty();
+        assert!(db.save_if_dirty(&state).is_err());
+        assert!(db.is_dirty());
+        let (loaded, _) = db.load_state().expect("load").expect("state present");
+        assert_eq!(loaded.world_turn, 5);
+
+        db.conn
+            .execute_batch("DROP TRIGGER fail_save;")
+            .expect("remove failing trigger");
+        assert!(db.save_if_dirty(&state).expect("retry save"));
+        drop(db);
+        let db = WorldDb::open_readonly(save.path()).expect("reopen world db");
+        let (loaded, _) = db.load_state().expect("load").expect("state present");
+        assert_eq!(loaded.world_turn, 6);
+    }
+
+    #[test]
+    fn capture_writes_incarcerated_character() {
+        let save = TempSave::new("capture");
+        let mut db = WorldDb::open(save.path()).expect("open world db");
+        let candidate = PromotionCandidate::captured("city:1".to_string(), 0x0305);
+        let captured = db.record_capture(&candidate, 42).expect("record capture");
+
+        let characters = db.load_characters().expect("load characters");
+        let stored = characters
+            .iter()
+            .find(|character| character.character_id == captured.character_id)
+            .expect("captured character row");
+        let opponent = stored
+            .roles
+            .iter()
+            .find(|role| role.role_type == "OPPONENT")
+            .expect("opponent role");
+        assert_eq!(opponent.end_tick, Some(42));
+        let incarcerated = stored
+            .roles
+            .iter()
+            .find(|role| role.role_type == "INCARCERATED")
+            .expect("incarcerated role");
+        assert_eq!((incarcerated.start_tick, incarcerated.end_tick), (42, None));
+
+    }
+
+    #[test]
+    fn paused_fight_keeps_its_log_across_reload() {
+        let save = TempSave::new("combat_log");
+        let mut db = WorldDb::open(save.path()).expect("open world db");
+        let (mut state, _) = db.load_or_init().expect("init world db");
+        state.combat.active = true;
+        state.combat.tick = 3;
+        state.combat.difficulty = DifficultySetting::Tactical;
+        state.combat.distance_m = Some(25);
+        state.combat.hazard_collateral = 8;
+        state.combat.rng_state = u64::MAX - 7;
+        state.combat.pending_waves = vec![crate::simulation::combat::CombatWave {
+            delay_ticks: 6,
+            opponent_count: 3,
+            scale: CombatScale::District,
+        }];
+        state.combat.log = vec![
+            "Combat started.".to_string(),
+            "Thug takes 12 stress.".to_string(),
+        ];
+        db.save_state(&state).expect("save");
+        drop(db);
+
+        let db = WorldDb::open(save.path()).expect("reopen world db");
+        let (loaded, _) = db.load_state().expect("load").expect("state present");
+        assert_eq!(loaded.combat.log, state.combat.log);
+        assert_eq!(loaded.combat.tick, 3);
+        assert_eq!(loaded.combat.difficulty, DifficultySetting::Tactical);
+        assert_eq!(loaded.combat.distance_m, Some(25));
+        assert_eq!(loaded.combat.hazard_collateral, 8);
+        assert_eq!(loaded.combat.rng_state, u64::MAX - 7);
+        assert_eq!(loaded.combat.pending_waves, state.combat.pending_waves);
+
+    }
+
+    #[test]
+    fn long_combat_logs_keep_only_their_latest_entries() {
+        let save = TempSave::new("long_combat_log");
+        let mut db = WorldDb::open(save.path()).expect("open world db");
+        let (mut state, _) = db.load_or_init().expect("init world db");
+        state.combat.active = true;
+        state.combat.log = (0..COMBAT_LOG_SAVE_LIMIT + 50)
+            .map(|line| format!("line {}", line))
+            .collect();
+        db.save_state(&state).expect("save");
+
+        let (loaded, _) = db.load_state().expect("load").expect("state present");
+        assert_eq!(loaded.combat.log.len(), COMBAT_LOG_SAVE_LIMIT);
+        assert_eq!(loaded.combat.log.first().map(String::as_str), Some("line 50"));
+        assert_eq!(loaded.combat.log[..], state.combat.log[50..]);
+
+    }
+
+    #[test]
+    fn snapshot_blob_round_trips_without_touching_the_normalized_tables() {
+        let save = TempSave::new("snapshot");
+        let mut db = WorldDb::open(save.path()).expect("open world db");
+        let mut state = WorldDbState {
+            world_turn: 42,
+            ..WorldDbState::default()
+        };
+        state.city.locations.get_mut(&LocationId(1)).unwrap().heat = 63;
+        state.cases.create_case("metro_police".to_string(), LocationId(1), Vec::new(), false);
+        state.combat.log = vec!["Combat started.".to_string()];
+        state.run_stats.fights_won = 3;
+        db.save_snapshot(&state).expect("save snapshot");
+        state.world_turn = 43;
+        state.run_stats.fights_won = 4;
+        db.save_snapshot(&state).expect("save later snapshot");
+        drop(db);
+
+        let db = WorldDb::open(save.path()).expect("reopen world db");
+        assert!(db.load_state().expect("load").is_none());
+        let loaded = db.load_snapshot(42).expect("load snapshot").expect("snapshot present");
+        assert_eq!(loaded.world_turn, 42);
+        assert_eq!(loaded.run_stats.fights_won, 3);
+        assert_eq!(loaded.city.locations[&LocationId(1)].heat, 63);
+        assert_eq!(loaded.cases.cases.len(), 1);
+        assert_eq!(loaded.combat.log, state.combat.log);
+        let latest = db.latest_snapshot().expect("latest").expect("snapshot present");
+        assert_eq!(
+            serde_json::to_value(&latest).unwrap(),
+            serde_json::to_value(&state).unwrap()
+        );
+        assert!(db.load_snapshot(7).expect("missing turn").is_none());
+
+    }
+
+    #[test]
+    fn evidence_stores_round_trip_through_the_normalized_tables() {
+        let save = TempSave::new("evidence");
+        let mut db = WorldDb::open(save.path()).expect("open world db");
+        let mut state = WorldDbState::default();
+        state.evidence.emit(
+            LocationId(2),
+            &[SignatureInstance {
+                signature: SignatureSpec {
+                    signature_type: SignatureType::ThermalBloom,
+                    strength: 7,
+                    persistence_turns: 4,
+                },
+                remaining_turns: 3,
+            }],
+        );
+        let first = state.identity_evidence.record(
+            LocationId(2),
+            11,
+            vec![SignatureType::ThermalBloom, SignatureType::KineticStress],
+            4,
+            62,
+            PersonaHint::Masked,
+            vec!["red scarf".to_string(), "limp".to_string()],
+        );
+        state.identity_evidence.attribute(first, "Night Owl");
+        db.save_state(&state).expect("save state");
+        drop(db);
+
+        let db = WorldDb::open(save.path()).expect("reopen world db");
+        let (mut loaded, report) = db.load_state().expect("load").expect("state present");
+        assert!(report.is_clean());
+        assert_eq!(
+            serde_json::to_value(&loaded.evidence).unwrap(),
+            serde_json::to_value(&state.evidence).unwrap()
+        );
+        let item = &loaded.identity_evidence.items[0];
+        assert_eq!(item.evidence_id, first);
+        assert_eq!(item.witness_count, 4);
+        assert_eq!(item.visual_quality, 62);
+        assert_eq!(item.persona_hint, PersonaHint::Masked);
+        assert_eq!(item.suspected_persona(), Some("Night Owl"));
+        assert_eq!(item.suspect_features, vec!["red scarf", "limp"]);
+        assert_eq!(item.signatures.len(), 2);
+        assert_eq!(loaded.identity_evidence.trails, state.identity_evidence.trails);
+        let next = loaded.identity_evidence.record(
+            LocationId(1),
+            12,
+            Vec::new(),
+            0,
+            0,
+            PersonaHint::Unknown,
+            Vec::new(),
+        );
+        assert_eq!(next, first + 1);
+
+    }
+
+    #[test]
+    fn elevated_pressure_and_region_momentum_survive_reload() {
+        let save = TempSave::new("pressure");
+        let mut db = WorldDb::open(save.path()).expect("open world db");
+        let mut state = WorldDbState::default();
+        state.pressure.psychological = 73.5;
+        for step in 0..4 {
+            state.pressure.institutional = 35.0 + step as f32 * 2.0;
+            state.pressure.record_history();
+        }
+        state.region.update_global_pressure(&state.pressure);
+        let region = state.region.regions.get_mut(&RegionId(1)).expect("default region");
+        region.heat_average = 48.0;
+        region.escalation = RegionEscalation::Alert;
+        db.save_state(&state).expect("save state");
+        drop(db);
+
+        let db = WorldDb::open(save.path()).expect("reopen world db");
+        let (loaded, report) = db.load_state().expect("load").expect("state present");
+        assert!(report.is_clean());
+        assert_eq!(loaded.pressure.psychological, 73.5);
+        assert_eq!(loaded.pressure.institutional, 41.0);
+        assert_eq!(loaded.pressure.temporal, 0.0);
+        assert_eq!(loaded.pressure.history, state.pressure.history);
+        assert_eq!(loaded.pressure.trend(PressureAxis::Institutional), 2.0);
+        assert_eq!(
+            loaded.region.global_pressure.total,
+            state.region.global_pressure.total
+        );
+        let region = &loaded.region.regions[&RegionId(1)];
+        assert_eq!(region.heat_average, 48.0);
+        assert_eq!(region.escalation, RegionEscalation::Alert);
+
+    }
+    #[test]
+    fn json_export_is_stable_and_imports_into_an_equivalent_save() {
+        let source = TempSave::new("export");
+        let import = TempSave::new("import");
+
+        let mut state = WorldDbState {
+            world_turn: 17,
+            ..WorldDbState::default()
+        };
+        let heat_response = state.city.heat_response;
+        let location = state.city.locations.get_mut(&LocationId(3)).unwrap();
+        location.heat = 44;
+        location.response = heat_response.response_for(44);
+        location.crime_pressure = 21;
+        state.city.active_location = LocationId(3);
+        let case_id =
+            state
+                .cases
+                .create_case("metro_police".to_string(), LocationId(3), Vec::new(), false);
+        state.cases.case_by_id_mut(case_id).unwrap().progress = 35;
+        state.combat.log = vec!["Combat started.".to_string()];
+        for storylet in ["b_second", "a_first", "c_third"] {
+            state.storylet_state.fired.insert(storylet.to_string());
+        }
+        for expr in ["zap", "bolt", "mend"] {
+            state
+                .growth
+                .unlocked_expressions
+                .insert(ExpressionId(expr.to_string()));
+        }
+
+        let mut db = WorldDb::open(source.path()).expect("open world db");
+        db.save_state(&state).expect("save state");
+        let (loaded, report) = db.load_state().expect("load").expect("state present");
+        assert!(report.is_clean());
+        assert_eq!(
+            serde_json::to_value(&loaded.city).unwrap(),
+            serde_json::to_value(&state.city).unwrap(),
+            "load_city rebuilds the same city the serde form describes"
+        );
+        assert_eq!(
+            serde_json::to_value(&loaded.cases).unwrap(),
+            serde_json::to_value(&state.cases).unwrap(),
+            "load_cases rebuilds the same registry the serde form describes"
+        );
+
+        let json = loaded.to_json().expect("export");
+        let reparsed = WorldDbState::from_json(&json).expect("import");
+        assert_eq!(reparsed.to_json().expect("re-export"), json);
+
+        let mut imported = WorldDb::open(import.path()).expect("open fresh world db");
+        imported.save_state(&reparsed).expect("save imported state");
+        let (reloaded, _) = imported.load_state().expect("load").expect("state present");
+        assert_eq!(reloaded.to_json().expect("export imported save"), json);
+
+        let stale = json.replacen(
+            &format!("\"save_version\": {}", WORLD_SAVE_VERSION),
+            "\"save_version\": 0",
+            1,
+        );
+        assert!(WorldDbState::from_json(&stale).is_err());
+
+    }
 }
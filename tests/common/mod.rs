@@ -0,0 +1,24 @@
+use std::path::Path;
+
+use superhero_universe::content::schema::{CONTENT_SCHEMA_VERSION, CONTENT_VERSION};
+
+/// Copies the shipped content database and stamps it with the current content
+/// version so `SqlitePowerRepository::open` accepts it.
+pub fn stamped_content_copy(path: &Path) {
+    std::fs::copy("./assets/db/content_v1.db", path).expect("copy content db");
+    let conn = rusqlite::Connection::open(path).expect("open content copy");
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS content_meta (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            schema_version INTEGER NOT NULL,
+            content_version TEXT NOT NULL
+        );
+        DELETE FROM content_meta;",
+    )
+    .expect("content_meta table");
+    conn.execute(
+        "INSERT INTO content_meta (id, schema_version, content_version) VALUES (1, ?1, ?2)",
+        rusqlite::params![CONTENT_SCHEMA_VERSION, CONTENT_VERSION],
+    )
+    .expect("content_meta row");
+}
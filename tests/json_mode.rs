@@ -0,0 +1,72 @@
+mod common;
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Jumping Jack Bolt: 2 stamina, a 2-turn cooldown and a visual anomaly.
+const BOLT: &str = "a3eece895ae9d8ddae44ad80bc2e817cddcc721b";
+
+#[test]
+fn piped_commands_answer_one_json_line_each() {
+    let content_path = std::env::temp_dir().join(format!(
+        "collapsing_worlds_json_content_{}.db",
+        std::process::id()
+    ));
+    common::stamped_content_copy(&content_path);
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_superhero-universe"))
+        .args(["--json", "--seed", "11", "--db"])
+        .arg(&content_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn --json");
+    let input = format!(
+        concat!(
+            "{{\"cmd\":\"snapshot\"}}\n",
+            "\n",
+            "{{\"cmd\":\"move\",\"dx\":1,\"dy\":0}}\n",
+            "{{\"cmd\":\"use\",\"expr\":\"{bolt}\"}}\n",
+            "{{\"cmd\":\"use\",\"expr\":\"{bolt}\"}}\n",
+            "{{\"cmd\":\"use\",\"expr\":\"no_such_expression\"}}\n",
+            "{{\"cmd\":\"events\"}}\n",
+            "not json\n",
+        ),
+        bolt = BOLT
+    );
+    child
+        .stdin
+        .take()
+        .expect("stdin")
+        .write_all(input.as_bytes())
+        .expect("write commands");
+    let output = child.wait_with_output().expect("wait for --json");
+    let _ = std::fs::remove_file(&content_path);
+    assert!(output.status.success());
+
+    let lines: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .expect("utf8")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("response is JSON"))
+        .collect();
+    assert_eq!(lines.len(), 7);
+    assert_eq!(lines[0]["type"], "snapshot");
+    assert_eq!(lines[0]["snapshot"]["player_pos"], serde_json::json!([0, 0]));
+    assert_eq!(lines[1]["type"], "snapshot");
+    assert_eq!(lines[1]["snapshot"]["player_pos"], serde_json::json!([1, 0]));
+
+    let used = &lines[2];
+    assert_eq!(used["type"], "used");
+    assert_eq!(used["used"]["expression_id"], BOLT);
+    assert_eq!(used["used"]["stamina"], 8);
+    assert_eq!(used["used"]["cooldown_turns"], 2);
+    assert_eq!(used["used"]["signatures"][0]["signature"]["signature_type"], "VISUAL_ANOMALY");
+    assert!(used["used"]["snapshot"]["tick"].as_u64() > lines[1]["snapshot"]["tick"].as_u64());
+
+    assert_eq!(lines[3]["type"], "error", "the bolt is still cooling down");
+    assert!(lines[3]["message"].as_str().unwrap().contains("OnCooldown"));
+    assert_eq!(lines[4]["type"], "error");
+    assert_eq!(lines[5]["type"], "events");
+    assert_eq!(lines[6]["type"], "error");
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use std::path::PathBuf;
+
+use superhero_universe::content::SqlitePowerRepository;
+use superhero_universe::data::civilian_events::DEFAULT_CIVILIAN_EVENT_CATALOG;
+use superhero_universe::scripting::Session;
+use superhero_universe::world::{WorldDb, WorldRepository};
+
+#[test]
+fn a_script_runs_through_the_repl_dispatcher() {
+    let dir = std::env::temp_dir();
+    let content_path = dir.join(format!(
+        "collapsing_worlds_script_content_{}.db",
+        std::process::id()
+    ));
+    let world_path = dir.join(format!(
+        "collapsing_worlds_script_world_{}.db",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&world_path);
+    common::stamped_content_copy(&content_path);
+    let repo = SqlitePowerRepository::open(&content_path).expect("open content db");
+    let world_repo: Box<dyn WorldRepository> =
+        Box::new(WorldDb::open(&world_path).expect("open world db"));
+    let mut session = Session::open(
+        repo,
+        world_repo,
+        &world_path,
+        Some(7),
+        &[PathBuf::from(DEFAULT_CIVILIAN_EVENT_CATALOG)],
+    );
+    let startup = session.take_output();
+    assert!(startup.iter().any(|line| line == "World seed: 7"));
+
+    let lines = session.run_script(&["loc", "tick 3", "bogus", "seed", "quit", "tick 5"]);
+
+    assert!(lines.iter().any(|line| line.starts_with("Location 1 |")));
+    assert!(lines.iter().any(|line| line == "Unknown command. Type 'help'."));
+    assert!(lines.iter().any(|line| line == "World seed: 7"));
+    assert!(lines.iter().any(|line| line.starts_with("RNG position: turn 3")));
+    assert_eq!(session.world_turn(), 3, "quit stops the script before the last tick");
+    assert!(session.take_output().is_empty(), "run_script drains the output");
+
+    let _ = std::fs::remove_file(&content_path);
+    let _ = std::fs::remove_file(&world_path);
+}
+
+#[test]
+fn the_ambush_setting_survives_reopening_the_session() {
+    let dir = std::env::temp_dir();
+    let content_path = dir.join(format!(
+        "collapsing_worlds_ambush_content_{}.db",
+        std::process::id()
+    ));
+    let world_path = dir.join(format!(
+        "collapsing_worlds_ambush_world_{}.db",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&world_path);
+    common::stamped_content_copy(&content_path);
+    let open = || {
+        let repo = SqlitePowerRepository::open(&content_path).expect("open content db");
+        let world_repo: Box<dyn WorldRepository> =
+            Box::new(WorldDb::open(&world_path).expect("open world db"));
+        Session::open(
+            repo,
+            world_repo,
+            &world_path,
+            Some(7),
+            &[PathBuf::from(DEFAULT_CIVILIAN_EVENT_CATALOG)],
+        )
+    };
+
+    let mut session = open();
+    session.run_script(&["ambush brutal"]);
+    session.persist();
+    drop(session);
+
+    let mut session = open();
+    let lines = session.run_script(&["ambush"]);
+    assert!(lines.iter().any(|line| line == "Ambush setting: Brutal"));
+
+    let _ = std::fs::remove_file(&content_path);
+    let _ = std::fs::remove_file(&world_path);
+}
+
+#[test]
+fn a_failed_save_shows_up_in_the_session_output() {
+    let dir = std::env::temp_dir();
+    let content_path = dir.join(format!(
+        "collapsing_worlds_readonly_content_{}.db",
+        std::process::id()
+    ));
+    let world_path = dir.join(format!(
+        "collapsing_worlds_readonly_world_{}.db",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&world_path);
+    common::stamped_content_copy(&content_path);
+    {
+        let mut db = WorldDb::open(&world_path).expect("open world db");
+        db.load_or_init().expect("init world db");
+        db.ensure_world_seed(7).expect("record seed");
+    }
+    let repo = SqlitePowerRepository::open(&content_path).expect("open content db");
+    let world_repo: Box<dyn WorldRepository> =
+        Box::new(WorldDb::open_readonly(&world_path).expect("open read-only world db"));
+    let mut session = Session::open(
+        repo,
+        world_repo,
+        &world_path,
+        Some(7),
+        &[PathBuf::from(DEFAULT_CIVILIAN_EVENT_CATALOG)],
+    );
+    session.take_output();
+
+    session.run_script(&["tick 1"]);
+    session.persist();
+    let lines = session.take_output();
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.starts_with("Failed to persist world state")),
+        "{:?}",
+        lines
+    );
+
+    let _ = std::fs::remove_file(&content_path);
+    let _ = std::fs::remove_file(&world_path);
+}
@@ -212,6 +212,10 @@ def tooltip_rules(constraints: dict) -> str:
     if "radius_m" in constraints and constraints["radius_m"]: bits.append(f"Radius {constraints['radius_m']}m")
     if constraints.get("requires_los"): bits.append("Requires line of sight")
     if constraints.get("requires_contact"): bits.append("Requires contact")
+    if constraints.get("requires_persona"): bits.append(f"{constraints['requires_persona'].title()} persona only")
+    if constraints.get("allowed_time") in ("DAY_ONLY", "NIGHT_ONLY"): bits.append(constraints["allowed_time"].replace("_", " ").capitalize())
+    if constraints.get("allowed_alignments"): bits.append("Alignments " + "/".join(a.title() for a in constraints["allowed_alignments"]))
+    if constraints.get("alignment_affinity"): bits.append(f"Leans {constraints['alignment_affinity'].title()}")
     if "cooldown" in constraints: bits.append(f"Cooldown {constraints['cooldown']}")
     if "duration_turns" in constraints: bits.append(f"Duration {constraints['duration_turns']}")
     if "cost_per_tick" in constraints: bits.append(f"Upkeep {constraints['cost_per_tick']}")